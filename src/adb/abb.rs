@@ -0,0 +1,77 @@
+//! Android Binder Bridge (`abb:`)
+//!
+//! On Android 10+ adbd can call a system service's shell command directly,
+//! the way `cmd <service> ...` does, without starting `sh` and `cmd` first.
+//! Arguments travel separately, so nothing has to survive shell quoting.
+//! Replies use the shell v2 framing, with stderr and the exit status.
+
+/// Split a simple command line into words
+///
+/// Handles plain words and single- or double-quoted strings, which is what
+/// `shell_quote` produces. Anything a shell would interpret (pipes,
+/// redirections, variables, globs, escapes) gives `None`, and the command is
+/// left to the shell.
+pub fn split_words(command: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '$' | '`' | '\\' => return None,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '|' | '&' | ';' | '<' | '>' | '(' | ')' | '$' | '`' | '\\' | '*' | '?' | '[' | ']' | '#' | '~' | '{'
+            | '}' | '\n' => return None,
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Some(words)
+}
+
+/// Service and arguments for `pm ...` or `cmd <service> ...`, if abb can run it
+pub fn route(command: &str) -> Option<Vec<String>> {
+    let mut words = split_words(command)?;
+    match words.first().map(String::as_str) {
+        // pm has been a wrapper around `cmd package` since before abb existed
+        Some("pm") if words.len() > 1 => {
+            words[0] = "package".to_string();
+            Some(words)
+        }
+        Some("cmd") if words.len() > 1 => Some(words.split_off(1)),
+        _ => None,
+    }
+}
+
+/// The `abb:` request for a service and its arguments
+pub fn service(args: &[String]) -> String {
+    format!("abb:{}", args.join("\0"))
+}
@@ -0,0 +1,43 @@
+use super::abb::{route, service, split_words};
+
+fn strings(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn test_split_words() {
+    assert_eq!(split_words("pm list packages -3"), Some(strings(&["pm", "list", "packages", "-3"])));
+    assert_eq!(split_words("  pm   path  com.example "), Some(strings(&["pm", "path", "com.example"])));
+    assert_eq!(
+        split_words("cmd package grant 'com.example' \"android.permission.CAMERA\""),
+        Some(strings(&["cmd", "package", "grant", "com.example", "android.permission.CAMERA"]))
+    );
+    assert_eq!(split_words("echo 'a b'c ''"), Some(strings(&["echo", "a bc", ""])));
+}
+
+#[test]
+fn test_split_words_needs_shell() {
+    assert_eq!(split_words("pm list packages | grep example"), None);
+    assert_eq!(split_words("pm enable com.example 2>&1"), None);
+    assert_eq!(split_words("pm path $PKG"), None);
+    assert_eq!(split_words("cmd package list packages \"$x\""), None);
+    assert_eq!(split_words("pm install /data/local/tmp/*.apk"), None);
+    assert_eq!(split_words("echo 'unterminated"), None);
+    // shell_quote's escape for a single quote
+    assert_eq!(split_words("echo 'it'\\''s'"), None);
+}
+
+#[test]
+fn test_route() {
+    assert_eq!(route("pm list packages"), Some(strings(&["package", "list", "packages"])));
+    assert_eq!(route("cmd activity get-standby-bucket"), Some(strings(&["activity", "get-standby-bucket"])));
+    assert_eq!(route("pm"), None);
+    assert_eq!(route("cmd"), None);
+    assert_eq!(route("dumpsys battery"), None);
+    assert_eq!(route("pm list packages; reboot"), None);
+}
+
+#[test]
+fn test_service() {
+    assert_eq!(service(&strings(&["package", "path", "com.example"])), "abb:package\0path\0com.example");
+}
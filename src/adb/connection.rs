@@ -1,3 +1,4 @@
+use crate::core::profile::{self, Phase};
 use crate::core::types::DeviceId;
 use crate::error::{AimError, Result};
 use log::*;
@@ -51,6 +52,7 @@ impl AdbConnection {
     }
     
     fn establish_connection(host: &str, port: u16) -> Result<TcpStream> {
+        let _span = profile::span(Phase::Connect);
         let server_address = format!(
             "{}:{}",
             if host == "localhost" { "127.0.0.1" } else { host },
@@ -91,6 +93,7 @@ impl AdbConnection {
     
     /// Send a command to the ADB server
     pub fn send_command(&mut self, command: &str) -> Result<()> {
+        let _span = profile::span(Phase::Handshake);
         debug!("Sending command: {}", command);
         let request = format!("{:04x}{}", command.len(), command);
         debug!("Formatted request: {:?}", request);
@@ -99,6 +102,7 @@ impl AdbConnection {
     
     /// Read response from the ADB server
     pub fn read_response(&mut self) -> Result<String> {
+        let _span = profile::span(Phase::Execute);
         let mut buffer = [0; BUFFER_SIZE];
         let mut response = Vec::new();
         debug!("Waiting for response...");
@@ -131,6 +135,18 @@ impl AdbConnection {
         self.process_response(&response)
     }
     
+    /// Read until the server closes the stream
+    ///
+    /// Unlike `read_response`, this does not stop at the first short read, so
+    /// large shell outputs arrive complete.
+    pub fn read_to_end(&mut self) -> Result<String> {
+        let _span = profile::span(Phase::Execute);
+        let mut data = Vec::new();
+        self.stream.read_to_end(&mut data)?;
+        debug!("read_to_end: {} bytes", data.len());
+        Ok(String::from_utf8_lossy(&data).into_owned())
+    }
+    
     fn process_response(&self, data: &[u8]) -> Result<String> {
         debug!("Raw bytes length: {}", data.len());
         match std::str::from_utf8(data) {
@@ -152,6 +168,7 @@ impl AdbConnection {
     
     /// Read and verify OKAY response
     pub fn read_okay(&mut self) -> Result<()> {
+        let _span = profile::span(Phase::Handshake);
         let mut response = [0u8; 4];
         self.stream.read_exact(&mut response)?;
         debug!("Response in read_okay: {:?}", response);
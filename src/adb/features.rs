@@ -0,0 +1,113 @@
+//! Device feature negotiation
+//!
+//! adbd advertises what it supports (`shell_v2`, `ls_v2`, ...) and the server
+//! reports it through `host-serial:<serial>:features`. The list is fetched
+//! once per device and cached, so callers can pick the best protocol variant
+//! without a failed attempt first.
+
+use crate::adb::connection::AdbConnection;
+use crate::adb::server::AdbServer;
+use crate::core::types::DeviceId;
+use crate::error::Result;
+use lazy_static::lazy_static;
+use log::debug;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+
+/// Features aim knows how to use
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    /// Separate stdout/stderr, exit status and pseudo-terminals
+    ShellV2,
+    /// The `cmd` binary, which talks to system services directly
+    Cmd,
+    /// 64-bit sizes and full metadata from `LST2`/`STA2`
+    StatV2,
+    /// Directory listings with full metadata (`LIS2`)
+    LsV2,
+    /// Compressed sync transfers
+    SendRecvV2,
+    /// The Android binder bridge (`abb:`), a faster `cmd`
+    Abb,
+    /// `abb_exec:`, abb with raw stdin/stdout for streaming
+    AbbExec,
+}
+
+impl Feature {
+    pub fn name(self) -> &'static str {
+        match self {
+            Feature::ShellV2 => "shell_v2",
+            Feature::Cmd => "cmd",
+            Feature::StatV2 => "stat_v2",
+            Feature::LsV2 => "ls_v2",
+            Feature::SendRecvV2 => "sendrecv_v2",
+            Feature::Abb => "abb",
+            Feature::AbbExec => "abb_exec",
+        }
+    }
+}
+
+/// What a device supports
+///
+/// A set that could not be fetched is unknown and allows everything, so
+/// callers try the modern variant and fall back on a protocol error as they
+/// did before negotiation existed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Features {
+    names: Option<BTreeSet<String>>,
+}
+
+impl Features {
+    /// Parse the comma-separated list adbd advertises
+    pub fn parse(list: &str) -> Self {
+        Self {
+            names: Some(list.split(',').map(str::trim).filter(|f| !f.is_empty()).map(String::from).collect()),
+        }
+    }
+
+    pub fn unknown() -> Self {
+        Self { names: None }
+    }
+
+    /// Whether `feature` may be used: advertised, or the set is unknown
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.names.as_ref().is_none_or(|names| names.contains(feature.name()))
+    }
+
+    /// Advertised feature names, sorted
+    pub fn names(&self) -> Vec<String> {
+        self.names.iter().flatten().cloned().collect()
+    }
+}
+
+lazy_static! {
+    static ref CACHE: Mutex<HashMap<(String, u16, String), Features>> = Mutex::new(HashMap::new());
+}
+
+/// Features of `device`, fetched on first use
+pub async fn for_device(host: &str, port: u16, device: &DeviceId) -> Features {
+    let key = (host.to_string(), port, device.to_string());
+    if let Some(features) = CACHE.lock().unwrap().get(&key) {
+        return features.clone();
+    }
+    match query(host, port, device) {
+        Ok(features) => {
+            debug!("Features of {}: {}", device, features.names().join(","));
+            CACHE.lock().unwrap().insert(key, features.clone());
+            features
+        }
+        Err(e) => {
+            // Not cached: the server may just not be up yet
+            debug!("Could not read the features of {}: {}", device, e);
+            Features::unknown()
+        }
+    }
+}
+
+fn query(host: &str, port: u16, device: &DeviceId) -> Result<Features> {
+    let mut conn = AdbConnection::new(host, port)?;
+    conn.send_command(&format!("host-serial:{}:features", device))?;
+    conn.read_okay()?;
+    Ok(Features::parse(&AdbServer::read_string(&mut conn)?))
+}
@@ -0,0 +1,33 @@
+use super::features::{Feature, Features};
+
+#[test]
+fn test_parse() {
+    let features = Features::parse("shell_v2,cmd,stat_v2,ls_v2,fixed_push_mkdir,apex,abb,fixed_push_symlink_timestamp,abb_exec");
+    assert!(features.supports(Feature::ShellV2));
+    assert!(features.supports(Feature::Cmd));
+    assert!(features.supports(Feature::AbbExec));
+    assert!(!features.supports(Feature::SendRecvV2));
+    assert_eq!(features.names().len(), 9);
+}
+
+#[test]
+fn test_old_device() {
+    // Android 6 advertises nothing aim uses
+    let features = Features::parse("");
+    assert!(!features.supports(Feature::ShellV2));
+    assert!(!features.supports(Feature::StatV2));
+    assert!(features.names().is_empty());
+}
+
+#[test]
+fn test_unknown_allows_everything() {
+    let features = Features::unknown();
+    assert!(features.supports(Feature::ShellV2));
+    assert!(features.supports(Feature::LsV2));
+    assert_ne!(features, Features::parse(""));
+}
+
+#[test]
+fn test_names_are_trimmed_and_sorted() {
+    assert_eq!(Features::parse(" shell_v2 , cmd,,").names(), vec!["cmd", "shell_v2"]);
+}
@@ -1,6 +1,7 @@
 use crate::core::types::{DeviceId, TransferDirection, TransferProgress as Progress};
 use crate::error::{AimError, Result};
 use crate::adb::connection::AdbConnection;
+use crate::adb::features::{self, Feature, Features};
 use crate::adb::protocol::{AdbLstatResponse, sync};
 use crate::progress::{ProgressReporter, ProgressFactory};
 use log::*;
@@ -20,12 +21,28 @@ const SYNC_SEND: &[u8] = sync::SEND;
 const SYNC_RECV: &[u8] = sync::RECV;
 #[allow(dead_code)]
 const SYNC_STAT: &[u8] = sync::STAT;
+#[allow(dead_code)]
+const SYNC_LIS2: &[u8] = sync::LIS2;
+#[allow(dead_code)]
+const SYNC_LST2: &[u8] = sync::LST2;
+#[allow(dead_code)]
+const SYNC_LIST: &[u8] = sync::LIST;
+
+/// An entry returned by a sync directory listing
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub struct RemoteDirEntry {
+    pub name: String,
+    pub stat: AdbLstatResponse,
+}
 
 /// File transfer operations
 #[allow(dead_code)]
 pub struct FileTransfer {
     conn: AdbConnection,
     progress_reporter: Option<Box<dyn ProgressReporter>>,
+    /// Decides between the v1 and v2 stat and listing requests
+    features: Features,
 }
 
 #[allow(dead_code)]
@@ -33,6 +50,10 @@ impl FileTransfer {
     /// Create a new file transfer instance
     pub async fn new(host: &str, port: u16, device_id: Option<&DeviceId>) -> Result<Self> {
         let mut conn = AdbConnection::connect(host, port).await?;
+        let features = match device_id {
+            Some(id) => features::for_device(host, port, id).await,
+            None => Features::unknown(),
+        };
         
         // Select device if specified
         if let Some(id) = device_id {
@@ -46,6 +67,7 @@ impl FileTransfer {
         Ok(Self {
             conn,
             progress_reporter: None,
+            features,
         })
     }
     
@@ -189,8 +211,17 @@ impl FileTransfer {
     }
     
     /// Get file statistics
+    ///
+    /// Uses `LST2` where the device supports it, for 64-bit sizes and full
+    /// metadata; older devices answer the v1 `STAT` with mode, size and mtime.
     pub async fn stat(&mut self, remote_path: &str) -> Result<AdbLstatResponse> {
-        self.send_sync_command(SYNC_STAT, remote_path)?;
+        if !self.features.supports(Feature::StatV2) {
+            self.send_sync_command(SYNC_STAT, remote_path)?;
+            let mut response = [0u8; 16];
+            self.conn.read_exact(&mut response)?;
+            return AdbLstatResponse::from_v1_bytes(&response);
+        }
+        self.send_sync_command(SYNC_LST2, remote_path)?;
         
         let mut response = [0u8; 72];
         self.conn.read_exact(&mut response)?;
@@ -198,6 +229,141 @@ impl FileTransfer {
         AdbLstatResponse::from_bytes(&response)
     }
     
+    /// List a directory with the LIS2 sync request (LIST on older devices)
+    ///
+    /// Works for any directory the shell user can read, without needing `du`
+    /// or root. The `.` and `..` entries are skipped.
+    pub async fn list_dir(&mut self, remote_path: &str) -> Result<Vec<RemoteDirEntry>> {
+        if !self.features.supports(Feature::LsV2) {
+            return self.list_dir_v1(remote_path);
+        }
+        self.send_sync_command(SYNC_LIS2, remote_path)?;
+        
+        let mut entries = Vec::new();
+        loop {
+            // Each dent is a 72-byte stat block followed by the name length
+            let mut block = [0u8; 72];
+            self.conn.read_exact(&mut block)?;
+            let mut len_bytes = [0u8; 4];
+            self.conn.read_exact(&mut len_bytes)?;
+            let name_len = u32::from_le_bytes(len_bytes) as usize;
+            
+            match &block[0..4] {
+                b"DONE" => break,
+                b"DNT2" => {}
+                other => {
+                    return Err(AimError::FileTransfer(format!(
+                        "Unexpected listing response: {:?}",
+                        String::from_utf8_lossy(other)
+                    )))
+                }
+            }
+            
+            let mut name = vec![0u8; name_len];
+            self.conn.read_exact(&mut name)?;
+            let name = String::from_utf8_lossy(&name).to_string();
+            if name == "." || name == ".." {
+                continue;
+            }
+            
+            entries.push(RemoteDirEntry {
+                name,
+                stat: AdbLstatResponse::from_bytes(&block)?,
+            });
+        }
+        
+        Ok(entries)
+    }
+    
+    /// `LIST`: each dent is a 20-byte header (id, mode, size, mtime, name length)
+    fn list_dir_v1(&mut self, remote_path: &str) -> Result<Vec<RemoteDirEntry>> {
+        self.send_sync_command(SYNC_LIST, remote_path)?;
+        
+        let mut entries = Vec::new();
+        loop {
+            let mut header = [0u8; 20];
+            self.conn.read_exact(&mut header)?;
+            let name_len = u32::from_le_bytes(header[16..20].try_into().unwrap()) as usize;
+            
+            match &header[0..4] {
+                b"DONE" => break,
+                b"DENT" => {}
+                other => {
+                    return Err(AimError::FileTransfer(format!(
+                        "Unexpected listing response: {:?}",
+                        String::from_utf8_lossy(other)
+                    )))
+                }
+            }
+            
+            let mut name = vec![0u8; name_len];
+            self.conn.read_exact(&mut name)?;
+            let name = String::from_utf8_lossy(&name).to_string();
+            if name == "." || name == ".." {
+                continue;
+            }
+            
+            entries.push(RemoteDirEntry {
+                name,
+                stat: AdbLstatResponse::from_v1_bytes(&header[..16])?,
+            });
+        }
+        
+        Ok(entries)
+    }
+    
+    /// Every regular file under `root`, with paths relative to it
+    ///
+    /// Directories that cannot be listed are skipped. When `root` is itself a
+    /// file it is returned with an empty relative path, as long as its parent
+    /// directory is readable.
+    pub async fn list_tree(&mut self, root: &str) -> Result<Vec<(String, AdbLstatResponse)>> {
+        let root = root.trim_end_matches('/');
+        let mut files = Vec::new();
+        let mut listed_root = false;
+        let mut pending = vec![String::new()];
+        while let Some(rel_dir) = pending.pop() {
+            let dir = if rel_dir.is_empty() {
+                root.to_string()
+            } else {
+                format!("{}/{}", root, rel_dir)
+            };
+            let entries = match self.list_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(e) => {
+                    debug!("Skipping {}: {}", dir, e);
+                    continue;
+                }
+            };
+            listed_root |= rel_dir.is_empty() && !entries.is_empty();
+            for entry in entries {
+                let rel = if rel_dir.is_empty() {
+                    entry.name.clone()
+                } else {
+                    format!("{}/{}", rel_dir, entry.name)
+                };
+                if entry.stat.is_dir() {
+                    pending.push(rel);
+                } else if entry.stat.is_file() {
+                    files.push((rel, entry.stat));
+                }
+            }
+        }
+
+        if !listed_root {
+            if let Some((parent, name)) = root.rsplit_once('/') {
+                let parent = if parent.is_empty() { "/" } else { parent };
+                if let Ok(entries) = self.list_dir(parent).await {
+                    if let Some(entry) = entries.into_iter().find(|e| e.name == name && e.stat.is_file()) {
+                        files.push((String::new(), entry.stat));
+                    }
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
     /// Send a sync command
     fn send_sync_command(&mut self, command: &[u8], path: &str) -> Result<()> {
         debug!("Sending sync command: {:?} {}", command, path);
@@ -0,0 +1,98 @@
+use crate::adb::connection::AdbConnection;
+use crate::adb::server::AdbServer;
+use crate::core::types::DeviceId;
+use crate::error::{AimError, Result};
+use serde::Serialize;
+
+/// Forwarding: host sockets that connect to a socket on the device
+pub struct Forward;
+
+/// One forward as the server lists it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ForwardEntry {
+    pub serial: String,
+    pub local: String,
+    pub remote: String,
+}
+
+impl Forward {
+    /// Make connections to `local` on the host reach `remote` on the device
+    ///
+    /// `local` may be `tcp:0` to let the server pick a free port, which is
+    /// then returned. With `no_rebind` the server refuses to replace an
+    /// existing forward of `local`.
+    pub async fn add(
+        host: &str,
+        port: u16,
+        device_id: &DeviceId,
+        local: &str,
+        remote: &str,
+        no_rebind: bool,
+    ) -> Result<Option<u16>> {
+        let mode = if no_rebind { "norebind:" } else { "" };
+        let mut conn = Self::open(host, port, device_id, &format!("forward:{}{};{}", mode, local, remote))?;
+        conn.read_okay()?;
+
+        if local != "tcp:0" {
+            return Ok(None);
+        }
+        let allocated = AdbServer::read_string(&mut conn)?
+            .trim()
+            .parse()
+            .map_err(|e| AimError::ParseError(format!("Invalid forwarded port: {}", e)))?;
+        Ok(Some(allocated))
+    }
+
+    /// Remove the forward of `local`
+    pub async fn remove(host: &str, port: u16, device_id: &DeviceId, local: &str) -> Result<()> {
+        let mut conn = Self::open(host, port, device_id, &format!("killforward:{}", local))?;
+        conn.read_okay()
+    }
+
+    /// Remove every forward of one device, returning the ones removed
+    ///
+    /// `killforward-all` would also drop the forwards of other devices, so
+    /// they are removed one by one.
+    pub async fn remove_all(host: &str, port: u16, device_id: &DeviceId) -> Result<Vec<ForwardEntry>> {
+        let forwards = Self::list(host, port, Some(device_id)).await?;
+        for forward in &forwards {
+            Self::remove(host, port, device_id, &forward.local).await?;
+        }
+        Ok(forwards)
+    }
+
+    /// Forwards known to the server, optionally only those of one device
+    pub async fn list(host: &str, port: u16, device_id: Option<&DeviceId>) -> Result<Vec<ForwardEntry>> {
+        let mut conn = AdbConnection::connect(host, port).await?;
+        conn.send_command("host:list-forward")?;
+        conn.read_okay()?;
+        let forwards = parse_forward_list(&AdbServer::read_string(&mut conn)?);
+        Ok(match device_id {
+            Some(id) => forwards.into_iter().filter(|f| f.serial == id.as_str()).collect(),
+            None => forwards,
+        })
+    }
+
+    /// Send a `host-serial:` service; the OKAY read here acknowledges the transport
+    fn open(host: &str, port: u16, device_id: &DeviceId, service: &str) -> Result<AdbConnection> {
+        let mut conn = AdbConnection::new(host, port)?;
+        conn.send_command(&format!("host-serial:{}:{}", device_id.as_str(), service))?;
+        conn.read_okay()?;
+        Ok(conn)
+    }
+}
+
+/// Parse `host:list-forward` output ("<serial> <local> <remote>" per line)
+pub fn parse_forward_list(output: &str) -> Vec<ForwardEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(ForwardEntry {
+                serial: fields.next()?.to_string(),
+                local: fields.next()?.to_string(),
+                remote: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
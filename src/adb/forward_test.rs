@@ -0,0 +1,24 @@
+use super::forward::{parse_forward_list, ForwardEntry};
+
+#[test]
+fn test_parse_forward_list() {
+    let output = "emulator-5554 tcp:8081 tcp:8081\n\
+                  R58M123ABC tcp:9222 localabstract:chrome_devtools_remote\n\
+                  \n";
+    assert_eq!(
+        parse_forward_list(output),
+        vec![
+            ForwardEntry {
+                serial: "emulator-5554".to_string(),
+                local: "tcp:8081".to_string(),
+                remote: "tcp:8081".to_string(),
+            },
+            ForwardEntry {
+                serial: "R58M123ABC".to_string(),
+                local: "tcp:9222".to_string(),
+                remote: "localabstract:chrome_devtools_remote".to_string(),
+            },
+        ]
+    );
+    assert!(parse_forward_list("").is_empty());
+}
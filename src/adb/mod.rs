@@ -1,8 +1,32 @@
+pub mod abb;
 pub mod connection;
+pub mod features;
 pub mod protocol;
 pub mod file_transfer;
 pub mod shell;
 pub mod server;
+pub mod reverse;
+pub mod forward;
+pub mod su;
+pub mod ssh;
 
 
 // Re-export commonly used types
+
+#[cfg(test)]
+mod abb_test;
+
+#[cfg(test)]
+mod features_test;
+
+#[cfg(test)]
+mod forward_test;
+
+#[cfg(test)]
+mod shell_test;
+
+#[cfg(test)]
+mod su_test;
+
+#[cfg(test)]
+mod ssh_test;
@@ -194,6 +194,47 @@ impl AdbLstatResponse {
         })
     }
     
+    /// Parse a v1 `STAT` reply or `DENT` header: id, mode, size and mtime
+    ///
+    /// v1 carries nothing else, so the other fields are zero. A missing file
+    /// has mode 0, which is neither a file nor a directory.
+    pub fn from_v1_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != 16 {
+            return Err(AimError::AdbProtocol("Invalid stat response length".into()));
+        }
+        let magic = &bytes[0..4];
+        if magic != b"STAT" && magic != b"DENT" {
+            return Err(AimError::AdbProtocol(
+                format!("Invalid magic number: {:?}", String::from_utf8_lossy(magic))
+            ));
+        }
+        let word = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+        let timestamp = |seconds| FileTimestamp { seconds, nanoseconds: 0 };
+        Ok(Self {
+            magic: magic.try_into().unwrap(),
+            metadata: FileMetadata {
+                unknown1: 0,
+                dev_major: 0,
+                dev_minor: 0,
+                unknown2: 0,
+                inode: 0,
+                unknown3: 0,
+                mode: word(4) as u16,
+                unknown4: 0,
+                nlink: 0,
+                uid: 0,
+                gid: 0,
+                size: word(8),
+                unknown5: 0,
+            },
+            timestamps: FileTimestamps {
+                atime: timestamp(0),
+                mtime: timestamp(word(12)),
+                ctime: timestamp(0),
+            },
+        })
+    }
+    
     // Accessor methods
     pub fn magic(&self) -> &[u8; 4] {
         &self.magic
@@ -211,6 +252,11 @@ impl AdbLstatResponse {
         self.metadata.size
     }
     
+    /// Full 64-bit size (the v2 stat layout stores the high word after `size`)
+    pub fn size64(&self) -> u64 {
+        ((self.metadata.unknown5 as u64) << 32) | self.metadata.size as u64
+    }
+    
     pub fn uid(&self) -> u32 {
         self.metadata.uid
     }
@@ -263,5 +309,8 @@ pub mod sync {
     pub const RECV: &[u8] = b"RECV";
     pub const LIST: &[u8] = b"LIST";
     pub const STAT: &[u8] = b"STAT";
+    pub const LST2: &[u8] = b"LST2";
+    pub const LIS2: &[u8] = b"LIS2";
+    pub const DNT2: &[u8] = b"DNT2";
     pub const QUIT: &[u8] = b"QUIT";
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+use crate::adb::connection::AdbConnection;
+use crate::core::types::DeviceId;
+use crate::error::{AimError, Result};
+
+/// Reverse forwarding: sockets on the device that connect back to the host
+pub struct Reverse;
+
+impl Reverse {
+    /// Make connections to `remote` on the device reach `local` on the host
+    pub async fn add(host: &str, port: u16, device_id: &DeviceId, remote: &str, local: &str) -> Result<()> {
+        let mut conn = Self::open(host, port, device_id, &format!("reverse:forward:{};{}", remote, local)).await?;
+        conn.read_okay()
+    }
+
+    /// Remove a reverse set up with [`Reverse::add`]
+    pub async fn remove(host: &str, port: u16, device_id: &DeviceId, remote: &str) -> Result<()> {
+        let mut conn = Self::open(host, port, device_id, &format!("reverse:killforward:{}", remote)).await?;
+        conn.read_okay()
+    }
+
+    /// Reverses active on the device as (remote, local) pairs
+    pub async fn list(host: &str, port: u16, device_id: &DeviceId) -> Result<Vec<(String, String)>> {
+        let mut conn = Self::open(host, port, device_id, "reverse:list-forward").await?;
+        let mut len_bytes = [0u8; 4];
+        conn.read_exact(&mut len_bytes)?;
+        let len = usize::from_str_radix(std::str::from_utf8(&len_bytes)?, 16)
+            .map_err(|e| AimError::ParseError(format!("Invalid length prefix: {}", e)))?;
+        let mut data = vec![0u8; len];
+        conn.read_exact(&mut data)?;
+        Ok(parse_reverse_list(&String::from_utf8_lossy(&data)))
+    }
+
+    /// Open a reverse service; the OKAY read here only means adbd accepted it
+    async fn open(host: &str, port: u16, device_id: &DeviceId, service: &str) -> Result<AdbConnection> {
+        let mut conn = AdbConnection::connect(host, port).await?;
+        conn.select_device(device_id).await?;
+        conn.send_command(service)?;
+        conn.read_okay()?;
+        Ok(conn)
+    }
+}
+
+/// Parse `reverse:list-forward` output ("<transport> <remote> <local>" per line)
+pub fn parse_reverse_list(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let _transport = fields.next()?;
+            Some((fields.next()?.to_string(), fields.next()?.to_string()))
+        })
+        .collect()
+}
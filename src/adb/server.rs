@@ -6,6 +6,8 @@ use tokio::time::sleep;
 
 const SERVER_START_DELAY: Duration = Duration::from_secs(1);
 const SERVER_CHECK_TIMEOUT: Duration = Duration::from_millis(500);
+/// Pairing and connecting wait on the device, well past the usual read timeout
+const NETWORK_REPLY_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// ADB server management
 pub struct AdbServer;
@@ -16,8 +18,9 @@ impl AdbServer {
     pub async fn start(port: u16) -> Result<()> {
         info!("Starting ADB server on port {}", port);
         
-        let adb_command = std::env::var("ADB_PATH").unwrap_or_else(|_| "adb".to_string());
-        
+        let adb_command = crate::library::adb::find_adb_binary()
+            .ok_or_else(|| AimError::Server(crate::library::adb::missing_adb_message(&port.to_string())))?;
+
         let output = Command::new(&adb_command)
             .args(&["-P", &port.to_string(), "start-server"])
             .output()
@@ -36,22 +39,23 @@ impl AdbServer {
         Ok(())
     }
     
-    /// Stop the ADB server
+    /// Stop the ADB server (`host:kill`, no adb binary needed)
     pub async fn stop(port: u16) -> Result<()> {
+        use crate::adb::connection::AdbConnection;
+
         info!("Stopping ADB server on port {}", port);
-        
-        let adb_command = std::env::var("ADB_PATH").unwrap_or_else(|_| "adb".to_string());
-        
-        let output = Command::new(&adb_command)
-            .args(&["-P", &port.to_string(), "kill-server"])
-            .output()
-            .map_err(|e| AimError::Server(format!("Failed to execute adb command: {}", e)))?;
-        
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(AimError::Server(format!("Failed to stop ADB server: {}", stderr)));
-        }
-        
+
+        let (host, _) = crate::commands::runner::get_adb_connection_params();
+        let mut conn = match AdbConnection::new(host, port) {
+            Ok(conn) => conn,
+            Err(_) => {
+                debug!("Server is already stopped");
+                return Ok(());
+            }
+        };
+        conn.send_command("host:kill")?;
+        conn.read_okay()?;
+
         info!("ADB server stopped successfully");
         Ok(())
     }
@@ -70,6 +74,7 @@ impl AdbServer {
         let address = format!("{}:{}", host, port);
         let addr_for_log = address.clone();
         
+        let _span = crate::core::profile::span(crate::core::profile::Phase::Connect);
         match tokio::time::timeout(
             SERVER_CHECK_TIMEOUT,
             tokio::task::spawn_blocking(move || TcpStream::connect(address))
@@ -92,13 +97,27 @@ impl AdbServer {
         let mut conn = AdbConnection::new(host, port)?;
         conn.send_command("host:version")?;
         conn.read_okay()?;
-        
-        let mut version_bytes = [0u8; 4];
-        conn.read_exact(&mut version_bytes)?;
-        let version = u32::from_be_bytes(version_bytes);
-        
+
+        // The reply is the protocol version as a length-prefixed hex string
+        let version = u32::from_str_radix(Self::read_string(&mut conn)?.trim(), 16)
+            .map_err(|e| AimError::ParseError(format!("Invalid server version: {}", e)))?;
+
         Ok(format!("{:04x}", version))
     }
+
+    /// Features the server itself supports (`host:host-features`)
+    pub async fn host_features(host: &str, port: u16) -> Result<Vec<String>> {
+        use crate::adb::connection::AdbConnection;
+
+        let mut conn = AdbConnection::new(host, port)?;
+        conn.send_command("host:host-features")?;
+        conn.read_okay()?;
+        Ok(Self::read_string(&mut conn)?
+            .split(',')
+            .map(|f| f.trim().to_string())
+            .filter(|f| !f.is_empty())
+            .collect())
+    }
     
     /// Get list of devices from server
     pub async fn list_devices(host: &str, port: u16) -> Result<String> {
@@ -121,6 +140,56 @@ impl AdbServer {
         Ok(String::from_utf8_lossy(&devices_data).to_string())
     }
     
+    /// Connect to a device over the network (`host:connect`)
+    ///
+    /// The server answers OKAY even when connecting fails, so the returned
+    /// message ("connected to ...", "failed to connect to ...") must be checked.
+    pub async fn connect(host: &str, port: u16, address: &str) -> Result<String> {
+        use crate::adb::connection::AdbConnection;
+
+        let mut conn = AdbConnection::new(host, port)?;
+        conn.stream().set_read_timeout(Some(NETWORK_REPLY_TIMEOUT))?;
+        conn.send_command(&format!("host:connect:{}", address))?;
+        conn.read_okay()?;
+        Self::read_string(&mut conn)
+    }
+
+    /// Pair with a device for wireless debugging (`host:pair`)
+    ///
+    /// Like `connect`, failures come back as a message rather than a FAIL.
+    pub async fn pair(host: &str, port: u16, code: &str, address: &str) -> Result<String> {
+        use crate::adb::connection::AdbConnection;
+
+        let mut conn = AdbConnection::new(host, port)?;
+        conn.stream().set_read_timeout(Some(NETWORK_REPLY_TIMEOUT))?;
+        conn.send_command(&format!("host:pair:{}:{}", code, address))?;
+        conn.read_okay()?;
+        Self::read_string(&mut conn)
+    }
+
+    /// Services the server currently sees over mDNS (`host:mdns:services`)
+    pub async fn mdns_services(host: &str, port: u16) -> Result<String> {
+        use crate::adb::connection::AdbConnection;
+
+        let mut conn = AdbConnection::new(host, port)?;
+        conn.send_command("host:mdns:services")?;
+        conn.read_okay()?;
+        Self::read_string(&mut conn)
+    }
+
+    /// Read a length-prefixed string from a host service reply
+    ///
+    /// Also reads each device list sent over a `track_devices` connection.
+    pub fn read_string(conn: &mut crate::adb::connection::AdbConnection) -> Result<String> {
+        let mut len_bytes = [0u8; 4];
+        conn.read_exact(&mut len_bytes)?;
+        let len = usize::from_str_radix(std::str::from_utf8(&len_bytes)?, 16)
+            .map_err(|e| AimError::ParseError(format!("Invalid length prefix: {}", e)))?;
+        let mut data = vec![0u8; len];
+        conn.read_exact(&mut data)?;
+        Ok(String::from_utf8_lossy(&data).into_owned())
+    }
+
     /// Track devices (returns a stream of device changes)
     pub async fn track_devices(host: &str, port: u16) -> Result<crate::adb::connection::AdbConnection> {
         use crate::adb::connection::AdbConnection;
@@ -1,6 +1,12 @@
+use crate::core::profile::{self, Phase};
 use crate::core::types::DeviceId;
 use crate::error::{AimError, Result};
 use crate::adb::connection::AdbConnection;
+use crate::adb::abb;
+use crate::adb::features::{self, Feature, Features};
+use log::debug;
+use std::io::{Read, Write};
+use std::time::Duration;
 use tokio::io::AsyncReadExt;
 use tokio::net::TcpStream;
 
@@ -27,31 +33,175 @@ impl ShellCommand {
         self
     }
     
+    /// Features of the target device
+    ///
+    /// Without a device, or when its features can't be read, everything is
+    /// assumed and a protocol error falls back to the older variant.
+    async fn features(&self, host: &str, port: u16) -> Features {
+        match &self.device_id {
+            Some(device_id) => features::for_device(host, port, device_id).await,
+            None => Features::unknown(),
+        }
+    }
+    
+    async fn has_shell_v2(&self, host: &str, port: u16) -> bool {
+        self.features(host, port).await.supports(Feature::ShellV2)
+    }
+    
     /// Execute the command and return output
+    ///
+    /// Uses the shell v2 protocol, which keeps stdout and stderr apart and
+    /// reports the exit status. Devices without it get the legacy shell,
+    /// where everything arrives as stdout and the exit status is always 0.
     pub async fn execute(&self, host: &str, port: u16) -> Result<ShellOutput> {
+        let started = std::time::Instant::now();
+        let features = self.features(host, port).await;
+        let output = match self.execute_abb(host, port, &features).await? {
+            Some(output) => output,
+            None if !features.supports(Feature::ShellV2) => self.execute_legacy(host, port).await?,
+            None => match self.execute_v2(host, port).await {
+                Err(AimError::AdbProtocol(msg)) => {
+                    debug!("shell v2 unavailable ({}), using the legacy shell", msg);
+                    self.execute_legacy(host, port).await?
+                }
+                other => other?,
+            },
+        };
+        let device = self.device_id.as_ref().map(|d| d.to_string()).unwrap_or_default();
+        debug!(
+            device = device.as_str(),
+            duration_ms = started.elapsed().as_millis() as u64,
+            bytes = output.stdout.len(),
+            exit_code = output.exit_code;
+            "shell: {}", self.command
+        );
+        Ok(output)
+    }
+    
+    /// Run a plain `pm` or `cmd` command through abb, skipping the shell
+    ///
+    /// `None` when the command needs a shell, the device has no abb, or abb
+    /// turned the request down; the caller then uses the shell. Once abb has
+    /// accepted the command, errors are returned rather than running it twice.
+    async fn execute_abb(&self, host: &str, port: u16, features: &Features) -> Result<Option<ShellOutput>> {
+        let (Some(device_id), Some(args)) = (&self.device_id, abb::route(&self.command)) else {
+            return Ok(None);
+        };
+        if !features.supports(Feature::Abb) {
+            return Ok(None);
+        }
         let mut conn = AdbConnection::connect(host, port).await?;
+        conn.select_device(device_id).await?;
+        conn.send_command(&abb::service(&args))?;
+        if let Err(e) = conn.read_okay() {
+            debug!("abb unavailable ({}), using the shell", e);
+            return Ok(None);
+        }
+        conn.stream().set_read_timeout(None)?;
+        read_v2_output(conn.stream()).map(Some)
+    }
+    
+    async fn execute_v2(&self, host: &str, port: u16) -> Result<ShellOutput> {
+        let mut conn = AdbConnection::connect(host, port).await?;
+        if let Some(device_id) = &self.device_id {
+            conn.select_device(device_id).await?;
+        }
+        conn.send_command(&format!("shell,v2,raw:{}", self.command))?;
+        conn.read_okay()?;
         
-        // Select device if specified
+        // The exit packet ends the output, however long the command runs;
+        // callers that need a bound use execute_cancellable
+        conn.stream().set_read_timeout(None)?;
+        read_v2_output(conn.stream())
+    }
+    
+    /// Execute like [`execute`](Self::execute), but stop the remote command
+    /// when `timeout` expires or Ctrl-C is pressed
+    ///
+    /// Closing the connection alone can leave the command running on the
+    /// device, so it records its pid and is killed through a second shell.
+    /// Devices without shell v2 run the command without cancellation.
+    pub async fn execute_cancellable(&self, host: &str, port: u16, timeout: Option<Duration>) -> Result<ShellOutput> {
+        if !self.has_shell_v2(host, port).await {
+            return self.execute_legacy(host, port).await;
+        }
+        let pid_file = format!("/data/local/tmp/.aim-{:016x}.pid", rand::random::<u64>());
+        let mut conn = AdbConnection::connect(host, port).await?;
         if let Some(device_id) = &self.device_id {
             conn.select_device(device_id).await?;
         }
+        conn.send_command(&format!("shell,v2,raw:{}", tracked_command(&self.command, &pid_file)))?;
+        match conn.read_okay() {
+            Err(AimError::AdbProtocol(msg)) => {
+                debug!("shell v2 unavailable ({}), running without cancellation", msg);
+                return self.execute_legacy(host, port).await;
+            }
+            other => other?,
+        }
         
-        // Send shell command
-        let shell_cmd = format!("shell:{}", self.command);
-        conn.send_command(&shell_cmd)?;
+        let stream = conn.stream().try_clone()?;
+        stream.set_read_timeout(None)?;
+        let mut reader = stream.try_clone()?;
+        let output = tokio::task::spawn_blocking(move || read_v2_output(&mut reader));
+        let expired = async {
+            match timeout {
+                Some(timeout) => tokio::time::sleep(timeout).await,
+                None => std::future::pending().await,
+            }
+        };
+        
+        let error = tokio::select! {
+            result = output => return result.map_err(|e| AimError::Shell(format!("Shell reader failed: {}", e)))?,
+            _ = expired => AimError::CommandTimedOut(crate::utils::format_duration(timeout.unwrap_or_default())),
+            _ = tokio::signal::ctrl_c() => AimError::Interrupted,
+        };
+        let _ = stream.shutdown(std::net::Shutdown::Both);
+        let mut kill = ShellCommand::new(kill_command(&pid_file));
+        kill.device_id = self.device_id.clone();
+        if let Err(e) = kill.execute(host, port).await {
+            debug!("Could not stop the remote command: {}", e);
+        }
+        Err(error)
+    }
+    
+    async fn execute_legacy(&self, host: &str, port: u16) -> Result<ShellOutput> {
+        let mut conn = AdbConnection::connect(host, port).await?;
+        if let Some(device_id) = &self.device_id {
+            conn.select_device(device_id).await?;
+        }
+        conn.send_command(&format!("shell:{}", self.command))?;
         conn.read_okay()?;
         
-        // Read response
         let response = conn.read_response()?;
-        let output = Self::clean_response(&response);
-        
         Ok(ShellOutput {
-            stdout: output,
+            stdout: Self::clean_response(&response),
             stderr: String::new(),
             exit_code: 0,
         })
     }
     
+    /// Start the command on a pseudo-terminal for interactive use
+    ///
+    /// An empty command opens a login shell. The caller feeds input and
+    /// reads output through the returned stream.
+    pub async fn open_pty(&self, host: &str, port: u16, term: &str) -> Result<PtyShell> {
+        if !self.has_shell_v2(host, port).await {
+            return Err(AimError::AdbProtocol("the device doesn't support shell v2".into()));
+        }
+        let mut conn = AdbConnection::connect(host, port).await?;
+        if let Some(device_id) = &self.device_id {
+            conn.select_device(device_id).await?;
+        }
+        conn.send_command(&format!("shell,v2,TERM={},pty:{}", term, self.command))?;
+        conn.read_okay()?;
+        
+        let stream = conn.stream().try_clone()?;
+        // Output arrives whenever the user or the program gets to it
+        stream.set_read_timeout(None)?;
+        stream.set_write_timeout(None)?;
+        Ok(PtyShell { stream })
+    }
+    
     /// Execute command asynchronously with streaming output
     pub async fn execute_streaming<F>(&self, host: &str, port: u16, mut callback: F) -> Result<()>
     where
@@ -95,7 +245,7 @@ impl ShellCommand {
     }
     
     /// Clean shell command response
-    fn clean_response(response: &str) -> String {
+    pub(crate) fn clean_response(response: &str) -> String {
         // Remove null bytes and trim
         response
             .replace('\0', "")
@@ -104,6 +254,242 @@ impl ShellCommand {
     }
 }
 
+/// `command` wrapped so its pid lands in `pid_file` while it runs
+///
+/// The subshell keeps `exit` in the command from skipping the cleanup.
+pub fn tracked_command(command: &str, pid_file: &str) -> String {
+    format!("echo $$ > {f}; ( {c} ); s=$?; rm -f {f}; exit $s", f = pid_file, c = command)
+}
+
+/// Stop a command started with [`tracked_command`], with everything it started
+pub fn kill_command(pid_file: &str) -> String {
+    format!(
+        "p=$(cat {f} 2>/dev/null) && {{ kill -TERM -$p; pkill -TERM -P $p; kill -TERM $p; }} 2>/dev/null; rm -f {f}",
+        f = pid_file
+    )
+}
+
+/// Packet ids of the shell v2 protocol (adb's `shell_protocol.h`)
+///
+/// Each packet is the id, a little-endian `u32` length and the payload.
+pub mod packet {
+    pub const STDIN: u8 = 0;
+    pub const STDOUT: u8 = 1;
+    pub const STDERR: u8 = 2;
+    /// Payload is the exit status, one byte
+    pub const EXIT: u8 = 3;
+    pub const CLOSE_STDIN: u8 = 4;
+    pub const WINDOW_SIZE_CHANGE: u8 = 5;
+}
+
+/// Frame `payload` as a shell v2 packet
+pub fn encode_packet(id: u8, payload: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(5 + payload.len());
+    data.push(id);
+    data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    data.extend_from_slice(payload);
+    data
+}
+
+/// Read the next shell v2 packet, or `None` if the stream ended between packets
+pub fn read_packet<R: Read>(reader: &mut R) -> Result<Option<(u8, Vec<u8>)>> {
+    let mut header = [0u8; 5];
+    let mut filled = 0;
+    while filled < header.len() {
+        match reader.read(&mut header[filled..])? {
+            0 if filled == 0 => return Ok(None),
+            0 => return Err(AimError::Shell("Shell stream ended inside a packet header".to_string())),
+            n => filled += n,
+        }
+    }
+    let len = u32::from_le_bytes([header[1], header[2], header[3], header[4]]) as usize;
+    let mut payload = vec![0u8; len];
+    reader
+        .read_exact(&mut payload)
+        .map_err(|e| AimError::Shell(format!("Shell stream ended inside a packet: {}", e)))?;
+    Ok(Some((header[0], payload)))
+}
+
+/// Collect stdout, stderr and the exit status from a shell v2 stream
+pub fn read_v2_output<R: Read>(reader: &mut R) -> Result<ShellOutput> {
+    let _span = profile::span(Phase::Execute);
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    loop {
+        match read_packet(reader)? {
+            Some((packet::STDOUT, data)) => stdout.extend_from_slice(&data),
+            Some((packet::STDERR, data)) => stderr.extend_from_slice(&data),
+            Some((packet::EXIT, data)) => {
+                let status = data.first().copied().ok_or_else(|| AimError::Shell("Empty exit packet".to_string()))?;
+                return Ok(ShellOutput {
+                    stdout: ShellCommand::clean_response(&String::from_utf8_lossy(&stdout)),
+                    stderr: ShellCommand::clean_response(&String::from_utf8_lossy(&stderr)),
+                    exit_code: status as i32,
+                });
+            }
+            // Nothing else is sent by the device; skip rather than fail on new ids
+            Some((id, _)) => debug!("Ignoring shell packet {}", id),
+            None => return Err(AimError::Shell("Shell closed without an exit status".to_string())),
+        }
+    }
+}
+
+/// A shell v2 session on a pseudo-terminal, see [`ShellCommand::open_pty`]
+pub struct PtyShell {
+    stream: std::net::TcpStream,
+}
+
+impl PtyShell {
+    /// A second handle, so input and output can be handled on separate threads
+    pub fn try_clone(&self) -> Result<Self> {
+        Ok(Self { stream: self.stream.try_clone()? })
+    }
+    
+    /// Send keystrokes; control characters such as Ctrl-C act on the remote terminal
+    pub fn write_stdin(&mut self, data: &[u8]) -> Result<()> {
+        self.stream.write_all(&encode_packet(packet::STDIN, data))?;
+        Ok(())
+    }
+    
+    /// Tell the remote side that no more input is coming
+    pub fn close_stdin(&mut self) -> Result<()> {
+        self.stream.write_all(&encode_packet(packet::CLOSE_STDIN, &[]))?;
+        Ok(())
+    }
+    
+    /// Pass on a new terminal size
+    pub fn resize(&mut self, rows: u16, cols: u16) -> Result<()> {
+        self.stream.write_all(&encode_packet(packet::WINDOW_SIZE_CHANGE, window_size_payload(rows, cols).as_bytes()))?;
+        Ok(())
+    }
+    
+    /// The next packet from the device, or `None` once it hangs up
+    pub fn read_packet(&mut self) -> Result<Option<(u8, Vec<u8>)>> {
+        read_packet(&mut self.stream)
+    }
+}
+
+/// Payload of a window size packet: `<rows>x<cols>,<width px>x<height px>`
+pub fn window_size_payload(rows: u16, cols: u16) -> String {
+    format!("{}x{},0x0", rows, cols)
+}
+
+/// Several shell commands run as one invocation on the device
+///
+/// Each command is followed by a marker line carrying its exit status, so the
+/// combined output can be split back into one [`ShellOutput`] per command.
+/// This saves a connection and transport handshake per command.
+pub struct ShellBatch {
+    commands: Vec<String>,
+    device_id: Option<DeviceId>,
+}
+
+impl ShellBatch {
+    pub fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+            device_id: None,
+        }
+    }
+    
+    /// Target a specific device
+    pub fn with_device(mut self, device_id: DeviceId) -> Self {
+        self.device_id = Some(device_id);
+        self
+    }
+    
+    /// Append a command to the batch
+    pub fn command(mut self, command: impl Into<String>) -> Self {
+        self.commands.push(command.into());
+        self
+    }
+    
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+    
+    /// Build the combined script, delimiting commands with `marker`
+    pub fn script(&self, marker: &str) -> String {
+        self.commands
+            .iter()
+            // A subshell keeps `exit` or `cd` in one command from affecting the next
+            .map(|cmd| format!("( {} ); printf '\\n{}:%s\\n' $?", cmd, marker))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+    
+    /// Run every command in one shell invocation
+    pub async fn execute(&self, host: &str, port: u16) -> Result<Vec<ShellOutput>> {
+        if self.commands.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        let started = std::time::Instant::now();
+        let marker = format!("__aim_batch_{:016x}__", rand::random::<u64>());
+        let mut conn = AdbConnection::connect(host, port).await?;
+        if let Some(device_id) = &self.device_id {
+            conn.select_device(device_id).await?;
+        }
+        conn.send_command(&format!("shell:{}", self.script(&marker)))?;
+        conn.read_okay()?;
+        let response = conn.read_to_end()?;
+        
+        let device = self.device_id.as_ref().map(|d| d.to_string()).unwrap_or_default();
+        debug!(
+            device = device.as_str(),
+            duration_ms = started.elapsed().as_millis() as u64,
+            commands = self.commands.len();
+            "shell batch finished"
+        );
+        
+        split_batch_output(&response, &marker, self.commands.len())
+    }
+}
+
+impl Default for ShellBatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Split the output of a [`ShellBatch`] script back into per-command results
+pub fn split_batch_output(output: &str, marker: &str, count: usize) -> Result<Vec<ShellOutput>> {
+    let _span = profile::span(Phase::Parse);
+    let output = output.replace('\0', "");
+    let mut results = Vec::with_capacity(count);
+    let mut current = String::new();
+    let prefix = format!("{}:", marker);
+    
+    for line in output.split_inclusive('\n') {
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        match trimmed.strip_prefix(&prefix) {
+            Some(status) => {
+                results.push(ShellOutput {
+                    stdout: current.trim().to_string(),
+                    stderr: String::new(),
+                    exit_code: status.trim().parse().unwrap_or(-1),
+                });
+                current.clear();
+            }
+            None => current.push_str(line),
+        }
+    }
+    
+    if results.len() != count {
+        return Err(AimError::Shell(format!(
+            "Batch output incomplete: expected {} results, got {}",
+            count,
+            results.len()
+        )));
+    }
+    Ok(results)
+}
+
 /// Shell command output
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -193,6 +579,23 @@ pub mod helpers {
         }
     }
     
+    /// Run a command on `device` and return its stdout
+    pub async fn shell(host: &str, port: u16, device: &DeviceId, command: &str) -> Result<String> {
+        Ok(ShellCommand::new(command).with_device(device.clone()).execute(host, port).await?.stdout)
+    }
+    
+    /// Run a command on `device` for its effect, failing if it reports an error
+    ///
+    /// For commands such as `settings` and `wm`, which print an exception or
+    /// "Error: ..." and still exit 0.
+    pub async fn shell_checked(host: &str, port: u16, device: &DeviceId, command: &str) -> Result<()> {
+        let out = shell(host, port, device, &format!("{} 2>&1", command)).await?;
+        if out.contains("Exception") || out.contains("Error") {
+            return Err(AimError::CommandExecution(format!("{} failed: {}", command, out.trim())));
+        }
+        Ok(())
+    }
+    
     /// Run a command and return exit code
     pub async fn run_with_exit_code(
         host: &str,
@@ -200,24 +603,10 @@ pub mod helpers {
         device_id: Option<&DeviceId>,
         command: &str,
     ) -> Result<i32> {
-        // Run command followed by echo $?
-        let cmd_with_exit = format!("{}; echo \"EXIT_CODE:$?\"", command);
-        let mut cmd = ShellCommand::new(cmd_with_exit);
+        let mut cmd = ShellCommand::new(command);
         if let Some(id) = device_id {
             cmd = cmd.with_device(id.clone());
         }
-        
-        let output = cmd.execute(host, port).await?;
-        
-        // Parse exit code from output
-        for line in output.stdout.lines().rev() {
-            if let Some(code_str) = line.strip_prefix("EXIT_CODE:") {
-                if let Ok(code) = code_str.trim().parse::<i32>() {
-                    return Ok(code);
-                }
-            }
-        }
-        
-        Ok(0) // Default to success if we can't parse
+        Ok(cmd.execute(host, port).await?.exit_code)
     }
-}
\ No newline at end of file
+}
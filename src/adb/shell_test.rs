@@ -0,0 +1,121 @@
+use super::shell::{
+    encode_packet, kill_command, packet, read_packet, read_v2_output, split_batch_output, tracked_command,
+    window_size_payload, ShellBatch,
+};
+use std::io::Cursor;
+
+const MARKER: &str = "__aim_batch_test__";
+
+#[test]
+fn test_batch_script_wraps_each_command() {
+    let batch = ShellBatch::new()
+        .command("pm path com.example")
+        .command("pidof com.example");
+    assert_eq!(
+        batch.script(MARKER),
+        "( pm path com.example ); printf '\\n__aim_batch_test__:%s\\n' $?; \
+         ( pidof com.example ); printf '\\n__aim_batch_test__:%s\\n' $?"
+    );
+}
+
+#[test]
+fn test_split_batch_output() {
+    let output = "package:/data/app/base.apk\n\n__aim_batch_test__:0\n\n__aim_batch_test__:1\n";
+    let results = split_batch_output(output, MARKER, 2).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].stdout, "package:/data/app/base.apk");
+    assert!(results[0].success());
+    assert_eq!(results[1].stdout, "");
+    assert_eq!(results[1].exit_code, 1);
+}
+
+#[test]
+fn test_split_batch_output_without_trailing_newline() {
+    // printf starts with a newline, so output that lacks one still splits cleanly
+    let output = "1234\n__aim_batch_test__:0\r\n";
+    let results = split_batch_output(output, MARKER, 1).unwrap();
+    assert_eq!(results[0].stdout, "1234");
+}
+
+#[test]
+fn test_split_batch_output_incomplete() {
+    let output = "partial\n__aim_batch_test__:0\nmore output";
+    assert!(split_batch_output(output, MARKER, 2).is_err());
+}
+
+fn v2_stream(packets: &[(u8, &[u8])]) -> Cursor<Vec<u8>> {
+    Cursor::new(packets.iter().flat_map(|(id, payload)| encode_packet(*id, payload)).collect())
+}
+
+#[test]
+fn test_encode_packet() {
+    assert_eq!(encode_packet(packet::STDOUT, b"hi"), vec![1, 2, 0, 0, 0, b'h', b'i']);
+    assert_eq!(encode_packet(packet::EXIT, &[3]), vec![3, 1, 0, 0, 0, 3]);
+}
+
+#[test]
+fn test_read_v2_output_separates_streams() {
+    let mut stream = v2_stream(&[
+        (packet::STDOUT, b"line one\n"),
+        (packet::STDERR, b"warning: x\n"),
+        (packet::STDOUT, b"line two\n"),
+        (packet::EXIT, &[0]),
+    ]);
+    let output = read_v2_output(&mut stream).unwrap();
+    assert_eq!(output.stdout, "line one\nline two");
+    assert_eq!(output.stderr, "warning: x");
+    assert!(output.success());
+}
+
+#[test]
+fn test_read_v2_output_exit_code() {
+    let mut stream = v2_stream(&[(packet::STDERR, b"ls: /nope: No such file or directory\n"), (packet::EXIT, &[1])]);
+    let output = read_v2_output(&mut stream).unwrap();
+    assert_eq!(output.stdout, "");
+    assert_eq!(output.exit_code, 1);
+    assert!(!output.success());
+
+    // Statuses above 127 (signals) come through unchanged
+    let mut stream = v2_stream(&[(packet::EXIT, &[137])]);
+    assert_eq!(read_v2_output(&mut stream).unwrap().exit_code, 137);
+}
+
+#[test]
+fn test_read_v2_output_skips_unknown_packets() {
+    let mut stream = v2_stream(&[(packet::WINDOW_SIZE_CHANGE, b"24x80,0x0"), (packet::STDOUT, b"ok"), (packet::EXIT, &[0])]);
+    assert_eq!(read_v2_output(&mut stream).unwrap().stdout, "ok");
+}
+
+#[test]
+fn test_read_v2_output_without_exit() {
+    let mut stream = v2_stream(&[(packet::STDOUT, b"partial")]);
+    assert!(read_v2_output(&mut stream).is_err());
+
+    // Cut off inside a header, and inside a payload
+    assert!(read_packet(&mut Cursor::new(vec![1u8, 5, 0])).is_err());
+    assert!(read_packet(&mut Cursor::new(vec![1u8, 5, 0, 0, 0, b'a'])).is_err());
+    assert!(read_packet(&mut Cursor::new(Vec::new())).unwrap().is_none());
+}
+
+#[test]
+fn test_window_size_payload() {
+    assert_eq!(window_size_payload(24, 80), "24x80,0x0");
+    assert_eq!(
+        encode_packet(packet::WINDOW_SIZE_CHANGE, window_size_payload(50, 132).as_bytes()),
+        [&[5u8, 10, 0, 0, 0][..], b"50x132,0x0"].concat()
+    );
+}
+
+#[test]
+fn test_tracked_command() {
+    assert_eq!(
+        tracked_command("sleep 60; exit 3", "/data/local/tmp/.aim-1.pid"),
+        "echo $$ > /data/local/tmp/.aim-1.pid; ( sleep 60; exit 3 ); s=$?; rm -f /data/local/tmp/.aim-1.pid; exit $s"
+    );
+    assert_eq!(
+        kill_command("/data/local/tmp/.aim-1.pid"),
+        "p=$(cat /data/local/tmp/.aim-1.pid 2>/dev/null) && { kill -TERM -$p; pkill -TERM -P $p; kill -TERM $p; } 2>/dev/null; \
+         rm -f /data/local/tmp/.aim-1.pid"
+    );
+}
@@ -0,0 +1,109 @@
+//! SSH tunnels to an adb server on another machine
+//!
+//! `--via-ssh user@host` runs `ssh -N -L` to the remote machine's adb server
+//! port and points every connection at the local end of the forward, so
+//! devices attached to a lab machine can be used without exposing its adb
+//! port on the network. The tunnel lives as long as the [`SshTunnel`].
+
+use crate::error::{AimError, Result};
+use log::debug;
+use std::io::Read;
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Local address the forward listens on
+pub const LOCAL_HOST: &str = "127.0.0.1";
+/// How long to wait for ssh to log in and set up the forward
+const OPEN_TIMEOUT: Duration = Duration::from_secs(30);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Reject targets ssh would read as options
+pub fn validate_target(target: &str) -> Result<()> {
+    if target.is_empty() || target.starts_with('-') || target.chars().any(char::is_whitespace) {
+        return Err(AimError::InvalidArgument(format!("Invalid SSH target: '{}'", target)));
+    }
+    Ok(())
+}
+
+/// Arguments for an ssh process that only forwards `local_port` to the remote server
+pub fn ssh_args(target: &str, local_port: u16, remote_host: &str, remote_port: u16, batch: bool) -> Vec<String> {
+    let mut args = vec!["-N".to_string(), "-o".to_string(), "ExitOnForwardFailure=yes".to_string()];
+    if batch {
+        // No password or host key prompts in CI
+        args.extend(["-o".to_string(), "BatchMode=yes".to_string()]);
+    }
+    args.extend([
+        "-L".to_string(),
+        format!("{}:{}:{}:{}", LOCAL_HOST, local_port, remote_host, remote_port),
+        target.to_string(),
+    ]);
+    args
+}
+
+/// A running `ssh -L` process; killed on drop
+pub struct SshTunnel {
+    child: Child,
+    pub local_port: u16,
+}
+
+impl SshTunnel {
+    /// Start ssh and wait until the local end of the forward accepts connections
+    pub async fn open(target: &str, remote_host: &str, remote_port: u16) -> Result<Self> {
+        validate_target(target)?;
+        let local_port = TcpListener::bind((LOCAL_HOST, 0))?.local_addr()?.port();
+        let batch = crate::core::ci::is_enabled();
+        let args = ssh_args(target, local_port, remote_host, remote_port, batch);
+        debug!("Starting ssh {}", args.join(" "));
+
+        let mut child = Command::new("ssh")
+            .args(&args)
+            .stdin(if batch { Stdio::null() } else { Stdio::inherit() })
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| AimError::SshTunnel(format!("Cannot run ssh: {}", e)))?;
+
+        let deadline = Instant::now() + OPEN_TIMEOUT;
+        loop {
+            if let Some(status) = child.try_wait()? {
+                let mut stderr = String::new();
+                if let Some(mut pipe) = child.stderr.take() {
+                    let _ = pipe.read_to_string(&mut stderr);
+                }
+                return Err(AimError::SshTunnel(format!(
+                    "ssh to {} exited ({}): {}",
+                    target,
+                    status,
+                    stderr.trim()
+                )));
+            }
+            if TcpStream::connect((LOCAL_HOST, local_port)).is_ok() {
+                break;
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AimError::SshTunnel(format!(
+                    "Timed out after {}s waiting for the tunnel to {}",
+                    OPEN_TIMEOUT.as_secs(),
+                    target
+                )));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        debug!(
+            "Tunnel to {}:{} via {} listening on {}:{}",
+            remote_host, remote_port, target, LOCAL_HOST, local_port
+        );
+        Ok(Self { child, local_port })
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
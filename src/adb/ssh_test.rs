@@ -0,0 +1,21 @@
+use super::ssh::{ssh_args, validate_target};
+
+#[test]
+fn test_validate_target() {
+    assert!(validate_target("me@lab-box").is_ok());
+    assert!(validate_target("lab").is_ok());
+    assert!(validate_target("").is_err());
+    assert!(validate_target("-oProxyCommand=evil").is_err());
+    assert!(validate_target("me@lab box").is_err());
+}
+
+#[test]
+fn test_ssh_args() {
+    assert_eq!(
+        ssh_args("me@lab", 40123, "localhost", 5037, false),
+        vec!["-N", "-o", "ExitOnForwardFailure=yes", "-L", "127.0.0.1:40123:localhost:5037", "me@lab"]
+    );
+    let batch = ssh_args("me@lab", 40123, "localhost", 5037, true);
+    assert!(batch.windows(2).any(|w| w == ["-o", "BatchMode=yes"]));
+    assert_eq!(batch.last().map(String::as_str), Some("me@lab"));
+}
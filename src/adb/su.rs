@@ -0,0 +1,76 @@
+//! Running shell commands as root
+//!
+//! Rooted devices differ in how `su` takes a command: Magisk and SuperSU
+//! accept `su -c <cmd>`, while the `su` of AOSP userdebug builds takes a uid
+//! followed by the command, `su 0 <cmd>`. Both are probed in one round trip.
+
+use crate::adb::shell::ShellCommand;
+use crate::core::types::DeviceId;
+use crate::error::{AimError, Result};
+use crate::utils::shell_quote;
+
+/// Prints which way of becoming root works, if any
+pub const PROBE_SCRIPT: &str = "if [ \"$(id -u)\" = 0 ]; then echo adbd; \
+     elif [ \"$(su -c 'id -u' 2>/dev/null)\" = 0 ]; then echo su-c; \
+     elif [ \"$(su 0 id -u 2>/dev/null)\" = 0 ]; then echo su-0; \
+     else echo none; fi";
+
+/// How commands are run on a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RootMode {
+    /// As the shell user, root was not asked for
+    User,
+    /// adbd already runs as root (`adb root`), nothing to wrap
+    Adbd,
+    /// `su -c <cmd>` (Magisk, SuperSU)
+    SuDashC,
+    /// `su 0 sh -c <cmd>` (AOSP userdebug)
+    SuUid,
+}
+
+impl RootMode {
+    /// Wrap a shell command so it runs with this mode
+    pub fn wrap(&self, command: &str) -> String {
+        match self {
+            RootMode::User | RootMode::Adbd => command.to_string(),
+            RootMode::SuDashC => format!("su -c {}", shell_quote(command)),
+            RootMode::SuUid => format!("su 0 sh -c {}", shell_quote(command)),
+        }
+    }
+
+    /// Whether commands run as root
+    pub fn is_root(&self) -> bool {
+        *self != RootMode::User
+    }
+
+    /// Find how to become root on the device
+    ///
+    /// Fails with [`AimError::RootUnavailable`] when neither adbd nor `su`
+    /// gives root, so callers can tell that apart from the command failing.
+    pub async fn detect(host: &str, port: u16, device: &DeviceId) -> Result<Self> {
+        let output = ShellCommand::new(PROBE_SCRIPT)
+            .with_device(device.clone())
+            .execute(host, port)
+            .await?;
+        parse_probe(&output.stdout).ok_or_else(|| AimError::RootUnavailable(device.to_string()))
+    }
+
+    /// [`RootMode::detect`] when `as_root` is set, otherwise [`RootMode::User`]
+    pub async fn for_args(as_root: bool, host: &str, port: u16, device: &DeviceId) -> Result<Self> {
+        if as_root {
+            Self::detect(host, port, device).await
+        } else {
+            Ok(RootMode::User)
+        }
+    }
+}
+
+/// Parse the output of [`PROBE_SCRIPT`]; `None` means no root
+pub fn parse_probe(output: &str) -> Option<RootMode> {
+    match output.trim() {
+        "adbd" => Some(RootMode::Adbd),
+        "su-c" => Some(RootMode::SuDashC),
+        "su-0" => Some(RootMode::SuUid),
+        _ => None,
+    }
+}
@@ -0,0 +1,24 @@
+use super::su::{parse_probe, RootMode};
+
+#[test]
+fn test_parse_probe() {
+    assert_eq!(parse_probe("adbd\n"), Some(RootMode::Adbd));
+    assert_eq!(parse_probe("su-c\r\n"), Some(RootMode::SuDashC));
+    assert_eq!(parse_probe("su-0"), Some(RootMode::SuUid));
+    assert_eq!(parse_probe("none"), None);
+    assert_eq!(parse_probe(""), None);
+}
+
+#[test]
+fn test_wrap() {
+    let cmd = "cat /data/system/packages.xml";
+    assert_eq!(RootMode::User.wrap(cmd), cmd);
+    assert_eq!(RootMode::Adbd.wrap(cmd), cmd);
+    assert_eq!(RootMode::SuDashC.wrap(cmd), "su -c 'cat /data/system/packages.xml'");
+    assert_eq!(RootMode::SuUid.wrap(cmd), "su 0 sh -c 'cat /data/system/packages.xml'");
+
+    // Quotes inside the command survive the extra level of quoting
+    assert_eq!(RootMode::SuDashC.wrap("echo 'hi'"), r"su -c 'echo '\''hi'\'''");
+    assert!(!RootMode::User.is_root());
+    assert!(RootMode::SuUid.is_root());
+}
@@ -0,0 +1,268 @@
+//! Parsers for `dumpsys` output
+//!
+//! [`PackageDump`] reads the package section of `dumpsys package <pkg>`. Its
+//! layout has been stable since Android 7, but fields move between releases
+//! (Android 13 put `firstInstallTime` under each user, requested permissions
+//! gained `: restricted=true` markers), so the parser goes by indentation and
+//! key names rather than line positions.
+
+use crate::adb::shell::ShellCommand;
+use crate::core::profile::{self, Phase};
+use crate::core::types::DeviceId;
+use crate::error::Result;
+use crate::utils::shell_quote;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+/// Enabled setting of a package for one user (`enabled=` in its user line)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EnabledState {
+    /// Whatever the manifest says, which is enabled for nearly every app
+    #[default]
+    Default,
+    Enabled,
+    Disabled,
+    /// Turned off with `pm disable-user` or from the settings
+    DisabledUser,
+    DisabledUntilUsed,
+}
+
+impl EnabledState {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => EnabledState::Enabled,
+            2 => EnabledState::Disabled,
+            3 => EnabledState::DisabledUser,
+            4 => EnabledState::DisabledUntilUsed,
+            _ => EnabledState::Default,
+        }
+    }
+
+    pub fn is_enabled(self) -> bool {
+        matches!(self, EnabledState::Default | EnabledState::Enabled)
+    }
+}
+
+/// State of a package for one user
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UserState {
+    pub id: u32,
+    pub installed: bool,
+    pub enabled: EnabledState,
+    /// Runtime permission to whether it is granted
+    pub runtime_permissions: BTreeMap<String, bool>,
+}
+
+/// The package section of `dumpsys package <pkg>`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PackageDump {
+    pub package: String,
+    pub version_code: Option<u64>,
+    pub version_name: Option<String>,
+    pub min_sdk: Option<u32>,
+    pub target_sdk: Option<u32>,
+    pub code_path: Option<String>,
+    pub data_dir: Option<String>,
+    pub primary_cpu_abi: Option<String>,
+    /// Device-local time, "YYYY-MM-DD HH:MM:SS"
+    pub first_install_time: Option<String>,
+    /// Device-local time, "YYYY-MM-DD HH:MM:SS"
+    pub last_update_time: Option<String>,
+    pub installer: Option<String>,
+    /// `pkgFlags`, e.g. `SYSTEM`, `UPDATED_SYSTEM_APP`, `DEBUGGABLE`
+    pub flags: Vec<String>,
+    pub requested_permissions: Vec<String>,
+    /// Install-time permission to whether it is granted
+    pub install_permissions: BTreeMap<String, bool>,
+    pub users: Vec<UserState>,
+}
+
+/// Indented lists inside the package section
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum List {
+    Requested,
+    Install,
+    Runtime,
+    /// Lists aim does not read (declared permissions, components, ...)
+    Other,
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// The `key=value` words of a line
+fn fields(text: &str) -> impl Iterator<Item = (&str, &str)> {
+    text.split_whitespace().filter_map(|word| word.split_once('='))
+}
+
+/// Keep the first real value of a field: later sections repeat some of them
+fn set(slot: &mut Option<String>, value: &str) {
+    if slot.is_none() && !value.is_empty() && value != "null" {
+        *slot = Some(value.to_string());
+    }
+}
+
+/// `android.permission.CAMERA: granted=true, flags=[ USER_SET ]`
+fn permission_grant(text: &str) -> Option<(String, bool)> {
+    let (name, rest) = text.split_once(": granted=")?;
+    Some((name.to_string(), rest.starts_with("true")))
+}
+
+/// `User 0: ceDataInode=... installed=true ... enabled=0 ...`
+fn parse_user(text: &str) -> Option<UserState> {
+    let (id, rest) = text.strip_prefix("User ")?.split_once(':')?;
+    let mut user = UserState { id: id.trim().parse().ok()?, ..Default::default() };
+    for (key, value) in fields(rest) {
+        match key {
+            "installed" => user.installed = value == "true",
+            "enabled" => user.enabled = EnabledState::from_code(value.parse().unwrap_or(0)),
+            _ => {}
+        }
+    }
+    Some(user)
+}
+
+impl PackageDump {
+    /// Read the first package in the `Packages:` section
+    ///
+    /// `None` when there is none, which is what `dumpsys package` gives for
+    /// a package that is not installed. Updated system apps are listed again
+    /// under `Hidden system packages:` with their factory version; that copy
+    /// is ignored.
+    pub fn parse(output: &str) -> Option<Self> {
+        let _span = profile::span(Phase::Parse);
+        let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+        let start = lines.iter().position(|line| *line == "Packages:").unwrap_or(0);
+        let header = start + lines[start..].iter().position(|line| line.trim_start().starts_with("Package ["))?;
+        let name = lines[header].trim_start().strip_prefix("Package [")?.split(']').next()?;
+        let depth = indent(lines[header]);
+
+        let mut dump = PackageDump { package: name.to_string(), ..Default::default() };
+        let mut list: Option<(List, usize)> = None;
+        for line in lines[header + 1..].iter().take_while(|line| !line.is_empty() && indent(line) > depth) {
+            let (at, text) = (indent(line), line.trim());
+            if let Some((kind, list_at)) = list {
+                if at > list_at {
+                    dump.add(kind, text);
+                    continue;
+                }
+            }
+            list = None;
+
+            if text.ends_with(':') && !text.contains('=') && !text.starts_with("User ") {
+                let kind = match text {
+                    "requested permissions:" => List::Requested,
+                    // Android 5 listed granted permissions by name only
+                    "install permissions:" | "grantedPermissions:" => List::Install,
+                    "runtime permissions:" => List::Runtime,
+                    _ => List::Other,
+                };
+                list = Some((kind, at));
+            } else if let Some(user) = parse_user(text) {
+                dump.users.push(user);
+            } else {
+                dump.read_fields(text);
+            }
+        }
+        Some(dump)
+    }
+
+    fn add(&mut self, list: List, text: &str) {
+        match list {
+            List::Requested => {
+                let name = text.split(':').next().unwrap_or(text).trim();
+                self.requested_permissions.push(name.to_string());
+            }
+            List::Install => {
+                let (name, granted) = permission_grant(text).unwrap_or_else(|| (text.to_string(), true));
+                self.install_permissions.insert(name, granted);
+            }
+            List::Runtime => {
+                if let (Some(user), Some((name, granted))) = (self.users.last_mut(), permission_grant(text)) {
+                    user.runtime_permissions.insert(name, granted);
+                }
+            }
+            List::Other => {}
+        }
+    }
+
+    fn read_fields(&mut self, text: &str) {
+        // These values can contain spaces and take the rest of the line
+        if let Some((key, value)) = text.split_once('=') {
+            let slot = match key {
+                "versionName" => Some(&mut self.version_name),
+                "firstInstallTime" => Some(&mut self.first_install_time),
+                "lastUpdateTime" => Some(&mut self.last_update_time),
+                _ => None,
+            };
+            if let Some(slot) = slot {
+                set(slot, value.trim());
+                return;
+            }
+        }
+        if let Some(flags) = text.strip_prefix("pkgFlags=[").or_else(|| text.strip_prefix("flags=[")) {
+            if self.flags.is_empty() {
+                self.flags = flags.trim_end_matches(']').split_whitespace().map(String::from).collect();
+            }
+            return;
+        }
+        for (key, value) in fields(text) {
+            match key {
+                "versionCode" => self.version_code = self.version_code.or(value.parse().ok()),
+                "minSdk" => self.min_sdk = self.min_sdk.or(value.parse().ok()),
+                "targetSdk" => self.target_sdk = self.target_sdk.or(value.parse().ok()),
+                "codePath" => set(&mut self.code_path, value),
+                "dataDir" => set(&mut self.data_dir, value),
+                "primaryCpuAbi" => set(&mut self.primary_cpu_abi, value),
+                "installerPackageName" => set(&mut self.installer, value),
+                _ => {}
+            }
+        }
+    }
+
+    /// Preinstalled with the system image, updated or not
+    pub fn is_system(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "SYSTEM")
+    }
+
+    /// Built debuggable, so `run-as` works for it
+    pub fn is_debuggable(&self) -> bool {
+        self.flags.iter().any(|flag| flag == "DEBUGGABLE")
+    }
+
+    pub fn user(&self, id: u32) -> Option<&UserState> {
+        self.users.iter().find(|user| user.id == id)
+    }
+
+    /// Enabled for the first user listed (the owner on phones)
+    pub fn is_enabled(&self) -> bool {
+        self.users.first().is_none_or(|user| user.enabled.is_enabled())
+    }
+
+    /// Runtime permissions of the first user listed
+    pub fn runtime_permissions(&self) -> BTreeMap<String, bool> {
+        self.users.first().map(|user| user.runtime_permissions.clone()).unwrap_or_default()
+    }
+
+    /// Granted install-time permissions and runtime permissions of the first user
+    pub fn granted_permissions(&self) -> HashSet<String> {
+        self.install_permissions
+            .iter()
+            .chain(self.users.first().into_iter().flat_map(|user| &user.runtime_permissions))
+            .filter(|(_, &granted)| granted)
+            .map(|(permission, _)| permission.clone())
+            .collect()
+    }
+}
+
+pub fn package_command(package: &str) -> String {
+    format!("dumpsys package {}", shell_quote(package))
+}
+
+/// Read the package section of `package` from a device; `None` if it is not installed
+pub async fn package(host: &str, port: u16, device: &DeviceId, package: &str) -> Result<Option<PackageDump>> {
+    let output = ShellCommand::new(package_command(package)).with_device(device.clone()).execute(host, port).await?;
+    Ok(PackageDump::parse(&output.stdout))
+}
@@ -0,0 +1,100 @@
+use crate::android::dumpsys::*;
+
+const ANDROID_7: &str = include_str!("../../tests/fixtures/dumpsys/package_android7.txt");
+const ANDROID_10: &str = include_str!("../../tests/fixtures/dumpsys/package_android10.txt");
+const ANDROID_14: &str = include_str!("../../tests/fixtures/dumpsys/package_android14.txt");
+
+#[test]
+fn test_parse_android_7() {
+    let dump = PackageDump::parse(ANDROID_7).unwrap();
+    assert_eq!(dump.package, "com.example.notes");
+    assert_eq!(dump.version_code, Some(31));
+    assert_eq!(dump.version_name.as_deref(), Some("3.1 beta"));
+    assert_eq!((dump.min_sdk, dump.target_sdk), (Some(21), Some(25)));
+    assert_eq!(dump.code_path.as_deref(), Some("/data/app/com.example.notes-1"));
+    assert_eq!(dump.primary_cpu_abi, None);
+    assert_eq!(dump.first_install_time.as_deref(), Some("2017-05-30 09:42:17"));
+    assert_eq!(dump.last_update_time.as_deref(), Some("2017-06-02 14:11:07"));
+    assert_eq!(dump.installer.as_deref(), Some("com.android.vending"));
+    assert!(!dump.is_system());
+    assert!(!dump.is_debuggable());
+    assert!(dump.is_enabled());
+    assert_eq!(
+        dump.requested_permissions,
+        vec!["android.permission.INTERNET", "android.permission.CAMERA", "android.permission.READ_CONTACTS"]
+    );
+    let runtime = dump.runtime_permissions();
+    assert_eq!(runtime.len(), 2);
+    assert!(runtime["android.permission.CAMERA"]);
+    assert!(!runtime["android.permission.READ_CONTACTS"]);
+}
+
+#[test]
+fn test_parse_android_10_updated_system_app() {
+    let dump = PackageDump::parse(ANDROID_10).unwrap();
+    assert_eq!(dump.package, "com.google.android.youtube");
+    // The updated version, not the factory one under "Hidden system packages"
+    assert_eq!(dump.version_code, Some(1530411712));
+    assert_eq!(dump.version_name.as_deref(), Some("15.30.41"));
+    assert_eq!(dump.primary_cpu_abi.as_deref(), Some("arm64-v8a"));
+    assert!(dump.is_system());
+    assert!(dump.flags.contains(&"UPDATED_SYSTEM_APP".to_string()));
+    // Restricted permissions are listed with a marker
+    assert_eq!(dump.requested_permissions[1], "android.permission.ACCESS_FINE_LOCATION");
+    // Declared permissions are not install permissions
+    assert_eq!(dump.install_permissions.len(), 2);
+
+    assert_eq!(dump.users.len(), 2);
+    assert_eq!(dump.users[0].enabled, EnabledState::DisabledUser);
+    assert!(!dump.is_enabled());
+    let secondary = dump.user(10).unwrap();
+    assert!(!secondary.installed);
+    assert!(secondary.runtime_permissions.is_empty());
+}
+
+#[test]
+fn test_parse_android_14() {
+    let dump = PackageDump::parse(ANDROID_14).unwrap();
+    assert_eq!(dump.version_code, Some(204010));
+    assert_eq!(dump.version_name.as_deref(), Some("2.4.1 (204010)"));
+    assert_eq!(dump.target_sdk, Some(34));
+    assert_eq!(dump.data_dir.as_deref(), Some("/data/user/0/com.example.shop"));
+    // Under the user since Android 13
+    assert_eq!(dump.first_install_time.as_deref(), Some("2024-01-20 08:15:03"));
+    assert_eq!(dump.user(0).unwrap().enabled, EnabledState::Default);
+    assert!(dump.is_debuggable());
+    assert_eq!(
+        dump.granted_permissions(),
+        [
+            "android.permission.INTERNET",
+            "android.permission.ACCESS_NETWORK_STATE",
+            "android.permission.POST_NOTIFICATIONS",
+        ]
+        .iter()
+        .map(|p| p.to_string())
+        .collect()
+    );
+}
+
+#[test]
+fn test_parse_missing_package() {
+    assert_eq!(PackageDump::parse(""), None);
+    assert_eq!(PackageDump::parse("Activity Resolver Table:\n\nPackages:\n\nQueries:\n"), None);
+}
+
+#[test]
+fn test_empty_values() {
+    let dump = PackageDump::parse("Packages:\n  Package [com.example] (1):\n    versionName=\n    installerPackageName=null\n").unwrap();
+    assert_eq!(dump.version_name, None);
+    assert_eq!(dump.installer, None);
+    assert!(dump.users.is_empty());
+    assert!(dump.is_enabled());
+}
+
+#[test]
+fn test_enabled_state() {
+    assert_eq!(EnabledState::from_code(0), EnabledState::Default);
+    assert_eq!(EnabledState::from_code(2), EnabledState::Disabled);
+    assert!(EnabledState::Enabled.is_enabled());
+    assert!(!EnabledState::DisabledUntilUsed.is_enabled());
+}
@@ -0,0 +1,14 @@
+//! Typed access to Android's own command-line tools
+
+pub mod dumpsys;
+pub mod pm;
+pub mod sdk;
+
+#[cfg(test)]
+mod dumpsys_test;
+
+#[cfg(test)]
+mod pm_test;
+
+#[cfg(test)]
+mod sdk_test;
@@ -0,0 +1,388 @@
+//! The package manager (`pm` / `cmd package`)
+//!
+//! Each operation has a command builder and an output parser, which
+//! `ShellBatch` users and tests call directly, and a method on [`Pm`] that
+//! runs it on a device. Commands go through `ShellCommand`, so the plain ones
+//! take the abb path on devices that have it.
+
+use crate::adb::shell::{ShellCommand, ShellOutput};
+use crate::core::context::CommandContext;
+use crate::core::profile::{self, Phase};
+use crate::core::types::DeviceId;
+use crate::error::{AimError, Result};
+use crate::utils::shell_quote;
+use serde::Serialize;
+
+/// Which packages `pm list packages` reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Origin {
+    #[default]
+    All,
+    /// Preinstalled with the system image (`-s`)
+    System,
+    /// Installed by the user (`-3`)
+    ThirdParty,
+}
+
+/// Enabled state filter of `pm list packages`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum State {
+    #[default]
+    Any,
+    Enabled,
+    Disabled,
+}
+
+/// A `pm list packages` query
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListPackages {
+    pub origin: Origin,
+    pub state: State,
+    /// Only packages whose name contains this
+    pub name: Option<String>,
+}
+
+impl ListPackages {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn origin(mut self, origin: Origin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn state(mut self, state: State) -> Self {
+        self.state = state;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn command(&self) -> String {
+        let mut command = "pm list packages".to_string();
+        match self.origin {
+            Origin::All => {}
+            Origin::System => command.push_str(" -s"),
+            Origin::ThirdParty => command.push_str(" -3"),
+        }
+        match self.state {
+            State::Any => {}
+            State::Enabled => command.push_str(" -e"),
+            State::Disabled => command.push_str(" -d"),
+        }
+        if let Some(name) = &self.name {
+            command.push(' ');
+            command.push_str(&shell_quote(name));
+        }
+        command
+    }
+}
+
+/// Package names from `pm list packages`, sorted
+///
+/// Also takes the `-f` form (`package:<apk path>=<name>`).
+pub fn parse_packages(output: &str) -> Vec<String> {
+    let _span = profile::span(Phase::Parse);
+    let mut packages: Vec<String> = output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("package:"))
+        .map(|entry| match entry.starts_with('/') {
+            true => entry.rsplit_once('=').map_or(entry, |(_, name)| name),
+            false => entry,
+        })
+        .map(str::to_string)
+        .collect();
+    packages.sort();
+    packages
+}
+
+pub fn path_command(package: &str) -> String {
+    format!("pm path {}", shell_quote(package))
+}
+
+/// APK paths from `pm path`, base APK first
+pub fn parse_paths(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("package:"))
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn clear_command(package: &str) -> String {
+    format!("pm clear {}", shell_quote(package))
+}
+
+pub fn grant_command(package: &str, permission: &str) -> String {
+    format!("pm grant {} {}", shell_quote(package), shell_quote(permission))
+}
+
+pub fn revoke_command(package: &str, permission: &str) -> String {
+    format!("pm revoke {} {}", shell_quote(package), shell_quote(permission))
+}
+
+/// `pm uninstall`, for one user only when `user` is given (the package stays on the system image)
+pub fn uninstall_command(package: &str, user: Option<u32>) -> String {
+    match user {
+        Some(user) => format!("pm uninstall --user {} {}", user, shell_quote(package)),
+        None => format!("pm uninstall {}", shell_quote(package)),
+    }
+}
+
+pub fn enable_command(package: &str, user: u32) -> String {
+    format!("pm enable --user {} {}", user, shell_quote(package))
+}
+
+pub fn disable_command(package: &str, user: u32) -> String {
+    format!("pm disable-user --user {} {}", user, shell_quote(package))
+}
+
+/// Reinstall a system package removed for one user
+pub fn install_existing_command(package: &str, user: u32) -> String {
+    format!("cmd package install-existing --user {} {}", user, shell_quote(package))
+}
+
+/// Session id from `pm install-create` ("Success: created install session [1234]")
+pub fn parse_session_id(output: &str) -> Option<u32> {
+    let (_, rest) = output.split_once('[')?;
+    rest.split(']').next()?.parse().ok()
+}
+
+/// How pm answered an install
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "result", rename_all = "lowercase")]
+pub enum InstallOutcome {
+    Success,
+    Failure {
+        /// `INSTALL_FAILED_*` or `INSTALL_PARSE_FAILED_*` code, when pm gave one
+        code: Option<String>,
+        message: String,
+    },
+}
+
+impl InstallOutcome {
+    /// "CODE: message", or whichever of the two pm gave; `None` on success
+    pub fn reason(&self) -> Option<String> {
+        match self {
+            InstallOutcome::Success => None,
+            InstallOutcome::Failure { code: Some(code), message } if message.is_empty() => Some(code.clone()),
+            InstallOutcome::Failure { code: Some(code), message } => Some(format!("{}: {}", code, message)),
+            InstallOutcome::Failure { code: None, message } => Some(message.clone()),
+        }
+    }
+}
+
+/// Read pm's answer: "Success" (install-write adds what it wrote), or
+/// "Failure [CODE: message]" / "Failure [CODE]"
+///
+/// Older releases put the failure on stderr and some print extra lines
+/// before it, so the whole output is searched.
+pub fn parse_install_output(output: &str) -> InstallOutcome {
+    if output.lines().any(|line| line.trim().starts_with("Success")) {
+        return InstallOutcome::Success;
+    }
+    let Some(start) = output.find("Failure [").map(|i| i + "Failure [".len()) else {
+        let message = output.trim();
+        return InstallOutcome::Failure {
+            code: None,
+            message: if message.is_empty() { "pm gave no answer".to_string() } else { message.to_string() },
+        };
+    };
+    let rest = &output[start..];
+    let inner = rest.rfind(']').map(|end| &rest[..end]).unwrap_or(rest).trim();
+    match inner.split_once(':') {
+        Some((code, message)) if is_failure_code(code) => InstallOutcome::Failure {
+            code: Some(code.to_string()),
+            message: message.trim().to_string(),
+        },
+        _ if is_failure_code(inner) => InstallOutcome::Failure { code: Some(inner.to_string()), message: String::new() },
+        _ => InstallOutcome::Failure { code: None, message: inner.to_string() },
+    }
+}
+
+fn is_failure_code(text: &str) -> bool {
+    text.starts_with("INSTALL_") && text.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// What to do about the common install failures
+pub fn failure_hint(code: &str) -> Option<&'static str> {
+    Some(match code {
+        "INSTALL_FAILED_ALREADY_EXISTS" => "the app is installed already; add --replace to update it",
+        "INSTALL_FAILED_UPDATE_INCOMPATIBLE" => {
+            "the installed app is signed with a different key; uninstall it first (its data is lost)"
+        }
+        "INSTALL_FAILED_VERSION_DOWNGRADE" => {
+            "the installed version is newer; add --downgrade (debuggable apps only) or uninstall it first"
+        }
+        "INSTALL_FAILED_TEST_ONLY" => "the APK is test-only; add --test",
+        "INSTALL_FAILED_INSUFFICIENT_STORAGE" => "the device is out of space; `aim clean` can free some",
+        "INSTALL_FAILED_NO_MATCHING_ABIS" => "the APK has no native code for this device's CPU",
+        "INSTALL_FAILED_OLDER_SDK" => "the app needs a newer Android version than the device runs",
+        "INSTALL_FAILED_MISSING_SPLIT" => "the app is split; pass the base APK and all its splits together",
+        "INSTALL_PARSE_FAILED_NO_CERTIFICATES" => "the APK is not signed",
+        "INSTALL_FAILED_USER_RESTRICTED" => "installing over USB is turned off in the developer options",
+        _ => return None,
+    })
+}
+
+/// The error in the output of a command that prints nothing when it works
+/// (`grant`, `revoke`, ...)
+///
+/// pm reports failures as a Java exception ("Exception occurred while
+/// executing 'grant':" followed by "java.lang.SecurityException: <reason>"),
+/// or as "Error: ..." / "Failure [...]" lines.
+pub fn parse_error(output: &str) -> Option<String> {
+    let lines: Vec<&str> = output.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+    if lines.is_empty() {
+        return None;
+    }
+    let reason = lines
+        .iter()
+        .find_map(|line| {
+            let (kind, reason) = line.split_once(": ")?;
+            (kind.ends_with("Exception") && !kind.contains(' ')).then_some(reason)
+        })
+        .or_else(|| lines.iter().find_map(|line| line.strip_prefix("Error: ")))
+        .unwrap_or(lines[0]);
+    Some(reason.to_string())
+}
+
+/// Package manager of one device
+pub struct Pm<'a> {
+    host: &'a str,
+    port: u16,
+    device: &'a DeviceId,
+}
+
+impl<'a> Pm<'a> {
+    pub fn new(host: &'a str, port: u16, device: &'a DeviceId) -> Self {
+        Self { host, port, device }
+    }
+
+    /// The package manager of the context's device
+    pub fn for_context(ctx: &'a CommandContext) -> Result<Self> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        Ok(Self::new(host, port, &device.id))
+    }
+
+    async fn run(&self, command: String) -> Result<ShellOutput> {
+        ShellCommand::new(command).with_device(self.device.clone()).execute(self.host, self.port).await
+    }
+
+    /// Run a command that prints nothing when it works
+    async fn run_silent(&self, what: &str, command: String) -> Result<()> {
+        let output = self.run(command).await?;
+        let text = format!("{}\n{}", output.stdout, output.stderr);
+        match parse_error(&text) {
+            None if output.success() => Ok(()),
+            error => Err(AimError::CommandExecution(format!(
+                "{} failed: {}",
+                what,
+                error.unwrap_or_else(|| format!("exit status {}", output.exit_code))
+            ))),
+        }
+    }
+
+    /// Run a command that answers "Success"
+    async fn run_checked(&self, what: &str, command: String) -> Result<()> {
+        let output = self.run(command).await?;
+        match parse_install_output(&format!("{}\n{}", output.stdout, output.stderr)).reason() {
+            None => Ok(()),
+            Some(reason) => Err(AimError::CommandExecution(format!("{} failed: {}", what, reason))),
+        }
+    }
+
+    /// Run `pm enable` / `pm disable-user`, which answer "Package <pkg> new state: <state>"
+    async fn run_state_change(&self, what: &str, command: String) -> Result<()> {
+        let output = self.run(command).await?;
+        let text = format!("{}\n{}", output.stdout, output.stderr);
+        if text.contains("new state:") {
+            return Ok(());
+        }
+        Err(AimError::CommandExecution(format!(
+            "{} failed: {}",
+            what,
+            parse_error(&text).unwrap_or_else(|| format!("exit status {}", output.exit_code))
+        )))
+    }
+
+    pub async fn list_packages(&self, query: &ListPackages) -> Result<Vec<String>> {
+        Ok(parse_packages(&self.run(query.command()).await?.stdout))
+    }
+
+    /// APK paths of an installed package, base APK first
+    pub async fn path(&self, package: &str) -> Result<Vec<String>> {
+        Ok(parse_paths(&self.run(path_command(package)).await?.stdout))
+    }
+
+    /// Delete all data of a package
+    pub async fn clear(&self, package: &str) -> Result<()> {
+        self.run_checked(&format!("Clearing {}", package), clear_command(package)).await
+    }
+
+    pub async fn grant(&self, package: &str, permission: &str) -> Result<()> {
+        self.run_silent(&format!("Granting {}", permission), grant_command(package, permission)).await
+    }
+
+    pub async fn revoke(&self, package: &str, permission: &str) -> Result<()> {
+        self.run_silent(&format!("Revoking {}", permission), revoke_command(package, permission)).await
+    }
+
+    /// Uninstall for all users, or for `user` only
+    pub async fn uninstall(&self, package: &str, user: Option<u32>) -> Result<()> {
+        self.run_checked(&format!("Uninstalling {}", package), uninstall_command(package, user)).await
+    }
+
+    pub async fn enable(&self, package: &str, user: u32) -> Result<()> {
+        self.run_state_change(&format!("Enabling {}", package), enable_command(package, user)).await
+    }
+
+    pub async fn disable(&self, package: &str, user: u32) -> Result<()> {
+        self.run_state_change(&format!("Disabling {}", package), disable_command(package, user)).await
+    }
+
+    /// Install an APK already on the device
+    pub async fn install(&self, remote: &str, flags: &str) -> Result<InstallOutcome> {
+        let output = self.run(format!("pm install{} {}", flags, shell_quote(remote))).await?;
+        Ok(parse_install_output(&format!("{}\n{}", output.stdout, output.stderr)))
+    }
+
+    /// Start an install session, for split APKs
+    pub async fn install_session(&self, flags: &str) -> Result<InstallSession<'_>> {
+        let output = self.run(format!("pm install-create{}", flags)).await?;
+        let id = parse_session_id(&output.stdout)
+            .ok_or_else(|| AimError::CommandExecution(format!("pm install-create failed: {}", output.stdout.trim())))?;
+        Ok(InstallSession { pm: self, id })
+    }
+}
+
+/// An install session from `pm install-create`
+pub struct InstallSession<'a> {
+    pm: &'a Pm<'a>,
+    pub id: u32,
+}
+
+impl InstallSession<'_> {
+    /// Add an APK already on the device as split `index`
+    pub async fn write(&self, index: usize, remote: &str) -> Result<InstallOutcome> {
+        let command = format!("pm install-write {} {} {}", self.id, index, shell_quote(remote));
+        let output = self.pm.run(command).await?;
+        Ok(parse_install_output(&format!("{}\n{}", output.stdout, output.stderr)))
+    }
+
+    pub async fn commit(self) -> Result<InstallOutcome> {
+        let output = self.pm.run(format!("pm install-commit {}", self.id)).await?;
+        Ok(parse_install_output(&format!("{}\n{}", output.stdout, output.stderr)))
+    }
+
+    pub async fn abandon(self) -> Result<()> {
+        self.pm.run(format!("pm install-abandon {}", self.id)).await?;
+        Ok(())
+    }
+}
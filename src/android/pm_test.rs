@@ -0,0 +1,116 @@
+use crate::android::pm::*;
+
+const LIST_PACKAGES_F: &str = include_str!("../../tests/fixtures/pm/list_packages_f.txt");
+const PATH_SPLIT: &str = include_str!("../../tests/fixtures/pm/path_split.txt");
+const INSTALL_CREATE: &str = include_str!("../../tests/fixtures/pm/install_create.txt");
+const INSTALL_DOWNGRADE: &str = include_str!("../../tests/fixtures/pm/install_downgrade.txt");
+const GRANT_NOT_REQUESTED: &str = include_str!("../../tests/fixtures/pm/grant_not_requested.txt");
+const GRANT_UNKNOWN_PACKAGE: &str = include_str!("../../tests/fixtures/pm/grant_unknown_package.txt");
+
+#[test]
+fn test_list_packages_command() {
+    assert_eq!(ListPackages::new().command(), "pm list packages");
+    assert_eq!(ListPackages::new().origin(Origin::ThirdParty).command(), "pm list packages -3");
+    assert_eq!(
+        ListPackages::new().origin(Origin::System).state(State::Disabled).command(),
+        "pm list packages -s -d"
+    );
+    assert_eq!(ListPackages::new().state(State::Enabled).name("my app").command(), "pm list packages -e 'my app'");
+}
+
+#[test]
+fn test_parse_packages_with_paths() {
+    assert_eq!(
+        parse_packages(LIST_PACKAGES_F),
+        vec!["com.android.bluetooth", "com.example.app", "com.google.android.dialer", "com.spotify.music"]
+    );
+}
+
+#[test]
+fn test_parse_paths() {
+    let paths = parse_paths(PATH_SPLIT);
+    assert_eq!(paths.len(), 3);
+    assert!(paths[0].ends_with("/base.apk"));
+    assert!(paths[2].ends_with("/split_config.xxhdpi.apk"));
+    assert!(parse_paths("").is_empty());
+}
+
+#[test]
+fn test_commands() {
+    assert_eq!(path_command("com.example"), "pm path com.example");
+    assert_eq!(clear_command("com.example"), "pm clear com.example");
+    assert_eq!(grant_command("com.example", "android.permission.CAMERA"), "pm grant com.example android.permission.CAMERA");
+    assert_eq!(revoke_command("com.example", "android.permission.CAMERA"), "pm revoke com.example android.permission.CAMERA");
+    assert_eq!(uninstall_command("com.example", None), "pm uninstall com.example");
+    assert_eq!(uninstall_command("com.example", Some(0)), "pm uninstall --user 0 com.example");
+    assert_eq!(enable_command("com.example", 10), "pm enable --user 10 com.example");
+    assert_eq!(disable_command("com.example", 0), "pm disable-user --user 0 com.example");
+    assert_eq!(install_existing_command("com.example", 0), "cmd package install-existing --user 0 com.example");
+}
+
+#[test]
+fn test_parse_session_id_fixture() {
+    assert_eq!(parse_session_id(INSTALL_CREATE), Some(1029384756));
+    assert_eq!(parse_session_id("Error: java.lang.IllegalArgumentException"), None);
+}
+
+#[test]
+fn test_parse_success() {
+    assert_eq!(parse_install_output("Performing Streamed Install\nSuccess\n"), InstallOutcome::Success);
+    assert_eq!(parse_install_output("Success: streamed 1024 bytes\n"), InstallOutcome::Success);
+}
+
+#[test]
+fn test_parse_failure() {
+    assert_eq!(
+        parse_install_output(
+            "Failure [INSTALL_FAILED_ALREADY_EXISTS: Attempt to re-install com.example without first uninstalling.]"
+        ),
+        InstallOutcome::Failure {
+            code: Some("INSTALL_FAILED_ALREADY_EXISTS".to_string()),
+            message: "Attempt to re-install com.example without first uninstalling.".to_string(),
+        }
+    );
+    assert_eq!(
+        parse_install_output("\nFailure [INSTALL_FAILED_TEST_ONLY]\n"),
+        InstallOutcome::Failure { code: Some("INSTALL_FAILED_TEST_ONLY".to_string()), message: String::new() }
+    );
+    // Older releases describe some failures in plain words
+    assert_eq!(
+        parse_install_output("Failure [not installed for 0]"),
+        InstallOutcome::Failure { code: None, message: "not installed for 0".to_string() }
+    );
+    assert_eq!(
+        parse_install_output("Error: Unable to open file: /data/local/tmp/x.apk"),
+        InstallOutcome::Failure { code: None, message: "Error: Unable to open file: /data/local/tmp/x.apk".to_string() }
+    );
+}
+
+#[test]
+fn test_failure_hint() {
+    assert!(failure_hint("INSTALL_FAILED_ALREADY_EXISTS").unwrap().contains("--replace"));
+    assert!(failure_hint("INSTALL_FAILED_TEST_ONLY").unwrap().contains("--test"));
+    assert_eq!(failure_hint("INSTALL_FAILED_INTERNAL_ERROR"), None);
+}
+
+#[test]
+fn test_parse_install_fixture() {
+    let outcome = parse_install_output(INSTALL_DOWNGRADE);
+    assert_eq!(
+        outcome.reason().unwrap(),
+        "INSTALL_FAILED_VERSION_DOWNGRADE: Downgrade detected: Update version code 41 is older than current 42"
+    );
+    assert_eq!(InstallOutcome::Success.reason(), None);
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(parse_error(""), None);
+    assert_eq!(parse_error("\n"), None);
+    assert_eq!(
+        parse_error(GRANT_NOT_REQUESTED).unwrap(),
+        "Package com.example.app has not requested permission android.permission.CAMERA"
+    );
+    assert_eq!(parse_error(GRANT_UNKNOWN_PACKAGE).unwrap(), "Unknown package: com.example.missing");
+    assert_eq!(parse_error("Failed\n").unwrap(), "Failed");
+}
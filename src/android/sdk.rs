@@ -0,0 +1,184 @@
+//! What each Android release can do from the shell
+//!
+//! Shell tools gain subcommands between releases, and older devices answer
+//! the new ones with a usage dump or "Unknown command" that means little to
+//! the user. Commands check the device's SDK level against [`Capability`]
+//! first, so they can take a fallback or say which release they need. The
+//! level is read once per device and cached, like its adbd features.
+
+use crate::adb::shell::ShellCommand;
+use crate::core::types::DeviceId;
+use crate::device::extras::PROP_SDK;
+use crate::error::{AimError, Result};
+use lazy_static::lazy_static;
+use log::debug;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Shell features that need a minimum Android release
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    /// `pm grant` / `pm revoke` of runtime permissions
+    RuntimePermissions,
+    /// The `cmd` binary, which talks to system services directly
+    Cmd,
+    /// `cmd jobscheduler run`
+    JobschedulerRun,
+    /// `cmd jobscheduler cancel`
+    JobschedulerCancel,
+    /// Perfetto configs in text format (`perfetto --txt`)
+    PerfettoTextConfig,
+    /// `cmd clipboard`
+    CmdClipboard,
+    /// `cmd wifi` (connect, list and forget networks)
+    CmdWifi,
+    /// System CA certificates read from the Conscrypt APEX, which can't be remounted
+    ApexCaStore,
+}
+
+impl Capability {
+    /// Every capability with the first SDK level that has it
+    pub const TABLE: [(Capability, u32); 8] = [
+        (Capability::RuntimePermissions, 23),
+        (Capability::Cmd, 24),
+        (Capability::JobschedulerRun, 24),
+        (Capability::JobschedulerCancel, 28),
+        (Capability::PerfettoTextConfig, 29),
+        (Capability::CmdClipboard, 29),
+        (Capability::CmdWifi, 30),
+        (Capability::ApexCaStore, 34),
+    ];
+
+    pub fn min_sdk(self) -> u32 {
+        Self::TABLE.iter().find(|(cap, _)| *cap == self).map_or(0, |(_, sdk)| *sdk)
+    }
+
+    /// How the capability is named in errors
+    pub fn name(self) -> &'static str {
+        match self {
+            Capability::RuntimePermissions => "Granting and revoking runtime permissions",
+            Capability::Cmd => "`cmd`",
+            Capability::JobschedulerRun => "`cmd jobscheduler run`",
+            Capability::JobschedulerCancel => "`cmd jobscheduler cancel`",
+            Capability::PerfettoTextConfig => "Tracing with a text config",
+            Capability::CmdClipboard => "`cmd clipboard`",
+            Capability::CmdWifi => "`cmd wifi`",
+            Capability::ApexCaStore => "The Conscrypt APEX trust store",
+        }
+    }
+}
+
+/// Marketing version of an SDK level, e.g. `10` for 29
+pub fn release(sdk: u32) -> Option<&'static str> {
+    let release = match sdk {
+        16 => "4.1",
+        17 => "4.2",
+        18 => "4.3",
+        19 => "4.4",
+        20 => "4.4W",
+        21 => "5.0",
+        22 => "5.1",
+        23 => "6.0",
+        24 => "7.0",
+        25 => "7.1",
+        26 => "8.0",
+        27 => "8.1",
+        28 => "9",
+        29 => "10",
+        30 => "11",
+        31 => "12",
+        32 => "12L",
+        33 => "13",
+        34 => "14",
+        35 => "15",
+        36 => "16",
+        _ => return None,
+    };
+    Some(release)
+}
+
+/// SDK level of a device
+///
+/// A level that could not be read is unknown and allows everything, so
+/// commands try the modern variant as they did before this check existed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sdk {
+    level: Option<u32>,
+}
+
+impl Sdk {
+    /// Parse `getprop ro.build.version.sdk`
+    pub fn parse(output: &str) -> Self {
+        Self { level: output.trim().parse().ok().filter(|level| *level > 0) }
+    }
+
+    #[allow(dead_code)]
+    pub fn known(level: u32) -> Self {
+        Self { level: Some(level) }
+    }
+
+    pub fn unknown() -> Self {
+        Self { level: None }
+    }
+
+    pub fn level(self) -> Option<u32> {
+        self.level
+    }
+
+    /// Whether `cap` may be used: the device is new enough, or its level is unknown
+    pub fn supports(self, cap: Capability) -> bool {
+        self.level.is_none_or(|level| level >= cap.min_sdk())
+    }
+
+    /// Whether the device is known to have `cap`, for changes that are not improvements
+    pub fn known_to_have(self, cap: Capability) -> bool {
+        self.level.is_some_and(|level| level >= cap.min_sdk())
+    }
+
+    /// Why `cap` can't be used on this device, e.g.
+    /// "`cmd wifi` requires Android 11+ (this device runs Android 9, SDK 28)"
+    pub fn missing(self, cap: Capability) -> Option<String> {
+        let level = self.level.filter(|_| !self.supports(cap))?;
+        let needed = release(cap.min_sdk()).map_or_else(|| format!("SDK {}", cap.min_sdk()), |r| format!("Android {}", r));
+        let running = match release(level) {
+            Some(release) => format!("Android {}, SDK {}", release, level),
+            None => format!("SDK {}", level),
+        };
+        Some(format!("{} requires {}+ (this device runs {})", cap.name(), needed, running))
+    }
+
+    /// Fail with [`Sdk::missing`] when `cap` can't be used
+    pub fn require(self, cap: Capability) -> Result<()> {
+        match self.missing(cap) {
+            Some(message) => Err(AimError::CommandExecution(message)),
+            None => Ok(()),
+        }
+    }
+}
+
+lazy_static! {
+    static ref CACHE: Mutex<HashMap<(String, u16, String), Sdk>> = Mutex::new(HashMap::new());
+}
+
+/// SDK level of `device`, read on first use
+pub async fn for_device(host: &str, port: u16, device: &DeviceId) -> Sdk {
+    let key = (host.to_string(), port, device.to_string());
+    if let Some(sdk) = CACHE.lock().unwrap().get(&key) {
+        return *sdk;
+    }
+    let output = ShellCommand::new(format!("getprop {}", PROP_SDK)).with_device(device.clone()).execute(host, port).await;
+    match output.map(|output| Sdk::parse(&output.stdout)) {
+        Ok(sdk) if sdk.level().is_some() => {
+            debug!("SDK level of {}: {:?}", device, sdk.level());
+            CACHE.lock().unwrap().insert(key, sdk);
+            sdk
+        }
+        Ok(_) => Sdk::unknown(),
+        Err(e) => {
+            // Not cached: the device may still be booting
+            debug!("Could not read the SDK level of {}: {}", device, e);
+            Sdk::unknown()
+        }
+    }
+}
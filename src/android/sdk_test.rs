@@ -0,0 +1,54 @@
+use crate::android::sdk::*;
+
+#[test]
+fn test_parse() {
+    assert_eq!(Sdk::parse("34\n").level(), Some(34));
+    assert_eq!(Sdk::parse("").level(), None);
+    assert_eq!(Sdk::parse("error: device offline").level(), None);
+}
+
+#[test]
+fn test_supports() {
+    assert!(Sdk::known(30).supports(Capability::CmdWifi));
+    assert!(!Sdk::known(29).supports(Capability::CmdWifi));
+    assert!(Sdk::known(29).supports(Capability::CmdClipboard));
+    // Unknown levels allow everything, so commands try as before
+    assert!(Sdk::unknown().supports(Capability::ApexCaStore));
+}
+
+#[test]
+fn test_known_to_have() {
+    assert!(Sdk::known(34).known_to_have(Capability::ApexCaStore));
+    assert!(!Sdk::known(33).known_to_have(Capability::ApexCaStore));
+    assert!(!Sdk::unknown().known_to_have(Capability::ApexCaStore));
+}
+
+#[test]
+fn test_missing() {
+    assert_eq!(
+        Sdk::known(28).missing(Capability::CmdWifi).as_deref(),
+        Some("`cmd wifi` requires Android 11+ (this device runs Android 9, SDK 28)")
+    );
+    assert_eq!(
+        Sdk::known(21).missing(Capability::JobschedulerRun).as_deref(),
+        Some("`cmd jobscheduler run` requires Android 7.0+ (this device runs Android 5.0, SDK 21)")
+    );
+    assert_eq!(Sdk::known(30).missing(Capability::CmdWifi), None);
+    assert_eq!(Sdk::unknown().missing(Capability::CmdWifi), None);
+    assert!(Sdk::known(30).require(Capability::CmdWifi).is_ok());
+    assert!(Sdk::known(23).require(Capability::JobschedulerCancel).is_err());
+}
+
+#[test]
+fn test_release() {
+    assert_eq!(release(29), Some("10"));
+    assert_eq!(release(32), Some("12L"));
+    assert_eq!(release(99), None);
+}
+
+#[test]
+fn test_table_is_ordered_by_sdk() {
+    let levels: Vec<u32> = Capability::TABLE.iter().map(|(_, sdk)| *sdk).collect();
+    assert!(levels.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert!(Capability::TABLE.iter().all(|(cap, sdk)| release(*sdk).is_some() && cap.min_sdk() == *sdk));
+}
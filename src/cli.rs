@@ -16,22 +16,70 @@ pub struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
 
-    /// ADB server hostname
-    #[arg(long, global = true, default_value = "localhost")]
-    pub host: String,
+    /// The command line this was parsed from, for `--all` to pass on
+    #[arg(skip)]
+    pub argv: Vec<String>,
+
+    /// Run on every connected device at once (run, getprop, screenshot, push)
+    #[arg(long, global = true)]
+    pub all: bool,
+
+    /// CI mode: no colors, no prompts, line-based progress, stable ordering (also AIM_CI=1)
+    #[arg(long, global = true)]
+    pub ci: bool,
+
+    /// Ask which device or package was meant when an argument matches several
+    #[arg(long, global = true)]
+    pub pick: bool,
+
+    /// Desktop notification when perfetto, pull or soak finishes
+    #[arg(long, global = true)]
+    pub notify: bool,
+
+    /// Format of aim's diagnostic log output
+    #[arg(long, global = true, value_enum, default_value_t = crate::core::logging::LogFormat::Text)]
+    pub log_format: crate::core::logging::LogFormat,
+
+    /// Write diagnostic logs to a file instead of stderr
+    #[arg(long, global = true)]
+    pub log_file: Option<PathBuf>,
+
+    /// Afterwards, show where aim spent its time: connect, handshake, execute, parse, render
+    #[arg(long, global = true)]
+    pub profile_self: bool,
+
+    /// ADB server hostname [default: ADB_SERVER_HOST or localhost]
+    #[arg(long, global = true)]
+    pub host: Option<String>,
 
     /// Output format (table or json)
     #[arg(long, short = 'o', default_value = "table")]
     pub output: OutputType,
 
-    /// ADB server port
-    #[arg(long, short = 'p', global = true, default_value = "5037")]
-    pub port: String,
+    /// ADB server port [default: ADB_SERVER_PORT or 5037]
+    #[arg(long, short = 'p', global = true)]
+    pub port: Option<u16>,
+
+    /// Reach the ADB server through an SSH tunnel to user@host
+    #[arg(long, global = true, value_name = "USER@HOST")]
+    pub via_ssh: Option<String>,
+
+    /// Server profile from the config file ([profile.<name>])
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
 
     /// Connection timeout in seconds
     #[arg(long, global = true, default_value_t = 5)]
     pub timeout: u8,
 
+    /// How times are shown: iso, relative or epoch
+    #[arg(long, global = true, value_enum)]
+    pub time_format: Option<crate::output::time::TimeFormat>,
+
+    /// Time zone for ISO times: host, device or utc
+    #[arg(long, global = true, value_enum, default_value_t = crate::output::time::Zone::Host)]
+    pub timezone: crate::output::time::Zone,
+
     /// Verbosity level
     #[command(flatten)]
     pub verbose: clap_verbosity_flag::Verbosity,
@@ -39,22 +87,99 @@ pub struct Cli {
 
 #[derive(Subcommand, Clone, Debug)]
 pub enum Commands {
+    /// Turn accessibility services on and off
+    A11y {
+        #[command(subcommand)]
+        command: crate::commands::a11y::A11yCommands,
+    },
+
     /// Run arbitrary adb commands
     #[command(name = "adb")]
     Adb {
-        #[arg(help = "ADB command to run")]
-        command: String,
+        /// Arguments for adb, e.g. `shell ls /sdcard` or `install app.apk`
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true, required = true)]
+        command: Vec<String>,
 
-        #[arg(help = "Device ID")]
+        /// Device ID or alias, passed to adb as `-s <serial>`
+        #[arg(short = 'd', long = "device")]
         device_id: Option<String>,
     },
 
+    /// Inspect activities
+    Activity {
+        #[command(subcommand)]
+        command: crate::commands::activity::ActivityCommands,
+    },
+
     /// Application management commands
     App {
         #[command(subcommand)]
         command: crate::commands::app::AppCommands,
     },
 
+    /// Battery diagnostics
+    Battery {
+        #[command(subcommand)]
+        command: crate::commands::battery::BatteryCommands,
+    },
+
+    /// Boot analysis
+    Boot {
+        #[command(subcommand)]
+        command: crate::commands::boot::BootCommands,
+    },
+
+    /// Recent broadcasts with their sender and receivers
+    Broadcasts {
+        /// Only broadcasts sent by, delivered to or aimed at this package (supports partial matching)
+        #[arg(long)]
+        package: Option<String>,
+
+        /// Keep running and print new broadcasts as they are sent
+        #[arg(short, long)]
+        follow: bool,
+
+        /// How often to check for new broadcasts with --follow
+        #[arg(long, default_value = "1s", value_parser = crate::utils::parse_duration)]
+        interval: std::time::Duration,
+
+        /// Device ID (required if multiple devices are connected)
+        #[arg(short = 'd', long = "device")]
+        device_id: Option<String>,
+    },
+
+    /// CA certificate management
+    Cert {
+        #[command(subcommand)]
+        command: crate::commands::cert::CertCommands,
+    },
+
+    /// Free up storage on the device
+    Clean {
+        /// Device ID (required if multiple devices are connected)
+        device_id: Option<String>,
+
+        /// Trim app caches
+        #[arg(long)]
+        caches: bool,
+
+        /// Remove leftovers in /data/local/tmp
+        #[arg(long)]
+        tmp: bool,
+
+        /// Remove stray APKs in /sdcard/Download
+        #[arg(long)]
+        apks: bool,
+
+        /// Show what would be removed without removing anything
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
     /// Display configuration
     Config,
 
@@ -67,16 +192,80 @@ pub enum Commands {
         dst: String,
     },
 
+    /// Interactive dashboard: devices, live log, app CPU/memory and quick actions
+    Dash {
+        /// Device to select first (can be partial)
+        device_id: Option<String>,
+        /// Package whose CPU and memory use is charted, and whose data `c` clears
+        #[arg(short = 'P', long)]
+        package: Option<String>,
+        /// How often CPU and memory are sampled
+        #[arg(long, value_parser = crate::utils::parse_duration, default_value = "2s")]
+        interval: std::time::Duration,
+    },
+
+    /// Reverse dev-server ports to devices and keep them in place
+    Dev {
+        /// Port to reverse from the device to the same port on this machine (repeatable)
+        #[arg(long = "reverse-port", required = true)]
+        ports: Vec<u16>,
+        /// Device ID (repeatable); every connected device if omitted
+        #[arg(short = 'd', long = "device")]
+        devices: Vec<String>,
+        /// How often to check that the reverses are still in place
+        #[arg(long, value_parser = crate::utils::parse_duration, default_value = "2s")]
+        interval: std::time::Duration,
+    },
+
+    /// Find devices advertising wireless debugging on the LAN
+    Discover {
+        /// Connect to a discovered device by alias, serial, mDNS name or address
+        #[arg(long, value_name = "NAME")]
+        connect: Option<String>,
+    },
+
     /// Run dmesg command on the device
     Dmesg {
         /// Device ID to target (required if multiple devices are connected)
         device_id: Option<String>,
 
+        /// Show wall-clock times in logcat's format instead of seconds since boot
+        #[arg(long)]
+        human_time: bool,
+
         /// Additional arguments to pass to dmesg
         #[arg(last = true)]
         args: Vec<String>,
     },
 
+    /// Device owner provisioning through `dpm`
+    Dpm {
+        #[command(subcommand)]
+        command: crate::commands::dpm::DpmCommands,
+    },
+
+    /// Commands that operate on every connected device
+    Fleet {
+        #[command(subcommand)]
+        command: crate::commands::fleet::FleetCommands,
+    },
+
+    /// Forward host sockets to the device, or list and remove forwards
+    #[command(args_conflicts_with_subcommands = true)]
+    Forward {
+        #[command(subcommand)]
+        command: Option<crate::commands::forward::ForwardCommands>,
+
+        #[command(flatten)]
+        args: crate::commands::forward::ForwardArgs,
+    },
+
+    /// Device filesystem tools
+    Fs {
+        #[command(subcommand)]
+        command: crate::commands::fs::FsCommands,
+    },
+
     /// Get device properties
     Getprop {
         /// Comma-separated list of property names to query. If empty, all properties will be shown
@@ -89,6 +278,63 @@ pub enum Commands {
         /// Output format
         #[arg(short, long, value_enum, default_value_t = OutputType::Plain)]
         output: OutputType,
+
+        /// Show values cached from an earlier run instead of querying the device
+        #[arg(long)]
+        cached: bool,
+
+        /// Save the properties to a file (JSON) instead of printing them
+        #[arg(long, value_name = "FILE", conflicts_with = "compare")]
+        export: Option<std::path::PathBuf>,
+
+        /// Compare the properties with a file written by --export
+        #[arg(long, value_name = "FILE")]
+        compare: Option<std::path::PathBuf>,
+
+        /// With --compare, exit with an error if a difference is at least this severe
+        #[arg(long, value_enum, value_name = "SEVERITY", requires = "compare")]
+        fail_on: Option<crate::commands::getprop::Severity>,
+    },
+
+    /// List and switch input methods, and type text
+    Ime {
+        #[command(subcommand)]
+        command: crate::commands::ime::ImeCommands,
+    },
+
+    /// Send intents to apps
+    Intent {
+        #[command(subcommand)]
+        command: crate::commands::intent::IntentCommands,
+    },
+
+    /// Pin an app to the screen for demo devices, or `aim kiosk off` to unpin
+    Kiosk {
+        /// Package to pin (supports partial matching), or `off` to unpin
+        package: String,
+
+        /// Device ID (required if multiple devices are connected)
+        #[arg(short = 'd', long = "device")]
+        device_id: Option<String>,
+    },
+
+    /// Stream the device log, colored by level
+    Logcat {
+        /// Only show this tag (repeatable); TAG:W sets its own minimum level
+        #[arg(short, long = "tag")]
+        tags: Vec<String>,
+
+        /// Minimum level to show: verbose, debug, info, warn, error or fatal (or V, D, I, W, E, F)
+        #[arg(long, value_enum, ignore_case = true, default_value = "verbose")]
+        priority: crate::commands::logcat::Priority,
+
+        /// Clear the log buffers before streaming
+        #[arg(short, long)]
+        clear: bool,
+
+        /// Device ID (required if multiple devices are connected)
+        #[arg(short = 'd', long = "device")]
+        device_id: Option<String>,
     },
 
     /// Lists connected devices
@@ -96,6 +342,62 @@ pub enum Commands {
         /// Output format (table, json, or plain)
         #[arg(short = 'o', long, value_enum, default_value_t = OutputType::Table)]
         output: OutputType,
+
+        /// Include every device seen before, not just connected ones
+        #[arg(long)]
+        known: bool,
+
+        /// List devices from the local cache without querying adb
+        #[arg(long)]
+        cached: bool,
+
+        /// Long listing with battery, Android version, security patch and Wi-Fi IP
+        #[arg(short, long)]
+        long: bool,
+
+        /// Extra columns for the long listing (implies --long)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        columns: Vec<crate::commands::ls::LsColumn>,
+
+        /// Only show devices in these states (device, offline, unauthorized)
+        #[arg(long, value_parser = crate::output::device::parse_device_state, value_delimiter = ',')]
+        state: Vec<crate::core::types::DeviceState>,
+
+        /// Only show devices connected over USB
+        #[arg(long, conflicts_with = "wireless")]
+        usb: bool,
+
+        /// Only show devices connected over the network
+        #[arg(long)]
+        wireless: bool,
+
+        /// Sort the list
+        #[arg(long, value_enum)]
+        sort: Option<crate::output::device::DeviceSort>,
+
+        /// Keep the table on screen, redrawn whenever devices change
+        #[arg(short, long, conflicts_with_all = ["known", "cached", "long", "columns"])]
+        watch: bool,
+
+        /// How often to redraw in watch mode when nothing changes
+        #[arg(long, value_parser = crate::utils::parse_duration, default_value = "2s")]
+        interval: std::time::Duration,
+    },
+
+    /// Pair with a device for wireless debugging
+    Pair {
+        /// Pairing address shown on the phone (ip:port)
+        #[arg(conflicts_with = "qr", required_unless_present = "qr")]
+        address: Option<String>,
+        /// Six-digit pairing code shown on the phone
+        #[arg(requires = "address")]
+        code: Option<String>,
+        /// Show a QR code to scan from "Pair device with QR code" instead
+        #[arg(long)]
+        qr: bool,
+        /// How long to wait for the phone to scan the code
+        #[arg(long, value_parser = crate::utils::parse_duration, default_value = "2m")]
+        wait: std::time::Duration,
     },
 
     /// Run perfetto trace
@@ -114,6 +416,57 @@ pub enum Commands {
         /// Time to run trace in seconds (if not specified, runs until 'q' is pressed)
         #[arg(short = 't', long = "time")]
         time: Option<u32>,
+
+        /// Print top CPU consumers, jank and app startups once the trace is pulled
+        #[arg(long)]
+        summary: bool,
+
+        /// Upload the result as set up in the [upload] config section
+        #[arg(long)]
+        upload: bool,
+    },
+
+    /// Factory reset, skip the setup wizard and run a setup script in one go
+    Provision {
+        /// aim script with the apps, files and settings to set up
+        #[arg(long)]
+        script: Option<PathBuf>,
+
+        /// Variable for the script (NAME=value, repeatable)
+        #[arg(long = "var", value_parser = crate::commands::script::parse_var)]
+        vars: Vec<(String, String)>,
+
+        /// Factory reset the device first (needs root)
+        #[arg(long)]
+        factory_reset: bool,
+
+        /// Mark setup as complete and disable the setup wizard
+        #[arg(long)]
+        skip_setup_wizard: bool,
+
+        /// Skip the factory reset confirmation
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// How long to wait for the device to come back after the reset
+        #[arg(long, default_value = "10m", value_parser = crate::utils::parse_duration)]
+        boot_timeout: std::time::Duration,
+
+        /// Device ID (required if multiple devices are connected)
+        #[arg(short = 'd', long = "device")]
+        device_id: Option<String>,
+    },
+
+    /// Reconnect wireless devices that dropped off
+    Reconnect {
+        /// Only reconnect this device (serial, partial serial or alias)
+        device_id: Option<String>,
+        /// Keep running and reconnect devices whenever they come back
+        #[arg(short, long)]
+        watch: bool,
+        /// How often to check for missing devices in watch mode
+        #[arg(long, value_parser = crate::utils::parse_duration, default_value = "5s")]
+        interval: std::time::Duration,
     },
 
     /// Rename a device
@@ -136,6 +489,26 @@ pub enum Commands {
         /// Watch mode - repeat command every second. Optional value specifies duration in seconds
         #[arg(short = 'w', long = "watch", num_args = 0..=1, default_missing_value = "0")]
         watch: Option<u32>,
+        /// Run as root through su (or adbd, if it already runs as root)
+        #[arg(long)]
+        as_root: bool,
+        /// Stop the command if it runs longer than this, e.g. 30s (exit status 124)
+        #[arg(long, value_parser = crate::utils::parse_duration)]
+        kill_after: Option<std::time::Duration>,
+    },
+
+    /// Run a Rhai script with access to devices
+    RunScript {
+        /// Rhai script to run
+        script: PathBuf,
+
+        /// Arguments for the script, available as `args`
+        #[arg(trailing_var_arg = true)]
+        args: Vec<String>,
+
+        /// Device ID for device(), shell(), getprop() and the transfer functions
+        #[arg(short = 'd', long = "device")]
+        device_id: Option<String>,
     },
 
     /// Record screen
@@ -147,11 +520,57 @@ pub enum Commands {
         #[arg(short = 'o', long = "output")]
         output: Option<PathBuf>,
 
+        /// Also save logcat for the recording window next to the video
+        #[arg(long)]
+        with_logs: bool,
+
+        /// Only keep log lines from this app (can be partial)
+        #[arg(long, requires = "with_logs")]
+        package: Option<String>,
+
+        /// File name template, e.g. "{alias}-{ts}" (see docs for the variables)
+        #[arg(long, value_name = "TEMPLATE")]
+        name_template: Option<String>,
+
+        /// Upload the result as set up in the [upload] config section
+        #[arg(long)]
+        upload: bool,
+
         /// Additional arguments to pass to screenrecord
         #[arg(last = true)]
         args: Vec<String>,
     },
 
+    /// Reboot repeatedly and check the device comes back healthy each time
+    Soak {
+        /// Optional device ID (can be partial)
+        device_id: Option<String>,
+
+        /// Number of reboots
+        #[arg(long, default_value_t = 10)]
+        reboots: u32,
+
+        /// Shell command to run after each boot; a non-zero exit fails the iteration
+        #[arg(long)]
+        between: Option<String>,
+
+        /// Property that must have this value after each boot, as NAME=VALUE (repeatable)
+        #[arg(long = "expect-prop", value_parser = crate::commands::soak::parse_prop_expectation)]
+        expect_props: Vec<(String, String)>,
+
+        /// Package that must be installed after each boot (repeatable)
+        #[arg(long = "expect-package")]
+        expect_packages: Vec<String>,
+
+        /// Fail an iteration if boot takes longer than this
+        #[arg(long, default_value = "5m", value_parser = crate::utils::parse_duration)]
+        boot_timeout: std::time::Duration,
+
+        /// Stop at the first failed iteration
+        #[arg(long)]
+        stop_on_failure: bool,
+    },
+
     /// Take a screenshot
     Screenshot {
         /// Additional arguments to pass to screencap
@@ -168,6 +587,87 @@ pub enum Commands {
         /// Output file location (overrides default location)
         #[arg(short = 'o', long = "output")]
         output: Option<PathBuf>,
+
+        /// Keep only this region of the screen, as WxH+X+Y in pixels
+        #[arg(long, value_parser = crate::commands::screenshot::parse_crop)]
+        crop: Option<crate::commands::screenshot::Crop>,
+
+        /// Resize the result, e.g. 50%
+        #[arg(long, value_parser = crate::commands::screenshot::parse_scale)]
+        scale: Option<f32>,
+
+        /// Draw a label and the device name onto the screenshot
+        #[arg(long, value_name = "TEXT")]
+        annotate: Option<String>,
+
+        /// Draw the device name and capture time onto the screenshot
+        #[arg(long)]
+        timestamp: bool,
+
+        /// Take a numbered series, one screenshot per interval (e.g. 2s)
+        #[arg(long, value_parser = crate::utils::parse_duration)]
+        every: Option<std::time::Duration>,
+
+        /// Stop the series after this many screenshots
+        #[arg(long, requires = "every")]
+        count: Option<u32>,
+
+        /// Stop the series after this long (e.g. 1m)
+        #[arg(long, requires = "every", value_parser = crate::utils::parse_duration)]
+        duration: Option<std::time::Duration>,
+
+        /// In interactive mode, SPACE keeps capturing until the next key press
+        #[arg(long, requires = "interactive")]
+        until_key: bool,
+
+        /// File name template, e.g. "{alias}-{ts}-{counter}" (see docs for the variables)
+        #[arg(long, value_name = "TEMPLATE")]
+        name_template: Option<String>,
+
+        /// Upload the result as set up in the [upload] config section
+        #[arg(long)]
+        upload: bool,
+    },
+
+    /// Run a sequence of aim commands from a file or -c
+    Script {
+        /// Script file with one aim command per line
+        #[arg(conflicts_with = "command", required_unless_present = "command")]
+        file: Option<PathBuf>,
+
+        /// Commands to run, separated by `;`, instead of a file
+        #[arg(short = 'c', long = "command")]
+        command: Option<String>,
+
+        /// Variable for the script (NAME=value, repeatable)
+        #[arg(long = "var", value_parser = crate::commands::script::parse_var)]
+        vars: Vec<(String, String)>,
+
+        /// Device ID used by every command (required if multiple devices are connected)
+        #[arg(short = 'd', long = "device")]
+        device_id: Option<String>,
+    },
+
+    /// Send text to the focused field, pasting it through the clipboard when possible
+    SendText {
+        /// Text to send, a file to send the contents of, or `-` for stdin
+        input: String,
+
+        /// Send the argument as text even if a file has that name
+        #[arg(long)]
+        literal: bool,
+
+        /// How to get the text into the field
+        #[arg(long, value_enum, default_value_t = crate::commands::send_text::SendMethod::Auto)]
+        method: crate::commands::send_text::SendMethod,
+
+        /// Characters per clipboard paste
+        #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u32).range(1..))]
+        chunk_size: u32,
+
+        /// Device ID (required if multiple devices are connected)
+        #[arg(short = 'd', long = "device")]
+        device_id: Option<String>,
     },
 
     /// Manage ADB server
@@ -185,6 +685,46 @@ pub enum Commands {
         /// Device ID (required if multiple devices are connected)
         #[arg(short = 'd', long = "device")]
         device_id: Option<String>,
+        /// Stop the command if it runs longer than this, e.g. 30s (exit status 124)
+        #[arg(long, value_parser = crate::utils::parse_duration, requires = "command")]
+        kill_after: Option<std::time::Duration>,
+        /// Record the interactive session to an asciinema v2 file (.cast)
+        #[arg(long, value_name = "FILE", conflicts_with = "command")]
+        record: Option<PathBuf>,
+    },
+
+    /// Sync a local directory with a directory on the device
+    Sync {
+        /// Local directory
+        local: PathBuf,
+        /// Directory on the device
+        remote: String,
+        /// Device ID (required if multiple devices are connected)
+        device_id: Option<String>,
+        /// Copy newer files in both directions and report conflicts
+        #[arg(long)]
+        bidirectional: bool,
+        /// Show what would be copied without copying anything
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
+
+    /// Work with native crash tombstones
+    Tombstone {
+        #[command(subcommand)]
+        command: crate::commands::tombstone::TombstoneCommands,
+    },
+
+    /// Font scale and display size, for testing layouts at other text sizes
+    Ui {
+        #[command(subcommand)]
+        command: crate::commands::ui::UiCommands,
+    },
+
+    /// Join, inspect and forget Wi-Fi networks
+    Wifi {
+        #[command(subcommand)]
+        command: crate::commands::wifi::WifiCommands,
     },
 
     /// Push files to device
@@ -195,24 +735,44 @@ pub enum Commands {
         /// Remote destination path on device
         dst: String,
         /// Device ID (required if multiple devices are connected)
+        #[arg(short = 'd', long = "device")]
         device_id: Option<String>,
         /// Recursive push (for directories)
         #[arg(short, long)]
         recursive: bool,
+        /// Fixed DATA chunk size (e.g. 16K, 64K) instead of auto-tuning
+        #[arg(long, value_parser = crate::commands::push::parse_chunk_size)]
+        chunk_size: Option<usize>,
+        /// Keep running and push files again whenever they change locally
+        #[arg(short, long)]
+        watch: bool,
+        /// Device shell command to run after each push in watch mode
+        #[arg(long, requires = "watch")]
+        exec: Option<String>,
+        /// Quiet time to wait for before pushing a burst of changes
+        #[arg(long, value_parser = crate::utils::parse_duration, default_value = "300ms")]
+        debounce: std::time::Duration,
     },
 
     /// Pull files from device
     Pull {
-        /// Remote file(s) on device to pull
-        #[arg(required = true)]
-        src: Vec<String>,
-        /// Local destination path
-        #[arg(default_value = ".")]
-        dst: PathBuf,
+        /// Remote file(s) on device to pull, then the local destination [default: .]
+        #[arg(required = true, value_name = "SRC... [DST]")]
+        paths: Vec<String>,
         /// Device ID (required if multiple devices are connected)
+        #[arg(short = 'd', long = "device")]
         device_id: Option<String>,
+        /// Keep running and pull new or changed files as they appear
+        #[arg(short, long)]
+        watch: bool,
+        /// How often to check the device for changes in watch mode
+        #[arg(long, value_parser = crate::utils::parse_duration, default_value = "2s")]
+        interval: std::time::Duration,
     },
 
+    /// Any other command runs an `aim-<name>` executable from PATH
+    #[command(external_subcommand)]
+    External(Vec<String>),
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -221,12 +781,23 @@ pub enum ServerOperation {
     Stop,
     Restart,
     Status,
+    Capabilities,
 }
 
 impl Cli {
     pub fn command(&self) -> Commands {
         self.command.clone().unwrap_or(Commands::Ls {
             output: OutputType::Table,
+            known: false,
+            cached: false,
+            long: false,
+            columns: Vec::new(),
+            state: Vec::new(),
+            usb: false,
+            wireless: false,
+            sort: None,
+            watch: false,
+            interval: std::time::Duration::from_secs(2),
         })
     }
 }
@@ -0,0 +1,110 @@
+use crate::cli::{Cli, Commands};
+use crate::commands::app::AppCommands;
+use clap::{CommandFactory, Parser};
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[test]
+fn test_cli_definition() {
+    <Cli as CommandFactory>::command().debug_assert();
+}
+
+/// `debug_assert` lets a subcommand reuse a global arg id; clap then panics
+/// when the command is parsed, as both read the same value with different types
+#[test]
+fn test_no_global_id_reused() {
+    fn walk(command: &clap::Command, globals: &[String], path: &str, clashes: &mut Vec<String>) {
+        for arg in command.get_arguments().filter(|a| !a.is_global_set()) {
+            if globals.iter().any(|id| id == arg.get_id().as_str()) {
+                clashes.push(format!("{} {}", path, arg.get_id()));
+            }
+        }
+        for sub in command.get_subcommands() {
+            walk(sub, globals, &format!("{} {}", path, sub.get_name()), clashes);
+        }
+    }
+
+    let mut command = <Cli as CommandFactory>::command();
+    command.build();
+    let globals: Vec<String> = command
+        .get_arguments()
+        .filter(|a| a.is_global_set())
+        .map(|a| a.get_id().to_string())
+        .collect();
+    let mut clashes = Vec::new();
+    for sub in command.get_subcommands() {
+        walk(sub, &globals, sub.get_name(), &mut clashes);
+    }
+    assert!(clashes.is_empty(), "subcommand args reusing a global id: {}", clashes.join(", "));
+}
+
+#[test]
+fn test_parse_run() {
+    let cli = Cli::try_parse_from(["aim", "--timeout", "3", "run", "logcat -d", "--kill-after", "10s"]).unwrap();
+    match cli.command() {
+        Commands::Run { command, kill_after, .. } => {
+            assert_eq!(command, "logcat -d");
+            assert_eq!(kill_after, Some(Duration::from_secs(10)));
+        }
+        other => panic!("parsed as {:?}", other),
+    }
+    assert!(Cli::try_parse_from(["aim", "run", "ls"]).is_ok());
+}
+
+#[test]
+fn test_parse_shell() {
+    let cli = Cli::try_parse_from(["aim", "shell", "--kill-after", "30s", "dumpsys"]).unwrap();
+    match cli.command() {
+        Commands::Shell { command, kill_after, .. } => {
+            assert_eq!(command, vec!["dumpsys"]);
+            assert_eq!(kill_after, Some(Duration::from_secs(30)));
+        }
+        other => panic!("parsed as {:?}", other),
+    }
+    assert!(Cli::try_parse_from(["aim", "--timeout", "3", "shell"]).is_ok());
+}
+
+#[test]
+fn test_parse_app_debloat() {
+    let cli = Cli::try_parse_from(["aim", "--profile", "lab", "app", "debloat", "--list", "p.toml"]).unwrap();
+    match cli.command() {
+        Commands::App { command: AppCommands::Debloat(args) } => {
+            assert_eq!(args.profile_file, Some(PathBuf::from("p.toml")));
+        }
+        other => panic!("parsed as {:?}", other),
+    }
+    assert_eq!(cli.profile.as_deref(), Some("lab"));
+}
+
+#[test]
+fn test_parse_app_grant_all_requested() {
+    for (name, grant) in [("grant", true), ("revoke", false)] {
+        let cli = Cli::try_parse_from(["aim", "app", name, "example", "--all-requested"]).unwrap();
+        // Not the global --all, which would send the command to every device
+        assert!(!cli.all, "{} --all-requested set --all", name);
+        let args = match (cli.command(), grant) {
+            (Commands::App { command: AppCommands::Grant(args) }, true) => args,
+            (Commands::App { command: AppCommands::Revoke(args) }, false) => args,
+            (other, _) => panic!("{} parsed as {:?}", name, other),
+        };
+        assert_eq!(args.package, "example");
+        assert!(args.all_requested);
+        assert!(args.permissions.is_empty());
+
+        assert!(Cli::try_parse_from(["aim", "app", name, "example"]).is_err());
+        assert!(Cli::try_parse_from(["aim", "app", name, "example", "CAMERA", "--all-requested"]).is_err());
+    }
+}
+
+#[test]
+fn test_dispatch_app_grant_all() {
+    // The global --all still reaches the fan-out check, which turns app commands down
+    for name in ["grant", "revoke"] {
+        let cli = Cli::try_parse_from(["aim", "--all", "app", name, "example", "CAMERA"]).unwrap();
+        assert!(cli.all);
+        assert!(crate::core::fanout::check(&cli.command()).is_err());
+
+        let cli = Cli::try_parse_from(["aim", "app", name, "example", "--all-requested"]).unwrap();
+        assert!(!cli.all);
+    }
+}
@@ -0,0 +1,334 @@
+use crate::adb::shell::{ShellBatch, ShellCommand};
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::{DeviceId, OutputFormat};
+use crate::error::{AimError, Result};
+use crate::output::TableFormat;
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use clap::Subcommand;
+use colored::*;
+use serde::Serialize;
+
+const ENABLED_SERVICES: &str = "enabled_accessibility_services";
+
+/// TalkBack on Google builds and on Samsung builds
+pub const TALKBACK_SERVICES: &[&str] = &[
+    "com.google.android.marvin.talkback/com.google.android.marvin.talkback.TalkBackService",
+    "com.samsung.android.accessibility.talkback/com.samsung.android.marvin.talkback.TalkBackService",
+];
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum A11yCommands {
+    /// List installed accessibility services and whether they are on
+    #[command(alias = "ls")]
+    List(ListArgs),
+    /// Turn on an accessibility service
+    Enable(ToggleArgs),
+    /// Turn off an accessibility service
+    Disable(ToggleArgs),
+    /// Turn TalkBack on or off
+    Talkback(TalkbackArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: A11yCommands) -> Result<()> {
+    match cmd {
+        A11yCommands::List(args) => {
+            let cmd = ListCommand::new();
+            cmd.run(ctx, args).await
+        }
+        A11yCommands::Enable(args) => {
+            let cmd = ToggleCommand::new(true);
+            cmd.run(ctx, args).await
+        }
+        A11yCommands::Disable(args) => {
+            let cmd = ToggleCommand::new(false);
+            cmd.run(ctx, args).await
+        }
+        A11yCommands::Talkback(args) => {
+            let cmd = TalkbackCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ListArgs {
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ToggleArgs {
+    /// Service as <package>/<service>, or part of an installed service's name
+    pub service: String,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Switch {
+    On,
+    Off,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct TalkbackArgs {
+    #[arg(value_enum)]
+    pub state: Switch,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// An installed accessibility service
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct A11yService {
+    pub service: String,
+    pub enabled: bool,
+}
+
+impl TableFormat for A11yService {
+    fn headers() -> Vec<&'static str> {
+        vec!["SERVICE", "ENABLED"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.service.clone(), if self.enabled { "yes" } else { "no" }.to_string()]
+    }
+}
+
+/// `pkg/.Service` spelled out as `pkg/pkg.Service`, so both forms compare equal
+pub fn normalize_component(component: &str) -> String {
+    match component.split_once('/') {
+        Some((package, class)) if class.starts_with('.') => format!("{}/{}{}", package, package, class),
+        _ => component.to_string(),
+    }
+}
+
+/// Services in the colon separated `enabled_accessibility_services` setting
+pub fn parse_enabled(value: &str) -> Vec<String> {
+    let value = value.trim();
+    if value == "null" {
+        return Vec::new();
+    }
+    value
+        .split(':')
+        .map(str::trim)
+        .filter(|s| s.contains('/'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// The setting with `service` added or removed, keeping the other services as they are
+pub fn merge_enabled(current: &[String], service: &str, enable: bool) -> Vec<String> {
+    let service = normalize_component(service);
+    let mut merged: Vec<String> = Vec::new();
+    for existing in current {
+        let same = normalize_component(existing) == service;
+        if (same && !enable) || merged.iter().any(|m| normalize_component(m) == normalize_component(existing)) {
+            continue;
+        }
+        merged.push(existing.clone());
+    }
+    if enable && !merged.iter().any(|m| normalize_component(m) == service) {
+        merged.push(service);
+    }
+    merged
+}
+
+/// Accessibility services from `cmd package query-services --brief`
+pub fn parse_installed(output: &str) -> Vec<String> {
+    let mut services: Vec<String> = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.contains('/') && !line.contains(' ') && !line.contains('='))
+        .map(normalize_component)
+        .collect();
+    services.sort();
+    services.dedup();
+    services
+}
+
+/// The installed service meant by `query`: a full component or part of one
+pub fn match_service(installed: &[String], query: &str) -> Result<String> {
+    if query.contains('/') {
+        return Ok(normalize_component(query));
+    }
+    let query = query.to_lowercase();
+    let matches: Vec<&String> = installed.iter().filter(|s| s.to_lowercase().contains(&query)).collect();
+    match matches.as_slice() {
+        [service] => Ok((*service).clone()),
+        [] => Err(AimError::InvalidArgument(format!(
+            "No installed accessibility service matches '{}' (see aim a11y list)",
+            query
+        ))),
+        _ => Err(AimError::InvalidArgument(format!(
+            "'{}' matches several services: {}",
+            query,
+            matches.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
+        ))),
+    }
+}
+
+/// Installed services and the current value of the setting
+async fn read_state(host: &str, port: u16, device: &DeviceId) -> Result<(Vec<String>, Vec<String>)> {
+    let outputs = ShellBatch::new()
+        .with_device(device.clone())
+        .command("cmd package query-services --brief -a android.accessibilityservice.AccessibilityService")
+        .command(format!("settings get secure {}", ENABLED_SERVICES))
+        .execute(host, port)
+        .await?;
+    let output = |i: usize| outputs.get(i).map(|o| o.stdout.as_str()).unwrap_or_default();
+    Ok((parse_installed(output(0)), parse_enabled(output(1))))
+}
+
+/// Write the setting back and keep `accessibility_enabled` in step with it
+async fn write_enabled(host: &str, port: u16, device: &DeviceId, services: &[String]) -> Result<()> {
+    let command = if services.is_empty() {
+        format!("settings delete secure {} && settings put secure accessibility_enabled 0", ENABLED_SERVICES)
+    } else {
+        format!(
+            "settings put secure {} {} && settings put secure accessibility_enabled 1",
+            ENABLED_SERVICES,
+            shell_quote(&services.join(":"))
+        )
+    };
+    let output = ShellCommand::new(format!("{} 2>&1", command))
+        .with_device(device.clone())
+        .execute(host, port)
+        .await?;
+    let output = output.stdout.trim();
+    if output.contains("Exception") || output.contains("Error") {
+        return Err(AimError::CommandExecution(format!("Could not change {}: {}", ENABLED_SERVICES, output)));
+    }
+    Ok(())
+}
+
+async fn toggle(ctx: &CommandContext, device: &DeviceId, service: &str, enable: bool) -> Result<()> {
+    let (host, port) = ctx.adb_address();
+    let (_, current) = read_state(host, port, device).await?;
+    let merged = merge_enabled(&current, service, enable);
+    let was_enabled = current.iter().any(|s| normalize_component(s) == normalize_component(service));
+
+    if was_enabled != enable {
+        write_enabled(host, port, device, &merged).await?;
+    }
+    if !ctx.quiet {
+        let state = if enable { "on".green() } else { "off".yellow() };
+        let note = if was_enabled == enable { " (already)" } else { "" };
+        println!("{} {} is {}{}", "✓".green(), service.bright_cyan(), state, note.dimmed());
+    }
+    Ok(())
+}
+
+pub struct ListCommand;
+
+impl ListCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for ListCommand {
+    type Args = ListArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let (installed, enabled) = read_state(host, port, &device.id).await?;
+        let enabled: Vec<String> = enabled.iter().map(|s| normalize_component(s)).collect();
+
+        let mut services: Vec<A11yService> = installed
+            .iter()
+            .map(|s| A11yService { service: s.clone(), enabled: enabled.contains(s) })
+            .collect();
+        // Enabled services whose app is gone still sit in the setting
+        for service in enabled.iter().filter(|s| !installed.contains(s)) {
+            services.push(A11yService { service: service.clone(), enabled: true });
+        }
+
+        match ctx.output_format {
+            OutputFormat::Json => crate::commands::format_json_output(&services)?,
+            OutputFormat::Plain => {
+                for service in &services {
+                    println!("{}\t{}", service.service, if service.enabled { "on" } else { "off" });
+                }
+            }
+            OutputFormat::Table => {
+                if services.is_empty() {
+                    println!("No accessibility services installed");
+                } else {
+                    crate::output::OutputFormatter::new().table(&services)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct ToggleCommand {
+    enable: bool,
+}
+
+impl ToggleCommand {
+    pub fn new(enable: bool) -> Self {
+        Self { enable }
+    }
+}
+
+#[async_trait]
+impl SubCommand for ToggleCommand {
+    type Args = ToggleArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let (installed, _) = read_state(host, port, &device.id).await?;
+        let service = match_service(&installed, &args.service)?;
+        toggle(ctx, &device.id, &service, self.enable).await
+    }
+}
+
+pub struct TalkbackCommand;
+
+impl TalkbackCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for TalkbackCommand {
+    type Args = TalkbackArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let (installed, enabled) = read_state(host, port, &device.id).await?;
+        let is_talkback = |s: &String| TALKBACK_SERVICES.contains(&normalize_component(s).as_str());
+
+        let service = match args.state {
+            // Turn off whichever TalkBack is on, even if it is no longer installed
+            Switch::Off => enabled.iter().find(|s| is_talkback(s)).map(|s| normalize_component(s)),
+            Switch::On => installed.iter().find(|s| is_talkback(s)).cloned(),
+        };
+        match (service, args.state) {
+            (Some(service), state) => toggle(ctx, &device.id, &service, state == Switch::On).await,
+            (None, Switch::Off) => {
+                if !ctx.quiet {
+                    println!("{} TalkBack is {}", "✓".green(), "off".yellow());
+                }
+                Ok(())
+            }
+            (None, Switch::On) => Err(AimError::CommandExecution(format!(
+                "TalkBack is not installed on {} (install com.google.android.marvin.talkback)",
+                device.id
+            ))),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use super::a11y::{match_service, merge_enabled, normalize_component, parse_enabled, parse_installed};
+
+const TALKBACK: &str = "com.google.android.marvin.talkback/com.google.android.marvin.talkback.TalkBackService";
+const SWITCH_ACCESS: &str = "com.google.android.accessibility.switchaccess/com.android.switchaccess.SwitchAccessService";
+
+#[test]
+fn test_normalize_component() {
+    assert_eq!(
+        normalize_component("com.google.android.marvin.talkback/.TalkBackService"),
+        TALKBACK
+    );
+    assert_eq!(normalize_component(TALKBACK), TALKBACK);
+}
+
+#[test]
+fn test_parse_enabled() {
+    assert!(parse_enabled("null\n").is_empty());
+    assert!(parse_enabled("").is_empty());
+    assert_eq!(
+        parse_enabled(&format!("{}::{}\n", TALKBACK, SWITCH_ACCESS)),
+        vec![TALKBACK.to_string(), SWITCH_ACCESS.to_string()]
+    );
+}
+
+#[test]
+fn test_merge_enabled() {
+    let current = vec![
+        "com.google.android.marvin.talkback/.TalkBackService".to_string(),
+        SWITCH_ACCESS.to_string(),
+    ];
+
+    // Disabling matches the short form and keeps the rest
+    assert_eq!(merge_enabled(&current, TALKBACK, false), vec![SWITCH_ACCESS.to_string()]);
+
+    // Enabling something already on changes nothing
+    assert_eq!(merge_enabled(&current, TALKBACK, true), current);
+
+    // New services go at the end
+    let merged = merge_enabled(&current, "com.example/.TestService", true);
+    assert_eq!(merged.len(), 3);
+    assert_eq!(merged[2], "com.example/com.example.TestService");
+
+    // Duplicates in the setting are dropped
+    let duplicated = vec![TALKBACK.to_string(), "com.google.android.marvin.talkback/.TalkBackService".to_string()];
+    assert_eq!(merge_enabled(&duplicated, SWITCH_ACCESS, false), vec![TALKBACK.to_string()]);
+
+    assert!(merge_enabled(&[], TALKBACK, false).is_empty());
+}
+
+#[test]
+fn test_parse_installed() {
+    let output = "2 services found:
+  Service #0:
+    priority=0 preferredOrder=0 match=0x108000 specificIndex=-1 isDefault=false
+    com.google.android.marvin.talkback/.TalkBackService
+  Service #1:
+    priority=0 preferredOrder=0 match=0x108000 specificIndex=-1 isDefault=false
+    com.google.android.accessibility.switchaccess/com.android.switchaccess.SwitchAccessService
+";
+    assert_eq!(parse_installed(output), vec![SWITCH_ACCESS.to_string(), TALKBACK.to_string()]);
+}
+
+#[test]
+fn test_match_service() {
+    let installed = vec![SWITCH_ACCESS.to_string(), TALKBACK.to_string()];
+    assert_eq!(match_service(&installed, "TalkBack").unwrap(), TALKBACK);
+    assert_eq!(
+        match_service(&installed, "com.example/.TestService").unwrap(),
+        "com.example/com.example.TestService"
+    );
+    assert!(match_service(&installed, "magnifier").is_err());
+    // Both live under com.google.android
+    assert!(match_service(&installed, "google").is_err());
+}
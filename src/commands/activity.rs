@@ -0,0 +1,185 @@
+use crate::adb::shell::ShellCommand;
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::{DeviceId, OutputFormat};
+use crate::error::{AimError, Result};
+use async_trait::async_trait;
+use clap::Subcommand;
+use colored::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::time::Duration;
+
+lazy_static! {
+    // "ActivityRecord{a1b2c3 u0 com.example/.MainActivity t123}"
+    static ref ACTIVITY_RECORD_RE: Regex =
+        Regex::new(r"ActivityRecord\{(\S+) u(\d+) ([^\s/]+/[^\s}]+)(?: t(-?\d+))?").unwrap();
+    // "Stack #12: type=standard mode=fullscreen", "RootTask #12" on newer releases
+    static ref STACK_RE: Regex = Regex::new(r"^\s*(?:Stack|RootTask) #(\d+)").unwrap();
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum ActivityCommands {
+    /// Show the activity in the foreground
+    Top(TopArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: ActivityCommands) -> Result<()> {
+    match cmd {
+        ActivityCommands::Top(args) => {
+            let cmd = TopCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct TopArgs {
+    /// Keep running and print the foreground activity whenever it changes
+    #[arg(short, long)]
+    pub watch: bool,
+
+    /// How often to check with --watch
+    #[arg(long, default_value = "500ms", value_parser = crate::utils::parse_duration)]
+    pub interval: Duration,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// The resumed activity and where it came from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TopActivity {
+    pub component: String,
+    pub package: String,
+    pub user: u32,
+    pub task: Option<i32>,
+    pub stack: Option<i32>,
+    /// The intent that started the activity, as `Intent { ... }` prints it
+    pub intent: Option<String>,
+    pub launched_from: Option<String>,
+}
+
+/// The resumed activity from `dumpsys activity activities`
+pub fn parse_top_activity(output: &str) -> Option<TopActivity> {
+    const RESUMED_KEYS: &[&str] = &["topResumedActivity=", "mResumedActivity:", "ResumedActivity:"];
+    let resumed = RESUMED_KEYS
+        .iter()
+        .find_map(|key| output.lines().find(|line| line.contains(key)))?;
+    let caps = ACTIVITY_RECORD_RE.captures(resumed)?;
+    let record = format!("ActivityRecord{{{} ", &caps[1]);
+    let component = caps[3].to_string();
+    let mut top = TopActivity {
+        package: component.split('/').next().unwrap_or_default().to_string(),
+        component,
+        user: caps[2].parse().unwrap_or(0),
+        task: caps.get(4).and_then(|t| t.as_str().parse().ok()),
+        stack: None,
+        intent: None,
+        launched_from: None,
+    };
+
+    // The activity's own entry in the history lists its intent and launcher
+    let lines: Vec<&str> = output.lines().collect();
+    let Some(entry) = lines.iter().position(|l| l.contains("Hist") && l.contains(&record)) else {
+        return Some(top);
+    };
+    top.stack = lines[..entry]
+        .iter()
+        .rev()
+        .find_map(|l| STACK_RE.captures(l).and_then(|c| c[1].parse().ok()));
+    let indent = |l: &str| l.len() - l.trim_start().len();
+    let entry_indent = indent(lines[entry]);
+    for line in lines[entry + 1..].iter().take_while(|l| indent(l) > entry_indent) {
+        let line = line.trim();
+        if let Some(intent) = line.strip_prefix("Intent ").or_else(|| line.strip_prefix("intent=")) {
+            top.intent.get_or_insert_with(|| intent.to_string());
+        }
+        if let Some((_, rest)) = line.split_once("launchedFromPackage=") {
+            let package = rest.split_whitespace().next().unwrap_or_default();
+            if !package.is_empty() && package != "null" {
+                top.launched_from.get_or_insert_with(|| package.to_string());
+            }
+        }
+    }
+    Some(top)
+}
+
+async fn read_top(host: &str, port: u16, device: &DeviceId) -> Result<Option<TopActivity>> {
+    let output = ShellCommand::new("dumpsys activity activities")
+        .with_device(device.clone())
+        .execute(host, port)
+        .await?;
+    Ok(parse_top_activity(&output.stdout))
+}
+
+fn print_top(top: &TopActivity) {
+    println!("Activity:       {}", top.component.bright_cyan());
+    println!("Package:        {}", top.package);
+    let rows = [
+        ("User:", Some(top.user.to_string())),
+        ("Task:", top.task.map(|t| t.to_string())),
+        ("Stack:", top.stack.map(|s| s.to_string())),
+        ("Intent:", top.intent.clone()),
+        ("Launched from:", top.launched_from.clone()),
+    ];
+    for (label, value) in rows {
+        if let Some(value) = value {
+            println!("{:<15} {}", label, value);
+        }
+    }
+}
+
+pub struct TopCommand;
+
+impl TopCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for TopCommand {
+    type Args = TopArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        if !args.watch {
+            let top = read_top(host, port, &device.id)
+                .await?
+                .ok_or_else(|| AimError::CommandExecution("No resumed activity (is the screen off?)".to_string()))?;
+            match ctx.output_format {
+                OutputFormat::Json => crate::commands::format_json_output(&top)?,
+                OutputFormat::Plain => println!("{}", top.component),
+                OutputFormat::Table => print_top(&top),
+            }
+            return Ok(());
+        }
+
+        let mut last: Option<String> = None;
+        loop {
+            let top = read_top(host, port, &device.id).await?;
+            let component = top.as_ref().map(|t| t.component.clone());
+            if component != last {
+                let time = chrono::Local::now().format("%H:%M:%S%.3f");
+                match (&top, ctx.output_format) {
+                    (Some(top), OutputFormat::Json) => println!("{}", serde_json::to_string(top)?),
+                    (Some(top), _) => println!(
+                        "{}  {}{}",
+                        time.to_string().dimmed(),
+                        top.component.bright_cyan(),
+                        top.task.map(|t| format!("  (task {})", t)).unwrap_or_default()
+                    ),
+                    (None, OutputFormat::Json) => {}
+                    (None, _) => println!("{}  {}", time.to_string().dimmed(), "(none)".dimmed()),
+                }
+                last = component;
+            }
+            tokio::time::sleep(args.interval).await;
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use super::activity::parse_top_activity;
+
+const ACTIVITIES: &str = "ACTIVITY MANAGER ACTIVITIES (dumpsys activity activities)
+Display #0 (activities from top to bottom):
+  RootTask #123: type=standard mode=fullscreen
+    * Task{4b2f0d1 #123 type=standard A=10154:com.example U=0 visible=true mode=fullscreen}
+      * Hist  #1: ActivityRecord{a1b2c3 u0 com.example/.DetailActivity t123}
+          packageName=com.example processName=com.example
+          launchedFromUid=10154 launchedFromPackage=com.example launchedFromFeature=null userId=0
+          Intent { cmp=com.example/.DetailActivity (has extras) }
+      * Hist  #0: ActivityRecord{d4e5f6 u0 com.example/.MainActivity t123}
+          packageName=com.example processName=com.example
+          launchedFromUid=2000 launchedFromPackage=null launchedFromFeature=null userId=0
+          Intent { act=android.intent.action.MAIN cat=[android.intent.category.LAUNCHER] flg=0x10000000 cmp=com.example/.MainActivity }
+  RootTask #1: type=home mode=fullscreen
+    * Task{7a8b9c0 #1 type=home A=10032:com.android.launcher3 U=0 visible=false}
+      * Hist  #0: ActivityRecord{0f1e2d u0 com.android.launcher3/.Launcher t1}
+
+  ResumedActivity: ActivityRecord{a1b2c3 u0 com.example/.DetailActivity t123}
+  topResumedActivity=ActivityRecord{a1b2c3 u0 com.example/.DetailActivity t123}
+";
+
+#[test]
+fn test_parse_top_activity() {
+    let top = parse_top_activity(ACTIVITIES).unwrap();
+    assert_eq!(top.component, "com.example/.DetailActivity");
+    assert_eq!(top.package, "com.example");
+    assert_eq!(top.user, 0);
+    assert_eq!(top.task, Some(123));
+    assert_eq!(top.stack, Some(123));
+    assert_eq!(top.intent.as_deref(), Some("{ cmp=com.example/.DetailActivity (has extras) }"));
+    assert_eq!(top.launched_from.as_deref(), Some("com.example"));
+}
+
+#[test]
+fn test_parse_top_activity_old_format() {
+    let output = "  Stack #0:\n    Running activities (most recent first):\n      TaskRecord{1 #7 A=com.android.settings U=0 sz=1}\n        Run #0: ActivityRecord{9f8e u0 com.android.settings/.Settings t7}\n  mResumedActivity: ActivityRecord{9f8e u0 com.android.settings/.Settings t7}\n";
+    let top = parse_top_activity(output).unwrap();
+    assert_eq!(top.component, "com.android.settings/.Settings");
+    assert_eq!(top.task, Some(7));
+    assert_eq!(top.intent, None);
+}
+
+#[test]
+fn test_parse_top_activity_none() {
+    assert_eq!(parse_top_activity("ACTIVITY MANAGER ACTIVITIES\n  ResumedActivity: null\n"), None);
+}
@@ -1,53 +1,200 @@
-use crate::commands::SubCommand;
+use crate::adb::shell::ShellCommand;
+use crate::commands::{get_device, SubCommand};
 use crate::core::context::CommandContext;
-use crate::error::Result;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
 use async_trait::async_trait;
-use std::process::Command;
+use serde::Serialize;
+use std::process::{Command, Stdio};
 
 pub struct AdbCommand;
 
 #[derive(Debug, Clone, clap::Args)]
 pub struct AdbArgs {
-    /// ADB command to run
-    pub command: String,
-    
-    /// Device ID
+    /// Arguments for adb, e.g. `shell ls /sdcard` or `install app.apk`
+    #[clap(trailing_var_arg = true, allow_hyphen_values = true, required = true)]
+    pub command: Vec<String>,
+
+    /// Device ID or alias, passed to adb as `-s <serial>`
+    #[clap(short = 'd', long = "device")]
     pub device_id: Option<String>,
 }
 
+/// adb commands that talk to the server rather than to one device
+const HOST_COMMANDS: &[&str] = &[
+    "devices",
+    "connect",
+    "disconnect",
+    "pair",
+    "version",
+    "help",
+    "start-server",
+    "kill-server",
+    "reconnect",
+    "mdns",
+    "keygen",
+    "host-features",
+];
+
+/// adb's own device-selection flags
+const SELECTION_FLAGS: &[&str] = &["-s", "-d", "-e", "-t"];
+
+/// Result of a captured adb run, for `-o json`
+#[derive(Debug, Serialize, PartialEq)]
+pub struct AdbRun {
+    pub command: Vec<String>,
+    pub serial: Option<String>,
+    pub exit_code: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// The adb arguments, splitting a single quoted command as earlier versions did
+pub fn normalize(command: &[String]) -> Vec<String> {
+    match command {
+        [single] if single.contains(char::is_whitespace) => single.split_whitespace().map(String::from).collect(),
+        _ => command.to_vec(),
+    }
+}
+
+/// Whether aim should pick the device: the command runs on one, and doesn't pick it itself
+pub fn needs_serial(args: &[String]) -> bool {
+    let Some(first) = args.first() else {
+        return false;
+    };
+    if SELECTION_FLAGS.contains(&first.as_str()) || (first.starts_with("-s") && first.len() > 2) {
+        return false;
+    }
+    !HOST_COMMANDS.contains(&first.as_str())
+}
+
+/// Full argument list for the adb binary
+///
+/// The server address is passed along so adb talks to the same server as aim,
+/// which matters with `ADB_SERVER_PORT` or an SSH tunnel.
+pub fn adb_argv(args: &[String], serial: Option<&str>, host: &str, port: u16) -> Vec<String> {
+    let mut argv = vec!["-H".to_string(), host.to_string(), "-P".to_string(), port.to_string()];
+    if let Some(serial) = serial {
+        argv.push("-s".to_string());
+        argv.push(serial.to_string());
+    }
+    argv.extend(args.iter().cloned());
+    argv
+}
+
+/// The device command of `adb shell <cmd>`, which aim can run without the adb binary
+pub fn shell_fallback(args: &[String]) -> Option<String> {
+    match args {
+        [shell, rest @ ..] if shell == "shell" && !rest.is_empty() && !rest[0].starts_with('-') => Some(rest.join(" ")),
+        _ => None,
+    }
+}
+
 impl AdbCommand {
     pub fn new() -> Self {
         Self
     }
+
+    /// Serial to inject, if the command targets a device
+    ///
+    /// An explicit `-d` must resolve. Without one, a failed lookup (no device,
+    /// or several) is left for adb to report in its own words.
+    async fn serial(args: &AdbArgs, adb_args: &[String]) -> Result<Option<String>> {
+        if !needs_serial(adb_args) {
+            if args.device_id.is_some() {
+                return Err(AimError::InvalidArgument(format!(
+                    "`adb {}` doesn't run on a device; drop -d",
+                    adb_args.first().map(String::as_str).unwrap_or_default()
+                )));
+            }
+            return Ok(None);
+        }
+        match get_device(args.device_id.as_deref()).await {
+            Ok(device) => Ok(Some(device.id.to_string())),
+            Err(e) if args.device_id.is_some() => Err(e),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Run `adb shell <cmd>` over aim's own connection to the server
+    async fn run_without_adb(ctx: &CommandContext, adb_args: &[String], serial: Option<String>) -> Result<AdbRun> {
+        let Some(command) = shell_fallback(adb_args) else {
+            return Err(AimError::CommandExecution(
+                "The adb binary was not found, and aim can only stand in for `adb shell <cmd>`.\n\
+                 Install platform-tools (https://developer.android.com/tools/releases/platform-tools) \
+                 or set ADB_PATH. aim has its own versions of many adb commands: \
+                 aim push, aim pull, aim app install, aim shell, aim forward"
+                    .to_string(),
+            ));
+        };
+        let Some(serial) = serial else {
+            return Err(AimError::DeviceNotFound("no single device to run the shell command on".to_string()));
+        };
+        let (host, port) = ctx.adb_address();
+        let output = ShellCommand::new(command)
+            .with_device(crate::core::types::DeviceId::new(serial.as_str()))
+            .execute(host, port)
+            .await?;
+        Ok(AdbRun {
+            command: adb_args.to_vec(),
+            serial: Some(serial),
+            exit_code: output.exit_code,
+            stdout: output.stdout,
+            stderr: output.stderr,
+        })
+    }
 }
 
 #[async_trait]
 impl SubCommand for AdbCommand {
     type Args = AdbArgs;
-    
+
     async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
-        let mut cmd = Command::new("adb");
-        
-        // Add device selection if specified
-        if let Some(device_id) = &args.device_id {
-            cmd.arg("-s").arg(device_id);
-        } else if let Some(device) = &ctx.device {
-            cmd.arg("-s").arg(device.id.to_string());
-        }
-        
-        // Split command into arguments
-        let cmd_parts: Vec<&str> = args.command.split_whitespace().collect();
-        for part in cmd_parts {
-            cmd.arg(part);
-        }
-        
-        // Execute command and inherit stdio
-        let status = cmd.status()?;
-        
-        if !status.success() {
-            std::process::exit(status.code().unwrap_or(1));
+        let adb_args = normalize(&args.command);
+        let serial = Self::serial(&args, &adb_args).await?;
+        let json = matches!(ctx.output_format, OutputFormat::Json);
+
+        let Some(adb) = crate::library::adb::find_adb_binary() else {
+            let run = Self::run_without_adb(ctx, &adb_args, serial).await?;
+            if json {
+                crate::commands::format_json_output(&run)?;
+            } else {
+                if !run.stdout.is_empty() {
+                    println!("{}", run.stdout);
+                }
+                if !run.stderr.is_empty() {
+                    eprintln!("{}", run.stderr);
+                }
+            }
+            return match run.exit_code {
+                0 => Ok(()),
+                code => Err(AimError::ExitStatus(code)),
+            };
+        };
+
+        let (host, port) = ctx.adb_address();
+        let mut cmd = Command::new(adb);
+        cmd.args(adb_argv(&adb_args, serial.as_deref(), host, port));
+        log::debug!("Running adb {:?}", adb_args);
+
+        let exit_code = if json {
+            let output = cmd.stdin(Stdio::null()).output()?;
+            let run = AdbRun {
+                command: adb_args,
+                serial,
+                exit_code: output.status.code().unwrap_or(1),
+                stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            };
+            crate::commands::format_json_output(&run)?;
+            run.exit_code
+        } else {
+            cmd.status()?.code().unwrap_or(1)
+        };
+
+        match exit_code {
+            0 => Ok(()),
+            code => Err(AimError::ExitStatus(code)),
         }
-        
-        Ok(())
     }
-}
\ No newline at end of file
+}
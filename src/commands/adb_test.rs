@@ -0,0 +1,46 @@
+use super::adb::{adb_argv, needs_serial, normalize, shell_fallback};
+
+fn strings(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn test_normalize() {
+    // A quoted command, as earlier versions took it
+    assert_eq!(normalize(&strings(&["shell ls /sdcard"])), strings(&["shell", "ls", "/sdcard"]));
+    assert_eq!(normalize(&strings(&["shell", "echo a  b"])), strings(&["shell", "echo a  b"]));
+    assert_eq!(normalize(&strings(&["devices"])), strings(&["devices"]));
+}
+
+#[test]
+fn test_needs_serial() {
+    assert!(needs_serial(&strings(&["shell", "ls"])));
+    assert!(needs_serial(&strings(&["install", "-r", "app.apk"])));
+    assert!(!needs_serial(&strings(&["devices", "-l"])));
+    assert!(!needs_serial(&strings(&["connect", "10.0.0.5:5555"])));
+    assert!(!needs_serial(&strings(&["-s", "abc", "shell"])));
+    assert!(!needs_serial(&strings(&["-sabc", "shell"])));
+    assert!(!needs_serial(&strings(&["-e", "shell"])));
+    assert!(!needs_serial(&[]));
+}
+
+#[test]
+fn test_adb_argv() {
+    assert_eq!(
+        adb_argv(&strings(&["shell", "ls"]), Some("emulator-5554"), "localhost", 5037),
+        strings(&["-H", "localhost", "-P", "5037", "-s", "emulator-5554", "shell", "ls"])
+    );
+    assert_eq!(
+        adb_argv(&strings(&["devices"]), None, "127.0.0.1", 15037),
+        strings(&["-H", "127.0.0.1", "-P", "15037", "devices"])
+    );
+}
+
+#[test]
+fn test_shell_fallback() {
+    assert_eq!(shell_fallback(&strings(&["shell", "ls", "/sdcard"])), Some("ls /sdcard".to_string()));
+    // Interactive shells and adb's own shell flags need the real binary
+    assert_eq!(shell_fallback(&strings(&["shell"])), None);
+    assert_eq!(shell_fallback(&strings(&["shell", "-t", "top"])), None);
+    assert_eq!(shell_fallback(&strings(&["install", "app.apk"])), None);
+}
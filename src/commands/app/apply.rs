@@ -0,0 +1,331 @@
+use crate::adb::file_transfer::FileTransfer;
+use crate::adb::shell::{helpers::shell, ShellBatch};
+use crate::android::dumpsys::PackageDump;
+use crate::android::pm::{self, InstallOutcome, ListPackages, Pm, State};
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::error::{AimError, Result};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use colored::*;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+pub struct ApplyCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ApplyArgs {
+    /// Manifest describing the wanted app state (TOML)
+    pub manifest: PathBuf,
+
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Show the changes without making them
+    #[clap(short = 'n', long)]
+    pub dry_run: bool,
+}
+
+/// Where to install a package from: one APK, or a base APK and its splits
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum InstallSource {
+    Apk(String),
+    Splits(Vec<String>),
+}
+
+impl InstallSource {
+    pub fn files(&self) -> Vec<&str> {
+        match self {
+            InstallSource::Apk(file) => vec![file.as_str()],
+            InstallSource::Splits(files) => files.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// The app state a device should end up in
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppManifest {
+    /// Packages to uninstall
+    pub uninstall: Vec<String>,
+    /// Packages to enable
+    pub enable: Vec<String>,
+    /// Packages to disable for user 0
+    pub disable: Vec<String>,
+    /// Package to local path or URL of its APK(s)
+    pub install: BTreeMap<String, InstallSource>,
+    /// Package to runtime permissions to grant
+    pub grant: BTreeMap<String, Vec<String>>,
+}
+
+impl AppManifest {
+    /// Parse a manifest and reject contradictions
+    pub fn parse(contents: &str) -> Result<Self> {
+        let manifest: AppManifest = toml::from_str(contents)?;
+        for package in manifest.install.keys().chain(&manifest.enable).chain(manifest.grant.keys()) {
+            if manifest.uninstall.contains(package) {
+                return Err(AimError::InvalidArgument(format!(
+                    "{} is to be uninstalled and also installed, enabled or granted permissions",
+                    package
+                )));
+            }
+        }
+        if let Some(package) = manifest.enable.iter().find(|p| manifest.disable.contains(p)) {
+            return Err(AimError::InvalidArgument(format!("{} is to be both enabled and disabled", package)));
+        }
+        if let Some((package, _)) = manifest.install.iter().find(|(_, source)| source.files().is_empty()) {
+            return Err(AimError::InvalidArgument(format!("{} has no APK to install", package)));
+        }
+        Ok(manifest)
+    }
+}
+
+/// What the device has now, as far as a manifest cares
+#[derive(Debug, Clone, Default)]
+pub struct DeviceApps {
+    pub installed: HashSet<String>,
+    pub disabled: HashSet<String>,
+    pub granted: BTreeMap<String, HashSet<String>>,
+}
+
+/// One change to bring the device in line with the manifest
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Install { package: String, files: Vec<String> },
+    Uninstall(String),
+    Enable(String),
+    Disable(String),
+    Grant { package: String, permission: String },
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Install { package, files } => write!(f, "install    {} ({})", package, files.join(", ")),
+            Action::Uninstall(package) => write!(f, "uninstall  {}", package),
+            Action::Enable(package) => write!(f, "enable     {}", package),
+            Action::Disable(package) => write!(f, "disable    {}", package),
+            Action::Grant { package, permission } => write!(f, "grant      {} {}", package, permission),
+        }
+    }
+}
+
+/// The changes needed to go from `device` to `manifest`, in the order they can be applied
+pub fn plan(manifest: &AppManifest, device: &DeviceApps) -> Vec<Action> {
+    let mut actions = Vec::new();
+    for package in &manifest.uninstall {
+        if device.installed.contains(package) {
+            actions.push(Action::Uninstall(package.clone()));
+        }
+    }
+    for (package, source) in &manifest.install {
+        if !device.installed.contains(package) {
+            actions.push(Action::Install {
+                package: package.clone(),
+                files: source.files().into_iter().map(str::to_string).collect(),
+            });
+        }
+    }
+    // Freshly installed packages start out enabled
+    for package in &manifest.enable {
+        if device.disabled.contains(package) {
+            actions.push(Action::Enable(package.clone()));
+        }
+    }
+    for package in &manifest.disable {
+        let present = device.installed.contains(package) || manifest.install.contains_key(package);
+        if present && !device.disabled.contains(package) {
+            actions.push(Action::Disable(package.clone()));
+        }
+    }
+    for (package, permissions) in &manifest.grant {
+        let granted = device.granted.get(package);
+        for permission in permissions {
+            if !granted.is_some_and(|g| g.contains(permission)) {
+                actions.push(Action::Grant {
+                    package: package.clone(),
+                    permission: permission.clone(),
+                });
+            }
+        }
+    }
+    actions
+}
+
+fn is_url(source: &str) -> bool {
+    source.starts_with("http://") || source.starts_with("https://")
+}
+
+fn check_outcome(what: &str, outcome: InstallOutcome) -> Result<()> {
+    match outcome.reason() {
+        None => Ok(()),
+        Some(reason) => Err(AimError::CommandExecution(format!("{} failed: {}", what, reason))),
+    }
+}
+
+impl ApplyCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn read_device(&self, ctx: &CommandContext, manifest: &AppManifest) -> Result<DeviceApps> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+
+        let batch = manifest
+            .grant
+            .keys()
+            .map(|package| format!("dumpsys package {}", shell_quote(package)))
+            .fold(
+                ShellBatch::new()
+                    .with_device(device.id.clone())
+                    .command(ListPackages::new().command())
+                    .command(ListPackages::new().state(State::Disabled).command()),
+                |batch, command| batch.command(command),
+            );
+        let outputs = batch.execute(host, port).await?;
+        let output = |i: usize| outputs.get(i).map(|o| o.stdout.as_str()).unwrap_or_default();
+
+        Ok(DeviceApps {
+            installed: pm::parse_packages(output(0)).into_iter().collect(),
+            disabled: pm::parse_packages(output(1)).into_iter().collect(),
+            granted: manifest
+                .grant
+                .keys()
+                .enumerate()
+                .map(|(i, package)| {
+                    let granted = PackageDump::parse(output(i + 2)).map(|dump| dump.granted_permissions());
+                    (package.clone(), granted.unwrap_or_default())
+                })
+                .collect(),
+        })
+    }
+
+    /// A local copy of an APK: a path relative to the manifest, or a URL downloaded with curl
+    fn fetch(source: &str, base: &Path, scratch: &Path, index: usize) -> Result<PathBuf> {
+        if !is_url(source) {
+            let path = base.join(source);
+            if !path.is_file() {
+                return Err(AimError::InvalidArgument(format!("APK not found: {}", path.display())));
+            }
+            return Ok(path);
+        }
+        std::fs::create_dir_all(scratch)?;
+        let path = scratch.join(format!("download-{}.apk", index));
+        let status = std::process::Command::new("curl")
+            .args(["-fsSL", "-o"])
+            .arg(&path)
+            .arg(source)
+            .status()
+            .map_err(|e| AimError::CommandExecution(format!("Downloading {} needs curl: {}", source, e)))?;
+        if !status.success() {
+            return Err(AimError::CommandExecution(format!("Could not download {}", source)));
+        }
+        Ok(path)
+    }
+
+    async fn install(&self, ctx: &CommandContext, package: &str, files: &[String], base: &Path) -> Result<()> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        let scratch = std::env::temp_dir().join(format!("aim-apply-{}-{}", std::process::id(), package));
+
+        let mut remotes = Vec::new();
+        let result = async {
+            for (index, source) in files.iter().enumerate() {
+                let local = Self::fetch(source, base, &scratch, index)?;
+                let remote = format!("/data/local/tmp/aim-apply-{}-{}.apk", package, index);
+                let mut transfer = FileTransfer::new(host, port, Some(&device.id)).await?;
+                transfer.push(&local, &remote).await?;
+                remotes.push(remote);
+            }
+
+            let pm = Pm::new(host, port, &device.id);
+            let what = format!("Installing {}", package);
+            if let [remote] = remotes.as_slice() {
+                return check_outcome(&what, pm.install(remote, " -r -t").await?);
+            }
+            // Split APKs go through an install session
+            let session = pm.install_session(" -r -t").await?;
+            for (index, remote) in remotes.iter().enumerate() {
+                if let Err(e) = check_outcome(&format!("Writing {}", remote), session.write(index, remote).await?) {
+                    session.abandon().await?;
+                    return Err(e);
+                }
+            }
+            check_outcome(&what, session.commit().await?)
+        }
+        .await;
+
+        if !remotes.is_empty() {
+            shell(host, port, &device.id, &format!("rm -f {}", remotes.join(" "))).await?;
+        }
+        let _ = std::fs::remove_dir_all(&scratch);
+        result
+    }
+
+    async fn apply(&self, ctx: &CommandContext, action: &Action, base: &Path) -> Result<()> {
+        let pm = Pm::for_context(ctx)?;
+        match action {
+            Action::Install { package, files } => self.install(ctx, package, files, base).await,
+            Action::Uninstall(package) => pm.uninstall(package, None).await,
+            Action::Enable(package) => pm.enable(package, 0).await,
+            Action::Disable(package) => pm.disable(package, 0).await,
+            Action::Grant { package, permission } => pm.grant(package, permission).await,
+        }
+    }
+}
+
+#[async_trait]
+impl SubCommand for ApplyCommand {
+    type Args = ApplyArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let contents = std::fs::read_to_string(&args.manifest)
+            .map_err(|e| AimError::InvalidArgument(format!("Cannot read {}: {}", args.manifest.display(), e)))?;
+        let manifest = AppManifest::parse(&contents)?;
+        let base = args.manifest.parent().map(Path::to_path_buf).unwrap_or_default();
+
+        let state = self.read_device(ctx, &manifest).await?;
+        let actions = plan(&manifest, &state);
+        let device = ctx.require_device()?;
+        if actions.is_empty() {
+            if !ctx.quiet {
+                println!("{} {} already matches {}", "✓".green(), device.id, args.manifest.display());
+            }
+            return Ok(());
+        }
+
+        if args.dry_run {
+            println!("{} change{} for {}:", actions.len(), if actions.len() == 1 { "" } else { "s" }, device.id);
+            for action in &actions {
+                println!("  {}", action);
+            }
+            return Ok(());
+        }
+
+        let mut failed = 0;
+        for action in &actions {
+            match self.apply(ctx, action, &base).await {
+                Ok(()) => {
+                    if !ctx.quiet {
+                        println!("{} {}", "✓".green(), action);
+                    }
+                }
+                Err(e) => {
+                    failed += 1;
+                    println!("{} {}: {}", "✗".red(), action, e);
+                }
+            }
+        }
+        if failed > 0 {
+            return Err(AimError::CommandExecution(format!("{} of {} changes failed", failed, actions.len())));
+        }
+        if !ctx.quiet {
+            println!("{} Applied {} change{}", "✓".green().bold(), actions.len(), if actions.len() == 1 { "" } else { "s" });
+        }
+        Ok(())
+    }
+}
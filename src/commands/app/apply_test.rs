@@ -0,0 +1,81 @@
+use super::apply::{plan, Action, AppManifest, DeviceApps, InstallSource};
+use crate::android::pm::parse_session_id;
+
+const MANIFEST: &str = r#"
+uninstall = ["com.bloat.game"]
+disable = ["com.android.chrome", "com.example.kiosk"]
+enable = ["com.android.camera2"]
+
+[install]
+"com.example.kiosk" = "builds/kiosk.apk"
+"com.example.maps" = ["maps/base.apk", "https://example.com/maps/split_config.arm64_v8a.apk"]
+
+[grant]
+"com.example.kiosk" = ["android.permission.CAMERA", "android.permission.ACCESS_FINE_LOCATION"]
+"#;
+
+fn set(items: &[&str]) -> std::collections::HashSet<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_parse_manifest() {
+    let manifest = AppManifest::parse(MANIFEST).unwrap();
+    assert_eq!(manifest.uninstall, vec!["com.bloat.game"]);
+    assert_eq!(manifest.install["com.example.kiosk"], InstallSource::Apk("builds/kiosk.apk".to_string()));
+    assert_eq!(manifest.install["com.example.maps"].files().len(), 2);
+    assert_eq!(manifest.grant["com.example.kiosk"].len(), 2);
+    assert_eq!(AppManifest::parse("").unwrap(), AppManifest::default());
+}
+
+#[test]
+fn test_parse_manifest_rejects_contradictions() {
+    assert!(AppManifest::parse("uninstall = [\"a.b\"]\nenable = [\"a.b\"]").is_err());
+    assert!(AppManifest::parse("enable = [\"a.b\"]\ndisable = [\"a.b\"]").is_err());
+    assert!(AppManifest::parse("[install]\n\"a.b\" = []").is_err());
+    assert!(AppManifest::parse("instal = [\"a.b\"]").is_err());
+}
+
+#[test]
+fn test_plan() {
+    let manifest = AppManifest::parse(MANIFEST).unwrap();
+    let device = DeviceApps {
+        installed: set(&["com.bloat.game", "com.example.maps", "com.android.chrome", "com.android.camera2"]),
+        disabled: set(&["com.android.camera2"]),
+        granted: [("com.example.kiosk".to_string(), set(&["android.permission.CAMERA"]))].into_iter().collect(),
+    };
+    assert_eq!(
+        plan(&manifest, &device),
+        vec![
+            Action::Uninstall("com.bloat.game".to_string()),
+            Action::Install {
+                package: "com.example.kiosk".to_string(),
+                files: vec!["builds/kiosk.apk".to_string()],
+            },
+            Action::Enable("com.android.camera2".to_string()),
+            Action::Disable("com.android.chrome".to_string()),
+            Action::Disable("com.example.kiosk".to_string()),
+            Action::Grant {
+                package: "com.example.kiosk".to_string(),
+                permission: "android.permission.ACCESS_FINE_LOCATION".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_plan_nothing_to_do() {
+    let manifest = AppManifest::parse("disable = [\"com.android.chrome\"]\nuninstall = [\"com.gone\"]").unwrap();
+    let device = DeviceApps {
+        installed: set(&["com.android.chrome"]),
+        disabled: set(&["com.android.chrome"]),
+        ..Default::default()
+    };
+    assert!(plan(&manifest, &device).is_empty());
+}
+
+#[test]
+fn test_parse_session_id() {
+    assert_eq!(parse_session_id("Success: created install session [1234567]"), Some(1234567));
+    assert_eq!(parse_session_id("Error: something"), None);
+}
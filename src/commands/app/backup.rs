@@ -19,7 +19,7 @@ pub struct BackupArgs {
     pub device_id: Option<String>,
     
     /// Include OBB files in backup
-    #[clap(short, long)]
+    #[clap(long)]
     pub obb: bool,
     
     /// Output file path
@@ -0,0 +1,215 @@
+use crate::android::dumpsys::{self, PackageDump};
+use crate::commands::SubCommand;
+use crate::config::Config;
+use crate::core::context::CommandContext;
+use crate::core::types::{DeviceState, OutputFormat};
+use crate::device::DeviceManager;
+use crate::error::{AimError, Result};
+use crate::output::{OutputFormatter, TableFormat};
+use async_trait::async_trait;
+use comfy_table::{Cell, Color};
+use serde::Serialize;
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Devices queried at the same time
+const MAX_PARALLEL: usize = 8;
+
+pub struct CompareCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct CompareArgs {
+    /// Full package name, looked up on every connected device
+    pub package: String,
+}
+
+/// How a device's copy of the app compares with the others
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Standing {
+    /// Has the highest version code seen on any device
+    Latest,
+    OutOfDate,
+    NotInstalled,
+    /// The device has the app but reports no version code
+    Unknown,
+    /// The device could not be asked
+    Error,
+}
+
+impl fmt::Display for Standing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Standing::Latest => "latest",
+            Standing::OutOfDate => "out of date",
+            Standing::NotInstalled => "not installed",
+            Standing::Unknown => "unknown",
+            Standing::Error => "error",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The app on one device
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DeviceVersion {
+    pub device_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    pub version_name: Option<String>,
+    pub version_code: Option<u64>,
+    /// Device-local time, "YYYY-MM-DD HH:MM:SS"
+    pub last_update_time: Option<String>,
+    pub standing: Standing,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DeviceVersion {
+    /// What `dumpsys package` returned for `device_id`; the standing is set by [`rank`]
+    pub fn from_dump(device_id: &str, alias: Option<String>, dump: Result<Option<PackageDump>>) -> Self {
+        let mut version = Self {
+            device_id: device_id.to_string(),
+            alias,
+            version_name: None,
+            version_code: None,
+            last_update_time: None,
+            standing: Standing::NotInstalled,
+            error: None,
+        };
+        match dump {
+            Ok(Some(dump)) => {
+                version.version_name = dump.version_name;
+                version.version_code = dump.version_code;
+                version.last_update_time = dump.last_update_time;
+                version.standing = Standing::Unknown;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                version.standing = Standing::Error;
+                version.error = Some(e.to_string());
+            }
+        }
+        version
+    }
+
+    fn device_name(&self) -> String {
+        match &self.alias {
+            Some(alias) => format!("{} ({})", alias, self.device_id),
+            None => self.device_id.clone(),
+        }
+    }
+}
+
+/// Mark each installed copy as latest or out of date against the highest version code
+///
+/// Copies without a version code stay unknown. Returns that highest code.
+pub fn rank(versions: &mut [DeviceVersion]) -> Option<u64> {
+    let latest = versions.iter().filter_map(|v| v.version_code).max()?;
+    for version in versions.iter_mut() {
+        if let Some(code) = version.version_code {
+            version.standing = if code < latest { Standing::OutOfDate } else { Standing::Latest };
+        }
+    }
+    Some(latest)
+}
+
+impl TableFormat for DeviceVersion {
+    fn headers() -> Vec<&'static str> {
+        vec!["DEVICE", "VERSION", "CODE", "LAST UPDATE", "STATUS"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let status = match &self.error {
+            Some(error) => format!("{}: {}", self.standing, error),
+            None => self.standing.to_string(),
+        };
+        vec![
+            self.device_name(),
+            self.version_name.clone().unwrap_or_else(|| "-".to_string()),
+            self.version_code.map_or_else(|| "-".to_string(), |code| code.to_string()),
+            self.last_update_time.clone().unwrap_or_else(|| "-".to_string()),
+            status,
+        ]
+    }
+
+    fn colored_row(&self) -> Vec<Cell> {
+        let color = match self.standing {
+            Standing::Latest => Color::Green,
+            Standing::OutOfDate => Color::Yellow,
+            Standing::Error => Color::Red,
+            Standing::NotInstalled | Standing::Unknown => Color::DarkGrey,
+        };
+        let mut cells: Vec<Cell> = self.row().into_iter().map(Cell::new).collect();
+        // The whole row, so an old device stands out in a long list
+        if self.standing == Standing::OutOfDate {
+            return cells.into_iter().map(|cell| cell.fg(color)).collect();
+        }
+        if let Some(last) = cells.pop() {
+            cells.push(last.fg(color));
+        }
+        cells
+    }
+}
+
+impl fmt::Display for DeviceVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.row().join("\t"))
+    }
+}
+
+impl CompareCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for CompareCommand {
+    type Args = CompareArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let (host, port) = ctx.adb_address();
+        let devices = DeviceManager::with_address(host, port.to_string()).list_devices().await?;
+        let devices: Vec<_> = devices.into_iter().filter(|d| d.state == DeviceState::Device).collect();
+        if devices.is_empty() {
+            return Err(AimError::NoDevicesFound);
+        }
+
+        let config = Config::load();
+        let permits = Arc::new(Semaphore::new(MAX_PARALLEL));
+        let mut tasks = JoinSet::new();
+        for device in devices {
+            let alias = device.alias.clone().or_else(|| config.get_device_name(device.id.as_str()));
+            let (host, package, permits) = (host.to_string(), args.package.clone(), permits.clone());
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                let dump = dumpsys::package(&host, port, &device.id, &package).await;
+                DeviceVersion::from_dump(device.id.as_str(), alias, dump)
+            });
+        }
+
+        let mut versions = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok(version) = joined {
+                versions.push(version);
+            }
+        }
+        versions.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+        rank(&mut versions);
+
+        let formatter = OutputFormatter::new();
+        match ctx.output_format {
+            OutputFormat::Table => formatter.table(&versions)?,
+            OutputFormat::Json => formatter.json(&versions)?,
+            OutputFormat::Plain => formatter.plain(&versions)?,
+        }
+
+        if versions.iter().all(|v| matches!(v.standing, Standing::NotInstalled | Standing::Error)) {
+            return Err(AimError::CommandExecution(format!("{} is not installed on any device", args.package)));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use super::compare::{rank, DeviceVersion, Standing};
+use crate::android::dumpsys::PackageDump;
+use crate::error::AimError;
+use crate::output::TableFormat;
+
+fn installed(device_id: &str, code: Option<u64>) -> DeviceVersion {
+    let dump = PackageDump {
+        package: "com.example".to_string(),
+        version_code: code,
+        version_name: code.map(|c| format!("1.{}", c)),
+        last_update_time: Some("2024-01-20 08:15:03".to_string()),
+        ..Default::default()
+    };
+    DeviceVersion::from_dump(device_id, None, Ok(Some(dump)))
+}
+
+#[test]
+fn test_rank_marks_older_devices() {
+    let mut versions = vec![
+        installed("a", Some(12)),
+        installed("b", Some(14)),
+        installed("c", Some(14)),
+        installed("d", None),
+        DeviceVersion::from_dump("e", None, Ok(None)),
+    ];
+    assert_eq!(rank(&mut versions), Some(14));
+    let standings: Vec<Standing> = versions.iter().map(|v| v.standing).collect();
+    assert_eq!(
+        standings,
+        vec![Standing::OutOfDate, Standing::Latest, Standing::Latest, Standing::Unknown, Standing::NotInstalled]
+    );
+}
+
+#[test]
+fn test_rank_without_versions() {
+    let mut versions = vec![DeviceVersion::from_dump("a", None, Ok(None))];
+    assert_eq!(rank(&mut versions), None);
+    assert_eq!(versions[0].standing, Standing::NotInstalled);
+}
+
+#[test]
+fn test_row() {
+    let mut version = installed("emulator-5554", Some(12));
+    version.alias = Some("pixel".to_string());
+    version.standing = Standing::OutOfDate;
+    assert_eq!(version.row(), vec!["pixel (emulator-5554)", "1.12", "12", "2024-01-20 08:15:03", "out of date"]);
+
+    let failed = DeviceVersion::from_dump("R58M", None, Err(AimError::CommandExecution("closed".to_string())));
+    assert_eq!(failed.row(), vec!["R58M", "-", "-", "-", "error: Command execution error: closed"]);
+}
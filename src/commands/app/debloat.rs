@@ -0,0 +1,311 @@
+use crate::adb::shell::{ShellBatch, ShellCommand};
+use crate::android::pm::{self, ListPackages, Origin, State};
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::error::{AimError, Result};
+use async_trait::async_trait;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Packages the device does not work without; never removed, whatever the profile says
+pub const PROTECTED_PACKAGES: &[&str] = &[
+    "android",
+    "com.android.systemui",
+    "com.android.settings",
+    "com.android.phone",
+    "com.android.shell",
+    "com.android.providers.settings",
+    "com.android.packageinstaller",
+    "com.google.android.packageinstaller",
+    "com.android.permissioncontroller",
+    "com.google.android.permissioncontroller",
+];
+
+pub struct DebloatCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct DebloatArgs {
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Profile with the system packages to keep and remove (TOML)
+    #[clap(long = "list", value_name = "PROFILE", required_unless_present = "undo")]
+    pub profile_file: Option<PathBuf>,
+
+    /// Remove the packages; without it the plan is only shown
+    #[clap(long)]
+    pub apply: bool,
+
+    /// Where to write the undo manifest with --apply [default: debloat-undo-<time>.json]
+    #[clap(long, value_name = "FILE")]
+    pub undo_file: Option<PathBuf>,
+
+    /// Bring back the packages recorded in an undo manifest
+    #[clap(long, value_name = "MANIFEST", conflicts_with_all = ["profile_file", "apply"])]
+    pub undo: Option<PathBuf>,
+}
+
+/// How removed packages are taken away
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Method {
+    /// `pm uninstall --user 0`: gone for the user, restorable with install-existing
+    #[default]
+    Uninstall,
+    /// `pm disable-user --user 0`: installed but not runnable
+    Disable,
+}
+
+/// What happens to system packages neither list mentions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Unlisted {
+    #[default]
+    Keep,
+    Remove,
+}
+
+/// Which system packages to keep and which to remove
+///
+/// Entries are package names, optionally with `*` wildcards
+/// (`com.facebook.*`). `keep` wins when both lists match.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DebloatProfile {
+    pub keep: Vec<String>,
+    pub remove: Vec<String>,
+    pub method: Method,
+    pub unlisted: Unlisted,
+}
+
+impl DebloatProfile {
+    pub fn parse(contents: &str) -> Result<Self> {
+        let profile: DebloatProfile = toml::from_str(contents)?;
+        if profile.remove.is_empty() && profile.unlisted == Unlisted::Keep {
+            return Err(AimError::InvalidArgument(
+                "The profile removes nothing: add packages to `remove` or set `unlisted = \"remove\"`".to_string(),
+            ));
+        }
+        Ok(profile)
+    }
+
+    /// Whether `package` should be removed
+    pub fn removes(&self, package: &str) -> bool {
+        let matches = |patterns: &[String]| patterns.iter().any(|p| glob_match(p, package));
+        if PROTECTED_PACKAGES.contains(&package) || matches(&self.keep) {
+            return false;
+        }
+        matches(&self.remove) || self.unlisted == Unlisted::Remove
+    }
+}
+
+/// Match `text` against a pattern where `*` stands for any run of characters
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let Some(mut remaining) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = rest.split('*').collect();
+    let (last, middle) = parts.split_last().expect("split yields at least one part");
+    for part in middle {
+        match remaining.find(part) {
+            Some(at) => remaining = &remaining[at + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.ends_with(last)
+}
+
+/// A package taken away, as recorded in the undo manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Removal {
+    pub package: String,
+    pub method: Method,
+}
+
+impl Removal {
+    /// The shell command that removes the package
+    pub fn command(&self) -> String {
+        match self.method {
+            Method::Uninstall => pm::uninstall_command(&self.package, Some(0)),
+            Method::Disable => pm::disable_command(&self.package, 0),
+        }
+    }
+
+    /// The shell command that brings the package back
+    pub fn undo_command(&self) -> String {
+        match self.method {
+            Method::Uninstall => pm::install_existing_command(&self.package, 0),
+            Method::Disable => pm::enable_command(&self.package, 0),
+        }
+    }
+
+    /// Whether the output of `command()` or `undo_command()` reports success
+    pub fn succeeded(output: &str) -> bool {
+        output.contains("Success")
+            || output.contains("new state:")
+            || output.contains("installed for user")
+    }
+}
+
+impl fmt::Display for Removal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.method {
+            Method::Uninstall => write!(f, "uninstall {}", self.package),
+            Method::Disable => write!(f, "disable {}", self.package),
+        }
+    }
+}
+
+/// Packages removed by `--apply`, so `--undo` can bring them back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UndoManifest {
+    pub device: String,
+    pub created: String,
+    pub packages: Vec<Removal>,
+}
+
+/// The removals `profile` asks for, skipping packages already disabled when disabling
+pub fn plan(profile: &DebloatProfile, system: &[String], disabled: &HashSet<String>) -> Vec<Removal> {
+    system
+        .iter()
+        .filter(|package| profile.removes(package))
+        .filter(|package| profile.method == Method::Uninstall || !disabled.contains(*package))
+        .map(|package| Removal { package: package.clone(), method: profile.method })
+        .collect()
+}
+
+impl DebloatCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Remove (or with `undo`, restore) each package, returning the ones that worked
+    async fn run_all(&self, ctx: &CommandContext, removals: &[Removal], undo: bool) -> Result<Vec<Removal>> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        let mut done = Vec::new();
+        for removal in removals {
+            let command = if undo { removal.undo_command() } else { removal.command() };
+            let output = ShellCommand::new(format!("{} 2>&1", command))
+                .with_device(device.id.clone())
+                .execute(host, port)
+                .await
+                .map(|o| o.stdout)
+                .unwrap_or_else(|e| e.to_string());
+            let label = if undo { format!("restore {}", removal.package) } else { removal.to_string() };
+            if Removal::succeeded(&output) {
+                if !ctx.quiet {
+                    println!("{} {}", "✓".green(), label);
+                }
+                done.push(removal.clone());
+            } else {
+                println!("{} {}: {}", "✗".red(), label, output.trim());
+            }
+        }
+        Ok(done)
+    }
+
+    async fn undo(&self, ctx: &CommandContext, path: &Path) -> Result<()> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| AimError::InvalidArgument(format!("Cannot read {}: {}", path.display(), e)))?;
+        let manifest: UndoManifest = serde_json::from_str(&contents)?;
+        let device = ctx.require_device()?;
+        if manifest.device != device.id.to_string() && !ctx.quiet {
+            println!("{} {} was written for {}", "Note:".yellow(), path.display(), manifest.device);
+        }
+
+        let restored = self.run_all(ctx, &manifest.packages, true).await?;
+        if restored.len() < manifest.packages.len() {
+            return Err(AimError::CommandExecution(format!(
+                "{} of {} packages could not be restored",
+                manifest.packages.len() - restored.len(),
+                manifest.packages.len()
+            )));
+        }
+        if !ctx.quiet {
+            println!("{} Restored {} packages", "✓".green().bold(), restored.len());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubCommand for DebloatCommand {
+    type Args = DebloatArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        if let Some(path) = &args.undo {
+            return self.undo(ctx, path).await;
+        }
+        let Some(path) = &args.profile_file else {
+            return Err(AimError::InvalidArgument("Pass a profile with --list or a manifest with --undo".to_string()));
+        };
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| AimError::InvalidArgument(format!("Cannot read {}: {}", path.display(), e)))?;
+        let profile = DebloatProfile::parse(&contents)?;
+
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        let outputs = ShellBatch::new()
+            .with_device(device.id.clone())
+            .command(ListPackages::new().origin(Origin::System).command())
+            .command(ListPackages::new().origin(Origin::System).state(State::Disabled).command())
+            .execute(host, port)
+            .await?;
+        let output = |i: usize| outputs.get(i).map(|o| o.stdout.as_str()).unwrap_or_default();
+        let system = pm::parse_packages(output(0));
+        let disabled: HashSet<String> = pm::parse_packages(output(1)).into_iter().collect();
+        let removals = plan(&profile, &system, &disabled);
+
+        if removals.is_empty() {
+            if !ctx.quiet {
+                println!("{} Nothing to remove on {}", "✓".green(), device.id);
+            }
+            return Ok(());
+        }
+        if !args.apply {
+            println!("{} of {} system packages would be removed from {}:", removals.len(), system.len(), device.id);
+            for removal in &removals {
+                println!("  {}", removal);
+            }
+            println!("{}", "Run again with --apply to remove them".dimmed());
+            return Ok(());
+        }
+
+        let done = self.run_all(ctx, &removals, false).await?;
+        // Written even after failures, so whatever was removed can be restored
+        if !done.is_empty() {
+            let undo_file = args.undo_file.clone().unwrap_or_else(|| {
+                PathBuf::from(format!("debloat-undo-{}.json", chrono::Local::now().format("%Y%m%d-%H%M%S")))
+            });
+            let manifest = UndoManifest {
+                device: device.id.to_string(),
+                created: chrono::Local::now().to_rfc3339(),
+                packages: done.clone(),
+            };
+            std::fs::write(&undo_file, serde_json::to_string_pretty(&manifest)?)?;
+            if !ctx.quiet {
+                println!(
+                    "{} Removed {} packages; undo with: aim app debloat --undo {}",
+                    "✓".green().bold(),
+                    done.len(),
+                    undo_file.display()
+                );
+            }
+        }
+        if done.len() < removals.len() {
+            return Err(AimError::CommandExecution(format!(
+                "{} of {} packages could not be removed",
+                removals.len() - done.len(),
+                removals.len()
+            )));
+        }
+        Ok(())
+    }
+}
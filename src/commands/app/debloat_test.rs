@@ -0,0 +1,86 @@
+use super::debloat::{glob_match, plan, DebloatProfile, Method, Removal, Unlisted};
+use crate::android::pm::parse_packages as parse_package_list;
+use std::collections::HashSet;
+
+#[test]
+fn test_glob_match() {
+    assert!(glob_match("com.facebook.katana", "com.facebook.katana"));
+    assert!(!glob_match("com.facebook.katana", "com.facebook.orca"));
+    assert!(glob_match("com.facebook.*", "com.facebook.orca"));
+    assert!(!glob_match("com.facebook.*", "com.facebookx"));
+    assert!(glob_match("*", "android"));
+    assert!(glob_match("com.*.game*", "com.samsung.android.game.gamehome"));
+    assert!(glob_match("*.overlay", "com.android.theme.overlay"));
+    assert!(!glob_match("a*a", "a"));
+}
+
+#[test]
+fn test_profile_parse() {
+    let profile = DebloatProfile::parse(
+        r#"
+method = "disable"
+keep = ["com.facebook.appmanager"]
+remove = ["com.facebook.*"]
+"#,
+    )
+    .unwrap();
+    assert_eq!(profile.method, Method::Disable);
+    assert_eq!(profile.unlisted, Unlisted::Keep);
+    assert!(profile.removes("com.facebook.katana"));
+    assert!(!profile.removes("com.facebook.appmanager"));
+    assert!(!profile.removes("com.android.chrome"));
+
+    // A profile that removes nothing is a mistake
+    assert!(DebloatProfile::parse("keep = [\"com.android.*\"]").is_err());
+    assert!(DebloatProfile::parse("remove = []\nunknown = 1").is_err());
+}
+
+#[test]
+fn test_protected_packages() {
+    let profile = DebloatProfile::parse("unlisted = \"remove\"\nkeep = [\"com.google.android.gms\"]").unwrap();
+    assert!(profile.removes("com.android.chrome"));
+    assert!(!profile.removes("com.google.android.gms"));
+    assert!(!profile.removes("android"));
+    assert!(!profile.removes("com.android.systemui"));
+}
+
+#[test]
+fn test_plan() {
+    let system = parse_package_list(
+        "package:com.facebook.katana\npackage:android\npackage:com.facebook.system\npackage:com.android.chrome\n",
+    );
+    assert_eq!(system[0], "android");
+
+    let profile = DebloatProfile::parse("remove = [\"com.facebook.*\"]").unwrap();
+    let removals = plan(&profile, &system, &HashSet::new());
+    assert_eq!(
+        removals,
+        vec![
+            Removal { package: "com.facebook.katana".to_string(), method: Method::Uninstall },
+            Removal { package: "com.facebook.system".to_string(), method: Method::Uninstall },
+        ]
+    );
+
+    // Already disabled packages are left out when disabling
+    let profile = DebloatProfile::parse("method = \"disable\"\nremove = [\"com.facebook.*\"]").unwrap();
+    let disabled: HashSet<String> = ["com.facebook.system".to_string()].into_iter().collect();
+    let removals = plan(&profile, &system, &disabled);
+    assert_eq!(removals.len(), 1);
+    assert_eq!(removals[0].package, "com.facebook.katana");
+}
+
+#[test]
+fn test_removal_commands() {
+    let uninstall = Removal { package: "com.facebook.katana".to_string(), method: Method::Uninstall };
+    assert_eq!(uninstall.command(), "pm uninstall --user 0 com.facebook.katana");
+    assert_eq!(uninstall.undo_command(), "cmd package install-existing --user 0 com.facebook.katana");
+
+    let disable = Removal { package: "com.facebook.katana".to_string(), method: Method::Disable };
+    assert_eq!(disable.command(), "pm disable-user --user 0 com.facebook.katana");
+    assert_eq!(disable.undo_command(), "pm enable --user 0 com.facebook.katana");
+
+    assert!(Removal::succeeded("Success\n"));
+    assert!(Removal::succeeded("Package com.facebook.katana new state: disabled-user\n"));
+    assert!(Removal::succeeded("Package com.facebook.katana installed for user: 0\n"));
+    assert!(!Removal::succeeded("Failure [not installed for 0]\n"));
+}
@@ -0,0 +1,151 @@
+use crate::android::dumpsys::{self, EnabledState};
+use crate::android::pm::Pm;
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use async_trait::async_trait;
+use colored::*;
+use serde::Serialize;
+
+pub struct EnableCommand;
+
+pub struct DisableCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct StateChangeArgs {
+    /// Package name (supports partial matching)
+    pub package: String,
+
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Android user to change the app for
+    #[clap(long, default_value_t = 0)]
+    pub user: u32,
+
+    /// Skip the confirmation prompt for system apps
+    #[clap(short = 'y', long)]
+    pub yes: bool,
+}
+
+/// Result of `aim app enable` / `aim app disable`, for `-o json`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StateChange {
+    pub package: String,
+    pub user: u32,
+    pub enabled: bool,
+    pub state: EnabledState,
+    /// Whether aim had to change anything
+    pub changed: bool,
+}
+
+/// The state of `package` for `user` as the device reports it
+async fn read_state(ctx: &CommandContext, package: &str, user: u32) -> Result<(EnabledState, bool)> {
+    let device = ctx.require_device()?;
+    let (host, port) = ctx.adb_address();
+    let dump = dumpsys::package(host, port, &device.id, package)
+        .await?
+        .ok_or_else(|| AimError::CommandExecution(format!("{} is not installed", package)))?;
+    let state = dump
+        .user(user)
+        .filter(|state| state.installed)
+        .ok_or_else(|| AimError::CommandExecution(format!("{} is not installed for user {}", package, user)))?
+        .enabled;
+    Ok((state, dump.is_system()))
+}
+
+fn confirm_disable(package: &str) -> Result<bool> {
+    use std::io::{self, Write};
+
+    crate::core::ci::require_interactive("Confirmation prompt (pass --yes)")?;
+
+    println!();
+    println!("{}", "WARNING: This is a system app!".yellow().bold());
+    println!("Package: {}", package.bright_cyan());
+    println!("Disabling it can break other apps or parts of the system until it is enabled again.");
+    println!();
+    print!("Disable it anyway? [y/N] ");
+    io::stdout().flush().unwrap();
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+/// Enable or disable the package in `args`, then check the device agrees
+async fn change(ctx: &CommandContext, args: StateChangeArgs, enable: bool) -> Result<()> {
+    let done = if enable { "Enabled" } else { "Disabled" };
+    let package = super::PackageResolver::new(ctx).resolve(&args.package).await?;
+    let (before, is_system) = read_state(ctx, &package, args.user).await?;
+
+    let changed = before.is_enabled() != enable;
+    let state = if changed {
+        if !enable && is_system && !args.yes && !confirm_disable(&package)? {
+            println!("Operation cancelled.");
+            return Ok(());
+        }
+        let pm = Pm::for_context(ctx)?;
+        if enable {
+            pm.enable(&package, args.user).await?;
+        } else {
+            pm.disable(&package, args.user).await?;
+        }
+
+        // pm reports the state it set; make sure it stuck
+        let (after, _) = read_state(ctx, &package, args.user).await?;
+        if after.is_enabled() != enable {
+            return Err(AimError::CommandExecution(format!(
+                "pm reported success, but {} is still {}",
+                package,
+                if enable { "disabled" } else { "enabled" }
+            )));
+        }
+        after
+    } else {
+        before
+    };
+
+    let result = StateChange { package, user: args.user, enabled: enable, state, changed };
+    if ctx.output_format == OutputFormat::Json {
+        return crate::commands::format_json_output(&result);
+    }
+    if !ctx.quiet {
+        match changed {
+            true => println!("{} {} {}", "✓".green(), done, result.package.bright_cyan()),
+            false => println!("{} {} is already {}", "✓".green(), result.package.bright_cyan(), done.to_lowercase()),
+        }
+    }
+    Ok(())
+}
+
+impl EnableCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl DisableCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for EnableCommand {
+    type Args = StateChangeArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        change(ctx, args, true).await
+    }
+}
+
+#[async_trait]
+impl SubCommand for DisableCommand {
+    type Args = StateChangeArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        change(ctx, args, false).await
+    }
+}
@@ -0,0 +1,187 @@
+use crate::adb::file_transfer::FileTransfer;
+use crate::adb::shell::{ShellBatch, ShellCommand};
+use crate::adb::su::RootMode;
+use crate::android::dumpsys::{self, PackageDump};
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::utils::{format_bytes, shell_quote};
+use async_trait::async_trait;
+use colored::*;
+use serde::Serialize;
+use std::path::PathBuf;
+
+pub struct ExportDataCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ExportDataArgs {
+    /// Package name (supports partial matching)
+    pub package: String,
+
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Archive to write [default: <package>-data-<time>.tar.gz]
+    #[clap(short = 'o', long)]
+    pub output: Option<PathBuf>,
+
+    /// Read the data as root, even when the app is debuggable
+    #[clap(long)]
+    pub as_root: bool,
+}
+
+/// How the app's private files are read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// `run-as <pkg>`, as the app itself; debuggable apps only
+    RunAs,
+    Root(RootMode),
+}
+
+impl Access {
+    pub fn name(self) -> &'static str {
+        match self {
+            Access::RunAs => "run-as",
+            Access::Root(_) => "root",
+        }
+    }
+}
+
+/// Result of `aim app export-data`, for `-o json`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportedData {
+    pub package: String,
+    pub file: PathBuf,
+    pub bytes: u64,
+    pub access: &'static str,
+    /// Whether the archive is gzipped; not when the device has no gzip
+    pub compressed: bool,
+}
+
+/// Whether reading the data of `dump` needs root
+pub fn needs_root(dump: &PackageDump, as_root: bool) -> bool {
+    as_root || !dump.is_debuggable()
+}
+
+/// Shell command that archives `data_dir` into `remote` on the device
+pub fn archive_command(access: Access, package: &str, data_dir: &str, remote: &str, gzip: bool) -> String {
+    let tar = format!("tar -cf - -C {} .", shell_quote(data_dir));
+    let tar = match access {
+        Access::RunAs => format!("run-as {} {}", shell_quote(package), tar),
+        Access::Root(mode) => mode.wrap(&tar),
+    };
+    // The redirect runs as the shell user, who can write to /data/local/tmp
+    let compress = if gzip { " | gzip" } else { "" };
+    format!("{}{} > {}", tar, compress, shell_quote(remote))
+}
+
+/// Error in what the archive command printed, e.g. "run-as: package not debuggable"
+///
+/// tar also warns about sockets and other files it skips; those are not errors.
+pub fn archive_error(output: &str) -> Option<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("run-as:") || line.contains("Permission denied") || line.contains("No such file"))
+        .map(String::from)
+}
+
+pub fn default_output(package: &str, gzip: bool) -> PathBuf {
+    let extension = if gzip { "tar.gz" } else { "tar" };
+    PathBuf::from(format!("{}-data-{}.{}", package, chrono::Local::now().format("%Y%m%d-%H%M%S"), extension))
+}
+
+impl ExportDataCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for ExportDataCommand {
+    type Args = ExportDataArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let package = super::PackageResolver::new(ctx).resolve(&args.package).await?;
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+
+        let outputs = ShellBatch::new()
+            .with_device(device.id.clone())
+            .command(dumpsys::package_command(&package))
+            .command("command -v gzip")
+            .execute(host, port)
+            .await?;
+        let output = |i: usize| outputs.get(i).map(|o| o.stdout.as_str()).unwrap_or_default();
+        let dump = PackageDump::parse(output(0))
+            .ok_or_else(|| AimError::CommandExecution(format!("{} is not installed", package)))?;
+        let gzip = !output(1).trim().is_empty();
+
+        let access = if needs_root(&dump, args.as_root) {
+            match RootMode::detect(host, port, &device.id).await {
+                Ok(mode) => Access::Root(mode),
+                Err(AimError::RootUnavailable(_)) if !args.as_root => {
+                    return Err(AimError::CommandExecution(format!(
+                        "{} is not debuggable, so run-as can't read its data, and the device is not rooted",
+                        package
+                    )));
+                }
+                Err(e) => return Err(e),
+            }
+        } else {
+            Access::RunAs
+        };
+
+        let data_dir = dump.data_dir.clone().unwrap_or_else(|| format!("/data/data/{}", package));
+        let remote = format!("/data/local/tmp/aim-export-{}.tar{}", package, if gzip { ".gz" } else { "" });
+        let local = args.output.clone().unwrap_or_else(|| default_output(&package, gzip));
+        if !gzip && !ctx.quiet {
+            eprintln!("{} The device has no gzip; {} is an uncompressed tar", "⚠".yellow(), local.display());
+        }
+
+        let archived = ShellCommand::new(archive_command(access, &package, &data_dir, &remote, gzip))
+            .with_device(device.id.clone())
+            .execute(host, port)
+            .await;
+        let pulled = match archived {
+            Ok(out) => match archive_error(&format!("{}\n{}", out.stdout, out.stderr)) {
+                Some(error) => Err(AimError::CommandExecution(format!("Could not archive {}: {}", data_dir, error))),
+                None => async {
+                    let mut transfer = FileTransfer::new(host, port, Some(&device.id)).await?;
+                    transfer.pull(&remote, &local).await
+                }
+                .await,
+            },
+            Err(e) => Err(e),
+        };
+        // Removed whatever happened: the archive holds the app's private data
+        let _ = ShellCommand::new(format!("rm -f {}", shell_quote(&remote)))
+            .with_device(device.id.clone())
+            .execute(host, port)
+            .await;
+        pulled?;
+
+        let result = ExportedData {
+            package,
+            bytes: std::fs::metadata(&local).map(|m| m.len()).unwrap_or(0),
+            file: local,
+            access: access.name(),
+            compressed: gzip,
+        };
+        if ctx.output_format == OutputFormat::Json {
+            return crate::commands::format_json_output(&result);
+        }
+        if !ctx.quiet {
+            println!(
+                "{} Exported the data of {} ({}, via {}) to {}",
+                "✓".green(),
+                result.package.bright_cyan(),
+                format_bytes(result.bytes),
+                result.access,
+                result.file.display()
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use super::export_data::{archive_command, archive_error, default_output, needs_root, Access};
+use crate::adb::su::RootMode;
+use crate::android::dumpsys::PackageDump;
+
+#[test]
+fn test_archive_command_run_as() {
+    assert_eq!(
+        archive_command(Access::RunAs, "com.example", "/data/user/0/com.example", "/data/local/tmp/x.tar.gz", true),
+        "run-as com.example tar -cf - -C /data/user/0/com.example . | gzip > /data/local/tmp/x.tar.gz"
+    );
+}
+
+#[test]
+fn test_archive_command_root() {
+    assert_eq!(
+        archive_command(Access::Root(RootMode::SuDashC), "com.example", "/data/data/com.example", "/data/local/tmp/x.tar", false),
+        "su -c 'tar -cf - -C /data/data/com.example .' > /data/local/tmp/x.tar"
+    );
+    assert_eq!(
+        archive_command(Access::Root(RootMode::Adbd), "com.example", "/data/data/com.example", "/data/local/tmp/x.tar.gz", true),
+        "tar -cf - -C /data/data/com.example . | gzip > /data/local/tmp/x.tar.gz"
+    );
+}
+
+#[test]
+fn test_needs_root() {
+    let debuggable = PackageDump { flags: vec!["DEBUGGABLE".to_string(), "HAS_CODE".to_string()], ..Default::default() };
+    let release = PackageDump { flags: vec!["HAS_CODE".to_string()], ..Default::default() };
+    assert!(!needs_root(&debuggable, false));
+    assert!(needs_root(&debuggable, true));
+    assert!(needs_root(&release, false));
+}
+
+#[test]
+fn test_archive_error() {
+    assert_eq!(
+        archive_error("run-as: package not debuggable: com.example\n").as_deref(),
+        Some("run-as: package not debuggable: com.example")
+    );
+    assert!(archive_error("tar: ./cache/sock: socket ignored\n").is_none());
+    assert!(archive_error("").is_none());
+}
+
+#[test]
+fn test_default_output() {
+    let gz = default_output("com.example", true).to_string_lossy().into_owned();
+    assert!(gz.starts_with("com.example-data-") && gz.ends_with(".tar.gz"));
+    assert!(default_output("com.example", false).to_string_lossy().ends_with(".tar"));
+}
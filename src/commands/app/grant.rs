@@ -0,0 +1,148 @@
+use crate::android::dumpsys;
+use crate::android::pm::Pm;
+use crate::android::sdk::{self, Capability};
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use async_trait::async_trait;
+use colored::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub struct GrantCommand;
+
+pub struct RevokeCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct PermissionChangeArgs {
+    /// Package name (supports partial matching)
+    pub package: String,
+
+    /// Permissions; `CAMERA` is short for `android.permission.CAMERA`
+    #[clap(required_unless_present = "all_requested")]
+    pub permissions: Vec<String>,
+
+    /// Every runtime permission the app requests
+    #[clap(long, conflicts_with = "permissions")]
+    pub all_requested: bool,
+
+    /// Device ID (required if multiple devices are connected)
+    #[clap(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// Outcome for one permission, for `-o json`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PermissionResult {
+    pub permission: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Full name of a permission, adding `android.permission.` to a bare one
+pub fn qualify(permission: &str) -> String {
+    if permission.contains('.') {
+        permission.to_string()
+    } else {
+        format!("android.permission.{}", permission.to_uppercase())
+    }
+}
+
+/// Requested runtime permissions that are not granted (`grant`) or are (`!grant`)
+pub fn pending(permissions: &BTreeMap<String, bool>, grant: bool) -> Vec<String> {
+    permissions
+        .iter()
+        .filter(|(_, &granted)| granted != grant)
+        .map(|(permission, _)| permission.clone())
+        .collect()
+}
+
+/// Grant or revoke the permissions in `args`, reporting each one
+async fn change(ctx: &CommandContext, args: PermissionChangeArgs, grant: bool) -> Result<()> {
+    let (verb, done) = if grant { ("grant", "granted") } else { ("revoke", "revoked") };
+    let device = ctx.require_device()?;
+    let (host, port) = ctx.adb_address();
+    sdk::for_device(host, port, &device.id).await.require(Capability::RuntimePermissions)?;
+    let package = super::PackageResolver::new(ctx).resolve(&args.package).await?;
+    let pm = Pm::for_context(ctx)?;
+
+    let permissions = if args.all_requested {
+        let dump = dumpsys::package(host, port, &device.id, &package).await?.unwrap_or_default();
+        pending(&dump.runtime_permissions(), grant)
+    } else {
+        args.permissions.iter().map(|p| qualify(p)).collect()
+    };
+
+    let json = ctx.output_format == OutputFormat::Json;
+    if permissions.is_empty() && !json {
+        if !ctx.quiet {
+            println!("{} {} has no runtime permissions left to {}", "✓".green(), package.bright_cyan(), verb);
+        }
+        return Ok(());
+    }
+
+    let mut results = Vec::new();
+    for permission in permissions {
+        let result = if grant {
+            pm.grant(&package, &permission).await
+        } else {
+            pm.revoke(&package, &permission).await
+        };
+        match &result {
+            Ok(()) if !json && !ctx.quiet => println!("{} {} {}", "✓".green(), done, permission),
+            Err(e) if !json => println!("{} {}", "✗".red(), e),
+            _ => {}
+        }
+        results.push(PermissionResult {
+            permission,
+            ok: result.is_ok(),
+            error: result.err().map(|e| e.to_string()),
+        });
+    }
+
+    if json {
+        crate::commands::format_json_output(&results)?;
+    }
+    let failed = results.iter().filter(|r| !r.ok).count();
+    if failed > 0 {
+        return Err(AimError::CommandExecution(format!(
+            "{} of {} permissions could not be {}",
+            failed,
+            results.len(),
+            done
+        )));
+    }
+    Ok(())
+}
+
+impl GrantCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RevokeCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for GrantCommand {
+    type Args = PermissionChangeArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        change(ctx, args, true).await
+    }
+}
+
+#[async_trait]
+impl SubCommand for RevokeCommand {
+    type Args = PermissionChangeArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        change(ctx, args, false).await
+    }
+}
@@ -0,0 +1,24 @@
+use super::grant::{pending, qualify};
+use std::collections::BTreeMap;
+
+#[test]
+fn test_qualify() {
+    assert_eq!(qualify("CAMERA"), "android.permission.CAMERA");
+    assert_eq!(qualify("post_notifications"), "android.permission.POST_NOTIFICATIONS");
+    assert_eq!(qualify("android.permission.CAMERA"), "android.permission.CAMERA");
+    assert_eq!(qualify("com.example.permission.SYNC"), "com.example.permission.SYNC");
+}
+
+#[test]
+fn test_pending() {
+    let permissions: BTreeMap<String, bool> = [
+        ("android.permission.CAMERA".to_string(), true),
+        ("android.permission.READ_CONTACTS".to_string(), false),
+        ("android.permission.RECORD_AUDIO".to_string(), false),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(pending(&permissions, true), vec!["android.permission.READ_CONTACTS", "android.permission.RECORD_AUDIO"]);
+    assert_eq!(pending(&permissions, false), vec!["android.permission.CAMERA"]);
+    assert!(pending(&BTreeMap::new(), true).is_empty());
+}
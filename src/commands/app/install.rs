@@ -0,0 +1,364 @@
+use crate::adb::connection::AdbConnection;
+use crate::adb::features::{self, Feature, Features};
+use crate::android::dumpsys;
+use crate::android::pm::{failure_hint, parse_install_output, parse_session_id, InstallOutcome, Pm};
+use crate::adb::file_transfer::FileTransfer;
+use crate::adb::shell::helpers::shell;
+use crate::commands::intent::find_aapt2;
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::progress::{ProgressFactory, ProgressReporter};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use colored::*;
+use log::debug;
+use serde::Serialize;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+pub struct InstallCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct InstallArgs {
+    /// APK to install; give a base APK and its splits to install them together
+    #[clap(required = true)]
+    pub apks: Vec<PathBuf>,
+
+    /// Replace the app if it is already installed, keeping its data
+    #[clap(short, long)]
+    pub replace: bool,
+
+    /// Grant all runtime permissions the app asks for
+    #[clap(short, long)]
+    pub grant: bool,
+
+    /// Allow test-only APKs (android:testOnly, as built by Android Studio)
+    #[clap(short, long)]
+    pub test: bool,
+
+    /// Allow installing an older version over a newer one (debuggable apps, or any app on userdebug builds)
+    #[clap(long)]
+    pub downgrade: bool,
+
+    /// Push to /data/local/tmp and install from there instead of streaming
+    #[clap(long)]
+    pub no_streaming: bool,
+
+    /// Device ID (required if multiple devices are connected)
+    #[clap(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// Result of `aim app install`, for `-o json`
+#[derive(Debug, Serialize)]
+pub struct InstallReport {
+    pub apks: Vec<String>,
+    #[serde(flatten)]
+    pub outcome: InstallOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<&'static str>,
+}
+
+/// Options for `pm install` and `pm install-create`
+pub fn pm_flags(args: &InstallArgs) -> String {
+    [(args.replace, " -r"), (args.grant, " -g"), (args.test, " -t"), (args.downgrade, " -d")]
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, flag)| *flag)
+        .collect()
+}
+
+/// Package name and versionCode from `aapt2 dump badging`
+///
+/// The first line reads `package: name='com.example' versionCode='42' versionName='1.2' ...`.
+pub fn parse_badging(output: &str) -> Option<(String, u64)> {
+    let line = output.lines().find(|line| line.starts_with("package:"))?;
+    let value = |key: &str| line.split(&format!(" {}='", key)).nth(1)?.split('\'').next();
+    Some((value("name")?.to_string(), value("versionCode")?.parse().ok()?))
+}
+
+/// Package name and versionCode of an APK, if aapt2 is around to read them
+fn apk_version(apk: &Path) -> Option<(String, u64)> {
+    let sdk = std::env::var_os("ANDROID_HOME").or_else(|| std::env::var_os("ANDROID_SDK_ROOT"));
+    let aapt2 = find_aapt2(std::env::var_os("PATH").as_deref(), sdk.as_deref())?;
+    let output = std::process::Command::new(aapt2).args(["dump", "badging"]).arg(apk).output().ok()?;
+    parse_badging(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Service that runs `cmd package <args>` with the connection as stdin and stdout
+///
+/// `abb_exec` reaches the package service directly; devices without it start
+/// `cmd` through `exec:`, which is raw as well.
+pub fn package_service(features: &Features, args: &[String]) -> String {
+    if features.supports(Feature::AbbExec) {
+        format!("abb_exec:package\0{}", args.join("\0"))
+    } else {
+        let quoted: Vec<String> = args.iter().map(|a| shell_quote(a)).collect();
+        format!("exec:cmd package {}", quoted.join(" "))
+    }
+}
+
+/// Arguments of a streamed `install`, `install-create` or `install-write`
+pub fn stream_args(command: &str, size: u64, flags: &str, rest: &[String]) -> Vec<String> {
+    let mut args = vec![command.to_string()];
+    args.extend(flags.split_whitespace().map(String::from));
+    args.push("-S".to_string());
+    args.push(size.to_string());
+    args.extend(rest.iter().cloned());
+    args
+}
+
+/// Path an APK is pushed to before pm installs it
+pub fn staging_path(index: usize) -> String {
+    format!("/data/local/tmp/aim-install-{}-{}.apk", std::process::id(), index)
+}
+
+impl InstallCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Push the APKs to the device with the sync protocol
+    async fn push(&self, ctx: &CommandContext, apks: &[PathBuf], show_progress: bool, remotes: &mut Vec<String>) -> Result<()> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        let factory = ProgressFactory::new(show_progress);
+        for (index, apk) in apks.iter().enumerate() {
+            let size = std::fs::metadata(apk)?.len();
+            let name = apk.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            let remote = staging_path(index);
+            let mut transfer = FileTransfer::new(host, port, Some(&device.id)).await?;
+            transfer.set_progress_reporter(factory.file_transfer(&name, size));
+            remotes.push(remote.clone());
+            transfer.push(apk, &remote).await?;
+        }
+        Ok(())
+    }
+
+    /// Install pushed APKs: one with `pm install`, several through a session
+    async fn pm_install(&self, ctx: &CommandContext, remotes: &[String], flags: &str) -> Result<InstallOutcome> {
+        let pm = Pm::for_context(ctx)?;
+        if let [remote] = remotes {
+            return pm.install(remote, flags).await;
+        }
+
+        let session = pm.install_session(flags).await?;
+        for (index, remote) in remotes.iter().enumerate() {
+            let outcome = session.write(index, remote).await?;
+            if outcome != InstallOutcome::Success {
+                session.abandon().await?;
+                return Ok(outcome);
+            }
+        }
+        session.commit().await
+    }
+
+    /// Run a package manager command, feeding it `input` when given
+    ///
+    /// pm reads exactly the announced number of bytes and then answers, so
+    /// the answer is read only once the whole file is written.
+    async fn package_command(
+        &self,
+        ctx: &CommandContext,
+        features: &Features,
+        args: &[String],
+        input: Option<(&Path, Box<dyn ProgressReporter>)>,
+    ) -> Result<String> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        let mut conn = AdbConnection::connect(host, port).await?;
+        conn.select_device(&device.id).await?;
+        conn.send_command(&package_service(features, args))?;
+        conn.read_okay()?;
+        // Verifying a large APK can take a while before pm answers
+        conn.stream().set_read_timeout(None)?;
+        conn.stream().set_write_timeout(None)?;
+
+        if let Some((path, progress)) = input {
+            let mut file = File::open(path)?;
+            let mut buffer = vec![0u8; 64 * 1024];
+            let mut sent = 0u64;
+            loop {
+                let n = file.read(&mut buffer)?;
+                if n == 0 {
+                    break;
+                }
+                conn.write_all(&buffer[..n])?;
+                sent += n as u64;
+                progress.update(sent);
+            }
+            progress.finish();
+        }
+        conn.read_to_end()
+    }
+
+    /// Stream the APKs straight into pm, without a copy on the device
+    async fn stream_install(&self, ctx: &CommandContext, features: &Features, args: &InstallArgs, show_progress: bool) -> Result<InstallOutcome> {
+        let factory = ProgressFactory::new(show_progress);
+        let flags = pm_flags(args);
+        let sizes = args.apks.iter().map(|apk| -> Result<u64> { Ok(std::fs::metadata(apk)?.len()) }).collect::<Result<Vec<_>>>()?;
+        let progress = |apk: &Path, size: u64| {
+            let name = apk.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            factory.file_transfer(&name, size)
+        };
+
+        if let ([apk], [size]) = (args.apks.as_slice(), sizes.as_slice()) {
+            let command = stream_args("install", *size, &flags, &[]);
+            let input = (apk.as_path(), progress(apk.as_path(), *size));
+            let out = self.package_command(ctx, features, &command, Some(input)).await?;
+            return Ok(parse_install_output(&out));
+        }
+
+        let total = sizes.iter().sum();
+        let created = self.package_command(ctx, features, &stream_args("install-create", total, &flags, &[]), None).await?;
+        let session = parse_session_id(&created)
+            .ok_or_else(|| AimError::CommandExecution(format!("install-create failed: {}", created.trim())))?;
+        for (index, (apk, size)) in args.apks.iter().zip(&sizes).enumerate() {
+            let rest = [session.to_string(), format!("{}.apk", index), "-".to_string()];
+            let command = stream_args("install-write", *size, "", &rest);
+            let input = (apk.as_path(), progress(apk.as_path(), *size));
+            let out = self.package_command(ctx, features, &command, Some(input)).await?;
+            let outcome = parse_install_output(&out);
+            if outcome != InstallOutcome::Success {
+                let abandon = ["install-abandon".to_string(), session.to_string()];
+                self.package_command(ctx, features, &abandon, None).await?;
+                return Ok(outcome);
+            }
+        }
+        let commit = ["install-commit".to_string(), session.to_string()];
+        Ok(parse_install_output(&self.package_command(ctx, features, &commit, None).await?))
+    }
+
+    /// Push the APKs to /data/local/tmp, install them from there and remove the copies
+    async fn push_install(&self, ctx: &CommandContext, args: &InstallArgs, show_progress: bool) -> Result<InstallOutcome> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        let mut remotes = Vec::new();
+        let result = async {
+            self.push(ctx, &args.apks, show_progress, &mut remotes).await?;
+            let spinner = ProgressFactory::new(show_progress).command("pm install");
+            let outcome = self.pm_install(ctx, &remotes, &pm_flags(args)).await;
+            spinner.finish();
+            outcome
+        }
+        .await;
+
+        if !remotes.is_empty() {
+            let quoted: Vec<String> = remotes.iter().map(|r| shell_quote(r)).collect();
+            shell(host, port, &device.id, &format!("rm -f {}", quoted.join(" "))).await?;
+        }
+        result
+    }
+
+    /// Warn before installing an older version than the device has
+    ///
+    /// The APK's versionCode comes from aapt2; without it pm's own
+    /// INSTALL_FAILED_VERSION_DOWNGRADE is all there is.
+    async fn check_downgrade(&self, ctx: &CommandContext, args: &InstallArgs) -> Result<()> {
+        let Some((package, apk_code)) = apk_version(&args.apks[0]) else {
+            debug!("Could not read the versionCode of {} (needs aapt2)", args.apks[0].display());
+            return Ok(());
+        };
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        let installed = dumpsys::package(host, port, &device.id, &package).await.ok().flatten();
+        let Some(installed_code) = installed.and_then(|dump| dump.version_code) else {
+            return Ok(());
+        };
+        if apk_code >= installed_code {
+            return Ok(());
+        }
+        if args.downgrade {
+            eprintln!("Downgrading {} from versionCode {} to {}", package.bright_cyan(), installed_code, apk_code);
+        } else {
+            eprintln!(
+                "{} {} has versionCode {} on the device and {} in the APK; pm refuses a downgrade without --downgrade",
+                "⚠".yellow(),
+                package.bright_cyan(),
+                installed_code,
+                apk_code
+            );
+        }
+        Ok(())
+    }
+
+    fn check_apks(apks: &[PathBuf]) -> Result<()> {
+        for apk in apks {
+            if !Path::new(apk).is_file() {
+                return Err(AimError::InvalidArgument(format!("APK not found: {}", apk.display())));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubCommand for InstallCommand {
+    type Args = InstallArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        Self::check_apks(&args.apks)?;
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        let json = matches!(ctx.output_format, OutputFormat::Json);
+        let show_progress = !ctx.quiet && !json;
+        if !ctx.quiet {
+            self.check_downgrade(ctx, &args).await?;
+        }
+
+        // Streaming needs `cmd` (Android 7+); a device that turns the service
+        // down gets the push install instead
+        let features = features::for_device(host, port, &device.id).await;
+        let streamed = if args.no_streaming || !features.supports(Feature::Cmd) {
+            None
+        } else {
+            match self.stream_install(ctx, &features, &args, show_progress).await {
+                Err(AimError::AdbProtocol(msg)) => {
+                    debug!("Streamed install unavailable ({}), pushing the APKs first", msg);
+                    None
+                }
+                other => Some(other?),
+            }
+        };
+        let outcome = match streamed {
+            Some(outcome) => outcome,
+            None => self.push_install(ctx, &args, show_progress).await?,
+        };
+
+        let hint = match &outcome {
+            InstallOutcome::Failure { code: Some(code), .. } => failure_hint(code),
+            _ => None,
+        };
+        let report = InstallReport {
+            apks: args.apks.iter().map(|a| a.display().to_string()).collect(),
+            outcome: outcome.clone(),
+            hint,
+        };
+        if json {
+            crate::commands::format_json_output(&report)?;
+        }
+
+        match outcome {
+            InstallOutcome::Success => {
+                if !json && !ctx.quiet {
+                    println!("{} Installed {}", "✓".green(), report.apks.join(", ").bright_cyan());
+                }
+                Ok(())
+            }
+            InstallOutcome::Failure { code, message } => {
+                let reason = match (code, message.is_empty()) {
+                    (Some(code), true) => code,
+                    (Some(code), false) => format!("{}: {}", code, message),
+                    (None, _) => message,
+                };
+                Err(AimError::CommandExecution(match hint {
+                    Some(hint) => format!("Install failed: {} ({})", reason, hint),
+                    None => format!("Install failed: {}", reason),
+                }))
+            }
+        }
+    }
+}
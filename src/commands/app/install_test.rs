@@ -0,0 +1,44 @@
+use super::install::{package_service, parse_badging, pm_flags, stream_args, InstallArgs};
+use crate::adb::features::Features;
+
+fn args(replace: bool, grant: bool, test: bool) -> InstallArgs {
+    InstallArgs { apks: vec!["app.apk".into()], replace, grant, test, downgrade: false, no_streaming: false, device_id: None }
+}
+
+#[test]
+fn test_pm_flags() {
+    assert_eq!(pm_flags(&args(false, false, false)), "");
+    assert_eq!(pm_flags(&args(true, false, false)), " -r");
+    assert_eq!(pm_flags(&args(true, true, true)), " -r -g -t");
+    assert_eq!(pm_flags(&InstallArgs { downgrade: true, ..args(true, false, false) }), " -r -d");
+}
+
+#[test]
+fn test_parse_badging() {
+    let output = "package: name='com.example.app' versionCode='42' versionName='1.2.0' platformBuildVersionName='14' \
+                  platformBuildVersionCode='34' compileSdkVersion='34' compileSdkVersionCodename='14'\n\
+                  sdkVersion:'24'\n\
+                  targetSdkVersion:'34'\n";
+    assert_eq!(parse_badging(output), Some(("com.example.app".to_string(), 42)));
+    assert_eq!(parse_badging("ERROR: dump failed because no AndroidManifest.xml found\n"), None);
+}
+
+fn strings(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn test_stream_args() {
+    assert_eq!(stream_args("install", 1234, " -r -t", &[]), strings(&["install", "-r", "-t", "-S", "1234"]));
+    assert_eq!(
+        stream_args("install-write", 99, "", &strings(&["7", "1.apk", "-"])),
+        strings(&["install-write", "-S", "99", "7", "1.apk", "-"])
+    );
+}
+
+#[test]
+fn test_package_service() {
+    let args = strings(&["install", "-r", "-S", "1234"]);
+    assert_eq!(package_service(&Features::parse("cmd,abb_exec"), &args), "abb_exec:package\0install\0-r\0-S\x001234");
+    assert_eq!(package_service(&Features::parse("cmd"), &args), "exec:cmd package install -r -S 1234");
+}
@@ -0,0 +1,262 @@
+use crate::android::sdk::{self, Capability};
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::output::TableFormat;
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use colored::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+
+lazy_static! {
+    // "JOB #u0a123/42: 1a2b3c com.example/androidx.work.impl.background.systemjob.SystemJobService"
+    static ref JOB_RE: Regex = Regex::new(r"^\s*JOB #(\S+)/(-?\d+): \S+ (\S+)").unwrap();
+    // "#u0a123/42" wherever a job is referred to in the queues
+    static ref JOB_REF_RE: Regex = Regex::new(r"#(u\d+a\d+|\d+)/(-?\d+)").unwrap();
+}
+
+pub struct JobsCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct JobsArgs {
+    /// Package name (supports partial matching)
+    pub package: String,
+
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Run a job now, even if its constraints are not met
+    #[clap(long, value_name = "JOB_ID")]
+    pub run: Option<i32>,
+
+    /// Cancel a job, or all jobs of the package without an id
+    #[clap(long, value_name = "JOB_ID", num_args = 0..=1, conflicts_with = "run")]
+    pub cancel: Option<Option<i32>>,
+}
+
+/// A job from `dumpsys jobscheduler`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Job {
+    pub id: i32,
+    /// Uid the job runs as, e.g. `u0a123`
+    pub uid: String,
+    /// Package the job was scheduled for
+    pub package: String,
+    /// JobService that runs it
+    pub service: String,
+    /// active, pending, ready or waiting
+    pub state: String,
+    /// Repeat interval of a periodic job
+    pub periodic: Option<String>,
+    pub persisted: bool,
+    pub constraints: Vec<String>,
+    pub unsatisfied: Vec<String>,
+}
+
+impl TableFormat for Job {
+    fn headers() -> Vec<&'static str> {
+        vec!["ID", "SERVICE", "STATE", "CONSTRAINTS", "UNSATISFIED", "PERIODIC"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let list = |items: &[String]| if items.is_empty() { "-".to_string() } else { items.join(" ") };
+        vec![
+            self.id.to_string(),
+            self.service.clone(),
+            self.state.clone(),
+            list(&self.constraints),
+            list(&self.unsatisfied),
+            self.periodic.clone().unwrap_or_else(|| "-".to_string()),
+        ]
+    }
+}
+
+/// Constraint names of a `Required constraints: CONNECTIVITY TIMING_DELAY` line
+fn constraint_names(list: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in list.split_whitespace().map(|n| n.trim_matches(|c| c == '[' || c == ']')) {
+        let is_name = !name.is_empty() && name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+        if is_name && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Jobs of `package` in `dumpsys jobscheduler <pkg>`
+///
+/// A job is `active` while it runs, `pending` when it is queued to run,
+/// `ready` when its constraints are met and `waiting` otherwise.
+pub fn parse_jobs(output: &str, package: &str) -> Vec<Job> {
+    let mut jobs: Vec<Job> = Vec::new();
+    let mut active: Vec<(String, i32)> = Vec::new();
+    let mut pending: Vec<(String, i32)> = Vec::new();
+    // Which block the lines below belong to, and the indent of its header
+    let mut section = "";
+    let mut section_indent = 0;
+    let indent = |l: &str| l.len() - l.trim_start().len();
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if let Some(caps) = JOB_RE.captures(line) {
+            let service = caps[3].to_string();
+            jobs.push(Job {
+                uid: caps[1].to_string(),
+                id: caps[2].parse().unwrap_or(0),
+                package: service.split('/').next().unwrap_or_default().to_string(),
+                service,
+                ..Default::default()
+            });
+            section = "job";
+            section_indent = indent(line);
+            continue;
+        }
+        let next_section = if trimmed.starts_with("Pending queue") {
+            Some("pending")
+        } else if trimmed.starts_with("Active jobs") || trimmed.starts_with("Active executing jobs") {
+            Some("active")
+        } else if indent(line) <= section_indent {
+            Some("")
+        } else {
+            None
+        };
+        if let Some(next) = next_section {
+            section = next;
+            section_indent = indent(line);
+            continue;
+        }
+
+        match section {
+            "pending" | "active" => {
+                // A slot of an active job also lists the job it ran before
+                if trimmed.starts_with("Previous job") || trimmed.contains("inactive since") {
+                    continue;
+                }
+                if let Some(caps) = JOB_REF_RE.captures(trimmed) {
+                    let job = (caps[1].to_string(), caps[2].parse().unwrap_or(0));
+                    if section == "active" { active.push(job) } else { pending.push(job) }
+                }
+            }
+            "job" => {
+                let Some(job) = jobs.last_mut() else {
+                    continue;
+                };
+                if let Some(source) = trimmed.strip_prefix("Source: ") {
+                    if let Some(pkg) = source.split_whitespace().find_map(|f| f.strip_prefix("pkg=")) {
+                        job.package = pkg.to_string();
+                    }
+                } else if let Some(periodic) = trimmed.strip_prefix("PERIODIC: interval=") {
+                    let interval = periodic.split_whitespace().next().unwrap_or_default();
+                    job.periodic = Some(interval.trim_start_matches('+').to_string());
+                } else if trimmed == "PERSISTED" {
+                    job.persisted = true;
+                } else if let Some(list) = trimmed.strip_prefix("Required constraints:") {
+                    job.constraints = constraint_names(list);
+                } else if let Some(list) = trimmed.strip_prefix("Unsatisfied constraints:") {
+                    job.unsatisfied = constraint_names(list);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    jobs.retain(|job| job.package == package || job.service.starts_with(&format!("{}/", package)));
+    for job in &mut jobs {
+        let key = (job.uid.clone(), job.id);
+        job.state = if active.contains(&key) {
+            "active"
+        } else if pending.contains(&key) {
+            "pending"
+        } else if job.unsatisfied.is_empty() {
+            "ready"
+        } else {
+            "waiting"
+        }
+        .to_string();
+    }
+    jobs.sort_by_key(|job| job.id);
+    jobs
+}
+
+/// Error in the output of `cmd jobscheduler run|cancel`, which exits 0 either way
+pub fn jobscheduler_error(output: &str) -> Option<String> {
+    const ERRORS: &[&str] = &["Could not find", "not runnable", "No matching", "Exception", "Error", "not allowed"];
+    output
+        .lines()
+        .map(str::trim)
+        .find(|line| ERRORS.iter().any(|e| line.contains(e)))
+        .map(str::to_string)
+}
+
+impl JobsCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn jobscheduler(&self, ctx: &CommandContext, capability: Capability, command: String) -> Result<()> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        sdk::for_device(host, port, &device.id).await.require(capability)?;
+        let results = ctx.shell_batch([format!("{} 2>&1", command)]).await?;
+        let output = results.first().map(|o| o.stdout.as_str()).unwrap_or_default();
+        if let Some(error) = jobscheduler_error(output) {
+            return Err(AimError::CommandExecution(error));
+        }
+        if !ctx.quiet {
+            for line in output.lines().filter(|l| !l.trim().is_empty()) {
+                println!("{} {}", "✓".green(), line.trim());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubCommand for JobsCommand {
+    type Args = JobsArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let package = super::PackageResolver::new(ctx).resolve(&args.package).await?;
+
+        if let Some(id) = args.run {
+            let command = format!("cmd jobscheduler run -f {} {}", shell_quote(&package), id);
+            return self.jobscheduler(ctx, Capability::JobschedulerRun, command).await;
+        }
+        if let Some(id) = args.cancel {
+            let mut command = format!("cmd jobscheduler cancel {}", shell_quote(&package));
+            if let Some(id) = id {
+                command.push_str(&format!(" {}", id));
+            }
+            return self.jobscheduler(ctx, Capability::JobschedulerCancel, command).await;
+        }
+
+        let results = ctx
+            .shell_batch([format!("dumpsys jobscheduler {}", shell_quote(&package))])
+            .await?;
+        let output = results.first().map(|o| o.stdout.as_str()).unwrap_or_default();
+        let jobs = parse_jobs(output, &package);
+
+        match ctx.output_format {
+            OutputFormat::Json => crate::commands::format_json_output(&jobs)?,
+            OutputFormat::Plain => {
+                for job in &jobs {
+                    println!("{}\t{}\t{}", job.id, job.state, job.service);
+                }
+            }
+            OutputFormat::Table => {
+                if jobs.is_empty() {
+                    println!("No scheduled jobs for {}", package.bright_cyan());
+                } else {
+                    crate::output::OutputFormatter::new().table(&jobs)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use super::jobs::{jobscheduler_error, parse_jobs};
+
+const DUMPSYS: &str = "JOB SCHEDULER MANAGER (dumpsys jobscheduler)
+  Settings:
+    min_ready_non_active_jobs_count=5
+  Started users: [0]
+  Registered 3 jobs:
+    JOB #u0a123/42: 1a2b3c com.example/androidx.work.impl.background.systemjob.SystemJobService
+      u0a123 tag=*job*/com.example/androidx.work.impl.background.systemjob.SystemJobService#42
+      Source: uid=u0a123 user=0 pkg=com.example
+      JobInfo:
+        Service: com.example/androidx.work.impl.background.systemjob.SystemJobService
+        PERIODIC: interval=+15m0s0ms flex=+5m0s0ms
+        PERSISTED
+        Requires: charging=false batteryNotLow=false deviceIdle=false
+      Required constraints: CONNECTIVITY TIMING_DELAY [FLEXIBLE]
+      Satisfied constraints: CONNECTIVITY DEVICE_NOT_DOZING BACKGROUND_NOT_RESTRICTED
+      Unsatisfied constraints: TIMING_DELAY
+    JOB #u0a123/7: 4d5e6f com.example/.sync.SyncJobService
+      u0a123 tag=*job*/com.example/.sync.SyncJobService
+      Source: uid=u0a123 user=0 pkg=com.example
+      Required constraints: CHARGING
+      Satisfied constraints: CHARGING DEVICE_NOT_DOZING
+    JOB #1000/3: 7a8b9c android/com.android.server.pm.BackgroundDexOptService
+      Source: uid=1000 user=0 pkg=android
+      Required constraints: IDLE CHARGING
+      Unsatisfied constraints: IDLE
+
+  Connectivity:
+    Requested standby exceptions: {}
+
+  Pending queue:
+    Pending #0: 4d5e6f #u0a123/7 com.example/.sync.SyncJobService
+      Source: uid=u0a123 user=0 pkg=com.example
+  Active jobs:
+    Slot #0: inactive since -2m3s, stopped because: app called jobFinished
+    Slot #1: inactive since -10m
+";
+
+#[test]
+fn test_parse_jobs() {
+    let jobs = parse_jobs(DUMPSYS, "com.example");
+    assert_eq!(jobs.len(), 2);
+
+    let sync = &jobs[0];
+    assert_eq!(sync.id, 7);
+    assert_eq!(sync.service, "com.example/.sync.SyncJobService");
+    assert_eq!(sync.state, "pending");
+    assert_eq!(sync.constraints, vec!["CHARGING"]);
+    assert!(sync.unsatisfied.is_empty());
+    assert_eq!(sync.periodic, None);
+    assert!(!sync.persisted);
+
+    let work = &jobs[1];
+    assert_eq!(work.id, 42);
+    assert_eq!(work.uid, "u0a123");
+    assert_eq!(work.package, "com.example");
+    assert_eq!(work.state, "waiting");
+    assert_eq!(work.constraints, vec!["CONNECTIVITY", "TIMING_DELAY", "FLEXIBLE"]);
+    assert_eq!(work.unsatisfied, vec!["TIMING_DELAY"]);
+    assert_eq!(work.periodic.as_deref(), Some("15m0s0ms"));
+    assert!(work.persisted);
+}
+
+#[test]
+fn test_parse_jobs_active() {
+    let output = "  Registered 1 jobs:
+    JOB #u0a123/7: 4d5e6f com.example/.sync.SyncJobService
+      Required constraints: CHARGING
+  Active jobs:
+    Slot #0: #u0a123/7 com.example/.sync.SyncJobService
+      Running for: +3s120ms, timeout at: +9m56s880ms
+";
+    let jobs = parse_jobs(output, "com.example");
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].state, "active");
+
+    // With nothing queued or unmet, a job is ready
+    let output = "    JOB #u0a123/7: 4d5e6f com.example/.sync.SyncJobService\n";
+    assert_eq!(parse_jobs(output, "com.example")[0].state, "ready");
+    assert!(parse_jobs(output, "com.other").is_empty());
+}
+
+#[test]
+fn test_jobscheduler_error() {
+    assert_eq!(jobscheduler_error("Running job [FORCED]\n"), None);
+    assert_eq!(jobscheduler_error("Canceling job com.example/#42 in user 0\n"), None);
+    assert_eq!(
+        jobscheduler_error("Could not find job 99 in package com.example / user 0\n").as_deref(),
+        Some("Could not find job 99 in package com.example / user 0")
+    );
+    assert!(jobscheduler_error("Job not runnable\n").is_some());
+}
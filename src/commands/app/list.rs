@@ -1,8 +1,11 @@
+use crate::android::dumpsys::PackageDump;
+use crate::android::pm::{ListPackages, Origin, Pm, State};
 use crate::commands::SubCommand;
 use crate::core::context::CommandContext;
 use crate::core::types::OutputFormat;
 use crate::error::{AimError, Result};
 use crate::output::OutputFormatter;
+use crate::utils::shell_quote;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -29,9 +32,9 @@ pub struct ListArgs {
     #[clap(short, long)]
     pub filter: Option<String>,
     
-    /// Output format
-    #[clap(short, long, value_parser = ["table", "json", "plain"], default_value = "plain")]
-    pub output: String,
+    /// Output format; defaults to the global `-o`
+    #[clap(short, long, value_parser = ["table", "json", "plain"])]
+    pub output: Option<String>,
     
     /// Show only system apps
     #[clap(short, long)]
@@ -59,89 +62,53 @@ impl ListCommand {
     }
     
     async fn get_packages(&self, ctx: &CommandContext, args: &ListArgs) -> Result<Vec<String>> {
-        let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
-        
-        // Build pm list command
-        let mut cmd = "pm list packages -f".to_string();
-        
-        if args.user {
-            cmd.push_str(" -3"); // Third party apps only
-        } else if args.system {
-            cmd.push_str(" -s"); // System apps only
-        }
-        
-        if args.enabled {
-            cmd.push_str(" -e"); // Enabled apps only
-        } else if args.disabled {
-            cmd.push_str(" -d"); // Disabled apps only
-        }
-        
-        let shell_cmd = crate::adb::shell::ShellCommand::new(cmd)
-            .with_device(device.id.clone());
-        
-        let output = shell_cmd.execute(host, port).await?;
-        
-        // Parse package list
-        let packages: Vec<String> = output.stdout
-            .lines()
-            .filter_map(|line| {
-                // Format: package:/path/to/apk=com.example.app
-                if let Some(pkg) = line.strip_prefix("package:") {
-                    if let Some(eq_pos) = pkg.rfind('=') {
-                        let package_name = &pkg[eq_pos + 1..];
-                        
-                        // Apply filter if provided
-                        if let Some(filter) = &args.filter {
-                            if package_name.contains(filter) {
-                                return Some(package_name.to_string());
-                            }
-                        } else {
-                            return Some(package_name.to_string());
-                        }
-                    }
-                }
-                None
+        let query = ListPackages::new()
+            .origin(if args.user {
+                Origin::ThirdParty
+            } else if args.system {
+                Origin::System
+            } else {
+                Origin::All
             })
-            .collect();
-            
+            .state(if args.enabled {
+                State::Enabled
+            } else if args.disabled {
+                State::Disabled
+            } else {
+                State::Any
+            });
+        let mut packages = Pm::for_context(ctx)?.list_packages(&query).await?;
+
+        if let Some(filter) = &args.filter {
+            packages.retain(|package| package.contains(filter.as_str()));
+        }
         Ok(packages)
     }
     
     async fn get_app_details(&self, ctx: &CommandContext, packages: Vec<String>) -> Result<Vec<AppInfo>> {
         let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
         
         let mut apps = Vec::new();
+        let device_offset = crate::output::time::device_utc_offset(&device.id).await;
         
         for package in packages {
             // Get app info via dumpsys
-            let cmd = format!("dumpsys package {} | grep -E 'versionName=|firstInstallTime=|lastUpdateTime=|codePath='", package);
+            let cmd = format!("dumpsys package {}", shell_quote(&package));
             let shell_cmd = crate::adb::shell::ShellCommand::new(cmd)
                 .with_device(device.id.clone());
             
             let output = shell_cmd.execute(host, port).await?;
+            let dump = PackageDump::parse(&output.stdout).unwrap_or_default();
             
-            // Parse the output
-            let mut version = "Unknown".to_string();
-            let mut installed_at = "Unknown".to_string();
-            let mut is_system = false;
-            
-            for line in output.stdout.lines() {
-                if let Some(v) = line.strip_prefix("    versionName=") {
-                    version = v.to_string();
-                } else if line.contains("firstInstallTime=") {
-                    // Extract timestamp
-                    if let Some(start) = line.find("firstInstallTime=") {
-                        let timestamp_str = &line[start + 17..];
-                        if let Some(end) = timestamp_str.find(' ') {
-                            installed_at = timestamp_str[..end].to_string();
-                        }
-                    }
-                } else if line.contains("codePath=") {
-                    is_system = line.contains("/system/") || line.contains("/vendor/");
-                }
-            }
+            let version = dump.version_name.clone().unwrap_or_else(|| "Unknown".to_string());
+            // Device-local "YYYY-MM-DD HH:MM:SS"
+            let installed_at = dump
+                .first_install_time
+                .as_deref()
+                .and_then(|timestamp| crate::output::time::parse_device_local(timestamp, device_offset))
+                .map(|at| crate::output::time::format_time(at, device_offset))
+                .unwrap_or_else(|| "Unknown".to_string());
             
             // Get app label (user-friendly name)
             let label_cmd = format!("cmd package resolve-activity --brief {} | tail -n 1", package);
@@ -171,8 +138,8 @@ impl ListCommand {
                 version,
                 installed_at,
                 size: "N/A".to_string(), // Size calculation would be expensive
-                is_system,
-                is_enabled: true, // Would need additional check
+                is_system: dump.is_system(),
+                is_enabled: dump.is_enabled(),
             });
         }
         
@@ -201,8 +168,11 @@ impl SubCommand for ListCommand {
         }
         
         // Get output format
-        let output_format = OutputFormat::from_str(&args.output)
-            .ok_or_else(|| AimError::InvalidArgument(format!("Invalid output format: {}", args.output)))?;
+        let output_format = match &args.output {
+            Some(output) => OutputFormat::from_str(output)
+                .ok_or_else(|| AimError::InvalidArgument(format!("Invalid output format: {}", output)))?,
+            None => ctx.output_format,
+        };
         
         // Never print to stdout when outputting JSON (except the JSON itself)
         let is_json = matches!(output_format, OutputFormat::Json);
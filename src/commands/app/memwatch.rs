@@ -0,0 +1,279 @@
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::error::Result;
+use crate::utils::{format_duration, parse_duration};
+use async_trait::async_trait;
+use colored::*;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub struct MemwatchCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct MemwatchArgs {
+    /// Package name (supports partial matching)
+    pub package: String,
+
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Time between samples (e.g. 5s, 1m)
+    #[clap(short, long, default_value = "5s", value_parser = parse_duration)]
+    pub interval: Duration,
+
+    /// How long to sample for (e.g. 10m, 1h). Runs until Ctrl+C if omitted
+    #[clap(short, long, value_parser = parse_duration)]
+    pub duration: Option<Duration>,
+
+    /// Write the time series to a file (.csv, or .ndjson/.jsonl for NDJSON)
+    #[clap(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Memory figures parsed from `dumpsys meminfo <pkg>`, in KB
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MemInfo {
+    pub pss_kb: u64,
+    pub rss_kb: Option<u64>,
+    pub java_heap_kb: Option<u64>,
+    pub native_heap_kb: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct MemSample {
+    timestamp: String,
+    elapsed_secs: u64,
+    #[serde(flatten)]
+    mem: MemInfo,
+}
+
+/// Summary of a sampled series, used to spot steady growth
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemTrend {
+    pub first_kb: u64,
+    pub last_kb: u64,
+    pub min_kb: u64,
+    pub max_kb: u64,
+    /// Least-squares slope of PSS in KB per minute
+    pub slope_kb_per_min: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SeriesFormat {
+    Csv,
+    Ndjson,
+}
+
+impl SeriesFormat {
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("ndjson") | Some("jsonl") | Some("json") => SeriesFormat::Ndjson,
+            _ => SeriesFormat::Csv,
+        }
+    }
+}
+
+/// First number following `label` on a line, e.g. "Java Heap:   5524   17084"
+fn value_after(line: &str, label: &str) -> Option<u64> {
+    let idx = line.find(label)?;
+    line[idx + label.len()..]
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Parse the App Summary section of `dumpsys meminfo <pkg>`.
+///
+/// Returns None when the process is not running or the output is not recognised.
+pub fn parse_meminfo(output: &str) -> Option<MemInfo> {
+    let mut info = MemInfo::default();
+    let mut found_pss = false;
+
+    for line in output.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("Java Heap:") {
+            info.java_heap_kb = value_after(trimmed, "Java Heap:");
+        } else if trimmed.starts_with("Native Heap:") {
+            info.native_heap_kb = value_after(trimmed, "Native Heap:");
+        } else if trimmed.starts_with("TOTAL PSS:") {
+            if let Some(pss) = value_after(trimmed, "TOTAL PSS:") {
+                info.pss_kb = pss;
+                found_pss = true;
+            }
+            info.rss_kb = value_after(trimmed, "TOTAL RSS:");
+        } else if !found_pss && trimmed.starts_with("TOTAL ") {
+            // Older releases only have the "TOTAL  <pss> ..." row of the main table
+            if let Some(pss) = value_after(trimmed, "TOTAL") {
+                info.pss_kb = pss;
+                found_pss = true;
+            }
+        }
+    }
+
+    found_pss.then_some(info)
+}
+
+/// Compute the trend of a PSS series sampled at the given elapsed seconds
+pub fn compute_trend(points: &[(u64, u64)]) -> Option<MemTrend> {
+    let (first, last) = (points.first()?, points.last()?);
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| *x as f64).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| *y as f64).sum::<f64>() / n;
+    let (mut num, mut den) = (0.0, 0.0);
+    for (x, y) in points {
+        let dx = *x as f64 - mean_x;
+        num += dx * (*y as f64 - mean_y);
+        den += dx * dx;
+    }
+    let slope_per_sec = if den > 0.0 { num / den } else { 0.0 };
+
+    Some(MemTrend {
+        first_kb: first.1,
+        last_kb: last.1,
+        min_kb: points.iter().map(|(_, y)| *y).min()?,
+        max_kb: points.iter().map(|(_, y)| *y).max()?,
+        slope_kb_per_min: slope_per_sec * 60.0,
+    })
+}
+
+fn opt_kb(value: Option<u64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+impl MemwatchCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn sample(&self, ctx: &CommandContext, package: &str) -> Result<Option<MemInfo>> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+
+        let cmd = format!("dumpsys meminfo {}", package);
+        let output = crate::adb::shell::ShellCommand::new(cmd)
+            .with_device(device.id.clone())
+            .execute(host, port)
+            .await?;
+
+        Ok(parse_meminfo(&output.stdout))
+    }
+
+    fn write_sample(writer: &mut BufWriter<File>, format: SeriesFormat, sample: &MemSample) -> Result<()> {
+        match format {
+            SeriesFormat::Csv => writeln!(
+                writer,
+                "{},{},{},{},{},{}",
+                sample.timestamp,
+                sample.elapsed_secs,
+                sample.mem.pss_kb,
+                opt_kb(sample.mem.rss_kb),
+                opt_kb(sample.mem.java_heap_kb),
+                opt_kb(sample.mem.native_heap_kb)
+            )?,
+            SeriesFormat::Ndjson => writeln!(writer, "{}", serde_json::to_string(sample)?)?,
+        }
+        // Flush every sample so a killed session still leaves usable data
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn print_summary(samples: &[MemSample], elapsed: Duration) {
+        let points: Vec<(u64, u64)> = samples.iter().map(|s| (s.elapsed_secs, s.mem.pss_kb)).collect();
+        let Some(trend) = compute_trend(&points) else {
+            println!("{}", "No samples collected".yellow());
+            return;
+        };
+
+        let change = trend.last_kb as i64 - trend.first_kb as i64;
+        println!();
+        println!("{}", "Summary".bold());
+        println!("  Samples:   {} over {}", samples.len(), format_duration(elapsed));
+        println!("  PSS start: {} KB", trend.first_kb);
+        println!("  PSS end:   {} KB ({:+} KB)", trend.last_kb, change);
+        println!("  PSS range: {} - {} KB", trend.min_kb, trend.max_kb);
+        println!("  Trend:     {:+.1} KB/min", trend.slope_kb_per_min);
+
+        // Flag sustained growth of more than 10% across the session
+        if samples.len() >= 3 && trend.slope_kb_per_min > 0.0 && change * 10 > trend.first_kb as i64 {
+            println!("  {} PSS grew steadily; possible leak", "⚠".yellow());
+        } else {
+            println!("  {} No sustained growth", "✓".green());
+        }
+    }
+}
+
+#[async_trait]
+impl SubCommand for MemwatchCommand {
+    type Args = MemwatchArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let package = super::PackageResolver::new(ctx).resolve(&args.package).await?;
+
+        let mut writer = match &args.output {
+            Some(path) => {
+                let format = SeriesFormat::from_path(path);
+                let mut writer = BufWriter::new(File::create(path)?);
+                if format == SeriesFormat::Csv {
+                    writeln!(writer, "timestamp,elapsed_secs,pss_kb,rss_kb,java_heap_kb,native_heap_kb")?;
+                }
+                Some((writer, format))
+            }
+            None => None,
+        };
+
+        println!("Watching memory of {} every {}", package.bright_cyan(), format_duration(args.interval));
+        println!("Press Ctrl+C to stop");
+        println!();
+        println!("{:>8}  {:>10}  {:>10}  {:>10}  {:>10}", "ELAPSED", "PSS", "RSS", "JAVA", "NATIVE");
+
+        let start = Instant::now();
+        let mut samples: Vec<MemSample> = Vec::new();
+
+        loop {
+            let elapsed = start.elapsed();
+            match self.sample(ctx, &package).await? {
+                Some(mem) => {
+                    let sample = MemSample {
+                        timestamp: chrono::Local::now().to_rfc3339(),
+                        elapsed_secs: elapsed.as_secs(),
+                        mem,
+                    };
+                    println!(
+                        "{:>8}  {:>10}  {:>10}  {:>10}  {:>10}",
+                        format_duration(elapsed),
+                        sample.mem.pss_kb,
+                        opt_kb(sample.mem.rss_kb),
+                        opt_kb(sample.mem.java_heap_kb),
+                        opt_kb(sample.mem.native_heap_kb)
+                    );
+                    if let Some((writer, format)) = writer.as_mut() {
+                        Self::write_sample(writer, *format, &sample)?;
+                    }
+                    samples.push(sample);
+                }
+                None => println!("{:>8}  {}", format_duration(elapsed), "process not running".dimmed()),
+            }
+
+            let next = args.interval.saturating_sub(start.elapsed().saturating_sub(elapsed));
+            if args.duration.is_some_and(|d| start.elapsed() + next > d) {
+                break;
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(next) => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+        }
+
+        Self::print_summary(&samples, start.elapsed());
+        if let Some(path) = &args.output {
+            println!("  Series written to {}", path.display().to_string().bright_cyan());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use super::memwatch::{compute_trend, parse_meminfo, MemInfo};
+
+const MEMINFO: &str = r#"Applications Memory Usage (in Kilobytes):
+Uptime: 123456 Realtime: 123456
+
+** MEMINFO in pid 4321 [com.example.app] **
+                   Pss  Private  Private  SwapPss      Rss     Heap     Heap     Heap
+                 Total    Dirty    Clean    Dirty    Total     Size    Alloc     Free
+                ------   ------   ------   ------   ------   ------   ------   ------
+  Native Heap     3380     3300        0       12     4712    10240     6000     4240
+  Dalvik Heap     5200     5100        0        8    14000    12000     9000     3000
+        TOTAL    27330    19000     4000       40    86128    22240    15000     7240
+
+ App Summary
+                       Pss(KB)                        Rss(KB)
+                        ------                         ------
+           Java Heap:     5524                          17084
+         Native Heap:     3380                           4712
+                Code:    11664                          61104
+
+           TOTAL PSS:    27330            TOTAL RSS:      86128       TOTAL SWAP PSS:       40
+"#;
+
+#[test]
+fn test_parse_meminfo_app_summary() {
+    assert_eq!(
+        parse_meminfo(MEMINFO),
+        Some(MemInfo {
+            pss_kb: 27330,
+            rss_kb: Some(86128),
+            java_heap_kb: Some(5524),
+            native_heap_kb: Some(3380),
+        })
+    );
+}
+
+#[test]
+fn test_parse_meminfo_legacy_total_row() {
+    let output = "  Native Heap     3380     3300        0\n        TOTAL    12000    9000     100\n";
+    let info = parse_meminfo(output).unwrap();
+    assert_eq!(info.pss_kb, 12000);
+    assert_eq!(info.rss_kb, None);
+}
+
+#[test]
+fn test_parse_meminfo_no_process() {
+    assert_eq!(parse_meminfo("No process found for: com.example.app"), None);
+}
+
+#[test]
+fn test_compute_trend() {
+    let trend = compute_trend(&[(0, 1000), (60, 1100), (120, 1200)]).unwrap();
+    assert_eq!(trend.first_kb, 1000);
+    assert_eq!(trend.last_kb, 1200);
+    assert_eq!(trend.min_kb, 1000);
+    assert_eq!(trend.max_kb, 1200);
+    assert!((trend.slope_kb_per_min - 100.0).abs() < 1e-9);
+
+    assert!(compute_trend(&[]).is_none());
+    assert_eq!(compute_trend(&[(0, 500)]).unwrap().slope_kb_per_min, 0.0);
+}
@@ -3,58 +3,163 @@ use crate::error::Result;
 use crate::core::context::CommandContext;
 use crate::commands::SubCommand;
 
+mod apply;
 mod list;
+mod memwatch;
 mod clear;
+mod compare;
+mod debloat;
+mod enable;
+mod export_data;
+mod grant;
+mod install;
+mod jobs;
+mod permissions;
 mod pull;
+mod service;
+mod snapshot;
 mod backup;
 mod stop;
 mod start;
+mod uninstall;
+mod package;
 
+pub use apply::ApplyCommand;
 pub use list::ListCommand;
+pub use memwatch::MemwatchCommand;
 pub use clear::ClearCommand;
+pub use compare::CompareCommand;
+pub use debloat::DebloatCommand;
+pub use enable::{DisableCommand, EnableCommand};
+pub use export_data::ExportDataCommand;
+pub use grant::{GrantCommand, RevokeCommand};
+pub use install::InstallCommand;
+pub use jobs::JobsCommand;
+pub use permissions::PermissionsCommand;
 pub use pull::PullCommand;
+pub use service::{ServiceCommand, ServicesCommand};
+pub use snapshot::{DiffCommand, SnapshotCommand};
 pub use backup::BackupCommand;
 pub use stop::StopCommand;
 pub use start::StartCommand;
+pub use uninstall::UninstallCommand;
+pub use package::PackageResolver;
 
 #[derive(Debug, Clone, Subcommand)]
 pub enum AppCommands {
+    /// Bring installed apps, their state and permissions in line with a manifest
+    Apply(apply::ApplyArgs),
+    
     /// Backup app data
     Backup(backup::BackupArgs),
     
     /// Clear app data
     Clear(clear::ClearArgs),
     
+    /// Show an app's version on every connected device, marking the out-of-date ones
+    Compare(compare::CompareArgs),
+    
+    /// Remove system packages according to a keep/remove profile
+    Debloat(debloat::DebloatArgs),
+    
+    /// Compare installed packages with a snapshot
+    Diff(snapshot::DiffArgs),
+    
+    /// Disable an app without uninstalling it
+    Disable(enable::StateChangeArgs),
+    
+    /// Enable a disabled app
+    Enable(enable::StateChangeArgs),
+    
+    /// Save an app's private data as a tar archive, through run-as or root
+    ExportData(export_data::ExportDataArgs),
+    
+    /// Grant runtime permissions to an app
+    Grant(grant::PermissionChangeArgs),
+    
+    /// Install an APK, or a base APK with its splits
+    Install(install::InstallArgs),
+    
+    /// Show scheduled jobs, or run or cancel one
+    Jobs(jobs::JobsArgs),
+    
     /// List installed applications
     #[command(alias = "ls")]
     List(list::ListArgs),
     
+    /// Sample app memory usage over time
+    Memwatch(memwatch::MemwatchArgs),
+    
+    /// Show, export or restore runtime permissions and app ops
+    Permissions(permissions::PermissionsArgs),
+    
     /// Pull APK from device
     Pull(pull::PullArgs),
     
+    /// Revoke runtime permissions from an app
+    Revoke(grant::PermissionChangeArgs),
+    
+    /// Start or stop a service of an app
+    Service(service::ServiceArgs),
+    
+    /// List the running services of an app with their start and bind state
+    Services(service::ServicesArgs),
+    
+    /// Save the installed packages and their versions
+    Snapshot(snapshot::SnapshotArgs),
+    
     /// Start an app
     Start(start::StartArgs),
     
     /// Force stop an app
     Stop(stop::StopArgs),
+    
+    /// Uninstall apps, given by name or listed in a file
+    Uninstall(uninstall::UninstallArgs),
 }
 
 impl AppCommands {
+    /// Whether the subcommand works on every connected device instead of one
+    pub fn all_devices(&self) -> bool {
+        matches!(self, AppCommands::Compare(_))
+    }
+
     /// Get the device_id from any app subcommand
     pub fn device_id(&self) -> Option<&str> {
         match self {
+            AppCommands::Apply(args) => args.device_id.as_deref(),
             AppCommands::Backup(args) => args.device_id.as_deref(),
             AppCommands::Clear(args) => args.device_id.as_deref(),
+            AppCommands::Compare(_) => None,
+            AppCommands::Debloat(args) => args.device_id.as_deref(),
+            AppCommands::Diff(args) => args.device_id.as_deref(),
+            AppCommands::Disable(args) => args.device_id.as_deref(),
+            AppCommands::Enable(args) => args.device_id.as_deref(),
+            AppCommands::ExportData(args) => args.device_id.as_deref(),
+            AppCommands::Grant(args) => args.device_id.as_deref(),
+            AppCommands::Install(args) => args.device_id.as_deref(),
+            AppCommands::Jobs(args) => args.device_id.as_deref(),
             AppCommands::List(args) => args.device_id.as_deref(),
-            AppCommands::Pull(args) => args.device_id.as_deref(),
+            AppCommands::Memwatch(args) => args.device_id.as_deref(),
+            AppCommands::Permissions(args) => args.device_id.as_deref(),
+            AppCommands::Pull(args) => args.device(),
+            AppCommands::Revoke(args) => args.device_id.as_deref(),
+            AppCommands::Service(args) => args.device_id.as_deref(),
+            AppCommands::Services(args) => args.device_id.as_deref(),
+            AppCommands::Snapshot(args) => args.device_id.as_deref(),
             AppCommands::Start(args) => args.device_id.as_deref(),
             AppCommands::Stop(args) => args.device_id.as_deref(),
+            AppCommands::Uninstall(args) => args.device_id.as_deref(),
         }
     }
 }
 
 pub async fn run(ctx: &CommandContext, cmd: AppCommands) -> Result<()> {
     match cmd {
+        AppCommands::Apply(args) => {
+            let cmd = ApplyCommand::new();
+            cmd.run(ctx, args).await
+        }
         AppCommands::Backup(args) => {
             let cmd = BackupCommand::new();
             cmd.run(ctx, args).await
@@ -63,14 +168,74 @@ pub async fn run(ctx: &CommandContext, cmd: AppCommands) -> Result<()> {
             let cmd = ClearCommand::new();
             cmd.run(ctx, args).await
         }
+        AppCommands::Compare(args) => {
+            let cmd = CompareCommand::new();
+            cmd.run(ctx, args).await
+        }
+        AppCommands::Debloat(args) => {
+            let cmd = DebloatCommand::new();
+            cmd.run(ctx, args).await
+        }
+        AppCommands::Diff(args) => {
+            let cmd = DiffCommand::new();
+            cmd.run(ctx, args).await
+        }
+        AppCommands::Disable(args) => {
+            let cmd = DisableCommand::new();
+            cmd.run(ctx, args).await
+        }
+        AppCommands::Enable(args) => {
+            let cmd = EnableCommand::new();
+            cmd.run(ctx, args).await
+        }
+        AppCommands::ExportData(args) => {
+            let cmd = ExportDataCommand::new();
+            cmd.run(ctx, args).await
+        }
+        AppCommands::Grant(args) => {
+            let cmd = GrantCommand::new();
+            cmd.run(ctx, args).await
+        }
+        AppCommands::Install(args) => {
+            let cmd = InstallCommand::new();
+            cmd.run(ctx, args).await
+        }
+        AppCommands::Jobs(args) => {
+            let cmd = JobsCommand::new();
+            cmd.run(ctx, args).await
+        }
         AppCommands::List(args) => {
             let cmd = ListCommand::new();
             cmd.run(ctx, args).await
         }
+        AppCommands::Memwatch(args) => {
+            let cmd = MemwatchCommand::new();
+            cmd.run(ctx, args).await
+        }
+        AppCommands::Permissions(args) => {
+            let cmd = PermissionsCommand::new();
+            cmd.run(ctx, args).await
+        }
         AppCommands::Pull(args) => {
             let cmd = PullCommand::new();
             cmd.run(ctx, args).await
         }
+        AppCommands::Revoke(args) => {
+            let cmd = RevokeCommand::new();
+            cmd.run(ctx, args).await
+        }
+        AppCommands::Service(args) => {
+            let cmd = ServiceCommand::new();
+            cmd.run(ctx, args).await
+        }
+        AppCommands::Services(args) => {
+            let cmd = ServicesCommand::new();
+            cmd.run(ctx, args).await
+        }
+        AppCommands::Snapshot(args) => {
+            let cmd = SnapshotCommand::new();
+            cmd.run(ctx, args).await
+        }
         AppCommands::Start(args) => {
             let cmd = StartCommand::new();
             cmd.run(ctx, args).await
@@ -79,5 +244,50 @@ pub async fn run(ctx: &CommandContext, cmd: AppCommands) -> Result<()> {
             let cmd = StopCommand::new();
             cmd.run(ctx, args).await
         }
+        AppCommands::Uninstall(args) => {
+            let cmd = UninstallCommand::new();
+            cmd.run(ctx, args).await
+        }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod apply_test;
+
+#[cfg(test)]
+mod compare_test;
+
+#[cfg(test)]
+mod debloat_test;
+
+#[cfg(test)]
+mod export_data_test;
+
+#[cfg(test)]
+mod grant_test;
+
+#[cfg(test)]
+mod install_test;
+
+#[cfg(test)]
+mod jobs_test;
+
+#[cfg(test)]
+mod memwatch_test;
+
+#[cfg(test)]
+mod package_test;
+
+#[cfg(test)]
+mod permissions_test;
+
+#[cfg(test)]
+mod pull_test;
+
+#[cfg(test)]
+mod service_test;
+
+#[cfg(test)]
+mod snapshot_test;
+
+#[cfg(test)]
+mod uninstall_test;
@@ -0,0 +1,106 @@
+//! Resolving a partial package name
+//!
+//! [`PackageResolver`] lists the installed packages of the selected device
+//! once per run and matches what the user typed against them: the exact name,
+//! a part of it, a dotted abbreviation (`c.e.app` for `com.example.app`) or,
+//! failing those, the letters in order (`gmaps`).
+
+use crate::android::pm::{ListPackages, Pm};
+use crate::core::context::CommandContext;
+use crate::error::{AimError, Result};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Package lists already read in this run, by device serial
+static CACHE: OnceLock<Mutex<HashMap<String, Arc<Vec<String>>>>> = OnceLock::new();
+
+/// Whether each dot-separated part of `query` starts the same part of `package`
+pub fn matches_abbreviation(package: &str, query: &str) -> bool {
+    let parts: Vec<&str> = query.split('.').collect();
+    let segments: Vec<&str> = package.split('.').collect();
+    parts.len() > 1
+        && parts.len() == segments.len()
+        && parts.iter().zip(&segments).all(|(part, segment)| segment.starts_with(part))
+}
+
+/// Whether the letters of `query` appear in `package` in order
+pub fn matches_subsequence(package: &str, query: &str) -> bool {
+    let mut chars = package.chars();
+    query.chars().all(|q| chars.any(|c| c == q))
+}
+
+/// Packages matching `query`, from the first rule that matches anything:
+/// exact name, substring, dotted abbreviation, letters in order
+pub fn match_packages<'a>(packages: &'a [String], query: &str) -> Vec<&'a String> {
+    let query = query.trim().to_lowercase();
+    let rules: [&dyn Fn(&str) -> bool; 4] = [
+        &|p| p == query,
+        &|p| p.contains(&query),
+        &|p| matches_abbreviation(p, &query),
+        &|p| matches_subsequence(p, &query),
+    ];
+    rules
+        .iter()
+        .map(|rule| packages.iter().filter(|p| rule(&p.to_lowercase())).collect::<Vec<_>>())
+        .find(|matches| !matches.is_empty())
+        .unwrap_or_default()
+}
+
+/// The package meant by `partial`, given the candidates that matched it
+///
+/// An exact name wins; otherwise several matches are an error listing them,
+/// or a prompt with `--pick`.
+pub fn select_package(partial: &str, mut matches: Vec<String>) -> Result<String> {
+    if let Some(exact) = matches.iter().position(|m| m == partial) {
+        return Ok(matches.swap_remove(exact));
+    }
+    match matches.len() {
+        0 => Err(AimError::CommandExecution(format!("No package found matching '{}'", partial))),
+        1 => Ok(matches.remove(0)),
+        _ if crate::core::pick::is_enabled() => {
+            let index = crate::core::pick::choose("package", &matches)?;
+            Ok(matches.swap_remove(index))
+        }
+        _ => {
+            matches.sort();
+            Err(AimError::Ambiguous {
+                query: partial.to_string(),
+                what: "packages",
+                headers: vec!["PACKAGE"],
+                rows: matches.into_iter().map(|m| vec![m]).collect(),
+            })
+        }
+    }
+}
+
+/// Finds packages on the context's device
+pub struct PackageResolver<'a> {
+    ctx: &'a CommandContext,
+}
+
+impl<'a> PackageResolver<'a> {
+    pub fn new(ctx: &'a CommandContext) -> Self {
+        Self { ctx }
+    }
+
+    /// Installed packages, read from the device on first use
+    pub async fn packages(&self) -> Result<Arc<Vec<String>>> {
+        let device = self.ctx.require_device()?;
+        let serial = device.id.to_string();
+        let cache = CACHE.get_or_init(Default::default);
+        if let Some(packages) = cache.lock().unwrap().get(&serial) {
+            return Ok(packages.clone());
+        }
+
+        let packages = Arc::new(Pm::for_context(self.ctx)?.list_packages(&ListPackages::new()).await?);
+        cache.lock().unwrap().insert(serial, packages.clone());
+        Ok(packages)
+    }
+
+    /// The full name of the package `partial` refers to
+    pub async fn resolve(&self, partial: &str) -> Result<String> {
+        let packages = self.packages().await?;
+        let matches = match_packages(&packages, partial).into_iter().cloned().collect();
+        select_package(partial, matches)
+    }
+}
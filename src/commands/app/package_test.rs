@@ -0,0 +1,58 @@
+use super::package::{match_packages, matches_abbreviation, select_package};
+use crate::android::pm::parse_packages as parse_package_list;
+use crate::error::AimError;
+
+fn packages(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| n.to_string()).collect()
+}
+
+#[test]
+fn test_select_package() {
+    assert_eq!(select_package("chrome", packages(&["com.android.chrome"])).unwrap(), "com.android.chrome");
+    assert_eq!(
+        select_package("com.example", packages(&["com.example.debug", "com.example"])).unwrap(),
+        "com.example"
+    );
+    assert!(matches!(select_package("nope", Vec::new()), Err(AimError::CommandExecution(_))));
+}
+
+#[test]
+fn test_select_package_ambiguous_lists_candidates() {
+    let err = select_package("google", packages(&["com.google.maps", "com.google.android.gm"])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "'google' matches 2 packages:\n\
+         \x20 PACKAGE\n\
+         \x20 com.google.android.gm\n\
+         \x20 com.google.maps\n\
+         Give more of the name, or add --pick to choose one"
+    );
+}
+
+const PM_LIST: &str = "package:com.example.app\npackage:com.example.app.debug\npackage:com.google.android.apps.maps\npackage:com.android.chrome\n";
+
+#[test]
+fn test_parse_package_list() {
+    assert_eq!(parse_package_list(PM_LIST).len(), 4);
+    assert_eq!(parse_package_list("\n"), Vec::<String>::new());
+}
+
+#[test]
+fn test_match_packages() {
+    let packages = parse_package_list(PM_LIST);
+    let names = |query: &str| -> Vec<&str> { match_packages(&packages, query).into_iter().map(String::as_str).collect() };
+
+    assert_eq!(names("com.example.app"), vec!["com.example.app"]);
+    assert_eq!(names("Chrome"), vec!["com.android.chrome"]);
+    assert_eq!(names("example"), vec!["com.example.app", "com.example.app.debug"]);
+    assert_eq!(names("c.e.app"), vec!["com.example.app"]);
+    assert_eq!(names("gmaps"), vec!["com.google.android.apps.maps"]);
+    assert!(names("zzz").is_empty());
+}
+
+#[test]
+fn test_matches_abbreviation() {
+    assert!(matches_abbreviation("com.example.app", "c.e.a"));
+    assert!(!matches_abbreviation("com.example.app.debug", "c.e.app"));
+    assert!(!matches_abbreviation("com.example.app", "app"));
+}
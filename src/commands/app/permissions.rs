@@ -0,0 +1,234 @@
+use crate::adb::shell::{ShellBatch, ShellCommand};
+use crate::android::dumpsys::PackageDump;
+use crate::android::pm;
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub struct PermissionsCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct PermissionsArgs {
+    /// Package name (supports partial matching)
+    pub package: String,
+
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Save the runtime permissions and app ops to a JSON file
+    #[clap(long, conflicts_with = "import")]
+    pub export: Option<PathBuf>,
+
+    /// Restore runtime permissions and app ops from a JSON file
+    #[clap(long)]
+    pub import: Option<PathBuf>,
+}
+
+/// Runtime permission grants and app op modes of a package
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PermissionSnapshot {
+    pub package: String,
+    /// Runtime permission to whether it is granted
+    #[serde(default)]
+    pub permissions: BTreeMap<String, bool>,
+    /// App op to its mode (allow, ignore, deny, default, foreground)
+    #[serde(default)]
+    pub appops: BTreeMap<String, String>,
+}
+
+/// A change to bring a package's state in line with a snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Grant(String),
+    Revoke(String),
+    SetAppOp(String, String),
+}
+
+impl Change {
+    /// The shell command that makes the change
+    pub fn command(&self, package: &str) -> String {
+        match self {
+            Change::Grant(permission) => pm::grant_command(package, permission),
+            Change::Revoke(permission) => pm::revoke_command(package, permission),
+            Change::SetAppOp(op, mode) => {
+                format!("appops set {} {} {}", shell_quote(package), shell_quote(op), shell_quote(mode))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Grant(permission) => write!(f, "grant {}", permission),
+            Change::Revoke(permission) => write!(f, "revoke {}", permission),
+            Change::SetAppOp(op, mode) => write!(f, "appop {} -> {}", op, mode),
+        }
+    }
+}
+
+/// Per-package app op modes from `appops get <pkg>`
+///
+/// Lines look like `CAMERA: allow; time=+5m ago`. `Uid mode:` lines are
+/// per uid rather than per package and are left out.
+pub fn parse_appops(output: &str) -> BTreeMap<String, String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("Uid mode:"))
+        .filter_map(|line| {
+            let (op, rest) = line.split_once(": ")?;
+            if op.is_empty() || !op.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_') {
+                return None;
+            }
+            let mode = rest.split([';', ' ']).next()?.trim();
+            (!mode.is_empty()).then(|| (op.to_string(), mode.to_string()))
+        })
+        .collect()
+}
+
+/// What has to change on the device (`current`) to match `wanted`
+pub fn diff(current: &PermissionSnapshot, wanted: &PermissionSnapshot) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for (permission, &granted) in &wanted.permissions {
+        if current.permissions.get(permission) != Some(&granted) {
+            changes.push(if granted {
+                Change::Grant(permission.clone())
+            } else {
+                Change::Revoke(permission.clone())
+            });
+        }
+    }
+    for (op, mode) in &wanted.appops {
+        if current.appops.get(op) != Some(mode) {
+            changes.push(Change::SetAppOp(op.clone(), mode.clone()));
+        }
+    }
+    changes
+}
+
+impl PermissionsCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn snapshot(&self, ctx: &CommandContext, package: &str) -> Result<PermissionSnapshot> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        let outputs = ShellBatch::new()
+            .with_device(device.id.clone())
+            .command(format!("dumpsys package {}", shell_quote(package)))
+            .command(format!("appops get {}", shell_quote(package)))
+            .execute(host, port)
+            .await?;
+        let output = |i: usize| outputs.get(i).map(|o| o.stdout.as_str()).unwrap_or_default();
+        Ok(PermissionSnapshot {
+            package: package.to_string(),
+            permissions: PackageDump::parse(output(0)).map(|dump| dump.runtime_permissions()).unwrap_or_default(),
+            appops: parse_appops(output(1)),
+        })
+    }
+
+    fn print(snapshot: &PermissionSnapshot) {
+        println!("{}", snapshot.package.bright_cyan().bold());
+        println!("{}", "Runtime permissions".bold());
+        for (permission, granted) in &snapshot.permissions {
+            let state = if *granted { "granted".green() } else { "denied".dimmed() };
+            println!("  {:<60} {}", permission, state);
+        }
+        if !snapshot.appops.is_empty() {
+            println!("{}", "App ops".bold());
+            for (op, mode) in &snapshot.appops {
+                println!("  {:<60} {}", op, mode);
+            }
+        }
+    }
+
+    async fn import(&self, ctx: &CommandContext, package: &str, path: &Path) -> Result<()> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| AimError::InvalidArgument(format!("Cannot read {}: {}", path.display(), e)))?;
+        let wanted: PermissionSnapshot = serde_json::from_str(&contents)?;
+        if wanted.package != package && !ctx.quiet {
+            println!(
+                "{} {} was exported from {}",
+                "Note:".yellow(),
+                path.display(),
+                wanted.package
+            );
+        }
+
+        let current = self.snapshot(ctx, package).await?;
+        let changes = diff(&current, &wanted);
+        if changes.is_empty() {
+            if !ctx.quiet {
+                println!("{} {} already matches {}", "✓".green(), package, path.display());
+            }
+            return Ok(());
+        }
+
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        let mut failed = 0;
+        for change in &changes {
+            let out = ShellCommand::new(format!("{} 2>&1", change.command(package)))
+                .with_device(device.id.clone())
+                .execute(host, port)
+                .await?;
+            // pm grant/revoke and appops set print nothing when they work
+            if out.stdout.trim().is_empty() {
+                if !ctx.quiet {
+                    println!("{} {}", "✓".green(), change);
+                }
+            } else {
+                failed += 1;
+                println!("{} {}: {}", "✗".red(), change, out.stdout.trim());
+            }
+        }
+        if failed > 0 {
+            return Err(AimError::CommandExecution(format!("{} of {} changes failed", failed, changes.len())));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubCommand for PermissionsCommand {
+    type Args = PermissionsArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let package = super::PackageResolver::new(ctx).resolve(&args.package).await?;
+
+        if let Some(path) = &args.import {
+            return self.import(ctx, &package, path).await;
+        }
+
+        let snapshot = self.snapshot(ctx, &package).await?;
+        if let Some(path) = &args.export {
+            std::fs::write(path, serde_json::to_string_pretty(&snapshot)?)?;
+            if !ctx.quiet {
+                println!(
+                    "{} Saved {} permissions and {} app ops of {} to {}",
+                    "✓".green(),
+                    snapshot.permissions.len(),
+                    snapshot.appops.len(),
+                    package.bright_cyan(),
+                    path.display()
+                );
+            }
+            return Ok(());
+        }
+
+        match ctx.output_format {
+            OutputFormat::Json => crate::commands::format_json_output(&snapshot)?,
+            _ => Self::print(&snapshot),
+        }
+        Ok(())
+    }
+}
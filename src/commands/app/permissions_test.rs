@@ -0,0 +1,73 @@
+use super::permissions::{diff, parse_appops, Change, PermissionSnapshot};
+use crate::android::dumpsys::PackageDump;
+use std::collections::BTreeMap;
+
+const DUMPSYS: &str = "Packages:
+  Package [com.example] (5e6f7a8):
+    install permissions:
+      android.permission.INTERNET: granted=true
+    User 0: ceDataInode=12345 installed=true hidden=false suspended=false
+      gids=[3003]
+      runtime permissions:
+        android.permission.CAMERA: granted=true, flags=[ USER_SET|USER_SENSITIVE_WHEN_GRANTED ]
+        android.permission.RECORD_AUDIO: granted=false, flags=[ USER_SENSITIVE_WHEN_GRANTED ]
+      disabledComponents:
+    User 10: ceDataInode=0 installed=true
+      runtime permissions:
+        android.permission.CAMERA: granted=false
+";
+
+const APPOPS: &str = "Uid mode: COARSE_LOCATION: foreground
+CAMERA: allow; time=+5m12s ago; duration=+1s
+RUN_IN_BACKGROUND: ignore
+WAKE_LOCK: allow; time=+1h ago
+";
+
+fn parse_runtime_permissions(output: &str) -> BTreeMap<String, bool> {
+    PackageDump::parse(output).map(|dump| dump.runtime_permissions()).unwrap_or_default()
+}
+
+#[test]
+fn test_parse_runtime_permissions() {
+    let permissions = parse_runtime_permissions(DUMPSYS);
+    assert_eq!(permissions.len(), 2);
+    assert!(permissions["android.permission.CAMERA"]);
+    assert!(!permissions["android.permission.RECORD_AUDIO"]);
+    assert!(!permissions.contains_key("android.permission.INTERNET"));
+    assert!(parse_runtime_permissions("").is_empty());
+}
+
+#[test]
+fn test_parse_appops() {
+    let appops = parse_appops(APPOPS);
+    assert_eq!(appops.len(), 3);
+    assert_eq!(appops["CAMERA"], "allow");
+    assert_eq!(appops["RUN_IN_BACKGROUND"], "ignore");
+    assert!(!appops.contains_key("COARSE_LOCATION"));
+    assert!(parse_appops("No operations.").is_empty());
+}
+
+#[test]
+fn test_diff() {
+    let current = PermissionSnapshot {
+        package: "com.example".to_string(),
+        permissions: parse_runtime_permissions(DUMPSYS),
+        appops: parse_appops(APPOPS),
+    };
+    let mut wanted = current.clone();
+    assert!(diff(&current, &wanted).is_empty());
+
+    wanted.permissions.insert("android.permission.CAMERA".to_string(), false);
+    wanted.permissions.insert("android.permission.RECORD_AUDIO".to_string(), true);
+    wanted.appops.insert("RUN_IN_BACKGROUND".to_string(), "allow".to_string());
+    let changes = diff(&current, &wanted);
+    assert_eq!(
+        changes,
+        vec![
+            Change::Revoke("android.permission.CAMERA".to_string()),
+            Change::Grant("android.permission.RECORD_AUDIO".to_string()),
+            Change::SetAppOp("RUN_IN_BACKGROUND".to_string(), "allow".to_string()),
+        ]
+    );
+    assert_eq!(changes[2].command("com.example"), "appops set com.example RUN_IN_BACKGROUND allow");
+}
@@ -1,17 +1,28 @@
+use crate::adb::file_transfer::FileTransfer;
+use crate::adb::shell::ShellBatch;
+use crate::android::dumpsys::PackageDump;
+use crate::android::pm::{self, ListPackages, Origin, Pm};
 use crate::commands::SubCommand;
 use crate::core::context::CommandContext;
+use crate::core::types::{DeviceId, OutputFormat};
 use crate::error::{AimError, Result};
 use crate::progress::{ProgressFactory, ProgressReporter};
+use crate::utils::shell_quote;
 use async_trait::async_trait;
 use colored::*;
+use serde::Serialize;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
 pub struct PullCommand;
 
 #[derive(Debug, Clone, clap::Args)]
 pub struct PullArgs {
     /// Package name (supports partial matching)
-    pub package: String,
+    #[clap(required_unless_present = "all_user")]
+    pub package: Option<String>,
     
     /// Device ID (required if multiple devices are connected)
     pub device_id: Option<String>,
@@ -23,6 +34,115 @@ pub struct PullArgs {
     /// Include split APKs (for app bundles)
     #[clap(short, long)]
     pub splits: bool,
+
+    /// Pull every user-installed package and write a manifest.json
+    #[clap(long)]
+    pub all_user: bool,
+
+    /// Packages to pull at once with --all-user
+    #[clap(short, long, default_value_t = 4)]
+    pub jobs: usize,
+}
+
+impl PullArgs {
+    /// The target device; with --all-user the only positional is the device
+    pub fn device(&self) -> Option<&str> {
+        match (&self.device_id, self.all_user) {
+            (None, true) => self.package.as_deref(),
+            (device_id, _) => device_id.as_deref(),
+        }
+    }
+}
+
+/// Name of the manifest written next to the APKs by --all-user
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// An APK saved from the device
+#[derive(Debug, Clone, Serialize)]
+pub struct SavedApk {
+    pub file: String,
+    pub source: String,
+    pub bytes: u64,
+}
+
+/// What was saved for one package
+#[derive(Debug, Clone, Serialize)]
+pub struct SavedPackage {
+    pub package: String,
+    pub version: String,
+    pub apks: Vec<SavedApk>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The manifest.json of an --all-user pull
+#[derive(Debug, Clone, Serialize)]
+pub struct PullManifest {
+    pub device: String,
+    pub model: Option<String>,
+    pub pulled_at: String,
+    pub packages: Vec<SavedPackage>,
+}
+
+/// Local file name for the `index`th of `count` APKs of a package
+///
+/// `com.example_v1.2.apk` for the base APK, `com.example_config.arm64_v8a_v1.2.apk`
+/// for a split.
+pub fn apk_filename(package: &str, version: &str, remote: &str, index: usize, count: usize) -> String {
+    let version = version.replace([' ', '/'], "_");
+    let split = remote
+        .rsplit('/')
+        .next()
+        .and_then(|name| name.strip_prefix("split_"))
+        .map(|name| name.strip_suffix(".apk").unwrap_or(name));
+    match split {
+        Some(split) if count > 1 => format!("{}_{}_v{}.apk", package, split, version),
+        _ if index == 0 || count == 1 => format!("{}_v{}.apk", package, version),
+        _ => format!("{}_part{}_v{}.apk", package, index, version),
+    }
+}
+
+/// Pull the APKs of one package into `dir`; errors end up in the result
+async fn pull_package(host: String, port: u16, device: DeviceId, package: String, dir: PathBuf, splits: bool) -> SavedPackage {
+    let mut saved = SavedPackage {
+        package: package.clone(),
+        version: "Unknown".to_string(),
+        apks: Vec::new(),
+        error: None,
+    };
+    let result: Result<()> = async {
+        let outputs = ShellBatch::new()
+            .with_device(device.clone())
+            .command(pm::path_command(&package))
+            .command(format!("dumpsys package {}", shell_quote(&package)))
+            .execute(&host, port)
+            .await?;
+        let output = |i: usize| outputs.get(i).map(|o| o.stdout.as_str()).unwrap_or_default();
+        let mut paths = pm::parse_paths(output(0));
+        if paths.is_empty() {
+            return Err(AimError::CommandExecution("no APK paths".to_string()));
+        }
+        if let Some(version) = PackageDump::parse(output(1)).and_then(|dump| dump.version_name) {
+            saved.version = version;
+        }
+        let count = paths.len();
+        if !splits {
+            paths.truncate(1);
+        }
+
+        for (index, remote) in paths.iter().enumerate() {
+            let file = apk_filename(&package, &saved.version, remote, index, count);
+            let local = dir.join(&file);
+            let mut transfer = FileTransfer::new(&host, port, Some(&device)).await?;
+            transfer.pull(remote, &local).await?;
+            let bytes = std::fs::metadata(&local).map(|m| m.len()).unwrap_or(0);
+            saved.apks.push(SavedApk { file, source: remote.clone(), bytes });
+        }
+        Ok(())
+    }
+    .await;
+    saved.error = result.err().map(|e| e.to_string());
+    saved
 }
 
 impl PullCommand {
@@ -30,65 +150,8 @@ impl PullCommand {
         Self
     }
     
-    async fn find_package(&self, ctx: &CommandContext, partial: &str) -> Result<String> {
-        let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
-        
-        // Get all packages
-        let cmd = "pm list packages".to_string();
-        let shell_cmd = crate::adb::shell::ShellCommand::new(cmd)
-            .with_device(device.id.clone());
-        
-        let output = shell_cmd.execute(host, port).await?;
-        
-        // Find matching packages
-        let matches: Vec<String> = output.stdout
-            .lines()
-            .filter_map(|line| {
-                if let Some(pkg) = line.strip_prefix("package:") {
-                    if pkg.contains(partial) {
-                        return Some(pkg.to_string());
-                    }
-                }
-                None
-            })
-            .collect();
-            
-        match matches.len() {
-            0 => Err(AimError::CommandExecution(format!("No package found matching '{}'", partial))),
-            1 => Ok(matches[0].clone()),
-            _ => {
-                // If there's an exact match, use it
-                if let Some(exact) = matches.iter().find(|&m| m == partial) {
-                    Ok(exact.clone())
-                } else {
-                    Err(AimError::AmbiguousDeviceMatch {
-                        prefix: partial.to_string(),
-                        matches,
-                    })
-                }
-            }
-        }
-    }
-    
     async fn get_apk_paths(&self, ctx: &CommandContext, package: &str) -> Result<Vec<String>> {
-        let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
-        
-        // Get APK paths
-        let cmd = format!("pm path {}", package);
-        let shell_cmd = crate::adb::shell::ShellCommand::new(cmd)
-            .with_device(device.id.clone());
-        
-        let output = shell_cmd.execute(host, port).await?;
-        
-        let paths: Vec<String> = output.stdout
-            .lines()
-            .filter_map(|line| {
-                line.strip_prefix("package:").map(|p| p.to_string())
-            })
-            .collect();
-            
+        let paths = Pm::for_context(ctx)?.path(package).await?;
         if paths.is_empty() {
             return Err(AimError::CommandExecution(format!("No APK paths found for package '{}'", package)));
         }
@@ -98,21 +161,18 @@ impl PullCommand {
     
     async fn get_app_info(&self, ctx: &CommandContext, package: &str) -> Result<(String, String)> {
         let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
         
         // Get version info
-        let cmd = format!("dumpsys package {} | grep versionName", package);
+        let cmd = format!("dumpsys package {}", shell_quote(package));
         let shell_cmd = crate::adb::shell::ShellCommand::new(cmd)
             .with_device(device.id.clone());
         
-        let mut version = "Unknown".to_string();
-        if let Ok(output) = shell_cmd.execute(host, port).await {
-            if let Some(line) = output.stdout.lines().next() {
-                if let Some(v) = line.trim().strip_prefix("versionName=") {
-                    version = v.to_string();
-                }
-            }
+        let version = match shell_cmd.execute(host, port).await {
+            Ok(output) => PackageDump::parse(&output.stdout).and_then(|dump| dump.version_name),
+            Err(_) => None,
         }
+        .unwrap_or_else(|| "Unknown".to_string());
         
         // Try to get app name
         let label_cmd = format!("cmd package resolve-activity --brief {} | tail -n 1", package);
@@ -135,7 +195,7 @@ impl PullCommand {
     
     async fn pull_file(&self, ctx: &CommandContext, remote_path: &str, local_path: &Path, progress: Box<dyn ProgressReporter>) -> Result<()> {
         let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
 
         // Use the new FileTransfer API
         let mut file_transfer = crate::adb::file_transfer::FileTransfer::new(host, port, Some(&device.id)).await?;
@@ -145,6 +205,91 @@ impl PullCommand {
 
         file_transfer.pull(remote_path, local_path).await
     }
+
+    /// Pull every user-installed package into the output directory, several at a time
+    async fn pull_all_user(&self, ctx: &CommandContext, args: PullArgs) -> Result<()> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        let output_dir = args.output.clone().unwrap_or_else(|| PathBuf::from("."));
+        std::fs::create_dir_all(&output_dir)?;
+
+        let packages = Pm::new(host, port, &device.id)
+            .list_packages(&ListPackages::new().origin(Origin::ThirdParty))
+            .await?;
+        if packages.is_empty() {
+            return Err(AimError::CommandExecution(format!("No user-installed packages on {}", device.id)));
+        }
+        if !ctx.quiet {
+            println!(
+                "Pulling {} packages from {} into {}",
+                packages.len(),
+                device.id.to_string().bright_cyan(),
+                output_dir.display()
+            );
+        }
+
+        let permits = Arc::new(Semaphore::new(args.jobs.max(1)));
+        let mut tasks = JoinSet::new();
+        for package in packages {
+            let permits = permits.clone();
+            let task = pull_package(host.to_string(), port, device.id.clone(), package, output_dir.clone(), args.splits);
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                task.await
+            });
+        }
+
+        let mut saved = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            let Ok(package) = joined else { continue };
+            if !ctx.quiet {
+                match &package.error {
+                    Some(error) => println!("{} {}: {}", "✗".red(), package.package, error),
+                    None => {
+                        let bytes: u64 = package.apks.iter().map(|a| a.bytes).sum();
+                        println!(
+                            "{} {} {} ({} APK{}, {:.1} MB)",
+                            "✓".green(),
+                            package.package.bright_cyan(),
+                            package.version,
+                            package.apks.len(),
+                            if package.apks.len() == 1 { "" } else { "s" },
+                            bytes as f64 / 1_048_576.0
+                        );
+                    }
+                }
+            }
+            saved.push(package);
+        }
+        saved.sort_by(|a, b| a.package.cmp(&b.package));
+
+        let failed = saved.iter().filter(|p| p.error.is_some()).count();
+        let manifest = PullManifest {
+            device: device.id.to_string(),
+            model: device.model.clone(),
+            pulled_at: chrono::Local::now().to_rfc3339(),
+            packages: saved,
+        };
+        let manifest_path = output_dir.join(MANIFEST_FILE);
+        std::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+        if ctx.output_format == OutputFormat::Json {
+            crate::commands::format_json_output(&manifest)?;
+        } else if !ctx.quiet {
+            println!();
+            println!(
+                "{} Pulled {} of {} packages, manifest at {}",
+                "✓".green().bold(),
+                manifest.packages.len() - failed,
+                manifest.packages.len(),
+                manifest_path.display()
+            );
+        }
+        if failed > 0 {
+            return Err(AimError::CommandExecution(format!("{} packages could not be pulled", failed)));
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -152,8 +297,13 @@ impl SubCommand for PullCommand {
     type Args = PullArgs;
     
     async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        if args.all_user {
+            return self.pull_all_user(ctx, args).await;
+        }
+
         // Find the full package name
-        let package = self.find_package(ctx, &args.package).await?;
+        let partial = args.package.as_deref().unwrap_or_default();
+        let package = super::PackageResolver::new(ctx).resolve(partial).await?;
         
         println!("Finding APK for package: {}", package.bright_cyan());
         
@@ -180,29 +330,7 @@ impl SubCommand for PullCommand {
         let progress_factory = ProgressFactory::new(true);
         
         for (idx, apk_path) in apk_paths.iter().enumerate() {
-            let filename = if apk_paths.len() == 1 {
-                // Single APK - use clean name
-                format!("{}_v{}.apk", package, version.replace(' ', "_"))
-            } else if apk_path.contains("split_") {
-                // Split APK - extract split name
-                if let Some(split_start) = apk_path.rfind("split_") {
-                    let split_part = &apk_path[split_start..];
-                    if let Some(dot_pos) = split_part.find('.') {
-                        format!("{}_{}_v{}.apk", package, &split_part[6..dot_pos], version.replace(' ', "_"))
-                    } else {
-                        format!("{}_split_{}_v{}.apk", package, idx, version.replace(' ', "_"))
-                    }
-                } else {
-                    format!("{}_split_{}_v{}.apk", package, idx, version.replace(' ', "_"))
-                }
-            } else {
-                // Base APK or unknown
-                if idx == 0 {
-                    format!("{}_v{}.apk", package, version.replace(' ', "_"))
-                } else {
-                    format!("{}_part{}_v{}.apk", package, idx, version.replace(' ', "_"))
-                }
-            };
+            let filename = apk_filename(&package, &version, apk_path, idx, apk_paths.len());
             
             let local_path = output_dir.join(&filename);
             
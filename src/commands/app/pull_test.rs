@@ -0,0 +1,38 @@
+use super::pull::apk_filename;
+use crate::android::pm::{parse_packages, parse_paths as parse_apk_paths};
+
+#[test]
+fn test_parse_packages() {
+    let output = "package:com.spotify.music\npackage:com.example.app\n\nWARNING: linker: something\n";
+    assert_eq!(parse_packages(output), vec!["com.example.app", "com.spotify.music"]);
+    assert!(parse_packages("").is_empty());
+}
+
+#[test]
+fn test_parse_apk_paths() {
+    let output = "package:/data/app/~~abc==/com.example-xyz==/base.apk\n\
+                  package:/data/app/~~abc==/com.example-xyz==/split_config.arm64_v8a.apk\n";
+    assert_eq!(
+        parse_apk_paths(output),
+        vec![
+            "/data/app/~~abc==/com.example-xyz==/base.apk",
+            "/data/app/~~abc==/com.example-xyz==/split_config.arm64_v8a.apk",
+        ]
+    );
+}
+
+#[test]
+fn test_apk_filename() {
+    let dir = "/data/app/~~abc==/com.example-xyz==";
+    assert_eq!(apk_filename("com.example", "1.2", &format!("{}/base.apk", dir), 0, 1), "com.example_v1.2.apk");
+    assert_eq!(apk_filename("com.example", "1.2", &format!("{}/base.apk", dir), 0, 3), "com.example_v1.2.apk");
+    assert_eq!(
+        apk_filename("com.example", "1.2", &format!("{}/split_config.arm64_v8a.apk", dir), 1, 3),
+        "com.example_config.arm64_v8a_v1.2.apk"
+    );
+    assert_eq!(
+        apk_filename("com.example", "1.2", &format!("{}/split_config.en.apk", dir), 2, 3),
+        "com.example_config.en_v1.2.apk"
+    );
+    assert_eq!(apk_filename("com.example", "1.2 beta/3", "/data/app/x/other.apk", 1, 2), "com.example_part1_v1.2_beta_3.apk");
+}
@@ -0,0 +1,270 @@
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::output::TableFormat;
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use colored::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+
+lazy_static! {
+    // "* ServiceRecord{7e3a1b u0 com.example/.SyncService}"
+    static ref SERVICE_RECORD_RE: Regex =
+        Regex::new(r"^\s*\* ServiceRecord\{\S+ u(\d+) ([^\s/]+/[^\s}]+)\}").unwrap();
+    // "app=ProcessRecord{4f2c1d0 12345:com.example/u0a154}"
+    static ref PROCESS_RECORD_RE: Regex = Regex::new(r"app=ProcessRecord\{\S+ (\d+):").unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ServiceAction {
+    Start,
+    Stop,
+}
+
+pub struct ServiceCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ServiceArgs {
+    /// Start or stop the service
+    #[clap(value_enum)]
+    pub action: ServiceAction,
+
+    /// Service as <package>/<service>, e.g. com.example/.SyncService (the package supports partial matching)
+    pub component: String,
+
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Start it as a foreground service (needed for apps in the background on Android 8+)
+    #[clap(long)]
+    pub foreground: bool,
+}
+
+pub struct ServicesCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ServicesArgs {
+    /// Package name (supports partial matching)
+    pub package: String,
+
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+}
+
+/// A running service from `dumpsys activity services`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RunningService {
+    pub component: String,
+    pub user: u32,
+    pub pid: Option<u32>,
+    /// Started with startService and not stopped yet
+    pub started: bool,
+    pub foreground: bool,
+    /// Clients bound to the service
+    pub connections: usize,
+    /// How long ago the service was created, e.g. `5m12s`
+    pub created: Option<String>,
+}
+
+impl RunningService {
+    /// Started, bound, or both
+    pub fn state(&self) -> &'static str {
+        match (self.started, self.connections > 0) {
+            (true, true) => "started+bound",
+            (true, false) => "started",
+            (false, true) => "bound",
+            (false, false) => "idle",
+        }
+    }
+}
+
+impl TableFormat for RunningService {
+    fn headers() -> Vec<&'static str> {
+        vec!["SERVICE", "STATE", "FOREGROUND", "CONNECTIONS", "PID", "CREATED"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.component.clone(),
+            self.state().to_string(),
+            if self.foreground { "yes".to_string() } else { "no".to_string() },
+            self.connections.to_string(),
+            self.pid.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string()),
+            self.created.clone().unwrap_or_else(|| "-".to_string()),
+        ]
+    }
+}
+
+/// Service records of `dumpsys activity services <pkg>`
+///
+/// Only services whose component belongs to `package` are kept; the dump
+/// also lists services the package is merely bound to.
+pub fn parse_services(output: &str, package: &str) -> Vec<RunningService> {
+    let mut services: Vec<RunningService> = Vec::new();
+    let mut current: Option<RunningService> = None;
+    let mut in_connections = false;
+
+    for line in output.lines() {
+        if let Some(caps) = SERVICE_RECORD_RE.captures(line) {
+            services.extend(current.take());
+            in_connections = false;
+            current = Some(RunningService {
+                component: caps[2].to_string(),
+                user: caps[1].parse().unwrap_or(0),
+                ..Default::default()
+            });
+            continue;
+        }
+        let Some(service) = current.as_mut() else {
+            continue;
+        };
+        let trimmed = line.trim();
+        // Services of the next user or section end the record
+        if !line.starts_with("    ") && !trimmed.is_empty() {
+            services.extend(current.take());
+            in_connections = false;
+            continue;
+        }
+        if let Some(caps) = PROCESS_RECORD_RE.captures(trimmed) {
+            service.pid = caps[1].parse().ok();
+        }
+        for field in trimmed.split_whitespace() {
+            match field.split_once('=') {
+                Some(("startRequested", value)) => service.started = value == "true",
+                Some(("isForeground", value)) => service.foreground = value == "true",
+                _ => {}
+            }
+        }
+        if let Some(rest) = trimmed.strip_prefix("createTime=") {
+            let age = rest.split_whitespace().next().unwrap_or_default();
+            let age = age.trim_start_matches(['-', '+']);
+            if !age.is_empty() {
+                service.created = Some(age.to_string());
+            }
+        }
+        if trimmed == "All Connections:" {
+            in_connections = true;
+        } else if in_connections && trimmed.starts_with("ConnectionRecord{") {
+            service.connections += 1;
+        }
+    }
+    services.extend(current);
+
+    services.retain(|s| s.component.split('/').next() == Some(package));
+    services.sort_by(|a, b| a.component.cmp(&b.component));
+    services.dedup_by(|a, b| a.component == b.component && a.user == b.user);
+    services
+}
+
+/// `<package>/<service>` with a relative service name (`.SyncService`) expanded
+pub fn service_component(package: &str, service: &str) -> Result<String> {
+    let service = match service.strip_prefix('.') {
+        Some(relative) => format!("{}.{}", package, relative),
+        None => service.to_string(),
+    };
+    let valid = !service.is_empty()
+        && service
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$'));
+    if !valid {
+        return Err(AimError::InvalidArgument(format!("Invalid service name '{}'", service)));
+    }
+    Ok(format!("{}/{}", package, service))
+}
+
+/// Error from `am start-service` / `am stop-service`, which exit 0 either way
+pub fn service_error(action: ServiceAction, output: &str) -> Option<String> {
+    let error = output
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("Error") || line.contains("Exception") || line.starts_with("Service not stopped"));
+    if let Some(error) = error {
+        return Some(error.to_string());
+    }
+    (action == ServiceAction::Stop && !output.contains("Service stopped"))
+        .then(|| "Service was not running".to_string())
+}
+
+impl ServiceCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for ServiceCommand {
+    type Args = ServiceArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let (package, service) = args.component.split_once('/').ok_or_else(|| {
+            AimError::InvalidArgument(format!(
+                "Invalid service '{}', expected <package>/<service> such as com.example/.SyncService",
+                args.component
+            ))
+        })?;
+        let package = super::PackageResolver::new(ctx).resolve(package).await?;
+        let component = service_component(&package, service)?;
+
+        let am = match (args.action, args.foreground) {
+            (ServiceAction::Start, true) => "start-foreground-service",
+            (ServiceAction::Start, false) => "start-service",
+            (ServiceAction::Stop, _) => "stop-service",
+        };
+        let results = ctx
+            .shell_batch([format!("am {} -n {} 2>&1", am, shell_quote(&component))])
+            .await?;
+        let output = results.first().map(|o| o.stdout.as_str()).unwrap_or_default();
+
+        if let Some(error) = service_error(args.action, output) {
+            return Err(AimError::CommandExecution(format!("{}: {}", component, error)));
+        }
+        if !ctx.quiet {
+            let verb = match args.action {
+                ServiceAction::Start => "Started",
+                ServiceAction::Stop => "Stopped",
+            };
+            println!("{} {} {}", "✓".green(), verb, component.bright_cyan());
+        }
+        Ok(())
+    }
+}
+
+impl ServicesCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for ServicesCommand {
+    type Args = ServicesArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let package = super::PackageResolver::new(ctx).resolve(&args.package).await?;
+        let results = ctx
+            .shell_batch([format!("dumpsys activity services {}", shell_quote(&package))])
+            .await?;
+        let output = results.first().map(|o| o.stdout.as_str()).unwrap_or_default();
+        let services = parse_services(output, &package);
+
+        match ctx.output_format {
+            OutputFormat::Json => crate::commands::format_json_output(&services)?,
+            OutputFormat::Plain => {
+                for service in &services {
+                    println!("{}\t{}", service.component, service.state());
+                }
+            }
+            OutputFormat::Table => {
+                if services.is_empty() {
+                    println!("No running services for {}", package.bright_cyan());
+                } else {
+                    crate::output::OutputFormatter::new().table(&services)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
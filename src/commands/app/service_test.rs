@@ -0,0 +1,107 @@
+use super::service::{parse_services, service_component, service_error, ServiceAction};
+
+const DUMPSYS: &str = "ACTIVITY MANAGER SERVICES (dumpsys activity services)
+  User 0 active services:
+  * ServiceRecord{7e3a1b u0 com.example/.SyncService}
+    intent={cmp=com.example/.SyncService}
+    packageName=com.example
+    processName=com.example
+    app=ProcessRecord{4f2c1d0 12345:com.example/u0a154}
+    createTime=-5m12s ago startingBgTimeout=--
+    lastActivity=-1m3s ago restartTime=-5m12s ago createdFromFg=true
+    isForeground=true foregroundId=7 foregroundNoti=Notification(channel=sync)
+    startRequested=true delayedStop=false stopIfKilled=false callStart=true lastStartId=1
+  * ServiceRecord{9c0d2e u0 com.example/com.example.media.PlaybackService}
+    intent={act=android.media.browse.MediaBrowserService cmp=com.example/com.example.media.PlaybackService}
+    packageName=com.example
+    app=ProcessRecord{4f2c1d0 12345:com.example/u0a154}
+    createTime=-30s ago startingBgTimeout=--
+    startRequested=false delayedStop=false stopIfKilled=false callStart=false lastStartId=0
+    Bindings:
+    * IntentBindRecord{3a4b5c CREATE}:
+      intent={act=android.media.browse.MediaBrowserService cmp=com.example/com.example.media.PlaybackService}
+      binder=android.os.BinderProxy@1f2e3d
+      requested=true received=true hasBound=true doRebind=false
+    All Connections:
+      ConnectionRecord{1a2b3c u0 CR com.example/com.example.media.PlaybackService:@4d5e6f}
+      ConnectionRecord{7a8b9c u0 com.android.systemui/com.example/com.example.media.PlaybackService:@0d1e2f}
+  * ServiceRecord{5d6e7f u0 com.google.android.gms/.chimera.GmsBoundBrokerService}
+    intent={act=com.google.android.gms.auth.service.START}
+    startRequested=false delayedStop=false
+    All Connections:
+      ConnectionRecord{2c3d4e u0 com.example/com.google.android.gms/.chimera.GmsBoundBrokerService:@5e6f7a}
+
+  Connection bindings to services:
+  * ConnectionRecord{2c3d4e u0 com.example/com.google.android.gms/.chimera.GmsBoundBrokerService:@5e6f7a}
+";
+
+#[test]
+fn test_parse_services() {
+    let services = parse_services(DUMPSYS, "com.example");
+    assert_eq!(services.len(), 2);
+
+    let sync = &services[0];
+    assert_eq!(sync.component, "com.example/.SyncService");
+    assert_eq!(sync.user, 0);
+    assert_eq!(sync.pid, Some(12345));
+    assert!(sync.started);
+    assert!(sync.foreground);
+    assert_eq!(sync.connections, 0);
+    assert_eq!(sync.created.as_deref(), Some("5m12s"));
+    assert_eq!(sync.state(), "started");
+
+    let playback = &services[1];
+    assert_eq!(playback.component, "com.example/com.example.media.PlaybackService");
+    assert!(!playback.started);
+    assert!(!playback.foreground);
+    assert_eq!(playback.connections, 2);
+    assert_eq!(playback.created.as_deref(), Some("30s"));
+    assert_eq!(playback.state(), "bound");
+}
+
+#[test]
+fn test_parse_services_empty() {
+    let output = "ACTIVITY MANAGER SERVICES (dumpsys activity services)\n  (nothing)\n";
+    assert!(parse_services(output, "com.example").is_empty());
+    assert!(parse_services(DUMPSYS, "com.other").is_empty());
+}
+
+#[test]
+fn test_service_component() {
+    assert_eq!(
+        service_component("com.example", ".SyncService").unwrap(),
+        "com.example/com.example.SyncService"
+    );
+    assert_eq!(
+        service_component("com.example", "com.lib.Worker$Inner").unwrap(),
+        "com.example/com.lib.Worker$Inner"
+    );
+    assert!(service_component("com.example", "").is_err());
+    assert!(service_component("com.example", "com..Worker").is_err());
+}
+
+#[test]
+fn test_service_error() {
+    let started = "Starting service: Intent { cmp=com.example/.SyncService }\n";
+    assert_eq!(service_error(ServiceAction::Start, started), None);
+
+    let missing = "Starting service: Intent { cmp=com.example/.Missing }\nError: Not found; no service started.\n";
+    assert_eq!(
+        service_error(ServiceAction::Start, missing).as_deref(),
+        Some("Error: Not found; no service started.")
+    );
+
+    let background = "Starting service: Intent { cmp=com.example/.SyncService }\n\
+        Error: app is in background uid UidRecord{...}\n";
+    assert!(service_error(ServiceAction::Start, background).unwrap().contains("background"));
+
+    let stopped = "Stopping service: Intent { cmp=com.example/.SyncService }\nService stopped\n";
+    assert_eq!(service_error(ServiceAction::Stop, stopped), None);
+
+    let not_running = "Stopping service: Intent { cmp=com.example/.SyncService }\n\
+        Service not stopped: Unable to find active service.\n";
+    assert_eq!(
+        service_error(ServiceAction::Stop, not_running).as_deref(),
+        Some("Service not stopped: Unable to find active service.")
+    );
+}
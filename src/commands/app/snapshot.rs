@@ -0,0 +1,231 @@
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::output::TableFormat;
+use async_trait::async_trait;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+pub struct SnapshotCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct SnapshotArgs {
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// File to save the snapshot to (JSON)
+    #[clap(short, long)]
+    pub output: PathBuf,
+}
+
+pub struct DiffCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct DiffArgs {
+    /// Snapshot taken earlier with `aim app snapshot`
+    pub snapshot: PathBuf,
+
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Exit with an error if anything changed
+    #[clap(long)]
+    pub fail_on_change: bool,
+}
+
+/// An installed package as `pm list packages -f --show-versioncode` reports it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstalledPackage {
+    pub version_code: Option<u64>,
+    /// Path of the base APK
+    pub path: String,
+}
+
+/// The packages on a device at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageSnapshot {
+    pub device: String,
+    pub created: String,
+    pub packages: BTreeMap<String, InstalledPackage>,
+}
+
+/// How a package differs between two snapshots
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "lowercase")]
+pub enum PackageChange {
+    Installed { package: String, version_code: Option<u64> },
+    Removed { package: String, version_code: Option<u64> },
+    Updated { package: String, from: Option<u64>, to: Option<u64> },
+    Downgraded { package: String, from: Option<u64>, to: Option<u64> },
+    /// Same version from a different APK, e.g. a system app that got an update installed over it
+    Moved { package: String, from: String, to: String },
+}
+
+impl PackageChange {
+    fn parts(&self) -> (&'static str, &str, String, String) {
+        let version = |v: &Option<u64>| v.map(|v| v.to_string()).unwrap_or_else(|| "?".to_string());
+        match self {
+            PackageChange::Installed { package, version_code } => ("installed", package.as_str(), "-".to_string(), version(version_code)),
+            PackageChange::Removed { package, version_code } => ("removed", package.as_str(), version(version_code), "-".to_string()),
+            PackageChange::Updated { package, from, to } => ("updated", package.as_str(), version(from), version(to)),
+            PackageChange::Downgraded { package, from, to } => ("downgraded", package.as_str(), version(from), version(to)),
+            PackageChange::Moved { package, from, to } => ("moved", package.as_str(), from.clone(), to.clone()),
+        }
+    }
+}
+
+impl TableFormat for PackageChange {
+    fn headers() -> Vec<&'static str> {
+        vec!["CHANGE", "PACKAGE", "FROM", "TO"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let (change, package, from, to) = self.parts();
+        vec![change.to_string(), package.to_string(), from, to]
+    }
+}
+
+impl fmt::Display for PackageChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (change, package, from, to) = self.parts();
+        write!(f, "{}\t{}\t{}\t{}", change, package, from, to)
+    }
+}
+
+/// Packages of `pm list packages -f --show-versioncode`
+///
+/// Lines look like `package:/data/app/~~ab==/com.example-cd==/base.apk=com.example versionCode:42`.
+pub fn parse_packages(output: &str) -> BTreeMap<String, InstalledPackage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim().strip_prefix("package:")?;
+            let (entry, version) = match line.rsplit_once(" versionCode:") {
+                Some((entry, version)) => (entry, version.trim().parse().ok()),
+                None => (line, None),
+            };
+            // The path itself can contain '=', the package name cannot
+            let (path, package) = entry.rsplit_once('=')?;
+            Some((package.to_string(), InstalledPackage { version_code: version, path: path.to_string() }))
+        })
+        .collect()
+}
+
+/// What changed from `before` to `after`, sorted by package
+pub fn diff(before: &BTreeMap<String, InstalledPackage>, after: &BTreeMap<String, InstalledPackage>) -> Vec<PackageChange> {
+    let mut changes = Vec::new();
+    for (package, old) in before {
+        let Some(new) = after.get(package) else {
+            changes.push(PackageChange::Removed { package: package.clone(), version_code: old.version_code });
+            continue;
+        };
+        let (from, to) = (old.version_code, new.version_code);
+        let package = package.clone();
+        match (from, to) {
+            (Some(a), Some(b)) if b > a => changes.push(PackageChange::Updated { package, from, to }),
+            (Some(a), Some(b)) if b < a => changes.push(PackageChange::Downgraded { package, from, to }),
+            _ if stable_path(&old.path) != stable_path(&new.path) => changes.push(PackageChange::Moved {
+                package,
+                from: old.path.clone(),
+                to: new.path.clone(),
+            }),
+            _ => {}
+        }
+    }
+    for (package, new) in after {
+        if !before.contains_key(package) {
+            changes.push(PackageChange::Installed { package: package.clone(), version_code: new.version_code });
+        }
+    }
+    changes.sort_by(|a, b| a.parts().1.cmp(b.parts().1));
+    changes
+}
+
+/// Where an APK lives, without the directory names /data/app randomizes on every install
+fn stable_path(path: &str) -> String {
+    match path.strip_prefix("/data/app/") {
+        Some(_) => "/data/app".to_string(),
+        None => path.to_string(),
+    }
+}
+
+async fn read_packages(ctx: &CommandContext) -> Result<BTreeMap<String, InstalledPackage>> {
+    let results = ctx.shell_batch(["pm list packages -f --show-versioncode"]).await?;
+    let output = results.first().map(|o| o.stdout.as_str()).unwrap_or_default();
+    Ok(parse_packages(output))
+}
+
+impl SnapshotCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for SnapshotCommand {
+    type Args = SnapshotArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = ctx.require_device()?;
+        let snapshot = PackageSnapshot {
+            device: device.id.to_string(),
+            created: chrono::Local::now().to_rfc3339(),
+            packages: read_packages(ctx).await?,
+        };
+        std::fs::write(&args.output, serde_json::to_string_pretty(&snapshot)?)?;
+        if !ctx.quiet {
+            println!(
+                "{} Saved {} packages of {} to {}",
+                "✓".green(),
+                snapshot.packages.len(),
+                device.id,
+                args.output.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+impl DiffCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for DiffCommand {
+    type Args = DiffArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let contents = std::fs::read_to_string(&args.snapshot)
+            .map_err(|e| AimError::InvalidArgument(format!("Cannot read {}: {}", args.snapshot.display(), e)))?;
+        let before: PackageSnapshot = serde_json::from_str(&contents)?;
+        let device = ctx.require_device()?;
+        if before.device != device.id.to_string() && !ctx.quiet {
+            eprintln!("{} {} was taken on {}", "Note:".yellow(), args.snapshot.display(), before.device);
+        }
+
+        let after = read_packages(ctx).await?;
+        let changes = diff(&before.packages, &after);
+
+        match ctx.output_format {
+            OutputFormat::Json => crate::commands::format_json_output(&changes)?,
+            OutputFormat::Plain => crate::output::OutputFormatter::new().plain(&changes)?,
+            OutputFormat::Table => {
+                if changes.is_empty() {
+                    println!("{} No package changes since {}", "✓".green(), before.created);
+                } else {
+                    crate::output::OutputFormatter::new().table(&changes)?;
+                }
+            }
+        }
+        if args.fail_on_change && !changes.is_empty() {
+            return Err(AimError::CommandExecution(format!("{} packages changed", changes.len())));
+        }
+        Ok(())
+    }
+}
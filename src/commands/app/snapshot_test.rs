@@ -0,0 +1,57 @@
+use super::snapshot::{diff, parse_packages, PackageChange};
+
+const BEFORE: &str = "package:/data/app/~~ab12==/com.example-cd34==/base.apk=com.example versionCode:42
+package:/system/priv-app/Chrome/Chrome.apk=com.android.chrome versionCode:600
+package:/data/app/~~ef56==/com.old-gh78==/base.apk=com.old versionCode:3
+package:/data/app/~~ij90==/com.beta-kl12==/base.apk=com.beta versionCode:20
+package:/product/app/Maps/Maps.apk=com.google.android.apps.maps versionCode:100
+";
+
+const AFTER: &str = "package:/data/app/~~zz99==/com.example-yy88==/base.apk=com.example versionCode:43
+package:/data/app/~~mm11==/com.android.chrome-nn22==/base.apk=com.android.chrome versionCode:600
+package:/data/app/~~ij90==/com.beta-kl12==/base.apk=com.beta versionCode:19
+package:/product/app/Maps/Maps.apk=com.google.android.apps.maps versionCode:100
+package:/data/app/~~oo33==/com.new-pp44==/base.apk=com.new versionCode:1
+";
+
+#[test]
+fn test_parse_packages() {
+    let packages = parse_packages(BEFORE);
+    assert_eq!(packages.len(), 5);
+    let example = &packages["com.example"];
+    assert_eq!(example.version_code, Some(42));
+    assert_eq!(example.path, "/data/app/~~ab12==/com.example-cd34==/base.apk");
+
+    // Releases without --show-versioncode still give the packages
+    let packages = parse_packages("package:/system/app/Foo/Foo.apk=com.foo\n");
+    assert_eq!(packages["com.foo"].version_code, None);
+}
+
+#[test]
+fn test_diff() {
+    let changes = diff(&parse_packages(BEFORE), &parse_packages(AFTER));
+    assert_eq!(
+        changes,
+        vec![
+            PackageChange::Moved {
+                package: "com.android.chrome".to_string(),
+                from: "/system/priv-app/Chrome/Chrome.apk".to_string(),
+                to: "/data/app/~~mm11==/com.android.chrome-nn22==/base.apk".to_string(),
+            },
+            PackageChange::Downgraded { package: "com.beta".to_string(), from: Some(20), to: Some(19) },
+            PackageChange::Updated { package: "com.example".to_string(), from: Some(42), to: Some(43) },
+            PackageChange::Installed { package: "com.new".to_string(), version_code: Some(1) },
+            PackageChange::Removed { package: "com.old".to_string(), version_code: Some(3) },
+        ]
+    );
+}
+
+#[test]
+fn test_diff_unchanged() {
+    let packages = parse_packages(BEFORE);
+    assert!(diff(&packages, &packages).is_empty());
+
+    // Reinstalling the same version only changes the random /data/app directory
+    let reinstalled = parse_packages(&BEFORE.replace("~~ab12==/com.example-cd34==", "~~qq55==/com.example-rr66=="));
+    assert!(diff(&packages, &reinstalled).is_empty());
+}
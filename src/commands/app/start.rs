@@ -24,50 +24,9 @@ impl StartCommand {
         Self
     }
     
-    async fn find_package(&self, ctx: &CommandContext, partial: &str) -> Result<String> {
-        let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
-        
-        // Get all packages
-        let cmd = "pm list packages".to_string();
-        let shell_cmd = crate::adb::shell::ShellCommand::new(cmd)
-            .with_device(device.id.clone());
-        
-        let output = shell_cmd.execute(host, port).await?;
-        
-        // Find matching packages
-        let matches: Vec<String> = output.stdout
-            .lines()
-            .filter_map(|line| {
-                if let Some(pkg) = line.strip_prefix("package:") {
-                    if pkg.contains(partial) {
-                        return Some(pkg.to_string());
-                    }
-                }
-                None
-            })
-            .collect();
-            
-        match matches.len() {
-            0 => Err(AimError::CommandExecution(format!("No package found matching '{}'", partial))),
-            1 => Ok(matches[0].clone()),
-            _ => {
-                // If there's an exact match, use it
-                if let Some(exact) = matches.iter().find(|&m| m == partial) {
-                    Ok(exact.clone())
-                } else {
-                    Err(AimError::AmbiguousDeviceMatch {
-                        prefix: partial.to_string(),
-                        matches,
-                    })
-                }
-            }
-        }
-    }
-    
     async fn get_launcher_activity(&self, ctx: &CommandContext, package: &str) -> Result<String> {
         let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
         
         // Get the main launcher activity
         let cmd = format!("cmd package resolve-activity --brief {} | tail -n 1", package);
@@ -102,7 +61,7 @@ impl StartCommand {
     
     async fn get_app_name(&self, ctx: &CommandContext, package: &str) -> Result<String> {
         let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
         
         // Try to get app label
         let cmd = format!("cmd package resolve-activity --brief {} | tail -n 1", package);
@@ -127,7 +86,7 @@ impl SubCommand for StartCommand {
     
     async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
         // Find the full package name
-        let package = self.find_package(ctx, &args.package).await?;
+        let package = super::PackageResolver::new(ctx).resolve(&args.package).await?;
         
         // Get app name for display
         let app_name = self.get_app_name(ctx, &package).await?;
@@ -158,7 +117,7 @@ impl SubCommand for StartCommand {
         
         // Start the app
         let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
         
         let cmd = format!("am start -n {}", activity);
         let shell_cmd = crate::adb::shell::ShellCommand::new(cmd)
@@ -166,11 +125,11 @@ impl SubCommand for StartCommand {
         
         let output = shell_cmd.execute(host, port).await?;
         
-        // Check result
-        if output.stdout.contains("Error") {
+        // Check result; am reports errors on stderr
+        if output.exit_code != 0 || output.stderr.contains("Error") || output.stdout.contains("Error") {
             return Err(AimError::CommandExecution(format!(
                 "Failed to start app: {}",
-                output.stdout.trim()
+                output.output().trim()
             )));
         }
         
@@ -0,0 +1,202 @@
+use crate::android::pm::Pm;
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::output::{OutputFormatter, TableFormat};
+use async_trait::async_trait;
+use colored::*;
+use comfy_table::{Cell, Color};
+use serde::Serialize;
+use std::fmt;
+use std::io::Read;
+
+pub struct UninstallCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct UninstallArgs {
+    /// Package names (support partial matching)
+    #[clap(required_unless_present = "from_file")]
+    pub packages: Vec<String>,
+
+    /// Read package names from a file, one per line (`-` for stdin)
+    #[clap(long, value_name = "FILE", conflicts_with = "packages")]
+    pub from_file: Option<String>,
+
+    /// Go on with the next package after one fails
+    #[clap(long)]
+    pub continue_on_error: bool,
+
+    /// Uninstall for this user only, which also works for system apps
+    #[clap(long)]
+    pub user: Option<u32>,
+
+    /// Device ID (required if multiple devices are connected)
+    #[clap(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// What happened to one package
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Removed,
+    Failed,
+    /// Not tried, because an earlier package failed
+    Skipped,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Outcome::Removed => "removed",
+            Outcome::Failed => "failed",
+            Outcome::Skipped => "skipped",
+        };
+        f.write_str(name)
+    }
+}
+
+/// One row of the summary
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UninstallResult {
+    pub package: String,
+    pub outcome: Outcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl TableFormat for UninstallResult {
+    fn headers() -> Vec<&'static str> {
+        vec!["RESULT", "PACKAGE", "ERROR"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.outcome.to_string(), self.package.clone(), self.error.clone().unwrap_or_default()]
+    }
+
+    fn colored_row(&self) -> Vec<Cell> {
+        let color = match self.outcome {
+            Outcome::Removed => Color::Green,
+            Outcome::Failed => Color::Red,
+            Outcome::Skipped => Color::DarkGrey,
+        };
+        let mut cells: Vec<Cell> = self.row().into_iter().map(Cell::new).collect();
+        cells[0] = Cell::new(self.outcome.to_string()).fg(color);
+        cells
+    }
+}
+
+/// Package names in a list file
+///
+/// One name per line; blank lines and `#` comments are ignored, and a name
+/// listed twice is uninstalled once.
+pub fn parse_package_list(text: &str) -> Result<Vec<String>> {
+    let mut packages: Vec<String> = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let name = line.split('#').next().unwrap_or_default().trim();
+        if name.is_empty() {
+            continue;
+        }
+        if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_') {
+            return Err(AimError::InvalidArgument(format!(
+                "Line {}: '{}' is not a package name",
+                number + 1,
+                name
+            )));
+        }
+        if !packages.iter().any(|p| p == name) {
+            packages.push(name.to_string());
+        }
+    }
+    Ok(packages)
+}
+
+/// Contents of `--from-file`: stdin for `-`, otherwise the file
+fn read_list(source: &str) -> Result<String> {
+    if source == "-" {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| AimError::InvalidArgument(format!("Cannot read package names from stdin: {}", e)))?;
+        return Ok(text);
+    }
+    std::fs::read_to_string(source).map_err(|e| AimError::InvalidArgument(format!("Cannot read {}: {}", source, e)))
+}
+
+impl UninstallCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// The packages to uninstall, in order
+    async fn packages(&self, ctx: &CommandContext, args: &UninstallArgs) -> Result<Vec<String>> {
+        if let Some(source) = &args.from_file {
+            // Names from a list are taken as they are: a partial match could pick the wrong app
+            return parse_package_list(&read_list(source)?);
+        }
+        let resolver = super::PackageResolver::new(ctx);
+        let mut packages = Vec::new();
+        for partial in &args.packages {
+            packages.push(resolver.resolve(partial).await?);
+        }
+        Ok(packages)
+    }
+}
+
+#[async_trait]
+impl SubCommand for UninstallCommand {
+    type Args = UninstallArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let packages = self.packages(ctx, &args).await?;
+        if packages.is_empty() {
+            return Err(AimError::InvalidArgument("No packages to uninstall".to_string()));
+        }
+        let pm = Pm::for_context(ctx)?;
+        let json = ctx.output_format == OutputFormat::Json;
+        let batch = packages.len() > 1;
+
+        let mut results: Vec<UninstallResult> = Vec::new();
+        let mut stopped = false;
+        for package in packages {
+            if stopped {
+                results.push(UninstallResult { package, outcome: Outcome::Skipped, error: None });
+                continue;
+            }
+            let result = pm.uninstall(&package, args.user).await;
+            match &result {
+                Ok(()) if !json && !ctx.quiet => println!("{} Uninstalled {}", "✓".green(), package.bright_cyan()),
+                Err(e) if !json => println!("{} {}", "✗".red(), e),
+                _ => {}
+            }
+            stopped = result.is_err() && !args.continue_on_error;
+            results.push(UninstallResult {
+                package,
+                outcome: if result.is_ok() { Outcome::Removed } else { Outcome::Failed },
+                error: result.err().map(|e| e.to_string()),
+            });
+        }
+
+        if json {
+            crate::commands::format_json_output(&results)?;
+        } else if batch && !ctx.quiet {
+            println!();
+            OutputFormatter::new().table(&results)?;
+        }
+
+        let count = |outcome: Outcome| results.iter().filter(|r| r.outcome == outcome).count();
+        let (failed, skipped) = (count(Outcome::Failed), count(Outcome::Skipped));
+        if failed > 0 {
+            let mut message = format!("{} of {} packages could not be uninstalled", failed, results.len());
+            if skipped > 0 {
+                message.push_str(&format!(" ({} skipped; pass --continue-on-error to go on after a failure)", skipped));
+            }
+            return Err(AimError::CommandExecution(message));
+        }
+        if batch && !json && !ctx.quiet {
+            println!("{} Uninstalled {} packages", "✓".green().bold(), results.len());
+        }
+        Ok(())
+    }
+}
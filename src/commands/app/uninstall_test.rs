@@ -0,0 +1,35 @@
+use super::uninstall::{parse_package_list, Outcome, UninstallResult};
+use crate::output::TableFormat;
+
+#[test]
+fn test_parse_package_list() {
+    let text = "# bloat on the lab phones\ncom.facebook.katana\n\n  com.netflix.mediaclient  # preinstalled\r\ncom.facebook.katana\n";
+    assert_eq!(parse_package_list(text).unwrap(), vec!["com.facebook.katana", "com.netflix.mediaclient"]);
+}
+
+#[test]
+fn test_parse_package_list_empty() {
+    assert!(parse_package_list("# nothing yet\n\n").unwrap().is_empty());
+}
+
+#[test]
+fn test_parse_package_list_rejects_other_text() {
+    let err = parse_package_list("com.example\nrm -rf /\n").unwrap_err();
+    assert!(err.to_string().contains("Line 2"));
+}
+
+#[test]
+fn test_result_row() {
+    let result = UninstallResult {
+        package: "com.example".to_string(),
+        outcome: Outcome::Failed,
+        error: Some("Uninstalling com.example failed: DELETE_FAILED_INTERNAL_ERROR".to_string()),
+    };
+    assert_eq!(
+        result.row(),
+        vec!["failed", "com.example", "Uninstalling com.example failed: DELETE_FAILED_INTERNAL_ERROR"]
+    );
+    let skipped = UninstallResult { package: "com.other".to_string(), outcome: Outcome::Skipped, error: None };
+    assert_eq!(skipped.row(), vec!["skipped", "com.other", ""]);
+    assert_eq!(serde_json::to_value(&skipped).unwrap(), serde_json::json!({"package": "com.other", "outcome": "skipped"}));
+}
@@ -0,0 +1,296 @@
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::Result;
+use crate::utils::{format_duration, parse_duration};
+use async_trait::async_trait;
+use clap::Subcommand;
+use colored::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const POWER_SUPPLY: &str = "/sys/class/power_supply/battery";
+const SECTION_MARKER: &str = "--aim--";
+
+lazy_static! {
+    // "  PARTIAL_WAKE_LOCK              'NlpWakeLock' ACQ=-1s (uid=10123 pid=4567)"
+    static ref WAKE_LOCK_RE: Regex = Regex::new(r"\b[A-Z_]*WAKE_LOCK\s+'([^']+)'").unwrap();
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum BatteryCommands {
+    /// Record battery drain over a monitoring session
+    Monitor(MonitorArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: BatteryCommands) -> Result<()> {
+    match cmd {
+        BatteryCommands::Monitor(args) => {
+            let cmd = MonitorCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+pub struct MonitorCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct MonitorArgs {
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Time between samples (e.g. 30s, 5m)
+    #[arg(short, long, default_value = "30s", value_parser = parse_duration)]
+    pub interval: Duration,
+
+    /// How long to monitor for (e.g. 1h). Runs until Ctrl+C if omitted
+    #[arg(short, long, value_parser = parse_duration)]
+    pub duration: Option<Duration>,
+
+    /// Number of wakelocks to show in the summary
+    #[arg(long, default_value_t = 5)]
+    pub top: usize,
+}
+
+/// One reading of the battery state
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BatterySample {
+    pub level: Option<u32>,
+    /// Instantaneous current in mA as reported by the kernel (sign is vendor specific)
+    pub current_ma: Option<i64>,
+    pub screen: Option<String>,
+    pub wakelocks: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct TimedSample {
+    elapsed_secs: u64,
+    #[serde(flatten)]
+    sample: BatterySample,
+}
+
+#[derive(Debug, Serialize)]
+struct MonitorReport {
+    samples: Vec<TimedSample>,
+    duration_secs: u64,
+    drain_percent: Option<i64>,
+    drain_percent_per_hour: Option<f64>,
+    average_current_ma: Option<f64>,
+    screen_on_percent: f64,
+    top_wakelocks: Vec<(String, usize)>,
+}
+
+/// Shell snippet that prints every section the sampler needs in one round trip
+fn sample_command() -> String {
+    format!(
+        "cat {ps}/capacity 2>/dev/null; echo {m}; cat {ps}/current_now 2>/dev/null; echo {m}; \
+         dumpsys power | grep -E 'mWakefulness=|WAKE_LOCK'",
+        ps = POWER_SUPPLY,
+        m = SECTION_MARKER
+    )
+}
+
+/// Parse the output of [`sample_command`]
+pub fn parse_sample(output: &str) -> BatterySample {
+    let mut sections = output.split(SECTION_MARKER).map(str::trim);
+    let level = sections.next().and_then(|s| s.parse().ok());
+    let current_ma = sections
+        .next()
+        .and_then(|s| s.parse::<i64>().ok())
+        .map(|ua| ua / 1000);
+    let power = sections.next().unwrap_or("");
+
+    let screen = power
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("mWakefulness="))
+        .map(|s| s.trim().to_string());
+
+    let mut wakelocks: Vec<String> = WAKE_LOCK_RE
+        .captures_iter(power)
+        .map(|caps| caps[1].to_string())
+        .collect();
+    wakelocks.sort();
+    wakelocks.dedup();
+
+    BatterySample {
+        level,
+        current_ma,
+        screen,
+        wakelocks,
+    }
+}
+
+impl MonitorCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn build_report(samples: Vec<TimedSample>, elapsed: Duration, top: usize) -> MonitorReport {
+        let levels: Vec<u32> = samples.iter().filter_map(|s| s.sample.level).collect();
+        let drain_percent = match (levels.first(), levels.last()) {
+            (Some(first), Some(last)) => Some(*first as i64 - *last as i64),
+            _ => None,
+        };
+        let hours = elapsed.as_secs_f64() / 3600.0;
+        let drain_percent_per_hour = drain_percent.filter(|_| hours > 0.0).map(|d| d as f64 / hours);
+
+        let currents: Vec<i64> = samples.iter().filter_map(|s| s.sample.current_ma).collect();
+        let average_current_ma = (!currents.is_empty())
+            .then(|| currents.iter().sum::<i64>() as f64 / currents.len() as f64);
+
+        let screen_on = samples
+            .iter()
+            .filter(|s| s.sample.screen.as_deref() == Some("Awake"))
+            .count();
+        let screen_on_percent = if samples.is_empty() {
+            0.0
+        } else {
+            screen_on as f64 * 100.0 / samples.len() as f64
+        };
+
+        // Rank wakelocks by how many samples they were held in
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for s in &samples {
+            for tag in &s.sample.wakelocks {
+                *counts.entry(tag).or_default() += 1;
+            }
+        }
+        let mut top_wakelocks: Vec<(String, usize)> =
+            counts.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+        top_wakelocks.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_wakelocks.truncate(top);
+
+        MonitorReport {
+            duration_secs: elapsed.as_secs(),
+            drain_percent,
+            drain_percent_per_hour,
+            average_current_ma,
+            screen_on_percent,
+            top_wakelocks,
+            samples,
+        }
+    }
+
+    fn print_row(sample: &TimedSample, previous_level: Option<u32>) {
+        let level = sample.sample.level.map(|l| format!("{}%", l)).unwrap_or_else(|| "-".into());
+        let delta = match (previous_level, sample.sample.level) {
+            (Some(prev), Some(cur)) if prev != cur => format!("{:+}", cur as i64 - prev as i64),
+            _ => String::new(),
+        };
+        let current = sample
+            .sample
+            .current_ma
+            .map(|c| format!("{} mA", c))
+            .unwrap_or_else(|| "-".into());
+        println!(
+            "{:>8}  {:>5} {:>4}  {:>9}  {:<8}  {}",
+            format_duration(Duration::from_secs(sample.elapsed_secs)),
+            level,
+            delta.red(),
+            current,
+            sample.sample.screen.as_deref().unwrap_or("-"),
+            sample.sample.wakelocks.len()
+        );
+    }
+
+    fn print_summary(report: &MonitorReport) {
+        println!();
+        println!("{}", "Summary".bold());
+        println!(
+            "  Duration:       {}",
+            format_duration(Duration::from_secs(report.duration_secs))
+        );
+        match (report.drain_percent, report.drain_percent_per_hour) {
+            (Some(drain), Some(rate)) => {
+                println!("  Drain:          {}% ({:.1}%/h)", drain, rate);
+                if rate > 0.0 {
+                    if let Some(level) = report.samples.iter().rev().find_map(|s| s.sample.level) {
+                        println!("  Time to empty:  ~{:.1}h at this rate", level as f64 / rate);
+                    }
+                }
+            }
+            (Some(drain), None) => println!("  Drain:          {}%", drain),
+            _ => println!("  Drain:          unknown (battery level unavailable)"),
+        }
+        if let Some(avg) = report.average_current_ma {
+            println!("  Avg current:    {:.0} mA", avg);
+        }
+        println!("  Screen on:      {:.0}% of samples", report.screen_on_percent);
+
+        if !report.top_wakelocks.is_empty() {
+            println!();
+            println!("{}", "Top wakelocks (samples held)".bold());
+            for (tag, count) in &report.top_wakelocks {
+                println!("  {:>4}  {}", count, tag.bright_cyan());
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SubCommand for MonitorCommand {
+    type Args = MonitorArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let show_rows = ctx.output_format != OutputFormat::Json;
+
+        if show_rows {
+            println!(
+                "Monitoring battery on {} every {}",
+                device.id.to_string().bright_cyan(),
+                format_duration(args.interval)
+            );
+            println!("Press Ctrl+C to stop");
+            println!();
+            println!(
+                "{:>8}  {:>5} {:>4}  {:>9}  {:<8}  WAKELOCKS",
+                "ELAPSED", "LEVEL", "", "CURRENT", "SCREEN"
+            );
+        }
+
+        let start = Instant::now();
+        let mut samples: Vec<TimedSample> = Vec::new();
+
+        loop {
+            let elapsed = start.elapsed();
+            let output = crate::adb::shell::ShellCommand::new(sample_command())
+                .with_device(device.id.clone())
+                .execute(host, port)
+                .await?;
+
+            let sample = TimedSample {
+                elapsed_secs: elapsed.as_secs(),
+                sample: parse_sample(&output.stdout),
+            };
+            if show_rows {
+                let previous = samples.last().and_then(|s| s.sample.level);
+                Self::print_row(&sample, previous);
+            }
+            samples.push(sample);
+
+            let next = args.interval.saturating_sub(start.elapsed().saturating_sub(elapsed));
+            if args.duration.is_some_and(|d| start.elapsed() + next > d) {
+                break;
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(next) => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+        }
+
+        let report = Self::build_report(samples, start.elapsed(), args.top);
+        if show_rows {
+            Self::print_summary(&report);
+        } else {
+            crate::commands::format_json_output(&report)?;
+        }
+
+        Ok(())
+    }
+}
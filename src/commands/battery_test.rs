@@ -0,0 +1,28 @@
+use super::battery::{parse_sample, BatterySample};
+
+#[test]
+fn test_parse_sample() {
+    let output = "87\n--aim--\n-412000\n--aim--\n  mWakefulness=Awake\n\
+Wake Locks: size=2\n\
+  PARTIAL_WAKE_LOCK              'NlpWakeLock' ACQ=-1s (uid=10123 pid=4567)\n\
+  SCREEN_BRIGHT_WAKE_LOCK        'WindowManager' ON_AFTER_RELEASE ACQ=-5m (uid=1000 pid=1234)\n";
+
+    assert_eq!(
+        parse_sample(output),
+        BatterySample {
+            level: Some(87),
+            current_ma: Some(-412),
+            screen: Some("Awake".to_string()),
+            wakelocks: vec!["NlpWakeLock".to_string(), "WindowManager".to_string()],
+        }
+    );
+}
+
+#[test]
+fn test_parse_sample_missing_sysfs() {
+    let sample = parse_sample("\n--aim--\n\n--aim--\n  mWakefulness=Asleep\n");
+    assert_eq!(sample.level, None);
+    assert_eq!(sample.current_ma, None);
+    assert_eq!(sample.screen.as_deref(), Some("Asleep"));
+    assert!(sample.wakelocks.is_empty());
+}
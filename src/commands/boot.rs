@@ -0,0 +1,306 @@
+use crate::adb::connection::AdbConnection;
+use crate::adb::shell::ShellCommand;
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::{DeviceId, DeviceState, OutputFormat};
+use crate::device::DeviceManager;
+use crate::error::{AimError, Result};
+use crate::utils::{format_duration, parse_duration};
+use async_trait::async_trait;
+use chrono::Local;
+use clap::Subcommand;
+use colored::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// How often device state is checked while waiting for a reboot
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+lazy_static! {
+    // "01-01 00:00:12.345  1000  1000 I boot_progress_start: 5432"
+    static ref BOOT_PROGRESS_RE: Regex = Regex::new(r"\bboot_progress_(\w+)\s*:\s*(\d+)").unwrap();
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum BootCommands {
+    /// Reboot, capture logcat and dmesg from early boot, and report boot timings
+    Capture(CaptureArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: BootCommands) -> Result<()> {
+    match cmd {
+        BootCommands::Capture(args) => {
+            let cmd = CaptureCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+pub struct CaptureCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct CaptureArgs {
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Directory for logcat.txt, dmesg.txt and report.txt
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Give up if the device hasn't finished booting after this long
+    #[arg(long, default_value = "5m", value_parser = parse_duration)]
+    pub wait: Duration,
+}
+
+/// Host-side timings of one reboot, measured from the reboot request
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct BootTimes {
+    /// adb saw the device go away
+    pub offline_secs: f64,
+    /// adb saw the device come back
+    pub online_secs: f64,
+    /// `sys.boot_completed` became 1
+    pub completed_secs: f64,
+}
+
+/// A `boot_progress_*` event: stage name and device uptime in ms
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Milestone {
+    pub stage: String,
+    pub uptime_ms: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct BootReport {
+    serial: String,
+    #[serde(flatten)]
+    times: BootTimes,
+    /// Device uptime when boot completed, i.e. boot time as the device counts it
+    device_uptime_secs: Option<f64>,
+    milestones: Vec<Milestone>,
+    logcat: PathBuf,
+    dmesg: Option<PathBuf>,
+}
+
+/// `boot_progress_*` events in the order they first appear
+pub fn parse_boot_progress(logcat: &str) -> Vec<Milestone> {
+    let mut milestones: Vec<Milestone> = Vec::new();
+    for caps in logcat.lines().filter_map(|line| BOOT_PROGRESS_RE.captures(line)) {
+        let stage = caps[1].to_string();
+        if milestones.iter().any(|m| m.stage == stage) {
+            continue;
+        }
+        if let Ok(uptime_ms) = caps[2].parse() {
+            milestones.push(Milestone { stage, uptime_ms });
+        }
+    }
+    milestones
+}
+
+/// Seconds since boot from `/proc/uptime`
+pub fn parse_uptime(output: &str) -> Option<f64> {
+    output.split_whitespace().next()?.parse().ok()
+}
+
+/// Ask adbd to reboot the device
+pub async fn reboot(host: &str, port: u16, device: &DeviceId) -> Result<()> {
+    let mut conn = AdbConnection::connect(host, port).await?;
+    conn.select_device(device).await?;
+    conn.send_command("reboot:")?;
+    conn.read_okay()?;
+    Ok(())
+}
+
+async fn is_online(host: &str, port: u16, device: &DeviceId) -> bool {
+    DeviceManager::with_address(host, port.to_string())
+        .list_devices()
+        .await
+        .is_ok_and(|devices| devices.iter().any(|d| d.id == *device && d.state == DeviceState::Device))
+}
+
+async fn boot_completed(host: &str, port: u16, device: &DeviceId) -> bool {
+    ShellCommand::new("getprop sys.boot_completed")
+        .with_device(device.clone())
+        .execute(host, port)
+        .await
+        .is_ok_and(|out| out.stdout.trim() == "1")
+}
+
+/// Wait until the device is back after a reboot and adb is usable
+///
+/// `started` is when the reboot was requested. Returns the seconds after
+/// it at which adb lost and regained the device; boot has usually not
+/// completed yet at that point.
+pub async fn wait_for_online(host: &str, port: u16, device: &DeviceId, started: Instant, timeout: Duration) -> Result<(f64, f64)> {
+    let deadline = started + timeout;
+    let timed_out = || AimError::Timeout(timeout.as_secs());
+
+    while is_online(host, port, device).await {
+        if Instant::now() > deadline {
+            return Err(timed_out());
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    let offline = started.elapsed().as_secs_f64();
+
+    while !is_online(host, port, device).await {
+        if Instant::now() > deadline {
+            return Err(timed_out());
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    Ok((offline, started.elapsed().as_secs_f64()))
+}
+
+/// Wait for `sys.boot_completed`, returning seconds since `started`
+pub async fn wait_for_boot_completed(
+    host: &str,
+    port: u16,
+    device: &DeviceId,
+    started: Instant,
+    timeout: Duration,
+) -> Result<f64> {
+    while !boot_completed(host, port, device).await {
+        if started.elapsed() > timeout {
+            return Err(AimError::Timeout(timeout.as_secs()));
+        }
+        tokio::time::sleep(POLL_INTERVAL * 2).await;
+    }
+    Ok(started.elapsed().as_secs_f64())
+}
+
+impl CaptureCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn report_text(report: &BootReport) -> String {
+        let mut text = format!("Boot report for {}\n\n", report.serial);
+        let mut row = |label: &str, secs: f64| text.push_str(&format!("  {:<22}{:>7.1}s\n", label, secs));
+        row("adb offline after", report.times.offline_secs);
+        row("adb online after", report.times.online_secs);
+        row("boot completed after", report.times.completed_secs);
+        if let Some(uptime) = report.device_uptime_secs {
+            row("device uptime then", uptime);
+        }
+        if !report.milestones.is_empty() {
+            text.push_str("\nBoot progress (device uptime)\n");
+            for m in &report.milestones {
+                text.push_str(&format!("  {:>8.2}s  {}\n", m.uptime_ms as f64 / 1000.0, m.stage));
+            }
+        }
+        text
+    }
+}
+
+#[async_trait]
+impl SubCommand for CaptureCommand {
+    type Args = CaptureArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let show_progress = ctx.output_format != OutputFormat::Json;
+
+        let dir = args.output.clone().unwrap_or_else(|| {
+            PathBuf::from(format!(
+                "boot-{}-{}",
+                device.id.short_id(),
+                Local::now().format("%Y%m%d-%H%M%S")
+            ))
+        });
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| AimError::CommandExecution(format!("Cannot create {}: {}", dir.display(), e)))?;
+        let logcat_path = dir.join("logcat.txt");
+        let mut logcat_file = std::fs::File::create(&logcat_path)
+            .map_err(|e| AimError::CommandExecution(format!("Cannot create {}: {}", logcat_path.display(), e)))?;
+
+        if show_progress {
+            println!("Rebooting {}...", device.id.to_string().bright_cyan());
+        }
+        let started = Instant::now();
+        reboot(host, port, &device.id).await?;
+
+        let (offline_secs, online_secs) = wait_for_online(host, port, &device.id, started, args.wait).await?;
+        if show_progress {
+            println!("Device back after {}, capturing logs...", format_duration(started.elapsed()));
+        }
+
+        // All buffers are read from the start, so early boot is included
+        // as long as nothing has rotated out yet
+        let (host_owned, device_id) = (host.to_string(), device.id.clone());
+        let logcat = tokio::spawn(async move {
+            ShellCommand::new("logcat -b all -v threadtime")
+                .with_device(device_id)
+                .execute_streaming(&host_owned, port, move |chunk| {
+                    let _ = logcat_file.write_all(chunk.as_bytes());
+                })
+                .await
+        });
+
+        let completed_secs = wait_for_boot_completed(host, port, &device.id, started, args.wait).await;
+        let uptime = ShellCommand::new("cat /proc/uptime")
+            .with_device(device.id.clone())
+            .execute(host, port)
+            .await
+            .ok()
+            .and_then(|out| parse_uptime(&out.stdout));
+
+        // Let logcat catch up with the last boot messages before stopping it
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        logcat.abort();
+        let completed_secs = completed_secs?;
+
+        // dmesg is restricted on some user builds; the report says so
+        let dmesg = ShellCommand::new("dmesg")
+            .with_device(device.id.clone())
+            .execute(host, port)
+            .await
+            .ok()
+            .filter(|out| !out.stdout.trim().is_empty() && !out.stdout.contains("Permission denied"));
+        let dmesg_path = match dmesg {
+            Some(out) => {
+                let path = dir.join("dmesg.txt");
+                std::fs::write(&path, out.stdout)
+                    .map_err(|e| AimError::CommandExecution(format!("Cannot write {}: {}", path.display(), e)))?;
+                Some(path)
+            }
+            None => None,
+        };
+
+        let captured = std::fs::read_to_string(&logcat_path).unwrap_or_default();
+        let report = BootReport {
+            serial: device.id.to_string(),
+            times: BootTimes {
+                offline_secs,
+                online_secs,
+                completed_secs,
+            },
+            device_uptime_secs: uptime,
+            milestones: parse_boot_progress(&captured),
+            logcat: logcat_path,
+            dmesg: dmesg_path,
+        };
+
+        let text = Self::report_text(&report);
+        let report_path = dir.join("report.txt");
+        std::fs::write(&report_path, &text)
+            .map_err(|e| AimError::CommandExecution(format!("Cannot write {}: {}", report_path.display(), e)))?;
+
+        if show_progress {
+            println!();
+            print!("{}", text);
+            if report.dmesg.is_none() {
+                println!("\n{}", "dmesg was not readable on this device; only logcat was captured".yellow());
+            }
+            println!("\nSaved to {}", dir.display());
+        } else {
+            crate::commands::format_json_output(&report)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use super::boot::{parse_boot_progress, parse_uptime, Milestone};
+
+#[test]
+fn test_parse_boot_progress() {
+    let logcat = "\
+01-01 00:00:04.100   512   512 I boot_progress_start: 4100
+01-01 00:00:05.200   600   600 I boot_progress_preload_start: 5200
+01-01 00:00:05.300   600   600 I Zygote  : Preloading classes...
+01-01 00:00:09.900  1000  1000 I boot_progress_ams_ready: 9900
+01-01 00:00:12.000  1000  1000 I boot_progress_start: 12000
+";
+    assert_eq!(
+        parse_boot_progress(logcat),
+        vec![
+            Milestone { stage: "start".to_string(), uptime_ms: 4100 },
+            Milestone { stage: "preload_start".to_string(), uptime_ms: 5200 },
+            Milestone { stage: "ams_ready".to_string(), uptime_ms: 9900 },
+        ]
+    );
+    assert!(parse_boot_progress("no milestones here").is_empty());
+}
+
+#[test]
+fn test_parse_uptime() {
+    assert_eq!(parse_uptime("23.41 80.12\n"), Some(23.41));
+    assert_eq!(parse_uptime(""), None);
+}
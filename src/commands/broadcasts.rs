@@ -0,0 +1,270 @@
+use crate::adb::shell::ShellCommand;
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::{DeviceId, OutputFormat};
+use crate::error::Result;
+use crate::output::TableFormat;
+use async_trait::async_trait;
+use colored::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::time::Duration;
+
+lazy_static! {
+    // "BroadcastRecord{a1b2c3 u0 android.intent.action.SCREEN_OFF}", u-1 for all users
+    static ref BROADCAST_RECORD_RE: Regex = Regex::new(r"BroadcastRecord\{(\S+) u(-?\d+) ([^\s}]+)").unwrap();
+    // "Deliver +2ms #0: ...", "Skipped #1: ...", "Pending #2: ..."
+    static ref RECEIVER_RE: Regex = Regex::new(r"^([A-Za-z]+)(?: [+-]\S+)? #\d+: (.*)$").unwrap();
+    // "ReceiverList{ba7bc28 1234 com.example/10123/u0 remote:5d6e7f}"
+    static ref RECEIVER_LIST_RE: Regex = Regex::new(r"ReceiverList\{\S+ (\d+) ([^\s/]+)/").unwrap();
+    static ref COMPONENT_RE: Regex = Regex::new(r"([A-Za-z_][\w.]*/[\w.$]+)").unwrap();
+}
+
+pub struct BroadcastsCommand;
+
+#[derive(Debug, Clone)]
+pub struct BroadcastsArgs {
+    pub package: Option<String>,
+    pub follow: bool,
+    pub interval: Duration,
+    pub device_id: Option<String>,
+}
+
+/// A receiver a broadcast went to, and what happened to it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BroadcastReceiver {
+    /// Component of a manifest receiver, or `<process> (pid N)` for a registered one
+    pub receiver: String,
+    /// delivered, skipped, pending, timeout or failed
+    pub state: String,
+}
+
+/// A broadcast from the activity manager's history
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BroadcastRecord {
+    #[serde(skip)]
+    pub id: String,
+    pub action: String,
+    pub user: i32,
+    /// Enqueue time in device local time, `YYYY-MM-DD HH:MM:SS.mmm`
+    pub time: Option<String>,
+    pub sender: Option<String>,
+    pub intent: Option<String>,
+    pub receivers: Vec<BroadcastReceiver>,
+}
+
+impl BroadcastRecord {
+    /// Whether `package` sent the broadcast, received it or was targeted by it
+    pub fn involves(&self, package: &str) -> bool {
+        let is_package = |name: &str| {
+            name == package
+                || name.strip_prefix(package).is_some_and(|rest| rest.starts_with('/') || rest.starts_with(':') || rest.starts_with(' '))
+        };
+        self.sender.as_deref().is_some_and(is_package)
+            || self.receivers.iter().any(|r| is_package(&r.receiver))
+            || self.intent.as_deref().is_some_and(|intent| {
+                intent.contains(&format!("cmp={}/", package)) || intent.contains(&format!("pkg={} ", package))
+            })
+    }
+
+    fn key(&self) -> (String, Option<String>) {
+        (self.id.clone(), self.time.clone())
+    }
+
+    fn short_time(&self) -> String {
+        self.time
+            .as_deref()
+            .and_then(|t| t.split_whitespace().nth(1))
+            .unwrap_or("-")
+            .to_string()
+    }
+
+    fn receivers_summary(&self) -> String {
+        if self.receivers.is_empty() {
+            return "(none)".to_string();
+        }
+        self.receivers
+            .iter()
+            .map(|r| match r.state.as_str() {
+                "delivered" => r.receiver.clone(),
+                state => format!("{} [{}]", r.receiver, state),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl TableFormat for BroadcastRecord {
+    fn headers() -> Vec<&'static str> {
+        vec!["TIME", "ACTION", "SENDER", "RECEIVERS"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.short_time(),
+            self.action.clone(),
+            self.sender.clone().unwrap_or_else(|| "-".to_string()),
+            self.receivers_summary(),
+        ]
+    }
+}
+
+impl std::fmt::Display for BroadcastRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}  {}  {} -> {}",
+            self.short_time(),
+            self.action,
+            self.sender.as_deref().unwrap_or("-"),
+            self.receivers_summary()
+        )
+    }
+}
+
+fn delivery_state(state: &str) -> String {
+    match state {
+        "Deliver" | "Delivered" => "delivered",
+        "Skip" | "Skipped" => "skipped",
+        "Pending" | "Scheduled" => "pending",
+        "Timeout" => "timeout",
+        "Failure" | "Failed" => "failed",
+        other => return other.to_lowercase(),
+    }
+    .to_string()
+}
+
+fn parse_receiver(target: &str) -> String {
+    if let Some(caps) = RECEIVER_LIST_RE.captures(target) {
+        return format!("{} (pid {})", &caps[2], &caps[1]);
+    }
+    if target.contains("ResolveInfo{") {
+        if let Some(component) = COMPONENT_RE.captures(target) {
+            return component[1].to_string();
+        }
+    }
+    target.trim_matches(|c| c == '(' || c == ')').to_string()
+}
+
+/// Broadcast records of `dumpsys activity broadcasts`, oldest first
+///
+/// Records show up in the active queues and in the per-queue history; each
+/// one is kept once.
+pub fn parse_broadcasts(output: &str) -> Vec<BroadcastRecord> {
+    let mut records: Vec<BroadcastRecord> = Vec::new();
+    let mut current: Option<BroadcastRecord> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(caps) = BROADCAST_RECORD_RE.captures(trimmed).filter(|_| trimmed.starts_with("BroadcastRecord{")) {
+            records.extend(current.take());
+            current = Some(BroadcastRecord {
+                id: caps[1].to_string(),
+                user: caps[2].parse().unwrap_or(0),
+                action: caps[3].to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+        let Some(record) = current.as_mut() else {
+            continue;
+        };
+        // Section headers and the summary list end a record
+        if trimmed.starts_with("Historical") || trimmed.is_empty() || !line.starts_with("    ") {
+            records.extend(current.take());
+            continue;
+        }
+
+        if let Some(intent) = trimmed.strip_prefix("Intent ") {
+            record.intent.get_or_insert_with(|| intent.to_string());
+        } else if let Some(caller) = trimmed.strip_prefix("caller=") {
+            record.sender = caller.split_whitespace().next().map(str::to_string);
+        } else if let Some(rest) = trimmed.strip_prefix("enqueueClockTime=") {
+            let time = rest.split(" dispatchClockTime=").next().unwrap_or(rest).trim();
+            record.time = Some(time.to_string());
+        } else if let Some(caps) = RECEIVER_RE.captures(trimmed) {
+            let receiver = parse_receiver(&caps[2]);
+            if !record.receivers.iter().any(|r| r.receiver == receiver) {
+                record.receivers.push(BroadcastReceiver { receiver, state: delivery_state(&caps[1]) });
+            }
+        }
+    }
+    records.extend(current);
+
+    let mut seen = HashSet::new();
+    records.retain(|r| seen.insert(r.key()));
+    // Times are zero padded, so they sort as strings; the history lists newest first
+    records.reverse();
+    records.sort_by(|a, b| a.time.cmp(&b.time));
+    records
+}
+
+async fn read_broadcasts(host: &str, port: u16, device: &DeviceId) -> Result<Vec<BroadcastRecord>> {
+    let output = ShellCommand::new("dumpsys activity broadcasts")
+        .with_device(device.clone())
+        .execute(host, port)
+        .await?;
+    Ok(parse_broadcasts(&output.stdout))
+}
+
+impl BroadcastsCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for BroadcastsCommand {
+    type Args = BroadcastsArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let package = match &args.package {
+            Some(package) => {
+                let device_ctx = ctx.clone().with_device(device.clone());
+                Some(crate::commands::app::PackageResolver::new(&device_ctx).resolve(package).await?)
+            }
+            None => None,
+        };
+        let wanted = |r: &BroadcastRecord| package.as_deref().is_none_or(|p| r.involves(p));
+
+        let mut records = read_broadcasts(host, port, &device.id).await?;
+        records.retain(&wanted);
+
+        if !args.follow {
+            match ctx.output_format {
+                OutputFormat::Json => crate::commands::format_json_output(&records)?,
+                OutputFormat::Plain => crate::output::OutputFormatter::new().plain(&records)?,
+                OutputFormat::Table => {
+                    if records.is_empty() {
+                        println!("No broadcasts in the history");
+                    } else {
+                        crate::output::OutputFormatter::new().table(&records)?;
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        // Start from what is already in the history and print only new records
+        let mut seen: HashSet<_> = records.iter().map(|r| r.key()).collect();
+        if !ctx.quiet && ctx.output_format != OutputFormat::Json {
+            println!("{}", "Waiting for broadcasts (Ctrl+C to stop)".dimmed());
+        }
+        loop {
+            tokio::time::sleep(args.interval).await;
+            for record in read_broadcasts(host, port, &device.id).await? {
+                if !seen.insert(record.key()) || !wanted(&record) {
+                    continue;
+                }
+                match ctx.output_format {
+                    OutputFormat::Json => println!("{}", serde_json::to_string(&record)?),
+                    _ => println!("{}", record),
+                }
+            }
+        }
+    }
+}
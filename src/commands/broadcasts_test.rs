@@ -0,0 +1,95 @@
+use super::broadcasts::parse_broadcasts;
+
+const DUMPSYS: &str = "ACTIVITY MANAGER BROADCAST STATE (dumpsys activity broadcasts)
+  Registered Receivers:
+  * ReceiverList{ba7bc28 1234 com.example/10123/u0 remote:5d6e7f}
+    app=1234:com.example/u0a123 pid=1234 uid=10123 user=0
+
+  Historical broadcasts [foreground]:
+  Historical Broadcast foreground #0:
+    BroadcastRecord{c3d4e5 u0 com.example.SYNC_DONE} to user 0
+    Intent { act=com.example.SYNC_DONE flg=0x10 pkg=com.example }
+    caller=com.example 1234:com.example/u0a123 pid=1234 uid=10123
+    enqueueClockTime=2024-05-01 10:15:02.500 dispatchClockTime=2024-05-01 10:15:02.501
+    dispatchTime=-3s120ms (+1ms since enq) finishTime=-3s100ms (+20ms since disp)
+    resultTo=null resultCode=0 resultData=null
+    nextReceiver=1 receiver=null
+    Deliver +1ms #0: (BroadcastFilter{96c2f41 u0 ReceiverList{ba7bc28 1234 com.example/10123/u0 remote:5d6e7f}})
+  Historical Broadcast foreground #1:
+    BroadcastRecord{a1b2c3 u-1 android.intent.action.SCREEN_OFF} to user -1
+    Intent { act=android.intent.action.SCREEN_OFF flg=0x50200010 }
+    caller=android 1000:system/1000 pid=1000 uid=1000
+    enqueueClockTime=2024-05-01 10:14:58.010 dispatchClockTime=2024-05-01 10:14:58.012
+    Deliver +2ms #0: (BroadcastFilter{1f2e3d u-1 ReceiverList{4a5b6c 2345 com.android.systemui/10100/u-1 remote:7d8e9f}})
+    Skipped #1: ResolveInfo{7d8c9b com.example/.ScreenReceiver m=0x108000 userHandle=UserHandle{0}}
+      priority=0 preferredOrder=0 match=0x108000 specificIndex=-1 isDefault=false
+    Pending #2: ResolveInfo{8e9fa0 com.other/.Listener m=0x108000 userHandle=UserHandle{0}}
+
+  Historical broadcasts summary [foreground]:
+  #0: act=com.example.SYNC_DONE flg=0x10 pkg=com.example
+    +1ms dispatch +20ms finish
+    enq=2024-05-01 10:15:02.500 disp=2024-05-01 10:15:02.501 fin=2024-05-01 10:15:02.521
+";
+
+#[test]
+fn test_parse_broadcasts() {
+    let records = parse_broadcasts(DUMPSYS);
+    assert_eq!(records.len(), 2);
+
+    // Oldest first
+    let screen_off = &records[0];
+    assert_eq!(screen_off.action, "android.intent.action.SCREEN_OFF");
+    assert_eq!(screen_off.user, -1);
+    assert_eq!(screen_off.sender.as_deref(), Some("android"));
+    assert_eq!(screen_off.time.as_deref(), Some("2024-05-01 10:14:58.010"));
+    let receivers: Vec<(&str, &str)> = screen_off
+        .receivers
+        .iter()
+        .map(|r| (r.receiver.as_str(), r.state.as_str()))
+        .collect();
+    assert_eq!(
+        receivers,
+        vec![
+            ("com.android.systemui (pid 2345)", "delivered"),
+            ("com.example/.ScreenReceiver", "skipped"),
+            ("com.other/.Listener", "pending"),
+        ]
+    );
+
+    let sync = &records[1];
+    assert_eq!(sync.action, "com.example.SYNC_DONE");
+    assert_eq!(sync.intent.as_deref(), Some("{ act=com.example.SYNC_DONE flg=0x10 pkg=com.example }"));
+    assert_eq!(sync.receivers.len(), 1);
+    assert_eq!(sync.receivers[0].receiver, "com.example (pid 1234)");
+}
+
+#[test]
+fn test_broadcast_involves() {
+    let records = parse_broadcasts(DUMPSYS);
+    let (screen_off, sync) = (&records[0], &records[1]);
+
+    // Sender, receivers and the intent's target package all count
+    assert!(sync.involves("com.example"));
+    assert!(screen_off.involves("com.example"));
+    assert!(screen_off.involves("com.android.systemui"));
+    assert!(screen_off.involves("android"));
+    assert!(!sync.involves("com.other"));
+    // A prefix of another package is not a match
+    assert!(!sync.involves("com.exam"));
+}
+
+#[test]
+fn test_parse_broadcasts_dedup() {
+    // A broadcast in flight shows up in the active queue and the history
+    let output = "  Active ordered broadcasts [background]:
+  Active Ordered Broadcast background #0:
+    BroadcastRecord{f0e1d2 u0 android.intent.action.BOOT_COMPLETED}
+    enqueueClockTime=2024-05-01 10:00:00.000 dispatchClockTime=2024-05-01 10:00:00.010
+  Historical broadcasts [background]:
+  Historical Broadcast background #0:
+    BroadcastRecord{f0e1d2 u0 android.intent.action.BOOT_COMPLETED}
+    enqueueClockTime=2024-05-01 10:00:00.000 dispatchClockTime=2024-05-01 10:00:00.010
+";
+    assert_eq!(parse_broadcasts(output).len(), 1);
+    assert!(parse_broadcasts("").is_empty());
+}
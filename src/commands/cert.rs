@@ -0,0 +1,421 @@
+use crate::android::sdk::{self, Capability};
+use crate::adb::connection::AdbConnection;
+use crate::adb::shell::helpers::shell;
+use crate::adb::su::RootMode;
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::{DeviceId, OutputFormat};
+use crate::error::{AimError, Result};
+use crate::output::{OutputFormatter, TableFormat};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use clap::Subcommand;
+use colored::*;
+use md5::{Digest, Md5};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// CA certificates shipped with the system image
+pub const SYSTEM_STORE: &str = "/system/etc/security/cacerts";
+/// CA certificates read from the Conscrypt APEX on Android 14 and later
+pub const APEX_STORE: &str = "/apex/com.android.conscrypt/cacerts";
+/// CA certificates the user added, for user 0
+pub const USER_STORE: &str = "/data/misc/user/0/cacerts-added";
+
+/// DER object identifiers (content bytes) of the name attributes shown for a certificate
+const OID_COMMON_NAME: &[u8] = &[0x55, 0x04, 0x03];
+const OID_ORGANIZATION: &[u8] = &[0x55, 0x04, 0x0a];
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum CertCommands {
+    /// Install a CA certificate (PEM or DER) on the device
+    Install(InstallArgs),
+    /// List CA certificates on the device
+    List(ListArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: CertCommands) -> Result<()> {
+    match cmd {
+        CertCommands::Install(args) => {
+            let cmd = InstallCommand::new();
+            cmd.run(ctx, args).await
+        }
+        CertCommands::List(args) => {
+            let cmd = ListCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct InstallArgs {
+    /// Certificate file, PEM or DER
+    pub file: PathBuf,
+
+    /// Install into the system store (needs root; remounts /system when adbd is root)
+    #[arg(long)]
+    pub system: bool,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Store {
+    /// Certificates the user added
+    #[default]
+    User,
+    /// Certificates that ship with the system
+    System,
+    All,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ListArgs {
+    /// Which store to list
+    #[arg(long, value_enum, default_value_t = Store::User)]
+    pub store: Store,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// Decode standard base64, ignoring whitespace
+pub fn base64_decode(input: &str) -> Option<Vec<u8>> {
+    let value = |c: u8| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    };
+    let symbols: Vec<u8> = input.bytes().filter(|c| !c.is_ascii_whitespace()).collect();
+    let data = symbols.strip_suffix(b"==").or_else(|| symbols.strip_suffix(b"=")).unwrap_or(&symbols);
+    let mut out = Vec::with_capacity(data.len() * 3 / 4);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for &c in data {
+        buffer = (buffer << 6) | value(c)? as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// Encode as standard base64
+pub fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | ((b as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// The first certificate in a PEM file, as DER
+pub fn pem_to_der(pem: &str) -> Option<Vec<u8>> {
+    let body: String = pem
+        .lines()
+        .skip_while(|l| !l.starts_with("-----BEGIN CERTIFICATE-----"))
+        .skip(1)
+        .take_while(|l| !l.starts_with("-----END CERTIFICATE-----"))
+        .collect();
+    base64_decode(&body).filter(|der| !der.is_empty())
+}
+
+/// PEM encoding of a DER certificate, the format of Android's store files
+pub fn der_to_pem(der: &[u8]) -> String {
+    let encoded = base64_encode(der);
+    let mut pem = String::from("-----BEGIN CERTIFICATE-----\n");
+    for line in encoded.as_bytes().chunks(64) {
+        pem.push_str(std::str::from_utf8(line).unwrap_or_default());
+        pem.push('\n');
+    }
+    pem.push_str("-----END CERTIFICATE-----\n");
+    pem
+}
+
+/// A DER element: tag, where it starts, where its content starts and where it ends
+fn read_tlv(data: &[u8], pos: usize) -> Option<(u8, usize, usize, usize)> {
+    let tag = *data.get(pos)?;
+    let first = *data.get(pos + 1)? as usize;
+    let (len, header) = if first & 0x80 == 0 {
+        (first, 2)
+    } else {
+        let count = first & 0x7f;
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let bytes = data.get(pos + 2..pos + 2 + count)?;
+        (bytes.iter().fold(0usize, |n, &b| (n << 8) | b as usize), 2 + count)
+    };
+    let end = (pos + header).checked_add(len)?;
+    (end <= data.len()).then_some((tag, pos, pos + header, end))
+}
+
+/// The subject Name of a certificate, tag and length included
+fn subject(der: &[u8]) -> Option<&[u8]> {
+    let (_, _, cert, _) = read_tlv(der, 0)?;
+    let (_, _, tbs, _) = read_tlv(der, cert)?;
+    let mut pos = tbs;
+    // Optional explicit [0] version
+    let (tag, _, _, end) = read_tlv(der, pos)?;
+    if tag == 0xa0 {
+        pos = end;
+    }
+    // serialNumber, signature, issuer, validity
+    for _ in 0..4 {
+        pos = read_tlv(der, pos)?.3;
+    }
+    let (tag, start, _, end) = read_tlv(der, pos)?;
+    (tag == 0x30).then(|| &der[start..end])
+}
+
+/// The hash Android names store files after (`openssl x509 -subject_hash_old`)
+pub fn subject_hash_old(der: &[u8]) -> Option<String> {
+    let digest = Md5::digest(subject(der)?);
+    Some(format!("{:08x}", u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]])))
+}
+
+/// The subject's common name, or its organization when there is none
+pub fn subject_name(der: &[u8]) -> Option<String> {
+    let name = subject(der)?;
+    let (_, _, mut pos, end) = read_tlv(name, 0)?;
+    let mut found: Vec<(&[u8], String)> = Vec::new();
+    while pos < end {
+        let (_, _, set, set_end) = read_tlv(name, pos)?;
+        let (_, _, attr, _) = read_tlv(name, set)?;
+        let (_, _, oid, oid_end) = read_tlv(name, attr)?;
+        let (_, _, value, value_end) = read_tlv(name, oid_end)?;
+        found.push((&name[oid..oid_end], String::from_utf8_lossy(&name[value..value_end]).into_owned()));
+        pos = set_end;
+    }
+    [OID_COMMON_NAME, OID_ORGANIZATION]
+        .iter()
+        .find_map(|wanted| found.iter().find(|(oid, _)| oid == wanted).map(|(_, v)| v.clone()))
+}
+
+/// Certificates from a `==> path` / contents listing, as (path, DER)
+pub fn parse_listing(output: &str) -> Vec<(String, Vec<u8>)> {
+    output
+        .split("==> ")
+        .filter_map(|entry| {
+            let (path, contents) = entry.split_once('\n')?;
+            Some((path.trim().to_string(), pem_to_der(contents)?))
+        })
+        .collect()
+}
+
+/// Shell loop printing every file in `dirs` after a `==> path` line
+fn listing_command(dirs: &[&str]) -> String {
+    format!(
+        "for f in {}; do [ -f \"$f\" ] && echo \"==> $f\" && cat \"$f\"; done 2>/dev/null",
+        dirs.iter().map(|d| format!("{}/*", d)).collect::<Vec<_>>().join(" ")
+    )
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CertEntry {
+    pub store: String,
+    pub file: String,
+    pub subject: String,
+}
+
+impl TableFormat for CertEntry {
+    fn headers() -> Vec<&'static str> {
+        vec!["STORE", "FILE", "SUBJECT"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.store.clone(), self.file.clone(), self.subject.clone()]
+    }
+}
+
+impl std::fmt::Display for CertEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\t{}\t{}", self.store, self.file, self.subject)
+    }
+}
+
+/// Remount /system read-write through adbd, as `adb remount` does
+async fn remount(host: &str, port: u16, device: &DeviceId) -> Result<String> {
+    let mut conn = AdbConnection::connect(host, port).await?;
+    conn.select_device(device).await?;
+    conn.send_command("remount:")?;
+    conn.read_okay()?;
+    conn.read_to_end()
+}
+
+pub struct InstallCommand;
+
+impl InstallCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Read a PEM or DER certificate
+    fn load(path: &PathBuf) -> Result<Vec<u8>> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| AimError::InvalidArgument(format!("Cannot read {}: {}", path.display(), e)))?;
+        let der = match std::str::from_utf8(&bytes) {
+            Ok(text) if text.contains("-----BEGIN CERTIFICATE-----") => pem_to_der(text),
+            _ => Some(bytes),
+        };
+        der.filter(|der| subject(der).is_some()).ok_or_else(|| {
+            AimError::InvalidArgument(format!("{} is not a PEM or DER certificate", path.display()))
+        })
+    }
+}
+
+#[async_trait]
+impl SubCommand for InstallCommand {
+    type Args = InstallArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let der = Self::load(&args.file)?;
+        let hash = subject_hash_old(&der).unwrap_or_default();
+        let name = subject_name(&der).unwrap_or_else(|| hash.clone());
+        let pem = der_to_pem(&der);
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        let staged = format!("/data/local/tmp/aim-{}.0", hash);
+        shell(host, port, &device.id, &format!("printf '%s' {} > {}", shell_quote(&pem), staged)).await?;
+
+        let root = RootMode::detect(host, port, &device.id).await;
+        if args.system {
+            let root = root?;
+            if sdk::for_device(host, port, &device.id).await.known_to_have(Capability::ApexCaStore) {
+                return Err(AimError::CommandExecution(format!(
+                    "Android 14 and later read system CA certificates from {}, which can't be remounted. \
+                     Install into the user store instead, or use a Magisk module that adds it.",
+                    APEX_STORE
+                )));
+            }
+
+            let target = format!("{}/{}.0", SYSTEM_STORE, hash);
+            let copy = format!(
+                "cp {staged} {target} && chmod 644 {target} && chcon u:object_r:system_file:s0 {target}",
+                staged = staged,
+                target = target
+            );
+            let note = if root == RootMode::Adbd {
+                let out = remount(host, port, &device.id).await?;
+                if out.contains("reboot") {
+                    return Err(AimError::CommandExecution(format!(
+                        "Remounting needs a reboot first ({}). Reboot, then run this again.",
+                        out.trim()
+                    )));
+                }
+                "installed"
+            } else {
+                // su can't remount a verified /system, so lay a tmpfs copy over the store
+                let overlay = format!(
+                    "mkdir -p /data/local/tmp/aim-cacerts && cp {store}/* /data/local/tmp/aim-cacerts/ && \
+                     mount -t tmpfs tmpfs {store} && cp /data/local/tmp/aim-cacerts/* {store}/ && \
+                     chmod 644 {store}/* && chcon u:object_r:system_file:s0 {store}/*",
+                    store = SYSTEM_STORE
+                );
+                shell(host, port, &device.id, &root.wrap(&overlay)).await?;
+                "installed until the next reboot"
+            };
+            shell(host, port, &device.id, &format!("{}; rm -f {}", root.wrap(&copy), staged)).await?;
+            if !ctx.quiet {
+                println!("{} {} {} in the system store as {}.0", "✓".green(), name.bright_cyan(), note, hash);
+                println!("Restart apps (or reboot) so they pick it up");
+            }
+        } else if let Ok(root) = root {
+            let target = format!("{}/{}.0", USER_STORE, hash);
+            let copy = format!(
+                "mkdir -p {store} && cp {staged} {target} && chown system:system {target} && chmod 644 {target}",
+                store = USER_STORE,
+                staged = staged,
+                target = target
+            );
+            shell(host, port, &device.id, &format!("{}; rm -f {}", root.wrap(&copy), staged)).await?;
+            if !ctx.quiet {
+                println!("{} {} installed in the user store as {}.0", "✓".green(), name.bright_cyan(), hash);
+                println!("Restart apps so they pick it up");
+            }
+        } else {
+            // Without root, Android only lets the user add CA certificates through Settings
+            let download = format!("/sdcard/Download/{}.crt", hash);
+            shell(host, port, &device.id, &format!("mv {} {}", staged, download)).await?;
+            if !ctx.quiet {
+                println!("Copied {} to {}", name.bright_cyan(), download);
+                println!("Android only installs CA certificates from Settings without root. On the device:");
+                println!("  Settings > Security > Encryption & credentials > Install a certificate > CA certificate");
+                println!("  and pick Download/{}.crt", hash);
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct ListCommand;
+
+impl ListCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for ListCommand {
+    type Args = ListArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        let mut listed: Vec<(&str, String)> = Vec::new();
+        if matches!(args.store, Store::System | Store::All) {
+            let command = listing_command(&[SYSTEM_STORE, APEX_STORE]);
+            listed.push(("system", shell(host, port, &device.id, &command).await?));
+        }
+        if matches!(args.store, Store::User | Store::All) {
+            // The user store is only readable by system and root
+            match RootMode::detect(host, port, &device.id).await {
+                Ok(root) => {
+                    let command = root.wrap(&listing_command(&[USER_STORE]));
+                    listed.push(("user", shell(host, port, &device.id, &command).await?));
+                }
+                Err(_) if args.store == Store::User => return Err(AimError::RootUnavailable(device.id.to_string())),
+                Err(_) => eprintln!("{}", "Skipping the user store, which needs root to read".yellow()),
+            }
+        }
+
+        let mut entries: Vec<CertEntry> = listed
+            .iter()
+            .flat_map(|(store, output)| {
+                parse_listing(output).into_iter().map(move |(path, der)| CertEntry {
+                    store: store.to_string(),
+                    file: path.rsplit('/').next().unwrap_or(&path).to_string(),
+                    subject: subject_name(&der).unwrap_or_default(),
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| (&a.store, &a.subject, &a.file).cmp(&(&b.store, &b.subject, &b.file)));
+        entries.dedup_by(|a, b| a.store == b.store && a.file == b.file);
+
+        let formatter = OutputFormatter::new();
+        match ctx.output_format {
+            OutputFormat::Table => formatter.table(&entries)?,
+            OutputFormat::Json => formatter.json(&entries)?,
+            OutputFormat::Plain => formatter.plain(&entries)?,
+        }
+        Ok(())
+    }
+}
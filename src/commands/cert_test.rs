@@ -0,0 +1,54 @@
+use super::cert::{base64_decode, base64_encode, der_to_pem, parse_listing, pem_to_der, subject_hash_old, subject_name};
+
+// openssl req -x509 -newkey ec -subj "/O=aim test/CN=aim Test CA"
+const CA_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIBpjCCAU2gAwIBAgIUAjXRHLWeVeOk80GLTB0KPwhgVcswCgYIKoZIzj0EAwIw
+KTERMA8GA1UECgwIYWltIHRlc3QxFDASBgNVBAMMC2FpbSBUZXN0IENBMB4XDTI2
+MTAxNjEwNTkxNVoXDTM2MTAxMzEwNTkxNVowKTERMA8GA1UECgwIYWltIHRlc3Qx
+FDASBgNVBAMMC2FpbSBUZXN0IENBMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAE
+Uh9Lz0Q36OZHXJUom/7Ocl/pNKGdx3xp8/KMtXfHFu8kDhVQZSDg1powWUbpP738
+EbIcfPtLzBXB7W/XHQ7eqqNTMFEwHQYDVR0OBBYEFAQGyt9XfFA0xIBvaoxj+ppj
+VfWQMB8GA1UdIwQYMBaAFAQGyt9XfFA0xIBvaoxj+ppjVfWQMA8GA1UdEwEB/wQF
+MAMBAf8wCgYIKoZIzj0EAwIDRwAwRAIgK66m9MXIIVjlE9xEnn5CwioxU/PVwzqz
+Y+OEBYcAU0MCIBUKuV/Tmkjb41Sevy+QzVTSkkQ+pxOfC7/QYHBBn4gR
+-----END CERTIFICATE-----
+";
+
+#[test]
+fn test_base64() {
+    assert_eq!(base64_encode(b"aim"), "YWlt");
+    assert_eq!(base64_encode(b"aim!"), "YWltIQ==");
+    assert_eq!(base64_encode(b"aim!?"), "YWltIT8=");
+    assert_eq!(base64_decode("YWltIQ==").unwrap(), b"aim!");
+    assert_eq!(base64_decode("YWlt\nIT8=").unwrap(), b"aim!?");
+    assert!(base64_decode("YW*t").is_none());
+}
+
+#[test]
+fn test_pem_roundtrip() {
+    let der = pem_to_der(CA_PEM).unwrap();
+    assert_eq!(der.len(), 426);
+    assert_eq!(der_to_pem(&der), CA_PEM);
+    assert!(pem_to_der("not a certificate").is_none());
+}
+
+#[test]
+fn test_subject() {
+    let der = pem_to_der(CA_PEM).unwrap();
+    // Matches `openssl x509 -subject_hash_old`
+    assert_eq!(subject_hash_old(&der).unwrap(), "15aeec3d");
+    assert_eq!(subject_name(&der).unwrap(), "aim Test CA");
+    assert!(subject_hash_old(b"\x30\x03\x02\x01\x00").is_none());
+}
+
+#[test]
+fn test_parse_listing() {
+    let output = format!(
+        "==> /system/etc/security/cacerts/15aeec3d.0\n{}==> /system/etc/security/cacerts/broken.0\ngarbage\n",
+        CA_PEM
+    );
+    let certs = parse_listing(&output);
+    assert_eq!(certs.len(), 1);
+    assert_eq!(certs[0].0, "/system/etc/security/cacerts/15aeec3d.0");
+    assert_eq!(subject_name(&certs[0].1).unwrap(), "aim Test CA");
+}
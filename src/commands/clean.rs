@@ -0,0 +1,186 @@
+use crate::commands::fs::{parse_du, DuEntry};
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::Device;
+use crate::error::Result;
+use crate::utils::{format_bytes, shell_quote};
+use async_trait::async_trait;
+use colored::*;
+
+const TMP_DIR: &str = "/data/local/tmp";
+const DOWNLOAD_DIR: &str = "/sdcard/Download";
+
+pub struct CleanCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct CleanArgs {
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Trim app caches
+    #[clap(long)]
+    pub caches: bool,
+
+    /// Remove leftovers in /data/local/tmp
+    #[clap(long)]
+    pub tmp: bool,
+
+    /// Remove stray APKs in /sdcard/Download
+    #[clap(long)]
+    pub apks: bool,
+
+    /// Show what would be removed without removing anything
+    #[clap(short = 'n', long)]
+    pub dry_run: bool,
+
+    /// Skip confirmation prompt
+    #[clap(short = 'y', long)]
+    pub yes: bool,
+}
+
+/// Parse available bytes from the last line of `df -k <path>`
+pub fn parse_df_available(output: &str) -> Option<u64> {
+    let line = output.lines().rev().find(|l| !l.trim().is_empty())?;
+    let available: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(available * 1024)
+}
+
+impl CleanCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn shell(&self, ctx: &CommandContext, device: &Device, cmd: String) -> Result<String> {
+        let (host, port) = ctx.adb_address();
+        crate::adb::shell::helpers::shell(host, port, &device.id, &cmd).await
+    }
+
+    async fn data_available(&self, ctx: &CommandContext, device: &Device) -> Result<Option<u64>> {
+        let output = self.shell(ctx, device, "df -k /data".to_string()).await?;
+        Ok(parse_df_available(&output))
+    }
+
+    fn confirm(&self, count: usize, total: u64) -> Result<bool> {
+        use std::io::{self, Write};
+
+        crate::core::ci::require_interactive("Confirmation prompt (pass --yes)")?;
+        print!(
+            "Remove {} item(s) ({})? [y/N] ",
+            count,
+            format_bytes(total)
+        );
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        Ok(input.trim().eq_ignore_ascii_case("y"))
+    }
+
+    fn print_entries(entries: &[DuEntry]) {
+        for entry in entries {
+            println!("  {:>8}  {}", format_bytes(entry.size), entry.path);
+        }
+    }
+
+    async fn trim_caches(&self, ctx: &CommandContext, device: &Device, dry_run: bool) -> Result<u64> {
+        println!("{}", "App caches".bold());
+        if dry_run {
+            println!("  Would run pm trim-caches");
+            return Ok(0);
+        }
+
+        let before = self.data_available(ctx, device).await?;
+        // Asking for more free space than the device has trims every cache
+        self.shell(ctx, device, "pm trim-caches 999G".to_string()).await?;
+        let after = self.data_available(ctx, device).await?;
+
+        let reclaimed = match (before, after) {
+            (Some(before), Some(after)) => after.saturating_sub(before),
+            _ => 0,
+        };
+        println!("  {} Trimmed caches ({})", "✓".green(), format_bytes(reclaimed));
+        Ok(reclaimed)
+    }
+
+    async fn remove_entries(
+        &self,
+        ctx: &CommandContext,
+        device: &Device,
+        title: &str,
+        entries: Vec<DuEntry>,
+        args: &CleanArgs,
+    ) -> Result<u64> {
+        println!("{}", title.bold());
+        if entries.is_empty() {
+            println!("  Nothing to remove");
+            return Ok(0);
+        }
+
+        Self::print_entries(&entries);
+        let total: u64 = entries.iter().map(|e| e.size).sum();
+        if args.dry_run {
+            println!("  Would remove {} item(s) ({})", entries.len(), format_bytes(total));
+            return Ok(0);
+        }
+        if !args.yes && !self.confirm(entries.len(), total)? {
+            println!("  Skipped");
+            return Ok(0);
+        }
+
+        let paths: Vec<String> = entries.iter().map(|e| shell_quote(&e.path)).collect();
+        self.shell(ctx, device, format!("rm -rf {}", paths.join(" "))).await?;
+        println!("  {} Removed {} item(s) ({})", "✓".green(), entries.len(), format_bytes(total));
+        Ok(total)
+    }
+}
+
+#[async_trait]
+impl SubCommand for CleanCommand {
+    type Args = CleanArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+
+        // No category selected means clean everything
+        let all = !(args.caches || args.tmp || args.apks);
+        let mut reclaimed = 0;
+
+        if all || args.caches {
+            reclaimed += self.trim_caches(ctx, &device, args.dry_run).await?;
+        }
+
+        if all || args.tmp {
+            let output = self
+                .shell(ctx, &device, format!("du -sk {}/* 2>/dev/null", TMP_DIR))
+                .await?;
+            let entries = parse_du(&output);
+            reclaimed += self
+                .remove_entries(ctx, &device, &format!("Leftovers in {}", TMP_DIR), entries, &args)
+                .await?;
+        }
+
+        if all || args.apks {
+            let output = self
+                .shell(
+                    ctx,
+                    &device,
+                    format!("find {} -name '*.apk' -type f -exec du -k {{}} + 2>/dev/null", DOWNLOAD_DIR),
+                )
+                .await?;
+            let entries = parse_du(&output);
+            reclaimed += self
+                .remove_entries(ctx, &device, &format!("APKs in {}", DOWNLOAD_DIR), entries, &args)
+                .await?;
+        }
+
+        println!();
+        if args.dry_run {
+            println!("Dry run: nothing was removed");
+        } else {
+            println!("Reclaimed {}", format_bytes(reclaimed).bright_green());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+use super::clean::parse_df_available;
+
+#[test]
+fn test_parse_df_available() {
+    let output = "Filesystem      1K-blocks     Used Available Use% Mounted on\n\
+/dev/block/dm-5 115249236 41231432  73886668  36% /data\n";
+    assert_eq!(parse_df_available(output), Some(73886668 * 1024));
+    assert_eq!(parse_df_available(""), None);
+}
@@ -42,7 +42,7 @@ impl SubCommand for CopyCommand {
     type Args = CopyArgs;
     
     async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
         
         // Parse destination
         let (dst_device_id, dst_path) = Self::parse_device_path(&args.dst);
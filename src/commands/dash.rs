@@ -0,0 +1,600 @@
+//! Interactive dashboard
+//!
+//! Shows the connected devices, a live logcat tail of the selected one and,
+//! with `--package`, CPU and memory sparklines for that app. Quick actions
+//! work on the selected device: screenshot, reboot and clearing the app's data.
+//! The device list follows `track-devices`, like `ls --watch`.
+
+use crate::adb::connection::AdbConnection;
+use crate::adb::server::AdbServer;
+use crate::adb::shell::{helpers, ShellBatch, ShellCommand};
+use crate::commands::SubCommand;
+use crate::core::artifact::{self, NameVars};
+use crate::core::context::CommandContext;
+use crate::core::types::{Device, DeviceState};
+use crate::device::DeviceManager;
+use crate::error::{AimError, Result};
+use crate::library::protocol::ProgressDisplay;
+use crate::utils::{parse_duration, shell_quote};
+use async_trait::async_trait;
+use log::debug;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Sparkline};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+
+/// Log lines kept for the tail view
+const LOG_LINES: usize = 1000;
+/// Samples kept per sparkline
+const HISTORY: usize = 120;
+/// Log lines shown from before the dashboard started
+const LOG_BACKLOG: u32 = 200;
+
+pub struct DashCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct DashArgs {
+    /// Device to select first (can be partial)
+    pub device_id: Option<String>,
+
+    /// Package whose CPU and memory use is charted, and whose data `c` clears
+    #[clap(short = 'P', long)]
+    pub package: Option<String>,
+
+    /// How often CPU and memory are sampled
+    #[clap(long, value_parser = parse_duration, default_value = "2s")]
+    pub interval: Duration,
+}
+
+/// The most recent samples of one value, oldest first
+#[derive(Debug, Clone)]
+pub struct History {
+    values: VecDeque<u64>,
+    capacity: usize,
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            values: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, value: u64) {
+        if self.values.len() == self.capacity {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    pub fn values(&self) -> Vec<u64> {
+        self.values.iter().copied().collect()
+    }
+
+    pub fn last(&self) -> Option<u64> {
+        self.values.back().copied()
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+/// Lines of a stream that arrives in arbitrary chunks
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    lines: VecDeque<String>,
+    partial: String,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::with_capacity(capacity),
+            partial: String::new(),
+            capacity,
+        }
+    }
+
+    /// Add a chunk; a trailing incomplete line waits for the next chunk
+    pub fn push_chunk(&mut self, chunk: &str) {
+        self.partial.push_str(chunk);
+        while let Some(end) = self.partial.find('\n') {
+            let line: String = self.partial.drain(..=end).collect();
+            if self.lines.len() == self.capacity {
+                self.lines.pop_front();
+            }
+            self.lines.push_back(line.trim_end_matches(['\r', '\n']).to_string());
+        }
+    }
+
+    /// The last `count` complete lines
+    pub fn tail(&self, count: usize) -> impl Iterator<Item = &str> {
+        self.lines.iter().skip(self.lines.len().saturating_sub(count)).map(String::as_str)
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.partial.clear();
+    }
+}
+
+/// CPU use from `top -b -n 1 -q -o %CPU -p <pid>`, which prints just the value
+pub fn parse_cpu_percent(output: &str) -> Option<f64> {
+    output.lines().find_map(|line| line.trim().parse().ok())
+}
+
+/// Total PSS in kB from `dumpsys meminfo <package>`
+///
+/// Newer releases print "TOTAL PSS:  123456", older ones a "TOTAL" table row
+/// whose first column is the PSS.
+pub fn parse_total_pss_kb(output: &str) -> Option<u64> {
+    let first_number = |rest: &str| rest.split_whitespace().next()?.parse().ok();
+    output.lines().map(str::trim).find_map(|line| {
+        if let Some(rest) = line.strip_prefix("TOTAL PSS:") {
+            first_number(rest)
+        } else {
+            line.strip_prefix("TOTAL ").and_then(first_number)
+        }
+    })
+}
+
+/// An action waiting for y/n
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pending {
+    Reboot,
+    ClearData,
+}
+
+enum DashEvent {
+    Key(KeyEvent),
+    /// Log output, tagged with the tail it belongs to
+    Log(u64, String),
+    DevicesChanged,
+}
+
+struct Dash {
+    devices: Vec<Device>,
+    selected: usize,
+    info: Vec<(&'static str, String)>,
+    log: LogBuffer,
+    log_generation: u64,
+    log_task: Option<JoinHandle<()>>,
+    cpu: History,
+    memory: History,
+    package: Option<String>,
+    status: String,
+    pending: Option<Pending>,
+    events: UnboundedSender<DashEvent>,
+    /// ADB server the dashboard talks to
+    host: String,
+    port: u16,
+}
+
+impl Dash {
+    fn new(host: &str, port: u16, package: Option<String>, events: UnboundedSender<DashEvent>) -> Self {
+        Self {
+            devices: Vec::new(),
+            selected: 0,
+            info: Vec::new(),
+            log: LogBuffer::new(LOG_LINES),
+            log_generation: 0,
+            log_task: None,
+            cpu: History::new(HISTORY),
+            memory: History::new(HISTORY),
+            package,
+            status: String::new(),
+            pending: None,
+            events,
+            host: host.to_string(),
+            port,
+        }
+    }
+
+    fn current(&self) -> Option<&Device> {
+        self.devices.get(self.selected).filter(|d| d.state == DeviceState::Device)
+    }
+
+    /// Reload the device list, keeping the selection on the same serial
+    async fn refresh_devices(&mut self, manager: &DeviceManager) {
+        let previous = self.devices.get(self.selected).map(|d| d.id.clone());
+        let was_online = self.current().map(|d| d.id.clone());
+        match manager.list_devices().await {
+            Ok(devices) => self.devices = devices,
+            Err(e) => {
+                self.status = format!("Listing devices failed: {}", e);
+                return;
+            }
+        }
+        let selected = previous
+            .as_ref()
+            .and_then(|id| self.devices.iter().position(|d| &d.id == id))
+            .unwrap_or(0);
+        self.selected = selected;
+        // Also restarts the tail when the selected device comes online or drops off
+        if self.current().map(|d| &d.id) != was_online.as_ref() {
+            self.select_changed().await;
+        }
+    }
+
+    /// Restart the log tail and reload details for a newly selected device
+    async fn select_changed(&mut self) {
+        if let Some(task) = self.log_task.take() {
+            task.abort();
+        }
+        self.log.clear();
+        self.info.clear();
+        self.cpu.clear();
+        self.memory.clear();
+        self.log_generation += 1;
+
+        let Some(device) = self.current().cloned() else {
+            return;
+        };
+        let (host, port) = (self.host.clone(), self.port);
+
+        match helpers::getprops(&host, port, Some(&device.id)).await {
+            Ok(props) => {
+                let get = |key: &str| props.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+                self.info = [
+                    ("Model", get("ro.product.model")),
+                    ("Manufacturer", get("ro.product.manufacturer")),
+                    ("Android", get("ro.build.version.release")),
+                    ("SDK", get("ro.build.version.sdk")),
+                ]
+                .into_iter()
+                .filter_map(|(label, value)| Some((label, value?)))
+                .collect();
+            }
+            Err(e) => debug!("Reading properties of {} failed: {}", device.id, e),
+        }
+
+        let generation = self.log_generation;
+        let events = self.events.clone();
+        let id = device.id.clone();
+        self.log_task = Some(tokio::spawn(async move {
+            let command = ShellCommand::new(format!("logcat -v brief -T {}", LOG_BACKLOG)).with_device(id);
+            let sender = events.clone();
+            let result = command
+                .execute_streaming(&host, port, move |chunk| {
+                    let _ = sender.send(DashEvent::Log(generation, chunk.to_string()));
+                })
+                .await;
+            if let Err(e) = result {
+                let _ = events.send(DashEvent::Log(generation, format!("logcat ended: {}\n", e)));
+            }
+        }));
+    }
+
+    /// Sample CPU and memory of the package on the selected device
+    async fn sample(&mut self) {
+        let (Some(package), Some(device)) = (self.package.clone(), self.current().cloned()) else {
+            return;
+        };
+        let (host, port) = (self.host.clone(), self.port);
+        let package = shell_quote(&package);
+        let outputs = ShellBatch::new()
+            .with_device(device.id.clone())
+            .command(format!("top -b -n 1 -q -o %CPU -p $(pidof {})", package))
+            .command(format!("dumpsys meminfo {}", package))
+            .execute(&host, port)
+            .await;
+        match outputs {
+            Ok(outputs) => {
+                let cpu = outputs.first().and_then(|o| parse_cpu_percent(&o.stdout));
+                let pss = outputs.get(1).and_then(|o| parse_total_pss_kb(&o.stdout));
+                self.cpu.push(cpu.map_or(0, |c| c.round() as u64));
+                self.memory.push(pss.map_or(0, |kb| kb / 1024));
+            }
+            Err(e) => debug!("Sampling {} failed: {}", package, e),
+        }
+    }
+
+    async fn screenshot(&mut self) -> Result<()> {
+        let Some(device) = self.current().cloned() else {
+            return Err(AimError::Screenshot("No device selected".to_string()));
+        };
+        let (host, port) = (self.host.clone(), self.port);
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let remote = format!("/sdcard/aim-dash-{}.png", timestamp);
+        let config = crate::config::Config::load().screenshot.and_then(|s| s.name_template);
+        let template = artifact::template(None, config.as_deref());
+        let local = PathBuf::from(artifact::file_name(&template, &NameVars::new("screenshot", &device), "png")?);
+
+        ShellCommand::new(format!("screencap -p {}", remote))
+            .with_device(device.id.clone())
+            .execute(&host, port)
+            .await?;
+        // Progress bars would draw over the dashboard
+        crate::library::adb::pull(
+            &host,
+            &port.to_string(),
+            Some(device.id.as_str()),
+            &PathBuf::from(&remote),
+            &local,
+            ProgressDisplay::Hide,
+        )
+        .await
+        .map_err(|e| AimError::Screenshot(e.to_string()))?;
+        ShellCommand::new(format!("rm -f {}", remote))
+            .with_device(device.id.clone())
+            .execute(&host, port)
+            .await?;
+
+        self.status = format!("Screenshot saved to {}", local.display());
+        Ok(())
+    }
+
+    async fn reboot(&mut self) -> Result<()> {
+        let Some(device) = self.current().cloned() else {
+            return Err(AimError::CommandExecution("No device selected".to_string()));
+        };
+        let (host, port) = (self.host.clone(), self.port);
+        let mut conn = AdbConnection::connect(&host, port).await?;
+        conn.select_device(&device.id).await?;
+        conn.send_command("reboot:")?;
+        conn.read_okay()?;
+        self.status = format!("Rebooting {}", device.id);
+        Ok(())
+    }
+
+    async fn clear_data(&mut self) -> Result<()> {
+        let (Some(package), Some(device)) = (self.package.clone(), self.current().cloned()) else {
+            return Err(AimError::CommandExecution("No device selected".to_string()));
+        };
+        let (host, port) = (self.host.clone(), self.port);
+        let output = ShellCommand::new(format!("pm clear {}", shell_quote(&package)))
+            .with_device(device.id.clone())
+            .execute(&host, port)
+            .await?;
+        if !output.stdout.contains("Success") {
+            return Err(AimError::CommandExecution(format!(
+                "Clearing data of {} failed: {}",
+                package,
+                output.output().trim()
+            )));
+        }
+        self.status = format!("Cleared data of {}", package);
+        Ok(())
+    }
+
+    /// Handle a key; returns false to quit
+    async fn key(&mut self, key: KeyEvent) -> bool {
+        if key.kind != KeyEventKind::Press {
+            return true;
+        }
+        if let Some(pending) = self.pending.take() {
+            let result = match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => match pending {
+                    Pending::Reboot => self.reboot().await,
+                    Pending::ClearData => self.clear_data().await,
+                },
+                _ => {
+                    self.status = "Cancelled".to_string();
+                    Ok(())
+                }
+            };
+            if let Err(e) = result {
+                self.status = e.to_string();
+            }
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Up | KeyCode::Char('k') if self.selected > 0 => {
+                self.selected -= 1;
+                self.select_changed().await;
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.selected + 1 < self.devices.len() => {
+                self.selected += 1;
+                self.select_changed().await;
+            }
+            KeyCode::Char('s') => {
+                self.status = "Taking screenshot...".to_string();
+                if let Err(e) = self.screenshot().await {
+                    self.status = e.to_string();
+                }
+            }
+            KeyCode::Char('r') if self.current().is_some() => self.pending = Some(Pending::Reboot),
+            KeyCode::Char('c') if self.package.is_none() => {
+                self.status = "Start with --package to clear app data".to_string();
+            }
+            KeyCode::Char('c') if self.current().is_some() => self.pending = Some(Pending::ClearData),
+            _ => {}
+        }
+        true
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let [top, log_area, footer] =
+            Layout::vertical([Constraint::Length(12), Constraint::Min(5), Constraint::Length(1)]).areas(frame.area());
+        let [list_area, side] = Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(top);
+
+        let items: Vec<ListItem> = self
+            .devices
+            .iter()
+            .map(|d| {
+                let name = d.alias.clone().unwrap_or_else(|| d.id.to_string());
+                let color = if d.state == DeviceState::Device { Color::Green } else { Color::Yellow };
+                ListItem::new(Line::from(vec![
+                    name.into(),
+                    " ".into(),
+                    d.model.clone().unwrap_or_default().dark_gray(),
+                    " ".into(),
+                    d.state.to_string().fg(color),
+                ]))
+            })
+            .collect();
+        let mut state = ListState::default().with_selected((!self.devices.is_empty()).then_some(self.selected));
+        let list = List::new(items)
+            .block(Block::bordered().title(" Devices "))
+            .highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(list, list_area, &mut state);
+
+        let info: Vec<Line> = self
+            .info
+            .iter()
+            .map(|(label, value)| Line::from(vec![format!("{}: ", label).dark_gray(), value.clone().into()]))
+            .collect();
+        match &self.package {
+            Some(package) => {
+                let [info_area, cpu_area, memory_area] = Layout::vertical([
+                    Constraint::Length(self.info.len() as u16 + 2),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                ])
+                .areas(side);
+                frame.render_widget(Paragraph::new(info).block(Block::bordered().title(" Device ")), info_area);
+
+                let cpu = self.cpu.values();
+                let title = format!(" {} CPU {}% ", package, self.cpu.last().unwrap_or(0));
+                let sparkline = Sparkline::default()
+                    .block(Block::bordered().title(title))
+                    .data(&cpu)
+                    .style(Style::new().fg(Color::Green));
+                frame.render_widget(sparkline, cpu_area);
+
+                let memory = self.memory.values();
+                let title = format!(" {} PSS {} MB ", package, self.memory.last().unwrap_or(0));
+                let sparkline = Sparkline::default()
+                    .block(Block::bordered().title(title))
+                    .data(&memory)
+                    .style(Style::new().fg(Color::Cyan));
+                frame.render_widget(sparkline, memory_area);
+            }
+            None => {
+                frame.render_widget(Paragraph::new(info).block(Block::bordered().title(" Device ")), side);
+            }
+        }
+
+        let title = match self.current() {
+            Some(device) => format!(" Log {} ", device.id),
+            None => " Log ".to_string(),
+        };
+        let height = log_area.height.saturating_sub(2) as usize;
+        let lines: Vec<Line> = self.log.tail(height).map(|l| Line::from(l.to_string())).collect();
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(title)), log_area);
+
+        let footer_text = match (self.pending, self.current()) {
+            (Some(Pending::Reboot), Some(device)) => format!("Reboot {}? (y/n)", device.id).yellow(),
+            (Some(Pending::ClearData), Some(device)) => format!(
+                "Clear all data of {} on {}? (y/n)",
+                self.package.as_deref().unwrap_or_default(),
+                device.id
+            )
+            .yellow(),
+            _ if !self.status.is_empty() => self.status.clone().into(),
+            _ => "↑/↓ select  s screenshot  r reboot  c clear app data  q quit".dark_gray(),
+        };
+        frame.render_widget(Paragraph::new(Line::from(footer_text)), footer);
+    }
+}
+
+impl DashCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn event_loop(&self, ctx: &CommandContext, terminal: &mut DefaultTerminal, args: DashArgs) -> Result<()> {
+        let (host, port) = ctx.adb_address();
+        let (tx, mut events) = tokio::sync::mpsc::unbounded_channel();
+
+        let keys = tx.clone();
+        std::thread::spawn(move || {
+            while !keys.is_closed() {
+                match event::poll(Duration::from_millis(100)) {
+                    Ok(true) => {
+                        if let Ok(Event::Key(key)) = event::read() {
+                            let _ = keys.send(DashEvent::Key(key));
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(_) => break,
+                }
+            }
+        });
+
+        match AdbServer::track_devices(host, port).await {
+            Ok(mut conn) => {
+                conn.stream().set_read_timeout(None)?;
+                let changes = tx.clone();
+                std::thread::spawn(move || {
+                    while AdbServer::read_string(&mut conn).is_ok() {
+                        if changes.send(DashEvent::DevicesChanged).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+            Err(e) => debug!("Device tracking unavailable: {}", e),
+        }
+
+        let manager = DeviceManager::with_address(host, port.to_string());
+        let mut dash = Dash::new(host, port, args.package, tx);
+        dash.refresh_devices(&manager).await;
+        let wanted = args.device_id.or_else(crate::device::manager::device_from_env);
+        if let Some(wanted) = wanted {
+            match manager.find_device(&wanted).await {
+                Ok(device) => {
+                    let index = dash.devices.iter().position(|d| d.id == device.id);
+                    if let Some(index) = index.filter(|&i| i != dash.selected) {
+                        dash.selected = index;
+                        dash.select_changed().await;
+                    }
+                }
+                Err(e) => dash.status = e.to_string(),
+            }
+        }
+
+        let mut ticker = tokio::time::interval(args.interval);
+        loop {
+            terminal.draw(|frame| dash.render(frame))?;
+            tokio::select! {
+                event = events.recv() => match event {
+                    Some(DashEvent::Key(key)) => {
+                        if !dash.key(key).await {
+                            break;
+                        }
+                    }
+                    Some(DashEvent::Log(generation, chunk)) => {
+                        if generation == dash.log_generation {
+                            dash.log.push_chunk(&chunk);
+                        }
+                    }
+                    Some(DashEvent::DevicesChanged) => dash.refresh_devices(&manager).await,
+                    None => break,
+                },
+                _ = ticker.tick() => dash.sample().await,
+            }
+        }
+
+        if let Some(task) = dash.log_task.take() {
+            task.abort();
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubCommand for DashCommand {
+    type Args = DashArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        crate::core::ci::require_interactive("aim dash")?;
+        let mut terminal = ratatui::try_init()?;
+        let result = self.event_loop(ctx, &mut terminal, args).await;
+        ratatui::restore();
+        result
+    }
+}
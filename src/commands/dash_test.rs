@@ -0,0 +1,42 @@
+use super::dash::{parse_cpu_percent, parse_total_pss_kb, History, LogBuffer};
+
+#[test]
+fn test_history_keeps_latest() {
+    let mut history = History::new(3);
+    for value in 1..=5 {
+        history.push(value);
+    }
+    assert_eq!(history.values(), vec![3, 4, 5]);
+    assert_eq!(history.last(), Some(5));
+}
+
+#[test]
+fn test_log_buffer_joins_chunks() {
+    let mut log = LogBuffer::new(2);
+    log.push_chunk("I/first( 12): one\r\nI/sec");
+    assert_eq!(log.tail(10).collect::<Vec<_>>(), vec!["I/first( 12): one"]);
+
+    log.push_chunk("ond( 12): two\nI/third( 12): three\n");
+    assert_eq!(
+        log.tail(10).collect::<Vec<_>>(),
+        vec!["I/second( 12): two", "I/third( 12): three"]
+    );
+    assert_eq!(log.tail(1).collect::<Vec<_>>(), vec!["I/third( 12): three"]);
+}
+
+#[test]
+fn test_parse_cpu_percent() {
+    assert_eq!(parse_cpu_percent(" 12.5\n"), Some(12.5));
+    assert_eq!(parse_cpu_percent("top: bad -p\n"), None);
+}
+
+#[test]
+fn test_parse_total_pss() {
+    let new = "Applications Memory Usage (in Kilobytes):\n  TOTAL PSS:    85123            TOTAL RSS:   150000\n";
+    assert_eq!(parse_total_pss_kb(new), Some(85123));
+
+    let old = "                 Pss  Private\n        TOTAL    64000    50000    12000\n";
+    assert_eq!(parse_total_pss_kb(old), Some(64000));
+
+    assert_eq!(parse_total_pss_kb("No process found for: com.example\n"), None);
+}
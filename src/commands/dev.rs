@@ -0,0 +1,157 @@
+use crate::adb::reverse::Reverse;
+use crate::commands::{resolve_device_alias, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::{Device, DeviceId, DeviceState};
+use crate::device::DeviceManager;
+use crate::error::{AimError, Result};
+use crate::utils::parse_duration;
+use async_trait::async_trait;
+use colored::*;
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+pub struct DevCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct DevArgs {
+    /// Port to reverse from the device to the same port on this machine (repeatable)
+    #[clap(long = "reverse-port", required = true)]
+    pub ports: Vec<u16>,
+
+    /// Device ID (repeatable); every connected device if omitted
+    #[clap(short = 'd', long = "device")]
+    pub devices: Vec<String>,
+
+    /// How often to check that the reverses are still in place
+    #[clap(long, value_parser = parse_duration, default_value = "2s")]
+    pub interval: Duration,
+}
+
+/// Online devices the reverses should be on
+///
+/// With no filters every online device is used; otherwise a device is used if
+/// its serial contains one of them, the same partial matching as `-d` elsewhere.
+pub fn target_devices(devices: &[Device], filters: &[String]) -> BTreeSet<String> {
+    devices
+        .iter()
+        .filter(|d| d.state == DeviceState::Device)
+        .map(|d| d.id.to_string())
+        .filter(|id| {
+            filters.is_empty()
+                || filters
+                    .iter()
+                    .any(|f| id.to_lowercase().contains(&f.to_lowercase()))
+        })
+        .collect()
+}
+
+/// Ports whose `tcp:<port>` reverse is not active on the device
+pub fn missing_ports(ports: &[u16], active: &[(String, String)]) -> Vec<u16> {
+    ports
+        .iter()
+        .copied()
+        .filter(|port| {
+            let spec = format!("tcp:{}", port);
+            !active.iter().any(|(remote, local)| *remote == spec && *local == spec)
+        })
+        .collect()
+}
+
+impl DevCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Add whichever reverses are missing on one device
+    async fn ensure(host: &str, port: u16, serial: &str, ports: &[u16]) -> Result<Vec<u16>> {
+        let id = DeviceId::new(serial);
+        let active = Reverse::list(host, port, &id).await?;
+        let missing = missing_ports(ports, &active);
+        for p in &missing {
+            let spec = format!("tcp:{}", p);
+            Reverse::add(host, port, &id, &spec, &spec).await?;
+        }
+        Ok(missing)
+    }
+
+    async fn teardown(host: &str, port: u16, serials: &BTreeSet<String>, ports: &[u16]) {
+        for serial in serials {
+            let id = DeviceId::new(serial);
+            for p in ports {
+                if let Err(e) = Reverse::remove(host, port, &id, &format!("tcp:{}", p)).await {
+                    log::debug!("Failed to remove reverse tcp:{} on {}: {}", p, serial, e);
+                }
+            }
+            println!("{} removed reverses on {}", "✓".green(), serial);
+        }
+    }
+}
+
+#[async_trait]
+impl SubCommand for DevCommand {
+    type Args = DevArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let mut filters: Vec<String> = args
+            .devices
+            .iter()
+            .filter_map(|d| resolve_device_alias(Some(d)))
+            .collect();
+        if filters.is_empty() {
+            filters.extend(crate::device::manager::device_from_env());
+        }
+
+        let (host, port) = ctx.adb_address();
+        let manager = DeviceManager::with_address(host, port.to_string());
+        let ports: Vec<String> = args.ports.iter().map(|p| p.to_string()).collect();
+        eprintln!(
+            "Reversing port(s) {} every {}, Ctrl-C to stop",
+            ports.join(", "),
+            crate::utils::format_duration(args.interval)
+        );
+
+        // Devices that currently have the reverses, so reconnects are noticed
+        let mut online: BTreeSet<String> = BTreeSet::new();
+        let mut first = true;
+        loop {
+            let devices = match manager.list_devices().await {
+                Ok(devices) => devices,
+                Err(e) => {
+                    log::debug!("Listing devices failed: {}", e);
+                    Vec::new()
+                }
+            };
+            let targets = target_devices(&devices, &filters);
+            if first && targets.is_empty() {
+                return Err(AimError::NoDevicesFound);
+            }
+            first = false;
+
+            for serial in online.difference(&targets) {
+                println!("{} {} disconnected", "✗".red(), serial);
+            }
+            online.retain(|s| targets.contains(s));
+
+            for serial in &targets {
+                match Self::ensure(host, port, serial, &args.ports).await {
+                    Ok(added) => {
+                        if !added.is_empty() {
+                            let added: Vec<String> = added.iter().map(|p| format!("tcp:{}", p)).collect();
+                            println!("{} {} reversed {}", "✓".green(), serial, added.join(", "));
+                        }
+                        online.insert(serial.clone());
+                    }
+                    Err(e) => eprintln!("{} {}: {}", "✗".red(), serial, e),
+                }
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(args.interval) => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+        }
+
+        Self::teardown(host, port, &online, &args.ports).await;
+        Ok(())
+    }
+}
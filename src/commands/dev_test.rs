@@ -0,0 +1,40 @@
+use super::dev::{missing_ports, target_devices};
+use crate::adb::reverse::parse_reverse_list;
+use crate::core::types::DeviceState;
+use crate::testing::{test_device, test_devices};
+
+#[test]
+fn test_target_devices() {
+    let mut devices = test_devices();
+    devices.push(test_device("R58M123ABC").with_state(DeviceState::Offline));
+
+    let all = target_devices(&devices, &[]);
+    assert_eq!(all.len(), 3);
+    assert!(!all.contains("R58M123ABC"));
+
+    let filtered = target_devices(&devices, &["ABC123".to_string(), "5554".to_string()]);
+    assert_eq!(
+        filtered.into_iter().collect::<Vec<_>>(),
+        vec!["abc123def456".to_string(), "emulator-5554".to_string()]
+    );
+
+    // Offline devices are skipped even when asked for by name
+    assert!(target_devices(&devices, &["R58M".to_string()]).is_empty());
+}
+
+#[test]
+fn test_missing_ports() {
+    let active = parse_reverse_list("UsbFfs tcp:8081 tcp:8081\nUsbFfs tcp:3000 tcp:4000\n");
+    assert_eq!(
+        active,
+        vec![
+            ("tcp:8081".to_string(), "tcp:8081".to_string()),
+            ("tcp:3000".to_string(), "tcp:4000".to_string()),
+        ]
+    );
+
+    // tcp:3000 points at the wrong host port, so it is set up again
+    assert_eq!(missing_ports(&[3000, 8081, 9090], &active), vec![3000, 9090]);
+    assert!(missing_ports(&[8081], &active).is_empty());
+    assert_eq!(missing_ports(&[8081], &parse_reverse_list("")), vec![8081]);
+}
@@ -0,0 +1,81 @@
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::device::discovery::DiscoveredDevice;
+use crate::device::DeviceManager;
+use crate::error::Result;
+use crate::output::{OutputFormatter, TableFormat};
+use async_trait::async_trait;
+use colored::*;
+
+pub struct DiscoverCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct DiscoverArgs {
+    /// Connect to a discovered device by alias, serial, mDNS name or address
+    #[clap(long, value_name = "NAME")]
+    pub connect: Option<String>,
+}
+
+impl TableFormat for DiscoveredDevice {
+    fn headers() -> Vec<&'static str> {
+        vec!["DEVICE", "SERIAL", "ADDRESS", "SERVICE", "CONNECTED"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let service = if self.service_type.starts_with("_adb-tls") { "tls" } else { "legacy" };
+        vec![
+            self.label().to_string(),
+            self.serial.clone().unwrap_or_else(|| "-".to_string()),
+            self.address.clone(),
+            service.to_string(),
+            if self.connected { "yes" } else { "no" }.to_string(),
+        ]
+    }
+}
+
+impl std::fmt::Display for DiscoveredDevice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\t{}", self.label(), self.address)
+    }
+}
+
+impl DiscoverCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for DiscoverCommand {
+    type Args = DiscoverArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let (host, port) = ctx.adb_address();
+        let manager = DeviceManager::with_address(host, port.to_string());
+
+        if let Some(query) = &args.connect {
+            let device = manager.connect_discovered(query).await?;
+            if !ctx.quiet {
+                println!("{} {} connected via {}", "✓".green(), device.label().bright_cyan(), device.address);
+            }
+            return Ok(());
+        }
+
+        let devices = manager.discover().await?;
+        let formatter = OutputFormatter::new();
+        match ctx.output_format {
+            OutputFormat::Json => formatter.json(&devices)?,
+            OutputFormat::Plain => formatter.plain(&devices)?,
+            OutputFormat::Table if devices.is_empty() => {
+                println!("No devices advertising wireless debugging");
+                println!(
+                    "{}",
+                    "Turn on Developer options > Wireless debugging, and pair first with aim pair".dimmed()
+                );
+            }
+            OutputFormat::Table => formatter.table(&devices)?,
+        }
+        Ok(())
+    }
+}
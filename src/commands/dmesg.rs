@@ -3,6 +3,7 @@ use crate::core::context::CommandContext;
 use crate::error::Result;
 use crate::library::adb::run_shell_command_async;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 pub struct DmesgCommand;
 
@@ -11,6 +12,10 @@ pub struct DmesgArgs {
     /// Device ID (required if multiple devices are connected)
     pub device_id: Option<String>,
     
+    /// Show wall-clock times in logcat's format instead of seconds since boot
+    #[clap(long)]
+    pub human_time: bool,
+
     /// Additional arguments to pass to dmesg
     #[clap(trailing_var_arg = true)]
     pub args: Vec<String>,
@@ -22,13 +27,30 @@ impl DmesgCommand {
     }
 }
 
+/// Replace a "[  123.456789]" uptime prefix with a formatted wall-clock time
+pub fn convert_uptime_prefix(line: &str, boot: DateTime<Utc>, format: impl Fn(DateTime<Utc>) -> String) -> String {
+    let Some(rest) = line.strip_prefix('[') else {
+        return line.to_string();
+    };
+    let Some((uptime, tail)) = rest.split_once(']') else {
+        return line.to_string();
+    };
+    match uptime.trim().parse::<f64>() {
+        Ok(secs) => {
+            let at = boot + chrono::Duration::microseconds((secs * 1_000_000.0) as i64);
+            format!("[{}]{}", format(at), tail)
+        }
+        Err(_) => line.to_string(),
+    }
+}
+
 #[async_trait]
 impl SubCommand for DmesgCommand {
     type Args = DmesgArgs;
     
     async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
         let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
         
         // Build dmesg command with any additional arguments
         let mut command = "dmesg".to_string();
@@ -41,8 +63,32 @@ impl SubCommand for DmesgCommand {
         let port_str = port.to_string();
         let output = run_shell_command_async(host, &port_str, &command, Some(&device_id)).await?;
         
-        // Print output directly (dmesg output is typically line-based)
-        print!("{}", output);
+        // Uptime prefixes are only rewritten when a time format was asked for
+        let settings = crate::output::time::settings();
+        let boot = if args.human_time || settings.explicit {
+            let stat = run_shell_command_async(host, &port_str, "cat /proc/stat", Some(&device_id)).await?;
+            crate::output::time::parse_boot_time(&stat)
+        } else {
+            None
+        };
+
+        match boot {
+            Some(boot) => {
+                let offset = crate::output::time::device_utc_offset(&device.id).await;
+                for line in output.lines() {
+                    let line = if args.human_time {
+                        convert_uptime_prefix(line, boot, |at| {
+                            crate::output::time::format_log_time(at, settings.zone, offset)
+                        })
+                    } else {
+                        convert_uptime_prefix(line, boot, |at| crate::output::time::format_time(at, offset))
+                    };
+                    println!("{}", line);
+                }
+            }
+            // Print output directly (dmesg output is typically line-based)
+            None => print!("{}", output),
+        }
         
         Ok(())
     }
@@ -0,0 +1,239 @@
+use crate::adb::shell::{helpers::shell, ShellBatch};
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::error::{AimError, Result};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use clap::Subcommand;
+use colored::*;
+
+/// Appended to errors that can only be fixed by wiping the device
+const FACTORY_RESET_HINT: &str = "A device owner can only be set on a device with no accounts and a single user, \
+     normally right after a factory reset. Remove the accounts in Settings > Accounts, or factory reset \
+     and run this before signing in.";
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum DpmCommands {
+    /// Make an app the device owner, as an MDM would be
+    SetOwner(SetOwnerArgs),
+    /// Remove the device owner (test-only owners, or factory reset)
+    RemoveOwner(RemoveOwnerArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: DpmCommands) -> Result<()> {
+    match cmd {
+        DpmCommands::SetOwner(args) => {
+            let cmd = SetOwnerCommand::new();
+            cmd.run(ctx, args).await
+        }
+        DpmCommands::RemoveOwner(args) => {
+            let cmd = RemoveOwnerCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct SetOwnerArgs {
+    /// Admin receiver as <package>/<receiver>, e.g. com.example.dpc/.AdminReceiver
+    pub component: String,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct RemoveOwnerArgs {
+    /// Admin receiver to remove (defaults to the current device owner)
+    pub component: Option<String>,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// Check `<package>/<receiver>` and expand a relative receiver name
+pub fn parse_component(input: &str) -> Result<String> {
+    let invalid = || {
+        AimError::InvalidArgument(format!(
+            "Invalid component '{}', expected <package>/<receiver> such as com.example.dpc/.AdminReceiver",
+            input
+        ))
+    };
+    let (package, receiver) = input.trim().split_once('/').ok_or_else(invalid)?;
+    let is_name = |s: &str| {
+        !s.is_empty()
+            && s.split('.').all(|part| {
+                part.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                    && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+            })
+    };
+    let receiver = match receiver.strip_prefix('.') {
+        Some(relative) => format!("{}.{}", package, relative),
+        None => receiver.to_string(),
+    };
+    if !is_name(package) || !is_name(&receiver) {
+        return Err(invalid());
+    }
+    Ok(format!("{}/{}", package, receiver))
+}
+
+/// Number of accounts from `dumpsys account`
+pub fn parse_account_count(output: &str) -> Option<usize> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Accounts:")?.trim().parse().ok())
+}
+
+/// Number of users from `pm list users`
+pub fn parse_user_count(output: &str) -> usize {
+    output.lines().filter(|line| line.trim().starts_with("UserInfo{")).count()
+}
+
+/// The device owner component from `dumpsys device_policy`
+pub fn parse_device_owner(output: &str) -> Option<String> {
+    output
+        .lines()
+        .skip_while(|line| !line.trim_start().starts_with("Device Owner"))
+        .find_map(|line| {
+            let (_, rest) = line.split_once("admin=ComponentInfo{")?;
+            Some(rest.split('}').next()?.to_string())
+        })
+}
+
+/// A readable explanation for a failed `dpm` call, if it is a known one
+pub fn explain_dpm_error(output: &str) -> Option<String> {
+    let reason = if output.contains("already some accounts") {
+        format!("The device has accounts on it. {}", FACTORY_RESET_HINT)
+    } else if output.contains("already several users") || output.contains("more than one user") {
+        format!("The device has more than one user. {}", FACTORY_RESET_HINT)
+    } else if output.contains("device owner is already set") || output.contains("already provisioned") {
+        format!(
+            "The device already has an owner or has been set up. {}",
+            FACTORY_RESET_HINT
+        )
+    } else if output.contains("Unknown admin") {
+        "The receiver is not a device admin: check the component name and that it is declared with \
+         android.permission.BIND_DEVICE_ADMIN and device-admin metadata"
+            .to_string()
+    } else if output.contains("non-test admin") {
+        "Only admins with android:testOnly=\"true\" can be removed from the shell. Otherwise the app \
+         has to call clearDeviceOwnerApp(), or the device has to be factory reset."
+            .to_string()
+    } else {
+        return None;
+    };
+    Some(reason)
+}
+
+fn dpm_error(output: &str) -> AimError {
+    let detail = output.trim();
+    match explain_dpm_error(output) {
+        Some(reason) => AimError::CommandExecution(format!("{}\n\ndpm said: {}", reason, detail)),
+        None => AimError::CommandExecution(format!("dpm failed: {}", detail)),
+    }
+}
+
+pub struct SetOwnerCommand;
+
+impl SetOwnerCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for SetOwnerCommand {
+    type Args = SetOwnerArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let component = parse_component(&args.component)?;
+        let package = component.split('/').next().unwrap_or_default().to_string();
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        // Check the preconditions first so the usual failures get a clear message
+        let outputs = ShellBatch::new()
+            .with_device(device.id.clone())
+            .command(format!("pm list packages {}", shell_quote(&package)))
+            .command("dumpsys account | grep -E '^Accounts:'")
+            .command("pm list users")
+            .execute(host, port)
+            .await?;
+        let output = |i: usize| outputs.get(i).map(|o| o.stdout.as_str()).unwrap_or_default();
+
+        let installed = output(0).lines().any(|l| l.trim().strip_prefix("package:") == Some(package.as_str()));
+        if !installed {
+            return Err(AimError::CommandExecution(format!(
+                "{} is not installed on {}; install the admin app first",
+                package, device.id
+            )));
+        }
+        if let Some(accounts) = parse_account_count(output(1)).filter(|&n| n > 0) {
+            return Err(AimError::CommandExecution(format!(
+                "{} has {} account(s). {}",
+                device.id, accounts, FACTORY_RESET_HINT
+            )));
+        }
+        let users = parse_user_count(output(2));
+        if users > 1 {
+            return Err(AimError::CommandExecution(format!(
+                "{} has {} users. {}",
+                device.id, users, FACTORY_RESET_HINT
+            )));
+        }
+
+        let out = shell(host, port, &device.id, &format!("dpm set-device-owner {} 2>&1", shell_quote(&component))).await?;
+        if !out.contains("Success") {
+            return Err(dpm_error(&out));
+        }
+        if !ctx.quiet {
+            println!("{} {} is now the device owner of {}", "✓".green(), component.bright_cyan(), device.id);
+            println!("Removing it later needs a factory reset unless the app is test-only");
+        }
+        Ok(())
+    }
+}
+
+pub struct RemoveOwnerCommand;
+
+impl RemoveOwnerCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for RemoveOwnerCommand {
+    type Args = RemoveOwnerArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        let component = match &args.component {
+            Some(component) => parse_component(component)?,
+            None => {
+                let policy = shell(host, port, &device.id, "dumpsys device_policy").await?;
+                parse_device_owner(&policy)
+                    .ok_or_else(|| AimError::CommandExecution(format!("{} has no device owner", device.id)))?
+            }
+        };
+
+        let out = shell(
+            host,
+            port,
+            &device.id,
+            &format!("dpm remove-active-admin {} 2>&1", shell_quote(&component)),
+        )
+        .await?;
+        if !out.contains("Success") {
+            return Err(dpm_error(&out));
+        }
+        if !ctx.quiet {
+            println!("{} Removed {} from {}", "✓".green(), component.bright_cyan(), device.id);
+        }
+        Ok(())
+    }
+}
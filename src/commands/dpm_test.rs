@@ -0,0 +1,50 @@
+use super::dpm::{explain_dpm_error, parse_account_count, parse_component, parse_device_owner, parse_user_count};
+
+#[test]
+fn test_parse_component() {
+    assert_eq!(
+        parse_component("com.example.dpc/.AdminReceiver").unwrap(),
+        "com.example.dpc/com.example.dpc.AdminReceiver"
+    );
+    assert_eq!(
+        parse_component("com.example.dpc/com.other.Receiver$Inner").unwrap(),
+        "com.example.dpc/com.other.Receiver$Inner"
+    );
+    assert!(parse_component("com.example.dpc").is_err());
+    assert!(parse_component("com.example.dpc/").is_err());
+    assert!(parse_component("com..dpc/.Receiver").is_err());
+    assert!(parse_component("1com.dpc/.Receiver").is_err());
+}
+
+#[test]
+fn test_parse_preconditions() {
+    assert_eq!(parse_account_count("Accounts: 2\n"), Some(2));
+    assert_eq!(parse_account_count("Accounts: 0"), Some(0));
+    assert_eq!(parse_account_count(""), None);
+
+    let users = "Users:\n\tUserInfo{0:Owner:c13} running\n\tUserInfo{10:Work profile:1030} running\n";
+    assert_eq!(parse_user_count(users), 2);
+}
+
+#[test]
+fn test_parse_device_owner() {
+    let policy = "Current Device Policy Manager state:\n\
+  Device Owner: \n\
+    admin=ComponentInfo{com.example.dpc/com.example.dpc.AdminReceiver}\n\
+    name=\n\
+    package=com.example.dpc\n";
+    assert_eq!(
+        parse_device_owner(policy).as_deref(),
+        Some("com.example.dpc/com.example.dpc.AdminReceiver")
+    );
+    assert_eq!(parse_device_owner("Current Device Policy Manager state:\n  Enabled Device Admins (User 0, provisioningState: 0):\n"), None);
+}
+
+#[test]
+fn test_explain_dpm_error() {
+    let accounts = "java.lang.IllegalStateException: Not allowed to set the device owner because there are already some accounts on the device";
+    assert!(explain_dpm_error(accounts).unwrap().contains("factory reset"));
+    let non_test = "java.lang.SecurityException: Attempt to remove non-test admin ComponentInfo{a/b}";
+    assert!(explain_dpm_error(non_test).unwrap().contains("testOnly"));
+    assert_eq!(explain_dpm_error("Error: something new"), None);
+}
@@ -0,0 +1,241 @@
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::{DeviceState, OutputFormat};
+use crate::device::DeviceManager;
+use crate::error::{AimError, Result};
+use crate::library::adb::getprops;
+use crate::output::{OutputFormatter, TableFormat};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use clap::Subcommand;
+use comfy_table::{Cell, Color};
+use serde::Serialize;
+use std::collections::HashMap;
+
+const PROP_PATCH: &str = "ro.build.version.security_patch";
+const PROP_SDK: &str = "ro.build.version.sdk";
+const PROP_RELEASE: &str = "ro.build.version.release";
+const PROP_MODEL: &str = "ro.product.model";
+const PROP_VERIFIED_BOOT: &str = "ro.boot.verifiedbootstate";
+const PROP_CRYPTO: &str = "ro.crypto.state";
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum FleetCommands {
+    /// Check every connected device against a security/build policy
+    Audit(AuditArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: FleetCommands) -> Result<()> {
+    match cmd {
+        FleetCommands::Audit(args) => {
+            let cmd = AuditCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+pub struct AuditCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct AuditArgs {
+    /// Oldest acceptable security patch level (YYYY-MM-DD)
+    #[arg(long, value_parser = parse_patch_date)]
+    pub min_patch: Option<NaiveDate>,
+
+    /// Lowest acceptable SDK level
+    #[arg(long)]
+    pub min_sdk: Option<u32>,
+
+    /// Require verified boot state to be green
+    #[arg(long)]
+    pub require_verified_boot: bool,
+
+    /// Require userdata encryption
+    #[arg(long)]
+    pub require_encryption: bool,
+}
+
+fn parse_patch_date(s: &str) -> std::result::Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| format!("invalid date '{}': {}", s, e))
+}
+
+/// Audit outcome for one device
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct AuditResult {
+    pub device_id: String,
+    pub model: String,
+    pub security_patch: String,
+    pub sdk: String,
+    pub release: String,
+    pub verified_boot: String,
+    pub encryption: String,
+    pub violations: Vec<String>,
+}
+
+impl AuditResult {
+    pub fn from_props(device_id: &str, props: &HashMap<String, String>) -> Self {
+        let get = |key: &str| props.get(key).map(|v| v.trim().to_string()).unwrap_or_default();
+        Self {
+            device_id: device_id.to_string(),
+            model: get(PROP_MODEL),
+            security_patch: get(PROP_PATCH),
+            sdk: get(PROP_SDK),
+            release: get(PROP_RELEASE),
+            verified_boot: get(PROP_VERIFIED_BOOT),
+            encryption: get(PROP_CRYPTO),
+            violations: Vec::new(),
+        }
+    }
+
+    /// Fill in `violations` according to the policy
+    pub fn evaluate(&mut self, args: &AuditArgs) {
+        let mut violations = Vec::new();
+
+        if let Some(min_patch) = args.min_patch {
+            match NaiveDate::parse_from_str(&self.security_patch, "%Y-%m-%d") {
+                Ok(patch) if patch < min_patch => {
+                    violations.push(format!("patch {} < {}", self.security_patch, min_patch))
+                }
+                Ok(_) => {}
+                Err(_) => violations.push("patch level unknown".to_string()),
+            }
+        }
+
+        if let Some(min_sdk) = args.min_sdk {
+            match self.sdk.parse::<u32>() {
+                Ok(sdk) if sdk < min_sdk => violations.push(format!("sdk {} < {}", sdk, min_sdk)),
+                Ok(_) => {}
+                Err(_) => violations.push("sdk unknown".to_string()),
+            }
+        }
+
+        if args.require_verified_boot && self.verified_boot != "green" {
+            violations.push(format!("verified boot {}", or_unknown(&self.verified_boot)));
+        }
+
+        if args.require_encryption && self.encryption != "encrypted" {
+            violations.push(format!("encryption {}", or_unknown(&self.encryption)));
+        }
+
+        self.violations = violations;
+    }
+
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    fn result_text(&self) -> String {
+        if self.passed() {
+            "ok".to_string()
+        } else {
+            self.violations.join("; ")
+        }
+    }
+}
+
+fn or_unknown(s: &str) -> &str {
+    if s.is_empty() {
+        "unknown"
+    } else {
+        s
+    }
+}
+
+impl TableFormat for AuditResult {
+    fn headers() -> Vec<&'static str> {
+        vec!["DEVICE ID", "MODEL", "PATCH", "SDK", "BOOT", "ENCRYPTION", "RESULT"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.device_id.clone(),
+            self.model.clone(),
+            self.security_patch.clone(),
+            self.sdk.clone(),
+            self.verified_boot.clone(),
+            self.encryption.clone(),
+            self.result_text(),
+        ]
+    }
+
+    fn colored_row(&self) -> Vec<Cell> {
+        let color = if self.passed() { Color::Green } else { Color::Red };
+        let mut cells: Vec<Cell> = self.row().into_iter().map(Cell::new).collect();
+        if let Some(last) = cells.pop() {
+            cells.push(last.fg(color));
+        }
+        cells
+    }
+}
+
+impl std::fmt::Display for AuditResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\t{}", self.device_id, self.result_text())
+    }
+}
+
+impl AuditCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for AuditCommand {
+    type Args = AuditArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let (host, port) = ctx.adb_address();
+        let port_str = port.to_string();
+        let devices = DeviceManager::with_address(host, port_str.as_str()).list_devices().await?;
+        if devices.is_empty() {
+            return Err(AimError::NoDevicesFound);
+        }
+
+        let propnames: Vec<String> = [PROP_PATCH, PROP_SDK, PROP_RELEASE, PROP_MODEL, PROP_VERIFIED_BOOT, PROP_CRYPTO]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+
+        let mut results = Vec::new();
+        for device in &devices {
+            let id = device.id.to_string();
+            let result = if device.state == DeviceState::Device {
+                let props = getprops(host, &port_str, &propnames, Some(&id)).await;
+                let mut result = AuditResult::from_props(&id, &props);
+                result.evaluate(&args);
+                result
+            } else {
+                // Offline or unauthorized devices cannot be verified, so they fail
+                AuditResult {
+                    device_id: id,
+                    violations: vec![format!("device {}", device.state)],
+                    ..Default::default()
+                }
+            };
+            results.push(result);
+        }
+
+        if crate::core::ci::is_enabled() {
+            results.sort_by(|a, b| a.device_id.cmp(&b.device_id));
+        }
+
+        let formatter = OutputFormatter::new();
+        match ctx.output_format {
+            OutputFormat::Table => formatter.table(&results)?,
+            OutputFormat::Json => formatter.json(&results)?,
+            OutputFormat::Plain => formatter.plain(&results)?,
+        }
+
+        let failed = results.iter().filter(|r| !r.passed()).count();
+        if failed > 0 {
+            return Err(AimError::CommandExecution(format!(
+                "{} of {} device(s) violate policy",
+                failed,
+                results.len()
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use super::fleet::{AuditArgs, AuditResult};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+fn props(patch: &str, sdk: &str, boot: &str, crypto: &str) -> HashMap<String, String> {
+    [
+        ("ro.build.version.security_patch", patch),
+        ("ro.build.version.sdk", sdk),
+        ("ro.product.model", "Pixel 8"),
+        ("ro.boot.verifiedbootstate", boot),
+        ("ro.crypto.state", crypto),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect()
+}
+
+fn policy() -> AuditArgs {
+    AuditArgs {
+        min_patch: NaiveDate::from_ymd_opt(2024, 6, 1),
+        min_sdk: Some(30),
+        require_verified_boot: true,
+        require_encryption: true,
+    }
+}
+
+#[test]
+fn test_compliant_device_passes() {
+    let mut result = AuditResult::from_props("abc123", &props("2024-07-05", "34", "green", "encrypted"));
+    result.evaluate(&policy());
+    assert!(result.passed());
+    assert_eq!(result.model, "Pixel 8");
+}
+
+#[test]
+fn test_violations_are_reported() {
+    let mut result = AuditResult::from_props("abc123", &props("2023-01-01", "29", "orange", "unencrypted"));
+    result.evaluate(&policy());
+    assert_eq!(
+        result.violations,
+        vec![
+            "patch 2023-01-01 < 2024-06-01",
+            "sdk 29 < 30",
+            "verified boot orange",
+            "encryption unencrypted",
+        ]
+    );
+}
+
+#[test]
+fn test_missing_props_fail_only_enabled_checks() {
+    let mut result = AuditResult::from_props("abc123", &HashMap::new());
+    result.evaluate(&AuditArgs {
+        min_patch: None,
+        min_sdk: Some(30),
+        require_verified_boot: false,
+        require_encryption: false,
+    });
+    assert_eq!(result.violations, vec!["sdk unknown"]);
+}
@@ -0,0 +1,391 @@
+use crate::adb::forward::{Forward, ForwardEntry};
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::output::{OutputFormatter, TableFormat};
+use async_trait::async_trait;
+use clap::Subcommand;
+use colored::*;
+use comfy_table::{Cell, Color};
+use serde::Serialize;
+use std::io::{ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+/// Most of an HTTP response that is kept for display
+const MAX_RESPONSE_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum ForwardCommands {
+    /// Check whether a socket on the device accepts connections
+    Probe(ProbeArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: Option<ForwardCommands>, args: ForwardArgs) -> Result<()> {
+    match cmd {
+        Some(ForwardCommands::Probe(args)) => {
+            let cmd = ProbeCommand::new();
+            cmd.run(ctx, args).await
+        }
+        None => {
+            let cmd = ForwardCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+pub struct ForwardCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ForwardArgs {
+    /// Socket on this machine: a port, tcp:<port>, localabstract:<name> or localfilesystem:<path>
+    #[arg(required_unless_present_any = ["list", "remove", "remove_all"])]
+    pub local: Option<String>,
+
+    /// Socket on the device: a port, tcp:<port>, abstract:<name>, localabstract:<name> or localfilesystem:<path>
+    #[arg(required_unless_present_any = ["list", "remove", "remove_all"])]
+    pub remote: Option<String>,
+
+    /// List forwards (of one device with -d)
+    #[arg(long, conflicts_with_all = ["local", "remove", "remove_all"])]
+    pub list: bool,
+
+    /// Remove the forward of a local socket
+    #[arg(long, value_name = "LOCAL", conflicts_with_all = ["local", "remove_all"])]
+    pub remove: Option<String>,
+
+    /// Remove every forward of the device
+    #[arg(long, conflicts_with = "local")]
+    pub remove_all: bool,
+
+    /// Fail instead of replacing an existing forward of the local socket
+    #[arg(long)]
+    pub no_rebind: bool,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+impl TableFormat for ForwardEntry {
+    fn headers() -> Vec<&'static str> {
+        vec!["DEVICE", "LOCAL", "REMOTE"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.serial.clone(), self.local.clone(), self.remote.clone()]
+    }
+}
+
+impl std::fmt::Display for ForwardEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\t{}\t{}", self.serial, self.local, self.remote)
+    }
+}
+
+impl ForwardCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn list(&self, ctx: &CommandContext, device_id: Option<&str>) -> Result<()> {
+        let (host, port) = ctx.adb_address();
+        let device = match device_id {
+            Some(id) => Some(get_device(Some(id)).await?.id),
+            None => None,
+        };
+        let forwards = Forward::list(host, port, device.as_ref()).await?;
+
+        let formatter = OutputFormatter::new();
+        match ctx.output_format {
+            OutputFormat::Json => formatter.json(&forwards)?,
+            OutputFormat::Plain => formatter.plain(&forwards)?,
+            OutputFormat::Table if forwards.is_empty() => {
+                if !ctx.quiet {
+                    println!("No forwards");
+                }
+            }
+            OutputFormat::Table => formatter.table(&forwards)?,
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubCommand for ForwardCommand {
+    type Args = ForwardArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        if args.list {
+            return self.list(ctx, args.device_id.as_deref()).await;
+        }
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        if let Some(local) = &args.remove {
+            let local = parse_endpoint(local)?;
+            Forward::remove(host, port, &device.id, &local).await?;
+            if !ctx.quiet {
+                println!("{} Removed forward {}", "✓".green(), local);
+            }
+            return Ok(());
+        }
+        if args.remove_all {
+            let removed = Forward::remove_all(host, port, &device.id).await?;
+            if !ctx.quiet {
+                println!("{} Removed {} forwards from {}", "✓".green(), removed.len(), device.id);
+            }
+            return Ok(());
+        }
+
+        let (Some(local), Some(remote)) = (&args.local, &args.remote) else {
+            return Err(AimError::InvalidArgument(
+                "Pass <local> <remote>, or one of --list, --remove, --remove-all".to_string(),
+            ));
+        };
+        let local = parse_endpoint(local)?;
+        let remote = parse_endpoint(remote)?;
+        let allocated = Forward::add(host, port, &device.id, &local, &remote, args.no_rebind).await?;
+        let local = allocated.map(|p| format!("tcp:{}", p)).unwrap_or(local);
+        if ctx.quiet {
+            // Scripts asking for tcp:0 need the port that was picked
+            if allocated.is_some() {
+                println!("{}", local);
+            }
+        } else {
+            println!("{} {} -> {} on {}", "✓".green(), local.bright_cyan(), remote, device.id);
+        }
+        Ok(())
+    }
+}
+
+pub struct ProbeCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ProbeArgs {
+    /// Socket on the device: a port, tcp:<port>, abstract:<name> or localabstract:<name>
+    pub remote: String,
+
+    /// Send an HTTP GET for this path and report the status
+    #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = "/")]
+    pub http: Option<String>,
+
+    /// How long to wait for the endpoint to answer
+    #[arg(long, value_parser = crate::utils::parse_duration, default_value = "2s")]
+    pub wait: Duration,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// Turn user input into an adb socket spec
+pub fn parse_endpoint(input: &str) -> Result<String> {
+    if input.parse::<u16>().is_ok() {
+        return Ok(format!("tcp:{}", input));
+    }
+    let Some((kind, rest)) = input.split_once(':') else {
+        return Err(AimError::InvalidArgument(format!("Invalid endpoint '{}'", input)));
+    };
+    if rest.is_empty() {
+        return Err(AimError::InvalidArgument(format!("Invalid endpoint '{}'", input)));
+    }
+    match kind {
+        "tcp" if rest.parse::<u16>().is_err() => {
+            Err(AimError::InvalidArgument(format!("Invalid port in '{}'", input)))
+        }
+        "tcp" | "localabstract" | "localreserved" | "localfilesystem" | "dev" => Ok(input.to_string()),
+        "abstract" => Ok(format!("localabstract:{}", rest)),
+        _ => Err(AimError::InvalidArgument(format!(
+            "Unsupported endpoint type '{}' (use tcp, abstract, localabstract, localreserved, localfilesystem or dev)",
+            kind
+        ))),
+    }
+}
+
+/// Minimal HTTP/1.0 request that asks the server to close when done
+pub fn http_request(path: &str) -> String {
+    let path = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path)
+    };
+    format!(
+        "GET {} HTTP/1.0\r\nHost: localhost\r\nUser-Agent: aim\r\nConnection: close\r\n\r\n",
+        path
+    )
+}
+
+/// Parse "HTTP/1.1 200 OK" into its status code and reason
+pub fn parse_status_line(response: &str) -> Option<(u16, String)> {
+    let line = response.lines().next()?;
+    let mut parts = line.splitn(3, ' ');
+    if !parts.next()?.starts_with("HTTP/") {
+        return None;
+    }
+    let code = parts.next()?.parse().ok()?;
+    Some((code, parts.next().unwrap_or("").trim().to_string()))
+}
+
+/// Outcome of probing one endpoint
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProbeResult {
+    pub remote: String,
+    pub local_port: u16,
+    pub open: bool,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+impl ProbeResult {
+    fn ok(&self) -> bool {
+        self.open && self.http_status.is_none_or(|code| code < 400)
+    }
+
+    fn status(&self) -> String {
+        match (self.open, self.http_status) {
+            (false, _) => "closed".to_string(),
+            (true, Some(code)) => format!("HTTP {} {}", code, self.http_reason.as_deref().unwrap_or("")).trim().to_string(),
+            (true, None) => "open".to_string(),
+        }
+    }
+}
+
+impl TableFormat for ProbeResult {
+    fn headers() -> Vec<&'static str> {
+        vec!["REMOTE", "LOCAL", "STATUS", "LATENCY"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.remote.clone(),
+            format!("tcp:{}", self.local_port),
+            self.status(),
+            format!("{} ms", self.latency_ms),
+        ]
+    }
+
+    fn colored_row(&self) -> Vec<Cell> {
+        let color = if self.ok() { Color::Green } else { Color::Red };
+        let mut cells: Vec<Cell> = self.row().into_iter().map(Cell::new).collect();
+        cells[2] = Cell::new(self.status()).fg(color);
+        cells
+    }
+}
+
+impl std::fmt::Display for ProbeResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\t{}\t{}ms", self.remote, self.status(), self.latency_ms)
+    }
+}
+
+impl ProbeCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Connect through the forward and see whether the device side answers
+    ///
+    /// The adb server accepts the local connection before it opens the device
+    /// socket and closes it again if the device refuses, so an immediate EOF
+    /// means closed while silence until the timeout means open.
+    fn probe(local_port: u16, http: Option<&str>, timeout: Duration) -> Result<(bool, Option<String>)> {
+        let mut stream = TcpStream::connect(("127.0.0.1", local_port))?;
+        stream.set_read_timeout(Some(timeout))?;
+        stream.set_write_timeout(Some(timeout))?;
+
+        if let Some(path) = http {
+            // A refused endpoint may already have closed the stream
+            if stream.write_all(http_request(path).as_bytes()).is_err() {
+                return Ok((false, None));
+            }
+        }
+
+        let mut response = Vec::new();
+        let mut buffer = [0u8; 4096];
+        loop {
+            match stream.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => {
+                    response.extend_from_slice(&buffer[..n]);
+                    if http.is_none() || response.len() >= MAX_RESPONSE_BYTES {
+                        break;
+                    }
+                }
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                    return Ok((true, Some(String::from_utf8_lossy(&response).into_owned())));
+                }
+                Err(e) if e.kind() == ErrorKind::ConnectionReset => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok((!response.is_empty(), Some(String::from_utf8_lossy(&response).into_owned())))
+    }
+}
+
+#[async_trait]
+impl SubCommand for ProbeCommand {
+    type Args = ProbeArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let remote = parse_endpoint(&args.remote)?;
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        let local_port = Forward::add(host, port, &device.id, "tcp:0", &remote, false)
+            .await?
+            .ok_or_else(|| AimError::AdbProtocol("Server did not report the forwarded port".into()))?;
+        let local = format!("tcp:{}", local_port);
+
+        let started = Instant::now();
+        let probed = Self::probe(local_port, args.http.as_deref(), args.wait);
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        // Always remove the temporary forward, even if probing failed
+        if let Err(e) = Forward::remove(host, port, &device.id, &local).await {
+            log::debug!("Failed to remove forward {}: {}", local, e);
+        }
+        let (open, response) = probed?;
+
+        let mut result = ProbeResult {
+            remote,
+            local_port,
+            open,
+            latency_ms,
+            http_status: None,
+            http_reason: None,
+            body: None,
+        };
+        if let (Some(_), Some(response)) = (&args.http, response) {
+            if let Some((code, reason)) = parse_status_line(&response) {
+                result.http_status = Some(code);
+                result.http_reason = Some(reason);
+                result.body = response.split_once("\r\n\r\n").map(|(_, body)| body.to_string());
+            } else if open {
+                return Err(AimError::CommandExecution(format!(
+                    "{} accepted the connection but did not answer HTTP",
+                    result.remote
+                )));
+            }
+        }
+
+        let formatter = OutputFormatter::new();
+        match ctx.output_format {
+            OutputFormat::Table => formatter.table(std::slice::from_ref(&result))?,
+            OutputFormat::Json => formatter.json(&result)?,
+            OutputFormat::Plain => formatter.plain(std::slice::from_ref(&result))?,
+        }
+
+        if !result.ok() {
+            return Err(AimError::CommandExecution(format!("{} is {}", result.remote, result.status())));
+        }
+        Ok(())
+    }
+}
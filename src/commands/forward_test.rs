@@ -0,0 +1,38 @@
+use super::forward::{http_request, parse_endpoint, parse_status_line};
+
+#[test]
+fn test_parse_endpoint() {
+    assert_eq!(parse_endpoint("8081").unwrap(), "tcp:8081");
+    assert_eq!(parse_endpoint("tcp:3000").unwrap(), "tcp:3000");
+    assert_eq!(parse_endpoint("abstract:flipper").unwrap(), "localabstract:flipper");
+    assert_eq!(parse_endpoint("localabstract:chrome_devtools_remote").unwrap(), "localabstract:chrome_devtools_remote");
+
+    assert!(parse_endpoint("tcp:http").is_err());
+    assert!(parse_endpoint("tcp:").is_err());
+    assert!(parse_endpoint("udp:53").is_err());
+    assert!(parse_endpoint("flipper").is_err());
+}
+
+#[test]
+fn test_http_request() {
+    assert_eq!(
+        http_request("status"),
+        "GET /status HTTP/1.0\r\nHost: localhost\r\nUser-Agent: aim\r\nConnection: close\r\n\r\n"
+    );
+    assert!(http_request("/").starts_with("GET / HTTP/1.0\r\n"));
+}
+
+#[test]
+fn test_parse_status_line() {
+    assert_eq!(
+        parse_status_line("HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok"),
+        Some((200, "OK".to_string()))
+    );
+    assert_eq!(
+        parse_status_line("HTTP/1.0 404 Not Found\r\n"),
+        Some((404, "Not Found".to_string()))
+    );
+    assert_eq!(parse_status_line("HTTP/1.1 204\r\n"), Some((204, String::new())));
+    assert_eq!(parse_status_line("SSH-2.0-OpenSSH\r\n"), None);
+    assert_eq!(parse_status_line(""), None);
+}
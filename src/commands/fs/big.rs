@@ -0,0 +1,99 @@
+use crate::adb::su::RootMode;
+use crate::commands::fs::{parse_du, DuEntry};
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::Result;
+use crate::output::OutputFormatter;
+use crate::utils::{parse_size, shell_quote};
+use async_trait::async_trait;
+
+pub struct BigCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct BigArgs {
+    /// Path to search on the device
+    #[clap(default_value = "/sdcard")]
+    pub path: String,
+
+    /// Number of entries to show
+    #[clap(short = 'n', long, default_value_t = 20)]
+    pub top: usize,
+
+    /// Only show entries at least this large (e.g. 100M, 1G)
+    #[clap(long, value_parser = parse_size)]
+    pub min_size: Option<u64>,
+
+    /// Rank directories instead of files
+    #[clap(long)]
+    pub dirs: bool,
+
+    /// Run as root through su (or adbd, if it already runs as root)
+    #[clap(long)]
+    pub as_root: bool,
+
+    /// Device ID (required if multiple devices are connected)
+    #[clap(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// Build the device command that lists candidate entries with their sizes
+pub fn build_command(args: &BigArgs) -> String {
+    let path = shell_quote(&args.path);
+    if args.dirs {
+        format!("du -k {} 2>/dev/null", path)
+    } else {
+        // Let find do the size filtering so less output crosses the wire
+        let size_filter = args
+            .min_size
+            .map(|bytes| format!(" -size +{}k", (bytes / 1024).saturating_sub(1)))
+            .unwrap_or_default();
+        format!(
+            "find {} -xdev -type f{} -exec du -k {{}} + 2>/dev/null",
+            path, size_filter
+        )
+    }
+}
+
+/// Sort entries largest first, drop those under the minimum and keep the top N
+pub fn rank(mut entries: Vec<DuEntry>, min_size: Option<u64>, top: usize) -> Vec<DuEntry> {
+    if let Some(min) = min_size {
+        entries.retain(|e| e.size >= min);
+    }
+    entries.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    entries.truncate(top);
+    entries
+}
+
+impl BigCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for BigCommand {
+    type Args = BigArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let root_mode = RootMode::for_args(args.as_root, host, port, &device.id).await?;
+
+        let output = crate::adb::shell::ShellCommand::new(root_mode.wrap(&build_command(&args)))
+            .with_device(device.id.clone())
+            .execute(host, port)
+            .await?;
+
+        let entries = rank(parse_du(&output.stdout), args.min_size, args.top);
+
+        let formatter = OutputFormatter::new();
+        match ctx.output_format {
+            OutputFormat::Table => formatter.table(&entries)?,
+            OutputFormat::Json => formatter.json(&entries)?,
+            OutputFormat::Plain => formatter.plain(&entries)?,
+        }
+
+        Ok(())
+    }
+}
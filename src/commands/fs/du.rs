@@ -0,0 +1,266 @@
+use crate::adb::file_transfer::FileTransfer;
+use crate::adb::su::RootMode;
+use crate::commands::fs::{parse_du, DuEntry};
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::{Device, OutputFormat};
+use crate::error::{AimError, Result};
+use crate::utils::{format_bytes, shell_quote};
+use async_trait::async_trait;
+use colored::*;
+use log::debug;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+pub struct DuCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct DuArgs {
+    /// Directory on the device to summarize
+    pub path: String,
+
+    /// How many directory levels to show
+    #[clap(long, default_value_t = 2)]
+    pub depth: usize,
+
+    /// Walk the tree with sync listings instead of running du
+    #[clap(long)]
+    pub sync: bool,
+
+    /// Run as root through su (or adbd, if it already runs as root)
+    #[clap(long)]
+    pub as_root: bool,
+
+    /// Device ID (required if multiple devices are connected)
+    #[clap(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// A directory with its total size and subdirectories, largest first
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuNode {
+    pub name: String,
+    pub size: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<DuNode>,
+}
+
+fn normalize(path: &str) -> String {
+    let trimmed = path.trim_end_matches('/');
+    if trimmed.is_empty() {
+        "/".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn relative_components<'a>(root: &str, path: &'a str) -> Option<Vec<&'a str>> {
+    let rest = if root == "/" {
+        path.strip_prefix('/')?
+    } else {
+        path.strip_prefix(root)?.strip_prefix('/')?
+    };
+    Some(rest.split('/').filter(|c| !c.is_empty()).collect())
+}
+
+/// Build a directory tree rooted at `root` from cumulative `du` entries
+pub fn build_tree(root: &str, entries: &[DuEntry]) -> DuNode {
+    let root = normalize(root);
+    let mut sizes: BTreeMap<Vec<String>, u64> = BTreeMap::new();
+    let mut root_size = None;
+
+    for entry in entries {
+        let path = normalize(&entry.path);
+        if path == root {
+            root_size = Some(entry.size);
+        } else if let Some(components) = relative_components(&root, &path) {
+            sizes.insert(components.iter().map(|c| c.to_string()).collect(), entry.size);
+        }
+    }
+
+    fn build(name: String, prefix: &[String], sizes: &BTreeMap<Vec<String>, u64>) -> DuNode {
+        let mut children: Vec<DuNode> = sizes
+            .keys()
+            .filter(|k| k.len() == prefix.len() + 1 && k.starts_with(prefix))
+            .map(|k| build(k[prefix.len()].clone(), k, sizes))
+            .collect();
+        children.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+        let size = sizes
+            .get(prefix)
+            .copied()
+            .unwrap_or_else(|| children.iter().map(|c| c.size).sum());
+        DuNode { name, size, children }
+    }
+
+    let mut tree = build(root.clone(), &[], &sizes);
+    if let Some(size) = root_size {
+        tree.size = size;
+    }
+    tree
+}
+
+/// Turn a flat list of files into cumulative directory sizes, like `du` would
+pub fn aggregate_dirs(root: &str, dirs: &[String], files: &[(String, u64)]) -> Vec<DuEntry> {
+    let root = normalize(root);
+    let mut totals: HashMap<String, u64> = dirs.iter().map(|d| (normalize(d), 0)).collect();
+    totals.insert(root.clone(), 0);
+
+    for (path, size) in files {
+        let mut current = normalize(path);
+        while current != root {
+            current = match current.rfind('/') {
+                Some(0) => "/".to_string(),
+                Some(idx) => current[..idx].to_string(),
+                None => break,
+            };
+            *totals.entry(current.clone()).or_default() += size;
+            if current == "/" {
+                break;
+            }
+        }
+    }
+
+    totals
+        .into_iter()
+        .map(|(path, size)| DuEntry { path, size })
+        .collect()
+}
+
+fn truncate_depth(node: &mut DuNode, depth: usize) {
+    if depth == 0 {
+        node.children.clear();
+    } else {
+        for child in &mut node.children {
+            truncate_depth(child, depth - 1);
+        }
+    }
+}
+
+impl DuCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn du_entries(&self, ctx: &CommandContext, device: &Device, path: &str, root_mode: RootMode) -> Result<Vec<DuEntry>> {
+        let (host, port) = ctx.adb_address();
+        let command = format!("du -k {} 2>/dev/null", shell_quote(path));
+        let output = crate::adb::shell::ShellCommand::new(root_mode.wrap(&command))
+            .with_device(device.id.clone())
+            .execute(host, port)
+            .await?;
+        Ok(parse_du(&output.stdout))
+    }
+
+    /// Walk the tree with LIS2 listings, summing apparent file sizes
+    async fn sync_entries(&self, ctx: &CommandContext, device: &Device, path: &str) -> Result<Vec<DuEntry>> {
+        let (host, port) = ctx.adb_address();
+        let mut transfer = FileTransfer::new(host, port, Some(&device.id)).await?;
+
+        let root = normalize(path);
+        let mut pending = vec![root.clone()];
+        let mut dirs = Vec::new();
+        let mut files = Vec::new();
+
+        while let Some(dir) = pending.pop() {
+            let entries = match transfer.list_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(e) => {
+                    debug!("Skipping {}: {}", dir, e);
+                    continue;
+                }
+            };
+            for entry in entries {
+                let child = if dir == "/" {
+                    format!("/{}", entry.name)
+                } else {
+                    format!("{}/{}", dir, entry.name)
+                };
+                if entry.stat.is_dir() {
+                    dirs.push(child.clone());
+                    pending.push(child);
+                } else if entry.stat.is_file() {
+                    files.push((child, entry.stat.size64()));
+                }
+            }
+        }
+
+        Ok(aggregate_dirs(&root, &dirs, &files))
+    }
+
+    fn print_tree(node: &DuNode, total: u64, prefix: &str, is_root: bool, is_last: bool) {
+        let percent = if total > 0 {
+            node.size as f64 * 100.0 / total as f64
+        } else {
+            0.0
+        };
+        let branch = if is_root {
+            ""
+        } else if is_last {
+            "└── "
+        } else {
+            "├── "
+        };
+        let name = if node.children.is_empty() {
+            node.name.normal()
+        } else {
+            node.name.bright_blue()
+        };
+        println!(
+            "{:>8} {:>6.1}%  {}{}{}",
+            format_bytes(node.size),
+            percent,
+            prefix,
+            branch,
+            name
+        );
+
+        let child_prefix = if is_root {
+            String::new()
+        } else if is_last {
+            format!("{}    ", prefix)
+        } else {
+            format!("{}│   ", prefix)
+        };
+        for (i, child) in node.children.iter().enumerate() {
+            Self::print_tree(child, total, &child_prefix, false, i + 1 == node.children.len());
+        }
+    }
+}
+
+#[async_trait]
+impl SubCommand for DuCommand {
+    type Args = DuArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let root = normalize(&args.path);
+        let (host, port) = ctx.adb_address();
+        let root_mode = RootMode::for_args(args.as_root, host, port, &device.id).await?;
+
+        let mut entries = if args.sync {
+            Vec::new()
+        } else {
+            self.du_entries(ctx, &device, &root, root_mode).await?
+        };
+
+        // du prints nothing for directories the shell user cannot read
+        if !entries.iter().any(|e| normalize(&e.path) == root) {
+            debug!("du gave no result for {}, falling back to sync listing", root);
+            entries = self.sync_entries(ctx, &device, &root).await?;
+        }
+
+        if entries.is_empty() {
+            return Err(AimError::FileTransfer(format!("Cannot read {}", root)));
+        }
+
+        let mut tree = build_tree(&root, &entries);
+        truncate_depth(&mut tree, args.depth);
+
+        match ctx.output_format {
+            OutputFormat::Json => crate::commands::format_json_output(&tree)?,
+            _ => Self::print_tree(&tree, tree.size, "", true, true),
+        }
+
+        Ok(())
+    }
+}
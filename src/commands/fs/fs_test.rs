@@ -0,0 +1,248 @@
+use super::big::{build_command, rank, BigArgs};
+use super::du::{aggregate_dirs, build_tree, DuNode};
+use super::grep::{grep_command, parse_grep_line, GrepArgs, GrepRow, LineBuffer};
+use super::hash::{compare_hashes, parse_hash_output, relative_path, HashAlgo, HashEntry, HashStatus};
+use super::tail::{resume_start, tail_command, TailStart};
+use super::{parse_du, DuEntry};
+
+#[test]
+fn test_parse_du() {
+    let output = "12\t/data/local/tmp/perfetto.trace\n4096\t/data/local/tmp/my dir\n\nbogus line\n";
+    assert_eq!(
+        parse_du(output),
+        vec![
+            DuEntry { path: "/data/local/tmp/perfetto.trace".to_string(), size: 12 * 1024 },
+            DuEntry { path: "/data/local/tmp/my dir".to_string(), size: 4096 * 1024 },
+        ]
+    );
+}
+
+fn big_args(dirs: bool, min_size: Option<u64>) -> BigArgs {
+    BigArgs {
+        path: "/sdcard/My Files".to_string(),
+        top: 20,
+        min_size,
+        dirs,
+        as_root: false,
+        device_id: None,
+    }
+}
+
+#[test]
+fn test_build_command() {
+    assert_eq!(
+        build_command(&big_args(false, None)),
+        "find '/sdcard/My Files' -xdev -type f -exec du -k {} + 2>/dev/null"
+    );
+    assert_eq!(
+        build_command(&big_args(false, Some(100 * 1024 * 1024))),
+        "find '/sdcard/My Files' -xdev -type f -size +102399k -exec du -k {} + 2>/dev/null"
+    );
+    assert_eq!(
+        build_command(&big_args(true, None)),
+        "du -k '/sdcard/My Files' 2>/dev/null"
+    );
+}
+
+#[test]
+fn test_rank() {
+    let entries = vec![
+        DuEntry { path: "a".to_string(), size: 10 },
+        DuEntry { path: "b".to_string(), size: 300 },
+        DuEntry { path: "c".to_string(), size: 200 },
+        DuEntry { path: "d".to_string(), size: 50 },
+    ];
+    let ranked = rank(entries, Some(50), 2);
+    assert_eq!(
+        ranked.iter().map(|e| e.path.as_str()).collect::<Vec<_>>(),
+        vec!["b", "c"]
+    );
+}
+
+fn entry(path: &str, size: u64) -> DuEntry {
+    DuEntry { path: path.to_string(), size }
+}
+
+#[test]
+fn test_build_tree() {
+    let entries = vec![
+        entry("/sdcard/DCIM/Camera", 300),
+        entry("/sdcard/DCIM", 400),
+        entry("/sdcard/Music", 500),
+        entry("/sdcard/", 1000),
+    ];
+    let tree = build_tree("/sdcard", &entries);
+
+    assert_eq!(tree.name, "/sdcard");
+    assert_eq!(tree.size, 1000);
+    assert_eq!(
+        tree.children.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+        vec!["Music", "DCIM"]
+    );
+    assert_eq!(
+        tree.children[1].children,
+        vec![DuNode { name: "Camera".to_string(), size: 300, children: vec![] }]
+    );
+}
+
+#[test]
+fn test_aggregate_dirs() {
+    let dirs = vec!["/sdcard/a".to_string(), "/sdcard/a/b".to_string(), "/sdcard/empty".to_string()];
+    let files = vec![
+        ("/sdcard/top.txt".to_string(), 1),
+        ("/sdcard/a/one".to_string(), 10),
+        ("/sdcard/a/b/two".to_string(), 100),
+    ];
+    let mut entries = aggregate_dirs("/sdcard", &dirs, &files);
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+    assert_eq!(
+        entries,
+        vec![
+            entry("/sdcard", 111),
+            entry("/sdcard/a", 110),
+            entry("/sdcard/a/b", 100),
+            entry("/sdcard/empty", 0),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_hash_output() {
+    let output = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855  /sdcard/empty.txt\n\
+                  D41D8CD98F00B204E9800998ECF8427E  /sdcard/My Files/a b.txt\n\
+                  sha256sum: /sdcard/locked: Permission denied\n";
+    assert_eq!(
+        parse_hash_output(output),
+        vec![
+            HashEntry {
+                path: "/sdcard/empty.txt".to_string(),
+                hash: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".to_string(),
+            },
+            HashEntry {
+                path: "/sdcard/My Files/a b.txt".to_string(),
+                hash: "d41d8cd98f00b204e9800998ecf8427e".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_relative_path() {
+    assert_eq!(relative_path("/sdcard/DCIM/", "/sdcard/DCIM/Camera/a.jpg"), Some("Camera/a.jpg".to_string()));
+    assert_eq!(relative_path("/sdcard/DCIM", "/sdcard/DCIMx/a.jpg"), None);
+    assert_eq!(relative_path("/sdcard/DCIM", "/sdcard/DCIM"), None);
+}
+
+#[test]
+fn test_compare_hashes() {
+    let remote = std::collections::BTreeMap::from([
+        ("a".to_string(), "11".to_string()),
+        ("b".to_string(), "22".to_string()),
+        ("c".to_string(), "33".to_string()),
+    ]);
+    let local = std::collections::BTreeMap::from([
+        ("a".to_string(), "11".to_string()),
+        ("b".to_string(), "99".to_string()),
+        ("d".to_string(), "44".to_string()),
+    ]);
+
+    let statuses: Vec<(String, HashStatus)> = compare_hashes(&remote, &local)
+        .into_iter()
+        .map(|c| (c.path, c.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            ("a".to_string(), HashStatus::Match),
+            ("b".to_string(), HashStatus::Mismatch),
+            ("c".to_string(), HashStatus::MissingLocal),
+            ("d".to_string(), HashStatus::MissingRemote),
+        ]
+    );
+}
+
+#[test]
+fn test_hash_local_file() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut file, b"abc").unwrap();
+
+    assert_eq!(
+        HashAlgo::Sha256.hash_file(file.path()).unwrap(),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(HashAlgo::Md5.hash_file(file.path()).unwrap(), "900150983cd24fb0d6963f7d28e17f72");
+}
+
+#[test]
+fn test_tail_command() {
+    assert_eq!(tail_command("/data/local/tmp/app.log", TailStart::Last(100), false), "tail -n 100 /data/local/tmp/app.log");
+    assert_eq!(tail_command("/sdcard/my log.txt", TailStart::End, true), "tail -n 0 -F '/sdcard/my log.txt'");
+    assert_eq!(tail_command("/sdcard/a.log", TailStart::Beginning, true), "tail -n +1 -F /sdcard/a.log");
+}
+
+#[test]
+fn test_resume_start() {
+    assert_eq!(resume_start(Some(42), Some(42)), TailStart::End);
+    assert_eq!(resume_start(Some(42), Some(43)), TailStart::Beginning);
+    // Unknown inodes (file missing, stat failed) just pick up new lines
+    assert_eq!(resume_start(None, Some(43)), TailStart::End);
+    assert_eq!(resume_start(Some(42), None), TailStart::End);
+}
+
+fn grep_args(pattern: &str, paths: &[&str]) -> GrepArgs {
+    GrepArgs {
+        pattern: pattern.to_string(),
+        paths: paths.iter().map(|p| p.to_string()).collect(),
+        recursive: false,
+        context: None,
+        ignore_case: false,
+        as_root: false,
+        device_id: None,
+    }
+}
+
+#[test]
+fn test_grep_command() {
+    assert_eq!(
+        grep_command(&grep_args("FATAL", &["/data/local/tmp/app.log"])),
+        "grep -HnZ -e FATAL /data/local/tmp/app.log 2>/dev/null"
+    );
+
+    let mut args = grep_args("it's", &["/sdcard/logs", "/sdcard/a b.txt"]);
+    args.recursive = true;
+    args.ignore_case = true;
+    args.context = Some(2);
+    assert_eq!(
+        grep_command(&args),
+        "grep -HnZri -C 2 -e 'it'\\''s' /sdcard/logs '/sdcard/a b.txt' 2>/dev/null"
+    );
+}
+
+#[test]
+fn test_parse_grep_line() {
+    assert_eq!(
+        parse_grep_line("/sdcard/a:b.log\x0012:error: disk full"),
+        Some(GrepRow {
+            file: "/sdcard/a:b.log".to_string(),
+            line: 12,
+            text: "error: disk full".to_string(),
+            is_match: true,
+        })
+    );
+    let context = parse_grep_line("/sdcard/app.log\x0011-starting - up").unwrap();
+    assert_eq!(context.line, 11);
+    assert_eq!(context.text, "starting - up");
+    assert!(!context.is_match);
+
+    assert_eq!(parse_grep_line("--"), None);
+    assert_eq!(parse_grep_line("/sdcard/app.log\0oops"), None);
+}
+
+#[test]
+fn test_line_buffer() {
+    let mut buffer = LineBuffer::default();
+    assert!(buffer.push("first li").is_empty());
+    assert_eq!(buffer.push("ne\r\nsecond\nthi"), vec!["first line", "second"]);
+    assert_eq!(buffer.push("rd\n"), vec!["third"]);
+}
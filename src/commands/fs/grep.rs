@@ -0,0 +1,169 @@
+use crate::adb::su::RootMode;
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use colored::*;
+use serde::Serialize;
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+pub struct GrepCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct GrepArgs {
+    /// Pattern to search for (basic regular expression)
+    pub pattern: String,
+
+    /// Files or directories on the device
+    #[clap(required = true)]
+    pub paths: Vec<String>,
+
+    /// Search directories recursively
+    #[clap(short, long)]
+    pub recursive: bool,
+
+    /// Lines of context to show around each match
+    #[clap(short = 'C', long, value_name = "N")]
+    pub context: Option<usize>,
+
+    /// Match case-insensitively
+    #[clap(short, long)]
+    pub ignore_case: bool,
+
+    /// Run as root through su (or adbd, if it already runs as root)
+    #[clap(long)]
+    pub as_root: bool,
+
+    /// Device ID (required if multiple devices are connected)
+    #[clap(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// Build the device command; `-Z` ends file names with NUL so they can contain `:`
+pub fn grep_command(args: &GrepArgs) -> String {
+    let mut flags = String::from("-HnZ");
+    if args.recursive {
+        flags.push('r');
+    }
+    if args.ignore_case {
+        flags.push('i');
+    }
+    let mut command = format!("grep {}", flags);
+    if let Some(n) = args.context {
+        command.push_str(&format!(" -C {}", n));
+    }
+    command.push_str(&format!(" -e {}", shell_quote(&args.pattern)));
+    for path in &args.paths {
+        command.push(' ');
+        command.push_str(&shell_quote(path));
+    }
+    command.push_str(" 2>/dev/null");
+    command
+}
+
+/// One line of grep output
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GrepRow {
+    pub file: String,
+    pub line: u64,
+    pub text: String,
+    /// False for lines shown only as context
+    #[serde(rename = "match")]
+    pub is_match: bool,
+}
+
+/// Parse one line of `grep -HnZ` output ("<file>\0<line>:<text>", or `-` for context)
+pub fn parse_grep_line(line: &str) -> Option<GrepRow> {
+    let (file, rest) = line.split_once('\0')?;
+    let sep = rest.find([':', '-'])?;
+    let number = rest[..sep].parse().ok()?;
+    Some(GrepRow {
+        file: file.to_string(),
+        line: number,
+        text: rest[sep + 1..].to_string(),
+        is_match: rest.as_bytes()[sep] == b':',
+    })
+}
+
+/// Splits streamed output into complete lines
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    partial: String,
+}
+
+impl LineBuffer {
+    /// Add a chunk, returning every line it completed
+    pub fn push(&mut self, chunk: &str) -> Vec<String> {
+        self.partial.push_str(chunk);
+        let Some(end) = self.partial.rfind('\n') else {
+            return Vec::new();
+        };
+        let rest = self.partial.split_off(end + 1);
+        let done = std::mem::replace(&mut self.partial, rest);
+        done.lines().map(|l| l.trim_end_matches('\r').to_string()).collect()
+    }
+}
+
+impl GrepCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn print_row(row: &GrepRow, format: OutputFormat) {
+        match format {
+            OutputFormat::Json => {
+                if let Ok(json) = serde_json::to_string(row) {
+                    println!("{}", json);
+                }
+            }
+            OutputFormat::Plain => {
+                let sep = if row.is_match { ':' } else { '-' };
+                println!("{}{}{}{}{}", row.file, sep, row.line, sep, row.text);
+            }
+            OutputFormat::Table => {
+                let text = if row.is_match { row.text.normal() } else { row.text.dimmed() };
+                println!("{}:{}: {}", row.file.magenta(), row.line.to_string().green(), text);
+            }
+        }
+        let _ = std::io::stdout().flush();
+    }
+}
+
+#[async_trait]
+impl SubCommand for GrepCommand {
+    type Args = GrepArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let root_mode = RootMode::for_args(args.as_root, host, port, &device.id).await?;
+
+        let format = ctx.output_format;
+        let matches = Arc::new(AtomicUsize::new(0));
+        let counter = matches.clone();
+        let mut buffer = LineBuffer::default();
+
+        crate::adb::shell::ShellCommand::new(root_mode.wrap(&grep_command(&args)))
+            .with_device(device.id.clone())
+            .execute_streaming(host, port, move |chunk| {
+                // `--` lines separate context groups and don't parse as rows
+                for row in buffer.push(chunk).iter().filter_map(|l| parse_grep_line(l)) {
+                    if row.is_match {
+                        counter.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Self::print_row(&row, format);
+                }
+            })
+            .await?;
+
+        if matches.load(Ordering::Relaxed) == 0 {
+            return Err(AimError::CommandExecution(format!("No matches for '{}'", args.pattern)));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,339 @@
+use crate::adb::su::RootMode;
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::output::{OutputFormatter, TableFormat};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use clap::ValueEnum;
+use comfy_table::{Cell, Color};
+use serde::Serialize;
+use sha2::Digest;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+pub struct HashCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct HashArgs {
+    /// File or directory on the device
+    pub path: String,
+
+    /// Local file or directory to compare against
+    #[clap(long)]
+    pub against: Option<PathBuf>,
+
+    /// Hash algorithm
+    #[clap(long, value_enum, default_value_t = HashAlgo::Sha256)]
+    pub algo: HashAlgo,
+
+    /// Run as root through su (or adbd, if it already runs as root)
+    #[clap(long)]
+    pub as_root: bool,
+
+    /// Device ID (required if multiple devices are connected)
+    #[clap(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum HashAlgo {
+    #[default]
+    Sha256,
+    Md5,
+}
+
+impl HashAlgo {
+    /// Toybox tool that computes this hash on the device
+    pub fn tool(&self) -> &'static str {
+        match self {
+            HashAlgo::Sha256 => "sha256sum",
+            HashAlgo::Md5 => "md5sum",
+        }
+    }
+
+    /// Hash a local file, streaming it rather than reading it whole
+    pub fn hash_file(&self, path: &Path) -> io::Result<String> {
+        match self {
+            HashAlgo::Sha256 => digest_file::<sha2::Sha256>(path),
+            HashAlgo::Md5 => digest_file::<md5::Md5>(path),
+        }
+    }
+}
+
+fn digest_file<D: Digest>(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = D::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// A file and its hash
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HashEntry {
+    pub path: String,
+    pub hash: String,
+}
+
+/// Parse `sha256sum`/`md5sum` output ("<hash>  <path>" per line)
+pub fn parse_hash_output(output: &str) -> Vec<HashEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (hash, path) = line.trim_end().split_once(char::is_whitespace)?;
+            if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            Some(HashEntry {
+                hash: hash.to_lowercase(),
+                path: path.trim_start().to_string(),
+            })
+        })
+        .collect()
+}
+
+impl TableFormat for HashEntry {
+    fn headers() -> Vec<&'static str> {
+        vec!["HASH", "PATH"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.hash.clone(), self.path.clone()]
+    }
+}
+
+impl std::fmt::Display for HashEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}  {}", self.hash, self.path)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashStatus {
+    Match,
+    Mismatch,
+    MissingLocal,
+    MissingRemote,
+}
+
+impl std::fmt::Display for HashStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            HashStatus::Match => "match",
+            HashStatus::Mismatch => "MISMATCH",
+            HashStatus::MissingLocal => "missing locally",
+            HashStatus::MissingRemote => "missing on device",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Result of comparing one file on both sides
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HashComparison {
+    pub path: String,
+    pub status: HashStatus,
+    pub remote: Option<String>,
+    pub local: Option<String>,
+}
+
+/// Compare hashes keyed by path relative to the compared roots
+pub fn compare_hashes(
+    remote: &BTreeMap<String, String>,
+    local: &BTreeMap<String, String>,
+) -> Vec<HashComparison> {
+    let paths: std::collections::BTreeSet<&String> = remote.keys().chain(local.keys()).collect();
+    paths
+        .into_iter()
+        .map(|path| {
+            let r = remote.get(path);
+            let l = local.get(path);
+            let status = match (r, l) {
+                (Some(r), Some(l)) if r == l => HashStatus::Match,
+                (Some(_), Some(_)) => HashStatus::Mismatch,
+                (Some(_), None) => HashStatus::MissingLocal,
+                (None, _) => HashStatus::MissingRemote,
+            };
+            HashComparison {
+                path: path.clone(),
+                status,
+                remote: r.cloned(),
+                local: l.cloned(),
+            }
+        })
+        .collect()
+}
+
+/// Path of a listed file relative to the hashed directory
+pub fn relative_path(root: &str, path: &str) -> Option<String> {
+    let root = root.trim_end_matches('/');
+    let rest = path.strip_prefix(root)?.strip_prefix('/')?;
+    (!rest.is_empty()).then(|| rest.to_string())
+}
+
+impl TableFormat for HashComparison {
+    fn headers() -> Vec<&'static str> {
+        vec!["STATUS", "PATH", "DEVICE", "LOCAL"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let short = |h: &Option<String>| h.as_deref().map(|h| h[..h.len().min(16)].to_string()).unwrap_or_default();
+        vec![
+            self.status.to_string(),
+            self.path.clone(),
+            short(&self.remote),
+            short(&self.local),
+        ]
+    }
+
+    fn colored_row(&self) -> Vec<Cell> {
+        let color = match self.status {
+            HashStatus::Match => Color::Green,
+            HashStatus::Mismatch => Color::Red,
+            HashStatus::MissingLocal | HashStatus::MissingRemote => Color::Yellow,
+        };
+        let mut cells: Vec<Cell> = self.row().into_iter().map(Cell::new).collect();
+        cells[0] = Cell::new(self.status.to_string()).fg(color);
+        cells
+    }
+}
+
+impl std::fmt::Display for HashComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\t{}", self.status, self.path)
+    }
+}
+
+impl HashCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Hash every local file under `root`, keyed by relative path
+    fn local_hashes(root: &Path, algo: HashAlgo) -> Result<BTreeMap<String, String>> {
+        let mut hashes = BTreeMap::new();
+        for entry in walkdir::WalkDir::new(root) {
+            let entry = entry.map_err(|e| AimError::FileTransfer(e.to_string()))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(rel) = entry.path().strip_prefix(root) else {
+                continue;
+            };
+            let rel = rel
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            hashes.insert(rel, algo.hash_file(entry.path())?);
+        }
+        Ok(hashes)
+    }
+
+    /// Line up device and local hashes by relative path
+    fn collect_pairs(
+        root: &str,
+        remote: &[HashEntry],
+        against: &Path,
+        algo: HashAlgo,
+    ) -> Result<(BTreeMap<String, String>, BTreeMap<String, String>)> {
+        let root = root.trim_end_matches('/');
+
+        // A single file is compared with a local file of the same name
+        if let [entry] = remote {
+            if entry.path.trim_end_matches('/') == root {
+                let name = root.rsplit('/').next().unwrap_or(root).to_string();
+                let local_file = if against.is_dir() {
+                    against.join(&name)
+                } else {
+                    against.to_path_buf()
+                };
+                let mut local = BTreeMap::new();
+                if local_file.is_file() {
+                    local.insert(name.clone(), algo.hash_file(&local_file)?);
+                }
+                let remote = BTreeMap::from([(name, entry.hash.clone())]);
+                return Ok((remote, local));
+            }
+        }
+
+        let remote = remote
+            .iter()
+            .filter_map(|e| Some((relative_path(root, &e.path)?, e.hash.clone())))
+            .collect();
+        Ok((remote, Self::local_hashes(against, algo)?))
+    }
+}
+
+#[async_trait]
+impl SubCommand for HashCommand {
+    type Args = HashArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        let command = format!(
+            "find {} -type f -exec {} {{}} + 2>/dev/null",
+            shell_quote(&args.path),
+            args.algo.tool()
+        );
+        let root_mode = RootMode::for_args(args.as_root, host, port, &device.id).await?;
+        let output = crate::adb::shell::ShellCommand::new(root_mode.wrap(&command))
+            .with_device(device.id.clone())
+            .execute(host, port)
+            .await?;
+        let mut entries = parse_hash_output(&output.stdout);
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let formatter = OutputFormatter::new();
+        let Some(against) = &args.against else {
+            if entries.is_empty() {
+                return Err(AimError::FileTransfer(format!("No files to hash at {}", args.path)));
+            }
+            return match ctx.output_format {
+                OutputFormat::Table => formatter.table(&entries),
+                OutputFormat::Json => formatter.json(&entries),
+                OutputFormat::Plain => formatter.plain(&entries),
+            };
+        };
+
+        if !against.exists() {
+            return Err(AimError::FileTransfer(format!("{} does not exist", against.display())));
+        }
+        let (remote, local) = Self::collect_pairs(&args.path, &entries, against, args.algo)?;
+        let results = compare_hashes(&remote, &local);
+
+        match ctx.output_format {
+            OutputFormat::Table => formatter.table(&results)?,
+            OutputFormat::Json => formatter.json(&results)?,
+            OutputFormat::Plain => formatter.plain(&results)?,
+        }
+
+        let differing = results.iter().filter(|r| r.status != HashStatus::Match).count();
+        if differing > 0 {
+            return Err(AimError::CommandExecution(format!(
+                "{} of {} file(s) differ",
+                differing,
+                results.len()
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use clap::Subcommand;
+use crate::error::Result;
+use crate::core::context::CommandContext;
+use crate::commands::SubCommand;
+use crate::output::TableFormat;
+use serde::Serialize;
+
+mod big;
+mod du;
+mod grep;
+mod hash;
+mod tail;
+
+pub use big::BigCommand;
+pub use du::DuCommand;
+pub use grep::GrepCommand;
+pub use hash::{parse_hash_output, HashAlgo, HashCommand};
+pub use tail::TailCommand;
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum FsCommands {
+    /// Find the largest files or directories
+    Big(big::BigArgs),
+
+    /// Show a directory size tree
+    Du(du::DuArgs),
+
+    /// Search files on the device, streaming matching lines
+    Grep(grep::GrepArgs),
+
+    /// Hash files on the device, optionally comparing with local copies
+    Hash(hash::HashArgs),
+
+    /// Show the end of a file, optionally following it
+    Tail(tail::TailArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: FsCommands) -> Result<()> {
+    match cmd {
+        FsCommands::Big(args) => {
+            let cmd = BigCommand::new();
+            cmd.run(ctx, args).await
+        }
+        FsCommands::Du(args) => {
+            let cmd = DuCommand::new();
+            cmd.run(ctx, args).await
+        }
+        FsCommands::Grep(args) => {
+            let cmd = GrepCommand::new();
+            cmd.run(ctx, args).await
+        }
+        FsCommands::Hash(args) => {
+            let cmd = HashCommand::new();
+            cmd.run(ctx, args).await
+        }
+        FsCommands::Tail(args) => {
+            let cmd = TailCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+/// A path on the device with its disk usage in bytes
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuEntry {
+    pub path: String,
+    pub size: u64,
+}
+
+/// Parse `du -k` style output ("<kb>\t<path>" per line)
+pub fn parse_du(output: &str) -> Vec<DuEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (size, path) = line.trim().split_once(char::is_whitespace)?;
+            Some(DuEntry {
+                path: path.trim().to_string(),
+                size: size.parse::<u64>().ok()? * 1024,
+            })
+        })
+        .collect()
+}
+
+impl TableFormat for DuEntry {
+    fn headers() -> Vec<&'static str> {
+        vec!["SIZE", "PATH"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![crate::utils::format_bytes(self.size), self.path.clone()]
+    }
+}
+
+impl std::fmt::Display for DuEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\t{}", self.size, self.path)
+    }
+}
+
+#[cfg(test)]
+mod fs_test;
@@ -0,0 +1,142 @@
+use crate::adb::su::RootMode;
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::Device;
+use crate::error::Result;
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use colored::*;
+use log::debug;
+use std::io::Write;
+use std::time::Duration;
+
+/// Pause before reconnecting after the stream ends
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+pub struct TailCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct TailArgs {
+    /// File on the device
+    pub path: String,
+
+    /// Number of lines to show
+    #[clap(short = 'n', long, default_value_t = 100)]
+    pub lines: usize,
+
+    /// Keep streaming appended lines, reconnecting if the file is rotated
+    #[clap(short, long)]
+    pub follow: bool,
+
+    /// Run as root through su (or adbd, if it already runs as root)
+    #[clap(long)]
+    pub as_root: bool,
+
+    /// Device ID (required if multiple devices are connected)
+    #[clap(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// Where a `tail` run starts reading
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TailStart {
+    /// The last N lines
+    Last(usize),
+    /// The whole file, after it was replaced
+    Beginning,
+    /// Only lines appended from now on
+    End,
+}
+
+/// Build the device command for one tail run
+pub fn tail_command(path: &str, start: TailStart, follow: bool) -> String {
+    let lines = match start {
+        TailStart::Last(n) => n.to_string(),
+        TailStart::Beginning => "+1".to_string(),
+        TailStart::End => "0".to_string(),
+    };
+    let follow = if follow { " -F" } else { "" };
+    format!("tail -n {}{} {}", lines, follow, shell_quote(path))
+}
+
+/// Decide where to resume after the stream dropped
+///
+/// A different inode means the file was rotated while disconnected, so the new
+/// file is shown from its first line; otherwise only new lines follow.
+pub fn resume_start(previous: Option<u64>, current: Option<u64>) -> TailStart {
+    match (previous, current) {
+        (Some(prev), Some(cur)) if prev != cur => TailStart::Beginning,
+        _ => TailStart::End,
+    }
+}
+
+impl TailCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn inode(host: &str, port: u16, device: &Device, path: &str, root_mode: RootMode) -> Option<u64> {
+        let command = format!("stat -L -c %i {} 2>/dev/null", shell_quote(path));
+        let output = crate::adb::shell::ShellCommand::new(root_mode.wrap(&command))
+            .with_device(device.id.clone())
+            .execute(host, port)
+            .await
+            .ok()?;
+        output.stdout.trim().parse().ok()
+    }
+}
+
+#[async_trait]
+impl SubCommand for TailCommand {
+    type Args = TailArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let root_mode = RootMode::for_args(args.as_root, host, port, &device.id).await?;
+
+        if !args.follow {
+            let command = tail_command(&args.path, TailStart::Last(args.lines), false);
+            let output = crate::adb::shell::ShellCommand::new(root_mode.wrap(&command))
+                .with_device(device.id.clone())
+                .execute(host, port)
+                .await?;
+            print!("{}", output.stdout);
+            return Ok(());
+        }
+
+        let mut start = TailStart::Last(args.lines);
+        let mut inode = Self::inode(host, port, &device, &args.path, root_mode).await;
+        loop {
+            let command = crate::adb::shell::ShellCommand::new(root_mode.wrap(&tail_command(&args.path, start, true)))
+                .with_device(device.id.clone());
+            let stream = command.execute_streaming(host, port, |chunk| {
+                print!("{}", chunk);
+                let _ = std::io::stdout().flush();
+            });
+
+            tokio::select! {
+                result = stream => {
+                    if let Err(e) = result {
+                        debug!("tail stream ended: {}", e);
+                    }
+                }
+                _ = tokio::signal::ctrl_c() => break,
+            }
+
+            eprintln!("{}", format!("-- {} stream ended, reconnecting --", args.path).dimmed());
+            tokio::select! {
+                _ = tokio::time::sleep(RECONNECT_DELAY) => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+
+            let current = Self::inode(host, port, &device, &args.path, root_mode).await;
+            start = resume_start(inode, current);
+            if current.is_some() {
+                inode = current;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,12 +1,17 @@
-use crate::commands::{SubCommand, get_device};
+use crate::commands::{SubCommand, get_device, resolve_device_alias};
 use crate::core::context::CommandContext;
 use crate::cli::OutputType;
-use crate::error::Result;
-use crate::library::adb::{getprop_async, getprops_parallel};
+use crate::error::{AimError, Result};
+use crate::library::adb::getprop_dump;
+use crate::output::{OutputFormatter, TableFormat};
 use async_trait::async_trait;
 use colored::*;
-use comfy_table::{Table, Cell, Attribute};
-use std::collections::HashMap;
+use comfy_table::{Table, Cell, Attribute, Color};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crate::state::{cached_at, StateStore};
 use crate::utils::print_colored_json;
 
 pub struct GetpropCommand;
@@ -23,6 +28,194 @@ pub struct GetpropArgs {
     /// Output format
     #[clap(short, long, value_enum, default_value_t = OutputType::Plain)]
     pub output: OutputType,
+
+    /// Show values cached from an earlier run instead of querying the device
+    #[clap(long)]
+    pub cached: bool,
+
+    /// Save the properties to a file (JSON) instead of printing them
+    #[clap(long, value_name = "FILE", conflicts_with = "compare")]
+    pub export: Option<PathBuf>,
+
+    /// Compare the properties with a file written by --export
+    #[clap(long, value_name = "FILE")]
+    pub compare: Option<PathBuf>,
+
+    /// With --compare, exit with an error if a difference is at least this severe
+    #[clap(long, value_enum, value_name = "SEVERITY", requires = "compare")]
+    pub fail_on: Option<Severity>,
+}
+
+/// How much a differing property matters when comparing devices
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    fn as_str(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+/// Properties that decide whether an image behaves like the real device
+const CRITICAL_PROPS: &[&str] = &[
+    "ro.build.fingerprint",
+    "ro.vendor.build.fingerprint",
+    "ro.build.version.security_patch",
+    "ro.build.version.sdk",
+    "ro.build.version.release",
+    "ro.product.cpu.abi",
+    "ro.product.cpu.abilist",
+    "ro.product.cpu.abilist32",
+    "ro.product.cpu.abilist64",
+];
+
+/// Properties apps commonly check, worth a look when they differ
+const WARNING_PROPS: &[&str] = &[
+    "ro.build.type",
+    "ro.build.tags",
+    "ro.debuggable",
+    "ro.secure",
+    "ro.product.brand",
+    "ro.product.device",
+    "ro.product.manufacturer",
+    "ro.product.model",
+    "ro.hardware",
+    "ro.sf.lcd_density",
+    "ro.boot.verifiedbootstate",
+    "ro.crypto.state",
+    "ro.vendor.api_level",
+    "ro.product.first_api_level",
+];
+
+/// Properties that change from boot to boot and say nothing about the image
+const VOLATILE_PREFIXES: &[&str] = &[
+    "init.svc.",
+    "init.svc_debug_pid.",
+    "ro.boottime.",
+    "ro.runtime.",
+    "ro.serialno",
+    "ro.boot.serialno",
+    "sys.boot_completed",
+    "dev.bootcomplete",
+    "persist.sys.boot.reason",
+];
+
+/// Severity of a difference in `prop`
+pub fn severity(prop: &str) -> Severity {
+    if CRITICAL_PROPS.contains(&prop) {
+        Severity::Critical
+    } else if WARNING_PROPS.contains(&prop) {
+        Severity::Warning
+    } else {
+        Severity::Info
+    }
+}
+
+fn is_volatile(prop: &str) -> bool {
+    VOLATILE_PREFIXES.iter().any(|prefix| prop.starts_with(prefix))
+}
+
+/// Properties saved by `--export`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropExport {
+    pub device: String,
+    pub created: String,
+    pub props: BTreeMap<String, String>,
+}
+
+/// A property that differs between an export and the device
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PropDiff {
+    pub prop: String,
+    pub severity: Severity,
+    /// Value in the exported file, `None` if it was not set there
+    pub expected: Option<String>,
+    /// Value on the device, `None` if it is not set
+    pub actual: Option<String>,
+}
+
+impl PropDiff {
+    fn change(&self) -> &'static str {
+        match (&self.expected, &self.actual) {
+            (None, _) => "added",
+            (_, None) => "missing",
+            _ => "changed",
+        }
+    }
+}
+
+impl TableFormat for PropDiff {
+    fn headers() -> Vec<&'static str> {
+        vec!["SEVERITY", "PROPERTY", "EXPECTED", "ACTUAL"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let value = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".to_string());
+        vec![
+            self.severity.as_str().to_string(),
+            self.prop.clone(),
+            value(&self.expected),
+            value(&self.actual),
+        ]
+    }
+
+    fn colored_row(&self) -> Vec<Cell> {
+        let color = match self.severity {
+            Severity::Critical => Color::Red,
+            Severity::Warning => Color::Yellow,
+            Severity::Info => Color::Reset,
+        };
+        let mut cells: Vec<Cell> = self.row().into_iter().map(Cell::new).collect();
+        cells[0] = Cell::new(self.severity.as_str()).fg(color);
+        cells
+    }
+}
+
+impl fmt::Display for PropDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}",
+            self.severity.as_str(),
+            self.change(),
+            self.prop,
+            self.expected.as_deref().unwrap_or(""),
+            self.actual.as_deref().unwrap_or("")
+        )
+    }
+}
+
+/// Differences from `expected` to `actual`, most severe first, volatile properties left out
+pub fn compare_props(expected: &BTreeMap<String, String>, actual: &BTreeMap<String, String>) -> Vec<PropDiff> {
+    let mut names: Vec<&String> = expected.keys().chain(actual.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    let mut diffs: Vec<PropDiff> = names
+        .into_iter()
+        .filter(|prop| !is_volatile(prop))
+        .filter_map(|prop| {
+            let (before, after) = (expected.get(prop), actual.get(prop));
+            (before != after).then(|| PropDiff {
+                prop: prop.clone(),
+                severity: severity(prop),
+                expected: before.cloned(),
+                actual: after.cloned(),
+            })
+        })
+        .collect();
+    // Stable, so properties stay sorted by name within a severity
+    diffs.sort_by_key(|d| std::cmp::Reverse(d.severity));
+    diffs
 }
 
 impl GetpropCommand {
@@ -38,20 +231,103 @@ impl GetpropCommand {
         port: u16,
     ) -> Result<HashMap<String, String>> {
         let port_str = port.to_string();
+        // One dump serves both cases, instead of a connection per property
+        let all = getprop_dump(host, &port_str, Some(device_id)).await?;
         if propnames.is_empty() {
-            // Get all properties
-            let empty_props: Vec<String> = vec![];
-            let props = getprops_parallel(host, &port_str, &empty_props, Some(device_id)).await;
-            Ok(props)
+            Ok(all)
         } else {
-            // Get specific properties
-            let mut props = HashMap::new();
-            for prop in propnames {
-                let value = getprop_async(host, &port_str, &prop, Some(device_id)).await?;
-                props.insert(prop, value);
+            Ok(propnames
+                .into_iter()
+                .map(|prop| {
+                    let value = all.get(&prop).cloned().unwrap_or_default();
+                    (prop, value)
+                })
+                .collect())
+        }
+    }
+
+    /// Look up properties in the local state store without touching the device
+    ///
+    /// Returns the serial of the device they belong to along with them.
+    fn cached_properties(
+        &self,
+        device_arg: Option<&str>,
+        propnames: &[String],
+    ) -> Result<(String, HashMap<String, String>)> {
+        let store = StateStore::load();
+        let query = resolve_device_alias(device_arg);
+        let known = store.resolve(query.as_deref())?;
+
+        let Some(updated) = known.props_updated else {
+            return Err(AimError::CommandExecution(format!(
+                "No cached properties for {}; run getprop while it is connected first",
+                known.serial
+            )));
+        };
+        eprintln!(
+            "{}",
+            format!(
+                "Cached values for {} from {}; may be stale",
+                known.serial,
+                cached_at(updated, chrono::Utc::now())
+            )
+            .yellow()
+        );
+
+        let props = if propnames.is_empty() {
+            known.props.clone().into_iter().collect()
+        } else {
+            // Unset properties read back as empty, same as a live getprop
+            propnames
+                .iter()
+                .map(|p| (p.clone(), known.props.get(p).cloned().unwrap_or_default()))
+                .collect()
+        };
+        Ok((known.serial.clone(), props))
+    }
+
+    /// Print how the properties differ from an export
+    fn compare(
+        &self,
+        ctx: &CommandContext,
+        path: &Path,
+        propnames: &[String],
+        results: HashMap<String, String>,
+        output: OutputType,
+        fail_on: Option<Severity>,
+    ) -> Result<()> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| AimError::InvalidArgument(format!("Cannot read {}: {}", path.display(), e)))?;
+        let export: PropExport = serde_json::from_str(&contents)?;
+        // A live getprop reports unset properties as empty; treat those as absent
+        let mut expected = export.props;
+        expected.retain(|prop, value| !value.is_empty() && (propnames.is_empty() || propnames.contains(prop)));
+        let actual: BTreeMap<String, String> = results.into_iter().filter(|(_, v)| !v.is_empty()).collect();
+        let diffs = compare_props(&expected, &actual);
+
+        let formatter = OutputFormatter::new();
+        match output {
+            OutputType::Json => formatter.json(&diffs)?,
+            OutputType::Plain => formatter.plain(&diffs)?,
+            OutputType::Table if diffs.is_empty() => {
+                if !ctx.quiet {
+                    println!("{} Properties match {} ({})", "✓".green(), path.display(), export.device);
+                }
+            }
+            OutputType::Table => formatter.table(&diffs)?,
+        }
+
+        if let Some(threshold) = fail_on {
+            let failing = diffs.iter().filter(|d| d.severity >= threshold).count();
+            if failing > 0 {
+                return Err(AimError::CommandExecution(format!(
+                    "{} properties differ at {} or above",
+                    failing,
+                    threshold.as_str()
+                )));
             }
-            Ok(props)
         }
+        Ok(())
     }
 }
 
@@ -59,21 +335,42 @@ impl GetpropCommand {
 impl SubCommand for GetpropCommand {
     type Args = GetpropArgs;
 
-    async fn run(&self, _ctx: &CommandContext, args: Self::Args) -> Result<()> {
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let (host, port) = ctx.adb_address();
 
-        // Resolve device from args.device_id (supports aliases and partial matches)
-        let device = get_device(args.device_id.as_deref()).await?;
-        
         // Parse comma-separated property names
         let propnames: Vec<String> = if args.propnames.is_empty() {
             vec![]
         } else {
             args.propnames.split(',').map(|s| s.trim().to_string()).collect()
         };
-        
-        let device_id = device.id.to_string();
-        let results = self.get_properties(&device_id, propnames.clone(), host, port).await?;
+
+        let (device_id, results) = if args.cached {
+            self.cached_properties(args.device_id.as_deref(), &propnames)?
+        } else {
+            // Resolve device from args.device_id (supports aliases and partial matches)
+            let device = get_device(args.device_id.as_deref()).await?;
+            let device_id = device.id.to_string();
+            let results = self.get_properties(&device_id, propnames.clone(), host, port).await?;
+            crate::state::remember_props(&device_id, &results);
+            (device_id, results)
+        };
+
+        if let Some(path) = &args.export {
+            let export = PropExport {
+                device: device_id,
+                created: chrono::Local::now().to_rfc3339(),
+                props: results.into_iter().collect(),
+            };
+            std::fs::write(path, serde_json::to_string_pretty(&export)?)?;
+            if !ctx.quiet {
+                println!("{} Saved {} properties to {}", "✓".green(), export.props.len(), path.display());
+            }
+            return Ok(());
+        }
+        if let Some(path) = &args.compare {
+            return self.compare(ctx, path, &propnames, results, args.output, args.fail_on);
+        }
         
         match args.output {
             OutputType::Plain => {
@@ -93,7 +390,9 @@ impl SubCommand for GetpropCommand {
                 }
             }
             OutputType::Json => {
-                print_colored_json(&results)?;
+                // Sorted keys keep the output diffable between runs
+                let sorted: BTreeMap<_, _> = results.iter().collect();
+                print_colored_json(&sorted)?;
             }
             OutputType::Table => {
                 let mut table = Table::new();
@@ -0,0 +1,79 @@
+use super::getprop::{compare_props, severity, PropDiff, Severity};
+use std::collections::BTreeMap;
+
+fn props(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+#[test]
+fn test_severity() {
+    assert_eq!(severity("ro.build.fingerprint"), Severity::Critical);
+    assert_eq!(severity("ro.build.version.security_patch"), Severity::Critical);
+    assert_eq!(severity("ro.product.cpu.abilist"), Severity::Critical);
+    assert_eq!(severity("ro.product.model"), Severity::Warning);
+    assert_eq!(severity("persist.sys.timezone"), Severity::Info);
+    assert!(Severity::Critical > Severity::Warning && Severity::Warning > Severity::Info);
+}
+
+#[test]
+fn test_compare_props() {
+    let device = props(&[
+        ("ro.build.fingerprint", "google/panther/panther:14/UQ1A/1:user/release-keys"),
+        ("ro.build.version.security_patch", "2024-01-05"),
+        ("ro.product.model", "Pixel 7"),
+        ("persist.sys.timezone", "Europe/Berlin"),
+        ("ro.vendor.radio", "g5300"),
+        ("ro.boottime.init", "1234"),
+    ]);
+    let emulator = props(&[
+        ("ro.build.fingerprint", "google/sdk_gphone64/emu64a:14/UE1A/1:userdebug/dev-keys"),
+        ("ro.build.version.security_patch", "2024-01-05"),
+        ("ro.product.model", "sdk_gphone64_arm64"),
+        ("persist.sys.timezone", "Europe/Berlin"),
+        ("ro.kernel.qemu", "1"),
+        ("ro.boottime.init", "987"),
+    ]);
+
+    let diffs = compare_props(&device, &emulator);
+    assert_eq!(
+        diffs,
+        vec![
+            PropDiff {
+                prop: "ro.build.fingerprint".to_string(),
+                severity: Severity::Critical,
+                expected: Some("google/panther/panther:14/UQ1A/1:user/release-keys".to_string()),
+                actual: Some("google/sdk_gphone64/emu64a:14/UE1A/1:userdebug/dev-keys".to_string()),
+            },
+            PropDiff {
+                prop: "ro.product.model".to_string(),
+                severity: Severity::Warning,
+                expected: Some("Pixel 7".to_string()),
+                actual: Some("sdk_gphone64_arm64".to_string()),
+            },
+            PropDiff {
+                prop: "ro.kernel.qemu".to_string(),
+                severity: Severity::Info,
+                expected: None,
+                actual: Some("1".to_string()),
+            },
+            PropDiff {
+                prop: "ro.vendor.radio".to_string(),
+                severity: Severity::Info,
+                expected: Some("g5300".to_string()),
+                actual: None,
+            },
+        ]
+    );
+    assert!(compare_props(&device, &device).is_empty());
+}
+
+#[test]
+fn test_prop_diff_plain() {
+    let diff = PropDiff {
+        prop: "ro.product.cpu.abi".to_string(),
+        severity: Severity::Critical,
+        expected: Some("arm64-v8a".to_string()),
+        actual: Some("x86_64".to_string()),
+    };
+    assert_eq!(diff.to_string(), "critical\tchanged\tro.product.cpu.abi\tarm64-v8a\tx86_64");
+}
@@ -0,0 +1,327 @@
+use crate::adb::shell::{helpers::shell, ShellBatch};
+use crate::commands::cert::base64_encode;
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::{DeviceId, OutputFormat};
+use crate::error::{AimError, Result};
+use crate::output::TableFormat;
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use clap::Subcommand;
+use colored::*;
+use serde::Serialize;
+use std::time::Duration;
+
+/// IME of ADB Keyboard (https://github.com/senzhk/ADBKeyBoard), which types any text it is sent
+pub const ADB_KEYBOARD: &str = "com.android.adbkeyboard/.AdbIME";
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum ImeCommands {
+    /// List input methods, which are enabled and which is in use
+    #[command(alias = "ls")]
+    List(ListArgs),
+    /// Switch to an input method
+    Set(SetArgs),
+    /// Go back to the default input methods
+    Reset(ResetArgs),
+    /// Type text into the focused field, including non-ASCII text
+    Text(TextArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: ImeCommands) -> Result<()> {
+    match cmd {
+        ImeCommands::List(args) => {
+            let cmd = ListCommand::new();
+            cmd.run(ctx, args).await
+        }
+        ImeCommands::Set(args) => {
+            let cmd = SetCommand::new();
+            cmd.run(ctx, args).await
+        }
+        ImeCommands::Reset(args) => {
+            let cmd = ResetCommand::new();
+            cmd.run(ctx, args).await
+        }
+        ImeCommands::Text(args) => {
+            let cmd = TextCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ListArgs {
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct SetArgs {
+    /// Input method id, or part of one (e.g. `gboard`, `adbkeyboard`)
+    pub ime: String,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ResetArgs {
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct TextArgs {
+    /// Text to type
+    pub text: String,
+
+    /// Type through ADB Keyboard even if the text is plain ASCII
+    #[arg(long)]
+    pub adb_keyboard: bool,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// An input method on the device
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InputMethod {
+    pub id: String,
+    pub enabled: bool,
+    pub current: bool,
+}
+
+impl TableFormat for InputMethod {
+    fn headers() -> Vec<&'static str> {
+        vec!["IME", "ENABLED", "CURRENT"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let yes_no = |b: bool| if b { "yes" } else { "no" }.to_string();
+        vec![self.id.clone(), yes_no(self.enabled), yes_no(self.current)]
+    }
+}
+
+/// Input method ids from `ime list -s` or `ime list -a -s`
+pub fn parse_ime_ids(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.contains('/') && !line.contains(' '))
+        .map(str::to_string)
+        .collect()
+}
+
+/// `settings get secure default_input_method`, which is `null` when unset
+pub fn parse_current(output: &str) -> Option<String> {
+    let current = output.trim();
+    (!current.is_empty() && current != "null").then(|| current.to_string())
+}
+
+/// The input method meant by `query`: an exact id or a unique part of one
+pub fn match_ime(all: &[String], query: &str) -> Result<String> {
+    if let Some(exact) = all.iter().find(|id| id.as_str() == query) {
+        return Ok(exact.clone());
+    }
+    let lower = query.to_lowercase();
+    let matches: Vec<&String> = all.iter().filter(|id| id.to_lowercase().contains(&lower)).collect();
+    match matches.as_slice() {
+        [id] => Ok((*id).clone()),
+        [] => Err(AimError::InvalidArgument(format!(
+            "No input method matches '{}' (see aim ime list)",
+            query
+        ))),
+        _ => Err(AimError::InvalidArgument(format!(
+            "'{}' matches several input methods: {}",
+            query,
+            matches.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
+        ))),
+    }
+}
+
+/// `input text` for ASCII text; it reads `%s` as a space and splits on real ones
+pub fn input_text_command(text: &str) -> String {
+    format!("input text {}", shell_quote(&text.replace(' ', "%s")))
+}
+
+/// Broadcast that makes ADB Keyboard type `text`; base64 keeps any character intact
+pub fn adb_keyboard_command(text: &str) -> String {
+    format!("am broadcast -a ADB_INPUT_B64 --es msg {}", base64_encode(text.as_bytes()))
+}
+
+/// All input methods, the enabled ones and the one in use
+async fn read_imes(host: &str, port: u16, device: &DeviceId) -> Result<(Vec<String>, Vec<String>, Option<String>)> {
+    let outputs = ShellBatch::new()
+        .with_device(device.clone())
+        .command("ime list -a -s")
+        .command("ime list -s")
+        .command("settings get secure default_input_method")
+        .execute(host, port)
+        .await?;
+    let output = |i: usize| outputs.get(i).map(|o| o.stdout.as_str()).unwrap_or_default();
+    Ok((parse_ime_ids(output(0)), parse_ime_ids(output(1)), parse_current(output(2))))
+}
+
+/// Enable and switch to `id`, failing if the device refuses
+async fn switch_to(host: &str, port: u16, device: &DeviceId, id: &str) -> Result<()> {
+    let quoted = shell_quote(id);
+    let out = shell(host, port, device, &format!("ime enable {} 2>&1; ime set {} 2>&1", quoted, quoted)).await?;
+    if out.contains("Unknown input method") || out.contains("Exception") || out.contains("Error") {
+        return Err(AimError::CommandExecution(format!("Could not switch to {}: {}", id, out.trim())));
+    }
+    Ok(())
+}
+
+pub struct ListCommand;
+
+impl ListCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for ListCommand {
+    type Args = ListArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let (all, enabled, current) = read_imes(host, port, &device.id).await?;
+        let imes: Vec<InputMethod> = all
+            .iter()
+            .map(|id| InputMethod {
+                id: id.clone(),
+                enabled: enabled.contains(id),
+                current: current.as_deref() == Some(id.as_str()),
+            })
+            .collect();
+
+        match ctx.output_format {
+            OutputFormat::Json => crate::commands::format_json_output(&imes)?,
+            OutputFormat::Plain => {
+                for ime in &imes {
+                    println!("{}", ime.id);
+                }
+            }
+            OutputFormat::Table => crate::output::OutputFormatter::new().table(&imes)?,
+        }
+        Ok(())
+    }
+}
+
+pub struct SetCommand;
+
+impl SetCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for SetCommand {
+    type Args = SetArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let (all, _, _) = read_imes(host, port, &device.id).await?;
+        let id = match_ime(&all, &args.ime)?;
+        switch_to(host, port, &device.id, &id).await?;
+        if !ctx.quiet {
+            println!("{} Input method set to {}", "✓".green(), id.bright_cyan());
+        }
+        Ok(())
+    }
+}
+
+pub struct ResetCommand;
+
+impl ResetCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for ResetCommand {
+    type Args = ResetArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let out = shell(host, port, &device.id, "ime reset 2>&1").await?;
+        if out.contains("Unknown command") || out.contains("Exception") {
+            return Err(AimError::CommandExecution(format!("ime reset failed: {}", out.trim())));
+        }
+        let current = parse_current(&shell(host, port, &device.id, "settings get secure default_input_method").await?);
+        if !ctx.quiet {
+            println!(
+                "{} Input methods reset, now using {}",
+                "✓".green(),
+                current.as_deref().unwrap_or("(none)").bright_cyan()
+            );
+        }
+        Ok(())
+    }
+}
+
+pub struct TextCommand;
+
+impl TextCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub(crate) async fn type_with_adb_keyboard(&self, host: &str, port: u16, device: &DeviceId, text: &str) -> Result<()> {
+        let (all, _, current) = read_imes(host, port, device).await?;
+        if !all.iter().any(|id| id == ADB_KEYBOARD) {
+            return Err(AimError::CommandExecution(
+                "Typing non-ASCII text needs ADB Keyboard; install it from \
+                 https://github.com/senzhk/ADBKeyBoard and run this again"
+                    .to_string(),
+            ));
+        }
+
+        let previous = current.filter(|id| id != ADB_KEYBOARD);
+        if previous.is_some() {
+            switch_to(host, port, device, ADB_KEYBOARD).await?;
+            // The keyboard only receives broadcasts once it is bound to the field
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        let result = shell(host, port, device, &adb_keyboard_command(text)).await;
+        if let Some(previous) = previous {
+            switch_to(host, port, device, &previous).await?;
+        }
+        let out = result?;
+        if !out.contains("Broadcast completed") {
+            return Err(AimError::CommandExecution(format!("ADB Keyboard did not take the text: {}", out.trim())));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubCommand for TextCommand {
+    type Args = TextArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        if args.text.is_ascii() && !args.adb_keyboard {
+            let out = shell(host, port, &device.id, &format!("{} 2>&1", input_text_command(&args.text))).await?;
+            if out.contains("Exception") || out.contains("Error") {
+                return Err(AimError::CommandExecution(format!("input text failed: {}", out.trim())));
+            }
+        } else {
+            self.type_with_adb_keyboard(host, port, &device.id, &args.text).await?;
+        }
+        if !ctx.quiet {
+            println!("{} Typed {} characters", "✓".green(), args.text.chars().count());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use super::ime::{adb_keyboard_command, input_text_command, match_ime, parse_current, parse_ime_ids};
+
+#[test]
+fn test_parse_ime_ids() {
+    let output = "com.google.android.inputmethod.latin/com.android.inputmethod.latin.LatinIME\n\
+                  com.android.adbkeyboard/.AdbIME\n";
+    assert_eq!(
+        parse_ime_ids(output),
+        vec![
+            "com.google.android.inputmethod.latin/com.android.inputmethod.latin.LatinIME".to_string(),
+            "com.android.adbkeyboard/.AdbIME".to_string(),
+        ]
+    );
+    assert!(parse_ime_ids("").is_empty());
+}
+
+#[test]
+fn test_parse_current() {
+    assert_eq!(parse_current("com.android.adbkeyboard/.AdbIME\n").as_deref(), Some("com.android.adbkeyboard/.AdbIME"));
+    assert_eq!(parse_current("null\n"), None);
+    assert_eq!(parse_current(""), None);
+}
+
+#[test]
+fn test_match_ime() {
+    let all = vec![
+        "com.google.android.inputmethod.latin/com.android.inputmethod.latin.LatinIME".to_string(),
+        "com.android.adbkeyboard/.AdbIME".to_string(),
+    ];
+    assert_eq!(match_ime(&all, "AdbKeyboard").unwrap(), "com.android.adbkeyboard/.AdbIME");
+    assert_eq!(match_ime(&all, "latin").unwrap(), all[0]);
+    assert!(match_ime(&all, "swiftkey").is_err());
+    assert!(match_ime(&all, "com.").is_err());
+}
+
+#[test]
+fn test_input_text_command() {
+    assert_eq!(input_text_command("hello world"), "input text hello%sworld");
+    assert_eq!(input_text_command("it's"), r"input text 'it'\''s'");
+}
+
+#[test]
+fn test_adb_keyboard_command() {
+    // "Grüße" as UTF-8, base64 encoded
+    assert_eq!(adb_keyboard_command("Grüße"), "am broadcast -a ADB_INPUT_B64 --es msg R3LDvMOfZQ==");
+}
@@ -0,0 +1,492 @@
+use crate::adb::file_transfer::FileTransfer;
+use crate::adb::shell::helpers::shell;
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::{DeviceId, OutputFormat};
+use crate::error::{AimError, Result};
+use crate::output::{OutputFormatter, TableFormat};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use clap::Subcommand;
+use colored::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// aapt2 binary name, on the PATH or in the SDK build tools
+const AAPT2: &str = "aapt2";
+
+/// Data URI payloads sent to components that take a scheme
+const URI_PAYLOADS: &[&str] = &[
+    "",
+    "../../../../etc/hosts",
+    "%00%ff%fe",
+    "\"><script>alert(1)</script>",
+    "?q='%20OR%201=1--",
+];
+
+lazy_static! {
+    // "        4b1c2d3 com.example/.MainActivity filter 8a9b0c1"
+    static ref RESOLVER_ENTRY_RE: Regex = Regex::new(r"^\s+[0-9a-f]+ (\S+/\S+) filter [0-9a-f]+").unwrap();
+    // `A: http://schemas.android.com/apk/res/android:name(0x01010003)="com.example.Main" (Raw: ...)`
+    static ref XML_ATTR_RE: Regex = Regex::new(r#"^A: (?:\S*:)?([A-Za-z]+)(?:\(0x[0-9a-f]+\))?=(?:"([^"]*)"|(\S+))"#).unwrap();
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum IntentCommands {
+    /// Send crafted intents to a package's exported components and report crashes
+    Fuzz(FuzzArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: IntentCommands) -> Result<()> {
+    match cmd {
+        IntentCommands::Fuzz(args) => {
+            let cmd = FuzzCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct FuzzArgs {
+    /// Package to fuzz (supports partial matching)
+    pub package: String,
+
+    /// Find components in the APK's manifest (needs aapt2) instead of the intent resolver
+    #[arg(long)]
+    pub from_manifest: bool,
+
+    /// Time to wait after each intent before checking for crashes
+    #[arg(long, default_value = "1s", value_parser = crate::utils::parse_duration)]
+    pub delay: Duration,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentKind {
+    Activity,
+    Receiver,
+}
+
+/// An exported component and what its intent filters accept
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Target {
+    pub kind: ComponentKind,
+    pub component: String,
+    pub actions: BTreeSet<String>,
+    pub schemes: BTreeSet<String>,
+}
+
+impl Target {
+    fn new(kind: ComponentKind, component: String) -> Self {
+        Self {
+            kind,
+            component,
+            actions: BTreeSet::new(),
+            schemes: BTreeSet::new(),
+        }
+    }
+}
+
+/// A crash from the crash log buffer
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Crash {
+    pub process: String,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub component: String,
+    pub intent: String,
+    pub process: String,
+    pub crash: String,
+}
+
+impl TableFormat for Finding {
+    fn headers() -> Vec<&'static str> {
+        vec!["COMPONENT", "INTENT", "CRASH"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.component.clone(), self.intent.clone(), self.crash.clone()]
+    }
+}
+
+fn quoted(line: &str, key: &str) -> Option<String> {
+    let rest = line.trim().strip_prefix(key)?.trim();
+    Some(rest.trim_matches('"').to_string())
+}
+
+/// Components of `package` in the activity and receiver resolver tables of `dumpsys package`
+pub fn parse_resolver_tables(output: &str, package: &str) -> Vec<Target> {
+    let mut targets: Vec<Target> = Vec::new();
+    let mut kind = None;
+    let mut current: Option<usize> = None;
+    for line in output.lines().filter(|l| !l.trim().is_empty()) {
+        if !line.starts_with(' ') {
+            kind = match line.trim() {
+                "Activity Resolver Table:" => Some(ComponentKind::Activity),
+                "Receiver Resolver Table:" => Some(ComponentKind::Receiver),
+                _ => None,
+            };
+            current = None;
+            continue;
+        }
+        let Some(kind) = kind else { continue };
+        if let Some(caps) = RESOLVER_ENTRY_RE.captures(line) {
+            let component = caps[1].to_string();
+            current = if component.starts_with(&format!("{}/", package)) {
+                let index = match targets.iter().position(|t| t.kind == kind && t.component == component) {
+                    Some(index) => index,
+                    None => {
+                        targets.push(Target::new(kind, component));
+                        targets.len() - 1
+                    }
+                };
+                Some(index)
+            } else {
+                None
+            };
+        } else if let Some(index) = current {
+            if let Some(action) = quoted(line, "Action:") {
+                targets[index].actions.insert(action);
+            } else if let Some(scheme) = quoted(line, "Scheme:") {
+                targets[index].schemes.insert(scheme);
+            }
+        }
+    }
+    targets.sort();
+    targets
+}
+
+/// Exported activities and receivers from `aapt2 dump xmltree --file AndroidManifest.xml`
+///
+/// A component is exported when it says so, or when it has an intent filter
+/// and does not say otherwise (the rule before Android 12).
+pub fn parse_manifest_tree(output: &str) -> Vec<Target> {
+    struct Element {
+        depth: usize,
+        name: String,
+    }
+    let is_component = |name: &str| matches!(name, "activity" | "activity-alias" | "receiver");
+
+    let mut package = String::new();
+    let mut stack: Vec<Element> = Vec::new();
+    let mut components: Vec<(Target, Option<bool>, bool)> = Vec::new();
+    for line in output.lines() {
+        let depth = line.len() - line.trim_start().len();
+        let line = line.trim();
+        if let Some(element) = line.strip_prefix("E: ") {
+            while stack.last().is_some_and(|e| e.depth >= depth) {
+                stack.pop();
+            }
+            let name = element.split_whitespace().next().unwrap_or_default().to_string();
+            match name.as_str() {
+                "activity" | "activity-alias" => {
+                    components.push((Target::new(ComponentKind::Activity, String::new()), None, false))
+                }
+                "receiver" => components.push((Target::new(ComponentKind::Receiver, String::new()), None, false)),
+                "intent-filter" if stack.last().is_some_and(|e| is_component(&e.name)) => {
+                    if let Some(last) = components.last_mut() {
+                        last.2 = true;
+                    }
+                }
+                _ => {}
+            }
+            stack.push(Element { depth, name });
+            continue;
+        }
+        let Some(caps) = XML_ATTR_RE.captures(line) else { continue };
+        let attr = &caps[1];
+        let value = caps.get(2).or_else(|| caps.get(3)).map(|m| m.as_str()).unwrap_or_default();
+        // Attributes are indented under their element
+        while stack.last().is_some_and(|e| e.depth >= depth) {
+            stack.pop();
+        }
+        let element = stack.last().map(|e| e.name.as_str()).unwrap_or_default();
+        let in_component = stack.iter().any(|e| is_component(&e.name));
+        match (element, attr) {
+            ("manifest", "package") => package = value.to_string(),
+            ("activity" | "activity-alias" | "receiver", "name") => {
+                if let Some(last) = components.last_mut() {
+                    last.0.component = value.to_string();
+                }
+            }
+            ("activity" | "activity-alias" | "receiver", "exported") => {
+                if let Some(last) = components.last_mut() {
+                    last.1 = Some(value == "true" || value == "0xffffffff");
+                }
+            }
+            ("action", "name") if in_component => {
+                if let Some(last) = components.last_mut() {
+                    last.0.actions.insert(value.to_string());
+                }
+            }
+            ("data", "scheme") if in_component => {
+                if let Some(last) = components.last_mut() {
+                    last.0.schemes.insert(value.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut targets: Vec<Target> = components
+        .into_iter()
+        .filter(|(target, exported, has_filter)| !target.component.is_empty() && exported.unwrap_or(*has_filter))
+        .map(|(mut target, _, _)| {
+            let class = match target.component.strip_prefix('.') {
+                Some(relative) => format!("{}.{}", package, relative),
+                None if !target.component.contains('.') => format!("{}.{}", package, target.component),
+                None => target.component.clone(),
+            };
+            target.component = format!("{}/{}", package, class);
+            target
+        })
+        .collect();
+    targets.sort();
+    targets
+}
+
+/// `am` arguments for the intents sent to a target, after the component
+pub fn crafted_intents(target: &Target) -> Vec<Vec<String>> {
+    let long = "A".repeat(4096);
+    let mut intents: Vec<Vec<String>> = vec![
+        vec![],
+        vec!["--esn".into(), "url".into(), "--esn".into(), "data".into()],
+        vec!["--es".into(), "url".into(), long.clone(), "--ei".into(), "id".into(), "-1".into()],
+        vec!["--ez".into(), "debug".into(), "true".into(), "--el".into(), "id".into(), i64::MIN.to_string()],
+    ];
+    for action in &target.actions {
+        intents.push(vec!["-a".into(), action.clone()]);
+        intents.push(vec!["-a".into(), action.clone(), "--es".into(), "android.intent.extra.TEXT".into(), long.clone()]);
+    }
+    for scheme in &target.schemes {
+        let action = if target.actions.contains("android.intent.action.VIEW") || target.actions.is_empty() {
+            "android.intent.action.VIEW".to_string()
+        } else {
+            target.actions.iter().next().cloned().unwrap_or_default()
+        };
+        for payload in URI_PAYLOADS {
+            intents.push(vec!["-a".into(), action.clone(), "-d".into(), format!("{}://aim-fuzz/{}", scheme, payload)]);
+        }
+        intents.push(vec!["-a".into(), action.clone(), "-d".into(), format!("{}://{}", scheme, long)]);
+    }
+    intents
+}
+
+/// Crashes in the output of `logcat -b crash -d -v raw`
+pub fn parse_crashes(output: &str) -> Vec<Crash> {
+    let lines: Vec<&str> = output.lines().map(str::trim).collect();
+    let mut crashes = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with("FATAL EXCEPTION") {
+            // FATAL EXCEPTION: main / Process: com.example, PID: 1234 / java.lang.Exception: ...
+            let process = lines
+                .get(i + 1)
+                .and_then(|l| l.strip_prefix("Process: "))
+                .and_then(|l| l.split(',').next())
+                .unwrap_or_default();
+            let summary = lines.get(i + 2).copied().unwrap_or_default();
+            crashes.push(Crash {
+                process: process.to_string(),
+                summary: summary.to_string(),
+            });
+        } else if let Some((_, rest)) = line.split_once(">>> ") {
+            // pid: 1234, tid: 1234, name: main  >>> com.example <<< / signal 11 (SIGSEGV), ...
+            let process = rest.split(" <<<").next().unwrap_or_default();
+            let signal = lines[i + 1..]
+                .iter()
+                .take(4)
+                .find(|l| l.starts_with("signal "))
+                .map(|l| l.split(", fault").next().unwrap_or(l))
+                .unwrap_or("native crash");
+            crashes.push(Crash {
+                process: process.to_string(),
+                summary: signal.to_string(),
+            });
+        }
+    }
+    crashes
+}
+
+/// aapt2 from the PATH or the newest SDK build tools
+pub fn find_aapt2(path: Option<&std::ffi::OsStr>, sdk: Option<&std::ffi::OsStr>) -> Option<PathBuf> {
+    let file = format!("{}{}", AAPT2, std::env::consts::EXE_SUFFIX);
+    let on_path = path.and_then(|p| std::env::split_paths(p).map(|dir| dir.join(&file)).find(|c| c.is_file()));
+    on_path.or_else(|| {
+        let mut versions: Vec<PathBuf> = std::fs::read_dir(Path::new(sdk?).join("build-tools"))
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        versions.sort();
+        versions.into_iter().rev().map(|dir| dir.join(&file)).find(|c| c.is_file())
+    })
+}
+
+pub struct FuzzCommand;
+
+impl FuzzCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn targets_from_manifest(&self, host: &str, port: u16, device: &DeviceId, package: &str) -> Result<Vec<Target>> {
+        let sdk = std::env::var_os("ANDROID_HOME").or_else(|| std::env::var_os("ANDROID_SDK_ROOT"));
+        let aapt2 = find_aapt2(std::env::var_os("PATH").as_deref(), sdk.as_deref()).ok_or_else(|| {
+            AimError::CommandExecution(
+                "--from-manifest needs aapt2 on the PATH or in $ANDROID_HOME/build-tools".to_string(),
+            )
+        })?;
+
+        let paths = shell(host, port, device, &format!("pm path {}", shell_quote(package))).await?;
+        let base = paths
+            .lines()
+            .filter_map(|l| l.trim().strip_prefix("package:"))
+            .find(|p| p.ends_with("/base.apk"))
+            .or_else(|| paths.lines().find_map(|l| l.trim().strip_prefix("package:")))
+            .ok_or_else(|| AimError::CommandExecution(format!("No APK found for {}", package)))?;
+
+        let local = std::env::temp_dir().join(format!("aim-fuzz-{}-{}.apk", std::process::id(), package));
+        let mut transfer = FileTransfer::new(host, port, Some(device)).await?;
+        transfer.pull(base, &local).await?;
+        let output = std::process::Command::new(&aapt2)
+            .args(["dump", "xmltree", "--file", "AndroidManifest.xml"])
+            .arg(&local)
+            .output();
+        let _ = std::fs::remove_file(&local);
+        let output = output.map_err(|e| AimError::CommandExecution(format!("Could not run {}: {}", aapt2.display(), e)))?;
+        if !output.status.success() {
+            return Err(AimError::CommandExecution(format!(
+                "aapt2 failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(parse_manifest_tree(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Short description of an intent for the report, with long values shortened
+    fn describe(extras: &[String]) -> String {
+        if extras.is_empty() {
+            return "(empty)".to_string();
+        }
+        extras
+            .iter()
+            .map(|arg| match arg.char_indices().nth(40) {
+                Some((cut, _)) => format!("{}...({} chars)", &arg[..cut], arg.chars().count()),
+                None => arg.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[async_trait]
+impl SubCommand for FuzzCommand {
+    type Args = FuzzArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let ctx = ctx.clone().with_device(device.clone());
+        let (host, port) = ctx.adb_address();
+        let package = crate::commands::app::PackageResolver::new(&ctx).resolve(&args.package).await?;
+
+        let targets = if args.from_manifest {
+            self.targets_from_manifest(host, port, &device.id, &package).await?
+        } else {
+            let dump = shell(host, port, &device.id, &format!("dumpsys package {}", shell_quote(&package))).await?;
+            parse_resolver_tables(&dump, &package)
+        };
+        if targets.is_empty() {
+            return Err(AimError::CommandExecution(format!(
+                "No exported activities or receivers found in {}{}",
+                package,
+                if args.from_manifest { "" } else { " (try --from-manifest)" }
+            )));
+        }
+
+        let show_progress = ctx.output_format != OutputFormat::Json && !ctx.quiet;
+        if show_progress {
+            println!(
+                "Fuzzing {} exported component{} of {}",
+                targets.len(),
+                if targets.len() == 1 { "" } else { "s" },
+                package.bright_cyan()
+            );
+        }
+
+        shell(host, port, &device.id, "logcat -b crash -c").await?;
+        let mut findings = Vec::new();
+        let mut sent = 0;
+        for target in &targets {
+            let intents = crafted_intents(target);
+            if show_progress {
+                println!("  {} ({} intents)", target.component, intents.len());
+            }
+            for extras in intents {
+                let verb = match target.kind {
+                    ComponentKind::Activity => "am start",
+                    ComponentKind::Receiver => "am broadcast",
+                };
+                let command = format!(
+                    "{} -n {} {} 2>&1",
+                    verb,
+                    shell_quote(&target.component),
+                    extras.iter().map(|a| shell_quote(a)).collect::<Vec<_>>().join(" ")
+                );
+                shell(host, port, &device.id, &command).await?;
+                sent += 1;
+                tokio::time::sleep(args.delay).await;
+
+                let crash_log = shell(host, port, &device.id, "logcat -b crash -d -v raw").await?;
+                let crashes = parse_crashes(&crash_log);
+                if !crashes.is_empty() {
+                    shell(host, port, &device.id, "logcat -b crash -c").await?;
+                }
+                for crash in crashes {
+                    if show_progress {
+                        println!("    {} {}: {}", "✗".red(), crash.process, crash.summary);
+                    }
+                    findings.push(Finding {
+                        component: target.component.clone(),
+                        intent: Self::describe(&extras),
+                        process: crash.process,
+                        crash: crash.summary,
+                    });
+                }
+            }
+            shell(host, port, &device.id, &format!("am force-stop {}", shell_quote(&package))).await?;
+        }
+
+        let formatter = OutputFormatter::new();
+        match ctx.output_format {
+            OutputFormat::Json => formatter.json(&findings)?,
+            _ if ctx.quiet => {}
+            _ => {
+                println!();
+                if findings.is_empty() {
+                    println!("{} No crashes after {} intents", "✓".green(), sent);
+                } else {
+                    formatter.table(&findings)?;
+                }
+            }
+        }
+        if !findings.is_empty() {
+            return Err(AimError::CommandExecution(format!(
+                "{} crash{} after {} intents",
+                findings.len(),
+                if findings.len() == 1 { "" } else { "es" },
+                sent
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use super::intent::{crafted_intents, parse_crashes, parse_manifest_tree, parse_resolver_tables, ComponentKind};
+
+const DUMPSYS: &str = "Activity Resolver Table:
+  Non-Data Actions:
+      android.intent.action.MAIN:
+        4b1c2d3 com.example/.MainActivity filter 8a9b0c1
+          Action: \"android.intent.action.MAIN\"
+          Category: \"android.intent.category.LAUNCHER\"
+
+  Schemes:
+      myapp:
+        5c2d3e4 com.example/.DeepLinkActivity filter 9b0c1d2
+          Action: \"android.intent.action.VIEW\"
+          Category: \"android.intent.category.BROWSABLE\"
+          Scheme: \"myapp\"
+          Scheme: \"https\"
+
+Receiver Resolver Table:
+  Non-Data Actions:
+      com.example.REFRESH:
+        6d3e4f5 com.example/.RefreshReceiver filter 0c1d2e3
+          Action: \"com.example.REFRESH\"
+
+Service Resolver Table:
+  Non-Data Actions:
+      com.example.SYNC:
+        7e4f506 com.example/.SyncService filter 1d2e3f4
+          Action: \"com.example.SYNC\"
+";
+
+const MANIFEST_TREE: &str = "N: android=http://schemas.android.com/apk/res/android (line=2)
+  E: manifest (line=2)
+    A: package=\"com.example\" (Raw: \"com.example\")
+    E: application (line=10)
+      E: activity (line=12)
+        A: http://schemas.android.com/apk/res/android:name(0x01010003)=\".MainActivity\" (Raw: \".MainActivity\")
+        A: http://schemas.android.com/apk/res/android:exported(0x01010010)=true
+        E: intent-filter (line=14)
+          E: action (line=15)
+            A: http://schemas.android.com/apk/res/android:name(0x01010003)=\"android.intent.action.MAIN\" (Raw: \"android.intent.action.MAIN\")
+      E: activity (line=20)
+        A: http://schemas.android.com/apk/res/android:name(0x01010003)=\"com.example.SettingsActivity\" (Raw: \"com.example.SettingsActivity\")
+      E: activity (line=24)
+        A: http://schemas.android.com/apk/res/android:name(0x01010003)=\"com.example.LinkActivity\" (Raw: \"com.example.LinkActivity\")
+        E: intent-filter (line=25)
+          E: action (line=26)
+            A: http://schemas.android.com/apk/res/android:name(0x01010003)=\"android.intent.action.VIEW\" (Raw: \"android.intent.action.VIEW\")
+          E: data (line=27)
+            A: http://schemas.android.com/apk/res/android:scheme(0x01010027)=\"myapp\" (Raw: \"myapp\")
+      E: receiver (line=30)
+        A: http://schemas.android.com/apk/res/android:name(0x01010003)=\".Private\" (Raw: \".Private\")
+        A: http://schemas.android.com/apk/res/android:exported(0x01010010)=false
+        E: intent-filter (line=31)
+          E: action (line=32)
+            A: http://schemas.android.com/apk/res/android:name(0x01010003)=\"com.example.PRIVATE\" (Raw: \"com.example.PRIVATE\")
+      E: service (line=35)
+        A: http://schemas.android.com/apk/res/android:name(0x01010003)=\".SyncService\" (Raw: \".SyncService\")
+        E: intent-filter (line=36)
+          E: action (line=37)
+            A: http://schemas.android.com/apk/res/android:name(0x01010003)=\"com.example.SYNC\" (Raw: \"com.example.SYNC\")
+";
+
+#[test]
+fn test_parse_resolver_tables() {
+    let targets = parse_resolver_tables(DUMPSYS, "com.example");
+    let components: Vec<(ComponentKind, &str)> = targets.iter().map(|t| (t.kind, t.component.as_str())).collect();
+    assert_eq!(
+        components,
+        vec![
+            (ComponentKind::Activity, "com.example/.DeepLinkActivity"),
+            (ComponentKind::Activity, "com.example/.MainActivity"),
+            (ComponentKind::Receiver, "com.example/.RefreshReceiver"),
+        ]
+    );
+    assert!(targets[0].schemes.contains("myapp") && targets[0].schemes.contains("https"));
+    assert!(targets[0].actions.contains("android.intent.action.VIEW"));
+    assert!(targets[2].actions.contains("com.example.REFRESH"));
+    assert!(parse_resolver_tables(DUMPSYS, "com.other").is_empty());
+}
+
+#[test]
+fn test_parse_manifest_tree() {
+    let targets = parse_manifest_tree(MANIFEST_TREE);
+    let components: Vec<&str> = targets.iter().map(|t| t.component.as_str()).collect();
+    // SettingsActivity has no filter, Private is not exported and services are not fuzzed
+    assert_eq!(
+        components,
+        vec!["com.example/com.example.LinkActivity", "com.example/com.example.MainActivity"]
+    );
+    assert!(targets[0].schemes.contains("myapp"));
+    assert!(targets[1].actions.contains("android.intent.action.MAIN"));
+}
+
+#[test]
+fn test_crafted_intents() {
+    let targets = parse_resolver_tables(DUMPSYS, "com.example");
+    let deep_link = crafted_intents(&targets[0]);
+    assert!(deep_link.contains(&vec![]));
+    assert!(deep_link.iter().any(|i| i.len() == 4 && i[3] == "myapp://aim-fuzz/../../../../etc/hosts"));
+    assert!(deep_link.iter().any(|i| i.len() == 4 && i[3].starts_with("https://AAAA")));
+    let receiver = crafted_intents(&targets[2]);
+    assert!(receiver.contains(&vec!["-a".to_string(), "com.example.REFRESH".to_string()]));
+    assert!(!receiver.iter().any(|i| i.contains(&"-d".to_string())));
+}
+
+#[test]
+fn test_parse_crashes() {
+    let log = "--------- beginning of crash
+FATAL EXCEPTION: main
+Process: com.example, PID: 4321
+java.lang.NullPointerException: Attempt to invoke virtual method on a null object reference
+\tat com.example.DeepLinkActivity.onCreate(DeepLinkActivity.java:42)
+*** *** *** *** *** *** *** *** *** *** *** *** *** *** *** ***
+pid: 5555, tid: 5555, name: example:worker  >>> com.example:worker <<<
+uid: 10154
+signal 11 (SIGSEGV), code 1 (SEGV_MAPERR), fault addr 0x0
+";
+    let crashes = parse_crashes(log);
+    assert_eq!(crashes.len(), 2);
+    assert_eq!(crashes[0].process, "com.example");
+    assert!(crashes[0].summary.starts_with("java.lang.NullPointerException"));
+    assert_eq!(crashes[1].process, "com.example:worker");
+    assert_eq!(crashes[1].summary, "signal 11 (SIGSEGV), code 1 (SEGV_MAPERR)");
+    assert!(parse_crashes("--------- beginning of crash\n").is_empty());
+}
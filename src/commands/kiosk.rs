@@ -0,0 +1,146 @@
+use crate::adb::shell::helpers::shell;
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::DeviceId;
+use crate::error::{AimError, Result};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use colored::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::time::Duration;
+
+/// Argument that unpins instead of pinning a package
+pub const OFF: &str = "off";
+
+lazy_static! {
+    // "  * Recent #0: Task{4b2f0d1 #215 type=standard A=10154:com.example.app U=0 visible=true ...}"
+    static ref TASK_ID_RE: Regex = Regex::new(r"Task\{\S+ #(\d+)").unwrap();
+}
+
+pub struct KioskCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct KioskArgs {
+    /// Package to pin (supports partial matching), or `off` to unpin
+    pub package: String,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// Id of the most recent task belonging to `package`, from `dumpsys activity recents`
+pub fn parse_task_id(recents: &str, package: &str) -> Option<u32> {
+    let activity_prefix = format!("{}/", package);
+    recents
+        .lines()
+        .filter(|line| line.contains("Task{"))
+        .find(|line| {
+            line.split_whitespace().any(|token| {
+                let token = token.trim_end_matches('}');
+                token.strip_prefix("A=").and_then(|a| a.split_once(':')).is_some_and(|(_, affinity)| affinity == package)
+                    || token.strip_prefix("I=").is_some_and(|i| i.starts_with(&activity_prefix))
+            })
+        })
+        .and_then(|line| TASK_ID_RE.captures(line)?[1].parse().ok())
+}
+
+/// Lock task state (`NONE`, `PINNED` or `LOCKED`) from `dumpsys activity activities`
+pub fn parse_lock_state(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let (_, state) = line.split_once("mLockTaskModeState=")?;
+        state.split_whitespace().next().map(str::to_string)
+    })
+}
+
+async fn lock_state(host: &str, port: u16, device: &DeviceId) -> Result<Option<String>> {
+    let out = shell(host, port, device, "dumpsys activity activities | grep mLockTaskModeState").await?;
+    Ok(parse_lock_state(&out))
+}
+
+impl KioskCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn pin(&self, ctx: &CommandContext, partial: &str) -> Result<()> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        let package = super::app::PackageResolver::new(ctx).resolve(partial).await?;
+
+        let resolve = format!("cmd package resolve-activity --brief {} | tail -n 1", shell_quote(&package));
+        let activity = shell(host, port, &device.id, &resolve).await?.trim().to_string();
+        if !activity.contains('/') {
+            return Err(AimError::CommandExecution(format!("{} has no launcher activity to pin", package)));
+        }
+        let started = shell(host, port, &device.id, &format!("am start -W -n {} 2>&1", shell_quote(&activity))).await?;
+        if started.contains("Error") {
+            return Err(AimError::CommandExecution(format!("Failed to start {}: {}", package, started.trim())));
+        }
+
+        let recents = shell(host, port, &device.id, "dumpsys activity recents").await?;
+        let task = parse_task_id(&recents, &package)
+            .ok_or_else(|| AimError::CommandExecution(format!("Could not find the task of {}", package)))?;
+        let locked = shell(host, port, &device.id, &format!("am task lock {} 2>&1", task)).await?;
+
+        // Lock task mode is entered asynchronously
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        match lock_state(host, port, &device.id).await?.as_deref() {
+            Some("NONE") | None => Err(AimError::CommandExecution(format!(
+                "Could not pin {}{}. Pin it by hand instead: enable Settings > Security > App pinning, \
+                 then pin the app from Recents.",
+                package,
+                if locked.trim().is_empty() { String::new() } else { format!(" ({})", locked.trim()) }
+            ))),
+            Some(state) => {
+                if !ctx.quiet {
+                    println!(
+                        "{} {} pinned on {} ({})",
+                        "✓".green(),
+                        package.bright_cyan(),
+                        device.id,
+                        state.to_lowercase()
+                    );
+                    println!("Run `aim kiosk off` to unpin");
+                }
+                Ok(())
+            }
+        }
+    }
+
+    async fn unpin(&self, ctx: &CommandContext) -> Result<()> {
+        let device = ctx.require_device()?;
+        let (host, port) = ctx.adb_address();
+        shell(host, port, &device.id, "am task lock stop 2>&1").await?;
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        match lock_state(host, port, &device.id).await?.as_deref() {
+            Some("NONE") | None => {
+                if !ctx.quiet {
+                    println!("{} {} unpinned", "✓".green(), device.id);
+                }
+                Ok(())
+            }
+            Some("LOCKED") => Err(AimError::CommandExecution(
+                "The device is in lock task mode set by its device owner app, which has to end it".to_string(),
+            )),
+            Some(state) => Err(AimError::CommandExecution(format!("Could not unpin, lock task mode is {}", state))),
+        }
+    }
+}
+
+#[async_trait]
+impl SubCommand for KioskCommand {
+    type Args = KioskArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let ctx = ctx.clone().with_device(device);
+        if args.package.eq_ignore_ascii_case(OFF) {
+            self.unpin(&ctx).await
+        } else {
+            self.pin(&ctx, &args.package).await
+        }
+    }
+}
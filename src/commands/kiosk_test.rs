@@ -0,0 +1,25 @@
+use super::kiosk::{parse_lock_state, parse_task_id};
+
+const RECENTS: &str = "\
+ACTIVITY MANAGER RECENT TASKS (dumpsys activity recents)
+  Recent tasks:
+  * Recent #0: Task{4b2f0d1 #215 type=standard A=10154:com.example.demo U=0 visible=true mode=fullscreen}
+    userId=0 effectiveUid=u0a154 mCallingUid=2000 mUserSetupComplete=true
+  * Recent #1: Task{8c1e2f3 #212 type=standard A=10154:com.example.demo.settings U=0 visible=false}
+  * Recent #2: Task{1a2b3c4 #198 type=home I=com.google.android.apps.nexuslauncher/.NexusLauncherActivity U=0}
+";
+
+#[test]
+fn test_parse_task_id() {
+    assert_eq!(parse_task_id(RECENTS, "com.example.demo"), Some(215));
+    assert_eq!(parse_task_id(RECENTS, "com.example.demo.settings"), Some(212));
+    assert_eq!(parse_task_id(RECENTS, "com.google.android.apps.nexuslauncher"), Some(198));
+    assert_eq!(parse_task_id(RECENTS, "com.example"), None);
+}
+
+#[test]
+fn test_parse_lock_state() {
+    assert_eq!(parse_lock_state("  mLockTaskModeState=PINNED mLockTaskModeTasks=[]\n").as_deref(), Some("PINNED"));
+    assert_eq!(parse_lock_state("mLockTaskModeState=NONE\n").as_deref(), Some("NONE"));
+    assert_eq!(parse_lock_state(""), None);
+}
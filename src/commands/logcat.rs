@@ -0,0 +1,160 @@
+use crate::adb::shell::ShellCommand;
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::error::{AimError, Result};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use colored::*;
+use log::debug;
+use std::io::Write;
+
+pub struct LogcatCommand;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Priority {
+    #[value(alias = "v")]
+    Verbose,
+    #[value(alias = "d")]
+    Debug,
+    #[value(alias = "i")]
+    Info,
+    #[value(alias = "w")]
+    Warn,
+    #[value(alias = "e")]
+    Error,
+    #[value(alias = "f")]
+    Fatal,
+}
+
+impl Priority {
+    /// The letter logcat uses in filter specs and log lines
+    pub fn letter(self) -> char {
+        match self {
+            Priority::Verbose => 'V',
+            Priority::Debug => 'D',
+            Priority::Info => 'I',
+            Priority::Warn => 'W',
+            Priority::Error => 'E',
+            Priority::Fatal => 'F',
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogcatArgs {
+    pub tags: Vec<String>,
+    pub priority: Priority,
+    pub clear: bool,
+    pub device_id: Option<String>,
+}
+
+/// The logcat invocation for the given tags and minimum priority
+///
+/// With tags, everything else is silenced (`*:S`); a tag that already carries
+/// a priority (`ActivityManager:W`) keeps it.
+pub fn logcat_command(tags: &[String], priority: Priority) -> String {
+    let mut command = "logcat -v threadtime".to_string();
+    if tags.is_empty() {
+        command.push_str(&format!(" '*:{}'", priority.letter()));
+        return command;
+    }
+    for tag in tags {
+        let spec = if tag.contains(':') {
+            tag.clone()
+        } else {
+            format!("{}:{}", tag, priority.letter())
+        };
+        command.push(' ');
+        command.push_str(&shell_quote(&spec));
+    }
+    command.push_str(" '*:S'");
+    command
+}
+
+/// Priority letter of a `threadtime` line: `date time pid tid P tag: message`
+pub fn line_priority(line: &str) -> Option<char> {
+    let field = line.split_whitespace().nth(4)?;
+    let mut chars = field.chars();
+    let letter = chars.next()?;
+    (chars.next().is_none() && "VDIWEF".contains(letter)).then_some(letter)
+}
+
+/// Collects streamed chunks and hands back whole lines
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    pending: String,
+}
+
+impl LineBuffer {
+    /// Add a chunk and return the lines it completed
+    pub fn push(&mut self, chunk: &str) -> Vec<String> {
+        self.pending.push_str(chunk);
+        let Some(end) = self.pending.rfind('\n') else {
+            return Vec::new();
+        };
+        let rest = self.pending.split_off(end + 1);
+        let complete = std::mem::replace(&mut self.pending, rest);
+        complete.lines().map(|l| l.trim_end_matches('\r').to_string()).collect()
+    }
+}
+
+fn colorize(line: &str) -> ColoredString {
+    match line_priority(line) {
+        Some('V') => line.dimmed(),
+        Some('D') => line.cyan(),
+        Some('W') => line.yellow(),
+        Some('E') => line.red(),
+        Some('F') => line.bright_red().bold(),
+        _ => line.normal(),
+    }
+}
+
+impl LogcatCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for LogcatCommand {
+    type Args = LogcatArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        if args.clear {
+            let out = ShellCommand::new("logcat -c 2>&1")
+                .with_device(device.id.clone())
+                .execute(host, port)
+                .await?;
+            if !out.stdout.trim().is_empty() {
+                return Err(AimError::CommandExecution(format!("logcat -c failed: {}", out.stdout.trim())));
+            }
+            if !ctx.quiet {
+                eprintln!("{}", "-- log buffers cleared --".dimmed());
+            }
+        }
+
+        let command = ShellCommand::new(logcat_command(&args.tags, args.priority)).with_device(device.id.clone());
+        let mut lines = LineBuffer::default();
+        let stream = command.execute_streaming(host, port, move |chunk| {
+            let mut stdout = std::io::stdout().lock();
+            for line in lines.push(chunk) {
+                let _ = writeln!(stdout, "{}", colorize(&line));
+            }
+            let _ = stdout.flush();
+        });
+
+        tokio::select! {
+            result = stream => {
+                if let Err(e) = result {
+                    debug!("logcat stream ended: {}", e);
+                }
+                eprintln!("{}", format!("-- logcat on {} ended --", device.id).dimmed());
+            }
+            _ = tokio::signal::ctrl_c() => {}
+        }
+        Ok(())
+    }
+}
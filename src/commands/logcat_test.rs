@@ -0,0 +1,34 @@
+use super::logcat::{line_priority, logcat_command, LineBuffer, Priority};
+
+#[test]
+fn test_logcat_command() {
+    assert_eq!(logcat_command(&[], Priority::Verbose), "logcat -v threadtime '*:V'");
+    assert_eq!(logcat_command(&[], Priority::Warn), "logcat -v threadtime '*:W'");
+    let tags = vec!["ActivityManager".to_string(), "MyApp:D".to_string()];
+    assert_eq!(
+        logcat_command(&tags, Priority::Info),
+        "logcat -v threadtime ActivityManager:I MyApp:D '*:S'"
+    );
+}
+
+#[test]
+fn test_line_priority() {
+    let line = "05-01 10:15:02.500  1234  1250 W ActivityManager: Slow operation";
+    assert_eq!(line_priority(line), Some('W'));
+    let line = "05-01 10:15:02.501  4321  4321 E AndroidRuntime: FATAL EXCEPTION: main";
+    assert_eq!(line_priority(line), Some('E'));
+    assert_eq!(line_priority("--------- beginning of main"), None);
+    assert_eq!(line_priority(""), None);
+}
+
+#[test]
+fn test_line_buffer() {
+    let mut buffer = LineBuffer::default();
+    assert!(buffer.push("05-01 10:15:02.500  1234").is_empty());
+    assert_eq!(
+        buffer.push("  1250 I Tag: one\r\nsecond line\nthi"),
+        vec!["05-01 10:15:02.500  1234  1250 I Tag: one".to_string(), "second line".to_string()]
+    );
+    assert_eq!(buffer.push("rd\n"), vec!["third".to_string()]);
+    assert!(buffer.push("").is_empty());
+}
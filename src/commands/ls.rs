@@ -1,30 +1,473 @@
+use crate::adb::server::AdbServer;
 use crate::commands::SubCommand;
 use crate::config::Config;
 use crate::core::context::CommandContext;
-use crate::core::types::OutputFormat;
+use crate::core::types::{Device, DeviceState, OutputFormat};
+use crate::device::extras::{fetch_extras, DeviceExtras, ExtraFields};
 use crate::device::DeviceManager;
-use crate::error::Result;
-use crate::output::OutputFormatter;
+use crate::error::{AimError, Result};
+use crate::output::device::{parse_device_state, Connection, DeviceFormatter, DeviceSort};
+use crate::output::{OutputFormatter, PlainFormat, TableFormat};
+use crate::state::{cached_at, KnownDevice, StateStore};
+use crate::utils::parse_duration;
 use async_trait::async_trait;
+use colored::*;
+use comfy_table::{Cell, Color};
 use log::{debug, info};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
-pub struct LsCommand {
-    device_manager: DeviceManager,
-}
+/// How long a device that just appeared stays highlighted in watch mode
+const WATCH_NEW_FOR: Duration = Duration::from_secs(10);
+/// How long a disconnected device stays listed in watch mode
+const WATCH_GONE_FOR: Duration = Duration::from_secs(30);
+
+pub struct LsCommand;
 
 #[derive(Debug, Clone, clap::Args)]
 pub struct LsArgs {
     /// Output format
     #[clap(short, long, value_parser = ["table", "json", "plain"], default_value = "table")]
     pub output: String,
+
+    /// Include every device seen before, not just connected ones
+    #[clap(long)]
+    pub known: bool,
+
+    /// List devices from the local cache without querying adb
+    #[clap(long)]
+    pub cached: bool,
+
+    /// Long listing with battery, Android version, security patch and Wi-Fi IP
+    #[clap(short, long)]
+    pub long: bool,
+
+    /// Extra columns for the long listing (implies --long)
+    #[clap(long, value_enum, value_delimiter = ',')]
+    pub columns: Vec<LsColumn>,
+
+    /// Only show devices in these states (device, unauthorized, offline)
+    #[clap(long, value_parser = parse_device_state, value_delimiter = ',')]
+    pub state: Vec<DeviceState>,
+
+    /// Only show devices connected over USB
+    #[clap(long, conflicts_with = "wireless")]
+    pub usb: bool,
+
+    /// Only show devices connected over the network
+    #[clap(long)]
+    pub wireless: bool,
+
+    /// Sort devices by this field
+    #[clap(long, value_enum)]
+    pub sort: Option<DeviceSort>,
+
+    /// Keep the table on screen, redrawn whenever devices change
+    #[clap(short, long, conflicts_with_all = ["known", "cached", "long", "columns"])]
+    pub watch: bool,
+
+    /// How often to redraw in watch mode when nothing changes
+    #[clap(long, value_parser = parse_duration, default_value = "2s")]
+    pub interval: Duration,
+}
+
+/// Extra column in the long device listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LsColumn {
+    Battery,
+    Android,
+    Patch,
+    Wifi,
+}
+
+impl LsColumn {
+    pub const ALL: [LsColumn; 4] = [LsColumn::Battery, LsColumn::Android, LsColumn::Patch, LsColumn::Wifi];
+
+    fn header(&self) -> &'static str {
+        match self {
+            LsColumn::Battery => "BATTERY",
+            LsColumn::Android => "ANDROID",
+            LsColumn::Patch => "PATCH",
+            LsColumn::Wifi => "WIFI IP",
+        }
+    }
+
+    /// What has to be fetched to fill these columns
+    pub fn fields(columns: &[LsColumn]) -> ExtraFields {
+        ExtraFields {
+            battery: columns.contains(&LsColumn::Battery),
+            build: columns.contains(&LsColumn::Android) || columns.contains(&LsColumn::Patch),
+            wifi: columns.contains(&LsColumn::Wifi),
+        }
+    }
+
+    pub fn value(&self, extras: &DeviceExtras) -> String {
+        let value = match self {
+            LsColumn::Battery => extras.battery.map(|b| format!("{}%", b)),
+            LsColumn::Android => extras.android.as_ref().map(|release| match extras.sdk {
+                Some(sdk) => format!("{} (SDK {})", release, sdk),
+                None => release.clone(),
+            }),
+            LsColumn::Patch => extras.security_patch.clone(),
+            LsColumn::Wifi => extras.wifi_ip.clone(),
+        };
+        value.unwrap_or_else(|| "-".to_string())
+    }
+}
+
+/// A connected device with the details of the long listing
+#[derive(Debug, Clone, Serialize)]
+pub struct LongDeviceRow {
+    #[serde(flatten)]
+    pub device: Device,
+    #[serde(flatten)]
+    pub extras: DeviceExtras,
+    /// Why details are missing, for a device that can't be queried
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<&'static str>,
+}
+
+impl LongDeviceRow {
+    pub fn new(device: Device, extras: DeviceExtras) -> Self {
+        let note = DeviceFormatter::state_note(device.state);
+        Self { device, extras, note }
+    }
+}
+
+/// How a row is highlighted in watch mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchMark {
+    /// Connected since a recent refresh
+    New,
+    /// Disconnected recently, kept so the change is visible
+    Gone,
+}
+
+/// A device in the watch table
+#[derive(Debug, Clone)]
+pub struct WatchRow {
+    pub device: Device,
+    pub mark: Option<WatchMark>,
+}
+
+/// Tracks which devices came and went between refreshes of `ls --watch`
+#[derive(Debug, Default)]
+pub struct DeviceWatch {
+    current: Vec<Device>,
+    appeared: HashMap<String, Instant>,
+    gone: BTreeMap<String, (Device, Instant)>,
+    started: bool,
+}
+
+impl DeviceWatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the current device list
+    ///
+    /// The first list is the baseline, so nothing in it counts as new.
+    pub fn update(&mut self, devices: Vec<Device>, now: Instant) {
+        let before: HashSet<String> = self.current.iter().map(|d| d.id.to_string()).collect();
+        let after: HashSet<String> = devices.iter().map(|d| d.id.to_string()).collect();
+
+        if self.started {
+            for id in after.difference(&before) {
+                self.appeared.insert(id.clone(), now);
+                self.gone.remove(id);
+            }
+        }
+        for device in self.current.drain(..) {
+            let id = device.id.to_string();
+            if !after.contains(&id) {
+                self.appeared.remove(&id);
+                self.gone.insert(id, (device, now));
+            }
+        }
+
+        self.current = devices;
+        self.started = true;
+        self.appeared.retain(|_, at| now.duration_since(*at) < WATCH_NEW_FOR);
+        self.gone.retain(|_, (_, at)| now.duration_since(*at) < WATCH_GONE_FOR);
+    }
+
+    /// Connected devices in list order, then recently disconnected ones
+    pub fn rows(&self, now: Instant) -> Vec<WatchRow> {
+        let connected = self.current.iter().map(|device| {
+            let is_new = self
+                .appeared
+                .get(device.id.as_str())
+                .is_some_and(|at| now.duration_since(*at) < WATCH_NEW_FOR);
+            WatchRow {
+                device: device.clone(),
+                mark: is_new.then_some(WatchMark::New),
+            }
+        });
+        let gone = self
+            .gone
+            .values()
+            .filter(|(_, at)| now.duration_since(*at) < WATCH_GONE_FOR)
+            .map(|(device, _)| WatchRow {
+                device: device.clone(),
+                mark: Some(WatchMark::Gone),
+            });
+        connected.chain(gone).collect()
+    }
+}
+
+/// A device from the state store, with whether it is connected right now
+#[derive(Debug, Clone, Serialize)]
+pub struct KnownDeviceRow {
+    #[serde(flatten)]
+    pub device: KnownDevice,
+    pub state: Option<String>,
+}
+
+impl TableFormat for KnownDeviceRow {
+    fn headers() -> Vec<&'static str> {
+        vec!["ALIAS", "DEVICE ID", "STATE", "MODEL", "LAST SEEN", "WIRELESS"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.device.aliases.join(","),
+            self.device.serial.clone(),
+            self.state.clone().unwrap_or_else(|| "-".to_string()),
+            self.device.model.clone().unwrap_or_default(),
+            crate::output::time::format_time(self.device.last_seen, None),
+            self.device.wireless_ip.clone().unwrap_or_default(),
+        ]
+    }
+
+    fn colored_row(&self) -> Vec<Cell> {
+        let color = match self.state.as_deref() {
+            Some("device") => Color::Green,
+            Some(_) => Color::Yellow,
+            None => Color::DarkGrey,
+        };
+        self.row()
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| if i == 2 { Cell::new(value).fg(color) } else { Cell::new(value) })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for KnownDeviceRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.device.serial)
+    }
 }
 
 impl LsCommand {
     pub fn new() -> Self {
-        Self { 
-            device_manager: DeviceManager::new()
+        Self
+    }
+
+    fn device_manager(ctx: &CommandContext) -> DeviceManager {
+        let (host, port) = ctx.adb_address();
+        DeviceManager::with_address(host, port.to_string())
+    }
+
+    /// Every device in the state store, marked with its state if connected
+    fn known_rows(store: &StateStore, connected: &[Device]) -> Vec<KnownDeviceRow> {
+        let mut rows: Vec<KnownDeviceRow> = store
+            .devices
+            .values()
+            .map(|known| KnownDeviceRow {
+                device: known.clone(),
+                state: connected
+                    .iter()
+                    .find(|d| d.id.as_str() == known.serial)
+                    .map(|d| d.state.to_string()),
+            })
+            .collect();
+        // Connected first, then most recently seen
+        rows.sort_by(|a, b| {
+            b.state
+                .is_some()
+                .cmp(&a.state.is_some())
+                .then_with(|| b.device.last_seen.cmp(&a.device.last_seen))
+                .then_with(|| a.device.serial.cmp(&b.device.serial))
+        });
+        rows
+    }
+
+    fn print_long(rows: &[LongDeviceRow], columns: &[LsColumn], output_format: OutputFormat) -> Result<()> {
+        let formatter = OutputFormatter::new();
+        match output_format {
+            OutputFormat::Json => formatter.json(&rows)?,
+            OutputFormat::Plain => {
+                for row in rows {
+                    let mut fields = vec![row.device.plain()];
+                    fields.extend(columns.iter().map(|c| c.value(&row.extras)));
+                    println!("{}", fields.join("\t"));
+                }
+            }
+            OutputFormat::Table => {
+                // Only when some device needs explaining, so the usual listing stays narrow
+                let noted = rows.iter().any(|row| row.note.is_some());
+                let mut headers: Vec<String> = Device::headers().into_iter().map(String::from).collect();
+                headers.extend(columns.iter().map(|c| c.header().to_string()));
+                if noted {
+                    headers.push("NOTE".to_string());
+                }
+                let table_rows = rows
+                    .iter()
+                    .map(|row| {
+                        let mut cells = row.device.colored_row();
+                        cells.extend(columns.iter().map(|c| Cell::new(c.value(&row.extras))));
+                        if noted {
+                            cells.push(Cell::new(row.note.unwrap_or_default()).fg(Color::DarkGrey));
+                        }
+                        cells
+                    })
+                    .collect();
+                formatter.dynamic_table(&headers, table_rows)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Connected devices with aliases, filtered and sorted as requested
+    async fn list(&self, ctx: &CommandContext, config: &Config, args: &LsArgs) -> Result<Vec<Device>> {
+        let mut devices = Self::device_manager(ctx).list_devices().await?;
+        if crate::core::ci::is_enabled() {
+            devices.sort_by_key(|d| d.id.to_string());
+        }
+        for device in &mut devices {
+            if let Some(name) = config.get_device_name(&device.id.to_string()) {
+                device.alias = Some(name);
+            }
         }
+
+        let connection = if args.usb {
+            Some(Connection::Usb)
+        } else if args.wireless {
+            Some(Connection::Wireless)
+        } else {
+            None
+        };
+        let mut devices = DeviceFormatter::filter(devices, &args.state, connection);
+        if let Some(sort) = args.sort {
+            DeviceFormatter::sort(&mut devices, sort);
+        }
+        Ok(devices)
+    }
+
+    fn draw_watch(rows: &[WatchRow], interval: Duration) -> Result<()> {
+        let mut headers = vec![String::new()];
+        headers.extend(Device::headers().into_iter().map(String::from));
+        let table_rows = rows
+            .iter()
+            .map(|row| {
+                let mut cells = match row.mark {
+                    Some(WatchMark::Gone) => {
+                        let mut values = row.device.row();
+                        values[2] = "gone".to_string();
+                        values.into_iter().map(|v| Cell::new(v).fg(Color::DarkGrey)).collect()
+                    }
+                    _ => row.device.colored_row(),
+                };
+                let marker = match row.mark {
+                    Some(WatchMark::New) => Cell::new("+").fg(Color::Green),
+                    Some(WatchMark::Gone) => Cell::new("-").fg(Color::DarkGrey),
+                    None => Cell::new(""),
+                };
+                cells.insert(0, marker);
+                cells
+            })
+            .collect();
+
+        // Clear screen and move the cursor home
+        print!("\x1B[2J\x1B[H");
+        println!(
+            "{}",
+            format!(
+                "{} device(s) at {}, refreshing on changes or every {}, Ctrl-C to stop",
+                rows.iter().filter(|r| r.mark != Some(WatchMark::Gone)).count(),
+                chrono::Local::now().format("%H:%M:%S"),
+                crate::utils::format_duration(interval)
+            )
+            .dimmed()
+        );
+        OutputFormatter::new().dynamic_table(&headers, table_rows)?;
+        if let Some(hint) = DeviceFormatter::unauthorized_hint(
+            &rows
+                .iter()
+                .filter(|r| r.mark != Some(WatchMark::Gone))
+                .map(|r| r.device.clone())
+                .collect::<Vec<_>>(),
+        ) {
+            println!("{}", hint.yellow());
+        }
+        Ok(())
+    }
+
+    /// Redraw the table on every device tracking event, or every interval
+    ///
+    /// Tracking runs on its own thread since the connection blocks; if it
+    /// cannot be opened or drops, the interval alone drives the refreshes.
+    async fn watch(&self, ctx: &CommandContext, config: &Config, args: &LsArgs) -> Result<()> {
+        crate::core::ci::require_interactive("ls --watch")?;
+
+        let (host, port) = ctx.adb_address();
+        let (tx, mut events) = tokio::sync::mpsc::unbounded_channel();
+        let mut tracking = match AdbServer::track_devices(host, port).await {
+            Ok(mut conn) => {
+                conn.stream().set_read_timeout(None)?;
+                std::thread::spawn(move || {
+                    // Each message is the full device list; it only signals a change here
+                    while AdbServer::read_string(&mut conn).is_ok() {
+                        if tx.send(()).is_err() {
+                            break;
+                        }
+                    }
+                });
+                true
+            }
+            Err(e) => {
+                debug!("Device tracking unavailable, polling instead: {}", e);
+                false
+            }
+        };
+
+        let mut watch = DeviceWatch::new();
+        loop {
+            match self.list(ctx, config, args).await {
+                Ok(devices) => {
+                    let now = Instant::now();
+                    watch.update(devices, now);
+                    Self::draw_watch(&watch.rows(now), args.interval)?;
+                }
+                Err(e) => debug!("Listing devices failed: {}", e),
+            }
+
+            tokio::select! {
+                event = events.recv(), if tracking => {
+                    if event.is_none() {
+                        debug!("Device tracking ended, polling instead");
+                        tracking = false;
+                    }
+                }
+                _ = tokio::time::sleep(args.interval) => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn print_known(rows: &[KnownDeviceRow], output_format: OutputFormat) -> Result<()> {
+        let formatter = OutputFormatter::new();
+        match output_format {
+            OutputFormat::Table => formatter.table(rows)?,
+            OutputFormat::Json => formatter.json(&rows)?,
+            OutputFormat::Plain => formatter.plain(rows)?,
+        }
+        Ok(())
     }
 }
 
@@ -32,44 +475,91 @@ impl LsCommand {
 impl SubCommand for LsCommand {
     type Args = LsArgs;
 
-    async fn run(&self, _ctx: &CommandContext, args: Self::Args) -> Result<()> {
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
         debug!("LsCommand::run() called with args: {:?}", args);
 
-        // Get list of devices
-        debug!("Listing devices...");
-        let mut devices = self.device_manager.list_devices().await?;
-        debug!("Found {} devices", devices.len());
+        // Parse output format
+        let output_format = OutputFormat::from_str(&args.output)
+            .unwrap_or(OutputFormat::Table);
 
-        if devices.is_empty() {
-            info!("No devices found");
-        } else {
-            info!("Found {} device(s)", devices.len());
+        if args.cached {
+            let store = StateStore::load();
+            let rows = Self::known_rows(&store, &[]);
+            if let Some(latest) = rows.iter().map(|r| r.device.last_seen).max() {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "Cached device list, last updated {}; connection state not checked",
+                        cached_at(latest, chrono::Utc::now())
+                    )
+                    .yellow()
+                );
+            }
+            return Self::print_known(&rows, output_format);
         }
 
-        // Load config and apply aliases
+        // Load config for aliases
         let config_path = dirs::home_dir()
             .map(|p| p.join(".config/aim/config.toml"))
             .unwrap_or_else(|| PathBuf::from(".config/aim/config.toml"));
         let config = Config::load_from_path(&config_path);
 
-        for device in &mut devices {
-            if let Some(name) = config.get_device_name(&device.id.to_string()) {
-                device.alias = Some(name);
+        if args.watch {
+            if output_format != OutputFormat::Table {
+                return Err(AimError::InvalidArgument("--watch only works with table output".to_string()));
             }
+            return self.watch(ctx, &config, &args).await;
         }
 
-        // Parse output format
-        let output_format = OutputFormat::from_str(&args.output)
-            .unwrap_or(OutputFormat::Table);
+        if args.known {
+            // Unfiltered, so every connected device is marked as such
+            let devices = Self::device_manager(ctx).list_devices().await?;
+            // list_devices() has already recorded the connected devices
+            let rows = Self::known_rows(&StateStore::load(), &devices);
+            return Self::print_known(&rows, output_format);
+        }
 
-        // Create formatter
-        let formatter = OutputFormatter::new();
+        // Get list of devices
+        debug!("Listing devices...");
+        let devices = self.list(ctx, &config, &args).await?;
+        if devices.is_empty() {
+            info!("No devices found");
+        } else {
+            info!("Found {} device(s)", devices.len());
+        }
+        let hint = DeviceFormatter::unauthorized_hint(&devices);
 
-        // Format and display
-        match output_format {
-            OutputFormat::Table => formatter.table(&devices)?,
-            OutputFormat::Json => formatter.json(&devices)?,
-            OutputFormat::Plain => formatter.plain(&devices)?,
+        if args.long || !args.columns.is_empty() {
+            let columns = if args.columns.is_empty() {
+                LsColumn::ALL.to_vec()
+            } else {
+                args.columns.clone()
+            };
+            let (host, port) = ctx.adb_address();
+            let mut extras = fetch_extras(host, port, &devices, LsColumn::fields(&columns)).await;
+            let rows: Vec<LongDeviceRow> = devices
+                .into_iter()
+                .map(|device| {
+                    let extras = extras.remove(device.id.as_str()).unwrap_or_default();
+                    LongDeviceRow::new(device, extras)
+                })
+                .collect();
+            Self::print_long(&rows, &columns, output_format)?;
+        } else {
+            // Create formatter
+            let formatter = OutputFormatter::new();
+
+            // Format and display
+            match output_format {
+                OutputFormat::Table => formatter.table(&devices)?,
+                OutputFormat::Json => formatter.json(&devices)?,
+                OutputFormat::Plain => formatter.plain(&devices)?,
+            }
+        }
+
+        // Only alongside the table, so JSON and plain output stay parseable
+        if let (OutputFormat::Table, Some(hint)) = (output_format, hint) {
+            eprintln!("{}", hint.yellow());
         }
 
         Ok(())
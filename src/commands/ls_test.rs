@@ -0,0 +1,65 @@
+use super::ls::{DeviceWatch, WatchMark};
+use crate::testing::test_device;
+use std::time::{Duration, Instant};
+
+fn marks(watch: &DeviceWatch, now: Instant) -> Vec<(String, Option<WatchMark>)> {
+    watch
+        .rows(now)
+        .into_iter()
+        .map(|row| (row.device.id.to_string(), row.mark))
+        .collect()
+}
+
+#[test]
+fn test_watch_baseline_is_not_new() {
+    let start = Instant::now();
+    let mut watch = DeviceWatch::new();
+    watch.update(vec![test_device("abc"), test_device("def")], start);
+    assert_eq!(
+        marks(&watch, start),
+        vec![("abc".to_string(), None), ("def".to_string(), None)]
+    );
+}
+
+#[test]
+fn test_watch_marks_new_and_gone() {
+    let start = Instant::now();
+    let mut watch = DeviceWatch::new();
+    watch.update(vec![test_device("abc"), test_device("def")], start);
+
+    let later = start + Duration::from_secs(1);
+    watch.update(vec![test_device("abc"), test_device("xyz")], later);
+    assert_eq!(
+        marks(&watch, later),
+        vec![
+            ("abc".to_string(), None),
+            ("xyz".to_string(), Some(WatchMark::New)),
+            ("def".to_string(), Some(WatchMark::Gone)),
+        ]
+    );
+
+    // Highlights fade and disconnected devices drop off after a while
+    let much_later = later + Duration::from_secs(11);
+    assert_eq!(
+        marks(&watch, much_later),
+        vec![
+            ("abc".to_string(), None),
+            ("xyz".to_string(), None),
+            ("def".to_string(), Some(WatchMark::Gone)),
+        ]
+    );
+    watch.update(vec![test_device("abc"), test_device("xyz")], later + Duration::from_secs(31));
+    assert_eq!(marks(&watch, later + Duration::from_secs(31)).len(), 2);
+}
+
+#[test]
+fn test_watch_device_coming_back() {
+    let start = Instant::now();
+    let mut watch = DeviceWatch::new();
+    watch.update(vec![test_device("abc")], start);
+    watch.update(Vec::new(), start + Duration::from_secs(1));
+
+    let back = start + Duration::from_secs(2);
+    watch.update(vec![test_device("abc")], back);
+    assert_eq!(marks(&watch, back), vec![("abc".to_string(), Some(WatchMark::New))]);
+}
@@ -55,6 +55,7 @@ pub trait OutputCommand {
 /// Default JSON formatting implementation
 #[allow(dead_code)]
 pub fn format_json_output<T: serde::Serialize>(output: &T) -> Result<()> {
+    let _span = crate::core::profile::span(crate::core::profile::Phase::Render);
     // Use the existing utility function
     crate::utils::print_colored_json(output)?;
     Ok(())
@@ -64,11 +65,14 @@ pub fn format_json_output<T: serde::Serialize>(output: &T) -> Result<()> {
 ///
 /// If the provided ID matches a device alias (e.g., "p10"), returns the
 /// corresponding device key (e.g., "510") that can be used for partial matching.
+/// When no ID is given, `AIM_DEVICE` or `ANDROID_SERIAL` is used instead.
 pub fn resolve_device_alias(device_id: Option<&str>) -> Option<String> {
     use crate::config::Config;
     use std::path::PathBuf;
 
-    let id = device_id?;
+    let id = device_id
+        .map(str::to_string)
+        .or_else(crate::device::manager::device_from_env)?;
 
     // Load config and check if this is an alias
     let config_path = dirs::home_dir()
@@ -79,7 +83,7 @@ pub fn resolve_device_alias(device_id: Option<&str>) -> Option<String> {
     // Check if any device config has this name as an alias
     for (device_key, device_config) in &config.devices {
         if let Some(name) = &device_config.name {
-            if name.eq_ignore_ascii_case(id) {
+            if name.eq_ignore_ascii_case(&id) {
                 // Return the device key (partial ID) instead of the alias
                 return Some(device_key.clone());
             }
@@ -87,7 +91,7 @@ pub fn resolve_device_alias(device_id: Option<&str>) -> Option<String> {
     }
 
     // Not an alias, return as-is
-    Some(id.to_string())
+    Some(id)
 }
 
 /// Helper for device selection in commands - supports aliases and partial IDs
@@ -101,7 +105,9 @@ pub async fn get_device(
     // Resolve alias first
     let resolved_id = resolve_device_alias(device_arg);
 
-    device_manager.get_target_device(resolved_id.as_deref()).await
+    let device = device_manager.get_target_device(resolved_id.as_deref()).await?;
+    log::debug!(device = device.id.to_string().as_str(); "Selected device");
+    Ok(device)
 }
 
 /// Helper for device selection in commands
@@ -143,6 +149,7 @@ pub async fn select_device(
 
 /// Module re-exports
 pub mod app;
+pub mod fs;
 pub mod runner;
 
 // Individual command modules
@@ -163,6 +170,135 @@ pub mod screenrecord;
 pub mod push;
 pub mod pull;
 pub mod shell;
+pub mod tombstone;
+pub mod battery;
+pub mod boot;
+pub mod soak;
+pub mod wifi;
+pub mod kiosk;
+pub mod dpm;
+pub mod cert;
+pub mod provision;
+pub mod intent;
+pub mod activity;
+pub mod broadcasts;
+pub mod a11y;
+pub mod ime;
+pub mod ui;
+pub mod logcat;
+pub mod discover;
+pub mod clean;
+pub mod fleet;
+pub mod dev;
+pub mod forward;
+pub mod sync;
+pub mod reconnect;
+pub mod pair;
+pub mod plugin;
+pub mod script;
+pub mod send_text;
+pub mod run_script;
+pub mod dash;
+
+#[cfg(test)]
+mod adb_test;
+
+#[cfg(test)]
+mod tombstone_test;
+
+#[cfg(test)]
+mod battery_test;
+
+#[cfg(test)]
+mod clean_test;
+
+#[cfg(test)]
+mod fleet_test;
+
+#[cfg(test)]
+mod sync_test;
+
+#[cfg(test)]
+mod push_test;
+
+#[cfg(test)]
+mod pull_test;
+
+#[cfg(test)]
+mod forward_test;
+
+#[cfg(test)]
+mod dev_test;
+
+#[cfg(test)]
+mod reconnect_test;
+
+#[cfg(test)]
+mod pair_test;
+
+#[cfg(test)]
+mod ls_test;
+
+#[cfg(test)]
+mod server_test;
+
+#[cfg(test)]
+mod plugin_test;
+
+#[cfg(test)]
+mod script_test;
+
+#[cfg(test)]
+mod run_script_test;
+
+#[cfg(test)]
+mod dash_test;
+
+#[cfg(test)]
+mod screenshot_test;
+
+#[cfg(test)]
+mod screenrecord_test;
+
+#[cfg(test)]
+mod perfetto_test;
+
+#[cfg(test)]
+mod boot_test;
+
+#[cfg(test)]
+mod soak_test;
+
+#[cfg(test)]
+mod wifi_test;
+
+#[cfg(test)]
+mod kiosk_test;
+
+#[cfg(test)]
+mod dpm_test;
+#[cfg(test)]
+mod cert_test;
+#[cfg(test)]
+mod provision_test;
+#[cfg(test)]
+mod intent_test;
+#[cfg(test)]
+mod activity_test;
+#[cfg(test)]
+mod broadcasts_test;
+#[cfg(test)]
+mod a11y_test;
+#[cfg(test)]
+mod ime_test;
+#[cfg(test)]
+mod ui_test;
+#[cfg(test)]
+mod logcat_test;
+#[cfg(test)]
+mod getprop_test;
+#[cfg(test)]
+mod send_text_test;
 
 // Tests for commands are in individual *_test.rs files
 // Currently: config_test.rs, device_info_test.rs, hash_test.rs, protocol_test.rs
@@ -0,0 +1,194 @@
+use crate::adb::server::AdbServer;
+use crate::commands::reconnect::{parse_mdns_services, MdnsService};
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::error::{AimError, Result};
+use crate::utils::parse_duration;
+use async_trait::async_trait;
+use colored::*;
+use qrcode::render::unicode;
+use qrcode::QrCode;
+use rand::{distr::Alphanumeric, Rng};
+use std::time::{Duration, Instant};
+
+const PAIRING_SERVICE: &str = "_adb-tls-pairing._tcp";
+const CONNECT_SERVICE: &str = "_adb-tls-connect._tcp";
+/// How often mDNS services are checked while waiting for the phone
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// How long to wait for the connect service to show up after pairing
+const CONNECT_WAIT: Duration = Duration::from_secs(15);
+
+pub struct PairCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct PairArgs {
+    /// Pairing address shown on the phone (ip:port)
+    #[clap(conflicts_with = "qr", required_unless_present = "qr")]
+    pub address: Option<String>,
+
+    /// Six-digit pairing code shown on the phone
+    #[clap(requires = "address")]
+    pub code: Option<String>,
+
+    /// Show a QR code to scan from "Pair device with QR code" instead
+    #[clap(long)]
+    pub qr: bool,
+
+    /// How long to wait for the phone to scan the code
+    #[clap(long, value_parser = parse_duration, default_value = "2m")]
+    pub wait: Duration,
+}
+
+/// Text encoded in the QR code, in the format the Android settings app expects
+pub fn qr_payload(name: &str, password: &str) -> String {
+    format!("WIFI:T:ADB;S:{};P:{};;", name, password)
+}
+
+/// The pairing service the phone advertises after scanning our code
+pub fn find_pairing_service<'a>(services: &'a [MdnsService], name: &str) -> Option<&'a MdnsService> {
+    services
+        .iter()
+        .find(|s| s.service_type == PAIRING_SERVICE && s.name == name)
+}
+
+/// The connect service of the device reachable at `ip`
+pub fn find_connect_service<'a>(services: &'a [MdnsService], ip: &str) -> Option<&'a MdnsService> {
+    services
+        .iter()
+        .find(|s| s.service_type == CONNECT_SERVICE && s.address.rsplit_once(':').is_some_and(|(host, _)| host == ip))
+}
+
+/// Check a pairing address is `host:port`; the server only answers a vague failure otherwise
+pub fn parse_pair_address(address: &str) -> Result<String> {
+    let address = address.trim();
+    let valid = address
+        .rsplit_once(':')
+        .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok_and(|p| p != 0));
+    if !valid {
+        return Err(AimError::InvalidArgument(format!(
+            "'{}' is not a pairing address; use the ip:port shown under \"Pair device with pairing code\"",
+            address
+        )));
+    }
+    Ok(address.to_string())
+}
+
+/// Normalize a pairing code, accepting it grouped as shown (`482 913`, `482-913`)
+pub fn parse_pair_code(code: &str) -> Result<String> {
+    let digits: String = code.chars().filter(|c| !c.is_whitespace() && *c != '-').collect();
+    if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(AimError::InvalidArgument(format!("'{}' is not a six-digit pairing code", code.trim())));
+    }
+    Ok(digits)
+}
+
+fn random_token(len: usize) -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+impl PairCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn render_qr(payload: &str) -> Result<String> {
+        let code = QrCode::new(payload).map_err(|e| AimError::Other(format!("Cannot create QR code: {}", e)))?;
+        // Inverted so the code reads as dark-on-light on dark terminals
+        Ok(code
+            .render::<unicode::Dense1x2>()
+            .dark_color(unicode::Dense1x2::Light)
+            .light_color(unicode::Dense1x2::Dark)
+            .build())
+    }
+
+    /// Poll mDNS until `find` matches or the deadline passes
+    async fn wait_for_service<F>(ctx: &CommandContext, deadline: Instant, find: F) -> Result<Option<MdnsService>>
+    where
+        F: Fn(&[MdnsService]) -> Option<&MdnsService> + Send,
+    {
+        let (host, port) = ctx.adb_address();
+        while Instant::now() < deadline {
+            let services = parse_mdns_services(&AdbServer::mdns_services(host, port).await?);
+            if let Some(service) = find(&services) {
+                return Ok(Some(service.clone()));
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                _ = tokio::signal::ctrl_c() => return Ok(None),
+            }
+        }
+        Ok(None)
+    }
+
+    async fn pair(ctx: &CommandContext, address: &str, code: &str) -> Result<()> {
+        let (host, port) = ctx.adb_address();
+        let reply = AdbServer::pair(host, port, code, address).await?;
+        if !reply.starts_with("Successfully paired") {
+            return Err(AimError::CommandExecution(format!("Pairing with {} failed: {}", address, reply.trim())));
+        }
+        println!("{} {}", "✓".green(), reply.trim());
+        Ok(())
+    }
+
+    /// Connect to the freshly paired device, which advertises its own port
+    async fn connect(ctx: &CommandContext, ip: &str) -> Result<()> {
+        let (host, port) = ctx.adb_address();
+        let deadline = Instant::now() + CONNECT_WAIT;
+        let Some(service) = Self::wait_for_service(ctx, deadline, |s| find_connect_service(s, ip)).await? else {
+            println!("Paired, but {} is not advertising a connection yet; run `aim reconnect` later", ip);
+            return Ok(());
+        };
+        let reply = AdbServer::connect(host, port, &service.address).await?;
+        if reply.starts_with("connected") || reply.starts_with("already connected") {
+            println!("{} {}", "✓".green(), reply.trim());
+            Ok(())
+        } else {
+            Err(AimError::CommandExecution(reply.trim().to_string()))
+        }
+    }
+}
+
+#[async_trait]
+impl SubCommand for PairCommand {
+    type Args = PairArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        if !args.qr {
+            let address = parse_pair_address(args.address.as_deref().unwrap_or_default())?;
+            let address = address.as_str();
+            let code = match &args.code {
+                Some(code) => code.clone(),
+                None => {
+                    crate::core::ci::require_interactive("Prompting for a pairing code")?;
+                    eprint!("Pairing code: ");
+                    let mut line = String::new();
+                    std::io::stdin().read_line(&mut line)?;
+                    line.trim().to_string()
+                }
+            };
+            Self::pair(ctx, address, &parse_pair_code(&code)?).await?;
+            let ip = address.rsplit_once(':').map_or(address, |(host, _)| host);
+            return Self::connect(ctx, ip).await;
+        }
+
+        crate::core::ci::require_interactive("QR code pairing")?;
+        let name = format!("aim-{}", random_token(6));
+        let password = random_token(10);
+        println!("{}", Self::render_qr(&qr_payload(&name, &password))?);
+        println!("On the device open Developer options > Wireless debugging > Pair device with QR code");
+        println!("and scan the code above. Waiting up to {}...", crate::utils::format_duration(args.wait));
+
+        let deadline = Instant::now() + args.wait;
+        let Some(service) = Self::wait_for_service(ctx, deadline, |s| find_pairing_service(s, &name)).await? else {
+            return Err(AimError::CommandExecution("No device scanned the QR code".to_string()));
+        };
+
+        Self::pair(ctx, &service.address, &password).await?;
+        let ip = service.address.rsplit_once(':').map_or(service.address.as_str(), |(host, _)| host);
+        Self::connect(ctx, ip).await
+    }
+}
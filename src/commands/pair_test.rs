@@ -0,0 +1,51 @@
+use super::pair::{find_connect_service, find_pairing_service, parse_pair_address, parse_pair_code, qr_payload};
+use super::reconnect::parse_mdns_services;
+
+const MDNS: &str = "aim-Xy12Ab\t_adb-tls-pairing._tcp.\t192.168.1.20:37099\n\
+adb-R58M123ABC-vWgJpq\t_adb-tls-connect._tcp.\t192.168.1.20:41235\n\
+adb-ZY22XYZ-abcd\t_adb-tls-connect._tcp.\t192.168.1.31:40001\n";
+
+#[test]
+fn test_qr_payload() {
+    assert_eq!(qr_payload("aim-Xy12Ab", "s3cretPass"), "WIFI:T:ADB;S:aim-Xy12Ab;P:s3cretPass;;");
+}
+
+#[test]
+fn test_find_services() {
+    let services = parse_mdns_services(MDNS);
+    assert_eq!(
+        find_pairing_service(&services, "aim-Xy12Ab").unwrap().address,
+        "192.168.1.20:37099"
+    );
+    assert_eq!(find_pairing_service(&services, "aim-other"), None);
+
+    assert_eq!(
+        find_connect_service(&services, "192.168.1.20").unwrap().address,
+        "192.168.1.20:41235"
+    );
+    // Hosts are compared whole, not by prefix
+    assert_eq!(find_connect_service(&services, "192.168.1.2"), None);
+}
+
+#[test]
+fn test_parse_pair_address() {
+    assert_eq!(parse_pair_address("192.168.1.20:37099").unwrap(), "192.168.1.20:37099");
+    assert_eq!(parse_pair_address(" pixel.lan:40123 ").unwrap(), "pixel.lan:40123");
+    assert_eq!(parse_pair_address("[fe80::1]:37099").unwrap(), "[fe80::1]:37099");
+
+    assert!(parse_pair_address("192.168.1.20").is_err());
+    assert!(parse_pair_address("192.168.1.20:").is_err());
+    assert!(parse_pair_address(":37099").is_err());
+    assert!(parse_pair_address("192.168.1.20:99999").is_err());
+}
+
+#[test]
+fn test_parse_pair_code() {
+    assert_eq!(parse_pair_code("482913").unwrap(), "482913");
+    assert_eq!(parse_pair_code("482 913").unwrap(), "482913");
+    assert_eq!(parse_pair_code("482-913\n").unwrap(), "482913");
+
+    assert!(parse_pair_code("48291").is_err());
+    assert!(parse_pair_code("48291a").is_err());
+    assert!(parse_pair_code("").is_err());
+}
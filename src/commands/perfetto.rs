@@ -1,13 +1,18 @@
+use crate::android::sdk::{self, Capability};
 use crate::commands::SubCommand;
 use crate::core::context::CommandContext;
-use crate::error::Result;
+use crate::core::upload;
+use crate::error::{AimError, Result};
 use crate::library::adb::{push, pull, run_shell_command_async, ProgressDisplay};
+use crate::output::OutputFormatter;
 use async_trait::async_trait;
+use comfy_table::Cell;
 use crossterm::event::{self, Event, KeyCode};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use indicatif::ProgressBar;
 use rand::{distr::Alphanumeric, Rng};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -29,6 +34,99 @@ pub struct PerfettoArgs {
     /// Output file path
     #[clap(short = 'o', long = "output", default_value = "trace.perfetto-trace")]
     pub output: PathBuf,
+
+    /// Print top CPU consumers, jank and app startups once the trace is pulled
+    #[clap(long)]
+    pub summary: bool,
+
+    /// Upload the result as set up in the [upload] config section
+    #[clap(long)]
+    pub upload: bool,
+}
+
+/// Names the trace processor is installed under
+const TRACE_PROCESSOR_NAMES: [&str; 2] = ["trace_processor_shell", "trace_processor"];
+
+/// CPU time per process (or thread, for kernel threads), busiest first
+pub const CPU_QUERY: &str = "\
+SELECT IFNULL(process.name, thread.name) AS name, SUM(sched.dur) AS dur \
+FROM sched JOIN thread USING (utid) LEFT JOIN process USING (upid) \
+WHERE sched.utid != 0 \
+GROUP BY name ORDER BY dur DESC LIMIT 10";
+
+/// Janky frames out of all frames, from the frame timeline
+pub const JANK_QUERY: &str = "\
+SELECT \
+  (SELECT COUNT(*) FROM actual_frame_timeline_slice WHERE jank_type != 'None') AS janky, \
+  (SELECT COUNT(*) FROM actual_frame_timeline_slice) AS frames";
+
+/// App startups in the order they happened
+pub const STARTUP_QUERY: &str = "\
+INCLUDE PERFETTO MODULE android.startup.startups; \
+SELECT package, dur FROM android_startups ORDER BY ts";
+
+/// Locate the trace processor on `PATH`
+pub fn find_trace_processor(path: Option<&std::ffi::OsStr>) -> Option<PathBuf> {
+    let dirs: Vec<PathBuf> = std::env::split_paths(path?).collect();
+    TRACE_PROCESSOR_NAMES.iter().find_map(|name| {
+        let file = format!("{}{}", name, std::env::consts::EXE_SUFFIX);
+        dirs.iter().map(|dir| dir.join(&file)).find(|candidate| candidate.is_file())
+    })
+}
+
+/// Rows of a `trace_processor_shell -q` result, without the header
+///
+/// The output is CSV with strings in double quotes and `""` for a quote.
+pub fn parse_query_output(output: &str) -> Vec<Vec<String>> {
+    let parse_line = |line: &str| {
+        let mut cells = Vec::new();
+        let mut cell = String::new();
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    cell.push('"');
+                    chars.next();
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => cells.push(std::mem::take(&mut cell)),
+                c => cell.push(c),
+            }
+        }
+        cells.push(cell);
+        cells
+    };
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .skip(1)
+        .map(parse_line)
+        .collect()
+}
+
+/// Nanoseconds as milliseconds with one decimal, e.g. `1234.5 ms`
+pub fn format_ns(value: &str) -> String {
+    match value.trim().parse::<f64>() {
+        Ok(ns) => format!("{:.1} ms", ns / 1e6),
+        Err(_) => value.to_string(),
+    }
+}
+
+/// One line describing the jank result, e.g. `12 of 840 frames janky (1.4%)`
+pub fn jank_line(rows: &[Vec<String>]) -> Option<String> {
+    let row = rows.first()?;
+    let janky: u64 = row.first()?.trim().parse().ok()?;
+    let frames: u64 = row.get(1)?.trim().parse().ok()?;
+    if frames == 0 {
+        return Some("No frame timeline data in this trace".to_string());
+    }
+    Some(format!(
+        "{} of {} frames janky ({:.1}%)",
+        janky,
+        frames,
+        janky as f64 * 100.0 / frames as f64
+    ))
 }
 
 impl PerfettoCommand {
@@ -53,6 +151,60 @@ impl PerfettoCommand {
         disable_raw_mode()?;
         Ok(())
     }
+
+    /// Run one query, or `None` if the trace processor rejects it
+    fn query(trace_processor: &Path, trace: &Path, sql: &str) -> Option<Vec<Vec<String>>> {
+        let query_file = std::env::temp_dir().join(format!("aim-perfetto-{}.sql", std::process::id()));
+        std::fs::write(&query_file, sql).ok()?;
+        let output = Command::new(trace_processor).arg("-q").arg(&query_file).arg(trace).output();
+        let _ = std::fs::remove_file(&query_file);
+        let output = output.ok().filter(|o| o.status.success())?;
+        Some(parse_query_output(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// `--summary`: print a few headline numbers from the pulled trace
+    fn print_summary(trace: &Path) -> Result<()> {
+        let trace_processor = find_trace_processor(std::env::var_os("PATH").as_deref()).ok_or_else(|| {
+            AimError::CommandExecution(
+                "--summary needs trace_processor_shell on PATH.\n\
+                 Download it from https://get.perfetto.dev/trace_processor"
+                    .to_string(),
+            )
+        })?;
+        let formatter = OutputFormatter::new();
+        let unavailable = || println!("  (not available in this trace)");
+
+        println!("\nTop CPU consumers");
+        match Self::query(&trace_processor, trace, CPU_QUERY) {
+            Some(rows) if !rows.is_empty() => {
+                let rows = rows
+                    .iter()
+                    .map(|r| vec![Cell::new(&r[0]), Cell::new(format_ns(r.get(1).map_or("", String::as_str)))])
+                    .collect();
+                formatter.dynamic_table(&["NAME".to_string(), "CPU".to_string()], rows)?;
+            }
+            _ => unavailable(),
+        }
+
+        println!("\nJank");
+        match Self::query(&trace_processor, trace, JANK_QUERY).as_deref().and_then(jank_line) {
+            Some(line) => println!("  {}", line),
+            None => unavailable(),
+        }
+
+        println!("\nApp startups");
+        match Self::query(&trace_processor, trace, STARTUP_QUERY) {
+            Some(rows) if !rows.is_empty() => {
+                let rows = rows
+                    .iter()
+                    .map(|r| vec![Cell::new(&r[0]), Cell::new(format_ns(r.get(1).map_or("", String::as_str)))])
+                    .collect();
+                formatter.dynamic_table(&["PACKAGE".to_string(), "DURATION".to_string()], rows)?;
+            }
+            _ => unavailable(),
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -61,7 +213,15 @@ impl SubCommand for PerfettoCommand {
     
     async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
         let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
+        // Older releases only take binary configs
+        sdk::for_device(host, port, &device.id).await.require(Capability::PerfettoTextConfig)?;
+        if args.time.is_none() {
+            // Without a duration the trace runs until a key is pressed
+            crate::core::ci::require_interactive("Tracing without --time")?;
+        }
+        // Fail before tracing, not after, when there is nowhere to upload to
+        let upload_config = if args.upload { Some(upload::load_config()?) } else { None };
         
         // Generate random temp file name
         let temp_file = format!(
@@ -146,6 +306,12 @@ impl SubCommand for PerfettoCommand {
         run_shell_command_async(host, &port_str, &rm_cmd, Some(&device_id_str)).await?;
         
         println!("\nTrace file saved to: {}", args.output.display());
+        if args.summary {
+            Self::print_summary(&args.output)?;
+        }
+        if let Some(config) = &upload_config {
+            upload::upload_artifact(ctx, config, &args.output).await?;
+        }
         Ok(())
     }
 }
\ No newline at end of file
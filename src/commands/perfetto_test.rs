@@ -0,0 +1,42 @@
+use super::perfetto::{find_trace_processor, format_ns, jank_line, parse_query_output};
+
+#[test]
+fn test_parse_query_output() {
+    let out = "\"name\",\"dur\"\n\"system_server\",1234000000\n\"com.example, \"\"beta\"\"\",5\n\n";
+    assert_eq!(
+        parse_query_output(out),
+        vec![
+            vec!["system_server".to_string(), "1234000000".to_string()],
+            vec!["com.example, \"beta\"".to_string(), "5".to_string()],
+        ]
+    );
+    assert!(parse_query_output("\"name\",\"dur\"\n").is_empty());
+}
+
+#[test]
+fn test_format_ns() {
+    assert_eq!(format_ns("1234500000"), "1234.5 ms");
+    assert_eq!(format_ns("[NULL]"), "[NULL]");
+}
+
+#[test]
+fn test_jank_line() {
+    let rows = vec![vec!["12".to_string(), "840".to_string()]];
+    assert_eq!(jank_line(&rows).as_deref(), Some("12 of 840 frames janky (1.4%)"));
+
+    let empty = vec![vec!["0".to_string(), "0".to_string()]];
+    assert_eq!(jank_line(&empty).as_deref(), Some("No frame timeline data in this trace"));
+    assert_eq!(jank_line(&[]), None);
+}
+
+#[test]
+fn test_find_trace_processor() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().as_os_str();
+    assert_eq!(find_trace_processor(Some(path)), None);
+
+    let binary = dir.path().join(format!("trace_processor{}", std::env::consts::EXE_SUFFIX));
+    std::fs::write(&binary, "").unwrap();
+    assert_eq!(find_trace_processor(Some(path)), Some(binary));
+    assert_eq!(find_trace_processor(None), None);
+}
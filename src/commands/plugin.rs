@@ -0,0 +1,77 @@
+//! External subcommands
+//!
+//! `aim foo ...` with no built-in `foo` runs an `aim-foo` executable from
+//! `PATH`, the way git finds `git-foo`. The plugin gets the remaining
+//! arguments and the connection details in its environment, and aim exits
+//! with the plugin's exit code.
+
+use crate::error::{AimError, Result};
+use log::debug;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Prefix of plugin executables
+pub const PLUGIN_PREFIX: &str = "aim-";
+
+/// Find `aim-<name>` in the directories of a `PATH`-style list
+pub fn find_plugin(name: &str, path: Option<&OsStr>) -> Option<PathBuf> {
+    let file = format!("{}{}{}", PLUGIN_PREFIX, name, std::env::consts::EXE_SUFFIX);
+    std::env::split_paths(path?)
+        .map(|dir| dir.join(&file))
+        .find(|candidate| is_executable(candidate))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Environment passed to a plugin
+///
+/// `AIM_DEVICE` and `ANDROID_SERIAL` are only set when a device could be
+/// selected, so `adb` run by the plugin targets the same device as aim would.
+pub fn plugin_env(host: &str, port: u16, serial: Option<&str>) -> Vec<(&'static str, String)> {
+    let mut env = vec![("AIM_HOST", host.to_string()), ("AIM_PORT", port.to_string())];
+    if let Some(serial) = serial {
+        env.push(("AIM_DEVICE", serial.to_string()));
+        env.push(("ANDROID_SERIAL", serial.to_string()));
+    }
+    env
+}
+
+/// Run the plugin for `args[0]` with the rest of `args`, exiting with its code
+pub async fn run(args: Vec<String>) -> Result<()> {
+    let Some((name, rest)) = args.split_first() else {
+        return Err(AimError::InvalidArgument("missing command".to_string()));
+    };
+    let plugin = find_plugin(name, std::env::var_os("PATH").as_deref()).ok_or_else(|| {
+        AimError::InvalidArgument(format!(
+            "unknown command '{}' (no {}{} found on PATH)",
+            name, PLUGIN_PREFIX, name
+        ))
+    })?;
+
+    // No device is fine; the plugin may not need one or may pick its own
+    let serial = crate::commands::get_device(None).await.ok().map(|d| d.id.to_string());
+    let (host, port) = crate::commands::runner::get_adb_connection_params();
+    debug!("Running plugin {} with device {:?}", plugin.display(), serial);
+
+    let status = Command::new(&plugin)
+        .args(rest)
+        .envs(plugin_env(host, port, serial.as_deref()))
+        .status()
+        .map_err(|e| AimError::CommandExecution(format!("Failed to run {}: {}", plugin.display(), e)))?;
+
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
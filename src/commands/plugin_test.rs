@@ -0,0 +1,41 @@
+use super::plugin::{find_plugin, plugin_env};
+use std::ffi::OsString;
+use std::fs;
+
+#[cfg(unix)]
+fn make_executable(path: &std::path::Path) {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[test]
+#[cfg(unix)]
+fn test_find_plugin() {
+    let first = tempfile::tempdir().unwrap();
+    let second = tempfile::tempdir().unwrap();
+
+    // Not executable, so skipped in favour of the one later on PATH
+    fs::write(first.path().join("aim-logs"), "#!/bin/sh\n").unwrap();
+    let plugin = second.path().join("aim-logs");
+    fs::write(&plugin, "#!/bin/sh\n").unwrap();
+    make_executable(&plugin);
+
+    let path: OsString = std::env::join_paths([first.path(), second.path()]).unwrap();
+    assert_eq!(find_plugin("logs", Some(&path)), Some(plugin));
+    assert_eq!(find_plugin("missing", Some(&path)), None);
+    assert_eq!(find_plugin("logs", None), None);
+}
+
+#[test]
+fn test_plugin_env() {
+    assert_eq!(
+        plugin_env("localhost", 5037, Some("abc123")),
+        vec![
+            ("AIM_HOST", "localhost".to_string()),
+            ("AIM_PORT", "5037".to_string()),
+            ("AIM_DEVICE", "abc123".to_string()),
+            ("ANDROID_SERIAL", "abc123".to_string()),
+        ]
+    );
+    assert_eq!(plugin_env("10.0.0.2", 5038, None).len(), 2);
+}
@@ -0,0 +1,191 @@
+//! Getting a device from factory state to ready-to-test in one run
+//!
+//! The steps are all optional and run in this order: factory reset, skipping
+//! the setup wizard, then an aim script (see `aim script`) that installs apps,
+//! pushes files and applies settings.
+
+use crate::adb::shell::helpers::shell;
+use crate::adb::su::RootMode;
+use crate::commands::boot::{wait_for_boot_completed, wait_for_online};
+use crate::commands::script::{parse_var, ScriptArgs, ScriptCommand};
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::DeviceId;
+use crate::error::{AimError, Result};
+use async_trait::async_trait;
+use colored::*;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Setup wizard packages of AOSP and GMS builds
+pub const SETUP_WIZARD_PACKAGES: &[&str] = &[
+    "com.google.android.setupwizard",
+    "com.android.provision",
+    "com.android.setupwizard",
+];
+
+/// Has recovery wipe /data on its next start
+const WIPE_COMMAND: &str = "[ -d /cache ] && mkdir -p /cache/recovery && echo --wipe_data > /cache/recovery/command && reboot recovery";
+
+pub struct ProvisionCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ProvisionArgs {
+    /// aim script with the apps, files and settings to set up
+    #[arg(long)]
+    pub script: Option<PathBuf>,
+
+    /// Variable for the script (NAME=value, repeatable)
+    #[arg(long = "var", value_parser = parse_var)]
+    pub vars: Vec<(String, String)>,
+
+    /// Factory reset the device first (needs root)
+    #[arg(long)]
+    pub factory_reset: bool,
+
+    /// Mark setup as complete and disable the setup wizard
+    #[arg(long)]
+    pub skip_setup_wizard: bool,
+
+    /// Skip the factory reset confirmation
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+
+    /// How long to wait for the device to come back after the reset
+    #[arg(long, default_value = "10m", value_parser = crate::utils::parse_duration)]
+    pub boot_timeout: Duration,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// Shell commands that finish setup without going through the wizard
+pub fn setup_wizard_commands(installed: &str) -> Vec<String> {
+    let installed: Vec<&str> = installed
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("package:"))
+        .collect();
+    let mut commands = vec![
+        "settings put global device_provisioned 1".to_string(),
+        "settings put secure user_setup_complete 1".to_string(),
+        "settings put global setup_wizard_has_run 1".to_string(),
+    ];
+    commands.extend(
+        SETUP_WIZARD_PACKAGES
+            .iter()
+            .filter(|p| installed.contains(*p))
+            .map(|p| format!("pm disable-user --user 0 {}", p)),
+    );
+    commands.push("am start -a android.intent.action.MAIN -c android.intent.category.HOME".to_string());
+    commands
+}
+
+fn step(text: &str) {
+    println!("{} {}", "==>".bright_blue().bold(), text.bold());
+}
+
+impl ProvisionCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn confirm_reset(device: &DeviceId) -> Result<bool> {
+        use std::io::{self, Write};
+
+        crate::core::ci::require_interactive("Confirmation prompt (pass --yes)")?;
+        println!("{}", "WARNING: This erases all data on the device!".yellow().bold());
+        print!("Factory reset {}? [y/N] ", device.to_string().bright_cyan());
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(input.trim().eq_ignore_ascii_case("y"))
+    }
+
+    async fn factory_reset(&self, host: &str, port: u16, device: &DeviceId, timeout: Duration) -> Result<()> {
+        let root = RootMode::detect(host, port, device).await.map_err(|_| {
+            AimError::CommandExecution(format!(
+                "Factory reset from adb needs root on {}. Reset it from Settings > System > Reset options \
+                 instead, then run provision without --factory-reset.",
+                device
+            ))
+        })?;
+
+        step("Factory reset");
+        let started = Instant::now();
+        let out = shell(host, port, device, &format!("{} 2>&1 || echo no-cache", root.wrap(WIPE_COMMAND))).await?;
+        if out.contains("no-cache") {
+            return Err(AimError::CommandExecution(format!(
+                "{} has no /cache partition to pass the wipe to recovery. Reset it from Settings \
+                 or recovery, then run provision without --factory-reset.",
+                device
+            )));
+        }
+
+        println!("Waiting for {} to come back (up to {}s)", device, timeout.as_secs());
+        println!(
+            "{}",
+            "After a wipe, USB debugging has to be enabled and authorized again unless the build enables it".dimmed()
+        );
+        wait_for_online(host, port, device, started, timeout).await?;
+        let secs = wait_for_boot_completed(host, port, device, started, timeout).await?;
+        println!("{} Booted after reset in {:.0}s", "✓".green(), secs);
+        Ok(())
+    }
+
+    async fn skip_setup_wizard(&self, host: &str, port: u16, device: &DeviceId) -> Result<()> {
+        step("Skipping the setup wizard");
+        let installed = shell(host, port, device, "pm list packages").await?;
+        for command in setup_wizard_commands(&installed) {
+            let out = shell(host, port, device, &format!("{} 2>&1", command)).await?;
+            if out.contains("Exception") || out.contains("Error") {
+                println!("{} {}: {}", "!".yellow(), command, out.trim());
+            }
+        }
+        println!("{} Setup marked as complete", "✓".green());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubCommand for ProvisionCommand {
+    type Args = ProvisionArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        if !args.factory_reset && !args.skip_setup_wizard && args.script.is_none() {
+            return Err(AimError::InvalidArgument(
+                "Nothing to do: pass --script, --skip-setup-wizard and/or --factory-reset".to_string(),
+            ));
+        }
+        if let Some(script) = args.script.as_ref().filter(|s| !s.is_file()) {
+            return Err(AimError::InvalidArgument(format!("Script not found: {}", script.display())));
+        }
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        if args.factory_reset {
+            if !args.yes && !Self::confirm_reset(&device.id)? {
+                println!("Provisioning cancelled.");
+                return Ok(());
+            }
+            self.factory_reset(host, port, &device.id, args.boot_timeout).await?;
+        }
+        if args.skip_setup_wizard {
+            self.skip_setup_wizard(host, port, &device.id).await?;
+        }
+        if let Some(script) = args.script {
+            step(&format!("Running {}", script.display()));
+            let script_args = ScriptArgs {
+                file: Some(script),
+                command: None,
+                vars: args.vars,
+                device_id: Some(device.id.to_string()),
+            };
+            ScriptCommand::new().run(ctx, script_args).await?;
+        }
+
+        println!("{} {} is provisioned", "✓".green().bold(), device.id.to_string().bright_cyan());
+        Ok(())
+    }
+}
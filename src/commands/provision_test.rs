@@ -0,0 +1,20 @@
+use super::provision::setup_wizard_commands;
+
+#[test]
+fn test_setup_wizard_commands_disables_installed_wizards() {
+    let installed = "package:com.android.settings\npackage:com.google.android.setupwizard\npackage:com.android.provision\n";
+    let commands = setup_wizard_commands(installed);
+    assert_eq!(commands[0], "settings put global device_provisioned 1");
+    assert_eq!(commands[1], "settings put secure user_setup_complete 1");
+    assert!(commands.contains(&"pm disable-user --user 0 com.google.android.setupwizard".to_string()));
+    assert!(commands.contains(&"pm disable-user --user 0 com.android.provision".to_string()));
+    assert!(!commands.iter().any(|c| c.ends_with("com.android.setupwizard")));
+    assert!(commands.last().unwrap().starts_with("am start"));
+}
+
+#[test]
+fn test_setup_wizard_commands_without_wizard() {
+    let commands = setup_wizard_commands("package:com.android.settings\n");
+    assert!(!commands.iter().any(|c| c.starts_with("pm disable-user")));
+    assert_eq!(commands.len(), 4);
+}
@@ -1,9 +1,17 @@
+use crate::adb::file_transfer::FileTransfer;
+use crate::commands::sync::FileMeta;
 use crate::commands::{SubCommand, get_device};
 use crate::core::context::CommandContext;
-use crate::error::Result;
-use crate::library::adb::{pull, ProgressDisplay};
+use crate::core::types::{Device, OutputFormat};
+use crate::error::{AimError, Result};
+use crate::library::adb::{pull, ProgressDisplay, TRANSFER_RETRIES};
+use crate::output::transfer::TransferSummary;
+use crate::utils::parse_duration;
 use async_trait::async_trait;
-use std::path::PathBuf;
+use colored::*;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 pub struct PullCommand;
 
@@ -18,39 +26,215 @@ pub struct PullArgs {
     pub dst: PathBuf,
 
     /// Device ID (required if multiple devices are connected)
+    #[clap(short = 'd', long = "device")]
     pub device_id: Option<String>,
+
+    /// Keep running and pull new or changed files as they appear
+    #[clap(short, long)]
+    pub watch: bool,
+
+    /// How often to check the device for changes in watch mode
+    #[clap(long, value_parser = parse_duration, default_value = "2s")]
+    pub interval: Duration,
+}
+
+/// Split `aim pull` arguments into the remote paths and the local destination
+///
+/// As with `adb pull`, the last of several paths is the destination, and a
+/// single path is pulled into the current directory.
+pub fn split_paths(mut paths: Vec<String>) -> (Vec<String>, PathBuf) {
+    let dst = match paths.len() {
+        0 | 1 => PathBuf::from("."),
+        _ => paths.pop().map(PathBuf::from).unwrap_or_default(),
+    };
+    (paths, dst)
+}
+
+/// Remote files keyed by path relative to the watched source
+pub type RemoteSnapshot = BTreeMap<String, FileMeta>;
+
+/// Files to pull after a poll
+///
+/// A file is pulled once it looks the same in two consecutive polls, so files
+/// still being written (a recording, a growing log) are not copied half done,
+/// and only if it differs from what was last pulled.
+pub fn ready_to_pull(previous: &RemoteSnapshot, current: &RemoteSnapshot, pulled: &RemoteSnapshot) -> Vec<String> {
+    current
+        .iter()
+        .filter(|(path, meta)| previous.get(*path) == Some(meta) && pulled.get(*path) != Some(meta))
+        .map(|(path, _)| path.clone())
+        .collect()
+}
+
+/// Where a remote file under `src` lands locally, matching a normal pull of
+/// `src` to `dst`. An empty `rel` means `src` itself is the file.
+pub fn local_path_for(src: &str, dst: &Path, rel: &str) -> PathBuf {
+    let src = src.trim_end_matches('/');
+    let name = src.rsplit('/').next().unwrap_or(src);
+    if rel.is_empty() {
+        if dst.is_dir() {
+            dst.join(name)
+        } else {
+            dst.to_path_buf()
+        }
+    } else {
+        // Directories are pulled with their own name under the destination
+        rel.split('/').fold(dst.join(name), |path, part| path.join(part))
+    }
 }
 
 impl PullCommand {
     pub fn new() -> Self {
         Self
     }
+
+    async fn snapshot(transfer: &mut FileTransfer, src: &str) -> Result<RemoteSnapshot> {
+        Ok(transfer
+            .list_tree(src)
+            .await?
+            .into_iter()
+            .map(|(rel, stat)| (rel, FileMeta::from_stat(&stat)))
+            .collect())
+    }
+
+    /// Poll the sources and pull files as they appear or change until interrupted
+    async fn watch(&self, ctx: &CommandContext, device: &Device, args: &PullArgs) -> Result<()> {
+        let (host, port) = ctx.adb_address();
+        let port_str = port.to_string();
+        let device_id_str = device.id.to_string();
+
+        // Everything present now was just pulled
+        let mut transfer = FileTransfer::new(host, port, Some(&device.id)).await?;
+        let mut previous = Vec::new();
+        for src in &args.src {
+            previous.push(Self::snapshot(&mut transfer, src).await?);
+        }
+        let mut pulled = previous.clone();
+
+        eprintln!(
+            "Watching {} path(s) every {}, Ctrl-C to stop",
+            args.src.len(),
+            crate::utils::format_duration(args.interval)
+        );
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(args.interval) => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+
+            // A fresh sync connection per poll survives the device dropping off briefly
+            let mut transfer = match FileTransfer::new(host, port, Some(&device.id)).await {
+                Ok(transfer) => transfer,
+                Err(e) => {
+                    log::debug!("Poll failed: {}", e);
+                    continue;
+                }
+            };
+            for (i, src) in args.src.iter().enumerate() {
+                let current = match Self::snapshot(&mut transfer, src).await {
+                    Ok(current) => current,
+                    Err(e) => {
+                        log::debug!("Cannot list {}: {}", src, e);
+                        continue;
+                    }
+                };
+
+                for rel in ready_to_pull(&previous[i], &current, &pulled[i]) {
+                    let remote = if rel.is_empty() {
+                        src.clone()
+                    } else {
+                        format!("{}/{}", src.trim_end_matches('/'), rel)
+                    };
+                    let local = local_path_for(src, &args.dst, &rel);
+                    let result = pull(
+                        host,
+                        &port_str,
+                        Some(&device_id_str),
+                        &PathBuf::from(&remote),
+                        &local,
+                        ProgressDisplay::Hide,
+                    )
+                    .await;
+                    match result {
+                        Ok(_) => {
+                            println!("{} {} -> {}", "✓".green(), remote, local.display());
+                            pulled[i].insert(rel.clone(), current[&rel]);
+                        }
+                        Err(e) => eprintln!("{} {}: {}", "✗".red(), remote, e),
+                    }
+                }
+                previous[i] = current;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl SubCommand for PullCommand {
     type Args = PullArgs;
 
-    async fn run(&self, _ctx: &CommandContext, args: Self::Args) -> Result<()> {
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
         let device = get_device(args.device_id.as_deref()).await?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
         let device_id_str = device.id.to_string();
         let port_str = port.to_string();
+        let json = ctx.output_format == OutputFormat::Json;
 
+        let start = Instant::now();
+        let mut summary = TransferSummary::new();
         for src in &args.src {
-            println!("Pulling {} to {}", src, args.dst.display());
-
-            pull(
-                host,
-                &port_str,
-                Some(&device_id_str),
-                &PathBuf::from(src),
-                &args.dst,
-                ProgressDisplay::Show,
-            )
-            .await?;
+            if !json {
+                println!("Pulling {} to {}", src, args.dst.display());
+            }
+
+            let mut attempt = 0;
+            loop {
+                let result = pull(
+                    host,
+                    &port_str,
+                    Some(&device_id_str),
+                    &PathBuf::from(src),
+                    &args.dst,
+                    ProgressDisplay::Show,
+                )
+                .await;
+                match result {
+                    Ok(stats) => summary.add(&stats),
+                    Err(e) if attempt < TRANSFER_RETRIES => {
+                        attempt += 1;
+                        summary.retries += 1;
+                        eprintln!("{} {}: {}, retrying", "!".yellow(), src, e);
+                        continue;
+                    }
+                    Err(e) => {
+                        summary.fail();
+                        eprintln!("{} {}: {}", "✗".red(), src, e);
+                    }
+                }
+                break;
+            }
+        }
+        summary.finish(start.elapsed());
+
+        if json {
+            crate::commands::format_json_output(&summary)?;
+        } else {
+            println!("{}", summary);
+        }
+
+        if summary.failed() {
+            return Err(AimError::FileTransfer(format!(
+                "{} of {} source(s) failed to pull",
+                summary.files_failed,
+                args.src.len()
+            )));
         }
 
+        if args.watch {
+            self.watch(ctx, &device, &args).await?;
+        }
         Ok(())
     }
 }
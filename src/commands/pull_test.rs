@@ -0,0 +1,55 @@
+use super::pull::{local_path_for, ready_to_pull, split_paths, RemoteSnapshot};
+use super::sync::FileMeta;
+use std::path::{Path, PathBuf};
+
+fn snapshot(entries: &[(&str, u64, i64)]) -> RemoteSnapshot {
+    entries
+        .iter()
+        .map(|(path, size, mtime)| (path.to_string(), FileMeta { size: *size, mtime: *mtime }))
+        .collect()
+}
+
+#[test]
+fn test_ready_to_pull_waits_for_stable_files() {
+    let pulled = snapshot(&[("old.jpg", 10, 100)]);
+    let previous = snapshot(&[("old.jpg", 10, 100), ("new.jpg", 5, 200)]);
+    let current = snapshot(&[("old.jpg", 10, 100), ("new.jpg", 8, 201), ("shot.png", 3, 300)]);
+
+    // new.jpg is still growing and shot.png was only just seen
+    assert!(ready_to_pull(&previous, &current, &pulled).is_empty());
+
+    let next = current.clone();
+    assert_eq!(ready_to_pull(&current, &next, &pulled), vec!["new.jpg", "shot.png"]);
+}
+
+#[test]
+fn test_ready_to_pull_changed_file() {
+    let pulled = snapshot(&[("app.log", 10, 100)]);
+    let current = snapshot(&[("app.log", 20, 150)]);
+
+    assert_eq!(ready_to_pull(&current, &current, &pulled), vec!["app.log"]);
+    assert!(ready_to_pull(&current, &current, &current).is_empty());
+}
+
+#[test]
+fn test_local_path_for() {
+    let dst = Path::new("/nonexistent/photos");
+    assert_eq!(
+        local_path_for("/sdcard/DCIM/", dst, "Camera/IMG_1.jpg"),
+        PathBuf::from("/nonexistent/photos/DCIM/Camera/IMG_1.jpg")
+    );
+    assert_eq!(local_path_for("/sdcard/app.log", dst, ""), PathBuf::from("/nonexistent/photos"));
+
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(local_path_for("/sdcard/app.log", dir.path(), ""), dir.path().join("app.log"));
+}
+
+#[test]
+fn test_split_paths() {
+    let paths = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+    assert_eq!(split_paths(paths(&["/sdcard/a.txt"])), (paths(&["/sdcard/a.txt"]), PathBuf::from(".")));
+    assert_eq!(
+        split_paths(paths(&["/sdcard/a.txt", "/sdcard/b.txt", "out"])),
+        (paths(&["/sdcard/a.txt", "/sdcard/b.txt"]), PathBuf::from("out"))
+    );
+}
@@ -1,9 +1,18 @@
 use crate::commands::{SubCommand, get_device};
 use crate::core::context::CommandContext;
-use crate::error::Result;
-use crate::library::adb::{push, ProgressDisplay};
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::library::adb::{push, set_chunk_size, ProgressDisplay, MIN_CHUNK_SIZE, SYNC_DATA_MAX, TRANSFER_RETRIES};
+use crate::output::transfer::TransferSummary;
+use crate::core::types::Device;
+use crate::utils::{format_bytes, parse_duration, parse_size};
 use async_trait::async_trait;
-use std::path::PathBuf;
+use colored::*;
+use log::debug;
+use notify::{RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 pub struct PushCommand;
 
@@ -17,46 +26,229 @@ pub struct PushArgs {
     pub dst: String,
 
     /// Device ID (required if multiple devices are connected)
+    #[clap(short = 'd', long = "device")]
     pub device_id: Option<String>,
 
     /// Recursive push (for directories)
     #[clap(short, long)]
     pub recursive: bool,
+
+    /// Fixed DATA chunk size (e.g. 16K, 64K) instead of auto-tuning
+    #[clap(long, value_parser = parse_chunk_size)]
+    pub chunk_size: Option<usize>,
+
+    /// Keep running and push files again whenever they change locally
+    #[clap(short, long)]
+    pub watch: bool,
+
+    /// Device shell command to run after each push in watch mode
+    #[clap(long, requires = "watch")]
+    pub exec: Option<String>,
+
+    /// Quiet time to wait for before pushing a burst of changes
+    #[clap(long, value_parser = parse_duration, default_value = "300ms")]
+    pub debounce: Duration,
+}
+
+/// Parse `--chunk-size`, which must fit in one sync DATA packet
+pub fn parse_chunk_size(input: &str) -> std::result::Result<usize, String> {
+    let size = parse_size(input)? as usize;
+    if !(MIN_CHUNK_SIZE..=SYNC_DATA_MAX).contains(&size) {
+        return Err(format!(
+            "chunk size must be between {} and {}",
+            format_bytes(MIN_CHUNK_SIZE as u64),
+            format_bytes(SYNC_DATA_MAX as u64)
+        ));
+    }
+    Ok(size)
+}
+
+/// Where a changed local file goes on the device, matching what a normal push
+/// of `src` (an absolute path) to `dst` would have produced
+pub fn remote_path_for(src: &Path, dst: &str, changed: &Path) -> Option<String> {
+    let rel = if changed == src {
+        let name = changed.file_name()?.to_string_lossy();
+        if !dst.ends_with('/') {
+            return Some(dst.to_string());
+        }
+        name.to_string()
+    } else {
+        if !changed.starts_with(src) {
+            return None;
+        }
+        // Directories are pushed with their own name under the destination
+        let base = src.parent().unwrap_or(src);
+        changed
+            .strip_prefix(base)
+            .ok()?
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+    Some(format!("{}/{}", dst.trim_end_matches('/'), rel))
 }
 
 impl PushCommand {
     pub fn new() -> Self {
         Self
     }
+
+    /// Push files under `srcs` as they change until interrupted
+    async fn watch(&self, ctx: &CommandContext, device: &Device, args: &PushArgs) -> Result<()> {
+        let (host, port) = ctx.adb_address();
+        let port_str = port.to_string();
+        let device_id_str = device.id.to_string();
+
+        let roots = args
+            .src
+            .iter()
+            .map(std::fs::canonicalize)
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+            Ok(event) if !event.kind.is_access() => {
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => debug!("Watch error: {}", e),
+        })
+        .map_err(|e| AimError::CommandExecution(format!("Cannot watch files: {}", e)))?;
+        for root in &roots {
+            watcher
+                .watch(root, RecursiveMode::Recursive)
+                .map_err(|e| AimError::CommandExecution(format!("Cannot watch {}: {}", root.display(), e)))?;
+        }
+
+        eprintln!("Watching {} path(s) for changes, Ctrl-C to stop", roots.len());
+        loop {
+            let first = tokio::select! {
+                path = rx.recv() => match path {
+                    Some(path) => path,
+                    None => break,
+                },
+                _ = tokio::signal::ctrl_c() => break,
+            };
+
+            // Collect the whole burst, e.g. an editor save or a build writing many files
+            let mut changed = BTreeSet::from([first]);
+            while let Ok(Some(path)) = tokio::time::timeout(args.debounce, rx.recv()).await {
+                changed.insert(path);
+            }
+
+            let mut pushed = 0;
+            for path in changed.iter().filter(|p| p.is_file()) {
+                let Some(remote) = roots.iter().find_map(|root| remote_path_for(root, &args.dst, path)) else {
+                    continue;
+                };
+                let result = push(
+                    host,
+                    &port_str,
+                    Some(&device_id_str),
+                    path,
+                    &PathBuf::from(&remote),
+                    false,
+                    ProgressDisplay::Hide,
+                )
+                .await;
+                match result {
+                    Ok(_) => {
+                        pushed += 1;
+                        println!("{} {} -> {}", "✓".green(), path.display(), remote);
+                    }
+                    Err(e) => eprintln!("{} {}: {}", "✗".red(), path.display(), e),
+                }
+            }
+
+            if pushed > 0 {
+                if let Some(cmd) = &args.exec {
+                    let output = crate::adb::shell::ShellCommand::new(cmd.clone())
+                        .with_device(device.id.clone())
+                        .execute(host, port)
+                        .await?;
+                    print!("{}", output.stdout);
+                    if output.exit_code != 0 {
+                        eprintln!("{} `{}` exited with {}", "!".yellow(), cmd, output.exit_code);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl SubCommand for PushCommand {
     type Args = PushArgs;
 
-    async fn run(&self, _ctx: &CommandContext, args: Self::Args) -> Result<()> {
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
         let device = get_device(args.device_id.as_deref()).await?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
         let device_id_str = device.id.to_string();
         let port_str = port.to_string();
+        let json = ctx.output_format == OutputFormat::Json;
 
         let has_multiple = args.src.len() > 1;
+        set_chunk_size(args.chunk_size);
 
+        let start = Instant::now();
+        let mut summary = TransferSummary::new();
         for src in &args.src {
-            println!("Pushing {} to {}", src.display(), args.dst);
+            if !json {
+                println!("Pushing {} to {}", src.display(), args.dst);
+            }
+
+            let mut attempt = 0;
+            loop {
+                let result = push(
+                    host,
+                    &port_str,
+                    Some(&device_id_str),
+                    src,
+                    &PathBuf::from(&args.dst),
+                    has_multiple,
+                    ProgressDisplay::Show,
+                )
+                .await;
+                match result {
+                    Ok(stats) => summary.add(&stats),
+                    Err(e) if attempt < TRANSFER_RETRIES => {
+                        attempt += 1;
+                        summary.retries += 1;
+                        eprintln!("{} {}: {}, retrying", "!".yellow(), src.display(), e);
+                        continue;
+                    }
+                    Err(e) => {
+                        summary.fail();
+                        eprintln!("{} {}: {}", "✗".red(), src.display(), e);
+                    }
+                }
+                break;
+            }
+        }
+        summary.finish(start.elapsed());
 
-            push(
-                host,
-                &port_str,
-                Some(&device_id_str),
-                src,
-                &PathBuf::from(&args.dst),
-                has_multiple,
-                ProgressDisplay::Show,
-            )
-            .await?;
+        if json {
+            crate::commands::format_json_output(&summary)?;
+        } else {
+            println!("{}", summary);
         }
 
+        if summary.failed() {
+            return Err(AimError::FileTransfer(format!(
+                "{} of {} source(s) failed to push",
+                summary.files_failed,
+                args.src.len()
+            )));
+        }
+
+        if args.watch {
+            self.watch(ctx, &device, &args).await?;
+        }
         Ok(())
     }
 }
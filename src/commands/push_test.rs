@@ -0,0 +1,36 @@
+use super::push::{parse_chunk_size, remote_path_for};
+use std::path::Path;
+
+#[test]
+fn test_remote_path_for_directory() {
+    let src = Path::new("/home/me/app/assets");
+    assert_eq!(
+        remote_path_for(src, "/data/local/tmp/", Path::new("/home/me/app/assets/css/main.css")),
+        Some("/data/local/tmp/assets/css/main.css".to_string())
+    );
+    assert_eq!(
+        remote_path_for(src, "/data/local/tmp", Path::new("/home/me/app/assets/index.html")),
+        Some("/data/local/tmp/assets/index.html".to_string())
+    );
+    assert_eq!(remote_path_for(src, "/data/local/tmp", Path::new("/home/me/app/other.txt")), None);
+}
+
+#[test]
+fn test_remote_path_for_file() {
+    let src = Path::new("/home/me/app/lib.so");
+    assert_eq!(
+        remote_path_for(src, "/data/local/tmp/", src),
+        Some("/data/local/tmp/lib.so".to_string())
+    );
+    assert_eq!(
+        remote_path_for(src, "/data/local/tmp/libfoo.so", src),
+        Some("/data/local/tmp/libfoo.so".to_string())
+    );
+}
+
+#[test]
+fn test_parse_chunk_size() {
+    assert_eq!(parse_chunk_size("32K"), Ok(32 * 1024));
+    assert!(parse_chunk_size("1K").is_err());
+    assert!(parse_chunk_size("1M").is_err());
+}
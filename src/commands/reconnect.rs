@@ -0,0 +1,222 @@
+use crate::adb::server::AdbServer;
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::device::DeviceManager;
+use crate::error::{AimError, Result};
+use crate::state::{is_network_serial, StateStore};
+use crate::utils::parse_duration;
+use async_trait::async_trait;
+use colored::*;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+/// Wait before trying a device again after a failed connect
+const RETRY_DELAY: Duration = Duration::from_secs(30);
+
+pub struct ReconnectCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ReconnectArgs {
+    /// Only reconnect this device (serial, partial serial or alias)
+    pub device_id: Option<String>,
+
+    /// Keep running and reconnect devices whenever they come back
+    #[clap(short, long)]
+    pub watch: bool,
+
+    /// How often to check for missing devices in watch mode
+    #[clap(long, value_parser = parse_duration, default_value = "5s")]
+    pub interval: Duration,
+}
+
+/// A device the adb server sees advertising itself over mDNS
+#[derive(Debug, Clone, PartialEq)]
+pub struct MdnsService {
+    pub name: String,
+    pub service_type: String,
+    pub address: String,
+}
+
+/// Parse `host:mdns:services` output ("<name>\t<type>\t<ip:port>" per line)
+pub fn parse_mdns_services(output: &str) -> Vec<MdnsService> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(MdnsService {
+                name: fields.next()?.to_string(),
+                service_type: fields.next()?.trim_end_matches('.').to_string(),
+                address: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// A wireless device that was connected before
+#[derive(Debug, Clone, PartialEq)]
+pub struct WirelessTarget {
+    /// Last `ip:port` it was connected through
+    pub address: String,
+    /// Hardware serial, when known, which mDNS service names carry
+    pub serial: Option<String>,
+}
+
+impl WirelessTarget {
+    /// Whether one of the connected serials is this device
+    pub fn is_connected(&self, connected: &[String]) -> bool {
+        let ip = host_of(&self.address);
+        connected.iter().any(|s| {
+            (is_network_serial(s) && host_of(s) == ip)
+                || self
+                    .serial
+                    .as_deref()
+                    .is_some_and(|hw| s == hw || s.starts_with(&format!("adb-{}-", hw)))
+        })
+    }
+
+    /// The connectable mDNS service this device currently advertises
+    pub fn find_service<'a>(&self, services: &'a [MdnsService]) -> Option<&'a MdnsService> {
+        let ip = host_of(&self.address);
+        services
+            .iter()
+            .filter(|s| s.service_type == "_adb-tls-connect._tcp" || s.service_type == "_adb._tcp")
+            .find(|s| {
+                self.serial
+                    .as_deref()
+                    .is_some_and(|hw| s.name.starts_with(&format!("adb-{}-", hw)))
+                    || host_of(&s.address) == ip
+            })
+    }
+}
+
+fn host_of(address: &str) -> &str {
+    address.rsplit_once(':').map_or(address, |(host, _)| host)
+}
+
+/// Wireless devices from the state store, one per address
+///
+/// A device connected over the network appears both under its `ip:port` serial
+/// and, once its properties were read, under its hardware serial; the two
+/// entries are merged so the hardware serial can be matched against mDNS names.
+pub fn wireless_targets(store: &StateStore) -> Vec<WirelessTarget> {
+    let mut targets: BTreeMap<String, Option<String>> = BTreeMap::new();
+    for device in store.devices.values() {
+        let Some(address) = &device.wireless_ip else {
+            continue;
+        };
+        let serial = (!is_network_serial(&device.serial)).then(|| device.serial.clone());
+        let entry = targets.entry(address.clone()).or_default();
+        if serial.is_some() {
+            *entry = serial;
+        }
+    }
+    targets
+        .into_iter()
+        .map(|(address, serial)| WirelessTarget { address, serial })
+        .collect()
+}
+
+impl ReconnectCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn log_event(message: String) {
+        log::info!("{}", message);
+        println!("{} {}", chrono::Local::now().format("%H:%M:%S").to_string().dimmed(), message);
+    }
+
+    /// Connect every missing target whose mDNS service is visible
+    ///
+    /// Returns how many devices were reconnected.
+    async fn pass(ctx: &CommandContext, targets: &[WirelessTarget], attempts: &mut HashMap<String, Instant>) -> Result<usize> {
+        let (host, port) = ctx.adb_address();
+        let connected: Vec<String> = DeviceManager::with_address(host, port.to_string())
+            .list_devices()
+            .await?
+            .iter()
+            .map(|d| d.id.to_string())
+            .collect();
+        let services = parse_mdns_services(&AdbServer::mdns_services(host, port).await?);
+
+        let mut reconnected = 0;
+        for target in targets {
+            if target.is_connected(&connected) {
+                attempts.remove(&target.address);
+                continue;
+            }
+            let Some(service) = target.find_service(&services) else {
+                continue;
+            };
+            if attempts.get(&target.address).is_some_and(|at| at.elapsed() < RETRY_DELAY) {
+                continue;
+            }
+            attempts.insert(target.address.clone(), Instant::now());
+
+            let name = target.serial.as_deref().unwrap_or(&target.address);
+            let reply = AdbServer::connect(host, port, &service.address).await?;
+            if reply.starts_with("connected") || reply.starts_with("already connected") {
+                Self::log_event(format!("{} {} reconnected via {}", "✓".green(), name, service.address));
+                attempts.remove(&target.address);
+                reconnected += 1;
+            } else {
+                Self::log_event(format!("{} {}: {}", "✗".red(), name, reply.trim()));
+            }
+        }
+        Ok(reconnected)
+    }
+}
+
+#[async_trait]
+impl SubCommand for ReconnectCommand {
+    type Args = ReconnectArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let load_targets = || -> Result<Vec<WirelessTarget>> {
+            let store = StateStore::load();
+            let mut targets = wireless_targets(&store);
+            if let Some(query) = &args.device_id {
+                let known = store.resolve(Some(query))?;
+                let address = known.wireless_ip.clone().ok_or_else(|| {
+                    AimError::InvalidArgument(format!("{} was never connected over the network", known.serial))
+                })?;
+                targets.retain(|t| t.address == address);
+            }
+            Ok(targets)
+        };
+
+        let targets = load_targets()?;
+        if targets.is_empty() {
+            return Err(AimError::DeviceNotFound("no wireless devices in the local cache".to_string()));
+        }
+
+        let mut attempts = HashMap::new();
+        if !args.watch {
+            let reconnected = Self::pass(ctx, &targets, &mut attempts).await?;
+            if reconnected == 0 {
+                println!("Nothing to reconnect");
+            }
+            return Ok(());
+        }
+
+        eprintln!(
+            "Watching {} wireless device(s) every {}, Ctrl-C to stop",
+            targets.len(),
+            crate::utils::format_duration(args.interval)
+        );
+        loop {
+            // Reload so addresses learned since the last pass are used
+            let targets = load_targets().unwrap_or_else(|_| targets.clone());
+            if let Err(e) = Self::pass(ctx, &targets, &mut attempts).await {
+                log::debug!("Reconnect pass failed: {}", e);
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(args.interval) => {}
+                _ = tokio::signal::ctrl_c() => break,
+            }
+        }
+
+        Ok(())
+    }
+}
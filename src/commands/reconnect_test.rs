@@ -0,0 +1,71 @@
+use super::reconnect::{parse_mdns_services, wireless_targets, MdnsService, WirelessTarget};
+use crate::state::StateStore;
+use chrono::Utc;
+use std::collections::HashMap;
+
+const MDNS: &str = "adb-R58M123ABC-vWgJpq\t_adb-tls-connect._tcp.\t192.168.1.20:41235\n\
+adb-R58M123ABC-vWgJpq\t_adb-tls-pairing._tcp.\t192.168.1.20:37099\n\
+adb-ZY22XYZ-abcd\t_adb-tls-connect._tcp.\t192.168.1.31:40001\n";
+
+#[test]
+fn test_parse_mdns_services() {
+    let services = parse_mdns_services(MDNS);
+    assert_eq!(services.len(), 3);
+    assert_eq!(
+        services[0],
+        MdnsService {
+            name: "adb-R58M123ABC-vWgJpq".to_string(),
+            service_type: "_adb-tls-connect._tcp".to_string(),
+            address: "192.168.1.20:41235".to_string(),
+        }
+    );
+    assert!(parse_mdns_services("").is_empty());
+}
+
+#[test]
+fn test_wireless_targets_merge_entries() {
+    let mut store = StateStore::default();
+    let now = Utc::now();
+    let props = HashMap::from([("ro.serialno".to_string(), "R58M123ABC".to_string())]);
+    store.record_props("192.168.1.20:5555", &props, now);
+    store.record_props("emulator-5554", &HashMap::new(), now);
+
+    let mut wifi = store.devices["192.168.1.20:5555"].clone();
+    wifi.wireless_ip = Some("192.168.1.20:5555".to_string());
+    store.devices.insert(wifi.serial.clone(), wifi);
+
+    assert_eq!(
+        wireless_targets(&store),
+        vec![WirelessTarget {
+            address: "192.168.1.20:5555".to_string(),
+            serial: Some("R58M123ABC".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn test_target_connected_and_service() {
+    let target = WirelessTarget {
+        address: "192.168.1.20:5555".to_string(),
+        serial: Some("R58M123ABC".to_string()),
+    };
+    // Wireless debugging picks a new port each time
+    assert!(target.is_connected(&["192.168.1.20:41235".to_string()]));
+    assert!(target.is_connected(&["adb-R58M123ABC-vWgJpq._adb-tls-connect._tcp".to_string()]));
+    assert!(!target.is_connected(&["192.168.1.31:5555".to_string(), "emulator-5554".to_string()]));
+
+    let services = parse_mdns_services(MDNS);
+    assert_eq!(target.find_service(&services).unwrap().address, "192.168.1.20:41235");
+
+    let by_ip = WirelessTarget {
+        address: "192.168.1.31:5555".to_string(),
+        serial: None,
+    };
+    assert_eq!(by_ip.find_service(&services).unwrap().address, "192.168.1.31:40001");
+
+    let gone = WirelessTarget {
+        address: "10.0.0.5:5555".to_string(),
+        serial: Some("OTHER".to_string()),
+    };
+    assert_eq!(gone.find_service(&services), None);
+}
@@ -1,7 +1,8 @@
+use crate::adb::shell::ShellCommand;
+use crate::adb::su::RootMode;
 use crate::commands::SubCommand;
 use crate::core::context::CommandContext;
-use crate::error::Result;
-use crate::library::adb::run_shell_command_async;
+use crate::error::{AimError, Result};
 use async_trait::async_trait;
 use std::time::Duration;
 use tokio::time::sleep;
@@ -23,6 +24,14 @@ pub struct RunArgs {
     /// Watch mode - repeat command every second. Optional value specifies duration in seconds
     #[clap(short = 'w', long = "watch", num_args = 0..=1, default_missing_value = "0")]
     pub watch: Option<u32>,
+
+    /// Run as root through su (or adbd, if it already runs as root)
+    #[clap(long)]
+    pub as_root: bool,
+
+    /// Stop the command if it runs longer than this, e.g. 30s (exit status 124)
+    #[clap(long, value_parser = crate::utils::parse_duration)]
+    pub timeout: Option<Duration>,
 }
 
 impl RunCommand {
@@ -37,7 +46,7 @@ impl SubCommand for RunCommand {
     
     async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
         let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
         
         // Device filtering is a planned feature for running commands on filtered device sets
         if !args.filters.is_empty() {
@@ -46,6 +55,8 @@ impl SubCommand for RunCommand {
             eprintln!("Executing command on selected device instead.\n");
         }
         
+        let root_mode = RootMode::for_args(args.as_root, host, port, &device.id).await?;
+
         if let Some(duration) = args.watch {
             // Watch mode
             let interval = if duration == 0 { 1 } else { duration };
@@ -53,7 +64,11 @@ impl SubCommand for RunCommand {
             println!("Press Ctrl+C to stop\n");
             
             loop {
-                self.execute_command(host, port, &device.id, &args.command).await?;
+                // A failing run is shown and watched again, like `watch` does
+                match self.execute_command(host, port, &device.id, &args, root_mode).await {
+                    Err(AimError::ExitStatus(_)) => {}
+                    other => other?,
+                }
                 
                 // Clear screen for next iteration
                 print!("\x1B[2J\x1B[H");
@@ -64,32 +79,40 @@ impl SubCommand for RunCommand {
             }
         } else {
             // Single execution
-            self.execute_command(host, port, &device.id, &args.command).await
+            self.execute_command(host, port, &device.id, &args, root_mode).await
         }
     }
 }
 
 impl RunCommand {
+    /// Run once, passing on stdout, stderr and a failing exit status
     async fn execute_command(
         &self,
         host: &str,
         port: u16,
         device_id: &crate::core::types::DeviceId,
-        command: &str,
+        args: &RunArgs,
+        root_mode: RootMode,
     ) -> Result<()> {
-        let device_id_str = device_id.to_string();
-        let port_str = port.to_string();
-        
-        let output = run_shell_command_async(host, &port_str, command, Some(&device_id_str)).await?;
-        
-        // Print output
-        if !output.is_empty() {
-            print!("{}", output);
-            if !output.ends_with('\n') {
-                println!();
-            }
+        let command = if root_mode.is_root() { root_mode.wrap(&args.command) } else { args.command.clone() };
+        let output = ShellCommand::new(command)
+            .with_device(device_id.clone())
+            .execute_cancellable(host, port, args.timeout)
+            .await?;
+
+        if !output.stdout.is_empty() {
+            println!("{}", output.stdout);
         }
-        
-        Ok(())
+        if !output.stderr.is_empty() {
+            eprintln!("{}", output.stderr);
+        }
+        if output.success() {
+            return Ok(());
+        }
+        // Root itself was checked beforehand, so this is the command failing rather than su refusing
+        if root_mode.is_root() {
+            eprintln!("Command failed as root with exit code {}", output.exit_code);
+        }
+        Err(AimError::ExitStatus(output.exit_code))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,204 @@
+//! Rhai scripts with access to devices
+//!
+//! Where `aim script` runs a fixed list of commands, `aim run-script` runs a
+//! [Rhai](https://rhai.rs) program, so flows can branch and loop. The API:
+//!
+//! - `devices()` connected devices as maps with `id`, `state`, `model`, `alias`
+//! - `device()` serial of the selected device
+//! - `shell(cmd)`, `shell(serial, cmd)` run a command, returning its output
+//! - `getprop(name)` read a property of the selected device
+//! - `push(local, remote)`, `pull(remote, local)` transfer files
+//! - `screenshot(path)` save a screenshot of the selected device
+//! - `sleep(seconds)` pause, with an integer or fractional number of seconds
+//!
+//! Arguments after the script path are available as the `args` array.
+
+use crate::adb::shell::ShellCommand;
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::{Device, DeviceId};
+use crate::device::DeviceManager;
+use crate::error::{AimError, Result};
+use crate::library::adb::ProgressDisplay;
+use async_trait::async_trait;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope, FLOAT, INT};
+use std::cell::OnceCell;
+use std::future::Future;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
+use tokio::runtime::Handle;
+
+type ScriptResult<T> = std::result::Result<T, Box<EvalAltResult>>;
+
+pub struct RunScriptCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct RunScriptArgs {
+    /// Rhai script to run
+    pub script: PathBuf,
+
+    /// Arguments for the script, available as `args`
+    #[clap(trailing_var_arg = true)]
+    pub args: Vec<String>,
+
+    /// Device ID for device(), shell(), getprop() and the transfer functions
+    #[clap(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// A device as scripts see it
+pub fn device_map(device: &Device) -> Map {
+    let mut map = Map::new();
+    map.insert("id".into(), device.id.to_string().into());
+    map.insert("state".into(), device.state.to_string().into());
+    map.insert("model".into(), device.model.clone().unwrap_or_default().into());
+    map.insert("alias".into(), device.alias.clone().unwrap_or_default().into());
+    map
+}
+
+fn script_error(e: impl std::fmt::Display) -> Box<EvalAltResult> {
+    e.to_string().into()
+}
+
+/// State shared by the functions registered with the engine
+struct ScriptApi {
+    handle: Handle,
+    ctx: CommandContext,
+    device_arg: Option<String>,
+    selected: OnceCell<Device>,
+}
+
+impl ScriptApi {
+    /// Run async code from a script function; the engine runs on a blocking thread
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.handle.block_on(future)
+    }
+
+    /// The selected device, resolved on first use
+    fn device(&self) -> ScriptResult<Device> {
+        if let Some(device) = self.selected.get() {
+            return Ok(device.clone());
+        }
+        let device = self
+            .block_on(crate::commands::get_device(self.device_arg.as_deref()))
+            .map_err(script_error)?;
+        Ok(self.selected.get_or_init(|| device).clone())
+    }
+
+    fn shell(&self, serial: &str, command: &str) -> ScriptResult<String> {
+        let (host, port) = self.ctx.adb_address();
+        let output = self
+            .block_on(ShellCommand::new(command).with_device(DeviceId::new(serial)).execute(host, port))
+            .map_err(script_error)?;
+        Ok(output.stdout.trim_end().to_string())
+    }
+
+    fn transfer(&self, src: &str, dst: &str, push: bool) -> ScriptResult<()> {
+        let serial = self.device()?.id.to_string();
+        let (host, port) = self.ctx.adb_address();
+        let (src, dst) = (PathBuf::from(src), PathBuf::from(dst));
+        let port = port.to_string();
+        let result = if push {
+            self.block_on(crate::library::adb::push(
+                host,
+                &port,
+                Some(&serial),
+                &src,
+                &dst,
+                false,
+                ProgressDisplay::Hide,
+            ))
+        } else {
+            self.block_on(crate::library::adb::pull(host, &port, Some(&serial), &src, &dst, ProgressDisplay::Hide))
+        };
+        result.map(|_| ()).map_err(script_error)
+    }
+}
+
+/// An engine with the device API registered
+fn build_engine(api: Rc<ScriptApi>) -> Engine {
+    let mut engine = Engine::new();
+
+    let a = api.clone();
+    engine.register_fn("devices", move || -> ScriptResult<Array> {
+        let (host, port) = a.ctx.adb_address();
+        let devices = a
+            .block_on(DeviceManager::with_address(host, port.to_string()).list_devices())
+            .map_err(script_error)?;
+        Ok(devices.iter().map(|d| Dynamic::from_map(device_map(d))).collect())
+    });
+
+    let a = api.clone();
+    engine.register_fn("device", move || -> ScriptResult<String> { Ok(a.device()?.id.to_string()) });
+
+    let a = api.clone();
+    engine.register_fn("shell", move |command: &str| -> ScriptResult<String> {
+        let serial = a.device()?.id.to_string();
+        a.shell(&serial, command)
+    });
+
+    let a = api.clone();
+    engine.register_fn("shell", move |serial: &str, command: &str| a.shell(serial, command));
+
+    let a = api.clone();
+    engine.register_fn("getprop", move |name: &str| -> ScriptResult<String> {
+        let serial = a.device()?.id.to_string();
+        a.shell(&serial, &format!("getprop {}", crate::utils::shell_quote(name)))
+    });
+
+    let a = api.clone();
+    engine.register_fn("push", move |local: &str, remote: &str| a.transfer(local, remote, true));
+
+    let a = api.clone();
+    engine.register_fn("pull", move |remote: &str, local: &str| a.transfer(remote, local, false));
+
+    let a = api;
+    engine.register_fn("screenshot", move |path: &str| -> ScriptResult<()> {
+        let device = a.device()?;
+        let command = crate::commands::screenshot::ScreenshotCommand::new();
+        a.block_on(command.take_screenshot(&a.ctx, &device, &PathBuf::from(path), &[]))
+            .map_err(script_error)
+    });
+
+    engine.register_fn("sleep", |seconds: INT| std::thread::sleep(Duration::from_secs(seconds.max(0) as u64)));
+    engine.register_fn("sleep", |seconds: FLOAT| std::thread::sleep(Duration::from_secs_f64(seconds.max(0.0))));
+
+    engine
+}
+
+impl RunScriptCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for RunScriptCommand {
+    type Args = RunScriptArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let handle = Handle::current();
+        let script = args.script.clone();
+        let ctx = ctx.clone();
+
+        // The engine blocks, so it gets its own thread and drives async calls through the handle
+        tokio::task::spawn_blocking(move || {
+            let api = Rc::new(ScriptApi {
+                handle,
+                ctx,
+                device_arg: args.device_id,
+                selected: OnceCell::new(),
+            });
+            let engine = build_engine(api);
+            let mut scope = Scope::new();
+            let script_args: Array = args.args.into_iter().map(Dynamic::from).collect();
+            scope.push("args", script_args);
+            engine
+                .run_file_with_scope(&mut scope, script)
+                .map_err(|e| AimError::CommandExecution(format!("{}: {}", args.script.display(), e)))
+        })
+        .await
+        .map_err(|e| AimError::Other(format!("Script thread failed: {}", e)))?
+    }
+}
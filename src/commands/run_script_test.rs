@@ -0,0 +1,15 @@
+use super::run_script::device_map;
+use crate::core::types::DeviceState;
+use crate::testing::test_device;
+
+#[test]
+fn test_device_map() {
+    let mut device = test_device("abc123").with_state(DeviceState::Unauthorized).with_model("Pixel 8");
+    device.alias = Some("work".to_string());
+
+    let map = device_map(&device);
+    assert_eq!(map["id"].clone().into_string().unwrap(), "abc123");
+    assert_eq!(map["state"].clone().into_string().unwrap(), "unauthorized");
+    assert_eq!(map["model"].clone().into_string().unwrap(), "Pixel 8");
+    assert_eq!(map["alias"].clone().into_string().unwrap(), "work");
+}
@@ -7,6 +7,7 @@ use crate::commands::{
     server::{ServerCommand, ServerArgs},
     adb::{AdbCommand, AdbArgs},
     config::{ConfigCommand, ConfigArgs},
+    clean::{CleanCommand, CleanArgs},
     dmesg::{DmesgCommand, DmesgArgs},
     perfetto::{PerfettoCommand, PerfettoArgs},
     screenrecord::{ScreenrecordCommand, ScreenrecordArgs},
@@ -17,7 +18,10 @@ use crate::commands::{
     shell::{ShellCommand, ShellArgs},
     SubCommand,
 };
-use crate::core::context::CommandContextBuilder;
+use crate::adb::ssh::SshTunnel;
+use crate::config::{Config, ProfileConfig};
+use crate::core::context::{CommandContext, CommandContextBuilder};
+use crate::core::notify;
 use crate::core::types::OutputFormat;
 use crate::device::DeviceManager;
 use crate::error::{AimError, Result};
@@ -60,16 +64,20 @@ impl CommandRunner {
             crate::cli::OutputType::Plain => OutputFormat::Plain,
         };
         context_builder = context_builder.output_format(output_format);
+
+        let (host, port) = get_adb_connection_params();
+        context_builder = context_builder.address(host, port);
         
-        // Set verbose mode
-        let verbose_level = cli.verbose.log_level();
-        context_builder = context_builder.verbose(verbose_level.is_some());
+        // Set verbose and quiet mode from -v/-q
+        context_builder = context_builder
+            .verbose(cli.verbose.log_level_filter() > log::LevelFilter::Error)
+            .quiet(cli.verbose.is_silent());
         
         let ctx = context_builder.build();
         
         // Route to appropriate command
         match cli.command() {
-            Commands::Ls { output } => {
+            Commands::Ls { output, known, cached, long, columns, state, usb, wireless, sort, watch, interval } => {
                 let cmd = LsCommand::new();
                 let output_str = match output {
                     crate::cli::OutputType::Table => "table",
@@ -78,29 +86,122 @@ impl CommandRunner {
                 };
                 let args = LsArgs {
                     output: output_str.to_string(),
+                    known,
+                    cached,
+                    long,
+                    columns,
+                    state,
+                    usb,
+                    wireless,
+                    sort,
+                    watch,
+                    interval,
                 };
                 cmd.run(&ctx, args).await?;
             }
-            Commands::Run { command, device_id, filters, watch } => {
+            Commands::Run { command, device_id, filters, watch, as_root, kill_after } => {
+                let ctx = with_target_device(ctx, device_id.as_deref()).await?;
                 let cmd = RunCommand::new();
                 let args = RunArgs {
                     command,
                     device_id,
                     filters,
                     watch,
+                    as_root,
+                    timeout: kill_after,
                 };
                 cmd.run(&ctx, args).await?;
             }
+            Commands::RunScript { script, args, device_id } => {
+                let cmd = crate::commands::run_script::RunScriptCommand::new();
+                let args = crate::commands::run_script::RunScriptArgs { script, args, device_id };
+                cmd.run(&ctx, args).await?;
+            }
             Commands::Copy { src, dst } => {
                 let cmd = CopyCommand::new();
                 let args = CopyArgs { src, dst };
                 cmd.run(&ctx, args).await?;
             }
+            Commands::Pair { address, code, qr, wait } => {
+                let cmd = crate::commands::pair::PairCommand::new();
+                let args = crate::commands::pair::PairArgs { address, code, qr, wait };
+                cmd.run(&ctx, args).await?;
+            }
+            Commands::Broadcasts { package, follow, interval, device_id } => {
+                let cmd = crate::commands::broadcasts::BroadcastsCommand::new();
+                let args = crate::commands::broadcasts::BroadcastsArgs { package, follow, interval, device_id };
+                cmd.run(&ctx, args).await?;
+            }
+            Commands::Ime { command } => {
+                crate::commands::ime::run(&ctx, command).await?;
+            }
+            Commands::Intent { command } => {
+                crate::commands::intent::run(&ctx, command).await?;
+            }
+            Commands::Kiosk { package, device_id } => {
+                let cmd = crate::commands::kiosk::KioskCommand::new();
+                let args = crate::commands::kiosk::KioskArgs { package, device_id };
+                cmd.run(&ctx, args).await?;
+            }
+            Commands::Logcat { tags, priority, clear, device_id } => {
+                let cmd = crate::commands::logcat::LogcatCommand::new();
+                let args = crate::commands::logcat::LogcatArgs { tags, priority, clear, device_id };
+                cmd.run(&ctx, args).await?;
+            }
+            Commands::Provision { script, vars, factory_reset, skip_setup_wizard, yes, boot_timeout, device_id } => {
+                let cmd = crate::commands::provision::ProvisionCommand::new();
+                let args = crate::commands::provision::ProvisionArgs {
+                    script,
+                    vars,
+                    factory_reset,
+                    skip_setup_wizard,
+                    yes,
+                    boot_timeout,
+                    device_id,
+                };
+                cmd.run(&ctx, args).await?;
+            }
+            Commands::Reconnect { device_id, watch, interval } => {
+                let cmd = crate::commands::reconnect::ReconnectCommand::new();
+                let args = crate::commands::reconnect::ReconnectArgs { device_id, watch, interval };
+                cmd.run(&ctx, args).await?;
+            }
             Commands::Rename { device_id, new_name } => {
                 let cmd = RenameCommand::new();
                 let args = RenameArgs { device_id, new_name };
                 cmd.run(&ctx, args).await?;
             }
+            Commands::Soak {
+                device_id,
+                reboots,
+                between,
+                expect_props,
+                expect_packages,
+                boot_timeout,
+                stop_on_failure,
+            } => {
+                let cmd = crate::commands::soak::SoakCommand::new();
+                let args = crate::commands::soak::SoakArgs {
+                    device_id,
+                    reboots,
+                    between,
+                    expect_props,
+                    expect_packages,
+                    boot_timeout,
+                    stop_on_failure,
+                };
+                cmd.run(&ctx, args).await?;
+            }
+            Commands::Script { file, command, vars, device_id } => {
+                let cmd = crate::commands::script::ScriptCommand::new();
+                let args = crate::commands::script::ScriptArgs { file, command, vars, device_id };
+                cmd.run(&ctx, args).await?;
+            }
+            Commands::SendText { input, literal, method, chunk_size, device_id } => {
+                let cmd = crate::commands::send_text::SendTextCommand::new();
+                let args = crate::commands::send_text::SendTextArgs { input, literal, method, chunk_size, device_id };
+                cmd.run(&ctx, args).await?;
+            }
             Commands::Server { operation } => {
                 let cmd = ServerCommand::new();
                 let args = ServerArgs { operation };
@@ -111,55 +212,159 @@ impl CommandRunner {
                 let args = AdbArgs { command, device_id };
                 cmd.run(&ctx, args).await?;
             }
+            Commands::Clean { device_id, caches, tmp, apks, dry_run, yes } => {
+                let cmd = CleanCommand::new();
+                let args = CleanArgs { device_id, caches, tmp, apks, dry_run, yes };
+                cmd.run(&ctx, args).await?;
+            }
             Commands::Config => {
                 let cmd = ConfigCommand::new();
                 let args = ConfigArgs { path_only: false };
                 cmd.run(&ctx, args).await?;
             }
-            Commands::Dmesg { device_id, args: dmesg_args } => {
+            Commands::Dash { device_id, package, interval } => {
+                let cmd = crate::commands::dash::DashCommand::new();
+                let args = crate::commands::dash::DashArgs { device_id, package, interval };
+                cmd.run(&ctx, args).await?;
+            }
+            Commands::Dev { ports, devices, interval } => {
+                let cmd = crate::commands::dev::DevCommand::new();
+                let args = crate::commands::dev::DevArgs { ports, devices, interval };
+                cmd.run(&ctx, args).await?;
+            }
+            Commands::Discover { connect } => {
+                let cmd = crate::commands::discover::DiscoverCommand::new();
+                let args = crate::commands::discover::DiscoverArgs { connect };
+                cmd.run(&ctx, args).await?;
+            }
+            Commands::Dmesg { device_id, human_time, args: dmesg_args } => {
+                let ctx = with_target_device(ctx, device_id.as_deref()).await?;
                 let cmd = DmesgCommand::new();
-                let args = DmesgArgs { device_id, args: dmesg_args };
+                let args = DmesgArgs { device_id, human_time, args: dmesg_args };
                 cmd.run(&ctx, args).await?;
             }
-            Commands::Perfetto { config, device_id, output, time } => {
+            Commands::Perfetto { config, device_id, output, time, summary, upload } => {
+                let ctx = with_target_device(ctx, device_id.as_deref()).await?;
                 let cmd = PerfettoCommand::new();
-                let args = PerfettoArgs { device_id, config, time, output };
+                let args = PerfettoArgs { device_id, config, time, output, summary, upload };
                 cmd.run(&ctx, args).await?;
             }
-            Commands::Screenrecord { device_id, output, args: sr_args } => {
+            Commands::Screenrecord { device_id, output, with_logs, package, name_template, upload, args: sr_args } => {
+                let ctx = with_target_device(ctx, device_id.as_deref()).await?;
                 let cmd = ScreenrecordCommand::new();
-                let args = ScreenrecordArgs { device_id, output, args: sr_args };
+                let args = ScreenrecordArgs { device_id, output, with_logs, package, name_template, upload, args: sr_args };
                 cmd.run(&ctx, args).await?;
             }
-            Commands::Getprop { propnames, device_id, output } => {
+            Commands::Getprop { propnames, device_id, output, cached, export, compare, fail_on } => {
                 let cmd = GetpropCommand::new();
-                let args = GetpropArgs { propnames, device_id, output };
+                let args = GetpropArgs { propnames, device_id, output, cached, export, compare, fail_on };
                 cmd.run(&ctx, args).await?;
             }
-            Commands::Screenshot { args: ss_args, device_id, interactive, output } => {
+            Commands::Screenshot {
+                args: ss_args,
+                device_id,
+                interactive,
+                output,
+                crop,
+                scale,
+                annotate,
+                timestamp,
+                every,
+                count,
+                duration,
+                until_key,
+                name_template,
+                upload,
+            } => {
+                let ctx = with_target_device(ctx, device_id.as_deref()).await?;
                 let cmd = ScreenshotCommand::new();
-                let args = ScreenshotArgs { device_id, interactive, output, args: ss_args };
+                let args = ScreenshotArgs {
+                    device_id,
+                    interactive,
+                    output,
+                    crop,
+                    scale,
+                    annotate,
+                    timestamp,
+                    every,
+                    count,
+                    duration,
+                    until_key,
+                    name_template,
+                    upload,
+                    args: ss_args,
+                };
                 cmd.run(&ctx, args).await?;
             }
-            Commands::App { .. } => {
-                // App commands are still handled by the old implementation
-                return Err(AimError::Other("App commands not yet migrated to new runner".to_string()));
+            Commands::App { command } => {
+                // Every app subcommand but compare works on one device, selected up front
+                let ctx = if command.all_devices() {
+                    ctx
+                } else {
+                    with_target_device(ctx, command.device_id()).await?
+                };
+                crate::commands::app::run(&ctx, command).await?;
             }
-            Commands::Push { src, dst, device_id, recursive } => {
+            Commands::Push { src, dst, device_id, recursive, chunk_size, watch, exec, debounce } => {
                 let cmd = PushCommand::new();
-                let args = PushArgs { src, dst, device_id, recursive };
+                let args = PushArgs { src, dst, device_id, recursive, chunk_size, watch, exec, debounce };
                 cmd.run(&ctx, args).await?;
             }
-            Commands::Pull { src, dst, device_id } => {
+            Commands::Pull { paths, device_id, watch, interval } => {
+                let (src, dst) = crate::commands::pull::split_paths(paths);
                 let cmd = PullCommand::new();
-                let args = PullArgs { src, dst, device_id };
+                let args = PullArgs { src, dst, device_id, watch, interval };
                 cmd.run(&ctx, args).await?;
             }
-            Commands::Shell { command, device_id } => {
+            Commands::Shell { command, device_id, kill_after, record } => {
                 let cmd = ShellCommand::new();
-                let args = ShellArgs { command, device_id };
+                let args = ShellArgs { command, device_id, timeout: kill_after, record };
+                cmd.run(&ctx, args).await?;
+            }
+            Commands::Fleet { command } => {
+                crate::commands::fleet::run(&ctx, command).await?;
+            }
+            Commands::Sync { local, remote, device_id, bidirectional, dry_run } => {
+                let cmd = crate::commands::sync::SyncCommand::new();
+                let args = crate::commands::sync::SyncArgs { local, remote, device_id, bidirectional, dry_run };
                 cmd.run(&ctx, args).await?;
             }
+            Commands::Forward { command, args } => {
+                crate::commands::forward::run(&ctx, command, args).await?;
+            }
+            Commands::Fs { command } => {
+                crate::commands::fs::run(&ctx, command).await?;
+            }
+            Commands::Battery { command } => {
+                crate::commands::battery::run(&ctx, command).await?;
+            }
+            Commands::A11y { command } => {
+                crate::commands::a11y::run(&ctx, command).await?;
+            }
+            Commands::Activity { command } => {
+                crate::commands::activity::run(&ctx, command).await?;
+            }
+            Commands::Boot { command } => {
+                crate::commands::boot::run(&ctx, command).await?;
+            }
+            Commands::Cert { command } => {
+                crate::commands::cert::run(&ctx, command).await?;
+            }
+            Commands::Tombstone { command } => {
+                crate::commands::tombstone::run(&ctx, command).await?;
+            }
+            Commands::Dpm { command } => {
+                crate::commands::dpm::run(&ctx, command).await?;
+            }
+            Commands::Ui { command } => {
+                crate::commands::ui::run(&ctx, command).await?;
+            }
+            Commands::Wifi { command } => {
+                crate::commands::wifi::run(&ctx, command).await?;
+            }
+            Commands::External(args) => {
+                crate::commands::plugin::run(args).await?;
+            }
         }
 
         Ok(())
@@ -172,20 +377,111 @@ impl CommandRunner {
     }
 }
 
-/// Helper to get the default ADB host and port
-#[allow(dead_code)]
-pub fn get_adb_connection_params() -> (&'static str, u16) {
-    let host = std::env::var("ADB_SERVER_HOST").unwrap_or_else(|_| "localhost".to_string());
-    let port = std::env::var("ADB_SERVER_PORT")
-        .ok()
-        .and_then(|p| p.parse().ok())
+/// `ctx` with the device named by `device_id` (or `AIM_DEVICE`) selected
+async fn with_target_device(ctx: CommandContext, device_id: Option<&str>) -> Result<CommandContext> {
+    let (host, port) = ctx.adb_address();
+    let device_manager = DeviceManager::with_address(host, port.to_string());
+    let device_id = crate::commands::resolve_device_alias(device_id);
+    let device = device_manager.get_target_device(device_id.as_deref()).await?;
+    Ok(ctx.with_device(device))
+}
+
+/// Run one parsed command; also used by `aim script` for each of its lines
+pub async fn execute(cli: Cli) -> Result<()> {
+    // Kept open until the command finishes
+    let _tunnel = connect_adb_server(&cli).await?;
+    if cli.all {
+        crate::core::fanout::check(&cli.command())?;
+        return crate::core::fanout::run(&cli.argv, matches!(cli.output, crate::cli::OutputType::Json)).await;
+    }
+    let long_running = notify::long_running(&cli.command());
+    let notify_flag = cli.notify;
+    let started = std::time::Instant::now();
+    debug!("Creating CommandRunner...");
+    let runner = CommandRunner::new().await?;
+    debug!("Running command through CommandRunner...");
+    let result = runner.run(cli).await;
+
+    if let Some(name) = long_running {
+        // Unattended runs only notify when asked to
+        let after = if crate::core::ci::is_enabled() {
+            None
+        } else {
+            Config::load().notify_after_secs.map(std::time::Duration::from_secs)
+        };
+        let elapsed = started.elapsed();
+        if notify::should_notify(notify_flag, after, elapsed) {
+            let error = result.as_ref().err().map(|e| e.to_string());
+            let (title, body) = notify::message(name, error.as_deref(), elapsed);
+            notify::send(&title, &body);
+        }
+    }
+    result
+}
+
+static ADB_ADDRESS: std::sync::OnceLock<(String, u16)> = std::sync::OnceLock::new();
+
+/// ADB server address from `--host`/`--port`, then ADB_SERVER_HOST/ADB_SERVER_PORT
+pub fn resolve_adb_address(host: Option<String>, port: Option<u16>) -> (String, u16) {
+    let host = host
+        .or_else(|| std::env::var("ADB_SERVER_HOST").ok())
+        .unwrap_or_else(|| "localhost".to_string());
+    let port = port
+        .or_else(|| std::env::var("ADB_SERVER_PORT").ok().and_then(|p| p.parse().ok()))
         .unwrap_or(5037);
-    
-    // Return static string for host
-    if host == "localhost" {
-        ("localhost", port)
-    } else {
-        // In a real implementation, we'd handle this better
-        ("localhost", port)
+    (host, port)
+}
+
+/// Set the ADB server address for the rest of the process
+///
+/// Only the first call counts, so commands run by `aim script` share the
+/// address given to the script itself.
+pub fn configure_adb_address(host: Option<String>, port: Option<u16>) {
+    let _ = ADB_ADDRESS.set(resolve_adb_address(host, port));
+}
+
+/// Point aim at the ADB server from the flags and the selected profile,
+/// opening an SSH tunnel first when one is asked for
+async fn connect_adb_server(cli: &Cli) -> Result<Option<SshTunnel>> {
+    // Commands run by `aim script` reuse the script's server
+    if ADB_ADDRESS.get().is_some() {
+        return Ok(None);
+    }
+
+    let profile = match &cli.profile {
+        Some(name) => {
+            let config_path = dirs::home_dir()
+                .map(|p| p.join(".config/aim/config.toml"))
+                .unwrap_or_else(|| std::path::PathBuf::from(".config/aim/config.toml"));
+            let config = Config::load_from_path(&config_path);
+            config
+                .profiles
+                .get(name)
+                .cloned()
+                .ok_or_else(|| AimError::Configuration(format!("No [profile.{}] in {}", name, config_path.display())))?
+        }
+        None => ProfileConfig::default(),
+    };
+    let host = cli.host.clone().or(profile.host);
+    let port = cli.port.or(profile.port);
+
+    match cli.via_ssh.clone().or(profile.via_ssh) {
+        Some(target) => {
+            // Host and port are where the server listens as seen from the SSH host
+            let remote_host = host.unwrap_or_else(|| "localhost".to_string());
+            let tunnel = SshTunnel::open(&target, &remote_host, port.unwrap_or(5037)).await?;
+            configure_adb_address(Some(crate::adb::ssh::LOCAL_HOST.to_string()), Some(tunnel.local_port));
+            Ok(Some(tunnel))
+        }
+        None => {
+            configure_adb_address(host, port);
+            Ok(None)
+        }
     }
+}
+
+/// The configured ADB server host and port
+pub fn get_adb_connection_params() -> (&'static str, u16) {
+    let (host, port) = ADB_ADDRESS.get_or_init(|| resolve_adb_address(None, None));
+    (host.as_str(), *port)
 }
\ No newline at end of file
@@ -1,10 +1,11 @@
 use crate::commands::SubCommand;
 use crate::core::context::CommandContext;
-use crate::error::Result;
+use crate::error::{AimError, Result};
 use crate::library::adb::{run_shell_command_async, pull, ProgressDisplay};
 use crate::config::Config;
+use crate::core::artifact::{self, NameVars};
+use crate::core::upload;
 use async_trait::async_trait;
-use chrono::Local;
 use crossterm::{
     cursor::MoveToColumn,
     event::{self, Event, KeyCode},
@@ -13,7 +14,7 @@ use crossterm::{
 };
 use rand::{distr::Alphanumeric, Rng};
 use std::io::{stdout, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
 
@@ -27,16 +28,147 @@ pub struct ScreenrecordArgs {
     /// Output file or directory path
     #[clap(short = 'o', long = "output")]
     pub output: Option<PathBuf>,
+
+    /// Also save logcat for the recording window next to the video
+    #[clap(long)]
+    pub with_logs: bool,
+
+    /// Only keep log lines from this app (can be partial)
+    #[clap(long, requires = "with_logs")]
+    pub package: Option<String>,
+
+    /// File name template, e.g. "{alias}-{ts}" (see docs for the variables)
+    #[clap(long, value_name = "TEMPLATE")]
+    pub name_template: Option<String>,
+
+    /// Upload the result as set up in the [upload] config section
+    #[clap(long)]
+    pub upload: bool,
     
     /// Additional arguments to pass to screenrecord
     #[clap(trailing_var_arg = true)]
     pub args: Vec<String>,
 }
 
+/// Device clock in milliseconds since the epoch, from `date +%s.%N`
+///
+/// Older toybox builds print `%N` literally; the seconds are still usable.
+pub fn parse_epoch_millis(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let (secs, frac) = s.split_once('.').unwrap_or((s, ""));
+    let secs: u64 = secs.parse().ok()?;
+    let millis = if !frac.is_empty() && frac.chars().all(|c| c.is_ascii_digit()) {
+        format!("{:0<3}", &frac[..frac.len().min(3)]).parse().unwrap_or(0)
+    } else {
+        0
+    };
+    Some(secs * 1000 + millis)
+}
+
+/// The app uid from `pm list packages -U <package>` output
+pub fn parse_package_uid(output: &str, package: &str) -> Option<u32> {
+    output.lines().find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let name = fields.next()?.strip_prefix("package:")?;
+        if name != package {
+            return None;
+        }
+        fields.find_map(|f| f.strip_prefix("uid:")).and_then(|uid| uid.split(',').next()?.parse().ok())
+    })
+}
+
+/// Background logcat writing everything since `start_ms` to `file`; prints its pid
+pub fn logcat_command(start_ms: u64, uid: Option<u32>, file: &str) -> String {
+    let uid = uid.map(|uid| format!(" --uid={}", uid)).unwrap_or_default();
+    format!(
+        "logcat -v epoch -T {}.{:03}{} > {} 2>/dev/null & echo $!",
+        start_ms / 1000,
+        start_ms % 1000,
+        uid,
+        file
+    )
+}
+
+/// Position in the video, e.g. `01:02.345`
+pub fn format_offset(millis: u64) -> String {
+    format!("{:02}:{:02}.{:03}", millis / 60_000, (millis / 1000) % 60, millis % 1000)
+}
+
+/// Keep the `-v epoch` lines inside the recording window, each prefixed
+/// with its position in the video
+pub fn sync_log(raw: &str, start_ms: u64, end_ms: u64) -> String {
+    raw.lines()
+        .filter_map(|line| {
+            let at = line.split_whitespace().next().and_then(parse_epoch_millis)?;
+            (start_ms..=end_ms)
+                .contains(&at)
+                .then(|| format!("[{}] {}\n", format_offset(at - start_ms), line))
+        })
+        .collect()
+}
+
+/// Logcat running on the device for the length of a recording
+struct LogCapture {
+    pid: String,
+    temp_file: String,
+    start_ms: u64,
+}
+
 impl ScreenrecordCommand {
     pub fn new() -> Self {
         Self
     }
+
+    async fn device_clock(host: &str, port: &str, device_id: &str) -> Result<u64> {
+        let out = run_shell_command_async(host, port, "date +%s.%N", Some(device_id)).await?;
+        parse_epoch_millis(&out)
+            .ok_or_else(|| AimError::CommandExecution(format!("Unexpected device time '{}'", out.trim())))
+    }
+
+    async fn start_logs(
+        ctx: &CommandContext,
+        device_id: &str,
+        package: Option<&str>,
+        temp_file: String,
+    ) -> Result<LogCapture> {
+        let (host, port) = ctx.adb_address();
+        let port = port.to_string();
+        let uid = match package {
+            Some(partial) => {
+                let package = super::app::PackageResolver::new(ctx).resolve(partial).await?;
+                let cmd = format!("pm list packages -U {}", package);
+                let out = run_shell_command_async(host, &port, &cmd, Some(device_id)).await?;
+                let uid = parse_package_uid(&out, &package)
+                    .ok_or_else(|| AimError::CommandExecution(format!("Cannot find the uid of {}", package)))?;
+                Some(uid)
+            }
+            None => None,
+        };
+        let start_ms = Self::device_clock(host, &port, device_id).await?;
+        let cmd = logcat_command(start_ms, uid, &temp_file);
+        let pid = run_shell_command_async(host, &port, &cmd, Some(device_id)).await?.trim().to_string();
+        Ok(LogCapture { pid, temp_file, start_ms })
+    }
+
+    /// Stop logcat and save the lines up to `end_ms` next to the video
+    async fn finish_logs(
+        ctx: &CommandContext,
+        device_id: &str,
+        logs: LogCapture,
+        end_ms: u64,
+        log_path: &Path,
+    ) -> Result<()> {
+        let (host, port) = ctx.adb_address();
+        let port = port.to_string();
+        let kill = format!("kill {}", logs.pid);
+        run_shell_command_async(host, &port, &kill, Some(device_id)).await?;
+        let raw = run_shell_command_async(host, &port, &format!("cat {}", logs.temp_file), Some(device_id)).await?;
+        let rm = format!("rm -f {}", logs.temp_file);
+        run_shell_command_async(host, &port, &rm, Some(device_id)).await?;
+        std::fs::write(log_path, sync_log(&raw, logs.start_ms, end_ms))
+            .map_err(|e| AimError::CommandExecution(format!("Cannot write {}: {}", log_path.display(), e)))?;
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -45,7 +177,9 @@ impl SubCommand for ScreenrecordCommand {
     
     async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
         let device = ctx.require_device()?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
+        crate::core::ci::require_interactive("Screen recording")?;
+        let upload_config = if args.upload { Some(upload::load_config()?) } else { None };
         
         // Generate random suffix for temp file
         let random_suffix: String = rand::rng()
@@ -56,30 +190,18 @@ impl SubCommand for ScreenrecordCommand {
         let temp_file = format!("/sdcard/screenrecord_{}.mp4", random_suffix);
         
         // Get output directory or file
-        let output_path = if let Some(path) = args.output {
+        let config = Config::load().screenrecord.unwrap_or_default();
+        let template = artifact::template(args.name_template.as_deref(), config.name_template.as_deref());
+        let filename = artifact::file_name(&template, &NameVars::new("screenrecord", device), "mp4")?;
+        let output_path = if let Some(path) = args.output.clone() {
             if path.is_dir() || path.as_os_str().to_string_lossy().ends_with('/') {
-                // Generate filename with timestamp
-                let timestamp = Local::now().format("%Y%m%d-%H%M%S");
-                path.join(format!(
-                    "aim-screenrecord-{}-{}.mp4",
-                    device.id.short_id(), timestamp
-                ))
+                path.join(filename)
             } else {
                 path
             }
         } else {
             // Use config or default
-            let config = Config::load();
-            let base_dir = config
-                .screenrecord
-                .and_then(|s| s.get_output_path())
-                .unwrap_or_else(|| PathBuf::from("/tmp"));
-            
-            let timestamp = Local::now().format("%Y%m%d-%H%M%S");
-            base_dir.join(format!(
-                "aim-screenrecord-{}-{}.mp4",
-                device.id.short_id(), timestamp
-            ))
+            config.get_output_path().unwrap_or_else(|| PathBuf::from("/tmp")).join(filename)
         };
         
         // Build screenrecord command with additional args
@@ -93,12 +215,21 @@ impl SubCommand for ScreenrecordCommand {
             )
         };
         
+        let device_id_str = device.id.to_string();
+        let port_str = port.to_string();
+
+        // Start logcat first so nothing from the first frames is missed
+        let logs = if args.with_logs {
+            let log_file = format!("/sdcard/screenrecord_{}.log", random_suffix);
+            Some(Self::start_logs(ctx, &device_id_str, args.package.as_deref(), log_file).await?)
+        } else {
+            None
+        };
+
         println!("Recording screen. Press 'q' to stop...");
         enable_raw_mode()?;
         
         // Start recording
-        let device_id_str = device.id.to_string();
-        let port_str = port.to_string();
         run_shell_command_async(host, &port_str, &screenrecord_cmd, Some(&device_id_str)).await?;
         
         let start_time = Instant::now();
@@ -130,6 +261,10 @@ impl SubCommand for ScreenrecordCommand {
         }
         
         // Stop recording
+        let end_ms = match logs {
+            Some(_) => Some(Self::device_clock(host, &port_str, &device_id_str).await?),
+            None => None,
+        };
         run_shell_command_async(host, &port_str, "killall -s 2 screenrecord", Some(&device_id_str)).await?;
         
         // Give it a moment to finish writing
@@ -149,6 +284,11 @@ impl SubCommand for ScreenrecordCommand {
         // Clean up temp file
         let rm_cmd = format!("rm -f {}", &temp_file);
         run_shell_command_async(host, &port_str, &rm_cmd, Some(&device_id_str)).await?;
+
+        let log_path = output_path.with_extension("log");
+        if let (Some(logs), Some(end_ms)) = (logs, end_ms) {
+            Self::finish_logs(ctx, &device_id_str, logs, end_ms, &log_path).await?;
+        }
         
         let total_elapsed = start_time.elapsed();
         let hours = total_elapsed.as_secs() / 3600;
@@ -157,10 +297,19 @@ impl SubCommand for ScreenrecordCommand {
         
         disable_raw_mode()?;
         println!("\nRecording saved to: {}", output_path.display());
+        if args.with_logs {
+            println!("Logs saved to: {}", log_path.display());
+        }
         println!(
             "Total recording time: {:02}:{:02}:{:02}",
             hours, minutes, seconds
         );
+        if let Some(config) = &upload_config {
+            upload::upload_artifact(ctx, config, &output_path).await?;
+            if args.with_logs {
+                upload::upload_artifact(ctx, config, &log_path).await?;
+            }
+        }
         Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,44 @@
+use super::screenrecord::{format_offset, logcat_command, parse_epoch_millis, parse_package_uid, sync_log};
+
+#[test]
+fn test_parse_epoch_millis() {
+    assert_eq!(parse_epoch_millis("1700000000.123456789\n"), Some(1_700_000_000_123));
+    assert_eq!(parse_epoch_millis("1700000000.5"), Some(1_700_000_000_500));
+    assert_eq!(parse_epoch_millis("1700000000.N"), Some(1_700_000_000_000));
+    assert_eq!(parse_epoch_millis("1700000000"), Some(1_700_000_000_000));
+    assert_eq!(parse_epoch_millis("---------"), None);
+}
+
+#[test]
+fn test_parse_package_uid() {
+    let out = "package:com.example.app uid:10123\npackage:com.example.app.test uid:10124\n";
+    assert_eq!(parse_package_uid(out, "com.example.app"), Some(10123));
+    assert_eq!(parse_package_uid(out, "com.example.app.test"), Some(10124));
+    assert_eq!(parse_package_uid("package:com.shared uid:10200,10201", "com.shared"), Some(10200));
+    assert_eq!(parse_package_uid(out, "com.other"), None);
+}
+
+#[test]
+fn test_logcat_command() {
+    assert_eq!(
+        logcat_command(1_700_000_000_005, Some(10123), "/sdcard/x.log"),
+        "logcat -v epoch -T 1700000000.005 --uid=10123 > /sdcard/x.log 2>/dev/null & echo $!"
+    );
+    assert!(!logcat_command(1_700_000_000_000, None, "/sdcard/x.log").contains("--uid"));
+}
+
+#[test]
+fn test_format_offset() {
+    assert_eq!(format_offset(0), "00:00.000");
+    assert_eq!(format_offset(62_345), "01:02.345");
+}
+
+#[test]
+fn test_sync_log_keeps_recording_window() {
+    let raw = "--------- beginning of main\n\
+               1700000000.900  100  100 I Early: before\n\
+               1700000001.250  100  100 I App: during\n\
+               1700000005.000  100  100 I Late: after\n";
+    let synced = sync_log(raw, 1_700_000_001_000, 1_700_000_004_000);
+    assert_eq!(synced, "[00:00.250] 1700000001.250  100  100 I App: during\n");
+}
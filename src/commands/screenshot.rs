@@ -1,14 +1,20 @@
 use crate::commands::SubCommand;
 use crate::core::context::CommandContext;
-use crate::error::Result;
+use crate::error::{AimError, Result};
 use crate::library::adb::{run_shell_command_async, pull, ProgressDisplay};
 use crate::config::Config;
+use crate::core::artifact::{self, NameVars};
+use crate::core::types::Device;
+use crate::core::upload;
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::{DateTime, Local};
 use crossterm::event::{self, Event, KeyCode};
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use font8x8::{UnicodeFonts, BASIC_FONTS};
+use image::imageops::FilterType;
+use image::{DynamicImage, Rgba, RgbaImage};
 use rand::{distr::Alphanumeric, Rng};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 pub struct ScreenshotCommand;
@@ -25,25 +31,302 @@ pub struct ScreenshotArgs {
     /// Interactive mode - take screenshots with spacebar
     #[clap(short = 'i', long = "interactive")]
     pub interactive: bool,
+
+    /// Keep only this region of the screen, as WxH+X+Y in pixels
+    #[clap(long, value_parser = parse_crop)]
+    pub crop: Option<Crop>,
+
+    /// Resize the result, e.g. 50%
+    #[clap(long, value_parser = parse_scale)]
+    pub scale: Option<f32>,
+
+    /// Draw a label and the device name onto the screenshot
+    #[clap(long, value_name = "TEXT")]
+    pub annotate: Option<String>,
+
+    /// Draw the device name and capture time onto the screenshot
+    #[clap(long)]
+    pub timestamp: bool,
+
+    /// Take a numbered series, one screenshot per interval (e.g. 2s)
+    #[clap(long, value_parser = crate::utils::parse_duration)]
+    pub every: Option<Duration>,
+
+    /// Stop the series after this many screenshots
+    #[clap(long, requires = "every")]
+    pub count: Option<u32>,
+
+    /// Stop the series after this long (e.g. 1m)
+    #[clap(long, requires = "every", value_parser = crate::utils::parse_duration)]
+    pub duration: Option<Duration>,
+
+    /// In interactive mode, SPACE keeps capturing until the next key press
+    #[clap(long, requires = "interactive")]
+    pub until_key: bool,
+
+    /// File name template, e.g. "{alias}-{ts}-{counter}" (see docs for the variables)
+    #[clap(long, value_name = "TEMPLATE")]
+    pub name_template: Option<String>,
+
+    /// Upload the result as set up in the [upload] config section
+    #[clap(long)]
+    pub upload: bool,
     
     /// Additional arguments to pass to screencap
     #[clap(trailing_var_arg = true)]
     pub args: Vec<String>,
 }
 
+/// A region of the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crop {
+    pub width: u32,
+    pub height: u32,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl std::fmt::Display for Crop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}+{}+{}", self.width, self.height, self.x, self.y)
+    }
+}
+
+/// Parse a crop region like "1080x600+0+200"; the offset defaults to +0+0
+pub fn parse_crop(s: &str) -> std::result::Result<Crop, String> {
+    let invalid = || format!("Invalid crop '{}', expected WxH+X+Y", s);
+    let (size, offset) = s.split_once('+').map_or((s, None), |(size, offset)| (size, Some(offset)));
+    let (width, height) = size.split_once('x').ok_or_else(invalid)?;
+    let (x, y) = match offset {
+        Some(offset) => offset.split_once('+').ok_or_else(invalid)?,
+        None => ("0", "0"),
+    };
+    let number = |v: &str| v.trim().parse::<u32>().map_err(|_| invalid());
+    let crop = Crop {
+        width: number(width)?,
+        height: number(height)?,
+        x: number(x)?,
+        y: number(y)?,
+    };
+    if crop.width == 0 || crop.height == 0 {
+        return Err(invalid());
+    }
+    Ok(crop)
+}
+
+/// Parse a scale like "50%" or "0.5" into a factor
+pub fn parse_scale(s: &str) -> std::result::Result<f32, String> {
+    let factor = match s.trim().strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f32>().map(|p| p / 100.0),
+        None => s.trim().parse::<f32>(),
+    }
+    .map_err(|_| format!("Invalid scale '{}', expected a percentage like 50%", s))?;
+    if !(factor > 0.0 && factor <= 10.0) {
+        return Err(format!("Scale '{}' must be above 0% and at most 1000%", s));
+    }
+    Ok(factor)
+}
+
+/// Crop, then scale, a captured screenshot
+pub fn transform(image: DynamicImage, crop: Option<Crop>, scale: Option<f32>) -> Result<DynamicImage> {
+    let mut image = image;
+    if let Some(crop) = crop {
+        let fits = crop.x.checked_add(crop.width).is_some_and(|right| right <= image.width())
+            && crop.y.checked_add(crop.height).is_some_and(|bottom| bottom <= image.height());
+        if !fits {
+            return Err(AimError::Screenshot(format!(
+                "Crop {} does not fit the {}x{} screenshot",
+                crop,
+                image.width(),
+                image.height()
+            )));
+        }
+        image = image.crop_imm(crop.x, crop.y, crop.width, crop.height);
+    }
+    if let Some(scale) = scale {
+        let width = ((image.width() as f32 * scale).round() as u32).max(1);
+        let height = ((image.height() as f32 * scale).round() as u32).max(1);
+        image = image.resize_exact(width, height, FilterType::Lanczos3);
+    }
+    Ok(image)
+}
+
+/// Number of screenshots in a series, or `None` to go on until interrupted
+///
+/// `--duration` counts the shot at the start, so `--every 2s --duration 10s`
+/// takes six. With both limits, whichever is reached first wins.
+pub fn burst_shots(every: Duration, count: Option<u32>, duration: Option<Duration>) -> Option<u32> {
+    let from_duration = duration.map(|d| match every.as_millis() {
+        0 => 1,
+        step => (d.as_millis() / step).min(u32::MAX as u128 - 1) as u32 + 1,
+    });
+    match (count, from_duration) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Path of the `counter`th screenshot of a series or interactive session
+fn numbered_path(dir: Option<&Path>, device: &Device, counter: u32, template: &str) -> Result<PathBuf> {
+    let vars = NameVars::new("screenshot", device).with_counter(counter);
+    let filename = artifact::file_name(template, &vars, "png")?;
+    Ok(match dir {
+        Some(dir) => dir.join(filename),
+        None => PathBuf::from(filename),
+    })
+}
+
+/// The file name template from `--name-template` or the config, checked before capturing
+fn name_template(args: &ScreenshotArgs) -> Result<String> {
+    let config = Config::load().screenshot.and_then(|s| s.name_template);
+    let template = artifact::template(args.name_template.as_deref(), config.as_deref());
+    artifact::validate(&template)?;
+    Ok(template)
+}
+
+/// Where screenshots go when no `-o` is given
+fn default_dir() -> PathBuf {
+    Config::load()
+        .screenshot
+        .and_then(|s| s.get_output_path())
+        .unwrap_or_else(|| PathBuf::from("/tmp"))
+}
+
+/// Lines of the `--annotate` / `--timestamp` overlay, top to bottom
+pub fn annotation_lines(label: Option<&str>, device: &Device, captured: Option<DateTime<Local>>) -> Vec<String> {
+    let label = label.map(str::trim).filter(|l| !l.is_empty()).map(str::to_string);
+    let captured = captured.map(|at| at.format("%Y-%m-%d %H:%M:%S %Z").to_string());
+    label
+        .into_iter()
+        .chain(std::iter::once(device.display_name()))
+        .chain(captured)
+        .collect()
+}
+
+/// Draw `lines` in a dark band along the bottom of the image
+///
+/// Uses an 8x8 bitmap font, enlarged so the text stays readable on
+/// high-resolution screens. Text that doesn't fit is clipped.
+pub fn draw_annotation(image: &mut RgbaImage, lines: &[String]) {
+    if lines.is_empty() {
+        return;
+    }
+    let zoom = (image.width() / 360).max(1);
+    let padding = 4 * zoom;
+    let line_height = 10 * zoom;
+    let band = (lines.len() as u32 * line_height + 2 * padding).min(image.height());
+    let top = image.height() - band;
+
+    for y in top..image.height() {
+        for x in 0..image.width() {
+            let Rgba([r, g, b, a]) = *image.get_pixel(x, y);
+            let dim = |c: u8| (c as u32 * 2 / 5) as u8;
+            image.put_pixel(x, y, Rgba([dim(r), dim(g), dim(b), a.max(200)]));
+        }
+    }
+
+    for (row, line) in lines.iter().enumerate() {
+        let y0 = top + padding + row as u32 * line_height;
+        for (col, ch) in line.chars().enumerate() {
+            let glyph = BASIC_FONTS.get(ch).or_else(|| BASIC_FONTS.get('?')).unwrap_or([0; 8]);
+            let x0 = padding + col as u32 * 8 * zoom;
+            for (gy, bits) in glyph.iter().enumerate() {
+                for gx in 0..8 {
+                    if bits & (1 << gx) == 0 {
+                        continue;
+                    }
+                    for dy in 0..zoom {
+                        for dx in 0..zoom {
+                            let (x, y) = (x0 + gx * zoom + dx, y0 + gy as u32 * zoom + dy);
+                            if x < image.width() && y < image.height() {
+                                image.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 impl ScreenshotCommand {
     pub fn new() -> Self {
         Self
     }
+
+    /// Apply `--crop`, `--scale`, `--annotate` and `--timestamp` to a saved screenshot in place
+    fn edit(path: &Path, device: &Device, captured: DateTime<Local>, args: &ScreenshotArgs) -> Result<()> {
+        let annotated = args.annotate.is_some() || args.timestamp;
+        if args.crop.is_none() && args.scale.is_none() && !annotated {
+            return Ok(());
+        }
+        let image = image::open(path)
+            .map_err(|e| AimError::Screenshot(format!("Cannot read {}: {}", path.display(), e)))?;
+        let mut image = transform(image, args.crop, args.scale)?;
+        if annotated {
+            let lines = annotation_lines(args.annotate.as_deref(), device, args.timestamp.then_some(captured));
+            let mut rgba = image.to_rgba8();
+            draw_annotation(&mut rgba, &lines);
+            image = DynamicImage::ImageRgba8(rgba);
+        }
+        image
+            .save(path)
+            .map_err(|e| AimError::Screenshot(format!("Cannot write {}: {}", path.display(), e)))?;
+        Ok(())
+    }
+
+    /// Take one screenshot, apply the edits from `args` and upload it if asked
+    async fn capture(&self, ctx: &CommandContext, device: &Device, path: &PathBuf, args: &ScreenshotArgs) -> Result<()> {
+        let captured = Local::now();
+        self.take_screenshot(ctx, device, path, &args.args).await?;
+        Self::edit(path, device, captured, args)?;
+        if args.upload {
+            upload::upload_artifact(ctx, &upload::load_config()?, path).await?;
+        }
+        Ok(())
+    }
+
+    /// `--every`: a numbered series at a fixed interval
+    async fn burst(
+        &self,
+        ctx: &CommandContext,
+        device: &Device,
+        args: &ScreenshotArgs,
+        every: Duration,
+        template: &str,
+    ) -> Result<()> {
+        let dir = args.output.clone().unwrap_or_else(default_dir);
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| AimError::Screenshot(format!("Cannot create {}: {}", dir.display(), e)))?;
+        let shots = burst_shots(every, args.count, args.duration);
+        match shots {
+            Some(n) => println!("Taking {} screenshots, one every {}", n, crate::utils::format_duration(every)),
+            None => println!(
+                "Taking a screenshot every {} - press Ctrl+C to stop",
+                crate::utils::format_duration(every)
+            ),
+        }
+
+        let mut ticker = tokio::time::interval(every.max(Duration::from_millis(1)));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut counter = 1;
+        while shots.is_none_or(|n| counter <= n) {
+            ticker.tick().await;
+            let path = numbered_path(Some(&dir), device, counter, template)?;
+            self.capture(ctx, device, &path, args).await?;
+            counter += 1;
+        }
+        Ok(())
+    }
     
-    async fn take_screenshot(
+    pub(crate) async fn take_screenshot(
         &self,
-        _ctx: &CommandContext,
+        ctx: &CommandContext,
         device: &crate::core::types::Device,
         output_path: &PathBuf,
         args: &[String],
     ) -> Result<()> {
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
         
         // Generate temp file on device
         let random_suffix: String = rand::rng()
@@ -91,34 +374,46 @@ impl SubCommand for ScreenshotCommand {
     
     async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
         let device = ctx.require_device()?;
+        let template = name_template(&args)?;
+        if args.upload {
+            upload::load_config()?;
+        }
         
         if args.interactive {
             // Interactive mode
+            crate::core::ci::require_interactive("Interactive screenshot mode")?;
             enable_raw_mode()?;
             println!("Interactive screenshot mode");
-            println!("Press SPACE to take a screenshot, 'q' to quit");
+            if args.until_key {
+                println!("Press SPACE to start capturing, any key to stop, 'q' to quit");
+            } else {
+                println!("Press SPACE to take a screenshot, 'q' to quit");
+            }
             
+            let every = args.every.unwrap_or(Duration::from_secs(1));
             let mut counter = 1;
             loop {
                 if event::poll(Duration::from_millis(100))? {
                     if let Event::Key(key) = event::read()? {
                         match key.code {
+                            KeyCode::Char(' ') if args.until_key => {
+                                println!("\nCapturing every {}...", crate::utils::format_duration(every));
+                                loop {
+                                    let next = std::time::Instant::now() + every;
+                                    let output_path = numbered_path(args.output.as_deref(), device, counter, &template)?;
+                                    self.capture(ctx, device, &output_path, &args).await?;
+                                    counter += 1;
+                                    if event::poll(next.saturating_duration_since(std::time::Instant::now()))? {
+                                        event::read()?;
+                                        println!("\nStopped");
+                                        break;
+                                    }
+                                }
+                            }
                             KeyCode::Char(' ') => {
-                                // Generate filename
-                                let timestamp = Local::now().format("%Y%m%d-%H%M%S");
-                                let filename = format!(
-                                    "aim-screenshot-{}-{}-{:03}.png",
-                                    device.id.short_id(), timestamp, counter
-                                );
-                                
-                                let output_path = if let Some(ref dir) = args.output {
-                                    dir.join(filename)
-                                } else {
-                                    PathBuf::from(filename)
-                                };
-                                
+                                let output_path = numbered_path(args.output.as_deref(), device, counter, &template)?;
                                 println!("\nTaking screenshot...");
-                                self.take_screenshot(ctx, device, &output_path, &args.args).await?;
+                                self.capture(ctx, device, &output_path, &args).await?;
                                 counter += 1;
                             }
                             KeyCode::Char('q') => break,
@@ -130,35 +425,23 @@ impl SubCommand for ScreenshotCommand {
             
             disable_raw_mode()?;
             println!("\nExiting interactive mode");
+        } else if let Some(every) = args.every {
+            self.burst(ctx, device, &args, every, &template).await?;
         } else {
             // Single screenshot
-            let output_path = if let Some(path) = args.output {
+            let filename = || artifact::file_name(&template, &NameVars::new("screenshot", device), "png");
+            let output_path = if let Some(path) = args.output.clone() {
                 if path.is_dir() || path.as_os_str().to_string_lossy().ends_with('/') {
-                    // Generate filename with timestamp
-                    let timestamp = Local::now().format("%Y%m%d-%H%M%S");
-                    path.join(format!(
-                        "aim-screenshot-{}-{}.png",
-                        device.id.short_id(), timestamp
-                    ))
+                    path.join(filename()?)
                 } else {
                     path
                 }
             } else {
                 // Use config or default
-                let config = Config::load();
-                let base_dir = config
-                    .screenshot
-                    .and_then(|s| s.get_output_path())
-                    .unwrap_or_else(|| PathBuf::from("/tmp"));
-                
-                let timestamp = Local::now().format("%Y%m%d-%H%M%S");
-                base_dir.join(format!(
-                    "aim-screenshot-{}-{}.png",
-                    device.id.short_id(), timestamp
-                ))
+                default_dir().join(filename()?)
             };
             
-            self.take_screenshot(ctx, device, &output_path, &args.args).await?
+            self.capture(ctx, device, &output_path, &args).await?;
         }
         
         Ok(())
@@ -0,0 +1,85 @@
+use super::screenshot::{annotation_lines, burst_shots, draw_annotation, parse_crop, parse_scale, transform, Crop};
+use crate::core::types::{Device, DeviceId};
+use chrono::{Local, TimeZone};
+use image::{DynamicImage, Rgba, RgbaImage};
+use std::time::Duration;
+
+fn image(width: u32, height: u32) -> DynamicImage {
+    DynamicImage::ImageRgba8(RgbaImage::from_fn(width, height, |x, y| Rgba([x as u8, y as u8, 0, 255])))
+}
+
+#[test]
+fn test_parse_crop() {
+    assert_eq!(
+        parse_crop("1080x600+0+200"),
+        Ok(Crop { width: 1080, height: 600, x: 0, y: 200 })
+    );
+    assert_eq!(parse_crop("100x50"), Ok(Crop { width: 100, height: 50, x: 0, y: 0 }));
+}
+
+#[test]
+fn test_parse_crop_rejects_bad_input() {
+    assert!(parse_crop("100").is_err());
+    assert!(parse_crop("100x50+10").is_err());
+    assert!(parse_crop("0x50").is_err());
+    assert!(parse_crop("-1x50+0+0").is_err());
+}
+
+#[test]
+fn test_parse_scale() {
+    assert_eq!(parse_scale("50%"), Ok(0.5));
+    assert_eq!(parse_scale("200%"), Ok(2.0));
+    assert_eq!(parse_scale("0.25"), Ok(0.25));
+    assert!(parse_scale("0%").is_err());
+    assert!(parse_scale("half").is_err());
+}
+
+#[test]
+fn test_transform_crops_then_scales() {
+    let crop = Crop { width: 40, height: 20, x: 10, y: 5 };
+    let result = transform(image(100, 50), Some(crop), Some(0.5)).unwrap();
+    assert_eq!((result.width(), result.height()), (20, 10));
+
+    let cropped = transform(image(100, 50), Some(crop), None).unwrap();
+    assert_eq!(cropped.to_rgba8().get_pixel(0, 0), &Rgba([10, 5, 0, 255]));
+}
+
+#[test]
+fn test_transform_rejects_crop_outside_image() {
+    let crop = Crop { width: 40, height: 20, x: 70, y: 0 };
+    assert!(transform(image(100, 50), Some(crop), None).is_err());
+}
+
+#[test]
+fn test_annotation_lines() {
+    let device = Device::new(DeviceId::new("abc123")).with_model("Pixel 7".to_string());
+    let captured = Local.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap();
+
+    let lines = annotation_lines(Some("login crash"), &device, Some(captured));
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "login crash");
+    assert_eq!(lines[1], "Pixel 7 (abc123)");
+    assert!(lines[2].starts_with("2024-03-01 09:30:00"));
+
+    assert_eq!(annotation_lines(Some("  "), &device, None), vec!["Pixel 7 (abc123)"]);
+}
+
+#[test]
+fn test_draw_annotation_darkens_bottom_band() {
+    let mut canvas = RgbaImage::from_pixel(100, 100, Rgba([200, 200, 200, 255]));
+    draw_annotation(&mut canvas, &["A".to_string()]);
+
+    assert_eq!(canvas.get_pixel(50, 10), &Rgba([200, 200, 200, 255]));
+    assert_eq!(canvas.get_pixel(99, 99), &Rgba([80, 80, 80, 255]));
+    let white = canvas.pixels().filter(|p| **p == Rgba([255, 255, 255, 255])).count();
+    assert!(white > 0);
+}
+
+#[test]
+fn test_burst_shots() {
+    let every = Duration::from_secs(2);
+    assert_eq!(burst_shots(every, Some(30), None), Some(30));
+    assert_eq!(burst_shots(every, None, Some(Duration::from_secs(10))), Some(6));
+    assert_eq!(burst_shots(every, Some(3), Some(Duration::from_secs(60))), Some(3));
+    assert_eq!(burst_shots(every, None, None), None);
+}
@@ -0,0 +1,320 @@
+//! Running a sequence of aim commands from a file or the command line
+//!
+//! A script is one aim command per line (or separated by `;`), without the
+//! leading `aim`. Besides commands there are two statements:
+//!
+//! - `set NAME=value ...` defines variables, used as `$NAME` or `${NAME}`
+//! - `onerror continue|stop` decides whether a failing command ends the script
+//!
+//! Words are split like a shell does: single quotes are literal, double
+//! quotes allow variables, and `#` starts a comment. Commands run in this
+//! process, one after another, against the same device.
+
+use crate::cli::Cli;
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::error::{AimError, Result};
+use async_trait::async_trait;
+use clap::Parser;
+use colored::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub struct ScriptCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ScriptArgs {
+    /// Script file with one aim command per line
+    #[clap(conflicts_with = "command", required_unless_present = "command")]
+    pub file: Option<PathBuf>,
+
+    /// Commands to run, separated by `;`, instead of a file
+    #[clap(short = 'c', long = "command")]
+    pub command: Option<String>,
+
+    /// Variable for the script (NAME=value, repeatable)
+    #[clap(long = "var", value_parser = parse_var)]
+    pub vars: Vec<(String, String)>,
+
+    /// Device ID used by every command (required if multiple devices are connected)
+    #[clap(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// What to do when a command fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnError {
+    Stop,
+    Continue,
+}
+
+/// Part of a word, and whether variables in it are expanded
+#[derive(Debug, Clone, PartialEq)]
+struct Part {
+    text: String,
+    expand: bool,
+}
+
+/// One word of a script line, before variables are expanded
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Word {
+    parts: Vec<Part>,
+}
+
+impl Word {
+    fn push(&mut self, c: char, expand: bool) {
+        match self.parts.last_mut() {
+            Some(part) if part.expand == expand => part.text.push(c),
+            _ => self.parts.push(Part {
+                text: c.to_string(),
+                expand,
+            }),
+        }
+    }
+
+    /// The word with `$NAME` and `${NAME}` replaced; `$$` is a literal `$`
+    pub fn expand(&self, vars: &HashMap<String, String>) -> std::result::Result<String, String> {
+        let mut out = String::new();
+        for part in &self.parts {
+            if part.expand {
+                out.push_str(&expand_vars(&part.text, vars)?);
+            } else {
+                out.push_str(&part.text);
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// A statement of the script with the line it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    pub line: usize,
+    pub words: Vec<Word>,
+}
+
+/// Parse a `--var` value
+pub fn parse_var(s: &str) -> std::result::Result<(String, String), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=value, got '{}'", s))?;
+    if !is_var_name(name) {
+        return Err(format!("invalid variable name '{}'", name));
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
+fn is_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn expand_vars(text: &str, vars: &HashMap<String, String>) -> std::result::Result<String, String> {
+    let mut out = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+        let name: String = match chars.peek() {
+            Some('$') => {
+                chars.next();
+                out.push('$');
+                continue;
+            }
+            Some('{') => {
+                chars.next();
+                let name: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                if !is_var_name(&name) {
+                    return Err(format!("invalid variable reference '${{{}}}'", name));
+                }
+                name
+            }
+            _ => {
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    name.push(c);
+                }
+                if name.is_empty() {
+                    out.push('$');
+                    continue;
+                }
+                name
+            }
+        };
+        let value = vars
+            .get(&name)
+            .ok_or_else(|| format!("undefined variable '{}'", name))?;
+        out.push_str(value);
+    }
+    Ok(out)
+}
+
+/// Split a script into statements
+///
+/// Statements end at a newline or an unquoted `;`. Empty statements and
+/// comments are dropped.
+pub fn parse_script(text: &str) -> std::result::Result<Vec<Statement>, String> {
+    let mut statements = Vec::new();
+    let mut words: Vec<Word> = Vec::new();
+    let mut word: Option<Word> = None;
+    let mut quote: Option<char> = None;
+    let mut line = 1;
+    let mut start_line = 1;
+    let mut chars = text.chars().peekable();
+
+    let finish = |words: &mut Vec<Word>, word: &mut Option<Word>| {
+        if let Some(w) = word.take() {
+            words.push(w);
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+        match (quote, c) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('\''), c) => word.get_or_insert_with(Word::default).push(c, false),
+            (Some(_), '\\') if matches!(chars.peek(), Some('"' | '\\' | '$')) => {
+                let escaped = chars.next().unwrap_or_default();
+                word.get_or_insert_with(Word::default).push(escaped, false);
+            }
+            (Some(_), c) => word.get_or_insert_with(Word::default).push(c, true),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                // An empty quoted string is still a word
+                word.get_or_insert_with(Word::default);
+            }
+            (None, '\\') => match chars.next() {
+                // A backslash at the end of a line continues the statement
+                Some('\n') => line += 1,
+                Some(escaped) => word.get_or_insert_with(Word::default).push(escaped, false),
+                None => {}
+            },
+            (None, '#') if word.is_none() => {
+                while chars.next_if(|c| *c != '\n').is_some() {}
+            }
+            (None, '\n' | ';') => {
+                finish(&mut words, &mut word);
+                if !words.is_empty() {
+                    statements.push(Statement {
+                        line: start_line,
+                        words: std::mem::take(&mut words),
+                    });
+                }
+                start_line = line;
+            }
+            (None, c) if c.is_whitespace() => finish(&mut words, &mut word),
+            (None, c) => word.get_or_insert_with(Word::default).push(c, true),
+        }
+    }
+
+    if quote.is_some() {
+        return Err(format!("line {}: unterminated quote", start_line));
+    }
+    finish(&mut words, &mut word);
+    if !words.is_empty() {
+        statements.push(Statement {
+            line: start_line,
+            words,
+        });
+    }
+    Ok(statements)
+}
+
+impl ScriptCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Run one aim command in this process
+    async fn run_command(words: Vec<String>) -> Result<()> {
+        if words.first().is_some_and(|w| w == "script") {
+            return Err(AimError::InvalidArgument("scripts cannot run other scripts".to_string()));
+        }
+        let argv: Vec<String> = std::iter::once("aim".to_string()).chain(words).collect();
+        let mut cli =
+            Cli::try_parse_from(&argv).map_err(|e| AimError::InvalidArgument(e.to_string().trim().to_string()))?;
+        cli.argv = argv;
+        crate::commands::runner::execute(cli).await
+    }
+}
+
+#[async_trait]
+impl SubCommand for ScriptCommand {
+    type Args = ScriptArgs;
+
+    async fn run(&self, _ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let (name, text) = match (&args.file, &args.command) {
+            (Some(file), _) => (file.display().to_string(), std::fs::read_to_string(file)?),
+            (None, Some(command)) => ("-c".to_string(), command.clone()),
+            (None, None) => return Err(AimError::InvalidArgument("no script given".to_string())),
+        };
+        let statements = parse_script(&text).map_err(|e| AimError::ParseError(format!("{}: {}", name, e)))?;
+
+        // Resolve the device once, so every command uses the same one
+        if let Some(device_id) = &args.device_id {
+            let device = crate::commands::get_device(Some(device_id)).await?;
+            std::env::set_var("AIM_DEVICE", device.id.to_string());
+        }
+
+        let mut vars: HashMap<String, String> = args.vars.into_iter().collect();
+        let mut on_error = OnError::Stop;
+        let mut failed = 0;
+        let mut commands = 0;
+
+        for statement in statements {
+            let words: Vec<String> = statement
+                .words
+                .iter()
+                .map(|w| w.expand(&vars))
+                .collect::<std::result::Result<_, _>>()
+                .map_err(|e| AimError::ParseError(format!("{}:{}: {}", name, statement.line, e)))?;
+            let location = format!("{}:{}", name, statement.line);
+
+            match words[0].as_str() {
+                "set" => {
+                    for assignment in &words[1..] {
+                        let (key, value) =
+                            parse_var(assignment).map_err(|e| AimError::ParseError(format!("{}: {}", location, e)))?;
+                        vars.insert(key, value);
+                    }
+                }
+                "onerror" => {
+                    on_error = match words.get(1).map(String::as_str) {
+                        Some("continue") => OnError::Continue,
+                        Some("stop") => OnError::Stop,
+                        _ => {
+                            return Err(AimError::ParseError(format!(
+                                "{}: expected 'onerror continue' or 'onerror stop'",
+                                location
+                            )))
+                        }
+                    };
+                }
+                _ => {
+                    commands += 1;
+                    eprintln!("{} {}", "$ aim".dimmed(), words.join(" ").dimmed());
+                    if let Err(e) = Self::run_command(words).await {
+                        failed += 1;
+                        eprintln!("{} {}: {}", "✗".red(), location, e);
+                        if on_error == OnError::Stop {
+                            return Err(AimError::CommandExecution(format!("script stopped at {}", location)));
+                        }
+                    }
+                }
+            }
+        }
+
+        if failed > 0 {
+            return Err(AimError::CommandExecution(format!(
+                "{} of {} command(s) failed",
+                failed, commands
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,61 @@
+use super::script::{parse_script, parse_var, Statement};
+use std::collections::HashMap;
+
+fn expanded(statements: &[Statement], vars: &HashMap<String, String>) -> Vec<Vec<String>> {
+    statements
+        .iter()
+        .map(|s| s.words.iter().map(|w| w.expand(vars).unwrap()).collect())
+        .collect()
+}
+
+#[test]
+fn test_parse_script_statements() {
+    let script = "# device setup\nls\n\ngetprop ro.build.id; screenshot -o shot.png  # trailing comment\n";
+    let statements = parse_script(script).unwrap();
+    assert_eq!(statements.iter().map(|s| s.line).collect::<Vec<_>>(), vec![2, 4, 4]);
+    assert_eq!(
+        expanded(&statements, &HashMap::new()),
+        vec![
+            vec!["ls".to_string()],
+            vec!["getprop".to_string(), "ro.build.id".to_string()],
+            vec!["screenshot".to_string(), "-o".to_string(), "shot.png".to_string()],
+        ]
+    );
+}
+
+#[test]
+fn test_parse_script_quoting() {
+    let statements = parse_script(r#"run "echo a; b" 'x # y' "" a\ b"#).unwrap();
+    assert_eq!(statements.len(), 1);
+    assert_eq!(
+        expanded(&statements, &HashMap::new())[0],
+        vec!["run", "echo a; b", "x # y", "", "a b"]
+    );
+
+    // Continued lines keep the line number of their first line
+    let statements = parse_script("ls \\\n  -l\nrun id").unwrap();
+    assert_eq!(statements.iter().map(|s| s.line).collect::<Vec<_>>(), vec![1, 3]);
+
+    assert!(parse_script("run 'unterminated").is_err());
+}
+
+#[test]
+fn test_variable_expansion() {
+    let vars: HashMap<String, String> = [("PKG".to_string(), "com.example".to_string())].into();
+    let statements = parse_script(r#"app start $PKG "${PKG}.debug" '$PKG' "\$PKG" $$"#).unwrap();
+    assert_eq!(
+        expanded(&statements, &vars)[0],
+        vec!["app", "start", "com.example", "com.example.debug", "$PKG", "$PKG", "$"]
+    );
+
+    let statements = parse_script("run $MISSING").unwrap();
+    assert!(statements[0].words[1].expand(&vars).is_err());
+}
+
+#[test]
+fn test_parse_var() {
+    assert_eq!(parse_var("PKG=com.example"), Ok(("PKG".to_string(), "com.example".to_string())));
+    assert_eq!(parse_var("EMPTY="), Ok(("EMPTY".to_string(), String::new())));
+    assert!(parse_var("novalue").is_err());
+    assert!(parse_var("1BAD=x").is_err());
+}
@@ -0,0 +1,201 @@
+use crate::adb::shell::helpers::shell;
+use crate::commands::ime::{input_text_command, TextCommand};
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::DeviceId;
+use crate::error::{AimError, Result};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use colored::*;
+use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
+
+/// Package of Clipper (https://github.com/majido/clipper), which sets the clipboard from a broadcast
+pub const CLIPPER_PACKAGE: &str = "ca.zgrs.clipper";
+
+/// `KEYCODE_PASTE`, pastes the clipboard into the focused field
+const KEYCODE_PASTE: u32 = 279;
+
+/// `KEYCODE_ENTER`, for line breaks when typing
+const KEYCODE_ENTER: u32 = 66;
+
+/// Longest piece `input text` takes reliably; longer strings drop characters
+const TYPE_CHUNK: usize = 100;
+
+pub struct SendTextCommand;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum SendMethod {
+    /// The clipboard when Clipper is installed, typing otherwise
+    #[default]
+    Auto,
+    /// Set the clipboard with Clipper and paste
+    Clipboard,
+    /// Type with `input text`, or ADB Keyboard for non-ASCII text
+    Type,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct SendTextArgs {
+    /// Text to send, a file to send the contents of, or `-` for stdin
+    pub input: String,
+
+    /// Send the argument as text even if a file has that name
+    #[clap(long)]
+    pub literal: bool,
+
+    /// How to get the text into the field
+    #[clap(long, value_enum, default_value_t = SendMethod::Auto)]
+    pub method: SendMethod,
+
+    /// Characters per clipboard paste
+    #[clap(long, default_value_t = 1000, value_parser = clap::value_parser!(u32).range(1..))]
+    pub chunk_size: u32,
+
+    /// Device ID (required if multiple devices are connected)
+    #[clap(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// The text `input` stands for: stdin for `-`, the contents of a file, or itself
+///
+/// One trailing line break is dropped so a token saved by an editor doesn't
+/// submit the form it is pasted into.
+pub fn read_input(input: &str, literal: bool) -> Result<String> {
+    let text = if literal {
+        input.to_string()
+    } else if input == "-" {
+        let mut text = String::new();
+        std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| AimError::InvalidArgument(format!("Cannot read text from stdin: {}", e)))?;
+        text
+    } else if Path::new(input).is_file() {
+        std::fs::read_to_string(input)
+            .map_err(|e| AimError::InvalidArgument(format!("Cannot read {} as text: {}", input, e)))?
+    } else {
+        input.to_string()
+    };
+    let text = text.strip_suffix('\n').map(|t| t.strip_suffix('\r').unwrap_or(t)).unwrap_or(&text);
+    Ok(text.to_string())
+}
+
+/// Split `text` into pieces of at most `size` characters
+pub fn chunks(text: &str, size: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    chars.chunks(size.max(1)).map(|c| c.iter().collect()).collect()
+}
+
+/// Broadcast that makes Clipper put `text` on the clipboard
+///
+/// Clipper listens from a service, which is started first in case it isn't running.
+pub fn clipper_command(text: &str) -> String {
+    format!(
+        "am startservice {}/.ClipboardService >/dev/null 2>&1; am broadcast -a clipper.set -e text {}",
+        CLIPPER_PACKAGE,
+        shell_quote(text)
+    )
+}
+
+/// Whether Clipper took the text (it answers with `Activity.RESULT_OK`)
+pub fn clipper_accepted(output: &str) -> bool {
+    output.contains("Broadcast completed: result=-1")
+}
+
+impl SendTextCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn has_clipper(host: &str, port: u16, device: &DeviceId) -> Result<bool> {
+        let out = shell(host, port, device, &format!("pm path {}", CLIPPER_PACKAGE)).await?;
+        Ok(out.contains("package:"))
+    }
+
+    /// Set the clipboard and paste, one chunk at a time
+    async fn paste(host: &str, port: u16, device: &DeviceId, text: &str, chunk_size: usize) -> Result<usize> {
+        let pieces = chunks(text, chunk_size);
+        for piece in &pieces {
+            let out = shell(host, port, device, &clipper_command(piece)).await?;
+            if !clipper_accepted(&out) {
+                return Err(AimError::CommandExecution(format!(
+                    "Clipper did not take the text (open the Clipper app once and retry): {}",
+                    out.trim()
+                )));
+            }
+            shell(host, port, device, &format!("input keyevent {}", KEYCODE_PASTE)).await?;
+            // Give the app time to handle the paste before the clipboard changes again
+            tokio::time::sleep(Duration::from_millis(150)).await;
+        }
+        Ok(pieces.len())
+    }
+
+    /// Type the text in short pieces, pressing ENTER for line breaks
+    async fn type_text(host: &str, port: u16, device: &DeviceId, text: &str) -> Result<usize> {
+        let mut sent = 0;
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                shell(host, port, device, &format!("input keyevent {}", KEYCODE_ENTER)).await?;
+            }
+            for piece in chunks(line.trim_end_matches('\r'), TYPE_CHUNK) {
+                if piece.is_ascii() {
+                    let out = shell(host, port, device, &format!("{} 2>&1", input_text_command(&piece))).await?;
+                    if out.contains("Exception") || out.contains("Error") {
+                        return Err(AimError::CommandExecution(format!("input text failed: {}", out.trim())));
+                    }
+                } else {
+                    TextCommand::new().type_with_adb_keyboard(host, port, device, &piece).await?;
+                }
+                sent += 1;
+            }
+        }
+        Ok(sent)
+    }
+}
+
+#[async_trait]
+impl SubCommand for SendTextCommand {
+    type Args = SendTextArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let text = read_input(&args.input, args.literal)?;
+        if text.is_empty() {
+            return Err(AimError::InvalidArgument("Nothing to send".to_string()));
+        }
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        let clipboard = match args.method {
+            SendMethod::Clipboard => {
+                if !Self::has_clipper(host, port, &device.id).await? {
+                    return Err(AimError::CommandExecution(format!(
+                        "Sending through the clipboard needs Clipper ({}); install it from \
+                         https://github.com/majido/clipper or use --method type",
+                        CLIPPER_PACKAGE
+                    )));
+                }
+                true
+            }
+            SendMethod::Type => false,
+            SendMethod::Auto => Self::has_clipper(host, port, &device.id).await?,
+        };
+
+        let pieces = if clipboard {
+            Self::paste(host, port, &device.id, &text, args.chunk_size as usize).await?
+        } else {
+            Self::type_text(host, port, &device.id, &text).await?
+        };
+        if !ctx.quiet {
+            println!(
+                "{} Sent {} characters in {} {} ({})",
+                "✓".green(),
+                text.chars().count(),
+                pieces,
+                if pieces == 1 { "piece" } else { "pieces" },
+                if clipboard { "pasted" } else { "typed" }
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use super::send_text::{chunks, clipper_accepted, clipper_command, read_input};
+
+#[test]
+fn test_chunks() {
+    assert_eq!(chunks("abcdefg", 3), vec!["abc", "def", "g"]);
+    assert_eq!(chunks("abc", 10), vec!["abc"]);
+    assert!(chunks("", 3).is_empty());
+    // Split on characters, never inside one
+    assert_eq!(chunks("grüße", 2), vec!["gr", "üß", "e"]);
+}
+
+#[test]
+fn test_clipper_command() {
+    assert_eq!(
+        clipper_command("it's a token"),
+        r"am startservice ca.zgrs.clipper/.ClipboardService >/dev/null 2>&1; am broadcast -a clipper.set -e text 'it'\''s a token'"
+    );
+}
+
+#[test]
+fn test_clipper_accepted() {
+    assert!(clipper_accepted(
+        "Broadcasting: Intent { act=clipper.set flg=0x400000 (has extras) }\n\
+         Broadcast completed: result=-1, data=\"Text is copied into clipboard.\"\n"
+    ));
+    assert!(!clipper_accepted("Broadcast completed: result=0\n"));
+}
+
+#[test]
+fn test_read_input() {
+    assert_eq!(read_input("https://example.com/a?b=c", false).unwrap(), "https://example.com/a?b=c");
+
+    let path = std::env::temp_dir().join(format!("aim-send-text-{}.txt", std::process::id()));
+    std::fs::write(&path, "line one\nline two\r\n").unwrap();
+    let name = path.to_string_lossy().into_owned();
+    // Only the final line break is dropped
+    assert_eq!(read_input(&name, false).unwrap(), "line one\nline two");
+    assert_eq!(read_input(&name, true).unwrap(), name);
+    std::fs::remove_file(&path).unwrap();
+}
@@ -1,10 +1,15 @@
+use crate::adb::server::AdbServer;
 use crate::commands::SubCommand;
 use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
 use crate::error::Result;
-use crate::library::adb::{start_adb_server, kill_server, check_server_status};
+use crate::library::adb::{start_adb_server, kill_server, check_server_status, find_adb_binary};
 use crate::cli::ServerOperation;
+use crate::output::{OutputFormatter, PlainFormat, TableFormat};
 use async_trait::async_trait;
 use colored::*;
+use comfy_table::{Cell, Color};
+use serde::Serialize;
 
 pub struct ServerCommand;
 
@@ -14,18 +19,143 @@ pub struct ServerArgs {
     pub operation: ServerOperation,
 }
 
+/// Something aim can do, and what it needs to do it
+#[derive(Debug, Clone, Copy)]
+pub struct Capability {
+    pub feature: &'static str,
+    /// Host service or binary invocation used
+    pub via: &'static str,
+    /// Whether the adb binary is needed, rather than just a running server
+    pub needs_binary: bool,
+}
+
+pub const CAPABILITIES: &[Capability] = &[
+    Capability { feature: "List devices", via: "host:devices-l", needs_binary: false },
+    Capability { feature: "Shell commands", via: "shell:", needs_binary: false },
+    Capability { feature: "Push, pull and sync", via: "sync:", needs_binary: false },
+    Capability { feature: "Port forwards", via: "host-serial:forward", needs_binary: false },
+    Capability { feature: "Reverse forwards", via: "reverse:forward", needs_binary: false },
+    Capability { feature: "Wireless connect and pair", via: "host:connect, host:pair", needs_binary: false },
+    Capability { feature: "mDNS discovery", via: "host:mdns:services", needs_binary: false },
+    Capability { feature: "Server status and version", via: "host:version", needs_binary: false },
+    Capability { feature: "Stop server", via: "host:kill", needs_binary: false },
+    Capability { feature: "Start server", via: "adb server", needs_binary: true },
+    Capability { feature: "aim adb pass-through", via: "adb <args>", needs_binary: true },
+];
+
+/// A capability and whether it works right now
+#[derive(Debug, Clone, Serialize)]
+pub struct CapabilityRow {
+    pub feature: String,
+    pub via: String,
+    pub needs_binary: bool,
+    pub available: bool,
+}
+
+/// The capability matrix given what is installed and running
+pub fn capability_rows(server_running: bool, binary_found: bool) -> Vec<CapabilityRow> {
+    CAPABILITIES
+        .iter()
+        .map(|c| CapabilityRow {
+            feature: c.feature.to_string(),
+            via: c.via.to_string(),
+            needs_binary: c.needs_binary,
+            available: if c.needs_binary { binary_found } else { server_running },
+        })
+        .collect()
+}
+
+impl TableFormat for CapabilityRow {
+    fn headers() -> Vec<&'static str> {
+        vec!["FEATURE", "VIA", "NEEDS", "AVAILABLE"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.feature.clone(),
+            self.via.clone(),
+            if self.needs_binary { "adb binary" } else { "running server" }.to_string(),
+            if self.available { "yes" } else { "no" }.to_string(),
+        ]
+    }
+
+    fn colored_row(&self) -> Vec<Cell> {
+        let color = if self.available { Color::Green } else { Color::Red };
+        self.row()
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| if i == 3 { Cell::new(value).fg(color) } else { Cell::new(value) })
+            .collect()
+    }
+}
+
+impl PlainFormat for CapabilityRow {
+    fn plain(&self) -> String {
+        format!("{}\t{}", self.feature, if self.available { "yes" } else { "no" })
+    }
+}
+
+/// Everything `server capabilities` reports
+#[derive(Debug, Clone, Serialize)]
+struct CapabilityReport {
+    adb_binary: Option<String>,
+    server: Option<String>,
+    host_features: Vec<String>,
+    capabilities: Vec<CapabilityRow>,
+}
+
 impl ServerCommand {
     pub fn new() -> Self {
         Self
     }
+
+    async fn capabilities(ctx: &CommandContext, host: &str, port: u16) -> Result<()> {
+        let binary = find_adb_binary();
+        let running = check_server_status(host, &port.to_string());
+        let version = if running { AdbServer::version(host, port).await.ok() } else { None };
+        let host_features = if running {
+            AdbServer::host_features(host, port).await.unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let report = CapabilityReport {
+            adb_binary: binary.as_ref().map(|p| p.display().to_string()),
+            server: running.then(|| format!("{}:{}", host, port)),
+            host_features,
+            capabilities: capability_rows(running, binary.is_some()),
+        };
+
+        let formatter = OutputFormatter::new();
+        match ctx.output_format {
+            OutputFormat::Json => formatter.json(&report)?,
+            OutputFormat::Plain => formatter.plain(&report.capabilities)?,
+            OutputFormat::Table => {
+                match &report.adb_binary {
+                    Some(path) => println!("adb binary:    {}", path),
+                    None => println!("adb binary:    {}", "not found".yellow()),
+                }
+                match (&report.server, &version) {
+                    (Some(addr), Some(v)) => println!("ADB server:    {} (protocol {})", addr, v),
+                    (Some(addr), None) => println!("ADB server:    {}", addr),
+                    (None, _) => println!("ADB server:    {}", "not running".red()),
+                }
+                if !report.host_features.is_empty() {
+                    println!("Host features: {}", report.host_features.join(", "));
+                }
+                println!();
+                formatter.table(&report.capabilities)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl SubCommand for ServerCommand {
     type Args = ServerArgs;
     
-    async fn run(&self, _ctx: &CommandContext, args: Self::Args) -> Result<()> {
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let (host, port) = ctx.adb_address();
         let port_str = port.to_string();
         
         match args.operation {
@@ -62,8 +192,9 @@ impl SubCommand for ServerCommand {
                     println!("{} ADB server is not running", "●".red());
                 }
             }
+            ServerOperation::Capabilities => Self::capabilities(ctx, host, port).await?,
         }
         
         Ok(())
     }
-}
\ No newline at end of file
+}
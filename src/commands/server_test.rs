@@ -0,0 +1,18 @@
+use super::server::{capability_rows, CAPABILITIES};
+
+#[test]
+fn test_capabilities_without_binary() {
+    let rows = capability_rows(true, false);
+    assert_eq!(rows.len(), CAPABILITIES.len());
+
+    // Everything but starting the server and the pass-through works against a running server
+    let missing: Vec<&str> = rows.iter().filter(|r| !r.available).map(|r| r.feature.as_str()).collect();
+    assert_eq!(missing, vec!["Start server", "aim adb pass-through"]);
+}
+
+#[test]
+fn test_capabilities_without_server() {
+    let rows = capability_rows(false, true);
+    let available: Vec<&str> = rows.iter().filter(|r| r.available).map(|r| r.feature.as_str()).collect();
+    assert_eq!(available, vec!["Start server", "aim adb pass-through"]);
+}
@@ -1,9 +1,21 @@
+use crate::adb::shell::{packet, ShellCommand as DeviceShell};
 use crate::commands::{SubCommand, get_device};
+use crate::core::cast::{CastEnv, Header, Recorder};
 use crate::core::context::CommandContext;
-use crate::error::Result;
+use crate::core::types::DeviceId;
+use crate::error::{AimError, Result};
 use crate::library::adb::run_shell_command_async;
 use async_trait::async_trait;
-use std::io::{self, BufRead, Write};
+use crossterm::terminal::{self, disable_raw_mode, enable_raw_mode};
+use colored::*;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type SharedRecorder = Arc<Mutex<Recorder<BufWriter<File>>>>;
 
 pub struct ShellCommand;
 
@@ -16,6 +28,30 @@ pub struct ShellArgs {
     /// Device ID (required if multiple devices are connected)
     #[clap(short = 'd', long = "device")]
     pub device_id: Option<String>,
+
+    /// Stop the command if it runs longer than this, e.g. 30s (exit status 124)
+    #[clap(long, value_parser = crate::utils::parse_duration, requires = "command")]
+    pub timeout: Option<Duration>,
+
+    /// Record the interactive session to an asciinema v2 file (.cast)
+    #[clap(long, value_name = "FILE", conflicts_with = "command")]
+    pub record: Option<PathBuf>,
+}
+
+/// Raw terminal mode for as long as the interactive shell runs
+struct RawMode;
+
+impl RawMode {
+    fn enable() -> Result<Self> {
+        enable_raw_mode()?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+    }
 }
 
 impl ShellCommand {
@@ -23,6 +59,107 @@ impl ShellCommand {
         Self
     }
 
+    /// A login shell on a remote pseudo-terminal, returning its exit status
+    ///
+    /// The local terminal is put in raw mode so every key, Ctrl-C included,
+    /// reaches the remote terminal as typed. With `record`, the output and
+    /// resizes are also written to an asciinema cast.
+    async fn run_pty(&self, host: &str, port: u16, device_id: &DeviceId, record: Option<&Path>) -> Result<i32> {
+        let term = std::env::var("TERM").unwrap_or_else(|_| "xterm-256color".to_string());
+        let mut shell = DeviceShell::new("").with_device(device_id.clone()).open_pty(host, port, &term).await?;
+        let (cols, rows) = terminal::size()?;
+        let recorder: Option<SharedRecorder> = match record {
+            Some(path) => {
+                let header = Header {
+                    version: 2,
+                    width: cols,
+                    height: rows,
+                    timestamp: std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_secs() as i64)
+                        .unwrap_or(0),
+                    title: Some(format!("aim shell {}", device_id)),
+                    env: CastEnv { term: term.clone(), shell: "/system/bin/sh".to_string() },
+                };
+                Some(Arc::new(Mutex::new(Recorder::create(path, &header)?)))
+            }
+            None => None,
+        };
+        let _raw = RawMode::enable()?;
+        shell.resize(rows, cols)?;
+
+        let mut input = shell.try_clone()?;
+        std::thread::spawn(move || {
+            let mut stdin = io::stdin();
+            let mut buf = [0u8; 1024];
+            loop {
+                match stdin.read(&mut buf) {
+                    Ok(0) | Err(_) => {
+                        let _ = input.close_stdin();
+                        break;
+                    }
+                    Ok(n) => {
+                        if input.write_stdin(&buf[..n]).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        // There is no portable resize signal, so the size is polled
+        let done = Arc::new(AtomicBool::new(false));
+        let mut resizer = shell.try_clone()?;
+        let resizer_done = done.clone();
+        let resize_recorder = recorder.clone();
+        std::thread::spawn(move || {
+            let mut last = (cols, rows);
+            while !resizer_done.load(Ordering::Relaxed) {
+                std::thread::sleep(Duration::from_millis(250));
+                if let Ok(size) = terminal::size() {
+                    if size != last {
+                        last = size;
+                        if resizer.resize(size.1, size.0).is_err() {
+                            break;
+                        }
+                        if let Some(recorder) = &resize_recorder {
+                            let _ = recorder.lock().unwrap().resize(size.0, size.1);
+                        }
+                    }
+                }
+            }
+        });
+
+        let mut stdout = io::stdout();
+        let mut stderr = io::stderr();
+        let record_output = |data: &[u8]| -> Result<()> {
+            match &recorder {
+                Some(recorder) => recorder.lock().unwrap().output(data),
+                None => Ok(()),
+            }
+        };
+        let status = loop {
+            match shell.read_packet()? {
+                Some((packet::STDOUT, data)) => {
+                    stdout.write_all(&data)?;
+                    stdout.flush()?;
+                    record_output(&data)?;
+                }
+                Some((packet::STDERR, data)) => {
+                    stderr.write_all(&data)?;
+                    stderr.flush()?;
+                    record_output(&data)?;
+                }
+                Some((packet::EXIT, data)) => break data.first().copied().unwrap_or(0) as i32,
+                Some(_) => {}
+                None => break 0,
+            }
+        };
+        done.store(true, Ordering::Relaxed);
+        Ok(status)
+    }
+
+    /// Line-at-a-time fallback for devices without shell v2
     async fn run_interactive(&self, host: &str, port: &str, device_id: &str) -> Result<()> {
         let stdin = io::stdin();
         let mut stdout = io::stdout();
@@ -71,27 +208,49 @@ impl ShellCommand {
 impl SubCommand for ShellCommand {
     type Args = ShellArgs;
 
-    async fn run(&self, _ctx: &CommandContext, args: Self::Args) -> Result<()> {
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
         let device = get_device(args.device_id.as_deref()).await?;
-        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        let (host, port) = ctx.adb_address();
         let device_id_str = device.id.to_string();
         let port_str = port.to_string();
 
         if args.command.is_empty() {
             // Interactive mode
-            self.run_interactive(host, &port_str, &device_id_str).await
+            crate::core::ci::require_interactive("Interactive shell")?;
+            let result = match self.run_pty(host, port, &device.id, args.record.as_deref()).await {
+                Ok(0) => Ok(()),
+                Ok(status) => Err(AimError::ExitStatus(status)),
+                Err(AimError::AdbProtocol(_)) => {
+                    if args.record.is_some() {
+                        eprintln!("{} Recording needs shell v2 (Android 7+); this session is not recorded", "!".yellow());
+                    }
+                    return self.run_interactive(host, &port_str, &device_id_str).await;
+                }
+                Err(e) => Err(e),
+            };
+            if let (Some(path), Ok(()) | Err(AimError::ExitStatus(_))) = (&args.record, &result) {
+                if !ctx.quiet {
+                    eprintln!("{} Session recorded to {} (play it with `asciinema play`)", "✓".green(), path.display());
+                }
+            }
+            result
         } else {
             // Single command mode
             let cmd = args.command.join(" ");
-            let output = run_shell_command_async(host, &port_str, &cmd, Some(&device_id_str)).await?;
+            let output = DeviceShell::new(cmd)
+                .with_device(device.id.clone())
+                .execute_cancellable(host, port, args.timeout)
+                .await?;
 
-            if !output.is_empty() {
-                print!("{}", output);
-                if !output.ends_with('\n') {
-                    println!();
-                }
+            if !output.stdout.is_empty() {
+                println!("{}", output.stdout);
+            }
+            if !output.stderr.is_empty() {
+                eprintln!("{}", output.stderr);
+            }
+            if !output.success() {
+                return Err(AimError::ExitStatus(output.exit_code));
             }
-
             Ok(())
         }
     }
@@ -0,0 +1,254 @@
+use crate::adb::shell::{ShellBatch, ShellOutput};
+use crate::commands::boot::{reboot, wait_for_boot_completed, wait_for_online};
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::utils::{parse_duration, shell_quote};
+use async_trait::async_trait;
+use colored::*;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+pub struct SoakCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct SoakArgs {
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Number of reboots
+    #[arg(long, default_value_t = 10)]
+    pub reboots: u32,
+
+    /// Shell command to run after each boot; a non-zero exit fails the iteration
+    #[arg(long)]
+    pub between: Option<String>,
+
+    /// Property that must have this value after each boot, as NAME=VALUE (repeatable)
+    #[arg(long = "expect-prop", value_parser = parse_prop_expectation)]
+    pub expect_props: Vec<(String, String)>,
+
+    /// Package that must be installed after each boot (repeatable)
+    #[arg(long = "expect-package")]
+    pub expect_packages: Vec<String>,
+
+    /// Fail an iteration if boot takes longer than this
+    #[arg(long, default_value = "5m", value_parser = parse_duration)]
+    pub boot_timeout: Duration,
+
+    /// Stop at the first failed iteration
+    #[arg(long)]
+    pub stop_on_failure: bool,
+}
+
+/// Parse `--expect-prop NAME=VALUE`
+pub fn parse_prop_expectation(s: &str) -> std::result::Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => Ok((name.trim().to_string(), value.to_string())),
+        _ => Err(format!("Invalid property check '{}', expected NAME=VALUE", s)),
+    }
+}
+
+/// The health checks run after every boot
+#[derive(Debug, Clone, Default)]
+pub struct HealthChecks {
+    pub props: Vec<(String, String)>,
+    pub packages: Vec<String>,
+    pub between: Option<String>,
+}
+
+impl HealthChecks {
+    /// One shell command per check, in the order [`HealthChecks::failures`] reads them
+    pub fn batch(&self) -> ShellBatch {
+        let props = self.props.iter().map(|(name, _)| format!("getprop {}", shell_quote(name)));
+        let packages = self.packages.iter().map(|p| format!("pm list packages {}", shell_quote(p)));
+        props
+            .chain(packages)
+            .chain(self.between.clone())
+            .fold(ShellBatch::new(), |batch, command| batch.command(command))
+    }
+
+    /// What went wrong, given the outputs of [`HealthChecks::batch`]
+    pub fn failures(&self, outputs: &[ShellOutput]) -> Vec<String> {
+        let mut outputs = outputs.iter();
+        let mut failures = Vec::new();
+        for (name, expected) in &self.props {
+            let actual = outputs.next().map(|o| o.stdout.trim()).unwrap_or_default();
+            if actual != expected {
+                failures.push(format!("{} is '{}', expected '{}'", name, actual, expected));
+            }
+        }
+        for package in &self.packages {
+            let installed = outputs
+                .next()
+                .is_some_and(|o| o.stdout.lines().any(|l| l.trim().strip_prefix("package:") == Some(package.as_str())));
+            if !installed {
+                failures.push(format!("{} is not installed", package));
+            }
+        }
+        if let Some(command) = &self.between {
+            match outputs.next() {
+                Some(o) if o.exit_code == 0 => {}
+                Some(o) => failures.push(format!("'{}' exited with {}", command, o.exit_code)),
+                None => failures.push(format!("'{}' did not run", command)),
+            }
+        }
+        failures
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Iteration {
+    pub iteration: u32,
+    /// Seconds from the reboot request to `sys.boot_completed`
+    pub boot_secs: Option<f64>,
+    pub passed: bool,
+    pub failures: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SoakReport {
+    pub serial: String,
+    pub iterations: Vec<Iteration>,
+    pub passed: usize,
+    pub failed: usize,
+    pub min_boot_secs: Option<f64>,
+    pub avg_boot_secs: Option<f64>,
+    pub max_boot_secs: Option<f64>,
+}
+
+impl SoakReport {
+    pub fn new(serial: String, iterations: Vec<Iteration>) -> Self {
+        let boots: Vec<f64> = iterations.iter().filter_map(|i| i.boot_secs).collect();
+        let passed = iterations.iter().filter(|i| i.passed).count();
+        Self {
+            serial,
+            passed,
+            failed: iterations.len() - passed,
+            min_boot_secs: boots.iter().copied().reduce(f64::min),
+            avg_boot_secs: (!boots.is_empty()).then(|| boots.iter().sum::<f64>() / boots.len() as f64),
+            max_boot_secs: boots.iter().copied().reduce(f64::max),
+            iterations,
+        }
+    }
+}
+
+impl SoakCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn print_row(iteration: &Iteration, total: u32) {
+        let boot = iteration
+            .boot_secs
+            .map(|s| format!("{:.1}s", s))
+            .unwrap_or_else(|| "-".to_string());
+        let result = if iteration.passed { "PASS".green() } else { "FAIL".red() };
+        println!(
+            "{:>4}/{:<4}  {:>7}  {}  {}",
+            iteration.iteration,
+            total,
+            boot,
+            result,
+            iteration.failures.join("; ")
+        );
+    }
+
+    fn print_summary(report: &SoakReport) {
+        println!();
+        println!("{}", "Summary".bold());
+        println!("  Passed:  {}/{}", report.passed, report.iterations.len());
+        if let (Some(min), Some(avg), Some(max)) = (report.min_boot_secs, report.avg_boot_secs, report.max_boot_secs) {
+            println!("  Boot:    min {:.1}s, avg {:.1}s, max {:.1}s", min, avg, max);
+        }
+    }
+}
+
+#[async_trait]
+impl SubCommand for SoakCommand {
+    type Args = SoakArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let show_rows = ctx.output_format != OutputFormat::Json;
+        let checks = HealthChecks {
+            props: args.expect_props.clone(),
+            packages: args.expect_packages.clone(),
+            between: args.between.clone(),
+        };
+
+        if show_rows {
+            println!(
+                "Rebooting {} {} times",
+                device.id.to_string().bright_cyan(),
+                args.reboots
+            );
+            println!();
+            println!("{:>9}  {:>7}  RESULT", "ITERATION", "BOOT");
+        }
+
+        let mut iterations = Vec::new();
+        for iteration in 1..=args.reboots {
+            let started = Instant::now();
+            let booted = async {
+                reboot(host, port, &device.id).await?;
+                wait_for_online(host, port, &device.id, started, args.boot_timeout).await?;
+                wait_for_boot_completed(host, port, &device.id, started, args.boot_timeout).await
+            }
+            .await;
+
+            let result = match booted {
+                Ok(boot_secs) => {
+                    let failures = if checks.batch().is_empty() {
+                        Vec::new()
+                    } else {
+                        match checks.batch().with_device(device.id.clone()).execute(host, port).await {
+                            Ok(outputs) => checks.failures(&outputs),
+                            Err(e) => vec![format!("health checks failed to run: {}", e)],
+                        }
+                    };
+                    Iteration {
+                        iteration,
+                        boot_secs: Some(boot_secs),
+                        passed: failures.is_empty(),
+                        failures,
+                    }
+                }
+                Err(e) => Iteration {
+                    iteration,
+                    boot_secs: None,
+                    passed: false,
+                    failures: vec![e.to_string()],
+                },
+            };
+
+            if show_rows {
+                Self::print_row(&result, args.reboots);
+            }
+            // Without a completed boot there is no device to reboot again
+            let stop = result.boot_secs.is_none() || (args.stop_on_failure && !result.passed);
+            iterations.push(result);
+            if stop {
+                break;
+            }
+        }
+
+        let report = SoakReport::new(device.id.to_string(), iterations);
+        if show_rows {
+            Self::print_summary(&report);
+        } else {
+            crate::commands::format_json_output(&report)?;
+        }
+
+        if report.failed > 0 {
+            return Err(AimError::CommandExecution(format!(
+                "{} of {} iterations failed",
+                report.failed,
+                report.iterations.len()
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use super::soak::{parse_prop_expectation, HealthChecks, Iteration, SoakReport};
+use crate::adb::shell::ShellOutput;
+
+fn output(stdout: &str, exit_code: i32) -> ShellOutput {
+    ShellOutput {
+        stdout: stdout.to_string(),
+        stderr: String::new(),
+        exit_code,
+    }
+}
+
+#[test]
+fn test_parse_prop_expectation() {
+    assert_eq!(
+        parse_prop_expectation("sys.usb.state=mtp,adb"),
+        Ok(("sys.usb.state".to_string(), "mtp,adb".to_string()))
+    );
+    assert_eq!(parse_prop_expectation("ro.debuggable="), Ok(("ro.debuggable".to_string(), String::new())));
+    assert!(parse_prop_expectation("no-equals").is_err());
+    assert!(parse_prop_expectation("=value").is_err());
+}
+
+#[test]
+fn test_health_checks() {
+    let checks = HealthChecks {
+        props: vec![("sys.usb.state".to_string(), "adb".to_string())],
+        packages: vec!["com.example".to_string()],
+        between: Some("am start -W com.example".to_string()),
+    };
+    assert_eq!(checks.batch().len(), 3);
+
+    let healthy = [
+        output("adb\n", 0),
+        output("package:com.example\npackage:com.example.test\n", 0),
+        output("Status: ok\n", 0),
+    ];
+    assert!(checks.failures(&healthy).is_empty());
+
+    let broken = [output("mtp\n", 0), output("package:com.example.test\n", 0), output("", 1)];
+    assert_eq!(
+        checks.failures(&broken),
+        vec![
+            "sys.usb.state is 'mtp', expected 'adb'".to_string(),
+            "com.example is not installed".to_string(),
+            "'am start -W com.example' exited with 1".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_soak_report() {
+    let iteration = |n, boot_secs, passed| Iteration {
+        iteration: n,
+        boot_secs,
+        passed,
+        failures: Vec::new(),
+    };
+    let report = SoakReport::new(
+        "abc".to_string(),
+        vec![iteration(1, Some(30.0), true), iteration(2, Some(40.0), false), iteration(3, None, false)],
+    );
+    assert_eq!((report.passed, report.failed), (1, 2));
+    assert_eq!(report.min_boot_secs, Some(30.0));
+    assert_eq!(report.avg_boot_secs, Some(35.0));
+    assert_eq!(report.max_boot_secs, Some(40.0));
+}
@@ -0,0 +1,439 @@
+use crate::adb::file_transfer::FileTransfer;
+use crate::adb::protocol::AdbLstatResponse;
+use crate::commands::fs::{parse_hash_output, HashAlgo};
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::{Device, OutputFormat};
+use crate::error::{AimError, Result};
+use crate::library::adb::{pull, push, ProgressDisplay};
+use crate::output::{OutputFormatter, TableFormat};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use colored::*;
+use comfy_table::{Cell, Color};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Paths hashed per `sha256sum` invocation on the device
+const HASH_BATCH: usize = 50;
+
+pub struct SyncCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct SyncArgs {
+    /// Local directory
+    pub local: PathBuf,
+
+    /// Directory on the device
+    pub remote: String,
+
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+
+    /// Copy newer files in both directions and report conflicts
+    #[clap(long)]
+    pub bidirectional: bool,
+
+    /// Show what would be copied without copying anything
+    #[clap(short = 'n', long)]
+    pub dry_run: bool,
+}
+
+/// Size and modification time (seconds) of a file on one side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileMeta {
+    pub size: u64,
+    pub mtime: i64,
+}
+
+impl FileMeta {
+    pub fn from_stat(stat: &AdbLstatResponse) -> Self {
+        Self {
+            size: stat.size64(),
+            mtime: stat.mtime() as i64,
+        }
+    }
+}
+
+/// Files as they were when the last bidirectional sync finished, identical on
+/// both sides, keyed by relative path
+pub type Baseline = BTreeMap<String, FileMeta>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncAction {
+    Push,
+    Pull,
+    /// Changed on both sides since the last sync
+    Conflict,
+    Skip,
+}
+
+impl std::fmt::Display for SyncAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            SyncAction::Push => "push",
+            SyncAction::Pull => "pull",
+            SyncAction::Conflict => "CONFLICT",
+            SyncAction::Skip => "skip",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// What to do with one path, before contents are compared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Plan {
+    Action(SyncAction),
+    /// Both sides differ from the baseline; hashes decide
+    Compare,
+}
+
+/// Decide what to do with a path from the metadata on each side
+///
+/// A side counts as changed when it no longer matches the baseline. Without
+/// a baseline (first sync) both sides count as changed. Deletions are never
+/// propagated: a file missing on one side is copied from the other.
+pub fn plan_entry(
+    local: Option<FileMeta>,
+    remote: Option<FileMeta>,
+    base: Option<FileMeta>,
+    bidirectional: bool,
+) -> Plan {
+    match (local, remote) {
+        (None, None) => Plan::Action(SyncAction::Skip),
+        (Some(_), None) => Plan::Action(SyncAction::Push),
+        (None, Some(_)) if bidirectional => Plan::Action(SyncAction::Pull),
+        (None, Some(_)) => Plan::Action(SyncAction::Skip),
+        (Some(l), Some(r)) if l == r => Plan::Action(SyncAction::Skip),
+        (Some(l), Some(r)) if !bidirectional => {
+            if l.size != r.size || l.mtime > r.mtime {
+                Plan::Action(SyncAction::Push)
+            } else {
+                Plan::Action(SyncAction::Skip)
+            }
+        }
+        (Some(l), Some(r)) => {
+            let local_changed = base != Some(l);
+            let remote_changed = base != Some(r);
+            match (local_changed, remote_changed) {
+                (true, false) => Plan::Action(SyncAction::Push),
+                (false, true) => Plan::Action(SyncAction::Pull),
+                _ => Plan::Compare,
+            }
+        }
+    }
+}
+
+/// Settle a [`Plan::Compare`] once both sides have been hashed
+///
+/// Identical contents need no copy. Otherwise it is a conflict if a baseline
+/// exists, and on a first sync the newer file wins.
+pub fn resolve_compare(local: FileMeta, remote: FileMeta, has_baseline: bool, same_content: bool) -> SyncAction {
+    if same_content {
+        SyncAction::Skip
+    } else if has_baseline {
+        SyncAction::Conflict
+    } else if local.mtime >= remote.mtime {
+        SyncAction::Push
+    } else {
+        SyncAction::Pull
+    }
+}
+
+/// One path that needs attention
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SyncItem {
+    pub path: String,
+    pub action: SyncAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl TableFormat for SyncItem {
+    fn headers() -> Vec<&'static str> {
+        vec!["ACTION", "PATH", "ERROR"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.action.to_string(),
+            self.path.clone(),
+            self.error.clone().unwrap_or_default(),
+        ]
+    }
+
+    fn colored_row(&self) -> Vec<Cell> {
+        let color = match (self.action, &self.error) {
+            (_, Some(_)) | (SyncAction::Conflict, _) => Color::Red,
+            (SyncAction::Push, _) | (SyncAction::Pull, _) => Color::Green,
+            (SyncAction::Skip, _) => Color::Reset,
+        };
+        let mut cells: Vec<Cell> = self.row().into_iter().map(Cell::new).collect();
+        cells[0] = Cell::new(self.action.to_string()).fg(color);
+        cells
+    }
+}
+
+impl std::fmt::Display for SyncItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\t{}", self.action, self.path)
+    }
+}
+
+/// Where the baseline for a device and directory pair is kept
+fn baseline_path(serial: &str, local: &Path, remote: &str) -> PathBuf {
+    let key = format!("{}|{}|{}", serial, local.display(), remote.trim_end_matches('/'));
+    crate::state::StateStore::default_path()
+        .with_file_name("sync")
+        .join(format!("{}.json", crate::library::hash::sha256_short(&key)))
+}
+
+fn load_baseline(path: &Path) -> Option<Baseline> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents)
+        .map_err(|e| debug!("Ignoring unreadable sync baseline {:?}: {}", path, e))
+        .ok()
+}
+
+fn save_baseline(path: &Path, baseline: &Baseline) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(baseline)?)?;
+    Ok(())
+}
+
+fn local_meta(path: &Path) -> Result<FileMeta> {
+    let metadata = std::fs::metadata(path)?;
+    let mtime = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    Ok(FileMeta { size: metadata.len(), mtime })
+}
+
+fn local_files(root: &Path) -> Result<BTreeMap<String, FileMeta>> {
+    let mut files = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(root) {
+        let entry = entry.map_err(|e| AimError::FileTransfer(e.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(rel) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let rel = rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        files.insert(rel, local_meta(entry.path())?);
+    }
+    Ok(files)
+}
+
+fn remote_join(root: &str, rel: &str) -> String {
+    format!("{}/{}", root.trim_end_matches('/'), rel)
+}
+
+impl SyncCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Walk the device directory with sync listings
+    async fn remote_files(&self, ctx: &CommandContext, device: &Device, root: &str) -> Result<BTreeMap<String, FileMeta>> {
+        let (host, port) = ctx.adb_address();
+        let mut transfer = FileTransfer::new(host, port, Some(&device.id)).await?;
+        Ok(transfer
+            .list_tree(root)
+            .await?
+            .into_iter()
+            .filter(|(rel, _)| !rel.is_empty())
+            .map(|(rel, stat)| (rel, FileMeta::from_stat(&stat)))
+            .collect())
+    }
+
+    /// sha256 of the given files on the device, keyed by relative path
+    async fn remote_hashes(&self, ctx: &CommandContext, device: &Device, root: &str, paths: &[String]) -> Result<BTreeMap<String, String>> {
+        let (host, port) = ctx.adb_address();
+        let root = root.trim_end_matches('/');
+        let mut hashes = BTreeMap::new();
+        for batch in paths.chunks(HASH_BATCH) {
+            let quoted: Vec<String> = batch.iter().map(|p| shell_quote(&remote_join(root, p))).collect();
+            let output = crate::adb::shell::ShellCommand::new(format!(
+                "{} {} 2>/dev/null",
+                HashAlgo::Sha256.tool(),
+                quoted.join(" ")
+            ))
+            .with_device(device.id.clone())
+            .execute(host, port)
+            .await?;
+            for entry in parse_hash_output(&output.stdout) {
+                if let Some(rel) = entry.path.strip_prefix(root).and_then(|p| p.strip_prefix('/')) {
+                    hashes.insert(rel.to_string(), entry.hash);
+                }
+            }
+        }
+        Ok(hashes)
+    }
+
+    async fn copy(&self, ctx: &CommandContext, device: &Device, action: SyncAction, local: &Path, remote: &str) -> Result<()> {
+        let (host, port) = ctx.adb_address();
+        let port_str = port.to_string();
+        let id = device.id.to_string();
+        let result = match action {
+            SyncAction::Push => {
+                let (src, dst) = (local.to_path_buf(), PathBuf::from(remote));
+                push(host, &port_str, Some(&id), &src, &dst, false, ProgressDisplay::Hide).await
+            }
+            SyncAction::Pull => {
+                let (src, dst) = (PathBuf::from(remote), local.to_path_buf());
+                pull(host, &port_str, Some(&id), &src, &dst, ProgressDisplay::Hide).await
+            }
+            SyncAction::Conflict | SyncAction::Skip => return Ok(()),
+        };
+        result.map_err(|e| AimError::FileTransfer(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl SubCommand for SyncCommand {
+    type Args = SyncArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        if !args.local.is_dir() {
+            return Err(AimError::FileTransfer(format!("{} is not a directory", args.local.display())));
+        }
+        let local_root = std::fs::canonicalize(&args.local)?;
+        let remote_root = args.remote.trim_end_matches('/').to_string();
+
+        let baseline_file = baseline_path(&device.id.to_string(), &local_root, &remote_root);
+        let baseline = if args.bidirectional {
+            load_baseline(&baseline_file)
+        } else {
+            None
+        };
+
+        let local = local_files(&local_root)?;
+        let remote = self.remote_files(ctx, &device, &remote_root).await?;
+        let paths: BTreeSet<&String> = local.keys().chain(remote.keys()).collect();
+
+        let mut items = Vec::new();
+        let mut to_compare = Vec::new();
+        for path in paths {
+            let base = baseline.as_ref().and_then(|b| b.get(path)).copied();
+            match plan_entry(local.get(path).copied(), remote.get(path).copied(), base, args.bidirectional) {
+                Plan::Action(action) => items.push(SyncItem { path: path.clone(), action, error: None }),
+                Plan::Compare => to_compare.push(path.clone()),
+            }
+        }
+
+        if !to_compare.is_empty() {
+            let remote_hashes = self.remote_hashes(ctx, &device, &remote_root, &to_compare).await?;
+            for path in to_compare {
+                let local_hash = HashAlgo::Sha256.hash_file(&local_root.join(&path))?;
+                let same = remote_hashes.get(&path) == Some(&local_hash);
+                let base = baseline.as_ref().and_then(|b| b.get(&path));
+                let action = resolve_compare(local[&path], remote[&path], base.is_some(), same);
+                items.push(SyncItem { path, action, error: None });
+            }
+            items.sort_by(|a, b| a.path.cmp(&b.path));
+        }
+
+        // The baseline starts from everything already identical on both sides
+        let mut new_baseline: Baseline = items
+            .iter()
+            .filter(|i| i.action == SyncAction::Skip)
+            .filter_map(|i| match (local.get(&i.path), remote.get(&i.path)) {
+                (Some(l), Some(_)) => Some((i.path.clone(), *l)),
+                _ => None,
+            })
+            .collect();
+
+        items.retain(|i| i.action != SyncAction::Skip);
+
+        if !args.dry_run {
+            for item in items.iter_mut() {
+                let local_path = local_root.join(&item.path);
+                let remote_path = remote_join(&remote_root, &item.path);
+                if let Err(e) = self.copy(ctx, &device, item.action, &local_path, &remote_path).await {
+                    item.error = Some(e.to_string());
+                    continue;
+                }
+                match item.action {
+                    SyncAction::Push => {
+                        new_baseline.insert(item.path.clone(), local[&item.path]);
+                    }
+                    SyncAction::Pull => {
+                        // Keep the device's mtime so the next run sees both sides as unchanged
+                        let meta = remote[&item.path];
+                        let mtime = UNIX_EPOCH + Duration::from_secs(meta.mtime.max(0) as u64);
+                        std::fs::File::options()
+                            .write(true)
+                            .open(&local_path)
+                            .and_then(|f| f.set_modified(mtime))?;
+                        new_baseline.insert(item.path.clone(), meta);
+                    }
+                    SyncAction::Conflict | SyncAction::Skip => {}
+                }
+            }
+
+            if args.bidirectional {
+                // Conflicting files keep their previous baseline so they stay conflicts
+                if let Some(old) = &baseline {
+                    for item in items.iter().filter(|i| i.action == SyncAction::Conflict) {
+                        if let Some(meta) = old.get(&item.path) {
+                            new_baseline.insert(item.path.clone(), *meta);
+                        }
+                    }
+                }
+                save_baseline(&baseline_file, &new_baseline)?;
+            }
+        }
+
+        let formatter = OutputFormatter::new();
+        match ctx.output_format {
+            OutputFormat::Table => {
+                if items.is_empty() {
+                    println!("Already in sync");
+                } else {
+                    formatter.table(&items)?;
+                }
+            }
+            OutputFormat::Json => formatter.json(&items)?,
+            OutputFormat::Plain => formatter.plain(&items)?,
+        }
+
+        let count = |action: SyncAction| items.iter().filter(|i| i.action == action && i.error.is_none()).count();
+        let failed = items.iter().filter(|i| i.error.is_some()).count();
+        let conflicts = count(SyncAction::Conflict);
+        if ctx.output_format != OutputFormat::Json && !items.is_empty() {
+            let verb = if args.dry_run { "Would push" } else { "Pushed" };
+            eprintln!(
+                "{} {}, {} {}, {} conflict(s), {} failed",
+                verb,
+                count(SyncAction::Push),
+                if args.dry_run { "pull" } else { "pulled" },
+                count(SyncAction::Pull),
+                if conflicts > 0 { conflicts.to_string().red() } else { conflicts.to_string().normal() },
+                failed
+            );
+        }
+
+        if conflicts > 0 || failed > 0 {
+            return Err(AimError::CommandExecution(format!(
+                "{} conflict(s) and {} failed transfer(s) need attention",
+                conflicts, failed
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use super::sync::{plan_entry, resolve_compare, FileMeta, Plan, SyncAction};
+
+fn meta(size: u64, mtime: i64) -> Option<FileMeta> {
+    Some(FileMeta { size, mtime })
+}
+
+#[test]
+fn test_plan_one_way() {
+    assert_eq!(plan_entry(meta(10, 100), None, None, false), Plan::Action(SyncAction::Push));
+    assert_eq!(plan_entry(None, meta(10, 100), None, false), Plan::Action(SyncAction::Skip));
+    assert_eq!(plan_entry(meta(10, 100), meta(10, 100), None, false), Plan::Action(SyncAction::Skip));
+    assert_eq!(plan_entry(meta(10, 200), meta(10, 100), None, false), Plan::Action(SyncAction::Push));
+    assert_eq!(plan_entry(meta(12, 100), meta(10, 200), None, false), Plan::Action(SyncAction::Push));
+    // Never overwrite a newer device copy of the same size
+    assert_eq!(plan_entry(meta(10, 100), meta(10, 200), None, false), Plan::Action(SyncAction::Skip));
+}
+
+#[test]
+fn test_plan_bidirectional_with_baseline() {
+    let base = meta(10, 100);
+    assert_eq!(plan_entry(meta(10, 100), meta(10, 100), base, true), Plan::Action(SyncAction::Skip));
+    assert_eq!(plan_entry(meta(11, 150), meta(10, 100), base, true), Plan::Action(SyncAction::Push));
+    assert_eq!(plan_entry(meta(10, 100), meta(11, 150), base, true), Plan::Action(SyncAction::Pull));
+    assert_eq!(plan_entry(meta(11, 150), meta(12, 160), base, true), Plan::Compare);
+    assert_eq!(plan_entry(None, meta(10, 100), base, true), Plan::Action(SyncAction::Pull));
+}
+
+#[test]
+fn test_plan_bidirectional_first_sync() {
+    assert_eq!(plan_entry(meta(10, 100), meta(11, 150), None, true), Plan::Compare);
+    assert_eq!(plan_entry(None, meta(11, 150), None, true), Plan::Action(SyncAction::Pull));
+}
+
+#[test]
+fn test_resolve_compare() {
+    let older = FileMeta { size: 10, mtime: 100 };
+    let newer = FileMeta { size: 12, mtime: 200 };
+
+    assert_eq!(resolve_compare(older, newer, true, true), SyncAction::Skip);
+    assert_eq!(resolve_compare(older, newer, true, false), SyncAction::Conflict);
+    assert_eq!(resolve_compare(older, newer, false, false), SyncAction::Pull);
+    assert_eq!(resolve_compare(newer, older, false, false), SyncAction::Push);
+}
@@ -0,0 +1,315 @@
+use crate::commands::SubCommand;
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use async_trait::async_trait;
+use clap::Subcommand;
+use colored::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use walkdir::WalkDir;
+
+lazy_static! {
+    // "    #00 pc 000000000004f8a4  /apex/.../libc.so (abort+164) (BuildId: 1234)"
+    static ref FRAME_RE: Regex = Regex::new(
+        r"^\s*#(\d+)\s+pc\s+([0-9a-fA-F]+)\s+(\S+)(?:\s+\(offset\s+0x[0-9a-fA-F]+\))?(?:\s+\((.*?)\))?(?:\s+\(BuildId:\s*([0-9a-fA-F]+)\))?\s*$"
+    )
+    .unwrap();
+}
+
+/// Header lines from the tombstone that are worth repeating above the backtrace
+const HEADER_PREFIXES: &[&str] = &["Build fingerprint:", "ABI:", "pid:", "signal ", "Abort message:"];
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum TombstoneCommands {
+    /// Symbolize the backtrace of a pulled tombstone using local symbol files
+    Symbolicate(SymbolicateArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: TombstoneCommands) -> Result<()> {
+    match cmd {
+        TombstoneCommands::Symbolicate(args) => {
+            let cmd = SymbolicateCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+pub struct SymbolicateCommand;
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct SymbolicateArgs {
+    /// Tombstone file pulled from the device
+    pub file: PathBuf,
+
+    /// Directory containing unstripped libraries (searched recursively)
+    #[arg(short = 's', long = "symbols")]
+    pub symbols: PathBuf,
+
+    /// Path to llvm-symbolizer (defaults to $LLVM_SYMBOLIZER or the one on PATH)
+    #[arg(long = "symbolizer")]
+    pub symbolizer: Option<PathBuf>,
+}
+
+/// A single `#NN pc ...` line from a tombstone backtrace
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Frame {
+    pub index: u32,
+    pub pc: u64,
+    pub library: String,
+    pub symbol: Option<String>,
+    pub build_id: Option<String>,
+}
+
+/// One resolved location; inlined calls produce several per frame
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SourceLocation {
+    pub function: String,
+    pub location: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SymbolizedFrame {
+    #[serde(flatten)]
+    frame: Frame,
+    resolved: Vec<SourceLocation>,
+}
+
+/// Parse a backtrace line, returning None for anything that is not a frame
+pub fn parse_frame(line: &str) -> Option<Frame> {
+    let caps = FRAME_RE.captures(line)?;
+    let mut symbol = caps.get(4).map(|m| m.as_str().to_string());
+    let mut build_id = caps.get(5).map(|m| m.as_str().to_string());
+
+    // Frames without a symbol only carry the BuildId group
+    if let Some(id) = symbol.as_deref().and_then(|s| s.strip_prefix("BuildId:")) {
+        build_id = Some(id.trim().to_string());
+        symbol = None;
+    }
+
+    Some(Frame {
+        index: caps[1].parse().ok()?,
+        pc: u64::from_str_radix(&caps[2], 16).ok()?,
+        library: caps[3].to_string(),
+        symbol,
+        build_id,
+    })
+}
+
+/// Extract the frames of the first backtrace in the tombstone (the crashing thread)
+pub fn parse_backtrace(content: &str) -> Vec<Frame> {
+    let mut frames = Vec::new();
+    for line in content.lines() {
+        match parse_frame(line) {
+            Some(frame) => {
+                // Frame numbering restarts for every other thread's backtrace
+                if frame.index == 0 && !frames.is_empty() {
+                    break;
+                }
+                frames.push(frame);
+            }
+            None if !frames.is_empty() && line.trim().is_empty() => break,
+            None => {}
+        }
+    }
+    frames
+}
+
+/// Parse llvm-symbolizer output: one block per address, separated by blank lines,
+/// each block holding function/location line pairs
+pub fn parse_symbolizer_output(output: &str) -> Vec<Vec<SourceLocation>> {
+    output
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .map(|block| {
+            let lines: Vec<&str> = block.lines().collect();
+            lines
+                .chunks(2)
+                .filter(|pair| pair.len() == 2)
+                .map(|pair| SourceLocation {
+                    function: pair[0].trim().to_string(),
+                    location: pair[1].trim().to_string(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Find the unstripped copy of a device library under the symbols directory.
+///
+/// Tries the device path relative to the symbols root first (the layout of
+/// `out/target/product/*/symbols`), then falls back to a search by file name.
+fn find_library(symbols: &Path, device_path: &str) -> Option<PathBuf> {
+    // Libraries loaded straight from an APK show up as "base.apk!libfoo.so"
+    let device_path = device_path.rsplit('!').next().unwrap_or(device_path);
+
+    let relative = symbols.join(device_path.trim_start_matches('/'));
+    if relative.is_file() {
+        return Some(relative);
+    }
+
+    let file_name = Path::new(device_path).file_name()?;
+    WalkDir::new(symbols)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .find(|e| e.file_type().is_file() && e.file_name() == file_name)
+        .map(|e| e.into_path())
+}
+
+impl SymbolicateCommand {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn symbolizer_path(args: &SymbolicateArgs) -> PathBuf {
+        args.symbolizer
+            .clone()
+            .or_else(|| std::env::var_os("LLVM_SYMBOLIZER").map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from("llvm-symbolizer"))
+    }
+
+    async fn symbolize(
+        &self,
+        symbolizer: &Path,
+        library: &Path,
+        addresses: &[u64],
+    ) -> Result<Vec<Vec<SourceLocation>>> {
+        let mut child = tokio::process::Command::new(symbolizer)
+            .arg(format!("--obj={}", library.display()))
+            .arg("--functions=linkage")
+            .arg("--demangle")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                AimError::CommandExecution(format!(
+                    "Failed to run {}: {}",
+                    symbolizer.display(),
+                    e
+                ))
+            })?;
+
+        let input: String = addresses.iter().map(|pc| format!("0x{:x}\n", pc)).collect();
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes()).await?;
+        }
+
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            return Err(AimError::CommandExecution(format!(
+                "{} failed: {}",
+                symbolizer.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(parse_symbolizer_output(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn print_frame(frame: &SymbolizedFrame) {
+        let original = match &frame.frame.symbol {
+            Some(symbol) => format!("{} ({})", frame.frame.library, symbol),
+            None => frame.frame.library.clone(),
+        };
+        println!(
+            "#{:02} pc {:016x}  {}",
+            frame.frame.index,
+            frame.frame.pc,
+            original.dimmed()
+        );
+        for loc in &frame.resolved {
+            println!("    {} {}", loc.function.bright_cyan(), loc.location.yellow());
+        }
+    }
+}
+
+#[async_trait]
+impl SubCommand for SymbolicateCommand {
+    type Args = SymbolicateArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        if !args.symbols.is_dir() {
+            return Err(AimError::InvalidArgument(format!(
+                "Symbols directory not found: {}",
+                args.symbols.display()
+            )));
+        }
+
+        let content = std::fs::read_to_string(&args.file).map_err(|e| {
+            AimError::InvalidArgument(format!("Cannot read {}: {}", args.file.display(), e))
+        })?;
+
+        let frames = parse_backtrace(&content);
+        if frames.is_empty() {
+            return Err(AimError::ParseError(format!(
+                "No backtrace found in {}",
+                args.file.display()
+            )));
+        }
+
+        // Group addresses by library so each object is symbolized in one call
+        let mut by_library: HashMap<&str, Vec<u64>> = HashMap::new();
+        for frame in &frames {
+            by_library.entry(&frame.library).or_default().push(frame.pc);
+        }
+
+        let symbolizer = Self::symbolizer_path(&args);
+        let mut resolved: HashMap<(String, u64), Vec<SourceLocation>> = HashMap::new();
+        for (library, addresses) in by_library {
+            let Some(local) = find_library(&args.symbols, library) else {
+                log::debug!("No symbols for {}", library);
+                continue;
+            };
+            let results = self.symbolize(&symbolizer, &local, &addresses).await?;
+            for (pc, locations) in addresses.iter().zip(results) {
+                resolved.insert((library.to_string(), *pc), locations);
+            }
+        }
+
+        let symbolized: Vec<SymbolizedFrame> = frames
+            .into_iter()
+            .map(|frame| {
+                let resolved = resolved
+                    .get(&(frame.library.clone(), frame.pc))
+                    .cloned()
+                    .unwrap_or_default();
+                SymbolizedFrame { frame, resolved }
+            })
+            .collect();
+
+        if ctx.output_format == OutputFormat::Json {
+            return crate::commands::format_json_output(&symbolized);
+        }
+
+        for line in content.lines() {
+            let trimmed = line.trim_start();
+            if HEADER_PREFIXES.iter().any(|p| trimmed.starts_with(p)) {
+                println!("{}", trimmed);
+            }
+        }
+        println!();
+        println!("{}", "backtrace:".bold());
+        for frame in &symbolized {
+            Self::print_frame(frame);
+        }
+
+        let missing = symbolized.iter().filter(|f| f.resolved.is_empty()).count();
+        if missing > 0 {
+            eprintln!(
+                "{} {} frame(s) could not be symbolized (no matching library under {})",
+                "warning:".yellow().bold(),
+                missing,
+                args.symbols.display()
+            );
+        }
+
+        Ok(())
+    }
+}
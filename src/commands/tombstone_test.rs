@@ -0,0 +1,75 @@
+use super::tombstone::{parse_backtrace, parse_frame, parse_symbolizer_output, Frame};
+
+const TOMBSTONE: &str = r#"*** *** *** *** *** *** *** *** *** *** *** *** *** *** *** ***
+Build fingerprint: 'google/oriole/oriole:14/UP1A.231005.007/10754064:user/release-keys'
+ABI: 'arm64'
+pid: 4321, tid: 4321, name: example  >>> com.example <<<
+signal 6 (SIGABRT), code -1 (SI_QUEUE), fault addr --------
+Abort message: 'boom'
+
+backtrace:
+      #00 pc 000000000005b2ac  /apex/com.android.runtime/lib64/bionic/libc.so (abort+164) (BuildId: a1b2c3)
+      #01 pc 0000000000001234  /data/app/com.example/lib/arm64/libnative.so (BuildId: d4e5f6)
+      #02 pc 0000000000002000  /data/app/com.example/base.apk!libfoo.so (offset 0x1000) (Foo::bar(int)+12)
+
+memory near x0:
+    #00 pc 0000000000000001  /system/lib64/libother.so
+"#;
+
+#[test]
+fn test_parse_frame_with_symbol_and_build_id() {
+    let frame = parse_frame(
+        "  #00 pc 000000000005b2ac  /apex/com.android.runtime/lib64/bionic/libc.so (abort+164) (BuildId: a1b2c3)",
+    )
+    .unwrap();
+
+    assert_eq!(
+        frame,
+        Frame {
+            index: 0,
+            pc: 0x5b2ac,
+            library: "/apex/com.android.runtime/lib64/bionic/libc.so".to_string(),
+            symbol: Some("abort+164".to_string()),
+            build_id: Some("a1b2c3".to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_parse_frame_without_symbol() {
+    let frame = parse_frame("#01 pc 0000000000001234  /data/libnative.so (BuildId: d4e5f6)").unwrap();
+    assert_eq!(frame.symbol, None);
+    assert_eq!(frame.build_id.as_deref(), Some("d4e5f6"));
+
+    let bare = parse_frame("#03 pc 00000000000000ff  /system/lib64/libc.so").unwrap();
+    assert_eq!(bare.pc, 0xff);
+    assert_eq!(bare.symbol, None);
+    assert_eq!(bare.build_id, None);
+}
+
+#[test]
+fn test_parse_frame_rejects_other_lines() {
+    assert!(parse_frame("backtrace:").is_none());
+    assert!(parse_frame("pid: 4321, tid: 4321").is_none());
+    assert!(parse_frame("").is_none());
+}
+
+#[test]
+fn test_parse_backtrace_stops_after_first_thread() {
+    let frames = parse_backtrace(TOMBSTONE);
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[2].library, "/data/app/com.example/base.apk!libfoo.so");
+    assert_eq!(frames[2].symbol.as_deref(), Some("Foo::bar(int)+12"));
+}
+
+#[test]
+fn test_parse_symbolizer_output_with_inlining() {
+    let output = "abort\nbionic/libc/bionic/abort.cpp:50:3\n\ninlined_fn\nsrc/a.cpp:10:1\ncaller\nsrc/a.cpp:20:5\n\n";
+    let results = parse_symbolizer_output(output);
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].len(), 1);
+    assert_eq!(results[0][0].function, "abort");
+    assert_eq!(results[1].len(), 2);
+    assert_eq!(results[1][1].location, "src/a.cpp:20:5");
+}
@@ -0,0 +1,282 @@
+use crate::adb::shell::{helpers::shell_checked, ShellBatch};
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::{DeviceId, OutputFormat};
+use crate::error::{AimError, Result};
+use async_trait::async_trait;
+use clap::Subcommand;
+use colored::*;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum UiCommands {
+    /// Show or set the font size multiplier (Settings > Display > Font size)
+    FontScale(FontScaleArgs),
+    /// Show or set the display size (Settings > Display > Display size)
+    DisplaySize(DisplaySizeArgs),
+    /// Put font scale and display size back to the defaults
+    Reset(ResetArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: UiCommands) -> Result<()> {
+    match cmd {
+        UiCommands::FontScale(args) => {
+            let cmd = FontScaleCommand::new();
+            cmd.run(ctx, args).await
+        }
+        UiCommands::DisplaySize(args) => {
+            let cmd = DisplaySizeCommand::new();
+            cmd.run(ctx, args).await
+        }
+        UiCommands::Reset(args) => {
+            let cmd = ResetCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+/// Display sizes as offered in Settings, relative to the panel's own density
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum DisplaySize {
+    Small,
+    Default,
+    Large,
+    Larger,
+    Largest,
+}
+
+impl DisplaySize {
+    const ALL: [DisplaySize; 5] = [
+        DisplaySize::Small,
+        DisplaySize::Default,
+        DisplaySize::Large,
+        DisplaySize::Larger,
+        DisplaySize::Largest,
+    ];
+
+    /// Density multiplier of the size, close to what Settings uses
+    pub fn factor(self) -> f64 {
+        match self {
+            DisplaySize::Small => 0.85,
+            DisplaySize::Default => 1.0,
+            DisplaySize::Large => 1.15,
+            DisplaySize::Larger => 1.3,
+            DisplaySize::Largest => 1.45,
+        }
+    }
+
+    /// Density for a panel with the given physical density
+    pub fn density(self, physical: u32) -> u32 {
+        (physical as f64 * self.factor()).round() as u32
+    }
+
+    /// The size a density corresponds to, if it is one of the presets
+    pub fn from_density(physical: u32, density: u32) -> Option<DisplaySize> {
+        Self::ALL.into_iter().find(|size| size.density(physical) == density)
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            DisplaySize::Small => "small",
+            DisplaySize::Default => "default",
+            DisplaySize::Large => "large",
+            DisplaySize::Larger => "larger",
+            DisplaySize::Largest => "largest",
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct FontScaleArgs {
+    /// Multiplier such as 0.85, 1.3 or 2, or `default`; leave out to show the current one
+    #[arg(value_parser = parse_font_scale)]
+    pub scale: Option<f64>,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct DisplaySizeArgs {
+    /// Size to switch to; leave out to show the current one
+    #[arg(value_enum)]
+    pub size: Option<DisplaySize>,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ResetArgs {
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+}
+
+/// Parse a font scale argument. Used as a clap `value_parser`.
+pub fn parse_font_scale(s: &str) -> std::result::Result<f64, String> {
+    if s.eq_ignore_ascii_case("default") {
+        return Ok(1.0);
+    }
+    let scale: f64 = s
+        .parse()
+        .map_err(|_| format!("'{}' is not a number (e.g. 1.3) or 'default'", s))?;
+    if !(0.25..=3.0).contains(&scale) {
+        return Err(format!("Font scale {} is outside 0.25-3.0", scale));
+    }
+    Ok(scale)
+}
+
+/// Physical and override density from `wm density`
+pub fn parse_wm_density(output: &str) -> (Option<u32>, Option<u32>) {
+    let value = |key: &str| {
+        output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(key))
+            .and_then(|v| v.trim().parse().ok())
+    };
+    (value("Physical density:"), value("Override density:"))
+}
+
+/// Font scale from `settings get system font_scale`, 1.0 when never set
+pub fn parse_font_scale_setting(output: &str) -> f64 {
+    output.trim().parse().unwrap_or(1.0)
+}
+
+/// Current font scale and display density
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UiState {
+    pub font_scale: f64,
+    pub physical_density: Option<u32>,
+    pub density: Option<u32>,
+    pub display_size: Option<DisplaySize>,
+}
+
+async fn read_state(host: &str, port: u16, device: &DeviceId) -> Result<UiState> {
+    let outputs = ShellBatch::new()
+        .with_device(device.clone())
+        .command("settings get system font_scale")
+        .command("wm density")
+        .execute(host, port)
+        .await?;
+    let output = |i: usize| outputs.get(i).map(|o| o.stdout.as_str()).unwrap_or_default();
+    let (physical, over) = parse_wm_density(output(1));
+    let density = over.or(physical);
+    Ok(UiState {
+        font_scale: parse_font_scale_setting(output(0)),
+        physical_density: physical,
+        density,
+        display_size: physical.zip(density).and_then(|(p, d)| DisplaySize::from_density(p, d)),
+    })
+}
+
+fn print_state(ctx: &CommandContext, state: &UiState) -> Result<()> {
+    match ctx.output_format {
+        OutputFormat::Json => crate::commands::format_json_output(state)?,
+        _ => {
+            println!("Font scale:    {}", state.font_scale);
+            let density = match (state.density, state.physical_density) {
+                (Some(d), Some(p)) if d != p => format!("{} (physical {})", d, p),
+                (Some(d), _) => d.to_string(),
+                (None, _) => "unknown".to_string(),
+            };
+            println!("Density:       {}", density);
+            let size = state.display_size.map(|s| s.as_str()).unwrap_or("custom");
+            println!("Display size:  {}", size);
+        }
+    }
+    Ok(())
+}
+
+pub struct FontScaleCommand;
+
+impl FontScaleCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for FontScaleCommand {
+    type Args = FontScaleArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        let Some(scale) = args.scale else {
+            let state = read_state(host, port, &device.id).await?;
+            match ctx.output_format {
+                OutputFormat::Json => crate::commands::format_json_output(&state)?,
+                _ => println!("{}", state.font_scale),
+            }
+            return Ok(());
+        };
+        shell_checked(host, port, &device.id, &format!("settings put system font_scale {}", scale)).await?;
+        if !ctx.quiet {
+            println!("{} Font scale set to {}", "✓".green(), scale.to_string().bright_cyan());
+        }
+        Ok(())
+    }
+}
+
+pub struct DisplaySizeCommand;
+
+impl DisplaySizeCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for DisplaySizeCommand {
+    type Args = DisplaySizeArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let state = read_state(host, port, &device.id).await?;
+
+        let Some(size) = args.size else {
+            return print_state(ctx, &state);
+        };
+        if size == DisplaySize::Default {
+            shell_checked(host, port, &device.id, "wm density reset").await?;
+        } else {
+            let physical = state.physical_density.ok_or_else(|| {
+                AimError::CommandExecution("Could not read the physical density from wm density".to_string())
+            })?;
+            shell_checked(host, port, &device.id, &format!("wm density {}", size.density(physical))).await?;
+        }
+        if !ctx.quiet {
+            println!("{} Display size set to {}", "✓".green(), size.as_str().bright_cyan());
+        }
+        Ok(())
+    }
+}
+
+pub struct ResetCommand;
+
+impl ResetCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for ResetCommand {
+    type Args = ResetArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        shell_checked(host, port, &device.id, "settings put system font_scale 1.0").await?;
+        shell_checked(host, port, &device.id, "wm density reset").await?;
+        if !ctx.quiet {
+            println!("{} Font scale and display size are back to the defaults", "✓".green());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use super::ui::{parse_font_scale, parse_font_scale_setting, parse_wm_density, DisplaySize};
+
+#[test]
+fn test_parse_font_scale() {
+    assert_eq!(parse_font_scale("1.3").unwrap(), 1.3);
+    assert_eq!(parse_font_scale("2").unwrap(), 2.0);
+    assert_eq!(parse_font_scale("default").unwrap(), 1.0);
+    assert!(parse_font_scale("0").is_err());
+    assert!(parse_font_scale("5").is_err());
+    assert!(parse_font_scale("big").is_err());
+}
+
+#[test]
+fn test_parse_font_scale_setting() {
+    assert_eq!(parse_font_scale_setting("1.15\n"), 1.15);
+    assert_eq!(parse_font_scale_setting("null\n"), 1.0);
+}
+
+#[test]
+fn test_parse_wm_density() {
+    assert_eq!(parse_wm_density("Physical density: 420\n"), (Some(420), None));
+    assert_eq!(
+        parse_wm_density("Physical density: 420\nOverride density: 546\n"),
+        (Some(420), Some(546))
+    );
+    assert_eq!(parse_wm_density(""), (None, None));
+}
+
+#[test]
+fn test_display_size_density() {
+    assert_eq!(DisplaySize::Small.density(420), 357);
+    assert_eq!(DisplaySize::Default.density(420), 420);
+    assert_eq!(DisplaySize::Larger.density(420), 546);
+    assert_eq!(DisplaySize::from_density(420, 546), Some(DisplaySize::Larger));
+    assert_eq!(DisplaySize::from_density(420, 420), Some(DisplaySize::Default));
+    assert_eq!(DisplaySize::from_density(420, 500), None);
+}
@@ -0,0 +1,363 @@
+use crate::adb::shell::{helpers::shell, ShellBatch};
+use crate::android::sdk::{self, Capability};
+use crate::commands::{get_device, SubCommand};
+use crate::core::context::CommandContext;
+use crate::core::types::{DeviceId, OutputFormat};
+use crate::device::extras::parse_inet_addr;
+use crate::error::{AimError, Result};
+use crate::utils::shell_quote;
+use async_trait::async_trait;
+use clap::Subcommand;
+use colored::*;
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum WifiCommands {
+    /// Join a Wi-Fi network
+    Connect(ConnectArgs),
+    /// Show the current Wi-Fi connection
+    Status(StatusArgs),
+    /// Remove a saved Wi-Fi network
+    Forget(ForgetArgs),
+}
+
+pub async fn run(ctx: &CommandContext, cmd: WifiCommands) -> Result<()> {
+    match cmd {
+        WifiCommands::Connect(args) => {
+            let cmd = ConnectCommand::new();
+            cmd.run(ctx, args).await
+        }
+        WifiCommands::Status(args) => {
+            let cmd = StatusCommand::new();
+            cmd.run(ctx, args).await
+        }
+        WifiCommands::Forget(args) => {
+            let cmd = ForgetCommand::new();
+            cmd.run(ctx, args).await
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Security {
+    Open,
+    Owe,
+    Wpa2,
+    Wpa3,
+}
+
+impl Security {
+    fn as_str(self) -> &'static str {
+        match self {
+            Security::Open => "open",
+            Security::Owe => "owe",
+            Security::Wpa2 => "wpa2",
+            Security::Wpa3 => "wpa3",
+        }
+    }
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ConnectArgs {
+    /// Network name
+    pub ssid: String,
+
+    /// Passphrase; leave out for open networks
+    #[arg(long)]
+    pub password: Option<String>,
+
+    /// Security type (defaults to wpa2 with a password, open without)
+    #[arg(long, value_enum)]
+    pub security: Option<Security>,
+
+    /// How long to wait for the device to join the network
+    #[arg(long, value_parser = crate::utils::parse_duration, default_value = "30s")]
+    pub wait: Duration,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct StatusArgs {
+    /// Device ID (required if multiple devices are connected)
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct ForgetArgs {
+    /// Network name
+    pub ssid: String,
+
+    /// Device ID (required if multiple devices are connected)
+    #[arg(short = 'd', long = "device")]
+    pub device_id: Option<String>,
+}
+
+/// Wi-Fi state as reported by `cmd wifi status` or `dumpsys wifi`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct WifiStatus {
+    pub enabled: Option<bool>,
+    pub ssid: Option<String>,
+    pub bssid: Option<String>,
+    pub rssi: Option<i32>,
+    pub link_speed: Option<String>,
+    pub frequency: Option<String>,
+    pub ip: Option<String>,
+}
+
+/// Whether the device lacks the `cmd wifi` shell commands (before Android 11)
+pub fn is_unsupported(output: &str) -> bool {
+    let output = output.to_lowercase();
+    ["can't find service", "unknown command", "no shell command implementation"]
+        .iter()
+        .any(|marker| output.contains(marker))
+}
+
+/// `cmd wifi connect-network` for the given network
+pub fn connect_command(ssid: &str, password: Option<&str>, security: Option<Security>) -> String {
+    let security = security.unwrap_or(if password.is_some() { Security::Wpa2 } else { Security::Open });
+    let mut command = format!("cmd wifi connect-network {} {}", shell_quote(ssid), security.as_str());
+    if let Some(password) = password.filter(|_| !matches!(security, Security::Open | Security::Owe)) {
+        command.push(' ');
+        command.push_str(&shell_quote(password));
+    }
+    command
+}
+
+/// Parse `cmd wifi status`, or the `mWifiInfo` line of `dumpsys wifi`
+pub fn parse_status(output: &str) -> WifiStatus {
+    let mut status = WifiStatus::default();
+    for line in output.lines().map(str::trim) {
+        if line.starts_with("Wifi is enabled") || line == "Wi-Fi is enabled" {
+            status.enabled = Some(true);
+        } else if line.starts_with("Wifi is disabled") || line == "Wi-Fi is disabled" {
+            status.enabled = Some(false);
+        }
+    }
+
+    // "WifiInfo: SSID: "lab", BSSID: 02:00:00:00:00:00, ..., RSSI: -52, Link speed: 866Mbps, ..."
+    let Some(info) = output.lines().find(|l| l.contains("SSID: ") && l.contains("BSSID: ")) else {
+        return status;
+    };
+    let info = info.trim().trim_start_matches("mWifiInfo").trim_start_matches("WifiInfo:").trim();
+    for field in info.split(", ") {
+        let Some((key, value)) = field.split_once(": ") else { continue };
+        let value = value.trim();
+        match key.trim() {
+            "SSID" => {
+                let ssid = value.trim_matches('"');
+                if !ssid.is_empty() && ssid != "<unknown ssid>" {
+                    status.ssid = Some(ssid.to_string());
+                }
+            }
+            "BSSID" if value != "<none>" && value != "02:00:00:00:00:00" => status.bssid = Some(value.to_string()),
+            "RSSI" => status.rssi = value.parse().ok().filter(|&rssi| rssi > -127),
+            "Link speed" => status.link_speed = Some(value.to_string()),
+            "Frequency" => status.frequency = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    status
+}
+
+/// Ids of saved networks called `ssid`, from `cmd wifi list-networks`
+pub fn parse_network_ids(output: &str, ssid: &str) -> Vec<String> {
+    output
+        .lines()
+        .skip_while(|l| !l.trim_start().starts_with("Network Id"))
+        .skip(1)
+        .filter_map(|line| {
+            let line = line.trim();
+            let (id, rest) = line.split_once(char::is_whitespace)?;
+            id.parse::<u32>().ok()?;
+            // The security type is the last column, the SSID is everything between
+            let (name, _security) = rest.trim().rsplit_once(char::is_whitespace)?;
+            (name.trim() == ssid).then(|| id.to_string())
+        })
+        .collect()
+}
+
+/// `dumpsys wifi`, cut down to the lines [`parse_status`] reads
+const DUMPSYS_STATUS: &str = "dumpsys wifi | grep -E '^Wi-Fi is|mWifiInfo'";
+
+/// Current status, through `cmd wifi` or `dumpsys wifi` on older releases
+async fn read_status(host: &str, port: u16, device: &DeviceId) -> Result<WifiStatus> {
+    let has_cmd_wifi = sdk::for_device(host, port, device).await.supports(Capability::CmdWifi);
+    let outputs = ShellBatch::new()
+        .with_device(device.clone())
+        .command(if has_cmd_wifi { "cmd wifi status 2>&1" } else { DUMPSYS_STATUS })
+        .command("ip -f inet addr show wlan0")
+        .execute(host, port)
+        .await?;
+    let (status_out, ip_out) = match outputs.as_slice() {
+        [status, ip] => (status.stdout.as_str(), ip.stdout.as_str()),
+        _ => return Err(AimError::CommandExecution("Unexpected output from the device".to_string())),
+    };
+    let mut status = if is_unsupported(status_out) {
+        // An unknown SDK level, or a build without the shell commands
+        let dumpsys = shell(host, port, device, DUMPSYS_STATUS).await?;
+        parse_status(&dumpsys)
+    } else {
+        parse_status(status_out)
+    };
+    status.ip = parse_inet_addr(ip_out);
+    Ok(status)
+}
+
+fn print_status(status: &WifiStatus) {
+    let enabled = match status.enabled {
+        Some(true) => "enabled".green(),
+        Some(false) => "disabled".red(),
+        None => "unknown".normal(),
+    };
+    println!("Wi-Fi:      {}", enabled);
+    match &status.ssid {
+        Some(ssid) => println!("Network:    {}", ssid.bright_cyan()),
+        None => println!("Network:    {}", "not connected".dimmed()),
+    }
+    let rows = [
+        ("BSSID:", status.bssid.clone()),
+        ("Signal:", status.rssi.map(|rssi| format!("{} dBm", rssi))),
+        ("Speed:", status.link_speed.clone()),
+        ("Frequency:", status.frequency.clone()),
+        ("IP:", status.ip.clone()),
+    ];
+    for (label, value) in rows {
+        if let Some(value) = value {
+            println!("{:<11} {}", label, value);
+        }
+    }
+}
+
+pub struct ConnectCommand;
+
+impl ConnectCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for ConnectCommand {
+    type Args = ConnectArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        if let Some(missing) = sdk::for_device(host, port, &device.id).await.missing(Capability::CmdWifi) {
+            return Err(AimError::CommandExecution(format!(
+                "{}. Join the network on the device, then use `aim wifi status` to check.",
+                missing
+            )));
+        }
+        let enabled = shell(host, port, &device.id, "cmd wifi set-wifi-enabled enabled 2>&1").await?;
+        if is_unsupported(&enabled) {
+            return Err(AimError::CommandExecution(
+                "This device has no `cmd wifi` (Android 11+ is needed to join networks from the shell). \
+                 Join the network on the device, then use `aim wifi status` to check."
+                    .to_string(),
+            ));
+        }
+
+        let command = connect_command(&args.ssid, args.password.as_deref(), args.security);
+        let out = shell(host, port, &device.id, &format!("{} 2>&1", command)).await?;
+        if out.to_lowercase().contains("fail") || out.contains("Exception") {
+            return Err(AimError::CommandExecution(format!(
+                "Could not connect to '{}': {}",
+                args.ssid,
+                out.trim()
+            )));
+        }
+
+        if !ctx.quiet {
+            println!("Connecting {} to {}...", device.id.to_string().bright_cyan(), args.ssid);
+        }
+        let started = Instant::now();
+        loop {
+            let status = read_status(host, port, &device.id).await?;
+            if status.ssid.as_deref() == Some(args.ssid.as_str()) && status.ip.is_some() {
+                match ctx.output_format {
+                    OutputFormat::Json => crate::commands::format_json_output(&status)?,
+                    _ if ctx.quiet => {}
+                    _ => print_status(&status),
+                }
+                return Ok(());
+            }
+            if started.elapsed() > args.wait {
+                return Err(AimError::CommandExecution(format!(
+                    "{} did not join '{}' within {}s; check the password and security type",
+                    device.id,
+                    args.ssid,
+                    args.wait.as_secs()
+                )));
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+}
+
+pub struct StatusCommand;
+
+impl StatusCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for StatusCommand {
+    type Args = StatusArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+        let status = read_status(host, port, &device.id).await?;
+        match ctx.output_format {
+            OutputFormat::Json => crate::commands::format_json_output(&status)?,
+            _ => print_status(&status),
+        }
+        Ok(())
+    }
+}
+
+pub struct ForgetCommand;
+
+impl ForgetCommand {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SubCommand for ForgetCommand {
+    type Args = ForgetArgs;
+
+    async fn run(&self, ctx: &CommandContext, args: Self::Args) -> Result<()> {
+        let device = get_device(args.device_id.as_deref()).await?;
+        let (host, port) = ctx.adb_address();
+
+        sdk::for_device(host, port, &device.id).await.require(Capability::CmdWifi)?;
+        let networks = shell(host, port, &device.id, "cmd wifi list-networks 2>&1").await?;
+        if is_unsupported(&networks) {
+            return Err(AimError::CommandExecution(
+                "This device has no `cmd wifi` (Android 11+ is needed to forget networks from the shell)"
+                    .to_string(),
+            ));
+        }
+        let ids = parse_network_ids(&networks, &args.ssid);
+        if ids.is_empty() {
+            return Err(AimError::InvalidArgument(format!("No saved network called '{}'", args.ssid)));
+        }
+        for id in &ids {
+            shell(host, port, &device.id, &format!("cmd wifi forget-network {}", id)).await?;
+        }
+        if !ctx.quiet {
+            println!("Forgot {} on {}", args.ssid, device.id.to_string().bright_cyan());
+        }
+        Ok(())
+    }
+}
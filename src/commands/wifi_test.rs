@@ -0,0 +1,67 @@
+use super::wifi::{connect_command, is_unsupported, parse_network_ids, parse_status, Security, WifiStatus};
+
+#[test]
+fn test_connect_command() {
+    assert_eq!(
+        connect_command("lab 5g", Some("it's"), None),
+        "cmd wifi connect-network 'lab 5g' wpa2 'it'\\''s'"
+    );
+    assert_eq!(connect_command("guest", None, None), "cmd wifi connect-network guest open");
+    assert_eq!(
+        connect_command("lab", Some("secret"), Some(Security::Wpa3)),
+        "cmd wifi connect-network lab wpa3 secret"
+    );
+    assert_eq!(connect_command("cafe", Some("ignored"), Some(Security::Open)), "cmd wifi connect-network cafe open");
+}
+
+#[test]
+fn test_parse_cmd_wifi_status() {
+    let output = "Wifi is enabled\n\
+Wifi scanning is always available\n\
+Wifi is connected to \"lab-5g\"\n\
+WifiInfo: SSID: \"lab-5g\", BSSID: 8c:3b:ad:12:34:56, MAC: 02:00:00:00:00:00, Security type: 2, \
+Supplicant state: COMPLETED, Wi-Fi standard: 11ac, RSSI: -52, Link speed: 866Mbps, Tx Link speed: 866Mbps, \
+Frequency: 5180MHz, Net ID: 3\n";
+    assert_eq!(
+        parse_status(output),
+        WifiStatus {
+            enabled: Some(true),
+            ssid: Some("lab-5g".to_string()),
+            bssid: Some("8c:3b:ad:12:34:56".to_string()),
+            rssi: Some(-52),
+            link_speed: Some("866Mbps".to_string()),
+            frequency: Some("5180MHz".to_string()),
+            ip: None,
+        }
+    );
+}
+
+#[test]
+fn test_parse_status_disconnected() {
+    let output = "Wi-Fi is enabled\n\
+mWifiInfo SSID: <unknown ssid>, BSSID: <none>, MAC: 02:00:00:00:00:00, RSSI: -127, Link speed: -1Mbps\n";
+    let status = parse_status(output);
+    assert_eq!(status.enabled, Some(true));
+    assert_eq!(status.ssid, None);
+    assert_eq!(status.bssid, None);
+    assert_eq!(status.rssi, None);
+    assert_eq!(parse_status("Wifi is disabled\n").enabled, Some(false));
+}
+
+#[test]
+fn test_parse_network_ids() {
+    let output = "Network Id      SSID                         Security type\n\
+0            lab-5g                       wpa2-psk\n\
+3            guest wifi                   open\n\
+4            lab-5g                       wpa3-sae\n";
+    assert_eq!(parse_network_ids(output, "lab-5g"), vec!["0", "4"]);
+    assert_eq!(parse_network_ids(output, "guest wifi"), vec!["3"]);
+    assert!(parse_network_ids(output, "missing").is_empty());
+}
+
+#[test]
+fn test_is_unsupported() {
+    assert!(is_unsupported("Can't find service: wifi"));
+    assert!(is_unsupported("Unknown command: connect-network"));
+    assert!(!is_unsupported("Wifi is enabled"));
+}
@@ -14,6 +14,13 @@ pub struct Config {
     pub screenshot: Option<ScreenshotConfig>,
     #[serde(default)]
     pub screenrecord: Option<ScreenrecordConfig>,
+    #[serde(default)]
+    pub profiles: HashMap<String, ProfileConfig>,
+    #[serde(default)]
+    pub upload: Option<UploadConfig>,
+    /// Notify when a long command runs at least this long, without `--notify`
+    #[serde(default)]
+    pub notify_after_secs: Option<u64>,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -21,14 +28,55 @@ pub struct DeviceConfig {
     pub name: Option<String>,
 }
 
+/// Where to find the adb server, selected with `--profile`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ProfileConfig {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    /// `user@host` to reach the server through an SSH tunnel
+    pub via_ssh: Option<String>,
+}
+
 #[derive(Debug, Default, Deserialize)]
 pub struct ScreenshotConfig {
     pub output: Option<String>,
+    /// File name template, see `crate::core::artifact`
+    pub name_template: Option<String>,
 }
 
 #[derive(Debug, Default, Deserialize)]
 pub struct ScreenrecordConfig {
     pub output: Option<String>,
+    /// File name template, see `crate::core::artifact`
+    pub name_template: Option<String>,
+}
+
+/// Where `--upload` sends captured artifacts
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UploadKind {
+    /// `aws s3 cp` to `s3://<bucket>/<prefix>/<name>`
+    S3,
+    /// `gcloud storage cp` to `gs://<bucket>/<prefix>/<name>`
+    Gcs,
+    /// HTTP PUT (with curl) to `url`
+    Http,
+    /// Any shell command, with `{file}`, `{name}` and `{key}` filled in
+    Command,
+}
+
+/// The `[upload]` section
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UploadConfig {
+    pub kind: UploadKind,
+    pub bucket: Option<String>,
+    /// Prepended to the file name to form the object key
+    pub prefix: Option<String>,
+    /// PUT target for `http`; for the others, the URL to print, e.g.
+    /// `https://artifacts.example.com/{key}`
+    pub url: Option<String>,
+    pub command: Option<String>,
 }
 
 impl ScreenshotConfig {
@@ -90,6 +138,10 @@ impl Config {
                                     .get("output")
                                     .and_then(|v| v.as_str())
                                     .map(String::from),
+                                name_template: screenshot_section
+                                    .get("name_template")
+                                    .and_then(|v| v.as_str())
+                                    .map(String::from),
                             });
                         }
 
@@ -101,9 +153,51 @@ impl Config {
                                     .get("output")
                                     .and_then(|v| v.as_str())
                                     .map(String::from),
+                                name_template: screenrecord_section
+                                    .get("name_template")
+                                    .and_then(|v| v.as_str())
+                                    .map(String::from),
                             });
                         }
 
+                        // Parse upload section
+                        if let Some(upload_section) = toml.get("upload") {
+                            debug!("Processing upload section: {:?}", upload_section);
+                            match upload_section.clone().try_into::<UploadConfig>() {
+                                Ok(upload) => config.upload = Some(upload),
+                                Err(e) => eprintln!("Invalid [upload] section in {}: {}", config_path.display(), e),
+                            }
+                        }
+
+                        if let Some(value) = toml.get("notify_after_secs") {
+                            match value.as_integer().and_then(|n| u64::try_from(n).ok()) {
+                                Some(secs) => config.notify_after_secs = Some(secs),
+                                None => eprintln!(
+                                    "Invalid notify_after_secs in {}: expected a number of seconds",
+                                    config_path.display()
+                                ),
+                            }
+                        }
+
+                        // Parse profile sections
+                        if let Some(profile_section) = toml.get("profile").and_then(|v| v.as_table()) {
+                            debug!("Processing profile section: {:?}", profile_section);
+                            for (name, value) in profile_section {
+                                if let Some(table) = value.as_table() {
+                                    let get = |key: &str| table.get(key).and_then(|v| v.as_str()).map(String::from);
+                                    let profile = ProfileConfig {
+                                        host: get("host"),
+                                        port: table
+                                            .get("port")
+                                            .and_then(|v| v.as_integer())
+                                            .and_then(|p| u16::try_from(p).ok()),
+                                        via_ssh: get("via_ssh"),
+                                    };
+                                    config.profiles.insert(name.to_string(), profile);
+                                }
+                            }
+                        }
+
                         debug!("Final config: {:?}", config);
                         config
                     }
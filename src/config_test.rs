@@ -31,6 +31,9 @@ fn test_alias_resolution() {
         devices: HashMap::new(),
         screenshot: None,
         screenrecord: None,
+        profiles: HashMap::new(),
+        upload: None,
+        notify_after_secs: None,
     };
 
     assert_eq!(config.resolve_alias("ls"), "shell ls -la");
@@ -60,6 +63,9 @@ fn test_device_name_lookup() {
         },
         screenshot: None,
         screenrecord: None,
+        profiles: HashMap::new(),
+        upload: None,
+        notify_after_secs: None,
     };
 
     assert_eq!(
@@ -122,6 +128,9 @@ fn test_case_insensitive_device_lookup() {
         },
         screenshot: None,
         screenrecord: None,
+        profiles: HashMap::new(),
+        upload: None,
+        notify_after_secs: None,
     };
 
     assert_eq!(
@@ -195,6 +204,9 @@ fn test_device_name_partial_match() {
         },
         screenshot: None,
         screenrecord: None,
+        profiles: HashMap::new(),
+        upload: None,
+        notify_after_secs: None,
     };
 
     // Ambiguous partial match should return None
@@ -221,6 +233,9 @@ fn test_device_without_name() {
         },
         screenshot: None,
         screenrecord: None,
+        profiles: HashMap::new(),
+        upload: None,
+        notify_after_secs: None,
     };
 
     assert_eq!(config.get_device_name("device123"), None);
@@ -261,6 +276,9 @@ fn test_multiple_partial_matches() {
         },
         screenshot: None,
         screenrecord: None,
+        profiles: HashMap::new(),
+        upload: None,
+        notify_after_secs: None,
     };
 
     // Multiple matches should return None
@@ -271,3 +289,26 @@ fn test_multiple_partial_matches() {
         Some("First Phone".to_string())
     );
 }
+
+#[test]
+fn test_profiles() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = create_test_config(
+        &temp_dir,
+        r#"
+[profile.lab]
+via_ssh = "me@lab-box"
+port = 5038
+
+[profile.remote]
+host = "10.0.0.5"
+"#,
+    );
+
+    let config = Config::load_from_path(&config_path);
+    let lab = &config.profiles["lab"];
+    assert_eq!(lab.via_ssh.as_deref(), Some("me@lab-box"));
+    assert_eq!(lab.port, Some(5038));
+    assert_eq!(lab.host, None);
+    assert_eq!(config.profiles["remote"].host.as_deref(), Some("10.0.0.5"));
+}
@@ -0,0 +1,147 @@
+//! File names for captured artifacts (screenshots, recordings)
+//!
+//! Names come from a template such as `{alias}-{ts}-{counter}`, given with
+//! `--name-template` or as `name_template` in the command's config section.
+
+use crate::core::types::Device;
+use crate::error::{AimError, Result};
+use chrono::{DateTime, Local};
+
+/// Template used when neither the command line nor the config sets one
+pub const DEFAULT_TEMPLATE: &str = "aim-{kind}-{id}-{ts}";
+
+/// Variables a template can use, for help and error messages
+pub const VARIABLES: &[&str] = &["alias", "id", "serial", "model", "kind", "ts", "date", "time", "counter"];
+
+/// Values for one artifact name
+#[derive(Debug, Clone)]
+pub struct NameVars {
+    pub kind: String,
+    pub alias: Option<String>,
+    pub serial: String,
+    pub short_id: String,
+    pub model: Option<String>,
+    pub time: DateTime<Local>,
+    /// Position in a series (interactive mode, bursts), starting at 1
+    pub counter: Option<u32>,
+}
+
+impl NameVars {
+    pub fn new(kind: &str, device: &Device) -> Self {
+        Self {
+            kind: kind.to_string(),
+            alias: device.alias.clone(),
+            serial: device.id.to_string(),
+            short_id: device.id.short_id().to_string(),
+            model: device.model.clone(),
+            time: Local::now(),
+            counter: None,
+        }
+    }
+
+    pub fn with_counter(mut self, counter: u32) -> Self {
+        self.counter = Some(counter);
+        self
+    }
+
+    fn value(&self, name: &str) -> Option<String> {
+        let value = match name {
+            "alias" => self.alias.clone().unwrap_or_else(|| self.short_id.clone()),
+            "id" => self.short_id.clone(),
+            "serial" => self.serial.clone(),
+            "model" => self.model.clone().unwrap_or_else(|| "unknown".to_string()),
+            "kind" => self.kind.clone(),
+            "ts" => self.time.format("%Y%m%d-%H%M%S").to_string(),
+            "date" => self.time.format("%Y-%m-%d").to_string(),
+            "time" => self.time.format("%H%M%S").to_string(),
+            "counter" => format!("{:03}", self.counter.unwrap_or(1)),
+            _ => return None,
+        };
+        Some(sanitize(&value))
+    }
+}
+
+/// Keep a value usable in a file name: `192.168.1.20:5555` becomes `192.168.1.20-5555`
+fn sanitize(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        let c = if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '-' };
+        if !(c == '-' && out.ends_with('-')) {
+            out.push(c);
+        }
+    }
+    out.trim_matches('-').to_string()
+}
+
+/// Check a template before anything is captured
+pub fn validate(template: &str) -> Result<()> {
+    let probe = NameVars {
+        kind: String::new(),
+        alias: None,
+        serial: String::new(),
+        short_id: String::new(),
+        model: None,
+        time: Local::now(),
+        counter: None,
+    };
+    render(template, &probe).map(|_| ())
+}
+
+/// Fill in the `{variables}` of `template`
+pub fn render(template: &str, vars: &NameVars) -> Result<String> {
+    if template.contains('/') || template.contains('\\') {
+        return Err(AimError::InvalidArgument(format!(
+            "Name template '{}' must be a file name; use -o for the directory",
+            template
+        )));
+    }
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            return Err(AimError::InvalidArgument(format!("Unclosed '{{' in name template '{}'", template)));
+        };
+        let name = &rest[start + 1..start + len];
+        let value = vars.value(name).ok_or_else(|| {
+            AimError::InvalidArgument(format!(
+                "Unknown variable {{{}}} in name template; use {}",
+                name,
+                VARIABLES.iter().map(|v| format!("{{{}}}", v)).collect::<Vec<_>>().join(", ")
+            ))
+        })?;
+        out.push_str(&value);
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    if out.is_empty() {
+        return Err(AimError::InvalidArgument("Name template produces an empty file name".to_string()));
+    }
+    Ok(out)
+}
+
+/// File name for an artifact, with `extension` added unless the template has it
+///
+/// Numbered artifacts get `-{counter}` appended when the template leaves it
+/// out, so a series never overwrites itself.
+pub fn file_name(template: &str, vars: &NameVars, extension: &str) -> Result<String> {
+    let mut template = template.to_string();
+    if vars.counter.is_some() && !template.contains("{counter}") {
+        let suffix = format!(".{}", extension);
+        template = match template.strip_suffix(&suffix) {
+            Some(stem) => format!("{}-{{counter}}{}", stem, suffix),
+            None => format!("{}-{{counter}}", template),
+        };
+    }
+    let name = render(&template, vars)?;
+    if name.ends_with(&format!(".{}", extension)) {
+        Ok(name)
+    } else {
+        Ok(format!("{}.{}", name, extension))
+    }
+}
+
+/// The template to use: the command line wins over the config
+pub fn template(flag: Option<&str>, config: Option<&str>) -> String {
+    flag.or(config).unwrap_or(DEFAULT_TEMPLATE).to_string()
+}
@@ -0,0 +1,57 @@
+use super::artifact::{file_name, render, template, validate, NameVars, DEFAULT_TEMPLATE};
+use super::types::Device;
+use chrono::{Local, TimeZone};
+
+fn vars() -> NameVars {
+    let device = Device::new("192.168.1.20:5555").with_model("Pixel 7").with_alias("work");
+    let mut vars = NameVars::new("screenshot", &device);
+    vars.time = Local.with_ymd_and_hms(2024, 3, 9, 14, 5, 7).unwrap();
+    vars
+}
+
+#[test]
+fn test_render() {
+    assert_eq!(render("{alias}-{ts}", &vars()).unwrap(), "work-20240309-140507");
+    assert_eq!(render("{model}_{date}_{time}", &vars()).unwrap(), "Pixel-7_2024-03-09_140507");
+    // Serials of network devices lose the ':'
+    assert_eq!(render("{serial}", &vars()).unwrap(), "192.168.1.20-5555");
+    assert_eq!(render("{kind}-{counter}", &vars().with_counter(12)).unwrap(), "screenshot-012");
+    assert_eq!(render("plain", &vars()).unwrap(), "plain");
+}
+
+#[test]
+fn test_render_errors() {
+    assert!(render("{nope}", &vars()).is_err());
+    assert!(render("{alias", &vars()).is_err());
+    assert!(render("shots/{ts}", &vars()).is_err());
+    assert!(render("", &vars()).is_err());
+    assert!(validate("{alias}-{ts}-{counter}").is_ok());
+    assert!(validate("{when}").is_err());
+}
+
+#[test]
+fn test_file_name() {
+    // The default keeps the names aim always used
+    let mut device_vars = vars();
+    device_vars.alias = None;
+    device_vars.short_id = "R58M123A".to_string();
+    assert_eq!(
+        file_name(DEFAULT_TEMPLATE, &device_vars, "png").unwrap(),
+        "aim-screenshot-R58M123A-20240309-140507.png"
+    );
+    assert_eq!(
+        file_name(DEFAULT_TEMPLATE, &device_vars.clone().with_counter(3), "png").unwrap(),
+        "aim-screenshot-R58M123A-20240309-140507-003.png"
+    );
+
+    assert_eq!(file_name("{alias}.mp4", &vars(), "mp4").unwrap(), "work.mp4");
+    assert_eq!(file_name("{alias}.png", &vars().with_counter(2), "png").unwrap(), "work-002.png");
+    assert_eq!(file_name("{counter}-{alias}", &vars().with_counter(2), "png").unwrap(), "002-work.png");
+}
+
+#[test]
+fn test_template_precedence() {
+    assert_eq!(template(Some("{alias}"), Some("{serial}")), "{alias}");
+    assert_eq!(template(None, Some("{serial}")), "{serial}");
+    assert_eq!(template(None, None), DEFAULT_TEMPLATE);
+}
@@ -0,0 +1,98 @@
+//! Session recordings in asciinema's v2 format (`.cast`)
+//!
+//! The file starts with a JSON header line, followed by one JSON array per
+//! event: `[seconds since start, "o", output]` for terminal output and
+//! `[seconds, "r", "COLSxROWS"]` for resizes. Keystrokes are not recorded, so
+//! passwords typed without echo stay out of the file.
+
+use crate::error::Result;
+use serde::Serialize;
+use std::io::Write;
+use std::path::Path;
+use std::time::Instant;
+
+#[derive(Debug, Serialize)]
+pub struct Header {
+    pub version: u8,
+    pub width: u16,
+    pub height: u16,
+    pub timestamp: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub env: CastEnv,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CastEnv {
+    #[serde(rename = "TERM")]
+    pub term: String,
+    #[serde(rename = "SHELL")]
+    pub shell: String,
+}
+
+/// Writes a recording as the session goes, so an aborted session still leaves a usable file
+pub struct Recorder<W: Write> {
+    out: W,
+    started: Instant,
+    /// Start of a UTF-8 character split across two output chunks
+    pending: Vec<u8>,
+}
+
+impl Recorder<std::io::BufWriter<std::fs::File>> {
+    pub fn create(path: &Path, header: &Header) -> Result<Self> {
+        let file = std::fs::File::create(path).map_err(|e| {
+            crate::error::AimError::InvalidArgument(format!("Cannot create {}: {}", path.display(), e))
+        })?;
+        Self::new(std::io::BufWriter::new(file), header)
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    pub fn new(mut out: W, header: &Header) -> Result<Self> {
+        writeln!(out, "{}", serde_json::to_string(header)?)?;
+        Ok(Self { out, started: Instant::now(), pending: Vec::new() })
+    }
+
+    fn event(&mut self, kind: &str, data: &str) -> Result<()> {
+        let at = self.started.elapsed().as_secs_f64();
+        writeln!(self.out, "{}", serde_json::to_string(&(round_time(at), kind, data))?)?;
+        self.out.flush()?;
+        Ok(())
+    }
+
+    /// Record terminal output
+    pub fn output(&mut self, data: &[u8]) -> Result<()> {
+        self.pending.extend_from_slice(data);
+        let (text, rest) = split_utf8(&self.pending);
+        let rest = rest.to_vec();
+        if !text.is_empty() {
+            self.event("o", &text)?;
+        }
+        self.pending = rest;
+        Ok(())
+    }
+
+    /// Record a terminal resize
+    pub fn resize(&mut self, cols: u16, rows: u16) -> Result<()> {
+        self.event("r", &format!("{}x{}", cols, rows))
+    }
+}
+
+/// Times with microsecond precision, as asciinema writes them
+fn round_time(secs: f64) -> f64 {
+    (secs * 1_000_000.0).round() / 1_000_000.0
+}
+
+/// Decode `data`, holding back an incomplete character at the end
+///
+/// Invalid bytes elsewhere are replaced, as a terminal would show them.
+pub fn split_utf8(data: &[u8]) -> (String, &[u8]) {
+    match std::str::from_utf8(data) {
+        Ok(text) => (text.to_string(), &[]),
+        Err(e) if e.error_len().is_none() => {
+            let (complete, rest) = data.split_at(e.valid_up_to());
+            (String::from_utf8_lossy(complete).into_owned(), rest)
+        }
+        Err(_) => (String::from_utf8_lossy(data).into_owned(), &[]),
+    }
+}
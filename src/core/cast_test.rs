@@ -0,0 +1,61 @@
+use super::cast::{split_utf8, CastEnv, Header, Recorder};
+
+fn header() -> Header {
+    Header {
+        version: 2,
+        width: 80,
+        height: 24,
+        timestamp: 1_700_000_000,
+        title: Some("pixel7".to_string()),
+        env: CastEnv { term: "xterm-256color".to_string(), shell: "/system/bin/sh".to_string() },
+    }
+}
+
+#[test]
+fn test_header() {
+    let mut out = Vec::new();
+    Recorder::new(&mut out, &header()).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert_eq!(
+        text,
+        "{\"version\":2,\"width\":80,\"height\":24,\"timestamp\":1700000000,\"title\":\"pixel7\",\
+         \"env\":{\"TERM\":\"xterm-256color\",\"SHELL\":\"/system/bin/sh\"}}\n"
+    );
+}
+
+#[test]
+fn test_events() {
+    let mut out = Vec::new();
+    let mut recorder = Recorder::new(&mut out, &header()).unwrap();
+    recorder.output(b"$ ls\r\n").unwrap();
+    recorder.resize(100, 30).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let events: Vec<serde_json::Value> = text.lines().skip(1).map(|l| serde_json::from_str(l).unwrap()).collect();
+
+    assert_eq!(events.len(), 2);
+    assert!(events[0][0].as_f64().unwrap() >= 0.0);
+    assert_eq!(events[0][1], "o");
+    assert_eq!(events[0][2], "$ ls\r\n");
+    assert_eq!(events[1][1], "r");
+    assert_eq!(events[1][2], "100x30");
+}
+
+#[test]
+fn test_output_split_character() {
+    // "é" is 0xC3 0xA9; the chunk boundary falls in the middle
+    let mut out = Vec::new();
+    let mut recorder = Recorder::new(&mut out, &header()).unwrap();
+    recorder.output(b"caf\xC3").unwrap();
+    recorder.output(b"\xA9!").unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let events: Vec<serde_json::Value> = text.lines().skip(1).map(|l| serde_json::from_str(l).unwrap()).collect();
+    assert_eq!(events[0][2], "caf");
+    assert_eq!(events[1][2], "é!");
+}
+
+#[test]
+fn test_split_utf8() {
+    assert_eq!(split_utf8(b"plain"), ("plain".to_string(), &b""[..]));
+    assert_eq!(split_utf8(b"ab\xE2\x82"), ("ab".to_string(), &b"\xE2\x82"[..]));
+    assert_eq!(split_utf8(b"a\xFFb").0, "a\u{FFFD}b");
+}
@@ -0,0 +1,44 @@
+//! CI mode
+//!
+//! One switch (`--ci` or `AIM_CI=1`) for running aim unattended: colors are
+//! disabled, prompts fail instead of blocking, progress bars become timestamped
+//! line updates and listings are sorted so output is stable between runs.
+
+use crate::error::{AimError, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether `AIM_CI` is set to a truthy value
+pub fn env_enabled() -> bool {
+    matches!(
+        std::env::var("AIM_CI").map(|v| v.to_ascii_lowercase()).as_deref(),
+        Ok("1") | Ok("true") | Ok("yes")
+    )
+}
+
+/// Turn on CI mode for the rest of the process
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+    colored::control::set_override(false);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Timestamp prefix used for status and progress lines in CI mode
+pub fn timestamp() -> String {
+    chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f").to_string()
+}
+
+/// Fail early for features that need a person at the keyboard
+pub fn require_interactive(what: &str) -> Result<()> {
+    if is_enabled() {
+        return Err(AimError::InvalidArgument(format!(
+            "{} needs an interactive terminal and is disabled in CI mode",
+            what
+        )));
+    }
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::adb::shell::{ShellBatch, ShellOutput};
 use crate::core::types::{Device, DeviceId, OutputFormat};
 use crate::error::Result;
 
@@ -10,16 +11,23 @@ pub struct CommandContext {
     #[allow(dead_code)]
     pub verbose: bool,
     pub quiet: bool,
+    /// ADB server host
+    pub host: String,
+    /// ADB server port
+    pub port: u16,
 }
 
 #[allow(dead_code)]
 impl CommandContext {
     pub fn new() -> Self {
+        let (host, port) = crate::commands::runner::get_adb_connection_params();
         Self {
             device: None,
             output_format: OutputFormat::Table,
             verbose: false,
             quiet: false,
+            host: host.to_string(),
+            port,
         }
     }
     
@@ -43,6 +51,11 @@ impl CommandContext {
         self
     }
     
+    /// ADB server host and port to connect to
+    pub fn adb_address(&self) -> (&str, u16) {
+        (&self.host, self.port)
+    }
+
     /// Get the device ID if a device is selected
     pub fn device_id(&self) -> Option<&DeviceId> {
         self.device.as_ref().map(|d| &d.id)
@@ -58,6 +71,24 @@ impl CommandContext {
         self.device.as_ref().ok_or(crate::error::AimError::DeviceIdRequired)
     }
     
+    /// Run several shell commands on the selected device in one round trip
+    ///
+    /// Results come back in the same order as `commands`, each with its own
+    /// output and exit status.
+    pub async fn shell_batch<I, S>(&self, commands: I) -> Result<Vec<ShellOutput>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let device = self.require_device()?;
+        let (host, port) = self.adb_address();
+        commands
+            .into_iter()
+            .fold(ShellBatch::new().with_device(device.id.clone()), |batch, cmd| batch.command(cmd))
+            .execute(host, port)
+            .await
+    }
+    
     /// Check if progress/status messages should be shown
     /// Returns false if quiet mode is enabled or output format is JSON
     pub fn should_show_progress(&self) -> bool {
@@ -114,6 +145,12 @@ impl CommandContextBuilder {
         self.ctx.quiet = quiet;
         self
     }
+
+    pub fn address(mut self, host: impl Into<String>, port: u16) -> Self {
+        self.ctx.host = host.into();
+        self.ctx.port = port;
+        self
+    }
     
     pub fn build(self) -> CommandContext {
         self.ctx
@@ -0,0 +1,178 @@
+//! `--all`: run one command on every connected device at once
+//!
+//! Each device gets its own aim process with `AIM_DEVICE` set, so commands
+//! need nothing special to support it. Their output is interleaved line by
+//! line, each line prefixed with the device it came from.
+
+use crate::cli::Commands;
+use crate::core::types::Device;
+use crate::device::DeviceManager;
+use crate::error::{AimError, Result};
+use colored::*;
+use serde::Serialize;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::task::JoinSet;
+
+/// Commands that take `--all`
+pub const SUPPORTED: &[&str] = &["run", "getprop", "screenshot", "push"];
+
+/// Result of one device's run, for `-o json`
+#[derive(Debug, Serialize)]
+pub struct DeviceRun {
+    pub device: String,
+    pub serial: String,
+    pub exit_code: i32,
+    /// The command's JSON output, or its text if it printed something else
+    pub output: serde_json::Value,
+}
+
+/// Check that `command` can run with `--all`
+pub fn check(command: &Commands) -> Result<()> {
+    let device_id = match command {
+        Commands::Run { device_id, .. }
+        | Commands::Getprop { device_id, .. }
+        | Commands::Screenshot { device_id, .. }
+        | Commands::Push { device_id, .. } => device_id,
+        _ => {
+            return Err(AimError::InvalidArgument(format!(
+                "--all works with {}",
+                SUPPORTED.join(", ")
+            )))
+        }
+    };
+    if device_id.is_some() {
+        return Err(AimError::InvalidArgument("--all runs on every device; leave out the device".to_string()));
+    }
+    Ok(())
+}
+
+/// Arguments for each device's aim: the original ones without `--all`
+pub fn child_args(argv: &[String]) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut passthrough = false;
+    for arg in argv.iter().skip(1) {
+        passthrough |= arg == "--";
+        if passthrough || arg != "--all" {
+            args.push(arg.clone());
+        }
+    }
+    args
+}
+
+/// How a device is named in prefixes
+pub fn label(device: &Device) -> String {
+    device.alias.clone().unwrap_or_else(|| device.id.to_string())
+}
+
+/// `label` padded to `width`, then the separator
+pub fn prefix(label: &str, width: usize) -> String {
+    format!("{:<width$} |", label, width = width)
+}
+
+/// Print each line of `reader` after `prefix`, or collect them when `collect`
+async fn forward<R: AsyncRead + Unpin>(reader: R, prefix: String, to_stderr: bool, collect: bool) -> String {
+    let mut lines = BufReader::new(reader).lines();
+    let mut collected = String::new();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if collect {
+            collected.push_str(&line);
+            collected.push('\n');
+        } else if to_stderr {
+            eprintln!("{} {}", prefix.cyan(), line);
+        } else {
+            println!("{} {}", prefix.cyan(), line);
+        }
+    }
+    collected
+}
+
+/// Run the command line `argv` once per available device, all at the same time
+///
+/// With `json`, each device's output is gathered into one JSON array instead
+/// of being printed as it comes.
+pub async fn run(argv: &[String], json: bool) -> Result<()> {
+    if argv.is_empty() {
+        return Err(AimError::InvalidArgument("--all is not available here".to_string()));
+    }
+    let (host, port) = crate::commands::runner::get_adb_connection_params();
+    let devices: Vec<Device> = DeviceManager::with_address(host, port.to_string())
+        .list_devices()
+        .await?
+        .into_iter()
+        .filter(Device::is_available)
+        .collect();
+    if devices.is_empty() {
+        return Err(AimError::NoDevicesFound);
+    }
+
+    let exe = std::env::current_exe()
+        .map_err(|e| AimError::CommandExecution(format!("Cannot find the aim executable: {}", e)))?;
+    let args = child_args(argv);
+    let width = devices.iter().map(|d| label(d).chars().count()).max().unwrap_or(0);
+
+    let mut tasks = JoinSet::new();
+    for device in &devices {
+        let serial = device.id.to_string();
+        let label = label(device);
+        let mut child = tokio::process::Command::new(&exe)
+            .args(&args)
+            .env("AIM_DEVICE", &serial)
+            .env("ANDROID_SERIAL", &serial)
+            .env("ADB_SERVER_HOST", host)
+            .env("ADB_SERVER_PORT", port.to_string())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| AimError::CommandExecution(format!("Cannot start aim for {}: {}", label, e)))?;
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        let prefix = prefix(&label, width);
+
+        tasks.spawn(async move {
+            let out = async {
+                match stdout {
+                    Some(stdout) => forward(stdout, prefix.clone(), false, json).await,
+                    None => String::new(),
+                }
+            };
+            let err = async {
+                match stderr {
+                    Some(stderr) => forward(stderr, prefix.clone(), true, false).await,
+                    None => String::new(),
+                }
+            };
+            let (output, _) = tokio::join!(out, err);
+            let exit_code = child.wait().await.ok().and_then(|s| s.code()).unwrap_or(-1);
+            DeviceRun {
+                device: label,
+                serial,
+                exit_code,
+                output: serde_json::from_str(&output).unwrap_or(serde_json::Value::String(output)),
+            }
+        });
+    }
+
+    let mut runs = Vec::new();
+    while let Some(run) = tasks.join_next().await {
+        runs.push(run.map_err(|e| AimError::CommandExecution(format!("Device task failed: {}", e)))?);
+    }
+    runs.sort_by(|a, b| a.device.cmp(&b.device));
+    if json {
+        crate::commands::format_json_output(&runs)?;
+    }
+
+    let failed: Vec<&str> = runs.iter().filter(|r| r.exit_code != 0).map(|r| r.device.as_str()).collect();
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(AimError::CommandExecution(format!(
+            "failed on {} of {} devices: {}",
+            failed.len(),
+            runs.len(),
+            failed.join(", ")
+        )))
+    }
+}
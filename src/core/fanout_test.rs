@@ -0,0 +1,35 @@
+use super::fanout::{check, child_args, prefix};
+use crate::cli::Cli;
+use clap::Parser;
+
+fn strings(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn test_child_args() {
+    assert_eq!(
+        child_args(&strings(&["aim", "--all", "run", "ls /sdcard"])),
+        strings(&["run", "ls /sdcard"])
+    );
+    // Arguments for the device command itself are left alone
+    assert_eq!(
+        child_args(&strings(&["aim", "screenshot", "--all", "--", "--all"])),
+        strings(&["screenshot", "--", "--all"])
+    );
+}
+
+#[test]
+fn test_check() {
+    let command = |args: &[&str]| Cli::parse_from(args).command();
+    assert!(check(&command(&["aim", "--all", "getprop", "ro.build.id"])).is_ok());
+    assert!(check(&command(&["aim", "--all", "screenshot"])).is_ok());
+    assert!(check(&command(&["aim", "--all", "dmesg"])).is_err());
+    assert!(check(&command(&["aim", "--all", "run", "ls", "pixel"])).is_err());
+}
+
+#[test]
+fn test_prefix() {
+    assert_eq!(prefix("work", 8), "work     |");
+    assert_eq!(prefix("emulator-5554", 8), "emulator-5554 |");
+}
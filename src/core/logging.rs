@@ -0,0 +1,107 @@
+//! Diagnostic logging setup
+//!
+//! aim's own `log` output goes to stderr as text by default. `--log-format json`
+//! switches to one JSON object per line with the record's module, level and any
+//! structured fields (device, duration_ms, ...), and `--log-file` redirects it to
+//! a file so it can be collected without mixing into command output.
+
+use chrono::{SecondsFormat, Utc};
+use clap::ValueEnum;
+use log::kv::{Error as KvError, Key, Value, VisitSource};
+use log::{LevelFilter, Record};
+use serde_json::{Map, Value as JsonValue};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human readable lines
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Collects a record's key-value pairs into a JSON object
+struct JsonFields<'a>(&'a mut Map<String, JsonValue>);
+
+impl<'kvs> VisitSource<'kvs> for JsonFields<'_> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), KvError> {
+        self.0.insert(key.to_string(), kv_to_json(&value));
+        Ok(())
+    }
+}
+
+fn kv_to_json(value: &Value) -> JsonValue {
+    if let Some(b) = value.to_bool() {
+        JsonValue::from(b)
+    } else if let Some(n) = value.to_u64() {
+        JsonValue::from(n)
+    } else if let Some(n) = value.to_i64() {
+        JsonValue::from(n)
+    } else if let Some(n) = value.to_f64() {
+        JsonValue::from(n)
+    } else if let Some(s) = value.to_borrowed_str() {
+        JsonValue::from(s)
+    } else {
+        JsonValue::from(value.to_string())
+    }
+}
+
+/// Build the JSON object written for one log record
+pub fn record_to_json(record: &Record, timestamp: &str) -> JsonValue {
+    let mut object = Map::new();
+    object.insert("ts".into(), timestamp.into());
+    object.insert("level".into(), record.level().as_str().into());
+    object.insert(
+        "module".into(),
+        record.module_path().unwrap_or(record.target()).into(),
+    );
+    object.insert("message".into(), record.args().to_string().into());
+
+    let mut fields = Map::new();
+    // A failing visitor only loses fields, never the record itself
+    let _ = record.key_values().visit(&mut JsonFields(&mut fields));
+    for (key, value) in fields {
+        // Structured fields sit next to the built-in ones without replacing them
+        object.entry(key).or_insert(value);
+    }
+
+    JsonValue::Object(object)
+}
+
+/// Install the global logger
+///
+/// `ci` adds millisecond timestamps and disables colors in text mode.
+pub fn init(level: LevelFilter, format: LogFormat, file: Option<&Path>, ci: bool) -> io::Result<()> {
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(level);
+
+    if let Some(path) = file {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        builder
+            .target(env_logger::Target::Pipe(Box::new(file)))
+            .write_style(env_logger::WriteStyle::Never);
+    }
+
+    match format {
+        LogFormat::Json => {
+            builder.format(|buf, record| {
+                let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+                writeln!(buf, "{}", record_to_json(record, &timestamp))
+            });
+        }
+        LogFormat::Text => {
+            if ci || file.is_some() {
+                builder.format_timestamp_millis();
+            }
+            if ci {
+                builder.write_style(env_logger::WriteStyle::Never);
+            }
+        }
+    }
+
+    builder.init();
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use super::logging::record_to_json;
+use log::{Level, Record};
+
+#[test]
+fn test_record_to_json_basic_fields() {
+    let record = Record::builder()
+        .args(format_args!("Selected device"))
+        .level(Level::Debug)
+        .target("aim::commands")
+        .module_path(Some("aim::commands"))
+        .build();
+
+    let json = record_to_json(&record, "2024-01-01T00:00:00.000Z");
+    assert_eq!(json["ts"], "2024-01-01T00:00:00.000Z");
+    assert_eq!(json["level"], "DEBUG");
+    assert_eq!(json["module"], "aim::commands");
+    assert_eq!(json["message"], "Selected device");
+}
+
+#[test]
+fn test_record_to_json_structured_fields() {
+    let kvs: &[(&str, &str)] = &[("device", "emulator-5554")];
+    let record = Record::builder()
+        .args(format_args!("shell: ls"))
+        .level(Level::Debug)
+        .key_values(&kvs)
+        .build();
+
+    let json = record_to_json(&record, "now");
+    assert_eq!(json["device"], "emulator-5554");
+    // Falls back to the target when there is no module path
+    assert_eq!(json["module"], "");
+}
+
+#[test]
+fn test_record_to_json_numeric_fields_stay_numbers() {
+    let kvs: &[(&str, u64)] = &[("duration_ms", 42)];
+    let record = Record::builder()
+        .args(format_args!("Command finished"))
+        .key_values(&kvs)
+        .build();
+
+    let json = record_to_json(&record, "now");
+    assert_eq!(json["duration_ms"], 42);
+}
+
+#[test]
+fn test_record_to_json_fields_do_not_override_builtins() {
+    let kvs: &[(&str, &str)] = &[("level", "bogus")];
+    let record = Record::builder()
+        .args(format_args!("msg"))
+        .level(Level::Warn)
+        .key_values(&kvs)
+        .build();
+
+    let json = record_to_json(&record, "now");
+    assert_eq!(json["level"], "WARN");
+}
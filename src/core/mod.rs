@@ -1,3 +1,36 @@
+pub mod artifact;
+pub mod cast;
+pub mod ci;
 pub mod context;
+pub mod fanout;
+pub mod logging;
+pub mod notify;
+pub mod pick;
+pub mod profile;
 pub mod types;
+pub mod upload;
 
+
+#[cfg(test)]
+mod artifact_test;
+
+#[cfg(test)]
+mod cast_test;
+
+#[cfg(test)]
+mod fanout_test;
+
+#[cfg(test)]
+mod logging_test;
+
+#[cfg(test)]
+mod notify_test;
+
+#[cfg(test)]
+mod pick_test;
+
+#[cfg(test)]
+mod profile_test;
+
+#[cfg(test)]
+mod upload_test;
@@ -0,0 +1,40 @@
+//! Desktop notifications when long commands finish
+//!
+//! `--notify` asks for one after any of the commands below; setting
+//! `notify_after_secs` in the config sends one without the flag whenever such
+//! a command runs at least that long.
+
+use crate::cli::Commands;
+use log::debug;
+use std::time::Duration;
+
+/// The name to show for commands that can run long enough to walk away from
+pub fn long_running(command: &Commands) -> Option<&'static str> {
+    match command {
+        Commands::Perfetto { .. } => Some("perfetto"),
+        Commands::Pull { .. } => Some("pull"),
+        Commands::Soak { .. } => Some("soak"),
+        _ => None,
+    }
+}
+
+/// Whether a command that ran for `elapsed` should notify
+pub fn should_notify(flag: bool, after: Option<Duration>, elapsed: Duration) -> bool {
+    flag || after.is_some_and(|after| elapsed >= after)
+}
+
+/// Title and body of the notification for a finished command
+pub fn message(name: &str, error: Option<&str>, elapsed: Duration) -> (String, String) {
+    let took = crate::utils::format_duration(elapsed);
+    match error {
+        None => (format!("aim {} finished", name), format!("Done in {}", took)),
+        Some(error) => (format!("aim {} failed", name), format!("{} (after {})", error, took)),
+    }
+}
+
+/// Show a notification; a desktop without a notification service is not an error
+pub fn send(title: &str, body: &str) {
+    if let Err(e) = notify_rust::Notification::new().appname("aim").summary(title).body(body).show() {
+        debug!("Could not show notification: {}", e);
+    }
+}
@@ -0,0 +1,22 @@
+use super::notify::{message, should_notify};
+use std::time::Duration;
+
+#[test]
+fn test_should_notify() {
+    let secs = Duration::from_secs;
+    assert!(should_notify(true, None, secs(1)));
+    assert!(!should_notify(false, None, secs(3600)));
+    assert!(should_notify(false, Some(secs(60)), secs(60)));
+    assert!(!should_notify(false, Some(secs(60)), secs(59)));
+}
+
+#[test]
+fn test_message() {
+    let (title, body) = message("perfetto", None, Duration::from_secs(125));
+    assert_eq!(title, "aim perfetto finished");
+    assert!(body.starts_with("Done in "));
+
+    let (title, body) = message("pull", Some("Device not found: abc"), Duration::from_secs(3));
+    assert_eq!(title, "aim pull failed");
+    assert!(body.starts_with("Device not found: abc (after "));
+}
@@ -0,0 +1,44 @@
+//! Interactive choice when a device or package argument is ambiguous
+//!
+//! With `--pick`, an argument matching several devices or packages shows a
+//! numbered list and asks which one was meant instead of failing.
+
+use crate::error::{AimError, Result};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn on picking for the rest of the process
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Index for a 1-based answer, if it names one of `count` entries
+pub fn parse_choice(input: &str, count: usize) -> Option<usize> {
+    let n: usize = input.trim().parse().ok()?;
+    (1..=count).contains(&n).then(|| n - 1)
+}
+
+/// Ask which of `labels` was meant; `what` names one entry ("device")
+pub fn choose(what: &str, labels: &[String]) -> Result<usize> {
+    crate::core::ci::require_interactive("--pick")?;
+    for (i, label) in labels.iter().enumerate() {
+        eprintln!("  {}) {}", i + 1, label);
+    }
+    loop {
+        eprint!("Pick a {} [1-{}]: ", what, labels.len());
+        std::io::stderr().flush()?;
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line)? == 0 {
+            return Err(AimError::InvalidArgument(format!("No {} picked", what)));
+        }
+        if let Some(index) = parse_choice(&line, labels.len()) {
+            return Ok(index);
+        }
+    }
+}
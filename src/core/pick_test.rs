@@ -0,0 +1,10 @@
+use super::pick::parse_choice;
+
+#[test]
+fn test_parse_choice() {
+    assert_eq!(parse_choice("1\n", 3), Some(0));
+    assert_eq!(parse_choice(" 3 ", 3), Some(2));
+    assert_eq!(parse_choice("0", 3), None);
+    assert_eq!(parse_choice("4", 3), None);
+    assert_eq!(parse_choice("pixel", 3), None);
+}
@@ -0,0 +1,148 @@
+//! Where aim spends its own time, for `--profile-self`
+//!
+//! The connection code, the main parsers and the output formatters add how
+//! long each call took to a total per [`Phase`]. After the command the totals
+//! are printed to stderr, with the rest of the run shown as `other` (file
+//! transfers, sleeps, prompts and aim's own work between calls). Recording
+//! does nothing until [`enable`] is called.
+//!
+//! Devices queried at the same time (`aim ls -l`) add up, so the phases can
+//! exceed the wall-clock total. With `--all` each device's aim prints its own.
+
+use lazy_static::lazy_static;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// A step of talking to a device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Opening the TCP connection to the ADB server
+    Connect,
+    /// Requests to the server and waiting for its OKAY, including device selection
+    Handshake,
+    /// Reading command output until the device is done
+    Execute,
+    /// Turning device output into aim's types
+    Parse,
+    /// Printing tables and JSON
+    Render,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 5] = [Phase::Connect, Phase::Handshake, Phase::Execute, Phase::Parse, Phase::Render];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Connect => "connect",
+            Phase::Handshake => "handshake",
+            Phase::Execute => "execute",
+            Phase::Parse => "parse",
+            Phase::Render => "render",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|phase| *phase == self).unwrap_or(0)
+    }
+}
+
+lazy_static! {
+    static ref TOTALS: Mutex<[(Duration, u32); 5]> = Mutex::new([(Duration::ZERO, 0); 5]);
+}
+
+/// Turn on recording for the rest of the process
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Add one call of `elapsed` to `phase`
+pub fn record(phase: Phase, elapsed: Duration) {
+    if !is_enabled() {
+        return;
+    }
+    let mut totals = TOTALS.lock().unwrap();
+    let (total, calls) = &mut totals[phase.index()];
+    *total += elapsed;
+    *calls += 1;
+}
+
+/// Times a phase until dropped, so early returns and `?` are counted too
+pub struct Span {
+    phase: Phase,
+    started: Instant,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        record(self.phase, self.started.elapsed());
+    }
+}
+
+pub fn span(phase: Phase) -> Span {
+    Span { phase, started: Instant::now() }
+}
+
+/// Time taken by one phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseTime {
+    pub phase: Phase,
+    pub elapsed: Duration,
+    pub calls: u32,
+}
+
+/// The recorded totals for a run that took `total`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub phases: Vec<PhaseTime>,
+    pub total: Duration,
+}
+
+impl Report {
+    /// What the recorded phases don't account for
+    pub fn other(&self) -> Duration {
+        self.total.saturating_sub(self.phases.iter().map(|p| p.elapsed).sum())
+    }
+
+    /// One line per phase: name, time, share of the total and number of calls
+    pub fn render(&self) -> String {
+        let share = |elapsed: Duration| match self.total.as_secs_f64() {
+            total if total > 0.0 => format!("{:>4.0}%", elapsed.as_secs_f64() / total * 100.0),
+            _ => "   -".to_string(),
+        };
+        let ms = |elapsed: Duration| format!("{:>9.1}ms", elapsed.as_secs_f64() * 1000.0);
+
+        let mut lines = vec![format!("aim profile: {:.1}ms total", self.total.as_secs_f64() * 1000.0)];
+        for phase in &self.phases {
+            let calls = match phase.calls {
+                1 => "1 call".to_string(),
+                n => format!("{} calls", n),
+            };
+            lines.push(format!("  {:<10}{} {}  {}", phase.phase.name(), ms(phase.elapsed), share(phase.elapsed), calls));
+        }
+        let other = self.other();
+        lines.push(format!("  {:<10}{} {}", "other", ms(other), share(other)));
+        lines.join("\n")
+    }
+}
+
+/// Totals recorded so far, for a run that took `total`
+pub fn report(total: Duration) -> Report {
+    let totals = *TOTALS.lock().unwrap();
+    Report {
+        phases: Phase::ALL
+            .iter()
+            .map(|phase| {
+                let (elapsed, calls) = totals[phase.index()];
+                PhaseTime { phase: *phase, elapsed, calls }
+            })
+            .collect(),
+        total,
+    }
+}
@@ -0,0 +1,47 @@
+use super::profile::{Phase, PhaseTime, Report};
+use std::time::Duration;
+
+fn report(times: &[(Phase, u64, u32)], total_ms: u64) -> Report {
+    Report {
+        phases: times
+            .iter()
+            .map(|&(phase, ms, calls)| PhaseTime { phase, elapsed: Duration::from_millis(ms), calls })
+            .collect(),
+        total: Duration::from_millis(total_ms),
+    }
+}
+
+#[test]
+fn test_other() {
+    let report = report(&[(Phase::Connect, 10, 2), (Phase::Execute, 600, 1)], 1000);
+    assert_eq!(report.other(), Duration::from_millis(390));
+}
+
+#[test]
+fn test_other_with_parallel_work() {
+    // Two devices read at once: the phases add up to more than the run
+    let report = report(&[(Phase::Execute, 800, 1), (Phase::Execute, 800, 1)], 1000);
+    assert_eq!(report.other(), Duration::ZERO);
+}
+
+#[test]
+fn test_render() {
+    let report = report(&[(Phase::Connect, 10, 2), (Phase::Handshake, 40, 1), (Phase::Render, 0, 0)], 200);
+    assert_eq!(
+        report.render(),
+        [
+            "aim profile: 200.0ms total",
+            "  connect        10.0ms    5%  2 calls",
+            "  handshake      40.0ms   20%  1 call",
+            "  render          0.0ms    0%  0 calls",
+            "  other         150.0ms   75%",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn test_phase_names() {
+    let names: Vec<&str> = Phase::ALL.iter().map(|phase| phase.name()).collect();
+    assert_eq!(names, ["connect", "handshake", "execute", "parse", "render"]);
+}
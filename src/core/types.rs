@@ -51,6 +51,10 @@ pub enum DeviceState {
     Device,
     Offline,
     Unauthorized,
+    /// Booted into recovery, where adbd runs without Android's services
+    Recovery,
+    /// Recovery waiting for `adb sideload`
+    Sideload,
     Unknown,
 }
 
@@ -61,6 +65,8 @@ impl DeviceState {
             "device" => DeviceState::Device,
             "offline" => DeviceState::Offline,
             "unauthorized" => DeviceState::Unauthorized,
+            "recovery" => DeviceState::Recovery,
+            "sideload" => DeviceState::Sideload,
             _ => DeviceState::Unknown,
         }
     }
@@ -70,6 +76,8 @@ impl DeviceState {
             DeviceState::Device => "device",
             DeviceState::Offline => "offline",
             DeviceState::Unauthorized => "unauthorized",
+            DeviceState::Recovery => "recovery",
+            DeviceState::Sideload => "sideload",
             DeviceState::Unknown => "unknown",
         }
     }
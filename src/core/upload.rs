@@ -0,0 +1,144 @@
+//! `--upload`: push a captured artifact to the team's storage
+//!
+//! The `[upload]` config section picks the backend. Uploads go through the
+//! tools people already have set up and authenticated (`aws`, `gcloud`,
+//! `curl`, or any command), so aim itself handles no credentials.
+
+use crate::config::{Config, UploadConfig, UploadKind};
+use crate::core::context::CommandContext;
+use crate::core::types::OutputFormat;
+use crate::error::{AimError, Result};
+use crate::utils::shell_quote;
+use colored::*;
+use serde::Serialize;
+use std::path::Path;
+
+/// A finished upload
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Uploaded {
+    pub file: String,
+    pub url: String,
+}
+
+/// The program to run for an upload and the URL to report, if known up front
+#[derive(Debug, Clone, PartialEq)]
+pub struct UploadPlan {
+    pub program: String,
+    pub args: Vec<String>,
+    /// `None` for commands whose output is the URL
+    pub url: Option<String>,
+}
+
+/// Object key of `name`: the configured prefix, then the file name
+pub fn object_key(config: &UploadConfig, name: &str) -> String {
+    match config.prefix.as_deref().map(|p| p.trim_matches('/')).filter(|p| !p.is_empty()) {
+        Some(prefix) => format!("{}/{}", prefix, name),
+        None => name.to_string(),
+    }
+}
+
+fn fill(template: &str, name: &str, key: &str) -> String {
+    template.replace("{name}", name).replace("{key}", key)
+}
+
+/// Work out how to upload `file`
+pub fn plan(config: &UploadConfig, file: &Path) -> Result<UploadPlan> {
+    let name = file
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .ok_or_else(|| AimError::InvalidArgument(format!("Cannot upload {}", file.display())))?;
+    let key = object_key(config, &name);
+    let path = file.to_string_lossy().into_owned();
+    let missing = |field: &str| AimError::InvalidArgument(format!("[upload] needs `{}` for this kind", field));
+    let public_url = config.url.as_deref().map(|u| fill(u, &name, &key));
+
+    Ok(match config.kind {
+        UploadKind::S3 => {
+            let target = format!("s3://{}/{}", config.bucket.as_deref().ok_or_else(|| missing("bucket"))?, key);
+            UploadPlan {
+                program: "aws".to_string(),
+                args: vec!["s3".to_string(), "cp".to_string(), "--only-show-errors".to_string(), path, target.clone()],
+                url: Some(public_url.unwrap_or(target)),
+            }
+        }
+        UploadKind::Gcs => {
+            let target = format!("gs://{}/{}", config.bucket.as_deref().ok_or_else(|| missing("bucket"))?, key);
+            UploadPlan {
+                program: "gcloud".to_string(),
+                args: vec!["storage".to_string(), "cp".to_string(), path, target.clone()],
+                url: Some(public_url.unwrap_or(target)),
+            }
+        }
+        UploadKind::Http => {
+            let url = config.url.as_deref().ok_or_else(|| missing("url"))?;
+            // A URL without placeholders is a collection the key goes under
+            let target = if url.contains("{name}") || url.contains("{key}") {
+                fill(url, &name, &key)
+            } else {
+                format!("{}/{}", url.trim_end_matches('/'), key)
+            };
+            UploadPlan {
+                program: "curl".to_string(),
+                args: vec!["-fsS".to_string(), "-T".to_string(), path, target.clone()],
+                url: Some(target),
+            }
+        }
+        UploadKind::Command => {
+            let command = config.command.as_deref().ok_or_else(|| missing("command"))?;
+            let command = command
+                .replace("{file}", &shell_quote(&path))
+                .replace("{name}", &shell_quote(&name))
+                .replace("{key}", &shell_quote(&key));
+            UploadPlan {
+                program: "sh".to_string(),
+                args: vec!["-c".to_string(), command],
+                url: public_url,
+            }
+        }
+    })
+}
+
+/// Upload `file` as configured, returning where it went
+pub async fn upload(config: &UploadConfig, file: &Path) -> Result<Uploaded> {
+    let plan = plan(config, file)?;
+    let output = tokio::process::Command::new(&plan.program)
+        .args(&plan.args)
+        .output()
+        .await
+        .map_err(|e| AimError::CommandExecution(format!("Cannot run {} to upload: {}", plan.program, e)))?;
+    if !output.status.success() {
+        return Err(AimError::CommandExecution(format!(
+            "Uploading {} failed: {}",
+            file.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    // A custom command reports the URL as the last line it prints
+    let url = plan.url.unwrap_or_else(|| {
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .unwrap_or_default()
+            .to_string()
+    });
+    Ok(Uploaded { file: file.display().to_string(), url })
+}
+
+/// The `[upload]` section, or an error explaining how to add one
+pub fn load_config() -> Result<UploadConfig> {
+    Config::load().upload.ok_or_else(|| {
+        AimError::InvalidArgument("--upload needs an [upload] section in the config (see docs/COMMANDS.md)".to_string())
+    })
+}
+
+/// Upload a captured artifact and report the URL the way the command's output asks for
+pub async fn upload_artifact(ctx: &CommandContext, config: &UploadConfig, file: &Path) -> Result<Uploaded> {
+    let uploaded = upload(config, file).await?;
+    match ctx.output_format {
+        OutputFormat::Json => crate::commands::format_json_output(&uploaded)?,
+        _ => println!("{} Uploaded to {}", "✓".green(), uploaded.url.bright_cyan()),
+    }
+    Ok(uploaded)
+}
@@ -0,0 +1,83 @@
+use super::upload::{object_key, plan, UploadPlan};
+use crate::config::{UploadConfig, UploadKind};
+use std::path::Path;
+
+fn config(kind: UploadKind) -> UploadConfig {
+    UploadConfig { kind, bucket: None, prefix: None, url: None, command: None }
+}
+
+fn strings(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn test_object_key() {
+    let mut cfg = config(UploadKind::S3);
+    assert_eq!(object_key(&cfg, "shot.png"), "shot.png");
+    cfg.prefix = Some("/team/captures/".to_string());
+    assert_eq!(object_key(&cfg, "shot.png"), "team/captures/shot.png");
+    cfg.prefix = Some("/".to_string());
+    assert_eq!(object_key(&cfg, "shot.png"), "shot.png");
+}
+
+#[test]
+fn test_plan_s3() {
+    let mut cfg = config(UploadKind::S3);
+    cfg.bucket = Some("team-artifacts".to_string());
+    cfg.prefix = Some("aim".to_string());
+    let file = Path::new("/tmp/shot.png");
+    assert_eq!(
+        plan(&cfg, file).unwrap(),
+        UploadPlan {
+            program: "aws".to_string(),
+            args: strings(&["s3", "cp", "--only-show-errors", "/tmp/shot.png", "s3://team-artifacts/aim/shot.png"]),
+            url: Some("s3://team-artifacts/aim/shot.png".to_string()),
+        }
+    );
+
+    // A public URL replaces the s3:// one in the output
+    cfg.url = Some("https://artifacts.example.com/{key}".to_string());
+    assert_eq!(plan(&cfg, file).unwrap().url.as_deref(), Some("https://artifacts.example.com/aim/shot.png"));
+}
+
+#[test]
+fn test_plan_gcs() {
+    let mut cfg = config(UploadKind::Gcs);
+    cfg.bucket = Some("team-artifacts".to_string());
+    let plan = plan(&cfg, Path::new("trace.perfetto-trace")).unwrap();
+    assert_eq!(plan.program, "gcloud");
+    assert_eq!(plan.args, strings(&["storage", "cp", "trace.perfetto-trace", "gs://team-artifacts/trace.perfetto-trace"]));
+    assert_eq!(plan.url.as_deref(), Some("gs://team-artifacts/trace.perfetto-trace"));
+}
+
+#[test]
+fn test_plan_http() {
+    let mut cfg = config(UploadKind::Http);
+    cfg.url = Some("https://uploads.example.com/files/".to_string());
+    let file = Path::new("rec.mp4");
+    assert_eq!(plan(&cfg, file).unwrap().args, strings(&["-fsS", "-T", "rec.mp4", "https://uploads.example.com/files/rec.mp4"]));
+
+    cfg.url = Some("https://uploads.example.com/put?name={name}".to_string());
+    assert_eq!(plan(&cfg, file).unwrap().url.as_deref(), Some("https://uploads.example.com/put?name=rec.mp4"));
+}
+
+#[test]
+fn test_plan_command() {
+    let mut cfg = config(UploadKind::Command);
+    cfg.command = Some("share-artifact {file} --as {key}".to_string());
+    cfg.prefix = Some("qa".to_string());
+    let plan = plan(&cfg, Path::new("my shot.png")).unwrap();
+    assert_eq!(plan.program, "sh");
+    assert_eq!(plan.args, strings(&["-c", "share-artifact 'my shot.png' --as 'qa/my shot.png'"]));
+    // The URL comes from the command's output
+    assert_eq!(plan.url, None);
+}
+
+#[test]
+fn test_plan_missing_fields() {
+    assert!(plan(&config(UploadKind::S3), Path::new("a.png")).is_err());
+    assert!(plan(&config(UploadKind::Gcs), Path::new("a.png")).is_err());
+    assert!(plan(&config(UploadKind::Http), Path::new("a.png")).is_err());
+    assert!(plan(&config(UploadKind::Command), Path::new("a.png")).is_err());
+    assert!(plan(&config(UploadKind::S3), Path::new("/")).is_err());
+}
@@ -78,14 +78,15 @@ async fn process_device(host: &str, port: &str, item: Value, config: &Config, fe
         info!("Device {} is {}", device.adb_id, device.device_type);
     }
 
-    if fetch_props {
+    // Only a device in the `device` state answers; the others would fail every request
+    if fetch_props && device.device_type == "device" {
         // Slow path: fetch additional properties from device
         let propnames: Vec<String> = DEVICE_PROPERTIES
             .iter()
             .map(|&s| s.to_string())
             .collect();
 
-        let props = adb::getprops_parallel(host, port, &propnames, Some(&device.adb_id)).await;
+        let props = adb::getprops(host, port, &propnames, Some(&device.adb_id)).await;
         debug!("Props for device {}: {:?}", device.adb_id, props);
         let identifiers = create_device_identifiers(&props, &device.adb_id, config);
 
@@ -93,7 +94,7 @@ async fn process_device(host: &str, port: &str, item: Value, config: &Config, fe
         all_props.extend(identifiers);
         device.update_from_props(all_props);
     } else {
-        // Fast path: just create identifiers from adb_id, skip property fetching
+        // Fast path, or a device that can't be asked: identifiers from adb_id only
         let empty_props = HashMap::new();
         let identifiers = create_device_identifiers(&empty_props, &device.adb_id, config);
         device.update_from_props(identifiers);
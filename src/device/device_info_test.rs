@@ -399,16 +399,55 @@ mod tests {
             propnames,
             device_id,
             output,
+            cached,
+            ..
         } = parse_getprop(&["getprop"])
         {
             assert!(propnames.is_empty());
             assert!(device_id.is_none());
             assert!(matches!(output, OutputType::Plain));
+            assert!(!cached);
         } else {
             panic!("Expected Getprop command");
         }
     }
 
+    #[test]
+    fn test_getprop_cached_flag() {
+        if let Commands::Getprop { cached, device_id, .. } =
+            parse_getprop(&["getprop", "ro.product.model", "pixel", "--cached"])
+        {
+            assert!(cached);
+            assert_eq!(device_id.as_deref(), Some("pixel"));
+        } else {
+            panic!("Expected Getprop command");
+        }
+    }
+
+    #[test]
+    fn test_getprop_export_compare_flags() {
+        if let Commands::Getprop { export, compare, .. } =
+            parse_getprop(&["getprop", "--export", "props.json"])
+        {
+            assert_eq!(export.as_deref(), Some(std::path::Path::new("props.json")));
+            assert!(compare.is_none());
+        } else {
+            panic!("Expected Getprop command");
+        }
+
+        if let Commands::Getprop { compare, fail_on, .. } =
+            parse_getprop(&["getprop", "--compare", "props.json", "--fail-on", "critical"])
+        {
+            assert_eq!(compare.as_deref(), Some(std::path::Path::new("props.json")));
+            assert_eq!(fail_on, Some(crate::commands::getprop::Severity::Critical));
+        } else {
+            panic!("Expected Getprop command");
+        }
+
+        assert!(Cli::try_parse_from(["aim", "getprop", "--export", "a.json", "--compare", "b.json"]).is_err());
+        assert!(Cli::try_parse_from(["aim", "getprop", "--fail-on", "warning"]).is_err());
+    }
+
     #[test]
     fn test_getprop_single_device() {
         let devices = create_test_devices();
@@ -0,0 +1,104 @@
+use crate::commands::reconnect::MdnsService;
+use crate::error::{AimError, Result};
+use serde::Serialize;
+
+/// mDNS service types of devices that accept connections
+pub const CONNECT_SERVICE_TYPES: [&str; 2] = ["_adb-tls-connect._tcp", "_adb._tcp"];
+
+/// A device on the LAN advertising wireless debugging
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiscoveredDevice {
+    /// mDNS instance name, e.g. `adb-R58M123ABC-vWgJpq`
+    pub name: String,
+    /// Hardware serial, taken from the instance name
+    pub serial: Option<String>,
+    /// Alias from the config, if the serial has one
+    pub alias: Option<String>,
+    pub address: String,
+    pub service_type: String,
+    /// Whether the adb server already has it as a device
+    pub connected: bool,
+}
+
+impl DiscoveredDevice {
+    /// Short name to show and to connect by
+    pub fn label(&self) -> &str {
+        self.alias.as_deref().or(self.serial.as_deref()).unwrap_or(&self.name)
+    }
+}
+
+/// Hardware serial in an instance name (`adb-<serial>-<random>`)
+pub fn serial_from_service_name(name: &str) -> Option<String> {
+    let (serial, _) = name.strip_prefix("adb-")?.rsplit_once('-')?;
+    (!serial.is_empty()).then(|| serial.to_string())
+}
+
+/// Whether one of the connected serials is the advertised device
+///
+/// adb names devices it connected through mDNS after the service
+/// (`adb-R58M123ABC-vWgJpq._adb-tls-connect._tcp`), others after the address.
+fn is_connected(service: &MdnsService, serial: Option<&str>, connected: &[String]) -> bool {
+    connected.iter().any(|s| {
+        s == &service.address
+            || s.strip_prefix(service.name.as_str()).is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+            || serial == Some(s.as_str())
+    })
+}
+
+/// Connectable devices among mDNS services, one per instance name
+///
+/// A device advertising both the TLS and the legacy service is listed once,
+/// with the TLS one.
+pub fn discovered_devices(services: &[MdnsService], connected: &[String]) -> Vec<DiscoveredDevice> {
+    let mut devices: Vec<DiscoveredDevice> = Vec::new();
+    for service in services {
+        if !CONNECT_SERVICE_TYPES.contains(&service.service_type.as_str()) {
+            continue;
+        }
+        let serial = serial_from_service_name(&service.name);
+        let device = DiscoveredDevice {
+            name: service.name.clone(),
+            connected: is_connected(service, serial.as_deref(), connected),
+            serial,
+            alias: None,
+            address: service.address.clone(),
+            service_type: service.service_type.clone(),
+        };
+        match devices.iter_mut().find(|d| d.name == device.name) {
+            Some(existing) if device.service_type == CONNECT_SERVICE_TYPES[0] => *existing = device,
+            Some(_) => {}
+            None => devices.push(device),
+        }
+    }
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices
+}
+
+/// What a discovered device can be called, lowercased
+fn names(device: &DiscoveredDevice) -> Vec<String> {
+    [device.alias.as_deref(), device.serial.as_deref(), Some(device.name.as_str()), Some(device.address.as_str())]
+        .into_iter()
+        .flatten()
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// The discovered device `query` names: an exact alias, serial, instance
+/// name or address, or else a unique part of one
+pub fn match_discovered<'a>(devices: &'a [DiscoveredDevice], query: &str) -> Result<&'a DiscoveredDevice> {
+    let query = query.trim().to_lowercase();
+    let exact: Vec<&DiscoveredDevice> = devices.iter().filter(|d| names(d).contains(&query)).collect();
+    let matches = if exact.is_empty() {
+        devices.iter().filter(|d| names(d).iter().any(|n| n.contains(&query))).collect()
+    } else {
+        exact
+    };
+    match matches.as_slice() {
+        [device] => Ok(*device),
+        [] => Err(AimError::DeviceNotFound(format!("{} (not advertised over mDNS; see aim discover)", query))),
+        _ => Err(AimError::AmbiguousDeviceMatch {
+            prefix: query,
+            matches: matches.iter().map(|d| d.label().to_string()).collect(),
+        }),
+    }
+}
@@ -0,0 +1,48 @@
+use super::discovery::{discovered_devices, match_discovered, serial_from_service_name};
+use crate::commands::reconnect::parse_mdns_services;
+
+const MDNS: &str = "adb-R58M123ABC-vWgJpq\t_adb-tls-connect._tcp.\t192.168.1.20:41235\n\
+adb-R58M123ABC-vWgJpq\t_adb._tcp.\t192.168.1.20:5555\n\
+adb-ZY22XYZ-abcd\t_adb-tls-connect._tcp.\t192.168.1.31:40001\n\
+adb-ZY22XYZ-efgh\t_adb-tls-pairing._tcp.\t192.168.1.31:37099\n\
+emulator-lab\t_adb._tcp.\t192.168.1.50:5555\n";
+
+#[test]
+fn test_serial_from_service_name() {
+    assert_eq!(serial_from_service_name("adb-R58M123ABC-vWgJpq").as_deref(), Some("R58M123ABC"));
+    assert_eq!(serial_from_service_name("adb-emulator-5554-x1y2").as_deref(), Some("emulator-5554"));
+    assert_eq!(serial_from_service_name("emulator-lab"), None);
+    assert_eq!(serial_from_service_name("adb--x"), None);
+}
+
+#[test]
+fn test_discovered_devices() {
+    let services = parse_mdns_services(MDNS);
+    let connected = vec!["adb-ZY22XYZ-abcd._adb-tls-connect._tcp".to_string()];
+    let devices = discovered_devices(&services, &connected);
+
+    // Pairing services are left out, and the TLS service wins over the legacy one
+    assert_eq!(devices.len(), 3);
+    assert_eq!(devices[0].name, "adb-R58M123ABC-vWgJpq");
+    assert_eq!(devices[0].address, "192.168.1.20:41235");
+    assert!(!devices[0].connected);
+    assert_eq!(devices[1].serial.as_deref(), Some("ZY22XYZ"));
+    assert!(devices[1].connected);
+    assert_eq!(devices[2].label(), "emulator-lab");
+
+    let by_address = discovered_devices(&services, &["192.168.1.50:5555".to_string()]);
+    assert!(by_address[2].connected);
+}
+
+#[test]
+fn test_match_discovered() {
+    let mut devices = discovered_devices(&parse_mdns_services(MDNS), &[]);
+    devices[0].alias = Some("work".to_string());
+
+    assert_eq!(match_discovered(&devices, "work").unwrap().name, "adb-R58M123ABC-vWgJpq");
+    assert_eq!(match_discovered(&devices, "zy22xyz").unwrap().address, "192.168.1.31:40001");
+    assert_eq!(match_discovered(&devices, "192.168.1.50").unwrap().name, "emulator-lab");
+    assert!(match_discovered(&devices, "nothing").is_err());
+    // Part of every instance name
+    assert!(match_discovered(&devices, "-").is_err());
+}
@@ -0,0 +1,228 @@
+//! Extra details shown in the long device listing
+//!
+//! Build properties rarely change, so they come from the state store cache
+//! when available. Battery level and Wi-Fi address are always read live. Each
+//! device is queried with a single shell batch, several devices at a time. A
+//! device that does not answer in time is listed with its cached details only,
+//! so one stalled device doesn't hold up the listing. Devices that can't be
+//! queried at all (unauthorized, offline, in recovery) are not asked and get
+//! their cached build details.
+
+use crate::adb::shell::ShellBatch;
+use crate::core::types::{Device, DeviceId, DeviceState};
+use crate::state::StateStore;
+use chrono::{DateTime, Utc};
+use log::debug;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+pub const PROP_RELEASE: &str = "ro.build.version.release";
+pub const PROP_SDK: &str = "ro.build.version.sdk";
+pub const PROP_PATCH: &str = "ro.build.version.security_patch";
+
+const BUILD_PROPS: [&str; 3] = [PROP_RELEASE, PROP_SDK, PROP_PATCH];
+
+/// Cached build properties older than this are read again, in case of an update
+const BUILD_CACHE_HOURS: i64 = 24;
+
+/// Devices queried at the same time
+const MAX_PARALLEL: usize = 8;
+
+/// How long one device may take before it is listed without live details
+const FETCH_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Which groups of details to fetch
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExtraFields {
+    pub battery: bool,
+    pub build: bool,
+    pub wifi: bool,
+}
+
+/// Details for one device; `None` where unknown or not requested
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DeviceExtras {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub battery: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub android: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sdk: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security_patch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wifi_ip: Option<String>,
+}
+
+impl DeviceExtras {
+    /// Details known without asking the device: the cached build properties, if any
+    pub fn cached_only(cached: Option<&HashMap<String, String>>) -> Self {
+        let mut extras = DeviceExtras::default();
+        if let Some(props) = cached {
+            extras.apply_props(props);
+        }
+        extras
+    }
+
+    /// Fill in build details from a property map
+    pub fn apply_props(&mut self, props: &HashMap<String, String>) {
+        let get = |key: &str| props.get(key).map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        self.android = get(PROP_RELEASE);
+        self.sdk = get(PROP_SDK).and_then(|v| v.parse().ok());
+        self.security_patch = get(PROP_PATCH);
+    }
+}
+
+/// Battery level from `dumpsys battery` ("  level: 87")
+pub fn parse_battery_level(output: &str) -> Option<u32> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("level:"))
+        .and_then(|level| level.trim().parse().ok())
+}
+
+/// First IPv4 address from `ip -f inet addr show` ("inet 192.168.1.20/24 brd ...")
+pub fn parse_inet_addr(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let addr = line.trim().strip_prefix("inet ")?;
+        let addr = addr.split_whitespace().next()?;
+        Some(addr.split('/').next()?.to_string())
+    })
+}
+
+/// Build properties cached for a device, if all of them are known and recent
+pub fn cached_build_props(store: &StateStore, serial: &str, now: DateTime<Utc>) -> Option<HashMap<String, String>> {
+    let known = store.get(serial)?;
+    if known
+        .props_updated
+        .is_none_or(|updated| now - updated >= chrono::Duration::hours(BUILD_CACHE_HOURS))
+    {
+        return None;
+    }
+    BUILD_PROPS
+        .iter()
+        .map(|key| {
+            let value = known.props.get(*key).filter(|v| !v.is_empty())?;
+            Some((key.to_string(), value.clone()))
+        })
+        .collect()
+}
+
+/// Query one device; also returns build properties that were read live
+async fn fetch_one(
+    host: &str,
+    port: u16,
+    serial: String,
+    fields: ExtraFields,
+    cached: Option<HashMap<String, String>>,
+) -> (String, DeviceExtras, Option<HashMap<String, String>>) {
+    let mut batch = ShellBatch::new().with_device(DeviceId::new(serial.as_str()));
+
+    let query_build = fields.build && cached.is_none();
+    let mut extras = DeviceExtras::cached_only(cached.as_ref());
+    if query_build {
+        for prop in BUILD_PROPS {
+            batch = batch.command(format!("getprop {}", prop));
+        }
+    }
+    if fields.battery {
+        batch = batch.command("dumpsys battery");
+    }
+    if fields.wifi {
+        batch = batch.command("ip -f inet addr show wlan0");
+    }
+    let outputs = match batch.execute(host, port).await {
+        Ok(outputs) => outputs,
+        Err(e) => {
+            debug!("Fetching details for {} failed: {}", serial, e);
+            return (serial, extras, None);
+        }
+    };
+
+    let mut outputs = outputs.into_iter();
+    let mut fetched = None;
+    if query_build {
+        let props: HashMap<String, String> = BUILD_PROPS
+            .iter()
+            .zip(outputs.by_ref())
+            .map(|(key, out)| (key.to_string(), out.stdout))
+            .collect();
+        extras.apply_props(&props);
+        fetched = Some(props);
+    }
+    if fields.battery {
+        extras.battery = outputs.next().and_then(|out| parse_battery_level(&out.stdout));
+    }
+    if fields.wifi {
+        extras.wifi_ip = outputs.next().and_then(|out| parse_inet_addr(&out.stdout));
+    }
+    (serial, extras, fetched)
+}
+
+/// [`fetch_one`], settling for the cached details after [`FETCH_TIMEOUT`]
+async fn fetch_in_time(
+    host: &str,
+    port: u16,
+    serial: String,
+    fields: ExtraFields,
+    cached: Option<HashMap<String, String>>,
+) -> (String, DeviceExtras, Option<HashMap<String, String>>) {
+    match tokio::time::timeout(FETCH_TIMEOUT, fetch_one(host, port, serial.clone(), fields, cached.clone())).await {
+        Ok(fetched) => fetched,
+        Err(_) => {
+            debug!("Fetching details for {} timed out after {:?}", serial, FETCH_TIMEOUT);
+            let extras = DeviceExtras::cached_only(cached.as_ref());
+            (serial, extras, None)
+        }
+    }
+}
+
+/// Fetch the requested details for every online device, keyed by serial
+///
+/// Other devices only get their cached build details.
+pub async fn fetch_extras(host: &str, port: u16, devices: &[Device], fields: ExtraFields) -> HashMap<String, DeviceExtras> {
+    let mut store = StateStore::load();
+    let permits = Arc::new(Semaphore::new(MAX_PARALLEL));
+    let mut tasks = JoinSet::new();
+    for device in devices.iter().filter(|d| d.state == DeviceState::Device) {
+        let serial = device.id.to_string();
+        let cached = if fields.build { cached_build_props(&store, &serial, Utc::now()) } else { None };
+        let (host, permits) = (host.to_string(), permits.clone());
+        tasks.spawn(async move {
+            // The timeout starts once the device's turn comes
+            let _permit = permits.acquire_owned().await;
+            fetch_in_time(&host, port, serial, fields, cached).await
+        });
+    }
+
+    let mut results = HashMap::new();
+    if fields.build {
+        for device in devices.iter().filter(|d| d.state != DeviceState::Device) {
+            let serial = device.id.to_string();
+            let cached = cached_build_props(&store, &serial, Utc::now());
+            results.insert(serial, DeviceExtras::cached_only(cached.as_ref()));
+        }
+    }
+    let mut learned = false;
+    while let Some(joined) = tasks.join_next().await {
+        let Ok((serial, extras, fetched)) = joined else {
+            continue;
+        };
+        if let Some(props) = fetched.filter(|p| p.values().any(|v| !v.is_empty())) {
+            store.record_props(&serial, &props, Utc::now());
+            learned = true;
+        }
+        results.insert(serial, extras);
+    }
+
+    if learned {
+        if let Err(e) = store.save() {
+            debug!("Failed to save device state: {}", e);
+        }
+    }
+    results
+}
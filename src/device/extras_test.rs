@@ -0,0 +1,68 @@
+//! Tests for the long listing details
+
+#[cfg(test)]
+mod tests {
+    use crate::device::extras::{
+        cached_build_props, parse_battery_level, parse_inet_addr, DeviceExtras, PROP_PATCH, PROP_RELEASE, PROP_SDK,
+    };
+    use crate::state::StateStore;
+    use chrono::{Duration, Utc};
+    use std::collections::HashMap;
+
+    fn build_props() -> HashMap<String, String> {
+        HashMap::from([
+            (PROP_RELEASE.to_string(), "14".to_string()),
+            (PROP_SDK.to_string(), "34".to_string()),
+            (PROP_PATCH.to_string(), "2024-06-05".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_parse_battery_level() {
+        let output = "Current Battery Service state:\n  AC powered: false\n  USB powered: true\n  status: 2\n  level: 87\n  scale: 100\n";
+        assert_eq!(parse_battery_level(output), Some(87));
+        assert_eq!(parse_battery_level("Can't find service: battery"), None);
+    }
+
+    #[test]
+    fn test_parse_inet_addr() {
+        let output = "30: wlan0: <BROADCAST,MULTICAST,UP,LOWER_UP> mtu 1500 qdisc mq state UP group default qlen 3000\n    inet 192.168.1.20/24 brd 192.168.1.255 scope global wlan0\n       valid_lft forever preferred_lft forever\n";
+        assert_eq!(parse_inet_addr(output), Some("192.168.1.20".to_string()));
+        assert_eq!(parse_inet_addr("Device \"wlan0\" does not exist."), None);
+    }
+
+    #[test]
+    fn test_cached_only() {
+        let extras = DeviceExtras::cached_only(Some(&build_props()));
+        assert_eq!(extras.android.as_deref(), Some("14"));
+        assert_eq!(extras.battery, None);
+        assert_eq!(DeviceExtras::cached_only(None), DeviceExtras::default());
+    }
+
+    #[test]
+    fn test_apply_props() {
+        let mut extras = DeviceExtras::default();
+        extras.apply_props(&build_props());
+        assert_eq!(extras.android.as_deref(), Some("14"));
+        assert_eq!(extras.sdk, Some(34));
+        assert_eq!(extras.security_patch.as_deref(), Some("2024-06-05"));
+
+        extras.apply_props(&HashMap::from([(PROP_RELEASE.to_string(), " \n".to_string())]));
+        assert_eq!(extras.android, None);
+    }
+
+    #[test]
+    fn test_cached_build_props() {
+        let mut store = StateStore::default();
+        let now = Utc::now();
+        store.record_props("R58M123ABC", &build_props(), now - Duration::hours(2));
+        store.record_props("emulator-5554", &HashMap::from([(PROP_SDK.to_string(), "34".to_string())]), now);
+        store.record_props("old", &build_props(), now - Duration::days(3));
+
+        assert_eq!(cached_build_props(&store, "R58M123ABC", now), Some(build_props()));
+        // Incomplete or stale caches are read again
+        assert_eq!(cached_build_props(&store, "emulator-5554", now), None);
+        assert_eq!(cached_build_props(&store, "old", now), None);
+        assert_eq!(cached_build_props(&store, "unknown", now), None);
+    }
+}
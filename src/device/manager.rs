@@ -1,8 +1,115 @@
+use super::discovery::{discovered_devices, match_discovered, DiscoveredDevice};
+use crate::adb::server::AdbServer;
+use crate::commands::reconnect::parse_mdns_services;
 use crate::core::types::{Device, DeviceId, DeviceState};
 use crate::error::{AimError, Result};
 use crate::types::DeviceDetails;
 use log::debug;
 
+/// Environment variables that pick a device when none is given, in priority order
+pub const DEVICE_ENV_VARS: [&str; 2] = ["AIM_DEVICE", "ANDROID_SERIAL"];
+
+/// Device selected through `AIM_DEVICE` or `ANDROID_SERIAL`, if set
+pub fn device_from_env() -> Option<String> {
+    device_from_vars(|name| std::env::var(name).ok())
+}
+
+pub(crate) fn device_from_vars(lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    DEVICE_ENV_VARS.iter().find_map(|name| {
+        lookup(name)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .inspect(|v| debug!("Using device '{}' from {}", v, name))
+    })
+}
+
+/// Lowercase, with adb's underscores read as spaces ("Pixel_7" is "pixel 7")
+fn normalize(s: &str) -> String {
+    s.to_lowercase().replace('_', " ")
+}
+
+/// Devices matching what the user typed for a device
+///
+/// Tries, in order, and stops at the first rule that matches anything:
+/// the exact serial; an exact alias, model, product or device name; part of
+/// the serial; part of the alias, model or product. So `pixel` picks the one
+/// Pixel, while `"My Phone"` or `oriole` work as well as a serial.
+pub fn match_devices<'a>(devices: &'a [Device], query: &str) -> Vec<&'a Device> {
+    let query = normalize(query.trim());
+    let names = |d: &'a Device| {
+        [&d.alias, &d.model, &d.product, &d.device]
+            .into_iter()
+            .flatten()
+            .map(|name| normalize(name))
+            .filter(|name| !name.is_empty())
+    };
+    let rules: [&dyn Fn(&'a Device) -> bool; 4] = [
+        &|d| normalize(d.id.as_str()) == query,
+        &|d| names(d).any(|name| name == query),
+        &|d| normalize(d.id.as_str()).contains(&query),
+        &|d| names(d).any(|name| name.contains(&query)),
+    ];
+    rules
+        .iter()
+        .map(|rule| devices.iter().filter(|d| rule(d)).collect::<Vec<_>>())
+        .find(|matches| !matches.is_empty())
+        .unwrap_or_default()
+}
+
+/// Error listing the devices an argument could mean
+pub fn ambiguous_devices(query: &str, candidates: &[&Device]) -> AimError {
+    let or_dash = |s: &Option<String>| s.clone().filter(|s| !s.is_empty()).unwrap_or_else(|| "-".to_string());
+    AimError::Ambiguous {
+        query: query.to_string(),
+        what: "devices",
+        headers: vec!["SERIAL", "ALIAS", "MODEL", "STATE"],
+        rows: candidates
+            .iter()
+            .map(|d| vec![d.id.to_string(), or_dash(&d.alias), or_dash(&d.model), d.state.to_string()])
+            .collect(),
+    }
+}
+
+/// The one device matching `query`, or an error listing the candidates
+///
+/// With `--pick`, asks which candidate was meant instead.
+pub fn select_device(devices: &[Device], query: &str) -> Result<Device> {
+    let matches = match_devices(devices, query);
+    match matches.as_slice() {
+        [] => Err(AimError::DeviceNotFound(query.to_string())),
+        [device] => Ok((*device).clone()),
+        _ if crate::core::pick::is_enabled() => pick_device(&matches),
+        _ => Err(ambiguous_devices(query, &matches)),
+    }
+}
+
+fn pick_device(candidates: &[&Device]) -> Result<Device> {
+    let labels: Vec<String> = candidates
+        .iter()
+        .map(|d| match &d.alias {
+            Some(alias) => format!("{} [{}]", d.display_name(), alias),
+            None => d.display_name(),
+        })
+        .collect();
+    let index = crate::core::pick::choose("device", &labels)?;
+    Ok(candidates[index].clone())
+}
+
+/// Config file that holds device aliases
+fn config_path() -> std::path::PathBuf {
+    dirs::home_dir()
+        .map(|p| p.join(".config/aim/config.toml"))
+        .unwrap_or_else(|| std::path::PathBuf::from(".config/aim/config.toml"))
+}
+
+/// Aliases from the config for devices that don't have one yet
+fn apply_config_aliases(devices: &mut [Device]) {
+    let config = crate::config::Config::load_from_path(&config_path());
+    for device in devices.iter_mut().filter(|d| d.alias.is_none()) {
+        device.alias = config.get_device_name(device.id.as_str());
+    }
+}
+
 /// Unified device management
 ///
 /// Provides consistent device discovery and selection across all commands.
@@ -19,12 +126,10 @@ impl Default for DeviceManager {
 }
 
 impl DeviceManager {
-    /// Create a new DeviceManager with default localhost:5037
+    /// Create a new DeviceManager for the configured ADB server
     pub fn new() -> Self {
-        Self {
-            host: "localhost".to_string(),
-            port: "5037".to_string(),
-        }
+        let (host, port) = crate::commands::runner::get_adb_connection_params();
+        Self::with_address(host, port.to_string())
     }
 
     /// Create a DeviceManager with custom host and port
@@ -43,7 +148,9 @@ impl DeviceManager {
         let device_details = device_info::get_devices_fast(&self.host, &self.port).await;
         debug!("Found {} devices", device_details.len());
 
-        Ok(device_details.into_iter().map(Self::details_to_device).collect())
+        let devices: Vec<Device> = device_details.into_iter().map(Self::details_to_device).collect();
+        crate::state::remember_devices(&devices);
+        Ok(devices)
     }
 
     /// List all connected devices with full details
@@ -56,40 +163,24 @@ impl DeviceManager {
         Ok(device_details)
     }
 
-    /// Find a device by partial ID match
-    pub async fn find_device(&self, partial_id: &str) -> Result<Device> {
-        let devices = self.list_devices().await?;
-
-        // Smart matching - check if ID contains the search string
-        let matches: Vec<_> = devices
-            .iter()
-            .filter(|d| {
-                d.id.as_str().to_lowercase().contains(&partial_id.to_lowercase())
-            })
-            .collect();
-
-        match matches.len() {
-            0 => Err(AimError::DeviceNotFound(partial_id.to_string())),
-            1 => Ok(matches[0].clone()),
-            _ => Err(AimError::MultipleDevicesFound),
-        }
+    /// Find a device by serial, alias, model or product (see [`match_devices`])
+    pub async fn find_device(&self, query: &str) -> Result<Device> {
+        let mut devices = self.list_devices().await?;
+        apply_config_aliases(&mut devices);
+        select_device(&devices, query)
     }
 
-    /// Find device details by partial ID match
+    /// Find device details the same way as [`DeviceManager::find_device`]
     #[allow(dead_code)]
-    pub async fn find_device_details(&self, partial_id: &str) -> Result<DeviceDetails> {
-        let devices = self.list_device_details().await?;
-
-        let matches: Vec<_> = devices
-            .iter()
-            .filter(|d| d.matches_id_prefix(partial_id))
-            .collect();
-
-        match matches.len() {
-            0 => Err(AimError::DeviceNotFound(partial_id.to_string())),
-            1 => Ok(matches[0].clone()),
-            _ => Err(AimError::MultipleDevicesFound),
-        }
+    pub async fn find_device_details(&self, query: &str) -> Result<DeviceDetails> {
+        let details = self.list_device_details().await?;
+        let mut devices: Vec<Device> = details.iter().cloned().map(Self::details_to_device).collect();
+        apply_config_aliases(&mut devices);
+        let device = select_device(&devices, query)?;
+        details
+            .into_iter()
+            .find(|d| d.adb_id == device.id.as_str())
+            .ok_or_else(|| AimError::DeviceNotFound(query.to_string()))
     }
 
     /// Get a single device, or error if none or multiple
@@ -99,14 +190,19 @@ impl DeviceManager {
         match devices.len() {
             0 => Err(AimError::NoDevicesFound),
             1 => Ok(devices.into_iter().next().unwrap()),
+            _ if crate::core::pick::is_enabled() => {
+                let mut devices = devices;
+                apply_config_aliases(&mut devices);
+                pick_device(&devices.iter().collect::<Vec<_>>())
+            }
             _ => Err(AimError::DeviceIdRequired),
         }
     }
 
     /// Get target device - uses device_id if provided, otherwise requires single device
     pub async fn get_target_device(&self, device_id: Option<&str>) -> Result<Device> {
-        match device_id {
-            Some(id) => self.find_device(id).await,
+        match device_id.map(str::to_string).or_else(device_from_env) {
+            Some(id) => self.find_device(&id).await,
             None => self.get_single_device().await,
         }
     }
@@ -114,8 +210,8 @@ impl DeviceManager {
     /// Get target device details - uses device_id if provided, otherwise requires single device
     #[allow(dead_code)]
     pub async fn get_target_device_details(&self, device_id: Option<&str>) -> Result<DeviceDetails> {
-        match device_id {
-            Some(id) => self.find_device_details(id).await,
+        match device_id.map(str::to_string).or_else(device_from_env) {
+            Some(id) => self.find_device_details(&id).await,
             None => {
                 let devices = self.list_device_details().await?;
                 match devices.len() {
@@ -127,14 +223,42 @@ impl DeviceManager {
         }
     }
 
+    fn server_port(&self) -> Result<u16> {
+        self.port
+            .parse()
+            .map_err(|_| AimError::InvalidArgument(format!("Invalid adb port '{}'", self.port)))
+    }
+
+    /// Devices the adb server sees advertising wireless debugging over mDNS
+    pub async fn discover(&self) -> Result<Vec<DiscoveredDevice>> {
+        let services = parse_mdns_services(&AdbServer::mdns_services(&self.host, self.server_port()?).await?);
+        let connected: Vec<String> = self.list_devices().await?.iter().map(|d| d.id.to_string()).collect();
+
+        let config = crate::config::Config::load_from_path(&config_path());
+        let mut devices = discovered_devices(&services, &connected);
+        for device in &mut devices {
+            device.alias = device.serial.as_deref().and_then(|serial| config.get_device_name(serial));
+        }
+        Ok(devices)
+    }
+
+    /// Connect to a discovered device by alias, serial, mDNS name or address
+    pub async fn connect_discovered(&self, query: &str) -> Result<DiscoveredDevice> {
+        let devices = self.discover().await?;
+        let device = match_discovered(&devices, query)?.clone();
+        if device.connected {
+            return Ok(device);
+        }
+        let reply = AdbServer::connect(&self.host, self.server_port()?, &device.address).await?;
+        if !(reply.starts_with("connected") || reply.starts_with("already connected")) {
+            return Err(AimError::CommandExecution(format!("{}: {}", device.label(), reply.trim())));
+        }
+        Ok(DiscoveredDevice { connected: true, ..device })
+    }
+
     /// Convert DeviceDetails to Device
     pub(crate) fn details_to_device(d: DeviceDetails) -> Device {
-        let state = match d.device_type.as_str() {
-            "device" => DeviceState::Device,
-            "offline" => DeviceState::Offline,
-            "unauthorized" => DeviceState::Unauthorized,
-            _ => DeviceState::Unknown,
-        };
+        let state = DeviceState::from_str(&d.device_type);
 
         Device::new(DeviceId::new(d.adb_id))
             .with_state(state)
@@ -130,4 +130,87 @@ mod tests {
 
         assert!(matches!(device.state, DeviceState::Unknown));
     }
+
+    #[test]
+    fn test_device_from_vars_prefers_aim_device() {
+        use crate::device::manager::device_from_vars;
+
+        let device = device_from_vars(|name| match name {
+            "AIM_DEVICE" => Some("pixel".to_string()),
+            "ANDROID_SERIAL" => Some("emulator-5554".to_string()),
+            _ => None,
+        });
+        assert_eq!(device.as_deref(), Some("pixel"));
+    }
+
+    #[test]
+    fn test_device_from_vars_falls_back_to_android_serial() {
+        use crate::device::manager::device_from_vars;
+
+        let device = device_from_vars(|name| match name {
+            "AIM_DEVICE" => Some("  ".to_string()),
+            "ANDROID_SERIAL" => Some("emulator-5554".to_string()),
+            _ => None,
+        });
+        assert_eq!(device.as_deref(), Some("emulator-5554"));
+        assert_eq!(device_from_vars(|_| None), None);
+    }
+
+    fn devices() -> Vec<crate::core::types::Device> {
+        use crate::core::types::Device;
+        vec![
+            Device::new("R58M123ABC").with_model("Pixel_7").with_product("panther").with_alias("My Phone"),
+            Device::new("ZY22XYZ").with_model("Pixel_6").with_product("oriole"),
+            Device::new("emulator-5554").with_model("sdk_gphone64_x86_64").with_product("sdk_gphone64"),
+        ]
+    }
+
+    #[test]
+    fn test_match_devices_by_name() {
+        use crate::device::manager::match_devices;
+
+        let devices = devices();
+        let ids = |query: &str| -> Vec<String> {
+            match_devices(&devices, query).iter().map(|d| d.id.to_string()).collect()
+        };
+        assert_eq!(ids("zy22"), vec!["ZY22XYZ"]);
+        assert_eq!(ids("my phone"), vec!["R58M123ABC"]);
+        assert_eq!(ids("Pixel 6"), vec!["ZY22XYZ"]);
+        assert_eq!(ids("oriole"), vec!["ZY22XYZ"]);
+        assert_eq!(ids("pixel"), vec!["R58M123ABC", "ZY22XYZ"]);
+        assert!(ids("tablet").is_empty());
+    }
+
+    #[test]
+    fn test_match_devices_prefers_exact() {
+        use crate::core::types::Device;
+        use crate::device::manager::match_devices;
+
+        let devices = vec![Device::new("emulator-5554"), Device::new("emulator-55540")];
+        let matches = match_devices(&devices, "emulator-5554");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].id.as_str(), "emulator-5554");
+        assert_eq!(match_devices(&devices, "emulator").len(), 2);
+    }
+
+    #[test]
+    fn test_select_device_ambiguous_lists_candidates() {
+        use crate::device::manager::select_device;
+        use crate::error::AimError;
+
+        match select_device(&devices(), "pixel") {
+            Err(err @ AimError::Ambiguous { .. }) => {
+                assert_eq!(
+                    err.to_string(),
+                    "'pixel' matches 2 devices:\n\
+                     \x20 SERIAL      ALIAS     MODEL    STATE\n\
+                     \x20 R58M123ABC  My Phone  Pixel_7  unknown\n\
+                     \x20 ZY22XYZ     -         Pixel_6  unknown\n\
+                     Give more of the name, or add --pick to choose one"
+                );
+            }
+            other => panic!("expected ambiguity, got {:?}", other),
+        }
+        assert!(matches!(select_device(&devices(), "nope"), Err(AimError::DeviceNotFound(_))));
+    }
 }
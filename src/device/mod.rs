@@ -1,4 +1,6 @@
 pub mod device_info;
+pub mod discovery;
+pub mod extras;
 pub mod manager;
 
 pub use manager::DeviceManager;
@@ -6,5 +8,11 @@ pub use manager::DeviceManager;
 #[cfg(test)]
 mod device_info_test;
 
+#[cfg(test)]
+mod discovery_test;
+
 #[cfg(test)]
 mod manager_test;
+
+#[cfg(test)]
+mod extras_test;
@@ -18,6 +18,15 @@ pub enum AimError {
         matches: Vec<String>,
     },
     
+    #[error("{}", ambiguity_message(.query, .what, .headers, .rows))]
+    Ambiguous {
+        query: String,
+        /// What matched, plural ("devices", "packages")
+        what: &'static str,
+        headers: Vec<&'static str>,
+        rows: Vec<Vec<String>>,
+    },
+    
     #[error("Ambiguous device configuration for '{device_id}': {}", matching_configs.join(", "))]
     AmbiguousConfigMatch {
         device_id: String,
@@ -63,6 +72,22 @@ pub enum AimError {
     #[error("Shell error: {0}")]
     Shell(String),
     
+    #[error("SSH tunnel error: {0}")]
+    SshTunnel(String),
+    
+    #[error("Root not available on {0}: adbd is not root and su is missing or denied access")]
+    RootUnavailable(String),
+    
+    /// A device command failed; aim exits with the same status
+    #[error("Command exited with status {0}")]
+    ExitStatus(i32),
+    
+    #[error("Remote command timed out after {0} and was stopped")]
+    CommandTimedOut(String),
+    
+    #[error("Interrupted; the remote command was stopped")]
+    Interrupted,
+    
     #[error("Timeout error: operation timed out after {0} seconds")]
     Timeout(u64),
     
@@ -87,6 +112,50 @@ pub enum AimError {
 
 pub type Result<T> = std::result::Result<T, AimError>;
 
+/// Exit status when `--timeout` stops a remote command, as with coreutils `timeout`
+pub const EXIT_TIMED_OUT: i32 = 124;
+
+/// Exit status after Ctrl-C, as shells report SIGINT
+pub const EXIT_INTERRUPTED: i32 = 130;
+
+impl AimError {
+    /// aim's exit status for this error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AimError::ExitStatus(code) => *code,
+            AimError::CommandTimedOut(_) => EXIT_TIMED_OUT,
+            AimError::Interrupted => EXIT_INTERRUPTED,
+            _ => 1,
+        }
+    }
+}
+
+/// The candidates of an ambiguous argument as a small aligned table
+pub fn ambiguity_message(query: &str, what: &str, headers: &[&str], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, h)| rows.iter().filter_map(|r| r.get(i)).map(|c| c.chars().count()).fold(h.len(), usize::max))
+        .collect();
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        format!("  {}", padded.join("  ").trim_end())
+    };
+
+    let mut message = format!("'{}' matches {} {}:\n", query, rows.len(), what);
+    message.push_str(&line(headers.to_vec()));
+    for row in rows {
+        message.push('\n');
+        message.push_str(&line(row.iter().map(String::as_str).collect()));
+    }
+    message.push_str("\nGive more of the name, or add --pick to choose one");
+    message
+}
+
 // Compatibility layer for existing AdbError references
 pub type AdbError = AimError;
 
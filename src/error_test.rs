@@ -44,4 +44,12 @@ mod tests {
         let aim_err: AimError = string_err.into();
         assert!(format!("{}", aim_err).contains("something went wrong"));
     }
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(AimError::ExitStatus(3).exit_code(), 3);
+        assert_eq!(AimError::CommandTimedOut("30s".to_string()).exit_code(), 124);
+        assert_eq!(AimError::Interrupted.exit_code(), 130);
+        assert_eq!(AimError::DeviceIdRequired.exit_code(), 1);
+    }
 }
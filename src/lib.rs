@@ -1,4 +1,5 @@
 pub mod adb;
+pub mod android;
 pub mod cli;
 pub mod commands;
 pub mod config;
@@ -8,14 +9,24 @@ pub mod error;
 pub mod library;
 pub mod output;
 pub mod progress;
+pub mod state;
 pub mod types;
 pub mod utils;
 
 #[cfg(test)]
 pub mod testing;
 
+#[cfg(test)]
+mod cli_test;
+
 #[cfg(test)]
 mod config_test;
 
 #[cfg(test)]
 mod error_test;
+
+#[cfg(test)]
+mod state_test;
+
+#[cfg(test)]
+mod utils_test;
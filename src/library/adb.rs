@@ -9,20 +9,21 @@
 //! Re-exports protocol types from the protocol module.
 
 use super::protocol::format_command;
-use indicatif::ProgressBar;
+use crate::core::profile::{self, Phase};
+use crate::progress::{transfer_reporter, ProgressReporter};
 use log::*;
 use std::collections::HashMap;
 use std::error::Error;
 use std::fs;
 use std::fs::File;
-use std::io::{Read, Write};
+use std::io::{BufWriter, IoSlice, Read, Write};
 use std::net::{TcpStream, ToSocketAddrs};
 use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
 use std::process::Command;
 use std::str;
-use std::sync::Arc;
-use tokio::task::JoinHandle;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
 // Re-export protocol types for backwards compatibility
 pub use super::protocol::{AdbLstatResponse, ProgressDisplay};
@@ -34,13 +35,173 @@ pub use super::protocol::{AdbLstatResponse, ProgressDisplay};
 const SYNC_DATA: &[u8] = b"SEND";
 const SYNC_DONE: &[u8] = b"DONE";
 const BUFFER_SIZE: usize = 1024;
-const CHUNK_SIZE: usize = 64 * 1024;
+/// Largest payload the sync protocol accepts in one DATA packet
+pub const SYNC_DATA_MAX: usize = 64 * 1024;
+/// Smallest chunk size accepted for `--chunk-size`
+pub const MIN_CHUNK_SIZE: usize = 4 * 1024;
+/// Chunk size auto-tuning starts from
+const INITIAL_CHUNK_SIZE: usize = 16 * 1024;
+/// Bytes sent at one chunk size before its throughput is judged
+const TUNE_WINDOW: usize = 1024 * 1024;
+/// Write buffer for pulled files, so many DATA packets go out in one write
+const PULL_WRITE_BUFFER: usize = 1024 * 1024;
+/// Interval throughput is sampled over when looking for the peak rate
+const PEAK_WINDOW: Duration = Duration::from_millis(500);
+/// How many times a failed push or pull source is retried
+pub const TRANSFER_RETRIES: u32 = 1;
 const SERVER_START_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
 const DEFAULT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+const TRANSFER_TEMPLATE: &str =
+    "{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}) ({eta})";
 
+/// Fixed push chunk size set with `--chunk-size`, 0 means auto-tune
+static CHUNK_SIZE_OVERRIDE: AtomicUsize = AtomicUsize::new(0);
 
 type AdbResult<T> = Result<T, Box<dyn Error>>;
 
+/// Use a fixed chunk size for pushes instead of auto-tuning
+pub fn set_chunk_size(size: Option<usize>) {
+    let size = size.map_or(0, |s| s.clamp(MIN_CHUNK_SIZE, SYNC_DATA_MAX));
+    CHUNK_SIZE_OVERRIDE.store(size, Ordering::Relaxed);
+}
+
+/// What one push or pull moved
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TransferStats {
+    pub files: usize,
+    /// Entries that are not regular files (symlinks, devices, ...)
+    pub skipped: usize,
+    pub bytes: u64,
+    /// Highest throughput over any sampling window, in bytes per second
+    pub peak_rate: f64,
+}
+
+impl TransferStats {
+    pub fn merge(&mut self, other: TransferStats) {
+        self.files += other.files;
+        self.skipped += other.skipped;
+        self.bytes += other.bytes;
+        self.peak_rate = self.peak_rate.max(other.peak_rate);
+    }
+}
+
+/// Samples throughput in fixed windows to find the peak rate of a transfer
+#[derive(Debug, Clone)]
+pub struct RateMeter {
+    start: Instant,
+    window_start: Instant,
+    window_bytes: u64,
+    total_bytes: u64,
+    peak: f64,
+}
+
+impl RateMeter {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            start: now,
+            window_start: now,
+            window_bytes: 0,
+            total_bytes: 0,
+            peak: 0.0,
+        }
+    }
+
+    pub fn record(&mut self, bytes: u64, now: Instant) {
+        self.window_bytes += bytes;
+        self.total_bytes += bytes;
+        let elapsed = now.duration_since(self.window_start);
+        if elapsed >= PEAK_WINDOW {
+            self.peak = self.peak.max(self.window_bytes as f64 / elapsed.as_secs_f64());
+            self.window_start = now;
+            self.window_bytes = 0;
+        }
+    }
+
+    /// Peak rate in bytes per second, or the average for transfers shorter
+    /// than one sampling window
+    pub fn peak(&self, now: Instant) -> f64 {
+        if self.peak > 0.0 {
+            return self.peak;
+        }
+        let elapsed = now.duration_since(self.start).as_secs_f64();
+        if elapsed > 0.0 {
+            self.total_bytes as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Picks the DATA chunk size for a push
+///
+/// Starts small and doubles the chunk size while each step measurably improves
+/// throughput, then settles on the best size seen. A fixed size disables tuning.
+#[derive(Debug, Clone)]
+pub struct ChunkTuner {
+    size: usize,
+    settled: bool,
+    window_bytes: usize,
+    window_time: Duration,
+    best: Option<(usize, f64)>,
+}
+
+impl ChunkTuner {
+    pub fn new(fixed: Option<usize>) -> Self {
+        Self {
+            size: fixed.unwrap_or(INITIAL_CHUNK_SIZE).clamp(MIN_CHUNK_SIZE, SYNC_DATA_MAX),
+            settled: fixed.is_some(),
+            window_bytes: 0,
+            window_time: Duration::ZERO,
+            best: None,
+        }
+    }
+
+    /// Tuner for the chunk size configured with [`set_chunk_size`]
+    fn from_override() -> Self {
+        match CHUNK_SIZE_OVERRIDE.load(Ordering::Relaxed) {
+            0 => Self::new(None),
+            size => Self::new(Some(size)),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Account for one chunk sent in `elapsed`
+    pub fn record(&mut self, bytes: usize, elapsed: Duration) {
+        if self.settled {
+            return;
+        }
+        self.window_bytes += bytes;
+        self.window_time += elapsed;
+        if self.window_bytes < TUNE_WINDOW {
+            return;
+        }
+
+        let rate = self.window_bytes as f64 / self.window_time.as_secs_f64().max(f64::EPSILON);
+        self.window_bytes = 0;
+        self.window_time = Duration::ZERO;
+
+        // Require a few percent gain so noise does not keep growing the chunk
+        let improved = self.best.is_none_or(|(_, best_rate)| rate >= best_rate * 1.05);
+        if improved {
+            self.best = Some((self.size, rate));
+            if self.size >= SYNC_DATA_MAX {
+                self.settled = true;
+            } else {
+                self.size = (self.size * 2).min(SYNC_DATA_MAX);
+            }
+        } else {
+            if let Some((best_size, _)) = self.best {
+                self.size = best_size;
+            }
+            self.settled = true;
+        }
+        debug!("Push chunk size {} (settled: {})", self.size, self.settled);
+    }
+}
+
 struct AdbStream {
     stream: TcpStream,
 }
@@ -80,6 +241,7 @@ impl AdbStream {
     }
 
     fn establish_connection(host: &str, port: &str) -> Result<TcpStream, Box<dyn Error>> {
+        let _span = profile::span(Phase::Connect);
         let server_address = format!(
             "{}:{}",
             if host == "localhost" {
@@ -115,6 +277,7 @@ impl AdbStream {
     }
 
     fn send_command(&mut self, command: &str) -> AdbResult<()> {
+        let _span = profile::span(Phase::Handshake);
         debug!("Sending command: {}", command);
         let request = format!("{:04x}{}", command.len(), command);
         debug!("Formatted request: {:?}", request);
@@ -122,6 +285,7 @@ impl AdbStream {
     }
 
     fn read_response(&mut self) -> Result<String, Box<dyn Error>> {
+        let _span = profile::span(Phase::Execute);
         let mut buffer = [0; BUFFER_SIZE];
         let mut response = Vec::new();
         debug!("read_response: start - waiting for response...");
@@ -159,6 +323,24 @@ impl AdbStream {
         self.process_response(&response)
     }
 
+    /// Read until the device closes the stream, for outputs too large for one read
+    fn read_to_end(&mut self) -> AdbResult<String> {
+        let _span = profile::span(Phase::Execute);
+        let mut data = Vec::new();
+        match self.stream.read_to_end(&mut data) {
+            Ok(_) => {}
+            // Keep what arrived if the device stalls instead of closing
+            Err(ref e)
+                if !data.is_empty()
+                    && matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) =>
+            {
+                debug!("read_to_end: timed out after {} bytes", data.len());
+            }
+            Err(e) => return Err(e.into()),
+        }
+        Ok(String::from_utf8_lossy(&data).into_owned())
+    }
+
     fn process_response(&self, data: &[u8]) -> Result<String, Box<dyn Error>> {
         debug!("Raw bytes length: {}", data.len());
         match str::from_utf8(data) {
@@ -178,12 +360,21 @@ impl AdbStream {
     }
 
     fn read_okay(&mut self) -> AdbResult<()> {
+        let _span = profile::span(Phase::Handshake);
         let mut response = [0u8; 4];
         self.stream.read_exact(&mut response)?;
         debug!("Response in read_okay: {:?}", response);
 
         match response {
             [b'O', b'K', b'A', b'Y'] => Ok(()),
+            [b'F', b'A', b'I', b'L'] => {
+                // The server's reason, e.g. "device unauthorized", behind a hex length
+                let mut len = [0u8; 4];
+                self.stream.read_exact(&mut len)?;
+                let mut message = vec![0u8; usize::from_str_radix(str::from_utf8(&len)?, 16)?];
+                self.stream.read_exact(&mut message)?;
+                Err(String::from_utf8_lossy(&message).trim().to_string().into())
+            }
             [n, 0, 0, 0] if n != 0 => Ok(()),  // Accept any non-zero first byte
             _ => Err(format!("Expected OKAY response or status code. Got {:?}", response).into())
         }
@@ -200,6 +391,24 @@ impl AdbStream {
         Ok(())
     }
 
+    /// Send one DATA packet, header and payload in a single vectored write
+    fn write_data_chunk(&mut self, payload: &[u8]) -> AdbResult<()> {
+        let mut header = [0u8; 8];
+        header[..4].copy_from_slice(b"DATA");
+        header[4..].copy_from_slice(&(payload.len() as u32).to_le_bytes());
+
+        let mut slices = [IoSlice::new(&header), IoSlice::new(payload)];
+        let mut remaining = &mut slices[..];
+        while !remaining.is_empty() {
+            let written = self.stream.write_vectored(remaining)?;
+            if written == 0 {
+                return Err(std::io::Error::from(std::io::ErrorKind::WriteZero).into());
+            }
+            IoSlice::advance_slices(&mut remaining, written);
+        }
+        Ok(())
+    }
+
     #[allow(dead_code)]
     fn stat(&mut self, path: &PathBuf) -> Result<AdbLstatResponse, Box<dyn Error>> {
         let path_str = path.to_string_lossy();
@@ -222,7 +431,7 @@ impl AdbStream {
         dst_path: &str,
         perms: u32,
         progress: ProgressDisplay,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<TransferStats, Box<dyn Error>> {
         // Send SEND command with path and mode
         debug!("Sending SEND command...");
         self.write_all(SYNC_DATA)?;
@@ -232,43 +441,42 @@ impl AdbStream {
         // Open and get file info
         let mut file = File::open(src_path)?;
         let file_size = fs::metadata(src_path)?.len();
-        let mut buffer = [0u8; CHUNK_SIZE];
+        let mut buffer = vec![0u8; SYNC_DATA_MAX];
+        let mut tuner = ChunkTuner::from_override();
         let mut total_bytes = 0;
 
         // Setup progress bar if enabled
-        let pb = match progress {
-            ProgressDisplay::Show => Some(ProgressBar::new(file_size)),
+        let pb: Option<Box<dyn ProgressReporter>> = match progress {
+            ProgressDisplay::Show => Some(transfer_reporter(
+                &src_path.to_string_lossy(),
+                file_size,
+                TRANSFER_TEMPLATE,
+            )),
             ProgressDisplay::Hide => None,
         };
 
-        if let Some(pb) = &pb {
-            pb.set_style(indicatif::ProgressStyle::default_bar()
-                .template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}) ({eta})")
-                .unwrap()
-                .progress_chars("#>-"));
-        }
-
         let transfer_start = std::time::Instant::now();
+        let mut meter = RateMeter::new(transfer_start);
         let mut chunk_start;
 
         // Transfer file data
         loop {
             chunk_start = std::time::Instant::now();
-            let bytes_read = file.read(&mut buffer)?;
+            let bytes_read = file.read(&mut buffer[..tuner.size()])?;
             if bytes_read == 0 {
                 break;
             }
             total_bytes += bytes_read;
 
-            self.write_all(b"DATA")?;
-            self.write_all(&(bytes_read as u32).to_le_bytes())?;
-            self.write_all(&buffer[..bytes_read])?;
+            self.write_data_chunk(&buffer[..bytes_read])?;
 
             let chunk_duration = chunk_start.elapsed();
+            tuner.record(bytes_read, chunk_duration);
+            meter.record(bytes_read as u64, Instant::now());
             let chunk_speed = bytes_read as f64 / chunk_duration.as_secs_f64() / 1024.0 / 1024.0;
             if let Some(pb) = &pb {
-                pb.set_message(format!("{:.2} MB/s", chunk_speed));
-                pb.set_position(total_bytes as u64);
+                pb.set_message(&format!("{:.2} MB/s", chunk_speed));
+                pb.update(total_bytes as u64);
             }
         }
 
@@ -282,14 +490,19 @@ impl AdbStream {
         if let Some(pb) = pb {
             let total_duration = transfer_start.elapsed();
             let avg_speed = total_bytes as f64 / total_duration.as_secs_f64() / 1024.0 / 1024.0;
-            pb.finish_with_message(format!(
+            pb.finish_with_message(&format!(
                 "Transfer completed in {:.2}s at {:.2} MB/s average",
                 total_duration.as_secs_f64(),
                 avg_speed
             ));
         }
 
-        Ok(())
+        Ok(TransferStats {
+            files: 1,
+            bytes: total_bytes as u64,
+            peak_rate: meter.peak(Instant::now()),
+            ..Default::default()
+        })
     }
 
     fn transfer_data(
@@ -298,31 +511,48 @@ impl AdbStream {
         file_size: u64,
         description: &str,
         progress: ProgressDisplay,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<TransferStats, Box<dyn Error>> {
         // Create parent directory if needed
         if let Some(parent) = dst_path.parent() {
             fs::create_dir_all(parent)?;
         }
 
-        // Open destination file
+        // Open destination file and reserve its full size up front
         debug!("Creating file: {:?}", dst_path);
-        let mut file = File::create(dst_path)?;
+        let file = File::create(dst_path)?;
+        let result = preallocate(&file, file_size)
+            .map_err(Into::into)
+            .and_then(|_| self.write_data(file, file_size, description, progress));
+        if result.is_err() {
+            // Don't leave a preallocated file padded with zeros behind
+            let _ = fs::remove_file(dst_path);
+        }
+        result
+    }
+
+    fn write_data(
+        &mut self,
+        file: File,
+        file_size: u64,
+        description: &str,
+        progress: ProgressDisplay,
+    ) -> Result<TransferStats, Box<dyn Error>> {
+        let mut writer = BufWriter::with_capacity(PULL_WRITE_BUFFER, file);
+        let mut buffer = vec![0u8; SYNC_DATA_MAX];
         let mut total_bytes = 0;
 
         // Setup progress bar if enabled
-        let pb = match progress {
-            ProgressDisplay::Show => Some(ProgressBar::new(file_size)),
+        let pb: Option<Box<dyn ProgressReporter>> = match progress {
+            ProgressDisplay::Show => Some(transfer_reporter(
+                description,
+                file_size,
+                TRANSFER_TEMPLATE,
+            )),
             ProgressDisplay::Hide => None,
         };
 
-        if let Some(pb) = &pb {
-            pb.set_style(indicatif::ProgressStyle::default_bar()
-                .template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}) ({eta})")
-                .unwrap()
-                .progress_chars("#>-"));
-        }
-
         let transfer_start = std::time::Instant::now();
+        let mut meter = RateMeter::new(transfer_start);
         let mut chunk_start;
 
         debug!("Transferring {}...", description);
@@ -340,16 +570,19 @@ impl AdbStream {
                     let len = u32::from_le_bytes(len_bytes) as usize;
 
                     chunk_start = std::time::Instant::now();
-                    let mut buffer = vec![0u8; len];
-                    self.stream.read_exact(&mut buffer)?;
-                    file.write_all(&buffer)?;
+                    if len > buffer.len() {
+                        buffer.resize(len, 0);
+                    }
+                    self.stream.read_exact(&mut buffer[..len])?;
+                    writer.write_all(&buffer[..len])?;
                     total_bytes += len;
+                    meter.record(len as u64, Instant::now());
 
                     let chunk_duration = chunk_start.elapsed();
                     let chunk_speed = len as f64 / chunk_duration.as_secs_f64() / 1024.0 / 1024.0;
                     if let Some(pb) = &pb {
-                        pb.set_message(format!("{:.2} MB/s", chunk_speed));
-                        pb.set_position(total_bytes as u64);
+                        pb.set_message(&format!("{:.2} MB/s", chunk_speed));
+                        pb.update(total_bytes as u64);
                     }
                 }
                 b"DNT2" => {
@@ -384,18 +617,29 @@ impl AdbStream {
             }
         }
 
+        // Drop any preallocated tail if the file changed size since it was listed
+        let file = writer.into_inner().map_err(|e| e.into_error())?;
+        if total_bytes as u64 != file_size {
+            file.set_len(total_bytes as u64)?;
+        }
+
         // Show final statistics if progress bar was enabled
         if let Some(pb) = pb {
             let total_duration = transfer_start.elapsed();
             let avg_speed = total_bytes as f64 / total_duration.as_secs_f64() / 1024.0 / 1024.0;
-            pb.finish_with_message(format!(
+            pb.finish_with_message(&format!(
                 "Transfer completed in {:.2}s at {:.2} MB/s average",
                 total_duration.as_secs_f64(),
                 avg_speed
             ));
         }
 
-        Ok(())
+        Ok(TransferStats {
+            files: 1,
+            bytes: total_bytes as u64,
+            peak_rate: meter.peak(Instant::now()),
+            ..Default::default()
+        })
     }
 
     fn read_dnt2_entry(&mut self) -> Result<(String, AdbLstatResponse), Box<dyn Error>> {
@@ -419,6 +663,18 @@ impl AdbStream {
     }
 }
 
+/// The reason in an ADB `FAIL` reply ("FAIL0014device unauthorized."), if `response` is one
+///
+/// Returned as an error by [`send`], so callers never mistake it for output.
+pub fn fail_message(response: &str) -> Option<String> {
+    let rest = response.strip_prefix("FAIL")?;
+    let message = match rest.get(..4).and_then(|len| usize::from_str_radix(len, 16).ok()) {
+        Some(len) => rest[4..].get(..len).unwrap_or(&rest[4..]),
+        None => rest,
+    };
+    Some(message.trim().to_string())
+}
+
 pub fn send(
     host: &str,
     port: &str,
@@ -444,6 +700,9 @@ pub fn send(
         debug!("send_command completed successfully");
 
         let response = adb.read_response()?;
+        if let Some(message) = fail_message(&response) {
+            return Err(message.into());
+        }
         if !response.is_empty() {
             if response != "OKAY" {
                 responses.push(if no_clean_response {
@@ -454,6 +713,9 @@ pub fn send(
             } else {
                 // response == "OKAY", check if there's more response
                 let response = adb.read_response()?;
+                if let Some(message) = fail_message(&response) {
+                    return Err(message.into());
+                }
                 if !response.is_empty() {
                     debug!("!! Got more response: {:?}", response);
                     responses.push(if no_clean_response {
@@ -568,72 +830,123 @@ pub async fn run_command_async(
     }
 }
 
-pub async fn getprop_async(
+/// Parse `getprop` output into a map
+///
+/// Lines look like `[ro.product.model]: [Pixel 7]`; a value containing newlines
+/// continues on the following lines until the closing bracket.
+pub fn parse_getprop_output(output: &str) -> HashMap<String, String> {
+    let _span = profile::span(Phase::Parse);
+    let mut props = HashMap::new();
+    let mut pending: Option<(String, String)> = None;
+
+    for line in output.lines() {
+        let line = line.trim_end_matches('\r');
+        if let Some((key, mut value)) = pending.take() {
+            value.push('\n');
+            match line.strip_suffix(']') {
+                Some(rest) => {
+                    value.push_str(rest);
+                    props.insert(key, value);
+                }
+                None => {
+                    value.push_str(line);
+                    pending = Some((key, value));
+                }
+            }
+            continue;
+        }
+
+        let Some((key, value)) = line
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once("]: ["))
+        else {
+            continue;
+        };
+        match value.strip_suffix(']') {
+            Some(value) => {
+                props.insert(key.to_string(), value.to_string());
+            }
+            None => pending = Some((key.to_string(), value.to_string())),
+        }
+    }
+
+    props
+}
+
+/// Read every property of a device with a single `getprop` over one connection
+pub async fn getprop_dump(
     host: &str,
     port: &str,
-    propname: &str,
     adb_id: Option<&str>,
-) -> Result<String, Box<dyn Error>> {
+) -> Result<HashMap<String, String>, Box<dyn Error>> {
     let host_command = match adb_id {
         Some(id) => format_command("SELECT_DEVICE", &[id]),
         None => format_command("ANY_DEVICE", &[]),
     };
 
-    let getprop_command = format_command("GETPROP_SINGLE", &[propname]);
-    let messages = vec![host_command.as_str(), getprop_command.as_str()];
+    let mut adb = AdbStream::new(host, port)?;
+    adb.send_command(&host_command)?;
+    adb.read_okay()?;
+    adb.send_command(&format_command("GETPROP", &[]))?;
+    adb.read_okay()?;
 
-    match send(host, port, messages, true) {
-        Ok(responses) => {
-            let formatted = format_responses(&responses);
-            // Check if the response is an error from ADB
-            if formatted.starts_with("FAIL") {
-                // Return empty string for unauthorized devices
-                Ok(String::new())
-            } else {
-                Ok(formatted)
-            }
-        },
-        Err(e) => Err(e),
-    }
+    let output = adb.read_to_end()?;
+    Ok(parse_getprop_output(&output))
 }
 
-pub async fn getprops_parallel(
+/// Fetch the given properties (or all of them when `propnames` is empty)
+///
+/// The whole property dump is read once, which is much cheaper than a
+/// connection and transport handshake per property on slow links. Properties
+/// that cannot be read come back as empty strings.
+pub async fn getprops(
     host: &str,
     port: &str,
     propnames: &[String],
     adb_id: Option<&str>,
 ) -> HashMap<String, String> {
-    let mut tasks: Vec<JoinHandle<(String, String)>> = Vec::new();
-    let host = Arc::new(host.to_string()); // Arc for shared ownership in async tasks
-    let port = Arc::new(port.to_string());
-    let adb_id = adb_id.map(|id| Arc::new(id.to_string()));
-
-    for propname in propnames {
-        let host_clone = Arc::clone(&host);
-        let port_clone = Arc::clone(&port);
-        let propname = propname.to_string();
-        let adb_id_clone = adb_id.clone();
-
-        tasks.push(tokio::spawn(async move {
-            let result = getprop_async(
-                &host_clone,
-                &port_clone,
-                &propname,
-                adb_id_clone.as_ref().map(|arc| arc.as_str()),
-            )
-            .await
-            .unwrap_or_default();
-            (propname, result)
-        }));
-    }
-
-    let mut results = HashMap::new();
-    for task in tasks {
-        let (propname, result) = task.await.unwrap();
-        results.insert(propname, result);
-    }
-
-    results
+    let all = match getprop_dump(host, port, adb_id).await {
+        Ok(all) => all,
+        Err(e) => {
+            debug!("getprop dump failed for {:?}: {}", adb_id, e);
+            HashMap::new()
+        }
+    };
+
+    if propnames.is_empty() {
+        return all;
+    }
+
+    propnames
+        .iter()
+        .map(|name| (name.clone(), all.get(name).cloned().unwrap_or_default()))
+        .collect()
+}
+
+/// Reserve `size` bytes for a file about to be written
+///
+/// On Linux this allocates the blocks so a large pull is laid out contiguously
+/// and fails early when the disk is full. Elsewhere, or on filesystems without
+/// fallocate support, the file is just extended.
+pub fn preallocate(file: &File, size: u64) -> std::io::Result<()> {
+    if size == 0 {
+        return Ok(());
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::io::AsRawFd;
+
+        // SAFETY: the descriptor stays valid for as long as `file` is borrowed
+        let ret = unsafe { libc::posix_fallocate(file.as_raw_fd(), 0, size as libc::off_t) };
+        match ret {
+            0 => return Ok(()),
+            libc::ENOSPC => return Err(std::io::Error::from_raw_os_error(ret)),
+            _ => debug!("posix_fallocate unsupported ({}), extending file instead", ret),
+        }
+    }
+
+    file.set_len(size)
 }
 
 fn get_permissions(path: &PathBuf) -> std::io::Result<u32> {
@@ -650,7 +963,7 @@ pub async fn push(
     dst_path: &PathBuf,
     has_multiple_sources: bool,
     progress: ProgressDisplay,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<TransferStats, Box<dyn Error>> {
     debug!("Starting push operation:");
     debug!("Source path: {:?}", src_path);
     debug!("Destination path: {:?}", dst_path);
@@ -671,6 +984,8 @@ pub async fn push(
     adb.read_response()?;
     adb.read_okay()?;
 
+    let mut stats = TransferStats::default();
+
     // If source is a directory, collect all files first
     let files_to_transfer = if src_path.is_dir() {
         let mut files = Vec::new();
@@ -682,6 +997,9 @@ pub async fn push(
                     entry.path().to_path_buf(),
                     dst_path.join(entry.path().strip_prefix(src_base)?),
                 ));
+            } else if !entry.file_type().is_dir() {
+                debug!("Skipping {:?}: not a regular file", entry.path());
+                stats.skipped += 1;
             }
         }
         files
@@ -701,17 +1019,59 @@ pub async fn push(
     for (src_file, dst_file) in files_to_transfer {
         // Get permissions and transfer file
         let perms = get_permissions(&src_file)?;
-        adb.transfer_file(&src_file, &dst_file.to_string_lossy(), perms, progress)?;
+        stats.merge(adb.transfer_file(&src_file, &dst_file.to_string_lossy(), perms, progress)?);
     }
 
-    Ok(())
+    Ok(stats)
+}
+
+/// Locate the adb binary: `ADB_PATH`, then `PATH`, then the Android SDK
+///
+/// Only starting the server needs it; everything else talks to a running
+/// server over its socket. An `ADB_PATH` that is not a file is reported and
+/// skipped.
+pub fn find_adb_binary() -> Option<PathBuf> {
+    let name = if cfg!(windows) { "adb.exe" } else { "adb" };
+    if let Some(path) = std::env::var_os("ADB_PATH").map(PathBuf::from) {
+        if path.is_file() {
+            return Some(path);
+        }
+        warn!("ADB_PATH is set to {}, which is not a file; looking on PATH instead", path.display());
+    }
+    let path_dirs = std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let sdk_dirs = ["ANDROID_HOME", "ANDROID_SDK_ROOT"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(|sdk| PathBuf::from(sdk).join("platform-tools"));
+    path_dirs
+        .into_iter()
+        .chain(sdk_dirs)
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Error for when no server is running and none can be started
+pub fn missing_adb_message(port: &str) -> String {
+    format!(
+        "No ADB server is running on port {} and the adb binary was not found.\n\
+         aim only needs adb to start the server. Either:\n  \
+         - start a server elsewhere and point aim at it (ADB_SERVER_HOST, ADB_SERVER_PORT)\n  \
+         - install platform-tools: https://developer.android.com/tools/releases/platform-tools\n  \
+         - set ADB_PATH to an existing adb binary",
+        port
+    )
 }
 
 pub fn start_adb_server(port: &str) -> Result<(), Box<dyn Error>> {
     debug!("Checking if ADB server needs to be started...");
 
+    let adb = find_adb_binary().ok_or_else(|| missing_adb_message(port))?;
+    debug!("Using adb binary at {}", adb.display());
+
     // Create the command with proper detached settings
-    let mut command = Command::new("adb");
+    let mut command = Command::new(adb);
     command
         .args(["-L", &format!("tcp:{}", port), "server", "--reply-fd", "4"])
         .stdin(std::process::Stdio::null())
@@ -804,7 +1164,7 @@ pub async fn pull(
     src_path: &PathBuf,
     dst_path: &PathBuf,
     progress: ProgressDisplay,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<TransferStats, Box<dyn Error>> {
     debug!("\n=== Starting Pull Operation ===");
     debug!("Source: {:?}", src_path);
     debug!("Destination: {:?}", dst_path);
@@ -871,6 +1231,8 @@ pub async fn pull(
     debug!("Source type: {}", lstat_response.file_type());
     debug!("Source size: {} bytes", file_size);
 
+    let mut stats = TransferStats::default();
+
     // If source is a directory, list its contents with LIS2
     let files_to_transfer = if lstat_response.file_type() == "Directory" {
         debug!("\n=== Pulling Directory ===");
@@ -901,6 +1263,14 @@ pub async fn pull(
             match &response {
                 b"DNT2" => {
                     let (name, entry_stat) = adb.read_dnt2_entry()?;
+                    if name == "." || name == ".." {
+                        continue;
+                    }
+                    if entry_stat.file_type() != "Regular file" {
+                        debug!("Skipping {}: {}", name, entry_stat.file_type());
+                        stats.skipped += 1;
+                        continue;
+                    }
                     // Preserve relative path by joining with src_path first, then getting relative component
                     let full_src_path = src_path.join(&name);
                     let relative_path = full_src_path.strip_prefix(src_path)?;
@@ -949,16 +1319,16 @@ pub async fn pull(
         adb.write_all(&command)?;
 
         // Transfer the file using shared function
-        adb.transfer_data(
+        stats.merge(adb.transfer_data(
             &dst_file,
             file_size,
             "file",
             progress,
-        )?;
+        )?);
     }
 
     debug!("\n=== Pull Operation Completed Successfully ===");
-    Ok(())
+    Ok(stats)
 }
 
 // AdbLstatResponse and ProgressDisplay are now in protocol.rs
@@ -0,0 +1,132 @@
+use super::adb::{
+    fail_message, parse_getprop_output, preallocate, ChunkTuner, RateMeter, TransferStats, MIN_CHUNK_SIZE,
+    SYNC_DATA_MAX,
+};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_parse_getprop_output() {
+    let output = "[ro.build.version.sdk]: [34]\n\
+                  [ro.product.model]: [Pixel 7]\n\
+                  [ro.empty]: []\n";
+    let props = parse_getprop_output(output);
+
+    assert_eq!(props.len(), 3);
+    assert_eq!(props["ro.build.version.sdk"], "34");
+    assert_eq!(props["ro.product.model"], "Pixel 7");
+    assert_eq!(props["ro.empty"], "");
+}
+
+#[test]
+fn test_parse_getprop_output_multiline_value() {
+    let output = "[persist.sys.motd]: [first line\n\
+                  second line]\n\
+                  [ro.serialno]: [R58M123ABC]\n";
+    let props = parse_getprop_output(output);
+
+    assert_eq!(props["persist.sys.motd"], "first line\nsecond line");
+    assert_eq!(props["ro.serialno"], "R58M123ABC");
+}
+
+#[test]
+fn test_parse_getprop_output_ignores_noise() {
+    let output = "\r\n[ro.hardware]: [qcom]\r\nsome warning\n[broken line\n";
+    let props = parse_getprop_output(output);
+
+    assert_eq!(props.len(), 1);
+    assert_eq!(props["ro.hardware"], "qcom");
+}
+
+/// Feed the tuner one 1 MiB window at the given throughput in MiB/s
+#[test]
+fn test_fail_message() {
+    assert_eq!(fail_message("FAIL0014device unauthorized.").as_deref(), Some("device unauthorized."));
+    assert_eq!(fail_message("FAIL0007offlinetrailing").as_deref(), Some("offline"));
+    assert_eq!(fail_message("FAILdevice offline").as_deref(), Some("device offline"));
+    assert_eq!(fail_message("OKAY[ro.product.model]: [Pixel 7]"), None);
+}
+
+fn feed_window(tuner: &mut ChunkTuner, mib_per_sec: f64) {
+    let chunks = (1024 * 1024) / tuner.size();
+    let per_chunk = Duration::from_secs_f64(tuner.size() as f64 / (mib_per_sec * 1024.0 * 1024.0));
+    for _ in 0..chunks {
+        tuner.record(tuner.size(), per_chunk);
+    }
+}
+
+#[test]
+fn test_chunk_tuner_grows_while_throughput_improves() {
+    let mut tuner = ChunkTuner::new(None);
+    assert_eq!(tuner.size(), 16 * 1024);
+
+    feed_window(&mut tuner, 10.0);
+    assert_eq!(tuner.size(), 32 * 1024);
+    feed_window(&mut tuner, 20.0);
+    assert_eq!(tuner.size(), SYNC_DATA_MAX);
+    feed_window(&mut tuner, 30.0);
+    assert_eq!(tuner.size(), SYNC_DATA_MAX);
+}
+
+#[test]
+fn test_chunk_tuner_settles_on_best_size() {
+    let mut tuner = ChunkTuner::new(None);
+    feed_window(&mut tuner, 10.0);
+    feed_window(&mut tuner, 20.0);
+    // 64K turns out slower than 32K
+    feed_window(&mut tuner, 15.0);
+    assert_eq!(tuner.size(), 32 * 1024);
+
+    // Settled, later windows do not move it
+    feed_window(&mut tuner, 50.0);
+    assert_eq!(tuner.size(), 32 * 1024);
+}
+
+#[test]
+fn test_chunk_tuner_fixed_size() {
+    let mut tuner = ChunkTuner::new(Some(8 * 1024));
+    feed_window(&mut tuner, 10.0);
+    feed_window(&mut tuner, 100.0);
+    assert_eq!(tuner.size(), 8 * 1024);
+
+    assert_eq!(ChunkTuner::new(Some(1)).size(), MIN_CHUNK_SIZE);
+    assert_eq!(ChunkTuner::new(Some(1 << 20)).size(), SYNC_DATA_MAX);
+}
+
+#[test]
+fn test_preallocate_sets_file_length() {
+    let file = tempfile::tempfile().unwrap();
+    preallocate(&file, 3 * 1024 * 1024 + 17).unwrap();
+    assert_eq!(file.metadata().unwrap().len(), 3 * 1024 * 1024 + 17);
+
+    let empty = tempfile::tempfile().unwrap();
+    preallocate(&empty, 0).unwrap();
+    assert_eq!(empty.metadata().unwrap().len(), 0);
+}
+
+#[test]
+fn test_rate_meter_peak() {
+    let start = Instant::now();
+    let mut meter = RateMeter::new(start);
+    // 1 MiB in the first second, 3 MiB in the next
+    meter.record(1024 * 1024, start + Duration::from_secs(1));
+    meter.record(3 * 1024 * 1024, start + Duration::from_secs(2));
+
+    assert_eq!(meter.peak(start + Duration::from_secs(2)), 3.0 * 1024.0 * 1024.0);
+}
+
+#[test]
+fn test_rate_meter_short_transfer_uses_average() {
+    let start = Instant::now();
+    let mut meter = RateMeter::new(start);
+    meter.record(1000, start + Duration::from_millis(100));
+
+    assert_eq!(meter.peak(start + Duration::from_millis(200)), 5000.0);
+}
+
+#[test]
+fn test_transfer_stats_merge() {
+    let mut stats = TransferStats { files: 1, skipped: 0, bytes: 10, peak_rate: 5.0 };
+    stats.merge(TransferStats { files: 2, skipped: 1, bytes: 20, peak_rate: 3.0 });
+
+    assert_eq!(stats, TransferStats { files: 3, skipped: 1, bytes: 30, peak_rate: 5.0 });
+}
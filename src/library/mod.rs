@@ -7,3 +7,6 @@ mod protocol_test;
 
 #[cfg(test)]
 mod hash_test;
+
+#[cfg(test)]
+mod adb_test;
@@ -230,7 +230,6 @@ impl fmt::Display for AdbLstatResponse {
 #[derive(Clone, Copy, Debug)]
 pub enum ProgressDisplay {
     Show,
-    #[allow(dead_code)]
     Hide,
 }
 
@@ -1,4 +1,5 @@
 mod adb;
+mod android;
 mod cli;
 mod commands;
 mod config;
@@ -8,6 +9,7 @@ mod error;
 mod library;
 mod output;
 mod progress;
+mod state;
 mod types;
 mod utils;
 
@@ -15,7 +17,7 @@ mod utils;
 mod testing;
 
 use clap::Parser;
-use cli::{Cli, Commands};
+use cli::Cli;
 use colored::Colorize;
 use log::debug;
 
@@ -51,56 +53,56 @@ fn parse_args() -> Cli {
     }
 
     debug!("Final args: {:?}", args);
-    Cli::parse_from(args)
+    let mut cli = Cli::parse_from(&args);
+    cli.argv = args;
+    cli
 }
 
 #[tokio::main]
 async fn main() {
     if let Err(e) = run().await {
-        eprintln!("{} {}", "error:".red().bold(), e);
-        std::process::exit(1);
+        let aim_error = e.downcast_ref::<error::AimError>();
+        // The command's own output already explains a failed device command
+        if !matches!(aim_error, Some(error::AimError::ExitStatus(_))) {
+            eprintln!("{} {}", "error:".red().bold(), e);
+        }
+        std::process::exit(aim_error.map_or(1, |e| e.exit_code()));
     }
 }
 
 async fn run() -> Result<(), Box<dyn std::error::Error>> {
     let cli = parse_args();
 
-    env_logger::Builder::new()
-        .filter_level(cli.verbose.log_level_filter())
-        .init();
-
-    debug!("Starting aim with command: {:?}", cli.command());
-
-    // Use CommandRunner for all non-app commands
-    match &cli.command() {
-        Commands::App { command } => {
-            // App commands use DeviceManager for device selection
-            use crate::core::context::CommandContext;
-            use crate::device::DeviceManager;
-
-            let device_manager = DeviceManager::with_address(&cli.host, &cli.port);
-            let device_id_arg = command.device_id();
+    let ci = cli.ci || core::ci::env_enabled();
+    if ci {
+        core::ci::enable();
+    }
+    output::time::configure(cli.time_format, cli.timezone);
+    if cli.pick {
+        core::pick::enable();
+    }
+    let profile_self = cli.profile_self;
+    if profile_self {
+        core::profile::enable();
+    }
 
-            // Get target device using DeviceManager
-            let device = device_manager
-                .get_target_device(device_id_arg.as_deref())
-                .await?;
+    core::logging::init(
+        cli.verbose.log_level_filter(),
+        cli.log_format,
+        cli.log_file.as_deref(),
+        ci,
+    )?;
 
-            let ctx = CommandContext::new().with_device(device);
+    debug!("Starting aim with command: {:?}", cli.command());
+    let started = std::time::Instant::now();
 
-            crate::commands::app::run(&ctx, command.clone()).await?
-        }
-        _ => {
-            // Use CommandRunner for all other commands
-            debug!("Using CommandRunner for command");
-            use crate::commands::runner::CommandRunner;
+    let result = commands::runner::execute(cli).await;
 
-            debug!("Creating CommandRunner...");
-            let runner = CommandRunner::new().await?;
-            debug!("Running command through CommandRunner...");
-            runner.run(cli).await?;
-        }
+    debug!(duration_ms = started.elapsed().as_millis() as u64; "Command finished");
+    // Also after a failure: a slow failure is worth profiling too
+    if profile_self {
+        eprintln!("{}", core::profile::report(started.elapsed()).render().dimmed());
     }
-
+    result?;
     Ok(())
-}
\ No newline at end of file
+}
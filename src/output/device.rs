@@ -1,5 +1,6 @@
 use crate::core::types::{Device, DeviceState};
 use crate::output::{TableFormat, PlainFormat};
+use crate::state::is_network_serial;
 use crate::types::DeviceDetails;
 use comfy_table::{Cell, Color};
 
@@ -7,6 +8,101 @@ use comfy_table::{Cell, Color};
 #[allow(dead_code)]
 pub struct DeviceFormatter;
 
+/// Order of the device list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum DeviceSort {
+    Model,
+    Id,
+    State,
+}
+
+/// How a device is attached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connection {
+    Usb,
+    Wireless,
+}
+
+impl Connection {
+    /// Network serials (`ip:port`) and mDNS serials (`adb-...._adb-tls-connect._tcp`) are wireless
+    pub fn of(device: &Device) -> Self {
+        let serial = device.id.as_str();
+        if is_network_serial(serial) || serial.contains("._adb-tls-connect.") {
+            Connection::Wireless
+        } else {
+            Connection::Usb
+        }
+    }
+}
+
+/// Parse a `--state` value, rejecting states adb never reports
+pub fn parse_device_state(s: &str) -> Result<DeviceState, String> {
+    match DeviceState::from_str(s) {
+        DeviceState::Unknown => Err(format!("unknown state '{}' (use device, offline, unauthorized, recovery or sideload)", s)),
+        state => Ok(state),
+    }
+}
+
+impl DeviceFormatter {
+    /// Keep devices in one of `states` (any if empty) and on `connection` (any if `None`)
+    pub fn filter(devices: Vec<Device>, states: &[DeviceState], connection: Option<Connection>) -> Vec<Device> {
+        devices
+            .into_iter()
+            .filter(|d| states.is_empty() || states.contains(&d.state))
+            .filter(|d| connection.is_none_or(|c| Connection::of(d) == c))
+            .collect()
+    }
+
+    /// Sort devices, using the serial to break ties
+    pub fn sort(devices: &mut [Device], by: DeviceSort) {
+        let state_rank = |state: DeviceState| match state {
+            DeviceState::Device => 0,
+            DeviceState::Unauthorized => 1,
+            DeviceState::Recovery | DeviceState::Sideload => 2,
+            DeviceState::Offline => 3,
+            DeviceState::Unknown => 4,
+        };
+        devices.sort_by(|a, b| {
+            let primary = match by {
+                DeviceSort::Model => match (&a.model, &b.model) {
+                    (Some(x), Some(y)) => x.to_lowercase().cmp(&y.to_lowercase()),
+                    // Devices without a model go last
+                    (x, y) => y.is_some().cmp(&x.is_some()),
+                },
+                DeviceSort::Id => std::cmp::Ordering::Equal,
+                DeviceSort::State => state_rank(a.state).cmp(&state_rank(b.state)),
+            };
+            primary.then_with(|| a.id.as_str().cmp(b.id.as_str()))
+        });
+    }
+
+    /// Why a device in `state` is listed without its details, for the NOTE column
+    pub fn state_note(state: DeviceState) -> Option<&'static str> {
+        let note = match state {
+            DeviceState::Device => return None,
+            DeviceState::Unauthorized => "accept the USB debugging prompt to read its details",
+            DeviceState::Offline => "not responding; reconnect it or run `aim reconnect`",
+            DeviceState::Recovery => "in recovery; details are read once Android boots",
+            DeviceState::Sideload => "waiting for `adb sideload`",
+            DeviceState::Unknown => "state not recognized",
+        };
+        Some(note)
+    }
+
+    /// Explain how to authorize devices that are waiting for it
+    pub fn unauthorized_hint(devices: &[Device]) -> Option<String> {
+        let count = devices.iter().filter(|d| d.state == DeviceState::Unauthorized).count();
+        if count == 0 {
+            return None;
+        }
+        Some(format!(
+            "{} unauthorized device(s): unlock the screen and accept the \"Allow USB debugging\" prompt. \
+             If no prompt appears, use \"Revoke USB debugging authorizations\" in Developer options and reconnect.",
+            count
+        ))
+    }
+}
+
 impl TableFormat for DeviceDetails {
     fn headers() -> Vec<&'static str> {
         vec![
@@ -84,6 +180,7 @@ impl TableFormat for Device {
             DeviceState::Device => Color::Green,
             DeviceState::Offline => Color::Red,
             DeviceState::Unauthorized => Color::Yellow,
+            DeviceState::Recovery | DeviceState::Sideload => Color::Magenta,
             DeviceState::Unknown => Color::DarkGrey,
         };
 
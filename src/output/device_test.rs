@@ -0,0 +1,91 @@
+use super::device::{parse_device_state, Connection, DeviceFormatter, DeviceSort};
+use crate::core::types::{Device, DeviceState};
+use crate::testing::test_device_full;
+
+fn devices() -> Vec<Device> {
+    vec![
+        test_device_full("emulator-5554", "sdk_gphone64", "Google"),
+        test_device_full("192.168.1.100:5555", "Galaxy S21", "Samsung"),
+        Device::new("R58M123ABC").with_state(DeviceState::Unauthorized),
+        test_device_full("adb-ZY22XYZ-abcd._adb-tls-connect._tcp", "moto g", "Motorola")
+            .with_state(DeviceState::Offline),
+    ]
+}
+
+fn ids(devices: &[Device]) -> Vec<&str> {
+    devices.iter().map(|d| d.id.as_str()).collect()
+}
+
+#[test]
+fn test_parse_device_state() {
+    assert_eq!(parse_device_state("offline"), Ok(DeviceState::Offline));
+    assert_eq!(parse_device_state("Unauthorized"), Ok(DeviceState::Unauthorized));
+    assert_eq!(parse_device_state("recovery"), Ok(DeviceState::Recovery));
+    assert!(parse_device_state("bootloader").is_err());
+}
+
+#[test]
+fn test_connection() {
+    let connections: Vec<Connection> = devices().iter().map(Connection::of).collect();
+    assert_eq!(
+        connections,
+        vec![Connection::Usb, Connection::Wireless, Connection::Usb, Connection::Wireless]
+    );
+}
+
+#[test]
+fn test_filter() {
+    assert_eq!(DeviceFormatter::filter(devices(), &[], None).len(), 4);
+    assert_eq!(
+        ids(&DeviceFormatter::filter(devices(), &[DeviceState::Device], None)),
+        vec!["emulator-5554", "192.168.1.100:5555"]
+    );
+    assert_eq!(
+        ids(&DeviceFormatter::filter(devices(), &[], Some(Connection::Wireless))),
+        vec!["192.168.1.100:5555", "adb-ZY22XYZ-abcd._adb-tls-connect._tcp"]
+    );
+    assert_eq!(
+        ids(&DeviceFormatter::filter(
+            devices(),
+            &[DeviceState::Offline, DeviceState::Unauthorized],
+            Some(Connection::Usb)
+        )),
+        vec!["R58M123ABC"]
+    );
+}
+
+#[test]
+fn test_sort() {
+    let mut list = devices();
+    DeviceFormatter::sort(&mut list, DeviceSort::Id);
+    assert_eq!(
+        ids(&list),
+        vec!["192.168.1.100:5555", "R58M123ABC", "adb-ZY22XYZ-abcd._adb-tls-connect._tcp", "emulator-5554"]
+    );
+
+    DeviceFormatter::sort(&mut list, DeviceSort::Model);
+    assert_eq!(
+        ids(&list),
+        vec!["192.168.1.100:5555", "adb-ZY22XYZ-abcd._adb-tls-connect._tcp", "emulator-5554", "R58M123ABC"]
+    );
+
+    DeviceFormatter::sort(&mut list, DeviceSort::State);
+    assert_eq!(
+        ids(&list),
+        vec!["192.168.1.100:5555", "emulator-5554", "R58M123ABC", "adb-ZY22XYZ-abcd._adb-tls-connect._tcp"]
+    );
+}
+
+#[test]
+fn test_unauthorized_hint() {
+    let hint = DeviceFormatter::unauthorized_hint(&devices()).unwrap();
+    assert!(hint.starts_with("1 unauthorized device(s)"));
+    assert_eq!(DeviceFormatter::unauthorized_hint(&devices()[..2]), None);
+}
+
+#[test]
+fn test_state_note() {
+    assert_eq!(DeviceFormatter::state_note(DeviceState::Device), None);
+    assert!(DeviceFormatter::state_note(DeviceState::Unauthorized).unwrap().contains("USB debugging prompt"));
+    assert!(DeviceFormatter::state_note(DeviceState::Recovery).unwrap().contains("recovery"));
+}
@@ -44,25 +44,7 @@ impl FileInfo {
     
     /// Format size in human-readable format
     pub fn format_size(&self) -> String {
-        const UNITS: &[&str] = &["B", "K", "M", "G", "T"];
-        
-        if self.size == 0 {
-            return "0B".to_string();
-        }
-        
-        let mut size = self.size as f64;
-        let mut unit_index = 0;
-        
-        while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-            size /= 1024.0;
-            unit_index += 1;
-        }
-        
-        if unit_index == 0 {
-            format!("{}{}", self.size, UNITS[unit_index])
-        } else {
-            format!("{:.1}{}", size, UNITS[unit_index])
-        }
+        crate::utils::format_bytes(self.size)
     }
 }
 
@@ -1,3 +1,4 @@
+use crate::core::profile::{self, Phase};
 use crate::error::Result;
 use comfy_table::{Table, Cell, Attribute};
 use colored::*;
@@ -7,14 +8,17 @@ use serde::Serialize;
 pub struct OutputFormatter {
     color_enabled: bool,
     quiet: bool,
+    timestamps: bool,
 }
 
 #[allow(dead_code)]
 impl OutputFormatter {
     pub fn new() -> Self {
+        let ci = crate::core::ci::is_enabled();
         Self {
-            color_enabled: true,
+            color_enabled: !ci,
             quiet: false,
+            timestamps: ci,
         }
     }
     
@@ -30,6 +34,7 @@ impl OutputFormatter {
     
     /// Format items as a table
     pub fn table<T: TableFormat>(&self, items: &[T]) -> Result<()> {
+        let _span = profile::span(Phase::Render);
         if self.quiet {
             return Ok(());
         }
@@ -62,8 +67,39 @@ impl OutputFormatter {
         Ok(())
     }
     
+    /// Format rows as a table whose columns are only known at runtime
+    pub fn dynamic_table(&self, headers: &[String], rows: Vec<Vec<Cell>>) -> Result<()> {
+        let _span = profile::span(Phase::Render);
+        if self.quiet {
+            return Ok(());
+        }
+
+        let mut table = Table::new();
+        table.set_header(headers.iter().map(|h| {
+            if self.color_enabled {
+                Cell::new(h).add_attribute(Attribute::Dim)
+            } else {
+                Cell::new(h)
+            }
+        }));
+        table.load_preset(comfy_table::presets::NOTHING);
+
+        for row in rows {
+            if self.color_enabled {
+                table.add_row(row);
+            } else {
+                // Rebuild the cells without their colors
+                table.add_row(row.iter().map(|c| Cell::new(c.content())).collect::<Vec<_>>());
+            }
+        }
+
+        println!("{}", table);
+        Ok(())
+    }
+
     /// Format items as JSON
     pub fn json<T: Serialize>(&self, items: &T) -> Result<()> {
+        let _span = profile::span(Phase::Render);
         if self.quiet {
             return Ok(());
         }
@@ -76,6 +112,7 @@ impl OutputFormatter {
     
     /// Format items as plain text
     pub fn plain<T: PlainFormat>(&self, items: &[T]) -> Result<()> {
+        let _span = profile::span(Phase::Render);
         if self.quiet {
             return Ok(());
         }
@@ -89,7 +126,7 @@ impl OutputFormatter {
     /// Print a message (respecting quiet mode)
     pub fn message(&self, msg: &str) -> Result<()> {
         if !self.quiet {
-            println!("{}", msg);
+            println!("{}{}", self.stamp(), msg);
         }
         Ok(())
     }
@@ -100,7 +137,7 @@ impl OutputFormatter {
             if self.color_enabled {
                 println!("{}", msg.bright_blue());
             } else {
-                println!("INFO: {}", msg);
+                println!("{}INFO: {}", self.stamp(), msg);
             }
         }
         Ok(())
@@ -112,7 +149,7 @@ impl OutputFormatter {
             if self.color_enabled {
                 println!("{}", msg.bright_green());
             } else {
-                println!("SUCCESS: {}", msg);
+                println!("{}SUCCESS: {}", self.stamp(), msg);
             }
         }
         Ok(())
@@ -124,7 +161,7 @@ impl OutputFormatter {
             if self.color_enabled {
                 eprintln!("{}", msg.bright_yellow());
             } else {
-                eprintln!("WARNING: {}", msg);
+                eprintln!("{}WARNING: {}", self.stamp(), msg);
             }
         }
         Ok(())
@@ -135,10 +172,19 @@ impl OutputFormatter {
         if self.color_enabled {
             eprintln!("{}", msg.bright_red());
         } else {
-            eprintln!("ERROR: {}", msg);
+            eprintln!("{}ERROR: {}", self.stamp(), msg);
         }
         Ok(())
     }
+    
+    /// Timestamp prefix for status lines (CI mode only)
+    fn stamp(&self) -> String {
+        if self.timestamps {
+            format!("[{}] ", crate::core::ci::timestamp())
+        } else {
+            String::new()
+        }
+    }
 }
 
 impl Default for OutputFormatter {
@@ -174,5 +220,16 @@ impl<T: ToString> PlainFormat for T {
 pub mod device;
 pub mod property;
 pub mod file;
+pub mod transfer;
+pub mod time;
+
+#[cfg(test)]
+mod transfer_test;
+
+#[cfg(test)]
+mod device_test;
+
+#[cfg(test)]
+mod time_test;
 
 // Re-exports
@@ -0,0 +1,163 @@
+//! Shared formatting of times shown to the user
+//!
+//! `--time-format` picks ISO 8601, relative ("3h05m ago") or Unix epoch
+//! seconds, and `--timezone` whether ISO times are shown in the host's zone,
+//! the device's zone or UTC. Like CI mode, the choice is set once per process
+//! so every formatter can use it without threading it through.
+
+use crate::adb::shell::ShellCommand;
+use crate::core::types::DeviceId;
+use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone, Utc};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum TimeFormat {
+    /// 2024-05-01T14:03:00+02:00
+    #[default]
+    Iso,
+    /// 3h05m ago
+    Relative,
+    /// Seconds since 1970-01-01 UTC
+    Epoch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum Zone {
+    /// This machine's time zone
+    #[default]
+    Host,
+    /// The device's time zone, where known
+    Device,
+    Utc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimeSettings {
+    pub format: TimeFormat,
+    pub zone: Zone,
+    /// Whether `--time-format` was given, so raw device timestamps are converted
+    pub explicit: bool,
+}
+
+static SETTINGS: OnceLock<TimeSettings> = OnceLock::new();
+
+/// Set the time format for the rest of the process
+pub fn configure(format: Option<TimeFormat>, zone: Zone) {
+    let _ = SETTINGS.set(TimeSettings {
+        format: format.unwrap_or_default(),
+        zone,
+        explicit: format.is_some(),
+    });
+}
+
+pub fn settings() -> TimeSettings {
+    SETTINGS.get().copied().unwrap_or_default()
+}
+
+/// Format a time with the configured settings
+///
+/// `device_offset` is the device's UTC offset, used with `--timezone device`.
+pub fn format_time(at: DateTime<Utc>, device_offset: Option<FixedOffset>) -> String {
+    format_time_with(at, Utc::now(), settings(), device_offset)
+}
+
+/// Format a time the way `logcat` does, e.g. `05-01 17:33:00.250`
+///
+/// Used where device logs are lined up against each other, so it keeps
+/// milliseconds and follows `--timezone` but not `--time-format`.
+pub fn format_log_time(at: DateTime<Utc>, zone: Zone, device_offset: Option<FixedOffset>) -> String {
+    const LOGCAT: &str = "%m-%d %H:%M:%S%.3f";
+    match (zone, device_offset) {
+        (Zone::Utc, _) => at.format(LOGCAT).to_string(),
+        (Zone::Device, Some(offset)) => at.with_timezone(&offset).format(LOGCAT).to_string(),
+        (Zone::Device, None) | (Zone::Host, _) => at.with_timezone(&Local).format(LOGCAT).to_string(),
+    }
+}
+
+pub fn format_time_with(
+    at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    settings: TimeSettings,
+    device_offset: Option<FixedOffset>,
+) -> String {
+    const ISO: &str = "%Y-%m-%dT%H:%M:%S%:z";
+    match settings.format {
+        TimeFormat::Epoch => at.timestamp().to_string(),
+        TimeFormat::Relative => format_relative(at, now),
+        TimeFormat::Iso => match (settings.zone, device_offset) {
+            (Zone::Utc, _) => at.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            (Zone::Device, Some(offset)) => at.with_timezone(&offset).format(ISO).to_string(),
+            // Without a known device offset, fall back to the host zone
+            (Zone::Device, None) | (Zone::Host, _) => at.with_timezone(&Local).format(ISO).to_string(),
+        },
+    }
+}
+
+/// "3h05m ago", "2d04h ago", or "in 5m00s" for times in the future
+pub fn format_relative(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let (age, future) = match (now - at).to_std() {
+        Ok(age) => (age, false),
+        Err(_) => ((at - now).to_std().unwrap_or_default(), true),
+    };
+    let secs = age.as_secs();
+    let text = if secs >= 86_400 {
+        format!("{}d{:02}h", secs / 86_400, (secs % 86_400) / 3600)
+    } else {
+        crate::utils::format_duration(age)
+    };
+    if future {
+        format!("in {}", text)
+    } else {
+        format!("{} ago", text)
+    }
+}
+
+/// Parse a `date +%z` style offset ("+0530", "-0800")
+pub fn parse_utc_offset(s: &str) -> Option<FixedOffset> {
+    let s = s.trim();
+    if s.len() != 5 {
+        return None;
+    }
+    let sign = match &s[..1] {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+    let hours: i32 = s[1..3].parse().ok()?;
+    let minutes: i32 = s[3..5].parse().ok()?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Parse a device-local "YYYY-MM-DD HH:MM:SS" time, as dumpsys prints them
+///
+/// Without the device's offset the time is taken to be in the host's zone.
+pub fn parse_device_local(s: &str, device_offset: Option<FixedOffset>) -> Option<DateTime<Utc>> {
+    let naive = NaiveDateTime::parse_from_str(s.trim(), "%Y-%m-%d %H:%M:%S").ok()?;
+    let local = match device_offset {
+        Some(offset) => offset.from_local_datetime(&naive).single()?.with_timezone(&Utc),
+        None => Local.from_local_datetime(&naive).earliest()?.with_timezone(&Utc),
+    };
+    Some(local)
+}
+
+/// Boot time from the "btime" line of `/proc/stat`
+pub fn parse_boot_time(proc_stat: &str) -> Option<DateTime<Utc>> {
+    let secs = proc_stat
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))?
+        .trim()
+        .parse()
+        .ok()?;
+    DateTime::from_timestamp(secs, 0)
+}
+
+/// The device's current UTC offset
+pub async fn device_utc_offset(device_id: &DeviceId) -> Option<FixedOffset> {
+    let (host, port) = crate::commands::runner::get_adb_connection_params();
+    let output = ShellCommand::new("date +%z")
+        .with_device(device_id.clone())
+        .execute(host, port)
+        .await
+        .ok()?;
+    parse_utc_offset(&output.stdout)
+}
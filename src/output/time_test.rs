@@ -0,0 +1,76 @@
+use super::time::{
+    format_log_time, format_relative, format_time_with, parse_boot_time, parse_device_local, parse_utc_offset, TimeFormat,
+    TimeSettings, Zone,
+};
+use crate::commands::dmesg::convert_uptime_prefix;
+use chrono::{FixedOffset, TimeZone, Utc};
+
+fn settings(format: TimeFormat, zone: Zone) -> TimeSettings {
+    TimeSettings {
+        format,
+        zone,
+        explicit: true,
+    }
+}
+
+#[test]
+fn test_format_time_with() {
+    let at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 3, 0).unwrap();
+    let now = Utc.with_ymd_and_hms(2024, 5, 1, 15, 8, 0).unwrap();
+    let ist = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+
+    assert_eq!(format_time_with(at, now, settings(TimeFormat::Epoch, Zone::Host), None), "1714564980");
+    assert_eq!(format_time_with(at, now, settings(TimeFormat::Relative, Zone::Host), None), "3h05m ago");
+    assert_eq!(
+        format_time_with(at, now, settings(TimeFormat::Iso, Zone::Utc), Some(ist)),
+        "2024-05-01T12:03:00Z"
+    );
+    assert_eq!(
+        format_time_with(at, now, settings(TimeFormat::Iso, Zone::Device), Some(ist)),
+        "2024-05-01T17:33:00+05:30"
+    );
+}
+
+#[test]
+fn test_format_relative() {
+    let now = Utc.with_ymd_and_hms(2024, 5, 10, 12, 0, 0).unwrap();
+    assert_eq!(format_relative(now, now), "0s ago");
+    assert_eq!(format_relative(Utc.with_ymd_and_hms(2024, 5, 8, 8, 0, 0).unwrap(), now), "2d04h ago");
+    assert_eq!(format_relative(Utc.with_ymd_and_hms(2024, 5, 10, 12, 5, 0).unwrap(), now), "in 5m00s");
+}
+
+#[test]
+fn test_parse_utc_offset() {
+    assert_eq!(parse_utc_offset("+0530\n"), FixedOffset::east_opt(19800));
+    assert_eq!(parse_utc_offset("-0800"), FixedOffset::west_opt(28800));
+    assert_eq!(parse_utc_offset("PST"), None);
+}
+
+#[test]
+fn test_parse_device_local() {
+    let offset = FixedOffset::west_opt(7 * 3600);
+    assert_eq!(
+        parse_device_local("2024-01-10 12:34:56", offset),
+        Some(Utc.with_ymd_and_hms(2024, 1, 10, 19, 34, 56).unwrap())
+    );
+    assert_eq!(parse_device_local("2024-01-10", offset), None);
+}
+
+#[test]
+fn test_dmesg_uptime_conversion() {
+    let boot = parse_boot_time("cpu  1 2 3\nbtime 1714564800\nprocesses 42\n").unwrap();
+    let epoch = |at: chrono::DateTime<Utc>| at.timestamp().to_string();
+    assert_eq!(
+        convert_uptime_prefix("[  180.250000] init: started", boot, epoch),
+        "[1714564980] init: started"
+    );
+    assert_eq!(convert_uptime_prefix("no prefix", boot, epoch), "no prefix");
+}
+
+#[test]
+fn test_format_log_time() {
+    let at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 3, 0).unwrap() + chrono::Duration::milliseconds(250);
+    let ist = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+    assert_eq!(format_log_time(at, Zone::Utc, Some(ist)), "05-01 12:03:00.250");
+    assert_eq!(format_log_time(at, Zone::Device, Some(ist)), "05-01 17:33:00.250");
+}
@@ -0,0 +1,72 @@
+use crate::library::adb::TransferStats;
+use crate::utils::format_bytes;
+use colored::*;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Totals printed after a push or pull
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TransferSummary {
+    pub files_transferred: usize,
+    pub files_skipped: usize,
+    pub files_failed: usize,
+    pub bytes: u64,
+    pub elapsed_secs: f64,
+    pub avg_bytes_per_sec: f64,
+    pub peak_bytes_per_sec: f64,
+    pub retries: u32,
+}
+
+impl TransferSummary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a source that transferred successfully
+    pub fn add(&mut self, stats: &TransferStats) {
+        self.files_transferred += stats.files;
+        self.files_skipped += stats.skipped;
+        self.bytes += stats.bytes;
+        self.peak_bytes_per_sec = self.peak_bytes_per_sec.max(stats.peak_rate);
+    }
+
+    /// Count a source that failed even after retrying
+    pub fn fail(&mut self) {
+        self.files_failed += 1;
+    }
+
+    /// Record the total wall time, which the average rate is based on
+    pub fn finish(&mut self, elapsed: Duration) {
+        self.elapsed_secs = elapsed.as_secs_f64();
+        self.avg_bytes_per_sec = if self.elapsed_secs > 0.0 {
+            self.bytes as f64 / self.elapsed_secs
+        } else {
+            0.0
+        };
+    }
+
+    pub fn failed(&self) -> bool {
+        self.files_failed > 0
+    }
+}
+
+impl std::fmt::Display for TransferSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let failed = format!("{} failed", self.files_failed);
+        write!(
+            f,
+            "{} file(s) transferred, {} skipped, {}: {} in {:.2}s (avg {}/s, peak {}/s)",
+            self.files_transferred,
+            self.files_skipped,
+            if self.failed() { failed.red() } else { failed.normal() },
+            format_bytes(self.bytes),
+            self.elapsed_secs,
+            format_bytes(self.avg_bytes_per_sec as u64),
+            format_bytes(self.peak_bytes_per_sec as u64),
+        )?;
+        if self.retries > 0 {
+            write!(f, ", {} retr{}", self.retries, if self.retries == 1 { "y" } else { "ies" })?;
+        }
+        Ok(())
+    }
+}
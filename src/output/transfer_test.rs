@@ -0,0 +1,58 @@
+use super::transfer::TransferSummary;
+use crate::library::adb::TransferStats;
+use std::time::Duration;
+
+#[test]
+fn test_summary_totals() {
+    let mut summary = TransferSummary::new();
+    summary.add(&TransferStats { files: 3, skipped: 1, bytes: 3 * 1024 * 1024, peak_rate: 4.0e6 });
+    summary.add(&TransferStats { files: 1, skipped: 0, bytes: 1024 * 1024, peak_rate: 9.0e6 });
+    summary.fail();
+    summary.retries = 2;
+    summary.finish(Duration::from_secs(2));
+
+    assert_eq!(summary.files_transferred, 4);
+    assert_eq!(summary.files_skipped, 1);
+    assert_eq!(summary.files_failed, 1);
+    assert_eq!(summary.bytes, 4 * 1024 * 1024);
+    assert_eq!(summary.avg_bytes_per_sec, 2.0 * 1024.0 * 1024.0);
+    assert_eq!(summary.peak_bytes_per_sec, 9.0e6);
+    assert!(summary.failed());
+}
+
+#[test]
+fn test_summary_display() {
+    colored::control::set_override(false);
+    let mut summary = TransferSummary::new();
+    summary.add(&TransferStats { files: 2, skipped: 0, bytes: 2 * 1024 * 1024, peak_rate: 2.0 * 1024.0 * 1024.0 });
+    summary.finish(Duration::from_secs(1));
+
+    assert_eq!(
+        summary.to_string(),
+        "2 file(s) transferred, 0 skipped, 0 failed: 2.0M in 1.00s (avg 2.0M/s, peak 2.0M/s)"
+    );
+
+    summary.retries = 1;
+    assert!(summary.to_string().ends_with(", 1 retry"));
+}
+
+#[test]
+fn test_summary_json_fields() {
+    let mut summary = TransferSummary::new();
+    summary.finish(Duration::ZERO);
+    let json = serde_json::to_value(&summary).unwrap();
+
+    for key in [
+        "files_transferred",
+        "files_skipped",
+        "files_failed",
+        "bytes",
+        "elapsed_secs",
+        "avg_bytes_per_sec",
+        "peak_bytes_per_sec",
+        "retries",
+    ] {
+        assert!(json.get(key).is_some(), "missing {}", key);
+    }
+    assert_eq!(json["avg_bytes_per_sec"], 0.0);
+}
@@ -1,6 +1,9 @@
 use indicatif::{ProgressBar, ProgressStyle, MultiProgress};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How often line-based progress prints an update
+const LINE_UPDATE_INTERVAL: Duration = Duration::from_secs(5);
 
 /// Trait for progress reporting
 #[allow(dead_code)]
@@ -10,6 +13,12 @@ pub trait ProgressReporter: Send + Sync {
     fn finish(&self);
     fn set_message(&self, msg: &str);
     fn inc(&self, delta: u64);
+
+    /// Finish and replace the message with a final summary
+    fn finish_with_message(&self, msg: &str) {
+        self.set_message(msg);
+        self.finish();
+    }
 }
 
 /// Indicatif-based progress reporter
@@ -79,6 +88,106 @@ impl ProgressReporter for IndicatifProgress {
     fn inc(&self, delta: u64) {
         self.bar.inc(delta);
     }
+    
+    fn finish_with_message(&self, msg: &str) {
+        self.bar.finish_with_message(msg.to_string());
+    }
+}
+
+/// Line-based progress for CI logs: a timestamped line every few seconds
+/// instead of a redrawn bar
+pub struct LineProgress {
+    label: String,
+    state: Mutex<LineState>,
+}
+
+struct LineState {
+    total: u64,
+    current: u64,
+    message: String,
+    last_print: Option<Instant>,
+}
+
+impl LineProgress {
+    pub fn new(label: impl Into<String>, total: u64) -> Self {
+        Self {
+            label: label.into(),
+            state: Mutex::new(LineState {
+                total,
+                current: 0,
+                message: String::new(),
+                last_print: None,
+            }),
+        }
+    }
+    
+    fn print(&self, state: &LineState, suffix: &str) {
+        let progress = if state.total > 0 {
+            format!(
+                "{}/{} ({:.0}%)",
+                state.current,
+                state.total,
+                state.current as f64 * 100.0 / state.total as f64
+            )
+        } else {
+            state.current.to_string()
+        };
+        let label = if self.label.is_empty() { "progress" } else { &self.label };
+        eprintln!(
+            "[{}] {}: {} {}",
+            crate::core::ci::timestamp(),
+            label,
+            progress,
+            suffix
+        );
+    }
+    
+    fn maybe_print(&self, state: &mut LineState) {
+        let due = state
+            .last_print
+            .is_none_or(|t| t.elapsed() >= LINE_UPDATE_INTERVAL);
+        if due {
+            self.print(state, &state.message);
+            state.last_print = Some(Instant::now());
+        }
+    }
+}
+
+impl ProgressReporter for LineProgress {
+    fn start(&self, total: u64) {
+        self.state.lock().unwrap().total = total;
+    }
+    
+    fn update(&self, current: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.current = current;
+        self.maybe_print(&mut state);
+    }
+    
+    fn finish(&self) {
+        let state = self.state.lock().unwrap();
+        let message = if state.message.is_empty() { "complete" } else { &state.message };
+        self.print(&state, message);
+    }
+    
+    fn set_message(&self, msg: &str) {
+        self.state.lock().unwrap().message = msg.to_string();
+    }
+    
+    fn inc(&self, delta: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.current += delta;
+        self.maybe_print(&mut state);
+    }
+}
+
+/// Progress reporter for a byte transfer: a bar on a terminal, lines in CI mode
+pub fn transfer_reporter(label: &str, total_bytes: u64, template: &str) -> Box<dyn ProgressReporter> {
+    if crate::core::ci::is_enabled() {
+        Box::new(LineProgress::new(label, total_bytes))
+    } else {
+        Box::new(IndicatifProgress::with_template(total_bytes, template))
+    }
 }
 
 /// No-op progress reporter for when progress reporting is disabled
@@ -122,6 +231,9 @@ impl ProgressFactory {
         if !self.enabled {
             return Box::new(NoOpProgress);
         }
+        if crate::core::ci::is_enabled() {
+            return Box::new(LineProgress::new(file_name, total_bytes));
+        }
         
         let template = format!(
             "{{spinner:.green}} {} [{{bar:40.cyan/blue}}] {{bytes}}/{{total_bytes}} ({{bytes_per_sec}}, {{eta}})",
@@ -142,6 +254,9 @@ impl ProgressFactory {
         if !self.enabled {
             return Box::new(NoOpProgress);
         }
+        if crate::core::ci::is_enabled() {
+            return Box::new(LineProgress::new(format!("Running: {}", command), 0));
+        }
         
         let progress = IndicatifProgress::spinner();
         progress.set_message(&format!("Running: {}", command));
@@ -158,6 +273,9 @@ impl ProgressFactory {
         if !self.enabled {
             return Box::new(NoOpProgress);
         }
+        if crate::core::ci::is_enabled() {
+            return Box::new(LineProgress::new("", total));
+        }
         
         let progress = IndicatifProgress::new(total);
         
@@ -173,6 +291,9 @@ impl ProgressFactory {
         if !self.enabled {
             return Box::new(NoOpProgress);
         }
+        if crate::core::ci::is_enabled() {
+            return Box::new(LineProgress::new("", total));
+        }
         
         let progress = IndicatifProgress::with_template(total, template);
         
@@ -0,0 +1,250 @@
+//! Local state store
+//!
+//! Remembers every device aim has seen in `~/.local/state/aim/state.json`:
+//! serial, aliases, when it was last connected, cached properties and the last
+//! wireless address it was reachable at. Unlike the config this is written by
+//! aim itself, and losing it only loses history.
+
+use crate::config::Config;
+use crate::core::types::Device;
+use crate::error::{AimError, Result};
+use chrono::{DateTime, Utc};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Everything remembered about one device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnownDevice {
+    pub serial: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product: Option<String>,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    /// Last `ip:port` the device was connected through over the network
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wireless_ip: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub props: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub props_updated: Option<DateTime<Utc>>,
+}
+
+impl KnownDevice {
+    fn new(serial: &str, now: DateTime<Utc>) -> Self {
+        Self {
+            serial: serial.to_string(),
+            aliases: Vec::new(),
+            model: None,
+            product: None,
+            first_seen: now,
+            last_seen: now,
+            wireless_ip: None,
+            props: BTreeMap::new(),
+            props_updated: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct StateStore {
+    #[serde(default)]
+    pub devices: BTreeMap<String, KnownDevice>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// Whether an adb serial is a network address (`192.168.1.20:5555`)
+pub fn is_network_serial(serial: &str) -> bool {
+    match serial.rsplit_once(':') {
+        Some((host, port)) => {
+            !host.is_empty() && port.parse::<u16>().is_ok() && host.parse::<std::net::IpAddr>().is_ok()
+        }
+        None => false,
+    }
+}
+
+impl StateStore {
+    /// `$XDG_STATE_HOME/aim/state.json`, falling back to `~/.local/state/aim/state.json`
+    pub fn default_path() -> PathBuf {
+        dirs::state_dir()
+            .or_else(|| dirs::home_dir().map(|p| p.join(".local/state")))
+            .unwrap_or_else(|| PathBuf::from(".local/state"))
+            .join("aim/state.json")
+    }
+
+    pub fn load() -> Self {
+        Self::load_from_path(&Self::default_path())
+    }
+
+    /// Load the store, starting empty if the file is missing or unreadable
+    pub fn load_from_path(path: &Path) -> Self {
+        let mut store = match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                debug!("Ignoring unreadable state file {:?}: {}", path, e);
+                StateStore::default()
+            }),
+            Err(_) => StateStore::default(),
+        };
+        store.path = path.to_path_buf();
+        store
+    }
+
+    /// Write the store back, replacing the file atomically
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub fn get(&self, serial: &str) -> Option<&KnownDevice> {
+        self.devices.get(serial)
+    }
+
+    /// Find a known device by partial serial or alias, like device arguments
+    pub fn find(&self, query: &str) -> Vec<&KnownDevice> {
+        let query = query.to_lowercase();
+        if let Some(exact) = self.devices.values().find(|d| {
+            d.serial.eq_ignore_ascii_case(&query) || d.aliases.iter().any(|a| a.eq_ignore_ascii_case(&query))
+        }) {
+            return vec![exact];
+        }
+        self.devices
+            .values()
+            .filter(|d| d.serial.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    /// Pick the cached device a device argument refers to
+    ///
+    /// Without an argument the cache must hold exactly one device.
+    pub fn resolve(&self, query: Option<&str>) -> Result<&KnownDevice> {
+        let matches = match query {
+            Some(query) => self.find(query),
+            None => self.devices.values().collect(),
+        };
+        match matches.as_slice() {
+            [] => Err(AimError::DeviceNotFound(format!(
+                "{} (not in the local cache)",
+                query.unwrap_or("any device")
+            ))),
+            [device] => Ok(device),
+            _ => match query {
+                Some(prefix) => Err(AimError::AmbiguousDeviceMatch {
+                    prefix: prefix.to_string(),
+                    matches: matches.iter().map(|d| d.serial.clone()).collect(),
+                }),
+                None => Err(AimError::DeviceIdRequired),
+            },
+        }
+    }
+
+    fn entry(&mut self, serial: &str, now: DateTime<Utc>) -> &mut KnownDevice {
+        self.devices
+            .entry(serial.to_string())
+            .or_insert_with(|| KnownDevice::new(serial, now))
+    }
+
+    /// Record devices that are currently connected
+    pub fn record_devices(&mut self, devices: &[Device], aliases: &HashMap<String, String>, now: DateTime<Utc>) {
+        for device in devices {
+            let serial = device.id.to_string();
+            let known = self.entry(&serial, now);
+            known.last_seen = now;
+            if device.model.as_deref().is_some_and(|m| !m.is_empty()) {
+                known.model = device.model.clone();
+            }
+            if device.product.as_deref().is_some_and(|p| !p.is_empty()) {
+                known.product = device.product.clone();
+            }
+            if let Some(alias) = aliases.get(&serial) {
+                if !known.aliases.contains(alias) {
+                    known.aliases.push(alias.clone());
+                }
+            }
+            if is_network_serial(&serial) {
+                known.wireless_ip = Some(serial.clone());
+            }
+        }
+    }
+
+    /// Cache properties read from a device
+    ///
+    /// For devices connected over the network, the address is also remembered
+    /// on the entry for its hardware serial so it can be reconnected later.
+    pub fn record_props(&mut self, serial: &str, props: &HashMap<String, String>, now: DateTime<Utc>) {
+        let known = self.entry(serial, now);
+        known.last_seen = now;
+        for (key, value) in props {
+            known.props.insert(key.clone(), value.trim().to_string());
+        }
+        known.props_updated = Some(now);
+
+        if is_network_serial(serial) {
+            if let Some(hw_serial) = props.get("ro.serialno").map(|s| s.trim()).filter(|s| !s.is_empty()) {
+                self.entry(hw_serial, now).wireless_ip = Some(serial.to_string());
+            }
+        }
+    }
+}
+
+/// Describe when cached data was recorded, e.g. "2024-05-01 14:03 (3h05m ago)"
+pub fn cached_at(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    use crate::output::time::{format_relative, format_time_with, settings, TimeFormat};
+    let settings = settings();
+    let age = format_relative(at, now);
+    if settings.format == TimeFormat::Relative {
+        return age;
+    }
+    format!("{} ({})", format_time_with(at, now, settings, None), age)
+}
+
+/// Aliases from the config for the given devices
+fn config_aliases(devices: &[Device]) -> HashMap<String, String> {
+    let config_path = dirs::home_dir()
+        .map(|p| p.join(".config/aim/config.toml"))
+        .unwrap_or_else(|| PathBuf::from(".config/aim/config.toml"));
+    let config = Config::load_from_path(&config_path);
+
+    devices
+        .iter()
+        .filter_map(|d| {
+            let serial = d.id.to_string();
+            config.get_device_name(&serial).map(|name| (serial, name))
+        })
+        .collect()
+}
+
+/// Remember the given connected devices. Failures are logged and otherwise ignored
+pub fn remember_devices(devices: &[Device]) {
+    if devices.is_empty() {
+        return;
+    }
+    let mut store = StateStore::load();
+    store.record_devices(devices, &config_aliases(devices), Utc::now());
+    if let Err(e) = store.save() {
+        debug!("Failed to save device state: {}", e);
+    }
+}
+
+/// Remember properties read from a device. Failures are logged and otherwise ignored
+pub fn remember_props(serial: &str, props: &HashMap<String, String>) {
+    if props.is_empty() {
+        return;
+    }
+    let mut store = StateStore::load();
+    store.record_props(serial, props, Utc::now());
+    if let Err(e) = store.save() {
+        debug!("Failed to save device state: {}", e);
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use crate::core::types::{Device, DeviceState};
+    use crate::state::{cached_at, is_network_serial, StateStore};
+    use chrono::{TimeZone, Utc};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn device(serial: &str, model: &str) -> Device {
+        Device::new(serial)
+            .with_state(DeviceState::Device)
+            .with_model(model.to_string())
+    }
+
+    #[test]
+    fn test_is_network_serial() {
+        assert!(is_network_serial("192.168.1.20:5555"));
+        assert!(!is_network_serial("emulator-5554"));
+        assert!(!is_network_serial("R58M123ABC"));
+        assert!(!is_network_serial("adb-R58M123ABC-xyz._adb-tls-connect._tcp"));
+    }
+
+    #[test]
+    fn test_record_devices_keeps_first_seen() {
+        let mut store = StateStore::default();
+        let t1 = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let t2 = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+
+        store.record_devices(&[device("R58M123ABC", "Pixel 7")], &HashMap::new(), t1);
+        store.record_devices(&[device("R58M123ABC", "")], &HashMap::new(), t2);
+
+        let known = store.get("R58M123ABC").unwrap();
+        assert_eq!(known.first_seen, t1);
+        assert_eq!(known.last_seen, t2);
+        // An empty model from a later listing does not erase the known one
+        assert_eq!(known.model.as_deref(), Some("Pixel 7"));
+    }
+
+    #[test]
+    fn test_record_devices_collects_aliases_once() {
+        let mut store = StateStore::default();
+        let aliases = HashMap::from([("R58M123ABC".to_string(), "pixel".to_string())]);
+        store.record_devices(&[device("R58M123ABC", "Pixel 7")], &aliases, Utc::now());
+        store.record_devices(&[device("R58M123ABC", "Pixel 7")], &aliases, Utc::now());
+
+        assert_eq!(store.get("R58M123ABC").unwrap().aliases, vec!["pixel".to_string()]);
+    }
+
+    #[test]
+    fn test_record_props_links_wireless_address() {
+        let mut store = StateStore::default();
+        let props = HashMap::from([
+            ("ro.serialno".to_string(), "R58M123ABC\n".to_string()),
+            ("ro.product.model".to_string(), "Pixel 7".to_string()),
+        ]);
+        store.record_props("192.168.1.20:5555", &props, Utc::now());
+
+        let wireless = store.get("192.168.1.20:5555").unwrap();
+        assert_eq!(wireless.props.get("ro.serialno").map(String::as_str), Some("R58M123ABC"));
+        assert!(wireless.props_updated.is_some());
+        assert_eq!(
+            store.get("R58M123ABC").unwrap().wireless_ip.as_deref(),
+            Some("192.168.1.20:5555")
+        );
+    }
+
+    #[test]
+    fn test_find_by_alias_and_partial_serial() {
+        let mut store = StateStore::default();
+        let aliases = HashMap::from([("R58M123ABC".to_string(), "pixel".to_string())]);
+        store.record_devices(
+            &[device("R58M123ABC", "Pixel 7"), device("emulator-5554", "sdk")],
+            &aliases,
+            Utc::now(),
+        );
+
+        assert_eq!(store.find("PIXEL")[0].serial, "R58M123ABC");
+        assert_eq!(store.find("5554")[0].serial, "emulator-5554");
+        assert!(store.find("nothing").is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("aim/state.json");
+
+        let mut store = StateStore::load_from_path(&path);
+        assert!(store.devices.is_empty());
+        store.record_devices(&[device("R58M123ABC", "Pixel 7")], &HashMap::new(), Utc::now());
+        store.save().unwrap();
+
+        let loaded = StateStore::load_from_path(&path);
+        assert_eq!(loaded.get("R58M123ABC"), store.get("R58M123ABC"));
+    }
+
+    #[test]
+    fn test_load_ignores_corrupt_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("state.json");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        assert!(StateStore::load_from_path(&path).devices.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_cached_device() {
+        let mut store = StateStore::default();
+        assert!(store.resolve(None).is_err());
+
+        store.record_devices(&[device("R58M123ABC", "Pixel 7")], &HashMap::new(), Utc::now());
+        assert_eq!(store.resolve(None).unwrap().serial, "R58M123ABC");
+        assert_eq!(store.resolve(Some("r58m")).unwrap().serial, "R58M123ABC");
+        assert!(store.resolve(Some("emulator")).is_err());
+
+        store.record_devices(&[device("emulator-5554", "sdk")], &HashMap::new(), Utc::now());
+        assert!(store.resolve(None).is_err());
+        assert_eq!(store.resolve(Some("5554")).unwrap().serial, "emulator-5554");
+    }
+
+    #[test]
+    fn test_cached_at_includes_age() {
+        let at = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 13, 5, 0).unwrap();
+        assert!(cached_at(at, now).ends_with("(3h05m ago)"));
+    }
+}
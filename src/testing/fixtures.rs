@@ -97,6 +97,16 @@ pub mod args {
     pub fn ls_args(output: OutputFormat) -> crate::commands::ls::LsArgs {
         crate::commands::ls::LsArgs {
             output: output.to_string(),
+            known: false,
+            cached: false,
+            long: false,
+            columns: Vec::new(),
+            state: Vec::new(),
+            usb: false,
+            wireless: false,
+            sort: None,
+            watch: false,
+            interval: std::time::Duration::from_secs(2),
         }
     }
 }